@@ -5,6 +5,10 @@
 //! and command implementation.
 
 use crate::error::CliResult;
+use abop_core::feed::EnclosureLayout;
+use abop_core::provider::OPEN_LIBRARY_URL;
+use abop_core::sidecar::{MetadataField, SidecarFormat};
+use abop_core::sync::{DEFAULT_CONFLICT_TOLERANCE_SECONDS, SyncPolicy};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -24,6 +28,10 @@ pub struct Args {
     #[arg(short, long, global = true)]
     pub json: bool,
 
+    /// Listener profile name or ID (defaults to the default profile)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     /// Commands to execute
     #[command(subcommand)]
     pub command: Commands,
@@ -62,6 +70,223 @@ pub enum Commands {
         #[command(subcommand)]
         operation: DbOperations,
     },
+    /// Fully decode audio files and report corruption
+    Verify {
+        /// Path to the database file
+        #[arg(short = 'f', long)]
+        database: PathBuf,
+
+        /// Only verify audiobooks in the library at this path
+        #[arg(short, long)]
+        library: Option<PathBuf>,
+
+        /// Only verify audiobooks in the collection with this name
+        #[arg(short, long, conflicts_with = "library")]
+        collection: Option<String>,
+
+        /// Omit healthy files from the report
+        #[arg(long)]
+        problems_only: bool,
+    },
+    /// Check audio files against ACX audiobook distribution requirements
+    CheckAcx {
+        /// Audio files, or directories whose audio files form one book
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Move a scanned library's files into a template-driven folder layout
+    Organize {
+        /// Path to the audiobook library directory
+        #[arg(short, long)]
+        library: PathBuf,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// Target path template using {author}, {narrator}, {title}, {series}, {series_index} and {part}
+        #[arg(short, long, default_value = abop_core::library::organizer::DEFAULT_ORGANIZE_TEMPLATE)]
+        template: String,
+
+        /// Only show the planned moves and collisions
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Split one long audio file into per-chapter files
+    Split {
+        /// Audio file to split
+        input: PathBuf,
+
+        /// Cue sheet with the chapter boundaries
+        #[arg(long, conflicts_with = "silence")]
+        cue: Option<PathBuf>,
+
+        /// Find chapter boundaries from gaps of silence
+        #[arg(long)]
+        silence: bool,
+
+        /// Directory for the parts (defaults to a folder named after the input)
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+
+        /// Filename template using {track}, {track:02}, {chapter} and {title}
+        #[arg(short, long, default_value = abop_core::audio::processing::splitter::DEFAULT_SPLIT_TEMPLATE)]
+        template: String,
+
+        /// Replace existing part files
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Process audio files as a job that resumes after an interruption
+    Process {
+        /// Audio files to process
+        #[arg(required_unless_present = "resume")]
+        inputs: Vec<PathBuf>,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// Mix the audio down to mono
+        #[arg(long)]
+        mono: bool,
+
+        /// Directory for the processed files (defaults to next to each input)
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+
+        /// Output filename pattern, where {filename} is the input name without extension
+        #[arg(long, default_value = "{filename}_processed")]
+        naming_pattern: String,
+
+        /// Resume interrupted processing jobs instead of starting a new one
+        #[arg(long, conflicts_with_all = ["inputs", "mono", "output_dir", "naming_pattern"])]
+        resume: bool,
+    },
+    /// Watch libraries and update the database as files change
+    Watch {
+        /// Library directories to watch
+        #[arg(short, long, required = true)]
+        library: Vec<PathBuf>,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// Poll for changes instead of using native filesystem notifications
+        #[arg(long)]
+        poll: bool,
+    },
+    /// Manage ordered audiobook collections
+    Collection {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: CollectionOperations,
+    },
+    /// Manage audiobook tags
+    Tag {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: TagOperations,
+    },
+    /// Manage smart collections defined by filter rules
+    Smart {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: SmartOperations,
+    },
+    /// Manage listener profiles
+    Profile {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: ProfileOperations,
+    },
+    /// Show or change listening progress of the selected profile
+    Progress {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: ProgressOperations,
+    },
+    /// Sync listening progress with other devices through a shared folder
+    Sync {
+        /// Shared directory holding the progress files of all devices
+        directory: PathBuf,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// How to choose between devices that disagree (last-writer-wins, furthest-position)
+        #[arg(long, default_value = "last-writer-wins")]
+        policy: SyncPolicy,
+
+        /// Position difference in seconds below which devices are not in conflict
+        #[arg(long, default_value_t = DEFAULT_CONFLICT_TOLERANCE_SECONDS)]
+        tolerance: u64,
+    },
+    /// Publish libraries and collections as private podcast feeds
+    Feed {
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        #[command(subcommand)]
+        operation: FeedOperations,
+    },
+    /// Look up an audiobook in an online catalog and review the differences
+    Lookup {
+        /// Audiobook ID or path
+        audiobook: String,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// Base URL of the Open Library compatible catalog
+        #[arg(long, default_value = OPEN_LIBRARY_URL)]
+        provider_url: String,
+
+        /// Search by ASIN instead of title and author
+        #[arg(long)]
+        asin: Option<String>,
+
+        /// Minimum match score between 0 and 1
+        #[arg(long, default_value_t = 0.5)]
+        min_score: f32,
+
+        /// Comma separated fields to accept (title, author, narrator, description)
+        #[arg(long, value_delimiter = ',')]
+        apply: Vec<MetadataField>,
+    },
+    /// Write audiobook metadata to sidecar files next to the audio
+    Sidecar {
+        /// Audiobook IDs or paths
+        #[arg(required = true)]
+        audiobooks: Vec<String>,
+
+        /// Path to the database file (optional, defaults to centralized app database)
+        #[arg(short = 'f', long)]
+        database: Option<PathBuf>,
+
+        /// Comma separated formats to write (audiobookshelf, opf, text); all when omitted
+        #[arg(long, value_delimiter = ',')]
+        format: Vec<SidecarFormat>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -74,6 +299,210 @@ pub enum DbOperations {
     Stats,
     /// Clean/optimize database
     Clean,
+    /// Find audiobooks that appear more than once
+    Duplicates {
+        /// Skip acoustic fingerprint confirmation and match on tags and duration only
+        #[arg(long)]
+        no_fingerprint: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CollectionOperations {
+    /// Create an empty collection
+    Create {
+        /// Collection name
+        name: String,
+
+        /// Optional description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+    /// List all collections
+    List,
+    /// Show the audiobooks of a collection in order
+    Show {
+        /// Collection name
+        name: String,
+    },
+    /// Append audiobooks to a collection
+    Add {
+        /// Collection name
+        name: String,
+
+        /// Audiobook IDs or file paths
+        #[arg(required_unless_present = "selected")]
+        audiobooks: Vec<String>,
+
+        /// Add every audiobook currently selected in the library
+        #[arg(long)]
+        selected: bool,
+    },
+    /// Remove audiobooks from a collection
+    Remove {
+        /// Collection name
+        name: String,
+
+        /// Audiobook IDs or file paths
+        #[arg(required_unless_present = "selected")]
+        audiobooks: Vec<String>,
+
+        /// Remove every audiobook currently selected in the library
+        #[arg(long)]
+        selected: bool,
+    },
+    /// Delete a collection, keeping its audiobooks
+    Delete {
+        /// Collection name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagOperations {
+    /// List all tags in use
+    List,
+    /// Show the audiobooks with a tag
+    Show {
+        /// Tag name
+        tag: String,
+    },
+    /// Tag audiobooks, creating the tag if needed
+    Add {
+        /// Tag name
+        tag: String,
+
+        /// Audiobook IDs or file paths
+        #[arg(required_unless_present = "selected")]
+        audiobooks: Vec<String>,
+
+        /// Tag every audiobook currently selected in the library
+        #[arg(long)]
+        selected: bool,
+    },
+    /// Remove a tag from audiobooks
+    Remove {
+        /// Tag name
+        tag: String,
+
+        /// Audiobook IDs or file paths
+        #[arg(required_unless_present = "selected")]
+        audiobooks: Vec<String>,
+
+        /// Untag every audiobook currently selected in the library
+        #[arg(long)]
+        selected: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum FeedOperations {
+    /// Write the RSS feed of a library or collection to a file
+    Generate {
+        /// Library name, ID or path
+        #[arg(
+            long,
+            required_unless_present = "collection",
+            conflicts_with = "collection"
+        )]
+        library: Option<String>,
+
+        /// Collection name
+        #[arg(long)]
+        collection: Option<String>,
+
+        /// URL that enclosure and cover URLs start from
+        #[arg(long)]
+        base_url: String,
+
+        /// File to write the feed to
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// How URLs are built from the base URL (server, files)
+        #[arg(long, default_value = "server")]
+        layout: EnclosureLayout,
+
+        /// ABOP server token to add to URLs
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Feed title instead of the library or collection name
+        #[arg(long)]
+        title: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SmartOperations {
+    /// Create a smart collection from a rule
+    Create {
+        /// Smart collection name
+        name: String,
+
+        /// Rule such as `completed = false and duration > 10h and added within 30d`
+        rule: String,
+    },
+    /// List all smart collections
+    List,
+    /// Re-evaluate a smart collection and show its audiobooks
+    Show {
+        /// Smart collection name
+        name: String,
+    },
+    /// Replace the rule of a smart collection
+    Edit {
+        /// Smart collection name
+        name: String,
+
+        /// New rule
+        rule: String,
+    },
+    /// Delete a smart collection, keeping its audiobooks
+    Delete {
+        /// Smart collection name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileOperations {
+    /// List all profiles
+    List,
+    /// Create a profile
+    Create {
+        /// Profile name
+        name: String,
+    },
+    /// Delete a profile with its progress and bookmarks
+    Delete {
+        /// Profile name or ID
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProgressOperations {
+    /// List progress, most recently played first
+    List,
+    /// Set the playback position of an audiobook
+    Set {
+        /// Audiobook ID or file path
+        audiobook: String,
+
+        /// Position in seconds
+        position: u64,
+    },
+    /// Mark an audiobook as completed
+    Complete {
+        /// Audiobook ID or file path
+        audiobook: String,
+    },
+    /// Clear the progress of an audiobook
+    Reset {
+        /// Audiobook ID or file path
+        audiobook: String,
+    },
 }
 
 /// Initialize logging based on CLI arguments
@@ -124,6 +553,154 @@ pub fn run_with_args(args: Args) -> CliResult<()> {
             log::debug!("Executing database command: {operation:?} on {database:?}");
             crate::commands::db::run(database, operation, args.json)
         }
+        Commands::Verify {
+            database,
+            library,
+            collection,
+            problems_only,
+        } => {
+            log::debug!("Executing verify command on {database:?}");
+            crate::commands::verify::run(database, library, collection, problems_only, args.json)
+        }
+        Commands::CheckAcx { paths } => {
+            log::debug!("Executing check-acx command on {paths:?}");
+            crate::commands::acx::run(paths, args.json)
+        }
+        Commands::Organize {
+            library,
+            database,
+            template,
+            dry_run,
+        } => {
+            log::debug!("Executing organize command on {library:?}");
+            crate::commands::organize::run(library, database, template, dry_run, args.json)
+        }
+        Commands::Split {
+            input,
+            cue,
+            silence,
+            output_dir,
+            template,
+            overwrite,
+        } => {
+            log::debug!("Executing split command on {input:?}");
+            crate::commands::split::run(
+                input, cue, silence, output_dir, template, overwrite, args.json,
+            )
+        }
+        Commands::Process {
+            inputs,
+            database,
+            mono,
+            output_dir,
+            naming_pattern,
+            resume,
+        } => {
+            log::debug!("Executing process command on {inputs:?}");
+            let options = crate::commands::process::ProcessOptions {
+                mono,
+                output_dir,
+                naming_pattern,
+            };
+            if resume {
+                crate::commands::process::resume(database, args.json)
+            } else {
+                crate::commands::process::run(database, inputs, options, args.json)
+            }
+        }
+        Commands::Watch {
+            library,
+            database,
+            poll,
+        } => {
+            log::debug!("Executing watch command on {library:?}");
+            crate::commands::watch::run(library, database, poll, args.json)
+        }
+        Commands::Collection {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing collection command: {operation:?}");
+            crate::commands::collection::run(database, operation, args.json)
+        }
+        Commands::Tag {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing tag command: {operation:?}");
+            crate::commands::tag::run(database, operation, args.json)
+        }
+        Commands::Smart {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing smart collection command: {operation:?}");
+            crate::commands::smart::run(database, args.profile, operation, args.json)
+        }
+        Commands::Profile {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing profile command: {operation:?}");
+            crate::commands::profile::run(database, operation, args.json)
+        }
+        Commands::Progress {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing progress command: {operation:?}");
+            crate::commands::progress::run(database, args.profile, operation, args.json)
+        }
+        Commands::Sync {
+            directory,
+            database,
+            policy,
+            tolerance,
+        } => {
+            log::debug!("Executing sync command on {directory:?}");
+            crate::commands::sync::run(
+                directory,
+                database,
+                args.profile,
+                policy,
+                tolerance,
+                args.json,
+            )
+        }
+        Commands::Feed {
+            database,
+            operation,
+        } => {
+            log::debug!("Executing feed command: {operation:?}");
+            crate::commands::feed::run(database, operation, args.json)
+        }
+        Commands::Lookup {
+            audiobook,
+            database,
+            provider_url,
+            asin,
+            min_score,
+            apply,
+        } => {
+            log::debug!("Executing lookup command for {audiobook}");
+            crate::commands::lookup::run(
+                database,
+                &audiobook,
+                &provider_url,
+                asin,
+                min_score,
+                &apply,
+                args.json,
+            )
+        }
+        Commands::Sidecar {
+            audiobooks,
+            database,
+            format,
+        } => {
+            log::debug!("Executing sidecar command for {audiobooks:?}");
+            crate::commands::sidecar::run(database, &audiobooks, &format, args.json)
+        }
     }
 }
 
@@ -215,6 +792,12 @@ mod tests {
             ("list", DbOperations::List),
             ("stats", DbOperations::Stats),
             ("clean", DbOperations::Clean),
+            (
+                "duplicates",
+                DbOperations::Duplicates {
+                    no_fingerprint: false,
+                },
+            ),
         ];
 
         for (op_name, expected_op) in operations {
@@ -237,6 +820,466 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_args_parsing_verify_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "verify",
+            "--database",
+            "/test/db.sqlite",
+            "--library",
+            "/test/path",
+            "--problems-only",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Verify {
+                database,
+                library,
+                collection,
+                problems_only,
+            } => {
+                assert_eq!(database, PathBuf::from("/test/db.sqlite"));
+                assert_eq!(library, Some(PathBuf::from("/test/path")));
+                assert_eq!(collection, None);
+                assert!(problems_only);
+            }
+            _ => panic!("Expected verify command"),
+        }
+
+        // A library and a collection cannot be combined
+        assert!(
+            Args::try_parse_from([
+                "abop-cli",
+                "verify",
+                "-f",
+                "/test/db.sqlite",
+                "--library",
+                "/test/path",
+                "--collection",
+                "Commute queue",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_args_parsing_collection_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "collection",
+            "add",
+            "Commute queue",
+            "book-1",
+            "/books/two.mp3",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Collection {
+                database,
+                operation:
+                    CollectionOperations::Add {
+                        name,
+                        audiobooks,
+                        selected,
+                    },
+            } => {
+                assert_eq!(database, None);
+                assert_eq!(name, "Commute queue");
+                assert_eq!(audiobooks, ["book-1", "/books/two.mp3"]);
+                assert!(!selected);
+            }
+            _ => panic!("Expected collection add command"),
+        }
+
+        // Adding needs audiobooks or the current selection
+        assert!(Args::try_parse_from(["abop-cli", "collection", "add", "Queue"]).is_err());
+        assert!(Args::try_parse_from(["abop-cli", "tag", "add", "Classic", "--selected"]).is_ok());
+    }
+
+    #[test]
+    fn test_args_parsing_smart_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "smart",
+            "create",
+            "Long listens",
+            "duration > 10h and completed = false",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Smart {
+                database,
+                operation: SmartOperations::Create { name, rule },
+            } => {
+                assert_eq!(database, None);
+                assert_eq!(name, "Long listens");
+                assert_eq!(rule, "duration > 10h and completed = false");
+            }
+            _ => panic!("Expected smart create command"),
+        }
+        assert!(Args::try_parse_from(["abop-cli", "smart", "create", "Long listens"]).is_err());
+    }
+
+    #[test]
+    fn test_args_parsing_profile_flag() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "progress",
+            "set",
+            "/books/dune.mp3",
+            "3600",
+            "--profile",
+            "Sam",
+        ])
+        .unwrap();
+
+        assert_eq!(args.profile.as_deref(), Some("Sam"));
+        match args.command {
+            Commands::Progress {
+                database,
+                operation:
+                    ProgressOperations::Set {
+                        audiobook,
+                        position,
+                    },
+            } => {
+                assert_eq!(database, None);
+                assert_eq!(audiobook, "/books/dune.mp3");
+                assert_eq!(position, 3600);
+            }
+            _ => panic!("Expected progress set command"),
+        }
+
+        let args = Args::try_parse_from(["abop-cli", "profile", "create", "Sam"]).unwrap();
+        assert!(args.profile.is_none());
+        assert!(matches!(
+            args.command,
+            Commands::Profile {
+                operation: ProfileOperations::Create { .. },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_args_parsing_sync_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "sync",
+            "/mnt/syncthing/abop",
+            "--policy",
+            "furthest",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Sync {
+                directory,
+                database,
+                policy,
+                tolerance,
+            } => {
+                assert_eq!(directory, PathBuf::from("/mnt/syncthing/abop"));
+                assert_eq!(database, None);
+                assert_eq!(policy, SyncPolicy::FurthestPosition);
+                assert_eq!(tolerance, DEFAULT_CONFLICT_TOLERANCE_SECONDS);
+            }
+            _ => panic!("Expected sync command"),
+        }
+        assert!(
+            Args::try_parse_from(["abop-cli", "sync", "/tmp/abop", "--policy", "closest"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_args_parsing_feed_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "feed",
+            "generate",
+            "--library",
+            "Books",
+            "--base-url",
+            "http://nas.lan:8765",
+            "--output",
+            "/srv/feeds/books.xml",
+            "--layout",
+            "files",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Feed {
+                operation:
+                    FeedOperations::Generate {
+                        library,
+                        collection,
+                        base_url,
+                        output,
+                        layout,
+                        token,
+                        ..
+                    },
+                ..
+            } => {
+                assert_eq!(library.as_deref(), Some("Books"));
+                assert_eq!(collection, None);
+                assert_eq!(base_url, "http://nas.lan:8765");
+                assert_eq!(output, PathBuf::from("/srv/feeds/books.xml"));
+                assert_eq!(layout, EnclosureLayout::Files);
+                assert_eq!(token, None);
+            }
+            _ => panic!("Expected feed generate command"),
+        }
+
+        let feed = |extra: &[&str]| {
+            let mut argv = vec![
+                "abop-cli",
+                "feed",
+                "generate",
+                "--base-url",
+                "http://x",
+                "-o",
+                "f.xml",
+            ];
+            argv.extend_from_slice(extra);
+            Args::try_parse_from(argv)
+        };
+        assert!(feed(&[]).is_err());
+        assert!(feed(&["--library", "a", "--collection", "b"]).is_err());
+        assert!(feed(&["--collection", "b"]).is_ok());
+    }
+
+    #[test]
+    fn test_args_parsing_lookup_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "lookup",
+            "book-1",
+            "--provider-url",
+            "http://localhost:8080",
+            "--apply",
+            "title,description",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Lookup {
+                audiobook,
+                provider_url,
+                asin,
+                min_score,
+                apply,
+                ..
+            } => {
+                assert_eq!(audiobook, "book-1");
+                assert_eq!(provider_url, "http://localhost:8080");
+                assert_eq!(asin, None);
+                assert!((min_score - 0.5).abs() < f32::EPSILON);
+                assert_eq!(apply, [MetadataField::Title, MetadataField::Description]);
+            }
+            _ => panic!("Expected lookup command"),
+        }
+        assert!(
+            Args::try_parse_from([
+                "abop-cli",
+                "lookup",
+                "book-1",
+                "--provider-url",
+                "http://x",
+                "--apply",
+                "cover",
+            ])
+            .is_err()
+        );
+
+        let args = Args::try_parse_from(["abop-cli", "lookup", "book-1"]).unwrap();
+        match args.command {
+            Commands::Lookup { provider_url, .. } => assert_eq!(provider_url, OPEN_LIBRARY_URL),
+            _ => panic!("Expected lookup command"),
+        }
+    }
+
+    #[test]
+    fn test_args_parsing_sidecar_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "sidecar",
+            "book-1",
+            "/books/hyperion.m4b",
+            "--format",
+            "opf,text",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Sidecar {
+                audiobooks, format, ..
+            } => {
+                assert_eq!(audiobooks, ["book-1", "/books/hyperion.m4b"]);
+                assert_eq!(format, [SidecarFormat::Opf, SidecarFormat::Text]);
+            }
+            _ => panic!("Expected sidecar command"),
+        }
+        assert!(Args::try_parse_from(["abop-cli", "sidecar"]).is_err());
+        assert!(
+            Args::try_parse_from(["abop-cli", "sidecar", "book-1", "--format", "nfo"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_args_parsing_check_acx_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "--json",
+            "check-acx",
+            "/book/01.mp3",
+            "/book/02.mp3",
+        ])
+        .unwrap();
+
+        assert!(args.json);
+        match args.command {
+            Commands::CheckAcx { paths } => {
+                assert_eq!(
+                    paths,
+                    vec![PathBuf::from("/book/01.mp3"), PathBuf::from("/book/02.mp3")]
+                );
+            }
+            _ => panic!("Expected check-acx command"),
+        }
+        assert!(Args::try_parse_from(["abop-cli", "check-acx"]).is_err());
+    }
+
+    #[test]
+    fn test_args_parsing_organize_command() {
+        let args =
+            Args::try_parse_from(["abop-cli", "organize", "-l", "/books", "--dry-run"]).unwrap();
+
+        match args.command {
+            Commands::Organize {
+                library,
+                database,
+                template,
+                dry_run,
+            } => {
+                assert_eq!(library, PathBuf::from("/books"));
+                assert_eq!(database, None);
+                assert_eq!(
+                    template,
+                    "{author}/{series}/{series_index} - {title}/{part}"
+                );
+                assert!(dry_run);
+            }
+            _ => panic!("Expected organize command"),
+        }
+    }
+
+    #[test]
+    fn test_args_parsing_process_command() {
+        let args =
+            Args::try_parse_from(["abop-cli", "process", "a.wav", "b.wav", "--mono"]).unwrap();
+        match args.command {
+            Commands::Process {
+                inputs,
+                mono,
+                naming_pattern,
+                resume,
+                ..
+            } => {
+                assert_eq!(inputs, [PathBuf::from("a.wav"), PathBuf::from("b.wav")]);
+                assert!(mono);
+                assert_eq!(naming_pattern, "{filename}_processed");
+                assert!(!resume);
+            }
+            _ => panic!("Expected process command"),
+        }
+
+        assert!(Args::try_parse_from(["abop-cli", "process", "--resume"]).is_ok());
+        assert!(Args::try_parse_from(["abop-cli", "process"]).is_err());
+        assert!(Args::try_parse_from(["abop-cli", "process", "a.wav", "--resume"]).is_err());
+    }
+
+    #[test]
+    fn test_args_parsing_split_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "split",
+            "/book/book.m4b",
+            "--cue",
+            "/book/book.cue",
+            "-o",
+            "/book/parts",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Split {
+                input,
+                cue,
+                silence,
+                output_dir,
+                template,
+                overwrite,
+            } => {
+                assert_eq!(input, PathBuf::from("/book/book.m4b"));
+                assert_eq!(cue, Some(PathBuf::from("/book/book.cue")));
+                assert!(!silence);
+                assert_eq!(output_dir, Some(PathBuf::from("/book/parts")));
+                assert_eq!(template, "{track:02} - {chapter}");
+                assert!(!overwrite);
+            }
+            _ => panic!("Expected split command"),
+        }
+        assert!(
+            Args::try_parse_from([
+                "abop-cli",
+                "split",
+                "/a.mp3",
+                "--cue",
+                "/a.cue",
+                "--silence"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_args_parsing_watch_command() {
+        let args = Args::try_parse_from([
+            "abop-cli",
+            "watch",
+            "--library",
+            "/test/one",
+            "--library",
+            "/test/two",
+            "--poll",
+        ])
+        .unwrap();
+
+        match args.command {
+            Commands::Watch {
+                library,
+                database,
+                poll,
+            } => {
+                assert_eq!(
+                    library,
+                    vec![PathBuf::from("/test/one"), PathBuf::from("/test/two")]
+                );
+                assert!(database.is_none());
+                assert!(poll);
+            }
+            _ => panic!("Expected watch command"),
+        }
+
+        assert!(Args::try_parse_from(["abop-cli", "watch"]).is_err());
+    }
+
     #[test]
     fn test_args_parsing_missing_required_args() {
         // Test that missing required arguments cause parsing to fail
@@ -0,0 +1,153 @@
+//! ACX compliance check command implementation
+//!
+//! This module measures audio files against audiobook distribution
+//! requirements (RMS level, peaks, noise floor, room tone and format) and
+//! reports which processor would fix each failed check.
+
+use crate::error::CliResult;
+use crate::output::CliOutput;
+use abop_core::SUPPORTED_AUDIO_EXTENSIONS;
+use abop_core::audio::processing::{AcxChecker, AcxFileReport, AcxReport};
+use anyhow::Context;
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+
+/// Execute the check-acx command
+///
+/// # Arguments
+/// * `paths` - Audio files, or directories whose audio files are checked as one book
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - A path does not exist or cannot be read
+/// - No audio files are found
+pub fn run(paths: Vec<PathBuf>, json_output: bool) -> CliResult<()> {
+    let files = collect_audio_files(&paths)?;
+    info!("Checking {} files for ACX compliance", files.len());
+
+    let report = AcxChecker::default().check_files(&files);
+
+    if json_output {
+        let json = CliOutput::check_acx_success(report)
+            .to_json()
+            .with_context(|| "serializing ACX report to JSON")?;
+        println!("{json}");
+    } else {
+        show_acx_report(&report);
+    }
+
+    Ok(())
+}
+
+/// Expand directories into their audio files, sorted by name
+fn collect_audio_files(paths: &[PathBuf]) -> CliResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Audio path does not exist: {}", path.display()))?;
+        if !metadata.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(path)
+            .with_context(|| format!("Cannot read directory {}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|entry| entry.is_file() && is_audio_file(entry))
+            .collect();
+        entries.sort();
+        files.extend(entries);
+    }
+
+    if files.is_empty() {
+        return Err(anyhow::anyhow!("No audio files found"));
+    }
+    Ok(files)
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            SUPPORTED_AUDIO_EXTENSIONS
+                .iter()
+                .any(|supported| ext.eq_ignore_ascii_case(supported))
+        })
+}
+
+/// Print a human readable compliance summary
+fn show_acx_report(report: &AcxReport) {
+    for file in &report.files {
+        show_file_report(file);
+    }
+    for (path, error) in &report.errors {
+        warn!("{}: {error}", path.display());
+    }
+    for finding in report.findings.iter().filter(|finding| !finding.passed) {
+        warn!(
+            "{} differs across files: {}",
+            finding.check, finding.expected
+        );
+        if let Some(fix) = finding.fix {
+            info!("  → {}", fix.description());
+        }
+    }
+
+    let passed = report.files.iter().filter(|file| file.passed).count();
+    info!(
+        "{passed} of {} files pass ACX checks; overall {}",
+        report.files.len() + report.errors.len(),
+        if report.passed { "PASS" } else { "FAIL" }
+    );
+}
+
+fn show_file_report(file: &AcxFileReport) {
+    let name = file
+        .path
+        .as_deref()
+        .map_or_else(String::new, |path| path.display().to_string());
+    let m = &file.measurements;
+    info!(
+        "{name} [{}]: RMS {:.1} dB, peak {:.1} dB, noise floor {:.1} dB",
+        if file.passed { "PASS" } else { "FAIL" },
+        m.rms_db,
+        m.peak_db,
+        m.noise_floor_db
+    );
+    for finding in file.failures() {
+        warn!(
+            "  {} {:.2} (expected {})",
+            finding.check, finding.measured, finding.expected
+        );
+        if let Some(fix) = finding.fix {
+            info!("    → {}", fix.description());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_acx_with_nonexistent_path() {
+        let result = run(vec![PathBuf::from("/nonexistent/chapter01.mp3")], false);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_collect_audio_files_filters_and_sorts() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["02.mp3", "01.M4B", "cover.jpg"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let files = collect_audio_files(&[dir.path().to_path_buf()]).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["01.M4B", "02.mp3"]);
+    }
+}
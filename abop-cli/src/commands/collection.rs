@@ -0,0 +1,261 @@
+//! Collection command implementation
+//!
+//! This module manages user collections: ordered lists of audiobooks such as
+//! a commute queue. Audiobooks are given by ID or file path, or taken from
+//! the current library selection with `--selected`.
+
+use crate::cli::CollectionOperations;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{AudiobookInfo, CliOutput, CollectionOutput};
+use abop_core::db::Database;
+use abop_core::models::Collection;
+use anyhow::Context;
+use log::{debug, info};
+use std::path::{Path, PathBuf};
+
+/// Execute a collection operation
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `operation` - The collection operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The collection or an audiobook does not exist
+/// - A new collection name is empty or already taken
+pub fn run(
+    database_path: Option<PathBuf>,
+    operation: CollectionOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting collection operation: {operation:?}");
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let repo = db.collection_repository();
+
+    let output = match operation {
+        CollectionOperations::Create { name, description } => {
+            let collection = repo
+                .create(&name, description.as_deref())
+                .with_database_context("creating collection")?;
+            CollectionOutput::Update {
+                collection,
+                changed: 0,
+            }
+        }
+        CollectionOperations::List => CollectionOutput::List {
+            collections: repo
+                .find_all()
+                .with_database_context("listing collections")?,
+        },
+        CollectionOperations::Show { name } => {
+            let collection = find_collection(&db, &name)?;
+            let audiobooks = repo
+                .find_audiobooks(&collection.id)
+                .with_database_context("loading collection")?;
+            CollectionOutput::Show {
+                collection,
+                audiobooks: audiobooks.iter().map(AudiobookInfo::from).collect(),
+            }
+        }
+        CollectionOperations::Add {
+            name,
+            audiobooks,
+            selected,
+        } => {
+            let collection = find_collection(&db, &name)?;
+            let ids = resolve_audiobooks(&db, &audiobooks)?;
+            let mut changed = repo
+                .add_audiobooks(&collection.id, &ids)
+                .with_database_context("adding to collection")?;
+            if selected {
+                changed += repo
+                    .add_selected(&collection.id)
+                    .with_database_context("adding selection to collection")?;
+            }
+            CollectionOutput::Update {
+                collection: find_collection(&db, &name)?,
+                changed,
+            }
+        }
+        CollectionOperations::Remove {
+            name,
+            audiobooks,
+            selected,
+        } => {
+            let collection = find_collection(&db, &name)?;
+            let ids = resolve_audiobooks(&db, &audiobooks)?;
+            let mut changed = repo
+                .remove_audiobooks(&collection.id, &ids)
+                .with_database_context("removing from collection")?;
+            if selected {
+                changed += repo
+                    .remove_selected(&collection.id)
+                    .with_database_context("removing selection from collection")?;
+            }
+            CollectionOutput::Update {
+                collection: find_collection(&db, &name)?,
+                changed,
+            }
+        }
+        CollectionOperations::Delete { name } => {
+            let collection = find_collection(&db, &name)?;
+            repo.delete(&collection.id)
+                .with_database_context("deleting collection")?;
+            CollectionOutput::Delete {
+                name: collection.name,
+            }
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::collection_success(output)
+            .to_json()
+            .with_context(|| "serializing collection results to JSON")?;
+        println!("{json}");
+    } else {
+        show_collection_output(&output);
+    }
+    Ok(())
+}
+
+/// Look up a collection by name
+pub(crate) fn find_collection(db: &Database, name: &str) -> CliResult<Collection> {
+    db.collection_repository()
+        .find_by_name(name)
+        .with_database_context("looking up collection")?
+        .ok_or_else(|| anyhow::anyhow!("Collection does not exist: {name}"))
+}
+
+/// Resolve audiobook IDs or file paths to audiobook IDs
+pub(crate) fn resolve_audiobooks(db: &Database, audiobooks: &[String]) -> CliResult<Vec<String>> {
+    let repo = db.audiobook_repository();
+    audiobooks
+        .iter()
+        .map(|audiobook| {
+            if let Some(found) = repo
+                .find_by_id(audiobook)
+                .with_database_context("looking up audiobook")?
+            {
+                return Ok(found.id);
+            }
+            // Paths are stored absolute, so try the canonical form as well
+            let canonical = Path::new(audiobook)
+                .canonicalize()
+                .ok()
+                .map(|path| path.to_string_lossy().to_string());
+            for path in std::iter::once(audiobook.clone()).chain(canonical) {
+                if let Some(found) = repo
+                    .find_by_path(&path)
+                    .with_database_context("looking up audiobook")?
+                {
+                    return Ok(found.id);
+                }
+            }
+            Err(anyhow::anyhow!("Audiobook not found: {audiobook}"))
+        })
+        .collect()
+}
+
+/// Print a human readable collection summary
+fn show_collection_output(output: &CollectionOutput) {
+    match output {
+        CollectionOutput::List { collections } => {
+            if collections.is_empty() {
+                info!("No collections");
+            }
+            for collection in collections {
+                info!(
+                    "{} ({} audiobooks)",
+                    collection.name, collection.audiobook_count
+                );
+            }
+        }
+        CollectionOutput::Show {
+            collection,
+            audiobooks,
+        } => {
+            info!("{}", collection.name);
+            if let Some(description) = &collection.description {
+                info!("{description}");
+            }
+            for (position, audiobook) in audiobooks.iter().enumerate() {
+                info!(
+                    "{:>3}. {} by {}",
+                    position + 1,
+                    audiobook.title,
+                    audiobook.author
+                );
+            }
+        }
+        CollectionOutput::Update {
+            collection,
+            changed,
+        } => {
+            info!(
+                "✓ {} changed, {} now has {} audiobooks",
+                changed, collection.name, collection.audiobook_count
+            );
+        }
+        CollectionOutput::Delete { name } => info!("✓ Deleted collection {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("abop.db");
+        let db = Database::open(&database).unwrap();
+        let library = db
+            .libraries()
+            .create("Books", dir.path().to_path_buf())
+            .unwrap();
+        let mut audiobook =
+            abop_core::models::Audiobook::new(&library.id, dir.path().join("a.mp3"));
+        audiobook.title = Some("Dune".to_string());
+        db.add_audiobook(&audiobook).unwrap();
+
+        let create = CollectionOperations::Create {
+            name: "Commute queue".to_string(),
+            description: None,
+        };
+        run(Some(database.clone()), create, true).unwrap();
+        let add = CollectionOperations::Add {
+            name: "commute queue".to_string(),
+            audiobooks: vec![dir.path().join("a.mp3").to_string_lossy().to_string()],
+            selected: false,
+        };
+        run(Some(database.clone()), add, true).unwrap();
+        assert_eq!(
+            find_collection(&db, "Commute queue")
+                .unwrap()
+                .audiobook_count,
+            1
+        );
+
+        let missing = CollectionOperations::Add {
+            name: "Commute queue".to_string(),
+            audiobooks: vec!["no-such-book".to_string()],
+            selected: false,
+        };
+        let result = run(Some(database.clone()), missing, true);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Audiobook not found")
+        );
+
+        let delete = CollectionOperations::Delete {
+            name: "Commute queue".to_string(),
+        };
+        run(Some(database), delete, true).unwrap();
+        assert!(find_collection(&db, "Commute queue").is_err());
+    }
+}
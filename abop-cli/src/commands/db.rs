@@ -1,12 +1,13 @@
 //! Database operations command implementation
 //!
 //! This module handles all database-related operations including
-//! initialization, listing, statistics, and cleanup operations.
+//! initialization, listing, statistics, cleanup and duplicate detection.
 
 use crate::cli::DbOperations;
 use crate::error::{CliResult, CliResultExt, validate_existing_database_path};
 use crate::utils::{get_audiobook_count, show_audiobook_list};
 use abop_core::db::Database;
+use abop_core::library::{DuplicateCluster, DuplicateConfig, DuplicateDetector};
 use anyhow::Context;
 use log::{debug, info};
 use std::path::PathBuf;
@@ -31,6 +32,9 @@ pub fn run(database_path: PathBuf, operation: DbOperations, json_output: bool) -
         DbOperations::List => list(database_path, json_output),
         DbOperations::Stats => stats(database_path, json_output),
         DbOperations::Clean => clean(database_path, json_output),
+        DbOperations::Duplicates { no_fingerprint } => {
+            duplicates(database_path, !no_fingerprint, json_output)
+        }
     }
 }
 
@@ -153,6 +157,71 @@ fn clean(database_path: PathBuf, json_output: bool) -> CliResult<()> {
     Ok(())
 }
 
+/// Find duplicate audiobooks across all libraries
+fn duplicates(database_path: PathBuf, use_fingerprints: bool, json_output: bool) -> CliResult<()> {
+    info!("Finding duplicate audiobooks in: {database_path:?}");
+
+    // Validate database exists before attempting connection
+    validate_existing_database_path(&database_path)?;
+
+    let db = Database::open(&database_path)
+        .with_database_context("opening for duplicate detection")?;
+    let audiobooks = db
+        .get_all_audiobooks()
+        .with_database_context("retrieving audiobooks for duplicate detection")?;
+
+    let detector = DuplicateDetector::new(DuplicateConfig {
+        use_fingerprints,
+        ..DuplicateConfig::default()
+    });
+    let clusters = detector.find_duplicates(&audiobooks);
+
+    if json_output {
+        let output = crate::output::CliOutput::database_duplicates_success(clusters);
+        let json = output
+            .to_json()
+            .with_context(|| "serializing duplicate results to JSON")?;
+        println!("{json}");
+    } else {
+        show_duplicate_clusters(&clusters);
+    }
+
+    Ok(())
+}
+
+/// Print duplicate clusters with the recommended keeper marked
+fn show_duplicate_clusters(clusters: &[DuplicateCluster]) {
+    for cluster in clusters {
+        let verified = if cluster.fingerprint_verified {
+            "fingerprint verified"
+        } else {
+            "tags and duration only"
+        };
+        info!("{} by {} ({verified})", cluster.title, cluster.author);
+        for candidate in &cluster.candidates {
+            let marker = if candidate.audiobook_id == cluster.keeper_id {
+                "keep"
+            } else {
+                "extra"
+            };
+            let bitrate = candidate
+                .bitrate_kbps
+                .map_or_else(|| "? kbps".to_string(), |kbps| format!("{kbps} kbps"));
+            info!(
+                "  [{marker}] {} ({bitrate}, {} tags)",
+                candidate.path.display(),
+                candidate.tag_score
+            );
+        }
+    }
+
+    let extras: usize = clusters.iter().map(|cluster| cluster.extras().count()).sum();
+    info!(
+        "Found {} duplicate groups with {extras} removable copies",
+        clusters.len()
+    );
+}
+
 /// Output audiobook list in JSON format
 fn output_audiobook_list_json(db: &Database) -> CliResult<()> {
     // 1) Gather libraries
@@ -369,7 +438,11 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("does not exist"));
 
-        let result = clean(nonexistent_path, false);
+        let result = clean(nonexistent_path.clone(), false);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+
+        let result = duplicates(nonexistent_path, false, false);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("does not exist"));
     }
@@ -432,6 +505,9 @@ mod tests {
             DbOperations::List,
             DbOperations::Stats,
             DbOperations::Clean,
+            DbOperations::Duplicates {
+                no_fingerprint: true,
+            },
         ];
 
         for op in operations {
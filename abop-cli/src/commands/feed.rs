@@ -0,0 +1,189 @@
+//! Podcast feed command implementation
+//!
+//! This module writes a library or collection as a static RSS feed so that
+//! podcast apps can subscribe to it, either through the ABOP server or a
+//! plain web server exposing the library directory.
+
+use crate::cli::FeedOperations;
+use crate::commands::collection::find_collection;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{CliOutput, FeedOutput};
+use abop_core::db::Database;
+use abop_core::feed::{COVERS_DIR, EnclosureLayout, Feed, FeedOptions, FeedSource};
+use abop_core::models::Library;
+use anyhow::Context;
+use log::{debug, info};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Execute feed operations
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `operation` - The feed operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The library or collection does not exist
+/// - The feed or cover images cannot be written
+pub fn run(
+    database_path: Option<PathBuf>,
+    operation: FeedOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+
+    let output = match operation {
+        FeedOperations::Generate {
+            library,
+            collection,
+            base_url,
+            output,
+            layout,
+            token,
+            title,
+        } => {
+            let source = match (library, collection) {
+                (Some(library), _) => FeedSource::Library(find_library(&db, &library)?.id),
+                (None, Some(collection)) => {
+                    FeedSource::Collection(find_collection(&db, &collection)?.id)
+                }
+                (None, None) => anyhow::bail!("Either --library or --collection is required"),
+            };
+            let mut options = FeedOptions::new(&base_url).with_layout(layout);
+            if let Some(token) = &token {
+                options = options.with_token(token);
+            }
+            if let Some(title) = &title {
+                options = options.with_title(title);
+            }
+            let feed =
+                Feed::build(&db, &source, &options).with_database_context("building feed")?;
+            write_feed(&feed, &output, layout)?
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::feed_success(output)
+            .to_json()
+            .with_context(|| "serializing feed output to JSON")?;
+        println!("{json}");
+    } else {
+        info!(
+            "✓ Wrote {} ({} episodes) to {}",
+            output.title,
+            output.episodes,
+            output.output.display()
+        );
+        if output.covers > 0 {
+            info!("  {} cover images in {COVERS_DIR}/", output.covers);
+        }
+    }
+    Ok(())
+}
+
+/// Find a library by ID, name or path
+fn find_library(db: &Database, library: &str) -> CliResult<Library> {
+    let repo = db.libraries();
+    if let Some(found) = repo
+        .find_by_id(library)
+        .with_database_context("looking up library")?
+    {
+        return Ok(found);
+    }
+    if let Some(found) = repo
+        .find_by_name(library)
+        .with_database_context("looking up library")?
+    {
+        return Ok(found);
+    }
+    // Library paths are stored absolute, so try the canonical form as well
+    let canonical = Path::new(library).canonicalize().ok();
+    for path in std::iter::once(PathBuf::from(library)).chain(canonical) {
+        if let Some(found) = repo
+            .find_by_path(&path)
+            .with_database_context("looking up library")?
+        {
+            return Ok(found);
+        }
+    }
+    anyhow::bail!("Library does not exist: {library}")
+}
+
+/// Write the feed, and its cover images in the static file layout
+fn write_feed(feed: &Feed, output: &Path, layout: EnclosureLayout) -> CliResult<FeedOutput> {
+    let directory = output.parent().unwrap_or_else(|| Path::new(""));
+    if !directory.as_os_str().is_empty() {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("Failed to create {}", directory.display()))?;
+    }
+    fs::write(output, feed.to_xml())
+        .with_context(|| format!("Failed to write feed to {}", output.display()))?;
+
+    if layout == EnclosureLayout::Files && !feed.covers.is_empty() {
+        let covers = directory.join(COVERS_DIR);
+        fs::create_dir_all(&covers)
+            .with_context(|| format!("Failed to create {}", covers.display()))?;
+        for cover in &feed.covers {
+            let path = covers.join(&cover.file_name);
+            debug!("Writing cover {}", path.display());
+            fs::write(&path, &cover.data)
+                .with_context(|| format!("Failed to write cover to {}", path.display()))?;
+        }
+    }
+
+    Ok(FeedOutput {
+        title: feed.title.clone(),
+        output: output.to_path_buf(),
+        episodes: feed.episodes.len(),
+        covers: feed.covers.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abop_core::models::Audiobook;
+
+    #[test]
+    fn test_generate_static_feed() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("library.db");
+        let root = dir.path().join("books");
+        let library_id = {
+            let db = Database::open(&database).unwrap();
+            let library = db.libraries().create("Books", root.clone()).unwrap();
+            let mut audiobook = Audiobook::new(&library.id, root.join("Dune.m4b"));
+            audiobook.title = Some("Dune".to_string());
+            audiobook.cover_art = Some(vec![0xFF, 0xD8, 0xFF, 0xE0]);
+            db.audiobook_repository().upsert(&audiobook).unwrap();
+            library.id
+        };
+
+        let output = dir.path().join("www/feed.xml");
+        let generate = |library: &str| FeedOperations::Generate {
+            library: Some(library.to_string()),
+            collection: None,
+            base_url: "http://nas.lan/books".to_string(),
+            output: output.clone(),
+            layout: EnclosureLayout::Files,
+            token: None,
+            title: None,
+        };
+        run(Some(database.clone()), generate("Books"), true).unwrap();
+
+        let xml = fs::read_to_string(&output).unwrap();
+        assert!(xml.contains("<title>Books</title>"));
+        assert!(xml.contains("url=\"http://nas.lan/books/Dune.m4b\""));
+        let covers: Vec<_> = fs::read_dir(dir.path().join("www").join(COVERS_DIR))
+            .unwrap()
+            .collect();
+        assert_eq!(covers.len(), 1);
+
+        run(Some(database.clone()), generate(&library_id), true).unwrap();
+        assert!(run(Some(database), generate("Missing"), true).is_err());
+    }
+}
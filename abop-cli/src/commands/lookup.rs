@@ -0,0 +1,121 @@
+//! Online metadata lookup command implementation
+//!
+//! The best match from the catalog is shown as a field-by-field review.
+//! Nothing is written unless the fields to accept are named with `--apply`.
+
+use crate::commands::collection::resolve_audiobooks;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{AudiobookInfo, CliOutput, LookupOutput};
+use abop_core::provider::{
+    Matcher, MetadataProvider, MetadataReview, OpenLibraryProvider, SearchQuery,
+};
+use abop_core::sidecar::MetadataField;
+use anyhow::Context;
+use log::{debug, info};
+use std::path::PathBuf;
+
+/// Execute a metadata lookup
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `audiobook` - Audiobook ID or path
+/// * `provider_url` - Base URL of the Open Library compatible catalog
+/// * `asin` - ASIN to search for instead of the title and author
+/// * `min_score` - Minimum match score for a result to be reviewed
+/// * `apply` - Fields to write to the database
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The audiobook does not exist
+/// - The catalog cannot be reached or answers with an error
+pub fn run(
+    database_path: Option<PathBuf>,
+    audiobook: &str,
+    provider_url: &str,
+    asin: Option<String>,
+    min_score: f32,
+    apply: &[MetadataField],
+    json_output: bool,
+) -> CliResult<()> {
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let audiobook_id = resolve_audiobooks(&db, &[audiobook.to_string()])?.remove(0);
+    let mut audiobook = db
+        .audiobook_repository()
+        .find_by_id(&audiobook_id)
+        .with_database_context("looking up audiobook")?
+        .ok_or_else(|| anyhow::anyhow!("Audiobook not found: {audiobook_id}"))?;
+
+    let query = asin.map_or_else(
+        || SearchQuery::from_audiobook(&audiobook),
+        SearchQuery::asin,
+    );
+    let provider = OpenLibraryProvider::new(provider_url);
+    debug!("Searching {} for {query:?}", provider.name());
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start async runtime")?;
+    let matched = runtime.block_on(async {
+        let Some(best) = Matcher::new(min_score)
+            .best_match(&provider, &query)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let book = provider.fetch(&best.book.id).await?;
+        Ok::<_, abop_core::error::AppError>(Some((book, best.score)))
+    });
+    let matched = matched.with_context(|| format!("Metadata lookup at {provider_url} failed"))?;
+
+    let (review, score) = match matched {
+        Some((book, score)) => (Some(MetadataReview::new(&audiobook, &book)), Some(score)),
+        None => (None, None),
+    };
+    let applied = match &review {
+        Some(review) if !apply.is_empty() => {
+            let applied = review.apply(&mut audiobook, apply);
+            if !applied.is_empty() {
+                db.audiobook_repository()
+                    .update(&audiobook)
+                    .with_database_context("updating audiobook")?;
+            }
+            applied
+        }
+        _ => Vec::new(),
+    };
+
+    let output = LookupOutput {
+        audiobook: AudiobookInfo::from(&audiobook),
+        review,
+        score,
+        applied,
+    };
+    if json_output {
+        let json = CliOutput::lookup_success(output)
+            .to_json()
+            .with_context(|| "serializing lookup output to JSON")?;
+        println!("{json}");
+    } else {
+        show_lookup_output(&output);
+    }
+    Ok(())
+}
+
+/// Print a human readable lookup summary
+fn show_lookup_output(output: &LookupOutput) {
+    let (Some(review), Some(score)) = (&output.review, output.score) else {
+        info!("No match found for {}", output.audiobook.title);
+        return;
+    };
+    info!("Best match (score {score:.2}): {review}");
+    if !output.applied.is_empty() {
+        let applied: Vec<&str> = output.applied.iter().map(|field| field.as_str()).collect();
+        info!("✓ Updated {}", applied.join(", "));
+    } else if !review.is_empty() {
+        info!("Re-run with --apply title,author,... to accept changes");
+    }
+}
@@ -3,5 +3,20 @@
 //! This module aggregates all command implementations, providing a clean
 //! separation between command parsing (in cli.rs) and command execution.
 
+pub mod acx;
+pub mod collection;
 pub mod db;
+pub mod feed;
+pub mod lookup;
+pub mod organize;
+pub mod process;
+pub mod profile;
+pub mod progress;
 pub mod scan;
+pub mod sidecar;
+pub mod smart;
+pub mod split;
+pub mod sync;
+pub mod tag;
+pub mod verify;
+pub mod watch;
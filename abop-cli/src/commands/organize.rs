@@ -0,0 +1,172 @@
+//! Library organize command implementation
+//!
+//! This module moves the audiobook files of a scanned library into a folder
+//! layout computed from a path template, updating the stored paths. With
+//! `--dry-run` it only prints the planned moves and any collisions.
+
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt, validate_library_path};
+use crate::output::{CliOutput, OrganizeOutput};
+use abop_core::library::{CollisionKind, LibraryOrganizer, OrganizePlan, OrganizerConfig};
+use anyhow::Context;
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+
+/// Execute the organize command
+///
+/// # Arguments
+/// * `library_path` - Library directory to organize
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `template` - Target path template relative to the library root
+/// * `dry_run` - Whether to only show the planned moves
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - The library path doesn't exist or hasn't been scanned
+/// - The template is invalid
+/// - Target paths collide (unless running with `--dry-run`)
+/// - A move or the database update fails; all files are moved back in that case
+pub fn run(
+    library_path: PathBuf,
+    database_path: Option<PathBuf>,
+    template: String,
+    dry_run: bool,
+    json_output: bool,
+) -> CliResult<()> {
+    validate_library_path(&library_path)?;
+    let organizer =
+        LibraryOrganizer::new(OrganizerConfig { template }).context("Invalid organize template")?;
+
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let library = db.libraries().find_by_path(&library_path)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Library is not in the database, scan it first: {}",
+            library_path.display()
+        )
+    })?;
+    let audiobooks = db
+        .get_audiobooks_in_library(&library.id)
+        .with_database_context("loading audiobooks")?;
+
+    let series = db
+        .series_repository()
+        .find_memberships_in_library(&library.id)
+        .with_database_context("loading series")?;
+
+    let plan = organizer.plan_with_series(&library.path, &audiobooks, &series);
+    let moved = if dry_run || plan.has_collisions() {
+        0
+    } else {
+        organizer
+            .apply(&plan, &db.audiobook_repository())
+            .context("Organizing library failed, all files were moved back")?
+    };
+
+    let collisions = plan.collisions.len();
+    if json_output {
+        let json = CliOutput::organize_success(OrganizeOutput {
+            dry_run,
+            moved,
+            plan,
+        })
+        .to_json()
+        .with_context(|| "serializing organize plan to JSON")?;
+        println!("{json}");
+    } else {
+        show_plan(&plan, &library.path, dry_run, moved);
+    }
+
+    if collisions > 0 && !dry_run {
+        return Err(anyhow::anyhow!(
+            "{collisions} target paths collide, nothing was moved"
+        ));
+    }
+    Ok(())
+}
+
+/// Print the planned moves relative to the library root
+fn show_plan(plan: &OrganizePlan, root: &Path, dry_run: bool, moved: usize) {
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    for planned in &plan.moves {
+        info!("{} → {}", relative(&planned.from), relative(&planned.to));
+    }
+    for collision in &plan.collisions {
+        let reason = match collision.kind {
+            CollisionKind::SharedTarget => "claimed by several files",
+            CollisionKind::TargetExists => "already exists",
+        };
+        warn!("{} {reason}:", relative(&collision.target));
+        for source in &collision.sources {
+            warn!("  {}", relative(source));
+        }
+    }
+
+    if dry_run {
+        info!(
+            "Dry run: {} files would be moved, {} already in place",
+            plan.moves.len(),
+            plan.unchanged
+        );
+    } else {
+        info!("Moved {moved} files, {} already in place", plan.unchanged);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_organize_with_nonexistent_library() {
+        let result = run(
+            PathBuf::from("/nonexistent/library"),
+            None,
+            abop_core::library::organizer::DEFAULT_ORGANIZE_TEMPLATE.to_string(),
+            true,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_organize_with_invalid_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = run(
+            dir.path().to_path_buf(),
+            Some(dir.path().join("abop.db")),
+            "{author}/{year}".to_string(),
+            true,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid organize template")
+        );
+    }
+
+    #[test]
+    fn test_organize_requires_scanned_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("library");
+        std::fs::create_dir(&library).unwrap();
+        let result = run(
+            library,
+            Some(dir.path().join("abop.db")),
+            abop_core::library::organizer::DEFAULT_ORGANIZE_TEMPLATE.to_string(),
+            true,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("scan it first"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Audio processing command implementation
+//!
+//! Every batch is recorded as a persisted job, so a run that is interrupted
+//! can be picked up again with `--resume`, skipping the files it already
+//! finished.
+
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{CliOutput, ProcessJobOutput, ProcessOutput};
+use abop_core::audio::processing::batch_processor::{BatchProcessor, ProcessingJobParameters};
+use abop_core::audio::processing::file_io::FileProcessingOptions;
+use abop_core::{ChannelMixerConfig, MixingAlgorithm, ProcessingConfig};
+use anyhow::Context;
+use log::{error, info};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Processing settings chosen on the command line
+#[derive(Debug, Clone)]
+pub struct ProcessOptions {
+    /// Mix the audio down to mono
+    pub mono: bool,
+    /// Directory for the processed files
+    pub output_dir: Option<PathBuf>,
+    /// Output filename pattern
+    pub naming_pattern: String,
+}
+
+/// Process audio files in a new persisted job
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `inputs` - Audio files to process
+/// * `options` - Processing settings
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - An input file does not exist
+/// - Database connection fails or the job cannot be stored
+/// - The processing pipeline cannot be created
+pub fn run(
+    database_path: Option<PathBuf>,
+    inputs: Vec<PathBuf>,
+    options: ProcessOptions,
+    json_output: bool,
+) -> CliResult<()> {
+    if let Some(missing) = inputs.iter().find(|input| !input.is_file()) {
+        return Err(anyhow::anyhow!(
+            "Input file does not exist: {}",
+            missing.display()
+        ));
+    }
+
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let mut config = ProcessingConfig::default();
+    if options.mono {
+        config.channel_mixer = Some(ChannelMixerConfig {
+            target_channels: Some(1),
+            mix_algorithm: MixingAlgorithm::Average,
+            ..Default::default()
+        });
+    }
+    let parameters = ProcessingJobParameters {
+        config,
+        options: FileProcessingOptions {
+            output_directory: options.output_dir,
+            naming_pattern: options.naming_pattern,
+            ..Default::default()
+        },
+        input_paths: inputs,
+    };
+
+    let (processor, input_paths) =
+        BatchProcessor::start_job(Arc::new(db.job_repository()), parameters)
+            .context("Failed to start processing job")?;
+    let job = process_job(&processor, &input_paths)?;
+    show_process_output(ProcessOutput { jobs: vec![job] }, json_output)
+}
+
+/// Resume every processing job that was interrupted before finishing
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if database connection fails or the interrupted jobs
+/// cannot be loaded.
+pub fn resume(database_path: Option<PathBuf>, json_output: bool) -> CliResult<()> {
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let batches = BatchProcessor::resume_unfinished(&Arc::new(db.job_repository()))
+        .context("Failed to load interrupted processing jobs")?;
+
+    let jobs = batches
+        .iter()
+        .map(|(processor, input_paths)| process_job(processor, input_paths))
+        .collect::<CliResult<Vec<_>>>()?;
+    show_process_output(ProcessOutput { jobs }, json_output)
+}
+
+/// Run one job and collect its results
+fn process_job(processor: &BatchProcessor, input_paths: &[PathBuf]) -> CliResult<ProcessJobOutput> {
+    let job_id = processor.job_id().unwrap_or_default().to_string();
+    info!("Processing {} files in job {job_id}", input_paths.len());
+    let result = processor
+        .process_files_detailed(input_paths)
+        .with_context(|| format!("Processing job {job_id} failed"))?;
+
+    for (path, e) in &result.failed {
+        error!("Failed to process {}: {e}", path.display());
+    }
+    Ok(ProcessJobOutput {
+        job_id,
+        failed: result.failed.into_iter().map(|(path, _)| path).collect(),
+        report: result.report,
+    })
+}
+
+/// Print the job results as JSON or a human readable summary
+fn show_process_output(output: ProcessOutput, json_output: bool) -> CliResult<()> {
+    if json_output {
+        let json = CliOutput::process_success(output)
+            .to_json()
+            .with_context(|| "serializing process output to JSON")?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    if output.jobs.is_empty() {
+        info!("No interrupted processing jobs to resume");
+    }
+    for job in &output.jobs {
+        info!(
+            "✓ Job {}: {} files processed, {} failed",
+            job.job_id, job.report.summary.files_processed, job.report.summary.files_failed
+        );
+        for file in &job.report.files {
+            info!("  {}", file.output_path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> ProcessOptions {
+        ProcessOptions {
+            mono: true,
+            output_dir: None,
+            naming_pattern: "{filename}_processed".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_process_with_nonexistent_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = run(
+            Some(dir.path().join("abop.db")),
+            vec![PathBuf::from("/nonexistent/book.wav")],
+            options(),
+            false,
+        );
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_resume_without_interrupted_jobs() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(resume(Some(dir.path().join("abop.db")), true).is_ok());
+    }
+}
@@ -0,0 +1,130 @@
+//! Listener profile command implementation
+//!
+//! Profiles let several people share one library while keeping their own
+//! progress, bookmarks and playback preferences. Other commands pick a
+//! profile with the global `--profile` flag.
+
+use crate::cli::ProfileOperations;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{CliOutput, ProfileOutput};
+use abop_core::db::Database;
+use abop_core::models::{DEFAULT_PROFILE_ID, Profile};
+use anyhow::Context;
+use log::{debug, info};
+use std::path::PathBuf;
+
+/// Execute a profile operation
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `operation` - The profile operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The profile does not exist
+/// - A new name is empty or already taken
+/// - The default profile is deleted
+pub fn run(
+    database_path: Option<PathBuf>,
+    operation: ProfileOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting profile operation: {operation:?}");
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let repo = db.profile_repository();
+
+    let output = match operation {
+        ProfileOperations::List => ProfileOutput::List {
+            profiles: repo.find_all().with_database_context("listing profiles")?,
+        },
+        ProfileOperations::Create { name } => ProfileOutput::Create {
+            profile: repo
+                .create(&name)
+                .with_database_context("creating profile")?,
+        },
+        ProfileOperations::Delete { name } => {
+            let profile = resolve_profile(&db, Some(&name))?;
+            repo.delete(&profile.id)
+                .with_database_context("deleting profile")?;
+            ProfileOutput::Delete { name: profile.name }
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::profile_success(output)
+            .to_json()
+            .with_context(|| "serializing profile results to JSON")?;
+        println!("{json}");
+    } else {
+        show_profile_output(&output);
+    }
+    Ok(())
+}
+
+/// Look up the profile selected with `--profile`, by ID or name
+///
+/// Without a selection this is the default profile.
+pub(crate) fn resolve_profile(db: &Database, profile: Option<&str>) -> CliResult<Profile> {
+    let repo = db.profile_repository();
+    let profile = profile.unwrap_or(DEFAULT_PROFILE_ID);
+    if let Some(found) = repo
+        .find_by_id(profile)
+        .with_database_context("looking up profile")?
+    {
+        return Ok(found);
+    }
+    repo.find_by_name(profile)
+        .with_database_context("looking up profile")?
+        .ok_or_else(|| anyhow::anyhow!("Profile does not exist: {profile}"))
+}
+
+/// Print a human readable profile summary
+fn show_profile_output(output: &ProfileOutput) {
+    match output {
+        ProfileOutput::List { profiles } => {
+            for profile in profiles {
+                if profile.is_default() {
+                    info!("{} (default)", profile.name);
+                } else {
+                    info!("{} ({})", profile.name, profile.id);
+                }
+            }
+        }
+        ProfileOutput::Create { profile } => info!("✓ Created profile {}", profile.name),
+        ProfileOutput::Delete { name } => info!("✓ Deleted profile {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("abop.db");
+        let db = Database::open(&database).unwrap();
+
+        let create = ProfileOperations::Create {
+            name: "Sam".to_string(),
+        };
+        run(Some(database.clone()), create, true).unwrap();
+        let sam = resolve_profile(&db, Some("sam")).unwrap();
+        assert_eq!(resolve_profile(&db, Some(&sam.id)).unwrap().name, "Sam");
+        assert!(resolve_profile(&db, None).unwrap().is_default());
+
+        let delete_default = ProfileOperations::Delete {
+            name: DEFAULT_PROFILE_ID.to_string(),
+        };
+        assert!(run(Some(database.clone()), delete_default, true).is_err());
+
+        let delete = ProfileOperations::Delete {
+            name: "Sam".to_string(),
+        };
+        run(Some(database), delete, true).unwrap();
+        assert!(resolve_profile(&db, Some("Sam")).is_err());
+    }
+}
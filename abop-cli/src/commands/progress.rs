@@ -0,0 +1,212 @@
+//! Listening progress command implementation
+//!
+//! Progress belongs to a listener profile, chosen with the global
+//! `--profile` flag, so setting a position for one listener leaves everyone
+//! else's untouched.
+
+use crate::cli::ProgressOperations;
+use crate::commands::collection::resolve_audiobooks;
+use crate::commands::profile::resolve_profile;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{AudiobookInfo, CliOutput, ProgressEntry, ProgressOutput};
+use abop_core::db::Database;
+use abop_core::models::Progress;
+use anyhow::Context;
+use log::{debug, info};
+use std::path::PathBuf;
+
+/// Execute a progress operation
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `profile` - Profile name or ID (uses the default profile if None)
+/// * `operation` - The progress operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The profile or audiobook does not exist
+pub fn run(
+    database_path: Option<PathBuf>,
+    profile: Option<String>,
+    operation: ProgressOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting progress operation: {operation:?}");
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let profile = resolve_profile(&db, profile.as_deref())?;
+    let repo = db.progress_repository();
+
+    let output = match operation {
+        ProgressOperations::List => {
+            let entries = repo
+                .find_all_for_profile(&profile.id)
+                .with_database_context("listing progress")?
+                .into_iter()
+                .map(|progress| progress_entry(&db, progress))
+                .collect::<CliResult<Vec<_>>>()?;
+            ProgressOutput::List {
+                profile: profile.name,
+                entries,
+            }
+        }
+        ProgressOperations::Set {
+            audiobook,
+            position,
+        } => {
+            let mut progress = find_or_new(&db, &profile.id, &audiobook)?;
+            progress.update_position(position);
+            repo.upsert(&progress)
+                .with_database_context("saving progress")?;
+            ProgressOutput::Update {
+                profile: profile.name,
+                entry: Box::new(progress_entry(&db, progress)?),
+            }
+        }
+        ProgressOperations::Complete { audiobook } => {
+            let mut progress = find_or_new(&db, &profile.id, &audiobook)?;
+            progress.mark_completed();
+            repo.upsert(&progress)
+                .with_database_context("saving progress")?;
+            ProgressOutput::Update {
+                profile: profile.name,
+                entry: Box::new(progress_entry(&db, progress)?),
+            }
+        }
+        ProgressOperations::Reset { audiobook } => {
+            let audiobook_id = resolve_audiobook(&db, &audiobook)?;
+            repo.delete_for_profile(&profile.id, &audiobook_id)
+                .with_database_context("clearing progress")?;
+            ProgressOutput::Reset {
+                profile: profile.name,
+                audiobook: find_audiobook(&db, &audiobook_id)?,
+            }
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::progress_success(output)
+            .to_json()
+            .with_context(|| "serializing progress results to JSON")?;
+        println!("{json}");
+    } else {
+        show_progress_output(&output);
+    }
+    Ok(())
+}
+
+fn resolve_audiobook(db: &Database, audiobook: &str) -> CliResult<String> {
+    let mut ids = resolve_audiobooks(db, &[audiobook.to_string()])?;
+    Ok(ids.remove(0))
+}
+
+fn find_audiobook(db: &Database, audiobook_id: &str) -> CliResult<AudiobookInfo> {
+    db.audiobook_repository()
+        .find_by_id(audiobook_id)
+        .with_database_context("looking up audiobook")?
+        .map(|audiobook| AudiobookInfo::from(&audiobook))
+        .ok_or_else(|| anyhow::anyhow!("Audiobook not found: {audiobook_id}"))
+}
+
+/// The profile's progress in an audiobook, or a fresh record at the start
+fn find_or_new(db: &Database, profile_id: &str, audiobook: &str) -> CliResult<Progress> {
+    let audiobook_id = resolve_audiobook(db, audiobook)?;
+    Ok(db
+        .progress_repository()
+        .find_for_profile(profile_id, &audiobook_id)
+        .with_database_context("looking up progress")?
+        .unwrap_or_else(|| Progress::new(&audiobook_id, 0).with_profile(profile_id)))
+}
+
+fn progress_entry(db: &Database, progress: Progress) -> CliResult<ProgressEntry> {
+    Ok(ProgressEntry {
+        audiobook: find_audiobook(db, &progress.audiobook_id)?,
+        progress,
+    })
+}
+
+fn format_position(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Print a human readable progress summary
+fn show_progress_output(output: &ProgressOutput) {
+    let show_entry = |entry: &ProgressEntry| {
+        if entry.progress.completed {
+            info!("{}: completed", entry.audiobook.title);
+        } else {
+            info!(
+                "{}: {}",
+                entry.audiobook.title,
+                format_position(entry.progress.position_seconds)
+            );
+        }
+    };
+    match output {
+        ProgressOutput::List { profile, entries } => {
+            if entries.is_empty() {
+                info!("No progress for {profile}");
+            }
+            entries.iter().for_each(show_entry);
+        }
+        ProgressOutput::Update { entry, .. } => show_entry(entry),
+        ProgressOutput::Reset { profile, audiobook } => {
+            info!("✓ Cleared progress of {} for {profile}", audiobook.title);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_is_per_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("abop.db");
+        let db = Database::open(&database).unwrap();
+        let library = db
+            .libraries()
+            .create("Books", dir.path().to_path_buf())
+            .unwrap();
+        let audiobook = abop_core::models::Audiobook::new(&library.id, dir.path().join("a.mp3"));
+        db.add_audiobook(&audiobook).unwrap();
+        let sam = db.profile_repository().create("Sam").unwrap();
+
+        let set = |position| ProgressOperations::Set {
+            audiobook: audiobook.id.clone(),
+            position,
+        };
+        run(
+            Some(database.clone()),
+            Some("Sam".to_string()),
+            set(600),
+            true,
+        )
+        .unwrap();
+        run(Some(database.clone()), None, set(60), true).unwrap();
+
+        let repo = db.progress_repository();
+        let position = |profile_id: &str| {
+            repo.find_for_profile(profile_id, &audiobook.id)
+                .unwrap()
+                .map(|progress| progress.position_seconds)
+        };
+        assert_eq!(position(&sam.id), Some(600));
+        assert_eq!(position(abop_core::models::DEFAULT_PROFILE_ID), Some(60));
+
+        let reset = ProgressOperations::Reset {
+            audiobook: audiobook.id.clone(),
+        };
+        run(Some(database), Some(sam.id.clone()), reset, true).unwrap();
+        assert_eq!(position(&sam.id), None);
+        assert_eq!(position(abop_core::models::DEFAULT_PROFILE_ID), Some(60));
+    }
+}
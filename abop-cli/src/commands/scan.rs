@@ -87,7 +87,7 @@ pub fn run(
 }
 
 /// Initialize the database connection
-fn initialize_database(database_path: Option<PathBuf>) -> CliResult<Database> {
+pub(crate) fn initialize_database(database_path: Option<PathBuf>) -> CliResult<Database> {
     match database_path {
         Some(db_path) => {
             info!("Using custom database: {db_path:?}");
@@ -101,7 +101,7 @@ fn initialize_database(database_path: Option<PathBuf>) -> CliResult<Database> {
 }
 
 /// Find existing library or create a new one
-fn find_or_create_library(
+pub(crate) fn find_or_create_library(
     db: &Database,
     library_path: &PathBuf,
 ) -> CliResult<abop_core::models::Library> {
@@ -247,7 +247,23 @@ fn output_json_results(
             scan_output.metrics = Some(metrics);
         }
         CliOutput::Success {
-            data: crate::output::OutputData::Database(_),
+            data:
+                crate::output::OutputData::Database(_)
+                | crate::output::OutputData::Verify(_)
+                | crate::output::OutputData::CheckAcx(_)
+                | crate::output::OutputData::Organize(_)
+                | crate::output::OutputData::Split(_)
+                | crate::output::OutputData::Process(_)
+                | crate::output::OutputData::Watch(_)
+                | crate::output::OutputData::Collection(_)
+                | crate::output::OutputData::Tag(_)
+                | crate::output::OutputData::Smart(_)
+                | crate::output::OutputData::Profile(_)
+                | crate::output::OutputData::Progress(_)
+                | crate::output::OutputData::Sync(_)
+                | crate::output::OutputData::Feed(_)
+                | crate::output::OutputData::Lookup(_)
+                | crate::output::OutputData::Sidecar(_),
         } => {
             log::warn!("Attempted to add scan metrics to database output - this shouldn't happen");
         }
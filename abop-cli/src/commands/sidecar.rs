@@ -0,0 +1,71 @@
+//! Sidecar export command implementation
+//!
+//! Writes the metadata ABOP holds for an audiobook next to its files so
+//! other players and tools pick up edits made here.
+
+use crate::commands::collection::resolve_audiobooks;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{CliOutput, SidecarOutput, SidecarWriteOutput};
+use abop_core::sidecar::{SidecarFormat, write_sidecars};
+use anyhow::Context;
+use log::info;
+use std::path::PathBuf;
+
+/// Write sidecars for audiobooks
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `audiobooks` - Audiobook IDs or paths
+/// * `formats` - Sidecar formats to write, every format when empty
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - An audiobook does not exist
+/// - A sidecar cannot be written
+pub fn run(
+    database_path: Option<PathBuf>,
+    audiobooks: &[String],
+    formats: &[SidecarFormat],
+    json_output: bool,
+) -> CliResult<()> {
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let formats = if formats.is_empty() {
+        &SidecarFormat::ALL[..]
+    } else {
+        formats
+    };
+
+    let mut written = Vec::new();
+    for id in resolve_audiobooks(&db, audiobooks)? {
+        let audiobook = db
+            .audiobook_repository()
+            .find_by_id(&id)
+            .with_database_context("looking up audiobook")?
+            .ok_or_else(|| anyhow::anyhow!("Audiobook not found: {id}"))?;
+        let files = write_sidecars(&audiobook, formats).with_context(|| {
+            format!("Failed to write sidecars for {}", audiobook.path.display())
+        })?;
+        written.push(SidecarWriteOutput {
+            audiobook_id: audiobook.id,
+            files,
+        });
+    }
+
+    let output = SidecarOutput { written };
+    if json_output {
+        let json = CliOutput::sidecar_success(output)
+            .to_json()
+            .with_context(|| "serializing sidecar output to JSON")?;
+        println!("{json}");
+    } else {
+        for entry in &output.written {
+            for file in &entry.files {
+                info!("✓ Wrote {}", file.display());
+            }
+        }
+    }
+    Ok(())
+}
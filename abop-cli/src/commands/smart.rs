@@ -0,0 +1,197 @@
+//! Smart collection command implementation
+//!
+//! This module manages smart collections: named filter rules whose members
+//! are the audiobooks that currently match, such as unfinished books longer
+//! than ten hours. Showing a smart collection re-evaluates its rule first.
+
+use crate::cli::SmartOperations;
+use crate::commands::profile::resolve_profile;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{AudiobookInfo, CliOutput, SmartOutput};
+use abop_core::db::Database;
+use abop_core::models::{Rule, SmartCollection};
+use anyhow::Context;
+use log::{debug, info};
+use std::path::PathBuf;
+
+/// Execute a smart collection operation
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `profile` - Profile whose progress new collections test (uses the default profile if None)
+/// * `operation` - The smart collection operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The smart collection or profile does not exist
+/// - A rule cannot be parsed
+/// - A new name is empty or already taken
+pub fn run(
+    database_path: Option<PathBuf>,
+    profile: Option<String>,
+    operation: SmartOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting smart collection operation: {operation:?}");
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let repo = db.smart_collection_repository();
+
+    let output = match operation {
+        SmartOperations::Create { name, rule } => {
+            let rule = parse_rule(&rule)?;
+            let profile = resolve_profile(&db, profile.as_deref())?;
+            SmartOutput::Update {
+                collection: repo
+                    .create_for_profile(&profile.id, &name, &rule)
+                    .with_database_context("creating smart collection")?,
+            }
+        }
+        SmartOperations::List => SmartOutput::List {
+            collections: repo
+                .find_all()
+                .with_database_context("listing smart collections")?,
+        },
+        SmartOperations::Show { name } => {
+            let collection = find_smart_collection(&db, &name)?;
+            let audiobooks = repo
+                .evaluate(&collection.id)
+                .with_database_context("evaluating smart collection")?;
+            SmartOutput::Show {
+                collection: find_smart_collection(&db, &name)?,
+                audiobooks: audiobooks.iter().map(AudiobookInfo::from).collect(),
+            }
+        }
+        SmartOperations::Edit { name, rule } => {
+            let rule = parse_rule(&rule)?;
+            let collection = find_smart_collection(&db, &name)?;
+            repo.update_rule(&collection.id, &rule)
+                .with_database_context("updating smart collection")?;
+            SmartOutput::Update {
+                collection: find_smart_collection(&db, &name)?,
+            }
+        }
+        SmartOperations::Delete { name } => {
+            let collection = find_smart_collection(&db, &name)?;
+            repo.delete(&collection.id)
+                .with_database_context("deleting smart collection")?;
+            SmartOutput::Delete {
+                name: collection.name,
+            }
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::smart_success(output)
+            .to_json()
+            .with_context(|| "serializing smart collection results to JSON")?;
+        println!("{json}");
+    } else {
+        show_smart_output(&output);
+    }
+    Ok(())
+}
+
+/// Parse rule text, reporting the syntax problem
+fn parse_rule(rule: &str) -> CliResult<Rule> {
+    Rule::parse(rule).map_err(|e| anyhow::anyhow!("Invalid rule '{rule}': {e}"))
+}
+
+/// Look up a smart collection by name
+fn find_smart_collection(db: &Database, name: &str) -> CliResult<SmartCollection> {
+    db.smart_collection_repository()
+        .find_by_name(name)
+        .with_database_context("looking up smart collection")?
+        .ok_or_else(|| anyhow::anyhow!("Smart collection does not exist: {name}"))
+}
+
+/// Print a human readable smart collection summary
+fn show_smart_output(output: &SmartOutput) {
+    match output {
+        SmartOutput::List { collections } => {
+            if collections.is_empty() {
+                info!("No smart collections");
+            }
+            for collection in collections {
+                info!(
+                    "{} ({} audiobooks): {}",
+                    collection.name, collection.audiobook_count, collection.rule
+                );
+            }
+        }
+        SmartOutput::Show {
+            collection,
+            audiobooks,
+        } => {
+            info!("{}: {}", collection.name, collection.rule);
+            for audiobook in audiobooks {
+                info!("  {} by {}", audiobook.title, audiobook.author);
+            }
+        }
+        SmartOutput::Update { collection } => info!(
+            "✓ {} now has {} audiobooks",
+            collection.name, collection.audiobook_count
+        ),
+        SmartOutput::Delete { name } => info!("✓ Deleted smart collection {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smart_collection_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("abop.db");
+        let db = Database::open(&database).unwrap();
+        let library = db
+            .libraries()
+            .create("Books", dir.path().to_path_buf())
+            .unwrap();
+        let mut audiobook =
+            abop_core::models::Audiobook::new(&library.id, dir.path().join("a.mp3"));
+        audiobook.title = Some("Dune".to_string());
+        audiobook.duration_seconds = Some(21 * 3600);
+        db.add_audiobook(&audiobook).unwrap();
+
+        let create = SmartOperations::Create {
+            name: "Long listens".to_string(),
+            rule: "duration > 10h".to_string(),
+        };
+        run(Some(database.clone()), None, create, true).unwrap();
+        assert_eq!(
+            find_smart_collection(&db, "long listens")
+                .unwrap()
+                .audiobook_count,
+            1
+        );
+
+        let invalid = SmartOperations::Edit {
+            name: "Long listens".to_string(),
+            rule: "duration contains 10h".to_string(),
+        };
+        let result = run(Some(database.clone()), None, invalid, true);
+        assert!(result.unwrap_err().to_string().contains("Invalid rule"));
+
+        let edit = SmartOperations::Edit {
+            name: "Long listens".to_string(),
+            rule: "duration > 30h".to_string(),
+        };
+        run(Some(database.clone()), None, edit, true).unwrap();
+        assert_eq!(
+            find_smart_collection(&db, "Long listens")
+                .unwrap()
+                .audiobook_count,
+            0
+        );
+
+        let delete = SmartOperations::Delete {
+            name: "Long listens".to_string(),
+        };
+        run(Some(database), None, delete, true).unwrap();
+        assert!(find_smart_collection(&db, "Long listens").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Chapter split command implementation
+//!
+//! This module cuts one long audio file into per-chapter files, taking the
+//! chapter boundaries from embedded chapters, a cue sheet or gaps of silence.
+
+use crate::error::CliResult;
+use crate::output::CliOutput;
+use abop_core::audio::processing::{ChapterSource, ChapterSplitter, SplitConfig, SplitReport};
+use anyhow::Context;
+use log::info;
+use std::path::PathBuf;
+
+/// Execute the split command
+///
+/// # Arguments
+/// * `input` - Audio file to split
+/// * `cue` - Cue sheet with the chapter boundaries
+/// * `silence` - Whether to find chapter boundaries from silence
+/// * `output_dir` - Directory for the parts
+/// * `template` - Filename template for the parts
+/// * `overwrite` - Whether existing part files may be replaced
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - The input file does not exist
+/// - The filename template is invalid
+/// - No chapters are found or a part cannot be written
+pub fn run(
+    input: PathBuf,
+    cue: Option<PathBuf>,
+    silence: bool,
+    output_dir: Option<PathBuf>,
+    template: String,
+    overwrite: bool,
+    json_output: bool,
+) -> CliResult<()> {
+    if !input.is_file() {
+        return Err(anyhow::anyhow!(
+            "Input file does not exist: {}",
+            input.display()
+        ));
+    }
+
+    let source = match (cue, silence) {
+        (Some(cue), _) => ChapterSource::CueSheet(cue),
+        (None, true) => ChapterSource::Silence,
+        (None, false) => ChapterSource::Auto,
+    };
+    let mut builder = SplitConfig::builder()
+        .with_source(source)
+        .with_filename_template(template)
+        .with_overwrite(overwrite);
+    if let Some(dir) = output_dir {
+        builder = builder.with_output_directory(dir);
+    }
+
+    let splitter = ChapterSplitter::new(builder.build()).context("Invalid split options")?;
+    let report = splitter
+        .split(&input)
+        .with_context(|| format!("Failed to split {}", input.display()))?;
+
+    if json_output {
+        let json = CliOutput::split_success(report)
+            .to_json()
+            .with_context(|| "serializing split report to JSON")?;
+        println!("{json}");
+    } else {
+        show_split_report(&report);
+    }
+
+    Ok(())
+}
+
+/// Print a human readable list of the written parts
+fn show_split_report(report: &SplitReport) {
+    info!(
+        "Split {} into {} parts using {:?} chapters",
+        report.input_path.display(),
+        report.parts.len(),
+        report.origin
+    );
+    for part in &report.parts {
+        info!(
+            "  {:>3}. {} ({:.1}s – {:.1}s) → {}",
+            part.track,
+            part.title,
+            part.start_secs,
+            part.end_secs,
+            part.output_path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_with_nonexistent_input() {
+        let result = run(
+            PathBuf::from("/nonexistent/book.m4b"),
+            None,
+            false,
+            None,
+            "{track:02} - {chapter}".to_string(),
+            false,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_split_rejects_template_without_placeholder() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("book.wav");
+        std::fs::write(&input, b"").unwrap();
+
+        let result = run(input, None, true, None, "part".to_string(), false, false);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid split options")
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Progress sync command implementation
+//!
+//! This module exchanges listening positions with other devices through a
+//! directory shared by a file synchronizer such as Syncthing. Every run writes
+//! this device's changes and merges those of the others.
+
+use crate::commands::profile::resolve_profile;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::CliOutput;
+use abop_core::sync::{ProgressSync, SyncOptions, SyncPolicy, SyncReport};
+use anyhow::Context;
+use log::{debug, info, warn};
+use std::path::PathBuf;
+
+/// Execute the sync command
+///
+/// # Arguments
+/// * `directory` - Shared directory holding the progress files of all devices
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `profile` - Profile name or ID (uses the default profile if None)
+/// * `policy` - How to choose between devices that disagree
+/// * `tolerance` - Position difference in seconds below which devices are not in conflict
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - The profile does not exist
+/// - The sync directory cannot be read or written
+pub fn run(
+    directory: PathBuf,
+    database_path: Option<PathBuf>,
+    profile: Option<String>,
+    policy: SyncPolicy,
+    tolerance: u64,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting progress sync through {}", directory.display());
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let profile = resolve_profile(&db, profile.as_deref())?;
+
+    let options = SyncOptions {
+        profile_id: profile.id,
+        policy,
+        conflict_tolerance_seconds: tolerance,
+        ..SyncOptions::new(&directory)
+    };
+    let report = ProgressSync::new(options)
+        .run(&db)
+        .with_context(|| format!("Failed to sync through {}", directory.display()))?;
+
+    if json_output {
+        let json = CliOutput::sync_success(report)
+            .to_json()
+            .with_context(|| "serializing sync report to JSON")?;
+        println!("{json}");
+    } else {
+        show_sync_report(&report);
+    }
+    Ok(())
+}
+
+fn format_position(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Print a human readable sync summary
+fn show_sync_report(report: &SyncReport) {
+    info!(
+        "✓ Synced {} as device {} ({}): {} changes written, {} audiobooks updated",
+        report.profile, report.device_id, report.policy, report.exported, report.applied
+    );
+    if report.unmatched > 0 {
+        info!(
+            "{} books from other devices are not in this library",
+            report.unmatched
+        );
+    }
+    for conflict in &report.conflicts {
+        let title = conflict.title.as_deref().unwrap_or(&conflict.content_hash);
+        warn!(
+            "Conflict in {title}: kept {} from {}",
+            format_position(conflict.chosen.position_seconds),
+            conflict.chosen.device_id
+        );
+        for other in &conflict.competing {
+            warn!(
+                "  {} had {}{}",
+                other.device_id,
+                format_position(other.position_seconds),
+                if other.completed { " (finished)" } else { "" }
+            );
+        }
+    }
+    for warning in &report.warnings {
+        warn!("{warning}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abop_core::db::Database;
+    use abop_core::models::{Audiobook, Progress};
+
+    #[test]
+    fn test_sync_moves_progress_between_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        let book = dir.path().join("book.m4b");
+        std::fs::write(&book, b"recording").unwrap();
+
+        let mut databases = Vec::new();
+        for name in ["laptop.db", "phone.db"] {
+            let database = dir.path().join(name);
+            let db = Database::open(&database).unwrap();
+            let library = db
+                .libraries()
+                .create(name, dir.path().to_path_buf())
+                .unwrap();
+            let audiobook = Audiobook::new(&library.id, &book);
+            db.add_audiobook(&audiobook).unwrap();
+            databases.push((database, db, audiobook.id));
+        }
+
+        let (laptop, laptop_db, laptop_book) = &databases[0];
+        laptop_db
+            .progress_repository()
+            .upsert(&Progress::new(laptop_book, 1800))
+            .unwrap();
+        let policy = SyncPolicy::LastWriterWins;
+        run(shared.clone(), Some(laptop.clone()), None, policy, 30, true).unwrap();
+
+        let (phone, phone_db, phone_book) = &databases[1];
+        run(shared, Some(phone.clone()), None, policy, 30, true).unwrap();
+        let synced = phone_db
+            .progress_repository()
+            .find_by_audiobook(phone_book)
+            .unwrap()
+            .unwrap();
+        assert_eq!(synced.position_seconds, 1800);
+    }
+}
@@ -0,0 +1,135 @@
+//! Tag command implementation
+//!
+//! This module adds and removes free-form tags on audiobooks given by ID or
+//! file path, or on the current library selection with `--selected`.
+
+use crate::cli::TagOperations;
+use crate::commands::collection::resolve_audiobooks;
+use crate::commands::scan::initialize_database;
+use crate::error::{CliResult, CliResultExt};
+use crate::output::{AudiobookInfo, CliOutput, TagOutput};
+use anyhow::Context;
+use log::{debug, info};
+use std::path::PathBuf;
+
+/// Execute a tag operation
+///
+/// # Arguments
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `operation` - The tag operation to perform
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database connection fails
+/// - An audiobook does not exist
+/// - The tag name is empty
+pub fn run(
+    database_path: Option<PathBuf>,
+    operation: TagOperations,
+    json_output: bool,
+) -> CliResult<()> {
+    debug!("Starting tag operation: {operation:?}");
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let repo = db.tag_repository();
+
+    let output = match operation {
+        TagOperations::List => TagOutput::List {
+            tags: repo.find_all().with_database_context("listing tags")?,
+        },
+        TagOperations::Show { tag } => {
+            let audiobooks = repo
+                .find_audiobooks(&tag)
+                .with_database_context("loading tagged audiobooks")?;
+            TagOutput::Show {
+                tag,
+                audiobooks: audiobooks.iter().map(AudiobookInfo::from).collect(),
+            }
+        }
+        TagOperations::Add {
+            tag,
+            audiobooks,
+            selected,
+        } => {
+            let ids = resolve_audiobooks(&db, &audiobooks)?;
+            let mut changed = repo
+                .tag_audiobooks(&tag, &ids)
+                .with_database_context("tagging audiobooks")?;
+            if selected {
+                changed += repo
+                    .tag_selected(&tag)
+                    .with_database_context("tagging selection")?;
+            }
+            TagOutput::Update { tag, changed }
+        }
+        TagOperations::Remove {
+            tag,
+            audiobooks,
+            selected,
+        } => {
+            let ids = resolve_audiobooks(&db, &audiobooks)?;
+            let mut changed = repo
+                .untag_audiobooks(&tag, &ids)
+                .with_database_context("untagging audiobooks")?;
+            if selected {
+                changed += repo
+                    .untag_selected(&tag)
+                    .with_database_context("untagging selection")?;
+            }
+            TagOutput::Update { tag, changed }
+        }
+    };
+
+    if json_output {
+        let json = CliOutput::tag_success(output)
+            .to_json()
+            .with_context(|| "serializing tag results to JSON")?;
+        println!("{json}");
+    } else {
+        show_tag_output(&output);
+    }
+    Ok(())
+}
+
+/// Print a human readable tag summary
+fn show_tag_output(output: &TagOutput) {
+    match output {
+        TagOutput::List { tags } => {
+            if tags.is_empty() {
+                info!("No tags");
+            }
+            for tag in tags {
+                info!("{} ({} audiobooks)", tag.name, tag.audiobook_count);
+            }
+        }
+        TagOutput::Show { tag, audiobooks } => {
+            info!("{tag}: {} audiobooks", audiobooks.len());
+            for audiobook in audiobooks {
+                info!("  {} by {}", audiobook.title, audiobook.author);
+            }
+        }
+        TagOutput::Update { tag, changed } => info!("✓ {tag}: {changed} audiobooks changed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_requires_existing_audiobooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let operation = TagOperations::Add {
+            tag: "Classic".to_string(),
+            audiobooks: vec!["no-such-book".to_string()],
+            selected: false,
+        };
+        let result = run(Some(dir.path().join("abop.db")), operation, true);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Audiobook not found")
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Audio integrity verification command implementation
+//!
+//! This module fully decodes every audiobook file in the database, stores the
+//! resulting health status and reports corrupt or truncated files together with
+//! repair suggestions.
+
+use crate::commands::collection::find_collection;
+use crate::error::{CliResult, CliResultExt, validate_existing_database_path};
+use crate::output::{CliOutput, FileHealthInfo, VerifyOutput};
+use abop_core::audio::{HealthStatus, IntegrityVerifier};
+use abop_core::db::Database;
+use abop_core::models::Audiobook;
+use abop_core::validation::{AudioHealthValidator, ValidationConfig, ValidationResult};
+use anyhow::Context;
+use log::{debug, info, warn};
+use std::path::{Path, PathBuf};
+
+/// Execute the verify command
+///
+/// # Arguments
+/// * `database_path` - Path to the database file
+/// * `library_path` - Optional library path to restrict verification to
+/// * `collection` - Optional collection name to restrict verification to
+/// * `problems_only` - Whether to omit healthy files from the report
+/// * `json_output` - Whether to output results in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - Database path is invalid
+/// - Database connection fails
+/// - The requested library or collection does not exist
+/// - Health status cannot be stored
+pub fn run(
+    database_path: PathBuf,
+    library_path: Option<PathBuf>,
+    collection: Option<String>,
+    problems_only: bool,
+    json_output: bool,
+) -> CliResult<()> {
+    info!("Verifying audio files in: {database_path:?}");
+
+    validate_existing_database_path(&database_path)?;
+    let db = Database::open(&database_path).with_database_context("opening for verification")?;
+
+    let audiobooks = match collection {
+        Some(name) => {
+            let collection = find_collection(&db, &name)?;
+            db.collection_repository()
+                .find_audiobooks(&collection.id)
+                .with_database_context("retrieving audiobooks for verification")?
+        }
+        None => audiobooks_to_verify(&db, library_path.as_deref())?,
+    };
+    info!("Verifying {} audio files", audiobooks.len());
+
+    let verifier = IntegrityVerifier::default();
+    let validator = AudioHealthValidator::new(&ValidationConfig::default())
+        .with_integrity_config(*verifier.config());
+    let health_repo = db.file_health_repository();
+
+    let mut verify = VerifyOutput::default();
+    for audiobook in &audiobooks {
+        debug!("Verifying {}", audiobook.path.display());
+        let report = verifier.verify(&audiobook.path);
+
+        health_repo
+            .upsert(&audiobook.id, &report)
+            .with_database_context("storing file health")?;
+
+        let mut validation = ValidationResult::new();
+        validator.validate_integrity_report(&report, &mut validation);
+
+        verify.record(report.status);
+        if report.status.is_problem() {
+            warn!("{}: {}", report.status, audiobook.path.display());
+        }
+        if problems_only && !report.status.is_problem() {
+            continue;
+        }
+        verify
+            .files
+            .push(FileHealthInfo::new(audiobook, report, &validation));
+    }
+
+    if json_output {
+        let json = CliOutput::verify_success(verify)
+            .to_json()
+            .with_context(|| "serializing verify results to JSON")?;
+        println!("{json}");
+    } else {
+        show_verify_results(&verify);
+    }
+
+    Ok(())
+}
+
+/// Collect the audiobooks to verify, optionally restricted to one library
+fn audiobooks_to_verify(db: &Database, library_path: Option<&Path>) -> CliResult<Vec<Audiobook>> {
+    match library_path {
+        Some(path) => {
+            let library = db
+                .libraries()
+                .find_by_path(path)
+                .with_database_context("looking up library")?
+                .ok_or_else(|| anyhow::anyhow!("Library does not exist: {}", path.display()))?;
+            db.get_audiobooks_in_library(&library.id)
+                .with_database_context("retrieving audiobooks for verification")
+        }
+        None => db
+            .get_all_audiobooks()
+            .with_database_context("retrieving audiobooks for verification"),
+    }
+}
+
+/// Print a human readable verification summary
+fn show_verify_results(verify: &VerifyOutput) {
+    for file in &verify.files {
+        if file.report.status == HealthStatus::Healthy {
+            continue;
+        }
+        info!("{} [{}]", file.report.path.display(), file.report.status);
+        for suggestion in &file.suggestions {
+            info!("  → {suggestion}");
+        }
+    }
+
+    info!(
+        "Verified {} files: {} healthy, {} degraded, {} corrupt, {} unreadable",
+        verify.total_files, verify.healthy, verify.degraded, verify.corrupt, verify.unreadable
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_with_nonexistent_database() {
+        let result = run(
+            PathBuf::from("/nonexistent/database.db"),
+            None,
+            None,
+            false,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Library watch command implementation
+//!
+//! This module keeps the database in sync with one or more library
+//! directories by applying filesystem changes as they happen.
+
+use crate::commands::scan::{find_or_create_library, initialize_database};
+use crate::error::{CliResult, CliResultExt, validate_library_path};
+use crate::output::CliOutput;
+use abop_core::scanner::{LibraryWatcher, WatchConfig, WatchUpdate};
+use anyhow::Context;
+use log::{info, warn};
+use std::path::PathBuf;
+
+/// Execute the watch command
+///
+/// Runs until the process is interrupted. Each applied change set is printed
+/// as it happens; with JSON output enabled every update is one JSON object
+/// per line.
+///
+/// # Arguments
+/// * `library_paths` - Library directories to watch
+/// * `database_path` - Optional path to database file (uses centralized app DB if None)
+/// * `force_polling` - Whether to poll instead of using native notifications
+/// * `json_output` - Whether to output updates in JSON format
+///
+/// # Errors
+/// Returns an error if:
+/// - A library path doesn't exist or isn't a directory
+/// - Database initialization fails
+/// - No filesystem watcher can be created
+pub fn run(
+    library_paths: Vec<PathBuf>,
+    database_path: Option<PathBuf>,
+    force_polling: bool,
+    json_output: bool,
+) -> CliResult<()> {
+    for path in &library_paths {
+        validate_library_path(path)?;
+    }
+
+    let db = initialize_database(database_path).with_database_context("initialization")?;
+    let libraries = library_paths
+        .iter()
+        .map(|path| find_or_create_library(&db, path))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let config = WatchConfig {
+        force_polling,
+        ..WatchConfig::default()
+    };
+    let watcher = LibraryWatcher::new(db, libraries).with_config(config);
+
+    info!(
+        "Watching {} libraries, press Ctrl+C to stop",
+        library_paths.len()
+    );
+    watcher
+        .run(|update| {
+            if json_output {
+                match CliOutput::watch_update(&update).to_json() {
+                    Ok(json) => println!("{json}"),
+                    Err(e) => warn!("Failed to serialize watch update: {e}"),
+                }
+            } else {
+                show_watch_update(&update);
+            }
+        })
+        .context("Watching libraries failed")?;
+
+    Ok(())
+}
+
+/// Print a human readable summary of one update
+fn show_watch_update(update: &WatchUpdate) {
+    for audiobook in &update.updated {
+        info!("Updated {}", audiobook.path.display());
+    }
+    for path in &update.removed {
+        info!("Removed {}", path.display());
+    }
+    if update.errors > 0 {
+        warn!(
+            "{} files in library {} could not be processed",
+            update.errors, update.library_id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_with_nonexistent_library() {
+        let result = run(
+            vec![PathBuf::from("/nonexistent/library")],
+            None,
+            false,
+            false,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -3,6 +3,15 @@
 //! This module provides structured output formats for machine consumption.
 //! All output structures are designed to be stable and backwards-compatible.
 
+use abop_core::audio::processing::{AcxReport, BatchProcessingReport, SplitReport};
+use abop_core::audio::{HealthStatus, IntegrityReport};
+use abop_core::library::{DuplicateCluster, OrganizePlan};
+use abop_core::models::{Collection, Profile, Progress, SmartCollection, Tag};
+use abop_core::provider::MetadataReview;
+use abop_core::scanner::WatchUpdate;
+use abop_core::sidecar::MetadataField;
+use abop_core::sync::SyncReport;
+use abop_core::validation::ValidationResult;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -28,6 +37,51 @@ pub enum OutputData {
     /// Database operation results
     #[serde(rename = "database")]
     Database(DatabaseOutput),
+    /// Audio integrity verification results
+    #[serde(rename = "verify")]
+    Verify(VerifyOutput),
+    /// ACX compliance check results
+    #[serde(rename = "check_acx")]
+    CheckAcx(AcxReport),
+    /// Library organization plan and results
+    #[serde(rename = "organize")]
+    Organize(OrganizeOutput),
+    /// Chapter split results
+    #[serde(rename = "split")]
+    Split(SplitReport),
+    /// Audio processing job results
+    #[serde(rename = "process")]
+    Process(ProcessOutput),
+    /// Changes applied by watch mode
+    #[serde(rename = "watch")]
+    Watch(WatchOutput),
+    /// Collection operation results
+    #[serde(rename = "collection")]
+    Collection(CollectionOutput),
+    /// Tag operation results
+    #[serde(rename = "tag")]
+    Tag(TagOutput),
+    /// Smart collection operation results
+    #[serde(rename = "smart")]
+    Smart(SmartOutput),
+    /// Listener profile operation results
+    #[serde(rename = "profile")]
+    Profile(ProfileOutput),
+    /// Listening progress operation results
+    #[serde(rename = "progress")]
+    Progress(ProgressOutput),
+    /// Progress sync results
+    #[serde(rename = "sync")]
+    Sync(SyncReport),
+    /// Podcast feed generation results
+    #[serde(rename = "feed")]
+    Feed(FeedOutput),
+    /// Online metadata lookup results
+    #[serde(rename = "lookup")]
+    Lookup(Box<LookupOutput>),
+    /// Sidecar export results
+    #[serde(rename = "sidecar")]
+    Sidecar(SidecarOutput),
 }
 
 /// Scan operation output
@@ -63,6 +117,12 @@ pub enum DatabaseOutput {
     /// Database cleanup result
     #[serde(rename = "clean")]
     Clean { libraries_validated: usize },
+    /// Duplicate detection result
+    #[serde(rename = "duplicates")]
+    Duplicates {
+        cluster_count: usize,
+        clusters: Vec<DuplicateCluster>,
+    },
 }
 
 /// Library information
@@ -103,6 +163,227 @@ pub struct ScanMetrics {
     pub files_per_second: f64,
 }
 
+/// Audio integrity verification output
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VerifyOutput {
+    /// Number of files that were verified
+    pub total_files: usize,
+    pub healthy: usize,
+    pub degraded: usize,
+    pub corrupt: usize,
+    pub unreadable: usize,
+    /// Per-file reports (healthy files are omitted with `--problems-only`)
+    pub files: Vec<FileHealthInfo>,
+}
+
+/// Library organize output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizeOutput {
+    /// Whether the plan was only previewed
+    pub dry_run: bool,
+    /// Number of files that were moved
+    pub moved: usize,
+    /// The planned moves and collisions
+    pub plan: OrganizePlan,
+}
+
+/// Verification result for a single audiobook file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileHealthInfo {
+    pub audiobook_id: String,
+    pub title: String,
+    #[serde(flatten)]
+    pub report: IntegrityReport,
+    /// Repair suggestions derived from the report
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub suggestions: Vec<String>,
+}
+
+/// Changes applied to one library in watch mode
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchOutput {
+    pub library_id: String,
+    /// Audiobooks that were added or re-extracted
+    pub updated: Vec<AudiobookInfo>,
+    /// Paths of audiobooks that were removed
+    pub removed: Vec<PathBuf>,
+    pub errors: usize,
+}
+
+/// Collection operation output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command")]
+pub enum CollectionOutput {
+    /// All collections
+    #[serde(rename = "list")]
+    List { collections: Vec<Collection> },
+    /// A collection and its audiobooks in order
+    #[serde(rename = "show")]
+    Show {
+        collection: Collection,
+        audiobooks: Vec<AudiobookInfo>,
+    },
+    /// A collection that was created or whose audiobooks changed
+    #[serde(rename = "update")]
+    Update {
+        collection: Collection,
+        /// Number of audiobooks added or removed
+        changed: usize,
+    },
+    /// A collection that was deleted
+    #[serde(rename = "delete")]
+    Delete { name: String },
+}
+
+/// Tag operation output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command")]
+pub enum TagOutput {
+    /// All tags in use
+    #[serde(rename = "list")]
+    List { tags: Vec<Tag> },
+    /// The audiobooks with a tag
+    #[serde(rename = "show")]
+    Show {
+        tag: String,
+        audiobooks: Vec<AudiobookInfo>,
+    },
+    /// Audiobooks that were tagged or untagged
+    #[serde(rename = "update")]
+    Update {
+        tag: String,
+        /// Number of audiobooks tagged or untagged
+        changed: usize,
+    },
+}
+
+/// Smart collection operation output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command")]
+pub enum SmartOutput {
+    /// All smart collections
+    #[serde(rename = "list")]
+    List { collections: Vec<SmartCollection> },
+    /// A smart collection and its audiobooks after re-evaluation
+    #[serde(rename = "show")]
+    Show {
+        collection: SmartCollection,
+        audiobooks: Vec<AudiobookInfo>,
+    },
+    /// A smart collection that was created or whose rule changed
+    #[serde(rename = "update")]
+    Update { collection: SmartCollection },
+    /// A smart collection that was deleted
+    #[serde(rename = "delete")]
+    Delete { name: String },
+}
+
+/// Listener profile operation output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command")]
+pub enum ProfileOutput {
+    /// All profiles
+    #[serde(rename = "list")]
+    List { profiles: Vec<Profile> },
+    /// A profile that was created
+    #[serde(rename = "create")]
+    Create { profile: Profile },
+    /// A profile that was deleted with its progress and bookmarks
+    #[serde(rename = "delete")]
+    Delete { name: String },
+}
+
+/// Progress of one audiobook for JSON output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgressEntry {
+    pub audiobook: AudiobookInfo,
+    pub progress: Progress,
+}
+
+/// Listening progress operation output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command")]
+pub enum ProgressOutput {
+    /// Progress of a profile, most recently played first
+    #[serde(rename = "list")]
+    List {
+        profile: String,
+        entries: Vec<ProgressEntry>,
+    },
+    /// Progress that was set or marked completed
+    #[serde(rename = "update")]
+    Update {
+        profile: String,
+        entry: Box<ProgressEntry>,
+    },
+    /// Progress that was cleared
+    #[serde(rename = "reset")]
+    Reset {
+        profile: String,
+        audiobook: AudiobookInfo,
+    },
+}
+
+/// Audio processing output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessOutput {
+    /// Persisted jobs that were run
+    pub jobs: Vec<ProcessJobOutput>,
+}
+
+/// Result of one persisted processing job
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessJobOutput {
+    /// ID of the persisted job
+    pub job_id: String,
+    /// Files that could not be processed
+    pub failed: Vec<PathBuf>,
+    /// Before/after measurements of the processed files
+    pub report: BatchProcessingReport,
+}
+
+/// Podcast feed generation output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedOutput {
+    /// Feed title
+    pub title: String,
+    /// File the feed was written to
+    pub output: PathBuf,
+    /// Number of episodes in the feed
+    pub episodes: usize,
+    /// Number of cover images written next to the feed
+    pub covers: usize,
+}
+
+/// Online metadata lookup output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LookupOutput {
+    /// The audiobook looked up
+    pub audiobook: AudiobookInfo,
+    /// The provider's best match, if any scored high enough
+    pub review: Option<MetadataReview>,
+    /// Match score of the record under review
+    pub score: Option<f32>,
+    /// Fields written to the database
+    pub applied: Vec<MetadataField>,
+}
+
+/// Sidecar export output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SidecarOutput {
+    /// Sidecars written per audiobook
+    pub written: Vec<SidecarWriteOutput>,
+}
+
+/// Sidecars written for one audiobook
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SidecarWriteOutput {
+    /// ID of the audiobook
+    pub audiobook_id: String,
+    /// Files written
+    pub files: Vec<PathBuf>,
+}
+
 /// Error output structure
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorOutput {
@@ -165,6 +446,127 @@ impl CliOutput {
         }
     }
 
+    /// Create a successful duplicate detection result
+    pub fn database_duplicates_success(clusters: Vec<DuplicateCluster>) -> Self {
+        let cluster_count = clusters.len();
+        Self::Success {
+            data: OutputData::Database(DatabaseOutput::Duplicates {
+                cluster_count,
+                clusters,
+            }),
+        }
+    }
+
+    /// Create a successful verify result
+    pub fn verify_success(verify: VerifyOutput) -> Self {
+        Self::Success {
+            data: OutputData::Verify(verify),
+        }
+    }
+
+    /// Create a successful ACX compliance check result
+    pub fn check_acx_success(report: AcxReport) -> Self {
+        Self::Success {
+            data: OutputData::CheckAcx(report),
+        }
+    }
+
+    /// Create a successful organize result
+    pub fn organize_success(organize: OrganizeOutput) -> Self {
+        Self::Success {
+            data: OutputData::Organize(organize),
+        }
+    }
+
+    /// Create a successful chapter split result
+    pub fn split_success(report: SplitReport) -> Self {
+        Self::Success {
+            data: OutputData::Split(report),
+        }
+    }
+
+    /// Create a successful audio processing result
+    pub fn process_success(output: ProcessOutput) -> Self {
+        Self::Success {
+            data: OutputData::Process(output),
+        }
+    }
+
+    /// Create a watch update result
+    pub fn watch_update(update: &WatchUpdate) -> Self {
+        Self::Success {
+            data: OutputData::Watch(WatchOutput {
+                library_id: update.library_id.clone(),
+                updated: update.updated.iter().map(AudiobookInfo::from).collect(),
+                removed: update.removed.clone(),
+                errors: update.errors,
+            }),
+        }
+    }
+
+    /// Create a successful collection operation result
+    pub fn collection_success(collection: CollectionOutput) -> Self {
+        Self::Success {
+            data: OutputData::Collection(collection),
+        }
+    }
+
+    /// Create a successful tag operation result
+    pub fn tag_success(tag: TagOutput) -> Self {
+        Self::Success {
+            data: OutputData::Tag(tag),
+        }
+    }
+
+    /// Create a successful smart collection operation result
+    pub fn smart_success(smart: SmartOutput) -> Self {
+        Self::Success {
+            data: OutputData::Smart(smart),
+        }
+    }
+
+    /// Create a successful profile operation result
+    pub fn profile_success(profile: ProfileOutput) -> Self {
+        Self::Success {
+            data: OutputData::Profile(profile),
+        }
+    }
+
+    /// Create a successful progress operation result
+    pub fn progress_success(progress: ProgressOutput) -> Self {
+        Self::Success {
+            data: OutputData::Progress(progress),
+        }
+    }
+
+    /// Create a successful progress sync result
+    pub fn sync_success(report: SyncReport) -> Self {
+        Self::Success {
+            data: OutputData::Sync(report),
+        }
+    }
+
+    /// Create a successful feed generation result
+    pub fn feed_success(feed: FeedOutput) -> Self {
+        Self::Success {
+            data: OutputData::Feed(feed),
+        }
+    }
+
+    /// Create a successful metadata lookup result
+    pub fn lookup_success(lookup: LookupOutput) -> Self {
+        Self::Success {
+            data: OutputData::Lookup(Box::new(lookup)),
+        }
+    }
+
+    /// Create a successful sidecar export result
+    pub fn sidecar_success(sidecar: SidecarOutput) -> Self {
+        Self::Success {
+            data: OutputData::Sidecar(sidecar),
+        }
+    }
+
     /// Create an error result
     pub fn error(message: String, error_type: String, context: Option<Vec<String>>) -> Self {
         Self::Error {
@@ -249,6 +651,42 @@ impl From<&abop_core::models::Audiobook> for AudiobookInfo {
     }
 }
 
+impl VerifyOutput {
+    /// Count a verified file towards the status totals
+    pub fn record(&mut self, status: HealthStatus) {
+        self.total_files += 1;
+        match status {
+            HealthStatus::Healthy => self.healthy += 1,
+            HealthStatus::Degraded => self.degraded += 1,
+            HealthStatus::Corrupt => self.corrupt += 1,
+            HealthStatus::Unreadable => self.unreadable += 1,
+        }
+    }
+}
+
+impl FileHealthInfo {
+    /// Create file health info from a report and the validation issues it produced
+    pub fn new(
+        audiobook: &abop_core::models::Audiobook,
+        report: IntegrityReport,
+        validation: &ValidationResult,
+    ) -> Self {
+        Self {
+            audiobook_id: audiobook.id.clone(),
+            title: audiobook
+                .title
+                .clone()
+                .unwrap_or_else(|| "Unknown Title".to_string()),
+            report,
+            suggestions: validation
+                .issues
+                .iter()
+                .filter_map(|issue| issue.suggestion.clone())
+                .collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +733,40 @@ mod tests {
         assert!(json.contains("TestError"));
     }
 
+    #[test]
+    fn test_check_acx_serialization() {
+        let checker = abop_core::audio::processing::AcxChecker::default();
+        let report = checker.check_files(&["/nonexistent/chapter01.mp3"]);
+        let output = CliOutput::check_acx_success(report);
+
+        let json = output.to_json().expect("Should serialize to JSON");
+        assert!(json.contains("success"));
+        assert!(json.contains("check_acx"));
+        assert!(json.contains("chapter01.mp3"));
+        assert!(json.contains("\"passed\":false"));
+    }
+
+    #[test]
+    fn test_split_serialization() {
+        use abop_core::audio::processing::{ChapterOrigin, SplitPart};
+
+        let report = SplitReport {
+            input_path: PathBuf::from("/book/book.m4b"),
+            origin: ChapterOrigin::CueSheet,
+            parts: vec![SplitPart {
+                track: 1,
+                title: "Opening".to_string(),
+                start_secs: 0.0,
+                end_secs: 600.0,
+                output_path: PathBuf::from("/book/book/01 - Opening.wav"),
+            }],
+        };
+        let json = CliOutput::split_success(report).to_json().unwrap();
+        assert!(json.contains("\"operation\":\"split\""));
+        assert!(json.contains("\"origin\":\"cue_sheet\""));
+        assert!(json.contains("01 - Opening.wav"));
+    }
+
     #[test]
     fn test_database_stats_serialization() {
         let output = CliOutput::database_stats_success(42, 3);
@@ -203,6 +203,7 @@ mod integration_tests {
             verbose: true,
             debug: false,
             json: false,
+            profile: None,
             command: Commands::Scan {
                 library: PathBuf::from("/test"),
                 database: None,
@@ -0,0 +1,206 @@
+//! Speaker layouts of multichannel audio
+//!
+//! Channel layouts describe which speaker each interleaved channel feeds.
+//! Layouts are detected from Symphonia channel masks and fall back to the
+//! conventional layout for a channel count when no mask is available.
+
+use serde::{Deserialize, Serialize};
+use symphonia::core::audio::Channels;
+
+/// Speaker positions understood by the channel mixer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Speaker {
+    /// Front left
+    FrontLeft,
+    /// Front right
+    FrontRight,
+    /// Front centre
+    FrontCenter,
+    /// Low-frequency effects
+    LowFrequency,
+    /// Rear (back) left
+    RearLeft,
+    /// Rear (back) right
+    RearRight,
+    /// Side left
+    SideLeft,
+    /// Side right
+    SideRight,
+}
+
+/// Standard channel layouts in interleaved (WAV/Symphonia) channel order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChannelLayout {
+    /// Single centre channel
+    Mono,
+    /// Left and right
+    Stereo,
+    /// Left, right and LFE
+    TwoPointOne,
+    /// Left, right, centre, LFE and two surround channels
+    FivePointOne,
+    /// Left, right, centre, LFE, rear pair and side pair
+    SevenPointOne,
+}
+
+impl ChannelLayout {
+    /// Speakers in interleaved channel order
+    #[must_use]
+    pub const fn speakers(self) -> &'static [Speaker] {
+        use Speaker::{
+            FrontCenter, FrontLeft, FrontRight, LowFrequency, RearLeft, RearRight, SideLeft,
+            SideRight,
+        };
+        match self {
+            Self::Mono => &[FrontCenter],
+            Self::Stereo => &[FrontLeft, FrontRight],
+            Self::TwoPointOne => &[FrontLeft, FrontRight, LowFrequency],
+            Self::FivePointOne => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                LowFrequency,
+                SideLeft,
+                SideRight,
+            ],
+            Self::SevenPointOne => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                LowFrequency,
+                RearLeft,
+                RearRight,
+                SideLeft,
+                SideRight,
+            ],
+        }
+    }
+
+    /// Number of interleaved channels
+    #[must_use]
+    pub const fn channel_count(self) -> u16 {
+        match self {
+            Self::Mono => 1,
+            Self::Stereo => 2,
+            Self::TwoPointOne => 3,
+            Self::FivePointOne => 6,
+            Self::SevenPointOne => 8,
+        }
+    }
+
+    /// Conventional layout for a channel count, if there is one
+    #[must_use]
+    pub const fn from_channel_count(channels: u16) -> Option<Self> {
+        match channels {
+            1 => Some(Self::Mono),
+            2 => Some(Self::Stereo),
+            3 => Some(Self::TwoPointOne),
+            6 => Some(Self::FivePointOne),
+            8 => Some(Self::SevenPointOne),
+            _ => None,
+        }
+    }
+
+    /// Layout described by a Symphonia channel mask
+    ///
+    /// 5.1 is accepted with either rear or side surrounds, both of which map
+    /// to the surround pair. Unknown masks fall back to the channel count.
+    #[must_use]
+    pub fn from_symphonia(channels: Channels) -> Option<Self> {
+        let front = Channels::FRONT_LEFT | Channels::FRONT_RIGHT;
+        let centre = Channels::FRONT_CENTRE | Channels::LFE1;
+        let rear = Channels::REAR_LEFT | Channels::REAR_RIGHT;
+        let side = Channels::SIDE_LEFT | Channels::SIDE_RIGHT;
+
+        if channels == Channels::FRONT_LEFT || channels == Channels::FRONT_CENTRE {
+            Some(Self::Mono)
+        } else if channels == front {
+            Some(Self::Stereo)
+        } else if channels == front | Channels::LFE1 {
+            Some(Self::TwoPointOne)
+        } else if channels == front | centre | rear || channels == front | centre | side {
+            Some(Self::FivePointOne)
+        } else if channels == front | centre | rear | side {
+            Some(Self::SevenPointOne)
+        } else {
+            u16::try_from(channels.count())
+                .ok()
+                .and_then(Self::from_channel_count)
+        }
+    }
+
+    /// Index of a speaker in the interleaved frame
+    #[must_use]
+    pub fn position(self, speaker: Speaker) -> Option<usize> {
+        self.speakers().iter().position(|s| *s == speaker)
+    }
+}
+
+impl std::fmt::Display for ChannelLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Mono => "mono",
+            Self::Stereo => "stereo",
+            Self::TwoPointOne => "2.1",
+            Self::FivePointOne => "5.1",
+            Self::SevenPointOne => "7.1",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_channel_counts() {
+        for layout in [
+            ChannelLayout::Mono,
+            ChannelLayout::Stereo,
+            ChannelLayout::TwoPointOne,
+            ChannelLayout::FivePointOne,
+            ChannelLayout::SevenPointOne,
+        ] {
+            assert_eq!(usize::from(layout.channel_count()), layout.speakers().len());
+            assert_eq!(
+                ChannelLayout::from_channel_count(layout.channel_count()),
+                Some(layout)
+            );
+        }
+        assert_eq!(ChannelLayout::from_channel_count(4), None);
+    }
+
+    #[test]
+    fn test_layout_from_symphonia_masks() {
+        use symphonia::core::audio::Layout;
+
+        assert_eq!(
+            ChannelLayout::from_symphonia(Layout::Mono.into_channels()),
+            Some(ChannelLayout::Mono)
+        );
+        assert_eq!(
+            ChannelLayout::from_symphonia(Layout::TwoPointOne.into_channels()),
+            Some(ChannelLayout::TwoPointOne)
+        );
+        assert_eq!(
+            ChannelLayout::from_symphonia(Layout::FivePointOne.into_channels()),
+            Some(ChannelLayout::FivePointOne)
+        );
+        let side_51 = Channels::FRONT_LEFT
+            | Channels::FRONT_RIGHT
+            | Channels::FRONT_CENTRE
+            | Channels::LFE1
+            | Channels::SIDE_LEFT
+            | Channels::SIDE_RIGHT;
+        assert_eq!(
+            ChannelLayout::from_symphonia(side_51),
+            Some(ChannelLayout::FivePointOne)
+        );
+        let quad = Channels::FRONT_LEFT
+            | Channels::FRONT_RIGHT
+            | Channels::REAR_LEFT
+            | Channels::REAR_RIGHT;
+        assert_eq!(ChannelLayout::from_symphonia(quad), None);
+    }
+}
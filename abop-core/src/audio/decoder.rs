@@ -13,9 +13,18 @@ use symphonia::core::{
     probe::Hint,
 };
 
-use super::{AudioBuffer, AudioStream, SampleFormat};
+use super::{AudioBuffer, AudioStream, ChannelLayout, SampleFormat};
 use crate::error::{AppError, Result};
 
+/// A chapter or track cue embedded in the container
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedCue {
+    /// Start of the cue in seconds
+    pub start_secs: f64,
+    /// Title tag of the cue, if any
+    pub title: Option<String>,
+}
+
 /// Audio decoder for various audio formats
 pub struct AudioDecoder {
     /// The format reader for the audio file
@@ -109,6 +118,10 @@ impl AudioDecoder {
         let stream = AudioStream {
             sample_rate,
             channels,
+            channel_layout: codec_params.channels.map_or_else(
+                || ChannelLayout::from_channel_count(channels),
+                ChannelLayout::from_symphonia,
+            ),
             sample_format,
             duration: codec_params.n_frames.map(|f| {
                 // Note: u64 to f64 cast may lose precision for very large values
@@ -299,6 +312,44 @@ impl AudioDecoder {
         self.stream.duration
     }
 
+    /// Gets the total number of frames declared by the container header, if known
+    #[must_use]
+    pub const fn total_frames(&self) -> Option<u64> {
+        self.track.codec_params.n_frames
+    }
+
+    /// Gets the chapter cues embedded in the container
+    ///
+    /// Only formats whose reader exposes cues, such as FLAC cue sheets,
+    /// provide any.
+    #[must_use]
+    pub fn embedded_cues(&self) -> Vec<EmbeddedCue> {
+        let time_base = self.track.codec_params.time_base;
+        self.format
+            .cues()
+            .iter()
+            .map(|cue| {
+                #[allow(clippy::cast_precision_loss)]
+                let start_secs = time_base.map_or_else(
+                    || cue.start_ts as f64 / f64::from(self.stream.sample_rate),
+                    |base| {
+                        let time = base.calc_time(cue.start_ts);
+                        time.seconds as f64 + time.frac
+                    },
+                );
+                let title = cue
+                    .tags
+                    .iter()
+                    .find(|tag| {
+                        tag.std_key == Some(symphonia::core::meta::StandardTagKey::TrackTitle)
+                            || tag.key.eq_ignore_ascii_case("title")
+                    })
+                    .map(|tag| tag.value.to_string());
+                EmbeddedCue { start_secs, title }
+            })
+            .collect()
+    }
+
     /// Seeks to a specific position in the audio stream
     ///
     /// # Errors
@@ -0,0 +1,454 @@
+//! Chroma-based acoustic fingerprints
+//!
+//! Two rips of the same recording rarely share a byte, but they share the same
+//! harmonic content over time. This module decodes the start of a file, folds
+//! its spectrum into the twelve pitch classes (a chromagram) and packs the
+//! relations between neighbouring pitch classes and frames into one `u32` code
+//! per frame. Comparing codes bit by bit, while allowing for a small time
+//! offset, gives a similarity score that survives re-encoding, bitrate changes
+//! and resampling.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+use super::processing::config::ResampleQuality;
+use super::processing::traits::AudioProcessor;
+use super::processing::{ResamplerConfig, SincResampler};
+use super::{AudioBuffer, AudioDecoder, SampleFormat};
+use crate::error::Result;
+
+/// Number of pitch classes in a chroma vector
+const PITCH_CLASSES: usize = 12;
+
+/// Number of comparable bits per frame code
+const CODE_BITS: u32 = 24;
+
+/// Mask selecting the comparable bits of a frame code
+const CODE_MASK: u32 = (1 << CODE_BITS) - 1;
+
+/// Flag set on frames that contain signal rather than silence
+const VOICED_FLAG: u32 = 1 << 31;
+
+/// Lowest frequency folded into the chromagram in Hz
+const MIN_FREQUENCY: f32 = 55.0;
+
+/// Highest frequency folded into the chromagram in Hz
+const MAX_FREQUENCY: f32 = 3520.0;
+
+/// Frames with less energy than this are treated as silence
+const SILENCE_ENERGY: f32 = 1e-6;
+
+/// Configuration for fingerprint extraction and comparison
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FingerprintConfig {
+    /// Seconds of audio to fingerprint from the start of the file
+    pub max_seconds: f64,
+    /// Sample rate the audio is resampled to before analysis
+    pub target_sample_rate: u32,
+    /// FFT frame size in samples at the target sample rate
+    pub frame_size: usize,
+    /// Hop between frames in samples at the target sample rate
+    pub hop_size: usize,
+    /// Largest time offset in seconds searched when comparing fingerprints
+    pub max_offset_seconds: f64,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        Self {
+            max_seconds: 120.0,
+            target_sample_rate: 11_025,
+            frame_size: 4096,
+            hop_size: 2048,
+            max_offset_seconds: 15.0,
+        }
+    }
+}
+
+/// Compact chroma fingerprint of the start of an audio file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChromaFingerprint {
+    /// One code per analysis frame
+    pub codes: Vec<u32>,
+    /// Number of frames per second of audio
+    pub frames_per_second: f32,
+}
+
+impl ChromaFingerprint {
+    /// Number of frames in the fingerprint
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Whether the fingerprint contains no frames
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Compares two fingerprints and returns a similarity between 0.0 and 1.0
+    ///
+    /// The other fingerprint is shifted by up to `max_offset_seconds` in both
+    /// directions and the best alignment wins. Unrelated audio scores around
+    /// 0.5, identical audio scores 1.0. Alignments where less than half of the
+    /// shorter fingerprint overlaps with signal are ignored.
+    #[must_use]
+    pub fn similarity(&self, other: &Self, max_offset_seconds: f64) -> f32 {
+        let shorter = self.len().min(other.len());
+        if shorter == 0 {
+            return 0.0;
+        }
+        let min_overlap = (shorter / 2).max(1);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let max_offset = (max_offset_seconds * f64::from(self.frames_per_second)).round() as isize;
+
+        let mut best = 0.0f32;
+        for offset in -max_offset..=max_offset {
+            let mut compared = 0u32;
+            let mut differing_bits = 0u32;
+            for (i, &a) in self.codes.iter().enumerate() {
+                let Some(j) = i.checked_add_signed(offset) else {
+                    continue;
+                };
+                let Some(&b) = other.codes.get(j) else {
+                    break;
+                };
+                if (a | b) & VOICED_FLAG == 0 {
+                    continue;
+                }
+                compared += 1;
+                differing_bits += ((a ^ b) & CODE_MASK).count_ones();
+            }
+
+            if (compared as usize) < min_overlap {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let score = 1.0 - differing_bits as f32 / (compared * CODE_BITS) as f32;
+            best = best.max(score);
+        }
+        best
+    }
+}
+
+/// Extracts chroma fingerprints from audio files
+pub struct ChromaFingerprinter {
+    config: FingerprintConfig,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+}
+
+impl std::fmt::Debug for ChromaFingerprinter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChromaFingerprinter")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ChromaFingerprinter {
+    fn default() -> Self {
+        Self::new(FingerprintConfig::default())
+    }
+}
+
+impl ChromaFingerprinter {
+    /// Creates a fingerprinter with the given configuration
+    #[must_use]
+    pub fn new(config: FingerprintConfig) -> Self {
+        let frame_size = config.frame_size.max(PITCH_CLASSES * 2);
+        let fft = FftPlanner::new().plan_fft_forward(frame_size);
+        #[allow(clippy::cast_precision_loss)]
+        let window = (0..frame_size)
+            .map(|i| {
+                0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (frame_size - 1) as f32).cos()
+            })
+            .collect();
+        Self {
+            config: FingerprintConfig {
+                frame_size,
+                hop_size: config.hop_size.max(1),
+                ..config
+            },
+            fft,
+            window,
+        }
+    }
+
+    /// Returns the fingerprinter configuration
+    #[must_use]
+    pub const fn config(&self) -> &FingerprintConfig {
+        &self.config
+    }
+
+    /// Fingerprints the start of the audio file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or decoded.
+    pub fn fingerprint<P: AsRef<Path>>(&self, path: P) -> Result<ChromaFingerprint> {
+        let mut decoder = AudioDecoder::open(path)?;
+        self.fingerprint_decoder(&mut decoder)
+    }
+
+    /// Fingerprints the remaining audio of an already opened decoder
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a packet cannot be decoded before any audio was read.
+    pub fn fingerprint_decoder(&self, decoder: &mut AudioDecoder) -> Result<ChromaFingerprint> {
+        let channels = usize::from(decoder.channels().max(1));
+        let sample_rate = decoder.sample_rate().max(1);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let max_frames = (self.config.max_seconds * f64::from(sample_rate)) as usize;
+
+        let mut mono = Vec::with_capacity(max_frames.min(sample_rate as usize * 600));
+        while mono.len() < max_frames {
+            let buffer = match decoder.next_packet() {
+                Ok(Some(buffer)) => buffer,
+                Ok(None) => break,
+                // Damaged packets late in a file should not hide an otherwise usable fingerprint
+                Err(e) if !mono.is_empty() => {
+                    log::debug!("Stopping fingerprint decode early: {e}");
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let scale = 1.0 / channels as f32;
+            mono.extend(
+                buffer
+                    .data
+                    .chunks_exact(channels)
+                    .map(|frame| frame.iter().sum::<f32>() * scale),
+            );
+        }
+        mono.truncate(max_frames);
+
+        Ok(self.fingerprint_samples(&mono, sample_rate))
+    }
+
+    /// Fingerprints mono samples at the given sample rate
+    #[must_use]
+    pub fn fingerprint_samples(&self, samples: &[f32], sample_rate: u32) -> ChromaFingerprint {
+        let (decimated, rate) = self.resample(samples, sample_rate.max(1));
+        #[allow(clippy::cast_precision_loss)]
+        let rate = rate as f32;
+
+        let bin_classes = self.bin_classes(rate);
+        let frame_size = self.config.frame_size;
+        let hop_size = self.config.hop_size;
+
+        let mut codes = Vec::new();
+        let mut previous = [0.0f32; PITCH_CLASSES];
+        let mut spectrum = vec![Complex::new(0.0, 0.0); frame_size];
+        let mut start = 0;
+        while start + frame_size <= decimated.len() {
+            for ((slot, &sample), &weight) in spectrum
+                .iter_mut()
+                .zip(&decimated[start..start + frame_size])
+                .zip(&self.window)
+            {
+                *slot = Complex::new(sample * weight, 0.0);
+            }
+            self.fft.process(&mut spectrum);
+
+            let mut chroma = [0.0f32; PITCH_CLASSES];
+            for (bin, class) in bin_classes.iter().enumerate() {
+                if let Some(class) = class {
+                    chroma[*class] += spectrum[bin].norm_sqr();
+                }
+            }
+            let energy: f32 = chroma.iter().sum();
+            if energy > SILENCE_ENERGY {
+                for value in &mut chroma {
+                    *value /= energy;
+                }
+            }
+
+            codes.push(frame_code(&chroma, &previous, energy > SILENCE_ENERGY));
+            previous = chroma;
+            start += hop_size;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let frames_per_second = rate / hop_size as f32;
+        ChromaFingerprint {
+            codes,
+            frames_per_second,
+        }
+    }
+
+    /// Resamples mono samples to exactly the target sample rate
+    ///
+    /// Frames are compared index by index, so they must span the same time
+    /// whatever rate a file was ripped at. Returns the samples with their
+    /// rate, which stays the input rate if resampling fails.
+    fn resample(&self, samples: &[f32], sample_rate: u32) -> (Vec<f32>, u32) {
+        let target = self.config.target_sample_rate.max(1);
+        if sample_rate == target {
+            return (samples.to_vec(), sample_rate);
+        }
+        let mut buffer = AudioBuffer::new(samples.to_vec(), SampleFormat::F32, sample_rate, 1);
+        let resampled = SincResampler::new(ResamplerConfig {
+            target_sample_rate: Some(target),
+            quality: ResampleQuality::High,
+            enable_anti_aliasing: true,
+        })
+        .and_then(|mut resampler| resampler.process(&mut buffer));
+        match resampled {
+            Ok(()) => (buffer.data, buffer.sample_rate),
+            Err(e) => {
+                log::warn!("Fingerprinting at {sample_rate} Hz, resampling failed: {e}");
+                (samples.to_vec(), sample_rate)
+            }
+        }
+    }
+
+    /// Maps each FFT bin below Nyquist to its pitch class, if in the analysed range
+    fn bin_classes(&self, rate: f32) -> Vec<Option<usize>> {
+        let frame_size = self.config.frame_size;
+        #[allow(clippy::cast_precision_loss)]
+        let bin_width = rate / frame_size as f32;
+        (0..frame_size / 2)
+            .map(|bin| {
+                #[allow(clippy::cast_precision_loss)]
+                let frequency = bin as f32 * bin_width;
+                if !(MIN_FREQUENCY..=MAX_FREQUENCY).contains(&frequency) {
+                    return None;
+                }
+                let midi = 69.0 + 12.0 * (frequency / 440.0).log2();
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let class = (midi.round() as usize) % PITCH_CLASSES;
+                Some(class)
+            })
+            .collect()
+    }
+}
+
+/// Packs pitch class relations within a frame and against the previous frame
+fn frame_code(chroma: &[f32; PITCH_CLASSES], previous: &[f32; PITCH_CLASSES], voiced: bool) -> u32 {
+    let mut code = 0u32;
+    for i in 0..PITCH_CLASSES {
+        if chroma[i] > chroma[(i + 1) % PITCH_CLASSES] {
+            code |= 1 << i;
+        }
+        if chroma[i] > previous[i] {
+            code |= 1 << (PITCH_CLASSES + i);
+        }
+    }
+    if voiced {
+        code |= VOICED_FLAG;
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 11_025;
+
+    /// Renders a sequence of notes (MIDI numbers) at `rate`, each lasting `note_seconds`
+    fn melody(notes: &[u8], note_seconds: f32, rate: u32) -> Vec<f32> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let note_len = (rate as f32 * note_seconds) as usize;
+        let mut samples = Vec::with_capacity(notes.len() * note_len);
+        for &note in notes {
+            let frequency = 440.0 * 2f32.powf((f32::from(note) - 69.0) / 12.0);
+            for i in 0..note_len {
+                let t = i as f32 / rate as f32;
+                let s = (2.0 * std::f32::consts::PI * frequency * t).sin()
+                    + 0.5 * (2.0 * std::f32::consts::PI * frequency * 1.5 * t).sin();
+                samples.push(0.3 * s);
+            }
+        }
+        samples
+    }
+
+    fn song_a() -> Vec<f32> {
+        song_a_at(RATE)
+    }
+
+    fn song_a_at(rate: u32) -> Vec<f32> {
+        melody(
+            &[
+                60, 64, 67, 72, 69, 65, 62, 59, 60, 67, 64, 60, 57, 62, 66, 69,
+            ]
+            .repeat(2),
+            0.6,
+            rate,
+        )
+    }
+
+    fn song_b() -> Vec<f32> {
+        melody(
+            &[
+                61, 58, 70, 63, 68, 56, 73, 66, 61, 71, 58, 63, 68, 65, 70, 56,
+            ]
+            .repeat(2),
+            0.6,
+            RATE,
+        )
+    }
+
+    #[test]
+    fn test_identical_audio_matches() {
+        let fingerprinter = ChromaFingerprinter::default();
+        let a = fingerprinter.fingerprint_samples(&song_a(), RATE);
+        assert!(!a.is_empty());
+        assert!((a.similarity(&a, 0.0) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_offset_and_gain_tolerated() {
+        let fingerprinter = ChromaFingerprinter::default();
+        let a = fingerprinter.fingerprint_samples(&song_a(), RATE);
+
+        // Two seconds of leading silence and a quieter level
+        let mut shifted = vec![0.0; RATE as usize * 2];
+        shifted.extend(song_a().iter().map(|s| s * 0.5));
+        let b = fingerprinter.fingerprint_samples(&shifted, RATE);
+
+        assert!(a.similarity(&b, 5.0) > 0.85);
+    }
+
+    #[test]
+    fn test_sample_rate_does_not_change_fingerprint() {
+        let fingerprinter = ChromaFingerprinter::default();
+        let cd = fingerprinter.fingerprint_samples(&song_a_at(44_100), 44_100);
+        let dat = fingerprinter.fingerprint_samples(&song_a_at(48_000), 48_000);
+
+        assert!((cd.frames_per_second - dat.frames_per_second).abs() < f32::EPSILON);
+        assert!(cd.len().abs_diff(dat.len()) <= 1);
+        let similarity = cd.similarity(&dat, 0.0);
+        assert!(
+            similarity > 0.9,
+            "44.1 kHz and 48 kHz rips scored {similarity}"
+        );
+    }
+
+    #[test]
+    fn test_different_audio_differs() {
+        let fingerprinter = ChromaFingerprinter::default();
+        let a = fingerprinter.fingerprint_samples(&song_a(), RATE);
+        let b = fingerprinter.fingerprint_samples(&song_b(), RATE);
+
+        let same = a.similarity(&a, 5.0);
+        let different = a.similarity(&b, 5.0);
+        assert!(different < 0.8, "unrelated audio scored {different}");
+        assert!(same - different > 0.15);
+    }
+
+    #[test]
+    fn test_silence_has_no_similarity() {
+        let fingerprinter = ChromaFingerprinter::default();
+        let silence = fingerprinter.fingerprint_samples(&vec![0.0; RATE as usize * 10], RATE);
+        assert!(silence.similarity(&silence, 1.0) < f32::EPSILON);
+    }
+}
@@ -0,0 +1,328 @@
+//! Audio integrity verification
+//!
+//! The scanner only probes container headers, so a file that is cut short or
+//! contains damaged packets looks perfectly healthy until playback reaches the
+//! bad region. This module fully decodes a file with [`AudioDecoder`] and
+//! reports decode errors, frames missing compared to the container header and
+//! duration mismatches, condensed into a [`HealthStatus`].
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::AudioDecoder;
+use crate::error::AppError;
+
+/// Overall health of an audio file after a full decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The file decoded cleanly and matches its header
+    Healthy,
+    /// The file plays but had recoverable decode errors or a duration mismatch
+    Degraded,
+    /// The file ends early or decoding had to be aborted
+    Corrupt,
+    /// The file could not be opened or probed at all
+    Unreadable,
+}
+
+impl HealthStatus {
+    /// Returns the string stored in the database for this status
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Corrupt => "corrupt",
+            Self::Unreadable => "unreadable",
+        }
+    }
+
+    /// Whether the file needs attention
+    #[must_use]
+    pub const fn is_problem(self) -> bool {
+        !matches!(self, Self::Healthy)
+    }
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HealthStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "healthy" => Ok(Self::Healthy),
+            "degraded" => Ok(Self::Degraded),
+            "corrupt" => Ok(Self::Corrupt),
+            "unreadable" => Ok(Self::Unreadable),
+            other => Err(AppError::InvalidData(format!(
+                "Unknown health status: {other}"
+            ))),
+        }
+    }
+}
+
+/// Configuration for integrity verification
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntegrityConfig {
+    /// Allowed difference between header and decoded duration in seconds
+    ///
+    /// Lossy formats commonly differ by a few encoder delay or padding frames,
+    /// so small mismatches are not reported.
+    pub duration_tolerance_secs: f64,
+    /// Decoding is aborted after this many consecutive failed packets
+    pub max_consecutive_errors: u32,
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        Self {
+            duration_tolerance_secs: 0.5,
+            max_consecutive_errors: 32,
+        }
+    }
+}
+
+/// Result of verifying a single audio file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Path of the verified file
+    pub path: PathBuf,
+    /// Overall health status
+    pub status: HealthStatus,
+    /// Number of packets that failed to read or decode
+    pub decode_errors: u32,
+    /// Number of frames declared by the container header, if known
+    pub expected_frames: Option<u64>,
+    /// Number of frames actually decoded
+    pub decoded_frames: u64,
+    /// Frames declared by the header that could not be decoded
+    pub truncated_frames: u64,
+    /// Duration declared by the container header in seconds, if known
+    pub header_duration_secs: Option<f64>,
+    /// Duration of the decoded audio in seconds
+    pub decoded_duration_secs: f64,
+    /// Decoded minus header duration in seconds, if the header declares one
+    pub duration_delta_secs: Option<f64>,
+    /// Whether decoding was aborted before the end of the stream
+    pub aborted: bool,
+    /// Last error message encountered, if any
+    pub message: Option<String>,
+}
+
+impl IntegrityReport {
+    /// Creates a report for a file that could not be opened
+    #[must_use]
+    pub fn unreadable(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            status: HealthStatus::Unreadable,
+            decode_errors: 0,
+            expected_frames: None,
+            decoded_frames: 0,
+            truncated_frames: 0,
+            header_duration_secs: None,
+            decoded_duration_secs: 0.0,
+            duration_delta_secs: None,
+            aborted: true,
+            message: Some(message.into()),
+        }
+    }
+
+    /// Whether the decoded duration differs from the header by more than the tolerance
+    #[must_use]
+    pub fn has_duration_mismatch(&self, config: &IntegrityConfig) -> bool {
+        self.duration_delta_secs
+            .is_some_and(|delta| delta.abs() > config.duration_tolerance_secs)
+    }
+}
+
+/// Fully decodes audio files to detect corruption
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityVerifier {
+    config: IntegrityConfig,
+}
+
+impl IntegrityVerifier {
+    /// Creates a verifier with the given configuration
+    #[must_use]
+    pub const fn new(config: IntegrityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the verifier configuration
+    #[must_use]
+    pub const fn config(&self) -> &IntegrityConfig {
+        &self.config
+    }
+
+    /// Verifies the file at `path`
+    ///
+    /// This never fails: files that cannot be opened are reported as
+    /// [`HealthStatus::Unreadable`].
+    #[must_use]
+    pub fn verify<P: AsRef<Path>>(&self, path: P) -> IntegrityReport {
+        let path = path.as_ref();
+        match AudioDecoder::open(path) {
+            Ok(mut decoder) => self.verify_decoder(path, &mut decoder),
+            Err(e) => IntegrityReport::unreadable(path, e.to_string()),
+        }
+    }
+
+    /// Decodes every remaining packet from an already opened decoder
+    #[must_use]
+    pub fn verify_decoder(&self, path: &Path, decoder: &mut AudioDecoder) -> IntegrityReport {
+        let channels = usize::from(decoder.channels().max(1));
+        let sample_rate = decoder.sample_rate().max(1);
+        let expected_frames = decoder.total_frames();
+
+        let mut decoded_frames = 0u64;
+        let mut decode_errors = 0u32;
+        let mut consecutive_errors = 0u32;
+        let mut aborted = false;
+        let mut message = None;
+
+        loop {
+            match decoder.next_packet() {
+                Ok(Some(buffer)) => {
+                    consecutive_errors = 0;
+                    decoded_frames += (buffer.data.len() / channels) as u64;
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    decode_errors = decode_errors.saturating_add(1);
+                    consecutive_errors += 1;
+                    message = Some(e.to_string());
+                    if consecutive_errors >= self.config.max_consecutive_errors {
+                        aborted = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let to_secs = |frames: u64| frames as f64 / f64::from(sample_rate);
+        let decoded_duration_secs = to_secs(decoded_frames);
+        let header_duration_secs = expected_frames.map(to_secs);
+        let duration_delta_secs = header_duration_secs.map(|header| decoded_duration_secs - header);
+        let truncated_frames =
+            expected_frames.map_or(0, |expected| expected.saturating_sub(decoded_frames));
+
+        let mut report = IntegrityReport {
+            path: path.to_path_buf(),
+            status: HealthStatus::Healthy,
+            decode_errors,
+            expected_frames,
+            decoded_frames,
+            truncated_frames,
+            header_duration_secs,
+            decoded_duration_secs,
+            duration_delta_secs,
+            aborted,
+            message,
+        };
+        report.status = self.classify(&report);
+        report
+    }
+
+    /// Derives the health status from the collected counters
+    fn classify(&self, report: &IntegrityReport) -> HealthStatus {
+        // Missing frames only count as truncation once they exceed the tolerance
+        let truncated = report.truncated_frames > 0
+            && report
+                .duration_delta_secs
+                .is_some_and(|delta| -delta > self.config.duration_tolerance_secs);
+
+        if report.aborted || report.decoded_frames == 0 || truncated {
+            HealthStatus::Corrupt
+        } else if report.decode_errors > 0 || report.has_duration_mismatch(&self.config) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_wav(path: &Path, sample_rate: u32, seconds: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..sample_rate * seconds {
+            #[allow(clippy::cast_possible_truncation)]
+            writer.write_sample((i % 200) as i16 * 100).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_healthy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("healthy.wav");
+        write_wav(&path, 8000, 2);
+
+        let report = IntegrityVerifier::default().verify(&path);
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.decode_errors, 0);
+        assert_eq!(report.expected_frames, Some(16000));
+        assert_eq!(report.decoded_frames, 16000);
+        assert_eq!(report.truncated_frames, 0);
+    }
+
+    #[test]
+    fn test_truncated_file_is_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("truncated.wav");
+        write_wav(&path, 8000, 4);
+
+        // Cut the data chunk in half while keeping the original header
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+        let report = IntegrityVerifier::default().verify(&path);
+        assert_eq!(report.status, HealthStatus::Corrupt);
+        assert_eq!(report.expected_frames, Some(32000));
+        assert!(report.truncated_frames > 8000);
+        assert!(report.duration_delta_secs.unwrap() < -1.0);
+    }
+
+    #[test]
+    fn test_unreadable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("garbage.mp3");
+        std::fs::write(&path, b"definitely not audio").unwrap();
+
+        let report = IntegrityVerifier::default().verify(&path);
+        assert_eq!(report.status, HealthStatus::Unreadable);
+        assert!(report.message.is_some());
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            HealthStatus::Healthy,
+            HealthStatus::Degraded,
+            HealthStatus::Corrupt,
+            HealthStatus::Unreadable,
+        ] {
+            assert_eq!(status.as_str().parse::<HealthStatus>().unwrap(), status);
+        }
+        assert!("broken".parse::<HealthStatus>().is_err());
+    }
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use symphonia::core::probe::Hint;
 
-use super::{AudioStream, SampleFormat};
+use super::{AudioStream, ChannelLayout, SampleFormat};
 use crate::error::{AppError, Result};
 
 /// Represents the metadata for an audio file
@@ -231,6 +231,10 @@ fn process_track_metadata(
         meta.stream = Some(AudioStream {
             sample_rate,
             channels,
+            channel_layout: track.codec_params.channels.map_or_else(
+                || ChannelLayout::from_channel_count(channels),
+                ChannelLayout::from_symphonia,
+            ),
             sample_format,
             duration,
         });
@@ -250,27 +254,29 @@ fn process_metadata_tags(
 ) -> AudioMetadata {
     if let Some(reader) = metadata.current() {
         for tag in reader.tags() {
+            // RIFF INFO values are NUL terminated strings
+            let value = tag.value.to_string().trim_end_matches('\0').to_string();
             if let Some(std_key) = tag.std_key {
                 match std_key {
                     symphonia::core::meta::StandardTagKey::TrackTitle => {
-                        meta.title = Some(tag.value.to_string());
+                        meta.title = Some(value);
                     }
                     symphonia::core::meta::StandardTagKey::Artist => {
-                        meta.artist = Some(tag.value.to_string());
+                        meta.artist = Some(value);
                     }
                     symphonia::core::meta::StandardTagKey::Album => {
-                        meta.album = Some(tag.value.to_string());
+                        meta.album = Some(value);
                     }
                     symphonia::core::meta::StandardTagKey::TrackNumber => {
-                        if let Ok(track) = tag.value.to_string().parse::<u32>() {
+                        if let Ok(track) = value.parse::<u32>() {
                             meta.track = Some(track);
                         }
                     }
                     symphonia::core::meta::StandardTagKey::Genre => {
-                        meta.genre = Some(tag.value.to_string());
+                        meta.genre = Some(value);
                     }
                     symphonia::core::meta::StandardTagKey::Date => {
-                        if let Ok(year) = tag.value.to_string().parse::<i32>() {
+                        if let Ok(year) = value.parse::<i32>() {
                             meta.year = Some(year);
                         }
                     } // Ignore composer and comment fields for now
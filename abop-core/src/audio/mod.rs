@@ -2,13 +2,20 @@
 //!
 //! This module provides functionality for decoding, processing, and analyzing audio files.
 
+pub mod channel_layout;
 pub mod decoder;
+pub mod fingerprint;
+pub mod integrity;
 pub mod metadata;
 pub mod player;
 pub mod processing;
+pub mod waveform;
 
 // Re-export the public API
-pub use decoder::AudioDecoder;
+pub use channel_layout::{ChannelLayout, Speaker};
+pub use decoder::{AudioDecoder, EmbeddedCue};
+pub use fingerprint::{ChromaFingerprint, ChromaFingerprinter, FingerprintConfig};
+pub use integrity::{HealthStatus, IntegrityConfig, IntegrityReport, IntegrityVerifier};
 pub use metadata::AudioMetadata;
 pub use player::{AudioPlayer, PlayerState};
 pub use processing::{
@@ -16,6 +23,7 @@ pub use processing::{
     MixingAlgorithm, NormalizerConfig, OutputConfig, ProcessingConfig, ResamplerConfig,
     SilenceDetector, SilenceDetectorConfig,
 };
+pub use waveform::{WaveformConfig, WaveformGenerator, WaveformSummary};
 
 use std::path::Path;
 use std::sync::{Arc, Mutex};
@@ -95,6 +103,8 @@ pub struct AudioStream {
     pub sample_rate: u32,
     /// The number of channels
     pub channels: u16,
+    /// The speaker layout of the channels, if it is a standard one
+    pub channel_layout: Option<ChannelLayout>,
     /// The sample format
     pub sample_format: SampleFormat,
     /// The duration in seconds, if known
@@ -102,7 +112,7 @@ pub struct AudioStream {
 }
 
 /// Represents the format of audio samples
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum SampleFormat {
     /// 8-bit unsigned integer
     U8,
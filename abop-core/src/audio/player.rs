@@ -6,11 +6,18 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::{Decoder, OutputStream, Sink, Source};
 
+use crate::audio::processing::{EffectSource, PipelineStage};
 use crate::error::{AppError, Result};
 
+/// Slowest supported playback rate
+pub const MIN_PLAYBACK_RATE: f32 = 0.5;
+/// Fastest supported playback rate
+pub const MAX_PLAYBACK_RATE: f32 = 3.0;
+
 /// Audio player state
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum PlayerState {
@@ -34,8 +41,12 @@ pub struct AudioPlayer {
     state: PlayerState,
     /// Current volume (0.0 to 1.0)
     volume: f32,
+    /// Playback rate multiplier
+    rate: f32,
     /// Current playing file path
     current_file: Option<PathBuf>,
+    /// Processing stages applied during playback
+    effects: Vec<PipelineStage>,
 }
 
 /// Thread-safe wrapper around AudioPlayer
@@ -69,6 +80,15 @@ impl ThreadSafeAudioPlayer {
             .play(file_path)
     }
 
+    /// Sets the processing stages applied during playback
+    ///
+    /// See `AudioPlayer::set_effects` for details.
+    pub fn set_effects(&self, effects: Vec<PipelineStage>) {
+        if let Ok(mut player) = self.inner.lock() {
+            player.set_effects(effects);
+        }
+    }
+
     /// Stops audio playback
     ///
     /// See `AudioPlayer::stop` for details.
@@ -100,6 +120,86 @@ impl ThreadSafeAudioPlayer {
             .map(|player| player.get_current_file())
             .unwrap_or(None)
     }
+
+    /// Pauses audio playback
+    ///
+    /// See `AudioPlayer::pause` for details.
+    pub fn pause(&self) {
+        if let Ok(mut player) = self.inner.lock() {
+            player.pause();
+        }
+    }
+
+    /// Resumes audio playback
+    ///
+    /// See `AudioPlayer::resume` for details.
+    pub fn resume(&self) {
+        if let Ok(mut player) = self.inner.lock() {
+            player.resume();
+        }
+    }
+
+    /// Gets the playback position in the current file
+    ///
+    /// Returns zero if the player lock cannot be acquired.
+    #[must_use]
+    pub fn position(&self) -> Duration {
+        self.inner
+            .lock()
+            .map(|player| player.position())
+            .unwrap_or_default()
+    }
+
+    /// Moves playback to a position in the current file
+    ///
+    /// See `AudioPlayer::seek` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Audio` if the lock cannot be acquired or seeking fails.
+    pub fn seek(&self, position: Duration) -> Result<()> {
+        self.inner
+            .lock()
+            .map_err(|e| AppError::Audio(format!("Failed to acquire audio player lock: {e}")))?
+            .seek(position)
+    }
+
+    /// Gets the playback rate multiplier
+    ///
+    /// Returns 1.0 if the player lock cannot be acquired.
+    #[must_use]
+    pub fn rate(&self) -> f32 {
+        self.inner.lock().map(|player| player.rate()).unwrap_or(1.0)
+    }
+
+    /// Sets the playback rate multiplier
+    ///
+    /// See `AudioPlayer::set_rate` for details.
+    pub fn set_rate(&self, rate: f32) {
+        if let Ok(mut player) = self.inner.lock() {
+            player.set_rate(rate);
+        }
+    }
+
+    /// Sets the volume (0.0 to 1.0)
+    ///
+    /// See `AudioPlayer::set_volume` for details.
+    pub fn set_volume(&self, volume: f32) {
+        if let Ok(mut player) = self.inner.lock() {
+            player.set_volume(volume);
+        }
+    }
+
+    /// Gets the current volume (0.0 to 1.0)
+    ///
+    /// Returns 0.0 if the player lock cannot be acquired.
+    #[must_use]
+    pub fn get_volume(&self) -> f32 {
+        self.inner
+            .lock()
+            .map(|player| player.get_volume())
+            .unwrap_or(0.0)
+    }
 }
 
 impl AudioPlayer {
@@ -113,7 +213,9 @@ impl AudioPlayer {
             sink: None,
             state: PlayerState::Stopped,
             volume: 0.7, // Default volume 70%
+            rate: 1.0,
             current_file: None,
+            effects: Vec::new(),
         })
     }
 
@@ -162,9 +264,17 @@ impl AudioPlayer {
         let sink = Sink::try_new(&stream_handle)
             .map_err(|e| AppError::Audio(format!("Failed to create audio sink: {e}")))?;
         sink.set_volume(self.volume);
-
-        // Append the source and play
-        sink.append(source);
+        sink.set_speed(self.rate);
+
+        // Append the source, through the playback effects if any, and play
+        if self.effects.is_empty() {
+            sink.append(source);
+        } else {
+            sink.append(EffectSource::new(
+                source.convert_samples::<f32>(),
+                self.effects.clone(),
+            ));
+        }
         sink.play();
 
         // Update state
@@ -176,6 +286,21 @@ impl AudioPlayer {
         Ok(())
     }
 
+    /// Sets the processing stages applied during playback
+    ///
+    /// Stages such as the equalizer and de-esser process the decoded stream
+    /// block by block. The new chain takes effect from the next call to
+    /// [`play`](Self::play).
+    pub fn set_effects(&mut self, effects: Vec<PipelineStage>) {
+        self.effects = effects;
+    }
+
+    /// Gets the processing stages applied during playback
+    #[must_use]
+    pub fn effects(&self) -> &[PipelineStage] {
+        &self.effects
+    }
+
     /// Stops audio playback
     pub fn stop(&mut self) {
         if let Some(sink) = self.sink.take() {
@@ -227,6 +352,51 @@ impl AudioPlayer {
         self.volume
     }
 
+    /// Gets the playback position in the current file
+    ///
+    /// Returns zero when nothing is loaded.
+    #[must_use]
+    pub fn position(&self) -> Duration {
+        self.sink.as_ref().map_or(Duration::ZERO, Sink::get_pos)
+    }
+
+    /// Moves playback to a position in the current file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Audio`] if nothing is loaded or the decoder
+    /// cannot seek in the file.
+    pub fn seek(&mut self, position: Duration) -> Result<()> {
+        let sink = self
+            .sink
+            .as_ref()
+            .ok_or_else(|| AppError::Audio("Nothing is playing".to_string()))?;
+        sink.try_seek(position)
+            .map_err(|e| AppError::Audio(format!("Failed to seek: {e}")))?;
+        log::debug!("Seeked to {:.1}s", position.as_secs_f64());
+        Ok(())
+    }
+
+    /// Gets the playback rate multiplier
+    #[must_use]
+    pub const fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets the playback rate multiplier
+    ///
+    /// The rate is clamped to [`MIN_PLAYBACK_RATE`]..=[`MAX_PLAYBACK_RATE`]
+    /// and applies to the current file as well as later ones.
+    pub fn set_rate(&mut self, rate: f32) {
+        let rate = rate.clamp(MIN_PLAYBACK_RATE, MAX_PLAYBACK_RATE);
+        self.rate = rate;
+
+        if let Some(ref sink) = self.sink {
+            sink.set_speed(rate);
+        }
+        log::debug!("Set playback rate to {rate:.2}x");
+    }
+
     /// Checks if the player is currently playing
     #[must_use]
     pub fn is_playing(&self) -> bool {
@@ -274,7 +444,9 @@ impl Default for AudioPlayer {
             sink: None,                  // Option<Sink>
             state: PlayerState::Stopped, // PlayerState
             volume: 0.7,                 // f32
+            rate: 1.0,                   // f32
             current_file: None,          // Option<PathBuf>
+            effects: Vec::new(),         // Vec<PipelineStage>
         }
     }
 }
@@ -298,6 +470,22 @@ mod tests {
         assert!(player.is_stopped());
         assert_eq!(player.get_volume(), 0.7);
         assert!(player.get_current_file().is_none());
+        assert!(player.effects().is_empty());
+    }
+
+    #[test]
+    fn test_rate_and_position_without_playback() {
+        let mut player = AudioPlayer::new().unwrap();
+        assert_eq!(player.rate(), 1.0);
+        assert_eq!(player.position(), Duration::ZERO);
+        assert!(player.seek(Duration::from_secs(5)).is_err());
+
+        player.set_rate(1.5);
+        assert_eq!(player.rate(), 1.5);
+        player.set_rate(10.0);
+        assert_eq!(player.rate(), MAX_PLAYBACK_RATE);
+        player.set_rate(0.0);
+        assert_eq!(player.rate(), MIN_PLAYBACK_RATE);
     }
 
     #[test]
@@ -0,0 +1,773 @@
+//! Audiobook distribution (ACX) compliance checks
+//!
+//! This module measures the properties distributors such as ACX check before
+//! accepting an audiobook: overall RMS level, true sample peak, the noise floor
+//! of the room tone, the length of the room tone at the head and tail of each
+//! file, and a consistent sample rate and channel format across the book.
+//!
+//! Files are analyzed packet by packet with [`AcxAnalyzer`], so multi-hour
+//! chapters are never held in memory. Every failed check names the processor
+//! that would cure it, and [`AcxFileReport::remediation_config`] turns those
+//! suggestions into a ready-to-run [`ProcessingConfig`].
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::chain::ProcessorRegistry;
+use super::config::{
+    LimiterConfig, NoiseGateConfig, NormalizationAlgorithm, NormalizerConfig, ProcessingConfig,
+    StageConfig,
+};
+use super::error::Result;
+use super::utils::{
+    buffer::validate_buffer,
+    channels::validate_channels,
+    gain::{linear_to_db, power_to_db},
+    sample_rate::{is_standard_sample_rate, validate_sample_rate},
+};
+use super::validation::ConfigValidator;
+use crate::audio::{AudioBuffer, AudioDecoder};
+use crate::error::{AppError, Result as AppResult};
+use crate::utils::casting::domain::audio::{safe_f64_to_usize_samples, safe_usize_to_f64_audio};
+
+/// Length of the blocks used for room tone detection, in seconds
+const BLOCK_SECS: f64 = 0.05;
+
+/// Length of the window the noise floor is measured over, in blocks
+const NOISE_WINDOW_BLOCKS: usize = 10;
+
+/// Gap left between the noise gate threshold and the measured noise floor, in dB
+const GATE_MARGIN_DB: f32 = 10.0;
+
+/// Levels and timings a distributor requires
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AcxRequirements {
+    /// Lowest acceptable RMS level in dBFS
+    pub rms_min_db: f32,
+    /// Highest acceptable RMS level in dBFS
+    pub rms_max_db: f32,
+    /// Highest acceptable sample peak in dBFS
+    pub peak_max_db: f32,
+    /// Highest acceptable noise floor in dBFS
+    pub noise_floor_max_db: f32,
+    /// Level below which audio counts as room tone, in dBFS
+    pub room_tone_threshold_db: f32,
+    /// Acceptable room tone at the start of a file, in seconds
+    pub head_room_tone_secs: (f64, f64),
+    /// Acceptable room tone at the end of a file, in seconds
+    pub tail_room_tone_secs: (f64, f64),
+    /// Highest acceptable channel count (1 = mono only, 2 = mono or stereo)
+    pub max_channels: u16,
+}
+
+impl Default for AcxRequirements {
+    fn default() -> Self {
+        Self {
+            rms_min_db: -23.0,
+            rms_max_db: -18.0,
+            peak_max_db: -3.0,
+            noise_floor_max_db: -60.0,
+            room_tone_threshold_db: -50.0,
+            head_room_tone_secs: (0.5, 1.0),
+            tail_room_tone_secs: (1.0, 5.0),
+            max_channels: 2,
+        }
+    }
+}
+
+/// A single compliance check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcxCheck {
+    /// Overall RMS level
+    Rms,
+    /// Highest sample peak
+    Peak,
+    /// Level of the quietest stretch
+    NoiseFloor,
+    /// Room tone before the first words
+    HeadRoomTone,
+    /// Room tone after the last words
+    TailRoomTone,
+    /// Sample rate, per file and across the book
+    SampleRate,
+    /// Channel count, per file and across the book
+    ChannelFormat,
+}
+
+impl fmt::Display for AcxCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Rms => "RMS level",
+            Self::Peak => "Peak level",
+            Self::NoiseFloor => "Noise floor",
+            Self::HeadRoomTone => "Head room tone",
+            Self::TailRoomTone => "Tail room tone",
+            Self::SampleRate => "Sample rate",
+            Self::ChannelFormat => "Channel format",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The fix that would cure a failed check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcxFix {
+    /// Run the normalizer in RMS mode
+    Normalizer,
+    /// Run the look-ahead limiter
+    Limiter,
+    /// Run the noise gate
+    NoiseGate,
+    /// Trim leading or trailing silence with the silence detector
+    TrimSilence,
+    /// Add room tone at the head or tail (needs editing)
+    AddRoomTone,
+    /// Resample to a common standard rate
+    Resampler,
+    /// Mix down to a common channel count
+    ChannelMixer,
+}
+
+impl AcxFix {
+    /// Short human readable description of the fix
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Normalizer => "normalize RMS level with the normalizer",
+            Self::Limiter => "reduce peaks with the limiter",
+            Self::NoiseGate => "lower the noise floor with the noise gate",
+            Self::TrimSilence => "trim excess silence with the silence detector",
+            Self::AddRoomTone => "add room tone in an editor",
+            Self::Resampler => "resample every file to the same standard rate",
+            Self::ChannelMixer => "mix every file to the same channel count",
+        }
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcxFinding {
+    /// Which check this is
+    pub check: AcxCheck,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Measured value (dB, seconds, Hz or channels depending on the check)
+    pub measured: f64,
+    /// The accepted range, for display
+    pub expected: String,
+    /// Suggested fix when the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<AcxFix>,
+}
+
+impl AcxFinding {
+    fn new(check: AcxCheck, passed: bool, measured: f64, expected: String, fix: AcxFix) -> Self {
+        Self {
+            check,
+            passed,
+            measured,
+            expected,
+            fix: (!passed).then_some(fix),
+        }
+    }
+}
+
+/// Measured properties of one file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AcxMeasurements {
+    /// Duration in seconds
+    pub duration_secs: f64,
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    /// Number of channels
+    pub channels: u16,
+    /// RMS level over the whole file in dBFS
+    pub rms_db: f32,
+    /// Highest sample peak in dBFS
+    pub peak_db: f32,
+    /// RMS level of the quietest half second in dBFS
+    pub noise_floor_db: f32,
+    /// Room tone before the first sound above the threshold, in seconds
+    pub head_room_tone_secs: f64,
+    /// Room tone after the last sound above the threshold, in seconds
+    pub tail_room_tone_secs: f64,
+}
+
+/// Incremental analyzer that measures a stream packet by packet
+#[derive(Debug, Clone)]
+pub struct AcxAnalyzer {
+    sample_rate: u32,
+    channels: u16,
+    threshold_power: f64,
+    block_frames: usize,
+    block_power: f64,
+    block_filled: usize,
+    sum_squares: f64,
+    frames: usize,
+    peak: f32,
+    window: VecDeque<f64>,
+    min_window_power: Option<f64>,
+    quietest_block: Option<f64>,
+    head_blocks: usize,
+    head_done: bool,
+    tail_blocks: usize,
+}
+
+impl AcxAnalyzer {
+    /// Creates an analyzer for a stream with the given format
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if
+    /// the sample rate or channel count is invalid.
+    pub fn new(sample_rate: u32, channels: u16, requirements: &AcxRequirements) -> Result<Self> {
+        validate_sample_rate(sample_rate)?;
+        validate_channels(channels)?;
+        let threshold = f64::from(requirements.room_tone_threshold_db);
+        let block_frames = safe_f64_to_usize_samples((f64::from(sample_rate) * BLOCK_SECS).round())
+            .unwrap_or(1)
+            .max(1);
+        Ok(Self {
+            sample_rate,
+            channels,
+            threshold_power: 10f64.powf(threshold / 10.0),
+            block_frames,
+            block_power: 0.0,
+            block_filled: 0,
+            sum_squares: 0.0,
+            frames: 0,
+            peak: 0.0,
+            window: VecDeque::with_capacity(NOISE_WINDOW_BLOCKS + 1),
+            min_window_power: None,
+            quietest_block: None,
+            head_blocks: 0,
+            head_done: false,
+            tail_blocks: 0,
+        })
+    }
+
+    /// Adds interleaved samples to the measurement
+    pub fn push_interleaved(&mut self, data: &[f32]) {
+        for frame in data.chunks(usize::from(self.channels)) {
+            for sample in frame {
+                let power = f64::from(*sample).powi(2);
+                self.block_power += power;
+                self.sum_squares += power;
+                self.peak = self.peak.max(sample.abs());
+            }
+            self.frames += 1;
+            self.block_filled += 1;
+            if self.block_filled == self.block_frames {
+                let samples = self.block_frames * usize::from(self.channels);
+                self.finish_block(self.block_power / safe_usize_to_f64_audio(samples));
+                self.block_power = 0.0;
+                self.block_filled = 0;
+            }
+        }
+    }
+
+    fn finish_block(&mut self, power: f64) {
+        let quiet = power <= self.threshold_power;
+        if !self.head_done {
+            if quiet {
+                self.head_blocks += 1;
+            } else {
+                self.head_done = true;
+            }
+        }
+        self.tail_blocks = if quiet { self.tail_blocks + 1 } else { 0 };
+        self.quietest_block = Some(self.quietest_block.map_or(power, |q| q.min(power)));
+
+        self.window.push_back(power);
+        if self.window.len() > NOISE_WINDOW_BLOCKS {
+            self.window.pop_front();
+        }
+        if self.window.len() == NOISE_WINDOW_BLOCKS {
+            let mean =
+                self.window.iter().sum::<f64>() / safe_usize_to_f64_audio(NOISE_WINDOW_BLOCKS);
+            self.min_window_power = Some(self.min_window_power.map_or(mean, |m| m.min(mean)));
+        }
+    }
+
+    /// Completes the measurement
+    #[must_use]
+    pub fn finish(&self) -> AcxMeasurements {
+        let samples = self.frames * usize::from(self.channels);
+        let mean_power = self.sum_squares / safe_usize_to_f64_audio(samples.max(1));
+        // Files shorter than the noise window fall back to the quietest block
+        let noise_power = self
+            .min_window_power
+            .or(self.quietest_block)
+            .unwrap_or(mean_power);
+        let block_secs = safe_usize_to_f64_audio(self.block_frames) / f64::from(self.sample_rate);
+        let duration_secs = safe_usize_to_f64_audio(self.frames) / f64::from(self.sample_rate);
+
+        let (head, tail) = if self.head_done {
+            (
+                safe_usize_to_f64_audio(self.head_blocks) * block_secs,
+                safe_usize_to_f64_audio(self.tail_blocks) * block_secs,
+            )
+        } else {
+            // Nothing rose above the threshold: the whole file is room tone
+            (duration_secs, duration_secs)
+        };
+
+        AcxMeasurements {
+            duration_secs,
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            rms_db: power_to_db(mean_power),
+            peak_db: linear_to_db(self.peak),
+            noise_floor_db: power_to_db(noise_power),
+            head_room_tone_secs: head,
+            tail_room_tone_secs: tail,
+        }
+    }
+}
+
+/// Compliance report for one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcxFileReport {
+    /// Path of the file, when checked from disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// What was measured
+    pub measurements: AcxMeasurements,
+    /// Outcome of every per-file check
+    pub findings: Vec<AcxFinding>,
+    /// Whether every check passed
+    pub passed: bool,
+}
+
+impl AcxFileReport {
+    /// Failed checks
+    pub fn failures(&self) -> impl Iterator<Item = &AcxFinding> {
+        self.findings.iter().filter(|finding| !finding.passed)
+    }
+
+    /// Distinct fixes suggested by the failed checks, in check order
+    #[must_use]
+    pub fn fixes(&self) -> Vec<AcxFix> {
+        let mut fixes = Vec::new();
+        for fix in self.failures().filter_map(|finding| finding.fix) {
+            if !fixes.contains(&fix) {
+                fixes.push(fix);
+            }
+        }
+        fixes
+    }
+
+    /// Builds a processing chain that applies the processor fixes
+    ///
+    /// The chain runs the noise gate, then the normalizer in RMS mode, then
+    /// the limiter. A limiter is added whenever the normalizer is, because
+    /// raising the level can push peaks over the ceiling. Fixes that need
+    /// editing, such as adding room tone, are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Audio`] if the resulting configuration fails validation.
+    pub fn remediation_config(
+        &self,
+        requirements: &AcxRequirements,
+    ) -> AppResult<ProcessingConfig> {
+        let fixes = self.fixes();
+        let mut builder = ProcessingConfig::builder();
+
+        if fixes.contains(&AcxFix::NoiseGate) {
+            let floor = self.measurements.noise_floor_db;
+            let gate = NoiseGateConfig::builder()
+                .with_threshold((floor + GATE_MARGIN_DB).clamp(-80.0, -30.0))
+                .with_range((requirements.noise_floor_max_db - floor - 3.0).clamp(-60.0, -3.0))
+                .build();
+            builder = builder.with_stage(stage(ProcessorRegistry::NOISE_GATE, &gate)?);
+        }
+        let normalize = fixes.contains(&AcxFix::Normalizer);
+        if normalize {
+            let normalizer = NormalizerConfig::builder()
+                .with_algorithm(NormalizationAlgorithm::Rms)
+                .with_target_loudness((requirements.rms_min_db + requirements.rms_max_db) / 2.0)
+                .with_limiting(false)
+                .build();
+            builder = builder.with_stage(stage(ProcessorRegistry::NORMALIZER, &normalizer)?);
+        }
+        if normalize || fixes.contains(&AcxFix::Limiter) {
+            let limiter = LimiterConfig::builder()
+                .with_ceiling(requirements.peak_max_db - 0.5)
+                .build();
+            builder = builder.with_stage(stage(ProcessorRegistry::LIMITER, &limiter)?);
+        }
+
+        let config = builder.build();
+        ConfigValidator::validate_config(&config)?;
+        Ok(config)
+    }
+}
+
+fn stage<T: Serialize>(name: &str, params: &T) -> AppResult<StageConfig> {
+    StageConfig::with_params(name, params).map_err(AppError::from)
+}
+
+/// Compliance report for a set of files, such as the chapters of one book
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AcxReport {
+    /// Per-file reports
+    pub files: Vec<AcxFileReport>,
+    /// Files that could not be analyzed, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub errors: Vec<(PathBuf, String)>,
+    /// Checks across the whole set (format consistency)
+    pub findings: Vec<AcxFinding>,
+    /// Whether every file and set-level check passed
+    pub passed: bool,
+}
+
+/// Checks audio against distributor requirements
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcxChecker {
+    requirements: AcxRequirements,
+}
+
+impl AcxChecker {
+    /// Creates a checker for the given requirements
+    #[must_use]
+    pub const fn new(requirements: AcxRequirements) -> Self {
+        Self { requirements }
+    }
+
+    /// Returns the requirements being checked
+    #[must_use]
+    pub const fn requirements(&self) -> &AcxRequirements {
+        &self.requirements
+    }
+
+    /// Checks an in-memory buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if
+    /// the buffer is empty or has an invalid format.
+    pub fn check_buffer(&self, buffer: &AudioBuffer<f32>) -> Result<AcxFileReport> {
+        validate_buffer(buffer)?;
+        let mut analyzer =
+            AcxAnalyzer::new(buffer.sample_rate, buffer.channels, &self.requirements)?;
+        analyzer.push_interleaved(&buffer.data);
+        Ok(self.evaluate(None, analyzer.finish()))
+    }
+
+    /// Decodes and checks a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded or contains no audio.
+    pub fn check_file(&self, path: &Path) -> AppResult<AcxFileReport> {
+        let mut decoder = AudioDecoder::open(path)?;
+        let mut analyzer: Option<AcxAnalyzer> = None;
+
+        while let Some(packet) = decoder.next_packet()? {
+            if packet.data.is_empty() {
+                continue;
+            }
+            let analyzer = match &mut analyzer {
+                Some(analyzer) => analyzer,
+                None => analyzer.insert(AcxAnalyzer::new(
+                    packet.sample_rate,
+                    packet.channels,
+                    &self.requirements,
+                )?),
+            };
+            analyzer.push_interleaved(&packet.data);
+        }
+
+        let analyzer =
+            analyzer.ok_or_else(|| AppError::Audio(format!("No audio in {}", path.display())))?;
+        Ok(self.evaluate(Some(path.to_path_buf()), analyzer.finish()))
+    }
+
+    /// Checks every file and the consistency of their formats
+    ///
+    /// Files that cannot be decoded are listed in [`AcxReport::errors`] and
+    /// make the report fail.
+    pub fn check_files<P: AsRef<Path>>(&self, paths: &[P]) -> AcxReport {
+        let mut report = AcxReport::default();
+        for path in paths {
+            let path = path.as_ref();
+            match self.check_file(path) {
+                Ok(file) => report.files.push(file),
+                Err(e) => {
+                    log::warn!("ACX check failed for {}: {e}", path.display());
+                    report.errors.push((path.to_path_buf(), e.to_string()));
+                }
+            }
+        }
+        report.findings = Self::consistency_findings(&report.files);
+        report.passed = report.errors.is_empty()
+            && report.files.iter().all(|file| file.passed)
+            && report.findings.iter().all(|finding| finding.passed);
+        report
+    }
+
+    /// Set-level checks: every file shares one sample rate and channel count
+    fn consistency_findings(files: &[AcxFileReport]) -> Vec<AcxFinding> {
+        let Some(first) = files.first() else {
+            return Vec::new();
+        };
+        let rates = files.iter().map(|f| f.measurements.sample_rate);
+        let channels = files.iter().map(|f| f.measurements.channels);
+        let distinct_rates = count_distinct(rates);
+        let distinct_channels = count_distinct(channels);
+
+        vec![
+            AcxFinding::new(
+                AcxCheck::SampleRate,
+                distinct_rates == 1,
+                f64::from(first.measurements.sample_rate),
+                format!("one sample rate for all files (found {distinct_rates})"),
+                AcxFix::Resampler,
+            ),
+            AcxFinding::new(
+                AcxCheck::ChannelFormat,
+                distinct_channels == 1,
+                f64::from(first.measurements.channels),
+                format!("one channel count for all files (found {distinct_channels})"),
+                AcxFix::ChannelMixer,
+            ),
+        ]
+    }
+
+    /// Compares measurements against the requirements
+    #[must_use]
+    pub fn evaluate(&self, path: Option<PathBuf>, measurements: AcxMeasurements) -> AcxFileReport {
+        let r = &self.requirements;
+        let m = &measurements;
+        let (head_min, head_max) = r.head_room_tone_secs;
+        let (tail_min, tail_max) = r.tail_room_tone_secs;
+        let room_tone_fix = |secs: f64, min: f64| {
+            if secs < min {
+                AcxFix::AddRoomTone
+            } else {
+                AcxFix::TrimSilence
+            }
+        };
+
+        let findings = vec![
+            AcxFinding::new(
+                AcxCheck::Rms,
+                (r.rms_min_db..=r.rms_max_db).contains(&m.rms_db),
+                f64::from(m.rms_db),
+                format!("{} to {} dBFS", r.rms_min_db, r.rms_max_db),
+                AcxFix::Normalizer,
+            ),
+            AcxFinding::new(
+                AcxCheck::Peak,
+                m.peak_db <= r.peak_max_db,
+                f64::from(m.peak_db),
+                format!("at most {} dBFS", r.peak_max_db),
+                AcxFix::Limiter,
+            ),
+            AcxFinding::new(
+                AcxCheck::NoiseFloor,
+                m.noise_floor_db <= r.noise_floor_max_db,
+                f64::from(m.noise_floor_db),
+                format!("at most {} dBFS", r.noise_floor_max_db),
+                AcxFix::NoiseGate,
+            ),
+            AcxFinding::new(
+                AcxCheck::HeadRoomTone,
+                (head_min..=head_max).contains(&m.head_room_tone_secs),
+                m.head_room_tone_secs,
+                format!("{head_min} to {head_max} s"),
+                room_tone_fix(m.head_room_tone_secs, head_min),
+            ),
+            AcxFinding::new(
+                AcxCheck::TailRoomTone,
+                (tail_min..=tail_max).contains(&m.tail_room_tone_secs),
+                m.tail_room_tone_secs,
+                format!("{tail_min} to {tail_max} s"),
+                room_tone_fix(m.tail_room_tone_secs, tail_min),
+            ),
+            AcxFinding::new(
+                AcxCheck::SampleRate,
+                is_standard_sample_rate(m.sample_rate),
+                f64::from(m.sample_rate),
+                "a standard sample rate".to_string(),
+                AcxFix::Resampler,
+            ),
+            AcxFinding::new(
+                AcxCheck::ChannelFormat,
+                m.channels <= r.max_channels,
+                f64::from(m.channels),
+                format!("at most {} channels", r.max_channels),
+                AcxFix::ChannelMixer,
+            ),
+        ];
+
+        AcxFileReport {
+            path,
+            passed: findings.iter().all(|finding| finding.passed),
+            measurements,
+            findings,
+        }
+    }
+}
+
+fn count_distinct<T: PartialEq>(values: impl Iterator<Item = T>) -> usize {
+    let mut seen = Vec::new();
+    for value in values {
+        if !seen.contains(&value) {
+            seen.push(value);
+        }
+    }
+    seen.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+    use crate::audio::processing::AudioProcessingPipeline;
+    use crate::audio::processing::utils::gain::db_to_linear;
+    use crate::test_utils::audio::create_sine_buffer;
+
+    const RATE: u32 = 44100;
+
+    /// Room tone, a stretch of tone standing in for narration, then room tone
+    fn chapter(
+        head_secs: f32,
+        tone_amplitude: f32,
+        tail_secs: f32,
+        noise_db: f32,
+    ) -> AudioBuffer<f32> {
+        let noise = db_to_linear(noise_db);
+        let mut seed = 0x1234_5678_u32;
+        let mut hiss = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (f32::from(u16::try_from(seed >> 16).unwrap_or(0)) / 32768.0 - 1.0) * noise
+        };
+        let frames = |secs: f32| {
+            safe_f64_to_usize_samples((f64::from(secs) * f64::from(RATE)).round()).unwrap()
+        };
+
+        let mut data: Vec<f32> = (0..frames(head_secs)).map(|_| hiss()).collect();
+        let tone = create_sine_buffer(RATE, 1, 220.0, 5.0, tone_amplitude);
+        data.extend(tone.data.iter().map(|s| s + hiss()));
+        data.extend((0..frames(tail_secs)).map(|_| hiss()));
+        AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate: RATE,
+            channels: 1,
+        }
+    }
+
+    fn failed_checks(report: &AcxFileReport) -> Vec<AcxCheck> {
+        report.failures().map(|finding| finding.check).collect()
+    }
+
+    #[test]
+    fn test_compliant_chapter_passes() {
+        // A sine at 0.14 peak has an RMS of about -20 dBFS
+        let report = AcxChecker::default()
+            .check_buffer(&chapter(0.75, 0.14, 2.0, -70.0))
+            .unwrap();
+        assert!(report.passed, "failed: {:?}", failed_checks(&report));
+        assert!((report.measurements.peak_db - linear_to_db(0.14)).abs() < 0.1);
+        assert!((report.measurements.head_room_tone_secs - 0.75).abs() <= 0.05);
+        assert!((report.measurements.tail_room_tone_secs - 2.0).abs() <= 0.05);
+        assert!(report.fixes().is_empty());
+    }
+
+    #[test]
+    fn test_each_failure_names_its_fix() {
+        let loud = AcxChecker::default()
+            .check_buffer(&chapter(0.75, 0.9, 2.0, -70.0))
+            .unwrap();
+        assert_eq!(failed_checks(&loud), vec![AcxCheck::Rms, AcxCheck::Peak]);
+        assert_eq!(loud.fixes(), vec![AcxFix::Normalizer, AcxFix::Limiter]);
+
+        let noisy = AcxChecker::default()
+            .check_buffer(&chapter(0.75, 0.14, 2.0, -48.0))
+            .unwrap();
+        assert!(failed_checks(&noisy).contains(&AcxCheck::NoiseFloor));
+        assert!(noisy.fixes().contains(&AcxFix::NoiseGate));
+
+        let clipped_head = AcxChecker::default()
+            .check_buffer(&chapter(0.1, 0.2, 6.0, -70.0))
+            .unwrap();
+        assert_eq!(
+            failed_checks(&clipped_head),
+            vec![AcxCheck::HeadRoomTone, AcxCheck::TailRoomTone]
+        );
+        assert_eq!(
+            clipped_head.fixes(),
+            vec![AcxFix::AddRoomTone, AcxFix::TrimSilence]
+        );
+    }
+
+    #[test]
+    fn test_remediation_config_cures_levels() {
+        let checker = AcxChecker::default();
+        let mut buffer = chapter(0.75, 0.9, 2.0, -70.0);
+        let report = checker.check_buffer(&buffer).unwrap();
+        let config = report.remediation_config(checker.requirements()).unwrap();
+        let names: Vec<_> = config.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["normalizer", "limiter"]);
+
+        AudioProcessingPipeline::new(config)
+            .unwrap()
+            .process_buffer(&mut buffer)
+            .unwrap();
+        let fixed = checker.check_buffer(&buffer).unwrap();
+        assert!(fixed.passed, "still failing: {:?}", failed_checks(&fixed));
+    }
+
+    #[test]
+    fn test_streamed_packets_match_whole_buffer() {
+        let buffer = chapter(0.75, 0.14, 2.0, -70.0);
+        let requirements = AcxRequirements::default();
+        let mut whole = AcxAnalyzer::new(RATE, 1, &requirements).unwrap();
+        whole.push_interleaved(&buffer.data);
+        let mut packets = AcxAnalyzer::new(RATE, 1, &requirements).unwrap();
+        for packet in buffer.data.chunks(1151) {
+            packets.push_interleaved(packet);
+        }
+        assert_eq!(whole.finish(), packets.finish());
+    }
+
+    #[test]
+    fn test_set_consistency() {
+        let checker = AcxChecker::default();
+        let mono = checker.evaluate(
+            None,
+            checker
+                .check_buffer(&chapter(0.75, 0.14, 2.0, -70.0))
+                .unwrap()
+                .measurements,
+        );
+        let mut stereo = mono.clone();
+        stereo.measurements.channels = 2;
+        stereo.measurements.sample_rate = 48000;
+
+        let findings = AcxChecker::consistency_findings(&[mono.clone(), mono.clone()]);
+        assert!(findings.iter().all(|finding| finding.passed));
+
+        let findings = AcxChecker::consistency_findings(&[mono, stereo]);
+        let fixes: Vec<_> = findings.iter().filter_map(|finding| finding.fix).collect();
+        assert_eq!(fixes, vec![AcxFix::Resampler, AcxFix::ChannelMixer]);
+    }
+
+    #[test]
+    fn test_missing_file_is_reported() {
+        let report = AcxChecker::default().check_files(&["/nonexistent/chapter01.mp3"]);
+        assert!(!report.passed);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.files.is_empty());
+    }
+}
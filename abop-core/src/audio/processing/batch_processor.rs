@@ -3,8 +3,13 @@
 use super::ProcessingConfig;
 use super::error::{AudioProcessingError, Result};
 use super::file_io::{AudioFileProcessor, FileProcessingOptions};
+use super::report::{BatchProcessingReport, FileProcessingReport};
 use crate::audio::AudioBufferPool;
 use crate::audio::processing::pipeline::AudioProcessingPipeline;
+use crate::db::{JobCursor, JobRepository};
+use crate::error::AppError;
+use crate::models::{Job, JobStatus, JobType};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::{
     Arc,
@@ -34,7 +39,7 @@ impl<'a> CancellationAwareProcessor<'a> {
     ///
     /// **Error Handling**: Provides detailed error context to distinguish between
     /// processing failures and cancellation requests.
-    fn process_file_with_cancellation(&self, input_path: &Path) -> Result<PathBuf> {
+    fn process_file_with_cancellation(&self, input_path: &Path) -> Result<FileProcessingReport> {
         // Pre-processing cancellation check
         if self.cancellation_token.load(Ordering::SeqCst) {
             return Err(AudioProcessingError::Cancelled(format!(
@@ -49,7 +54,7 @@ impl<'a> CancellationAwareProcessor<'a> {
         let mut processor_clone = self.processor.clone();
 
         // Perform the actual file processing
-        let result = processor_clone.process_file(input_path);
+        let result = processor_clone.process_file_with_report(input_path);
 
         // Post-processing cancellation check
         if self.cancellation_token.load(Ordering::SeqCst) {
@@ -82,6 +87,21 @@ pub struct BatchProcessor {
     buffer_pool: Option<Arc<AudioBufferPool<f32>>>,
     /// Flag to signal cancellation
     cancellation_token: Arc<AtomicBool>,
+    /// Persisted job tracking this batch, if any
+    job: Option<Arc<JobCursor>>,
+}
+
+/// Parameters stored with a persisted processing job
+///
+/// Contains everything needed to rebuild the batch after a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingJobParameters {
+    /// Processing pipeline configuration
+    pub config: ProcessingConfig,
+    /// Output file options
+    pub options: FileProcessingOptions,
+    /// Files the batch has to process
+    pub input_paths: Vec<PathBuf>,
 }
 
 /// Result of batch processing operation
@@ -95,6 +115,8 @@ pub struct BatchProcessingResult {
     pub total_time: Duration,
     /// Average time per file
     pub average_time_per_file: Duration,
+    /// Before/after measurements of the successfully processed files
+    pub report: BatchProcessingReport,
 }
 
 impl BatchProcessingResult {
@@ -136,9 +158,101 @@ impl BatchProcessor {
             progress_callback: None,
             buffer_pool: None,
             cancellation_token: Arc::new(AtomicBool::new(false)),
+            job: None,
         })
     }
 
+    /// Track processing in a persisted job
+    ///
+    /// Files the job already committed are skipped, and each processed file is
+    /// recorded so an interrupted batch can resume where it stopped.
+    #[must_use]
+    pub fn with_job(mut self, job: Arc<JobCursor>) -> Self {
+        self.job = Some(job);
+        self
+    }
+
+    /// ID of the persisted job tracking this batch, if any
+    #[must_use]
+    pub fn job_id(&self) -> Option<&str> {
+        self.job.as_deref().map(JobCursor::job_id)
+    }
+
+    /// Create a persisted processing job and a processor tracking it
+    ///
+    /// Returns the processor together with the files to pass to
+    /// [`Self::process_files_detailed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the job cannot be stored or the
+    /// pipeline cannot be created from the configuration.
+    pub fn start_job(
+        repository: Arc<JobRepository>,
+        parameters: ProcessingJobParameters,
+    ) -> Result<(Self, Vec<PathBuf>)> {
+        let serialized = serde_json::to_string(&parameters).map_err(|e| {
+            AudioProcessingError::Configuration(format!("Failed to serialize job parameters: {e}"))
+        })?;
+        let job = repository
+            .create(JobType::Process, None, &serialized)
+            .map_err(AppError::from)?;
+        Self::from_job(repository, &job)
+    }
+
+    /// Rebuild the processor of a persisted processing job
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the job parameters are invalid or the
+    /// job cursor cannot be loaded.
+    pub fn from_job(repository: Arc<JobRepository>, job: &Job) -> Result<(Self, Vec<PathBuf>)> {
+        let parameters: ProcessingJobParameters = job.parameters()?;
+        let cursor = JobCursor::load(repository, &job.id).map_err(AppError::from)?;
+        let processor =
+            Self::new(parameters.config, parameters.options)?.with_job(Arc::new(cursor));
+        Ok((processor, parameters.input_paths))
+    }
+
+    /// Rebuild the processors of all processing jobs that were interrupted
+    ///
+    /// Jobs whose parameters can no longer be loaded are marked as failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the unfinished jobs cannot be loaded.
+    pub fn resume_unfinished(repository: &Arc<JobRepository>) -> Result<Vec<(Self, Vec<PathBuf>)>> {
+        let jobs = repository
+            .find_resumable(JobType::Process)
+            .map_err(AppError::from)?;
+
+        let mut resumed = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            match Self::from_job(repository.clone(), &job) {
+                Ok(batch) => {
+                    log::info!("Resuming processing job {}", job.id);
+                    resumed.push(batch);
+                }
+                Err(e) => {
+                    log::warn!("Cannot resume processing job {}: {}", job.id, e);
+                    repository
+                        .finish(&job.id, JobStatus::Failed, Some(&e.to_string()))
+                        .map_err(AppError::from)?;
+                }
+            }
+        }
+        Ok(resumed)
+    }
+
+    /// Records the final status of the tracked job, if any
+    fn finish_job(&self, status: JobStatus) {
+        if let Some(job) = &self.job
+            && let Err(e) = job.finish(status, None)
+        {
+            log::warn!("Failed to record status of job {}: {}", job.job_id(), e);
+        }
+    }
+
     /// Set a progress callback for reporting batch progress
     #[must_use]
     pub fn with_progress_callback<F>(mut self, callback: F) -> Self
@@ -174,7 +288,8 @@ impl BatchProcessor {
     }
 
     /// Process a list of files, using parallelism if enabled.
-    /// Returns detailed results including successes, failures, and timing information.
+    /// Returns detailed results including successes, failures, timing information
+    /// and a before/after report of every processed file.
     ///
     /// # Errors
     ///
@@ -192,16 +307,44 @@ impl BatchProcessor {
             ));
         }
 
-        let total_files = input_paths.len();
-        let mut successful = Vec::new();
+        // Skip files committed by an earlier run of the tracked job
+        let pending: Vec<&Path> = input_paths
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|path| self.job.as_ref().is_none_or(|job| !job.is_committed(path)))
+            .collect();
+        if let Some(job) = &self.job {
+            if let Err(e) = job.start(input_paths.len()) {
+                log::warn!("Failed to start job {}: {}", job.job_id(), e);
+            }
+            let skipped = input_paths.len() - pending.len();
+            if skipped > 0 {
+                log::info!(
+                    "Resuming job {}: skipping {} already processed files",
+                    job.job_id(),
+                    skipped
+                );
+            }
+        }
+
+        let total_files = pending.len();
+        let mut reports = Vec::new();
         let mut failed = Vec::new();
 
-        if self.enable_parallel {
-            self.process_files_parallel_detailed(input_paths, &mut successful, &mut failed);
+        if pending.is_empty() {
+            // Nothing left to process
+        } else if self.enable_parallel {
+            self.process_files_parallel_detailed(&pending, &mut reports, &mut failed);
         } else {
-            self.process_files_sequential_detailed(input_paths, &mut successful, &mut failed);
+            self.process_files_sequential_detailed(&pending, &mut reports, &mut failed);
         }
 
+        self.finish_job(if !pending.is_empty() && self.is_cancelled() {
+            JobStatus::Cancelled
+        } else {
+            JobStatus::Completed
+        });
+
         let total_time = start_time.elapsed();
         let average_time_per_file = if total_files > 0 {
             // Safe conversion with bounds checking
@@ -222,11 +365,18 @@ impl BatchProcessor {
             Duration::ZERO
         };
 
+        let successful = reports
+            .iter()
+            .map(|report| (report.input_path.clone(), report.output_path.clone()))
+            .collect();
+        let report = BatchProcessingReport::new(reports, failed.len());
+
         Ok(BatchProcessingResult {
             successful,
             failed,
             total_time,
             average_time_per_file,
+            report,
         })
     }
 
@@ -295,7 +445,7 @@ impl BatchProcessor {
     fn process_files_parallel_detailed<P: AsRef<Path> + Send + Sync>(
         &self,
         input_paths: &[P],
-        successful: &mut Vec<(PathBuf, PathBuf)>,
+        successful: &mut Vec<FileProcessingReport>,
         failed: &mut Vec<(PathBuf, AudioProcessingError)>,
     ) {
         use rayon::prelude::*;
@@ -327,10 +477,8 @@ impl BatchProcessor {
                 let input_path = path.as_ref().to_path_buf();
 
                 // Process file with additional cancellation checks during processing
-                match self.process_single_file_with_cancellation_checks(&input_path) {
-                    Ok(output_path) => Ok((input_path, output_path)),
-                    Err(e) => Err((input_path, e)),
-                }
+                self.process_single_file_with_cancellation_checks(&input_path)
+                    .map_err(|e| (input_path, e))
             })
             .collect();
 
@@ -351,7 +499,7 @@ impl BatchProcessor {
         // Separate successful and failed results
         for result in results {
             match result {
-                Ok((input, output)) => successful.push((input, output)),
+                Ok(report) => successful.push(report),
                 Err((input, error)) => failed.push((input, error)),
             }
         }
@@ -394,7 +542,10 @@ impl BatchProcessor {
     ///
     /// **Implementation**: Provides periodic cancellation checks and proper error handling
     /// for responsive cancellation even during intensive processing operations.
-    fn process_single_file_with_cancellation_checks(&self, input_path: &Path) -> Result<PathBuf> {
+    fn process_single_file_with_cancellation_checks(
+        &self,
+        input_path: &Path,
+    ) -> Result<FileProcessingReport> {
         // Pre-processing cancellation check
         if self.is_cancelled() {
             return Err(AudioProcessingError::Cancelled(format!(
@@ -405,7 +556,15 @@ impl BatchProcessor {
 
         // Use a custom processing approach that integrates cancellation checks
         // This avoids the need to create new processor instances for each file
-        self.process_file_with_integrated_cancellation(input_path)
+        let report = self.process_file_with_integrated_cancellation(input_path)?;
+
+        // Record the file so a resumed job does not process it again
+        if let Some(job) = &self.job
+            && let Err(e) = job.commit(&[input_path.to_path_buf()])
+        {
+            log::warn!("Failed to record progress of job {}: {}", job.job_id(), e);
+        }
+        Ok(report)
     }
 
     /// Process a file with integrated cancellation support
@@ -419,7 +578,10 @@ impl BatchProcessor {
     ///
     /// **Future Enhancement**: This approach allows for easy migration to a cancellation-aware
     /// processor implementation when available.
-    fn process_file_with_integrated_cancellation(&self, input_path: &Path) -> Result<PathBuf> {
+    fn process_file_with_integrated_cancellation(
+        &self,
+        input_path: &Path,
+    ) -> Result<FileProcessingReport> {
         // Create a cancellation-aware processor wrapper
         let processor = CancellationAwareProcessor {
             processor: &self.file_processor,
@@ -441,7 +603,7 @@ impl BatchProcessor {
     fn process_files_sequential_detailed<P: AsRef<Path> + Send + Sync>(
         &self,
         input_paths: &[P],
-        successful: &mut Vec<(PathBuf, PathBuf)>,
+        successful: &mut Vec<FileProcessingReport>,
         failed: &mut Vec<(PathBuf, AudioProcessingError)>,
     ) {
         // Reset cancellation state at the start of processing
@@ -465,7 +627,7 @@ impl BatchProcessor {
 
             let input_path = path.as_ref().to_path_buf();
             match self.process_single_file_with_cancellation_checks(&input_path) {
-                Ok(output_path) => successful.push((input_path, output_path)),
+                Ok(report) => successful.push(report),
                 Err(e) => failed.push((input_path, e)),
             }
         }
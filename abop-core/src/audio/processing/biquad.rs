@@ -0,0 +1,295 @@
+//! Second-order IIR filter sections
+//!
+//! This module provides biquad coefficient design following the RBJ audio EQ
+//! cookbook, and a multichannel transposed direct form II filter used by the
+//! equalizer and de-esser. Coefficients and state are kept in `f64` so low
+//! frequency filters remain stable at high sample rates.
+
+use std::f64::consts::PI;
+
+/// Normalised biquad coefficients (`a0` divided out)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadCoefficients {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+}
+
+impl BiquadCoefficients {
+    /// Pass-through filter
+    pub const IDENTITY: Self = Self {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    fn normalised(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// Angular frequency terms shared by every design
+    fn omega(frequency: f32, sample_rate: u32) -> (f64, f64) {
+        let nyquist_limit = f64::from(sample_rate) * 0.49;
+        let w0 = 2.0 * PI * f64::from(frequency).clamp(1.0, nyquist_limit) / f64::from(sample_rate);
+        (w0.cos(), w0.sin())
+    }
+
+    /// Second-order high-pass filter
+    #[must_use]
+    pub fn high_pass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * f64::from(q));
+        Self::normalised(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Second-order low-pass filter
+    #[must_use]
+    pub fn low_pass(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * f64::from(q));
+        Self::normalised(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Band-reject (notch) filter; higher `q` gives a narrower notch
+    #[must_use]
+    pub fn notch(frequency: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let alpha = sin / (2.0 * f64::from(q));
+        Self::normalised(1.0, -2.0 * cos, 1.0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// Peaking (bell) filter
+    #[must_use]
+    pub fn peaking(frequency: f32, gain_db: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let a = 10f64.powf(f64::from(gain_db) / 40.0);
+        let alpha = sin / (2.0 * f64::from(q));
+        Self::normalised(
+            alpha.mul_add(a, 1.0),
+            -2.0 * cos,
+            (-alpha).mul_add(a, 1.0),
+            1.0 + alpha / a,
+            -2.0 * cos,
+            1.0 - alpha / a,
+        )
+    }
+
+    /// Low-shelf filter; `q` of 0.707 gives the steepest monotonic slope
+    #[must_use]
+    pub fn low_shelf(frequency: f32, gain_db: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let a = 10f64.powf(f64::from(gain_db) / 40.0);
+        let alpha = sin / (2.0 * f64::from(q));
+        let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Self::normalised(
+            a * ((a - 1.0).mul_add(-cos, a + 1.0) + sqrt_a_alpha),
+            2.0 * a * (a + 1.0).mul_add(-cos, a - 1.0),
+            a * ((a - 1.0).mul_add(-cos, a + 1.0) - sqrt_a_alpha),
+            (a - 1.0).mul_add(cos, a + 1.0) + sqrt_a_alpha,
+            -2.0 * (a + 1.0).mul_add(cos, a - 1.0),
+            (a - 1.0).mul_add(cos, a + 1.0) - sqrt_a_alpha,
+        )
+    }
+
+    /// High-shelf filter; `q` of 0.707 gives the steepest monotonic slope
+    #[must_use]
+    pub fn high_shelf(frequency: f32, gain_db: f32, q: f32, sample_rate: u32) -> Self {
+        let (cos, sin) = Self::omega(frequency, sample_rate);
+        let a = 10f64.powf(f64::from(gain_db) / 40.0);
+        let alpha = sin / (2.0 * f64::from(q));
+        let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        Self::normalised(
+            a * ((a - 1.0).mul_add(cos, a + 1.0) + sqrt_a_alpha),
+            -2.0 * a * (a + 1.0).mul_add(cos, a - 1.0),
+            a * ((a - 1.0).mul_add(cos, a + 1.0) - sqrt_a_alpha),
+            (a - 1.0).mul_add(-cos, a + 1.0) + sqrt_a_alpha,
+            2.0 * (a + 1.0).mul_add(-cos, a - 1.0),
+            (a - 1.0).mul_add(-cos, a + 1.0) - sqrt_a_alpha,
+        )
+    }
+
+    /// First K-weighting stage of ITU-R BS.1770: the head-effect high shelf
+    ///
+    /// The published 48 kHz filter is re-derived for other sample rates.
+    #[must_use]
+    pub fn k_weighting_shelf(sample_rate: u32) -> Self {
+        const FREQUENCY: f64 = 1_681.974_450_955_533;
+        const GAIN_DB: f64 = 3.999_843_853_973_347;
+        const Q: f64 = 0.707_175_236_955_419_6;
+
+        let k = (PI * FREQUENCY / f64::from(sample_rate)).tan();
+        let vh = 10f64.powf(GAIN_DB / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let k2 = k * k;
+        Self::normalised(
+            vh + vb * k / Q + k2,
+            2.0 * (k2 - vh),
+            vh - vb * k / Q + k2,
+            1.0 + k / Q + k2,
+            2.0 * (k2 - 1.0),
+            1.0 - k / Q + k2,
+        )
+    }
+
+    /// Second K-weighting stage of ITU-R BS.1770: the RLB high-pass
+    #[must_use]
+    pub fn k_weighting_high_pass(sample_rate: u32) -> Self {
+        const FREQUENCY: f64 = 38.135_470_876_024_44;
+        const Q: f64 = 0.500_327_037_323_877_3;
+
+        let k = (PI * FREQUENCY / f64::from(sample_rate)).tan();
+        let k2 = k * k;
+        let a0 = 1.0 + k / Q + k2;
+        Self {
+            b0: 1.0,
+            b1: -2.0,
+            b2: 1.0,
+            a1: 2.0 * (k2 - 1.0) / a0,
+            a2: (1.0 - k / Q + k2) / a0,
+        }
+    }
+}
+
+/// Biquad filter with independent state for each interleaved channel
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    coefficients: BiquadCoefficients,
+    /// Two state variables per channel
+    state: Vec<[f64; 2]>,
+}
+
+impl Biquad {
+    /// Creates a filter with cleared state
+    #[must_use]
+    pub const fn new(coefficients: BiquadCoefficients) -> Self {
+        Self {
+            coefficients,
+            state: Vec::new(),
+        }
+    }
+
+    /// Replaces the coefficients while keeping the filter state
+    pub const fn set_coefficients(&mut self, coefficients: BiquadCoefficients) {
+        self.coefficients = coefficients;
+    }
+
+    /// Clears the filter state
+    pub fn reset(&mut self) {
+        self.state.clear();
+    }
+
+    /// Filters a single sample of the given channel
+    pub fn process_sample(&mut self, channel: usize, input: f32) -> f32 {
+        if channel >= self.state.len() {
+            self.state.resize(channel + 1, [0.0; 2]);
+        }
+        let c = &self.coefficients;
+        let s = &mut self.state[channel];
+        let x = f64::from(input);
+        let y = c.b0.mul_add(x, s[0]);
+        s[0] = c.b1.mul_add(x, (-c.a1).mul_add(y, s[1]));
+        s[1] = c.b2.mul_add(x, -c.a2 * y);
+        #[allow(clippy::cast_possible_truncation)]
+        let output = y as f32;
+        output
+    }
+
+    /// Filters interleaved samples in place
+    pub fn process_interleaved(&mut self, data: &mut [f32], channels: usize) {
+        for frame in data.chunks_mut(channels.max(1)) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                *sample = self.process_sample(channel, *sample);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processing::utils::gain::linear_to_db;
+    use crate::test_utils::audio::{create_sine_buffer, settled_rms};
+
+    fn response_db(coefficients: BiquadCoefficients, frequency: f32) -> f32 {
+        let mut buffer = create_sine_buffer(44100, 1, frequency, 1.0, 0.5);
+        let input = settled_rms(&buffer.data);
+        Biquad::new(coefficients).process_interleaved(&mut buffer.data, 1);
+        linear_to_db(settled_rms(&buffer.data) / input)
+    }
+
+    #[test]
+    fn test_high_pass_response() {
+        let filter = BiquadCoefficients::high_pass(100.0, 0.707, 44100);
+        assert!(response_db(filter, 25.0) < -20.0);
+        assert!((response_db(filter, 100.0) + 3.0).abs() < 0.2);
+        assert!(response_db(filter, 2000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_low_pass_response() {
+        let filter = BiquadCoefficients::low_pass(1000.0, 0.707, 44100);
+        assert!(response_db(filter, 100.0).abs() < 0.1);
+        assert!(response_db(filter, 8000.0) < -30.0);
+    }
+
+    #[test]
+    fn test_peaking_response() {
+        let filter = BiquadCoefficients::peaking(1000.0, 6.0, 1.0, 44100);
+        assert!((response_db(filter, 1000.0) - 6.0).abs() < 0.1);
+        assert!(response_db(filter, 50.0).abs() < 0.2);
+        assert!(response_db(filter, 15000.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_notch_response() {
+        let filter = BiquadCoefficients::notch(60.0, 10.0, 44100);
+        assert!(response_db(filter, 60.0) < -40.0);
+        assert!(response_db(filter, 120.0).abs() < 0.2);
+        assert!(response_db(filter, 1000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_shelf_responses() {
+        let low = BiquadCoefficients::low_shelf(200.0, -6.0, 0.707, 44100);
+        assert!((response_db(low, 30.0) - -6.0).abs() < 0.3);
+        assert!(response_db(low, 5000.0).abs() < 0.1);
+
+        let high = BiquadCoefficients::high_shelf(5000.0, 4.0, 0.707, 44100);
+        assert!((response_db(high, 16000.0) - 4.0).abs() < 0.3);
+        assert!(response_db(high, 200.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_channels_are_independent() {
+        let mut filter = Biquad::new(BiquadCoefficients::low_pass(500.0, 0.707, 44100));
+        let mut data = vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        filter.process_interleaved(&mut data, 2);
+        assert!(data[0] > 0.0);
+        assert!(data[1].abs() < f32::EPSILON);
+        assert!(data[3].abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,496 @@
+//! Composable processing chain
+//!
+//! This module provides the building blocks for assembling the processing
+//! pipeline from an ordered list of stages. Each stage wraps a boxed
+//! [`ProcessingStage`] together with its name and bypass state, and a
+//! [`ProcessorRegistry`] maps stage names from [`StageConfig`] entries to
+//! factories that build the processors.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use serde::de::DeserializeOwned;
+
+use super::config::{
+    ChannelMixerConfig, CompressorConfig, DeEsserConfig, EqualizerConfig, LimiterConfig,
+    NoiseGateConfig, NoiseReductionConfig, NormalizerConfig, ResamplerConfig,
+    SilenceDetectorConfig, StageConfig,
+};
+use super::error::{AudioProcessingError, Result};
+use super::report::{LevelMeasurement, StageMeasurements};
+use super::traits::{
+    AudioProcessor, Bypassable, LatencyReporting, MeasurementReporting, Validatable,
+};
+use super::{
+    AudioNormalizer, ChannelMixer, Compressor, DeEsser, Equalizer, Limiter, NoiseGate,
+    NoiseReducer, Resampler, SilenceDetector,
+};
+use crate::audio::AudioBuffer;
+
+/// A processor that can be placed in the processing chain
+///
+/// This is implemented automatically for every cloneable processor that
+/// implements [`AudioProcessor`], [`Validatable`], [`LatencyReporting`] and
+/// [`MeasurementReporting`].
+pub trait ProcessingStage:
+    AudioProcessor + Validatable + LatencyReporting + MeasurementReporting + fmt::Debug + Send + Sync
+{
+    /// Clones the processor into a new box
+    fn clone_stage(&self) -> Box<dyn ProcessingStage>;
+}
+
+impl<T> ProcessingStage for T
+where
+    T: AudioProcessor
+        + Validatable
+        + LatencyReporting
+        + MeasurementReporting
+        + Clone
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    fn clone_stage(&self) -> Box<dyn ProcessingStage> {
+        Box::new(self.clone())
+    }
+}
+
+/// A named, bypassable stage of the processing chain
+#[derive(Debug)]
+pub struct PipelineStage {
+    name: String,
+    processor: Box<dyn ProcessingStage>,
+    bypassed: bool,
+}
+
+impl PipelineStage {
+    /// Creates an active stage wrapping the given processor
+    #[must_use]
+    pub fn new(name: impl Into<String>, processor: Box<dyn ProcessingStage>) -> Self {
+        Self {
+            name: name.into(),
+            processor,
+            bypassed: false,
+        }
+    }
+
+    /// Returns the registered name of this stage
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the wrapped processor
+    #[must_use]
+    pub fn processor(&self) -> &dyn ProcessingStage {
+        self.processor.as_ref()
+    }
+
+    /// Returns the wrapped processor mutably
+    pub fn processor_mut(&mut self) -> &mut dyn ProcessingStage {
+        self.processor.as_mut()
+    }
+}
+
+impl Clone for PipelineStage {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            processor: self.processor.clone_stage(),
+            bypassed: self.bypassed,
+        }
+    }
+}
+
+impl AudioProcessor for PipelineStage {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if self.bypassed {
+            return Ok(());
+        }
+        self.processor.process(buffer)
+    }
+
+    fn process_complete(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if self.bypassed {
+            return Ok(());
+        }
+        self.processor.process_complete(buffer)
+    }
+
+    fn reset(&mut self) {
+        self.processor.reset();
+    }
+}
+
+impl Bypassable for PipelineStage {
+    fn is_bypassed(&self) -> bool {
+        self.bypassed
+    }
+
+    fn set_bypassed(&mut self, bypassed: bool) {
+        self.bypassed = bypassed;
+    }
+}
+
+impl LatencyReporting for PipelineStage {
+    fn get_latency_samples(&self) -> usize {
+        if self.bypassed {
+            0
+        } else {
+            self.processor.get_latency_samples()
+        }
+    }
+}
+
+impl MeasurementReporting for PipelineStage {
+    fn stage_measurements(
+        &self,
+        before: &LevelMeasurement,
+        after: &LevelMeasurement,
+    ) -> StageMeasurements {
+        if self.bypassed {
+            StageMeasurements::from_levels(before, after)
+        } else {
+            self.processor.stage_measurements(before, after)
+        }
+    }
+}
+
+impl Validatable for PipelineStage {
+    fn validate(&self) -> Result<()> {
+        self.processor.validate()
+    }
+}
+
+/// Factory that builds a processor from its stage parameters
+pub type ProcessorFactory =
+    Arc<dyn Fn(&serde_json::Value) -> Result<Box<dyn ProcessingStage>> + Send + Sync>;
+
+static GLOBAL_REGISTRY: LazyLock<RwLock<ProcessorRegistry>> =
+    LazyLock::new(|| RwLock::new(ProcessorRegistry::with_builtins()));
+
+/// Registry of processors that can be instantiated by name
+///
+/// The registry returned by [`ProcessorRegistry::global`] is used by
+/// [`AudioProcessingPipeline::new`](super::AudioProcessingPipeline::new), so
+/// processors registered there become available to configuration files.
+#[derive(Clone, Default)]
+pub struct ProcessorRegistry {
+    factories: HashMap<String, ProcessorFactory>,
+}
+
+impl fmt::Debug for ProcessorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessorRegistry")
+            .field("processors", &self.names())
+            .finish()
+    }
+}
+
+impl ProcessorRegistry {
+    /// Name of the built-in resampler stage
+    pub const RESAMPLER: &'static str = "resampler";
+    /// Name of the built-in channel mixer stage
+    pub const CHANNEL_MIXER: &'static str = "channel_mixer";
+    /// Name of the built-in normalizer stage
+    pub const NORMALIZER: &'static str = "normalizer";
+    /// Name of the built-in silence detector stage
+    pub const SILENCE_DETECTOR: &'static str = "silence_detector";
+    /// Name of the built-in compressor stage
+    pub const COMPRESSOR: &'static str = "compressor";
+    /// Name of the built-in limiter stage
+    pub const LIMITER: &'static str = "limiter";
+    /// Name of the built-in noise gate stage
+    pub const NOISE_GATE: &'static str = "noise_gate";
+    /// Name of the built-in noise reduction stage
+    pub const NOISE_REDUCTION: &'static str = "noise_reduction";
+    /// Name of the built-in equalizer stage
+    pub const EQUALIZER: &'static str = "equalizer";
+    /// Name of the built-in de-esser stage
+    pub const DE_ESSER: &'static str = "de_esser";
+
+    /// Creates a registry containing the built-in processors
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        registry.register_config(Self::RESAMPLER, |config: ResamplerConfig| {
+            Resampler::new(config)
+        });
+        registry.register_config(Self::CHANNEL_MIXER, |config: ChannelMixerConfig| {
+            ChannelMixer::new(config)
+        });
+        registry.register_config(Self::NORMALIZER, |config: NormalizerConfig| {
+            AudioNormalizer::new(config)
+        });
+        registry.register_config(Self::SILENCE_DETECTOR, |config: SilenceDetectorConfig| {
+            SilenceDetector::new(config)
+        });
+        registry.register_config(Self::COMPRESSOR, |config: CompressorConfig| {
+            Compressor::new(config)
+        });
+        registry.register_config(Self::LIMITER, |config: LimiterConfig| Limiter::new(config));
+        registry.register_config(Self::NOISE_GATE, |config: NoiseGateConfig| {
+            NoiseGate::new(config)
+        });
+        registry.register_config(Self::NOISE_REDUCTION, |config: NoiseReductionConfig| {
+            NoiseReducer::new(config)
+        });
+        registry.register_config(Self::EQUALIZER, |config: EqualizerConfig| {
+            Equalizer::new(config)
+        });
+        registry.register_config(Self::DE_ESSER, |config: DeEsserConfig| DeEsser::new(config));
+        registry
+    }
+
+    /// Returns the process-wide registry used by the pipeline
+    pub fn global() -> &'static RwLock<Self> {
+        &GLOBAL_REGISTRY
+    }
+
+    /// Registers a processor factory, replacing any previous one with the same name
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F)
+    where
+        F: Fn(&serde_json::Value) -> Result<Box<dyn ProcessingStage>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Arc::new(factory));
+    }
+
+    /// Registers a processor built from a deserializable configuration
+    ///
+    /// Stages without parameters use the configuration's default value.
+    pub fn register_config<C, P, F>(&mut self, name: impl Into<String>, build: F)
+    where
+        C: DeserializeOwned + Default,
+        P: ProcessingStage + 'static,
+        F: Fn(C) -> Result<P> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let stage_name = name.clone();
+        self.register(name, move |params| {
+            let config = if params.is_null() {
+                C::default()
+            } else {
+                serde_json::from_value(params.clone()).map_err(|e| {
+                    AudioProcessingError::Configuration(format!(
+                        "Invalid parameters for stage '{stage_name}': {e}"
+                    ))
+                })?
+            };
+            Ok(Box::new(build(config)?) as Box<dyn ProcessingStage>)
+        });
+    }
+
+    /// Returns true if a processor is registered under the given name
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Returns the registered processor names in sorted order
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Builds a pipeline stage from its configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError::Configuration`] if no processor is
+    /// registered under the stage name, or the factory's error if the
+    /// parameters are invalid.
+    pub fn create(&self, stage: &StageConfig) -> Result<PipelineStage> {
+        let factory = self.factories.get(&stage.name).ok_or_else(|| {
+            AudioProcessingError::Configuration(format!(
+                "Unknown processing stage '{}'",
+                stage.name
+            ))
+        })?;
+        let mut pipeline_stage = PipelineStage::new(stage.name.clone(), factory(&stage.params)?);
+        pipeline_stage.set_bypassed(stage.bypassed);
+        Ok(pipeline_stage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processing::{AudioProcessingPipeline, ProcessingConfig};
+    use crate::test_utils::audio::create_test_buffer;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct GainConfig {
+        gain: f32,
+    }
+
+    #[derive(Debug, Clone)]
+    struct Gain(GainConfig);
+
+    impl AudioProcessor for Gain {
+        fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+            for sample in &mut buffer.data {
+                *sample *= self.0.gain;
+            }
+            Ok(())
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    impl Validatable for Gain {
+        fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl LatencyReporting for Gain {
+        fn get_latency_samples(&self) -> usize {
+            7
+        }
+    }
+
+    impl MeasurementReporting for Gain {}
+
+    fn registry_with_gain() -> ProcessorRegistry {
+        let mut registry = ProcessorRegistry::with_builtins();
+        registry.register_config("gain", |config: GainConfig| Ok(Gain(config)));
+        registry
+    }
+
+    fn gain_stage(gain: f32) -> StageConfig {
+        StageConfig::with_params("gain", &GainConfig { gain }).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_names() {
+        let registry = ProcessorRegistry::with_builtins();
+        assert_eq!(
+            registry.names(),
+            vec![
+                "channel_mixer",
+                "compressor",
+                "de_esser",
+                "equalizer",
+                "limiter",
+                "noise_gate",
+                "noise_reduction",
+                "normalizer",
+                "resampler",
+                "silence_detector"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_stage_is_rejected() {
+        let registry = ProcessorRegistry::with_builtins();
+        assert!(registry.create(&StageConfig::new("reverb")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_params_are_rejected() {
+        let registry = ProcessorRegistry::with_builtins();
+        let stage = StageConfig {
+            params: serde_json::json!({ "target_sample_rate": "fast" }),
+            ..StageConfig::new(ProcessorRegistry::RESAMPLER)
+        };
+        assert!(registry.create(&stage).is_err());
+    }
+
+    #[test]
+    fn test_custom_stage_runs_in_order() {
+        let config = ProcessingConfig {
+            stages: vec![gain_stage(0.5), gain_stage(4.0)],
+            ..Default::default()
+        };
+        let mut pipeline =
+            AudioProcessingPipeline::with_registry(config, &registry_with_gain()).unwrap();
+        assert_eq!(
+            pipeline
+                .stages()
+                .iter()
+                .map(PipelineStage::name)
+                .collect::<Vec<_>>(),
+            vec!["gain", "gain"]
+        );
+
+        let mut buffer = create_test_buffer(44100, 1, 0.01, Some(0.25));
+        let expected: Vec<f32> = buffer.data.iter().map(|s| s * 2.0).collect();
+        pipeline.process_buffer(&mut buffer).unwrap();
+        assert_eq!(buffer.data, expected);
+        assert_eq!(pipeline.get_total_latency_samples(), 14);
+    }
+
+    #[test]
+    fn test_bypassed_stage_is_skipped() {
+        let config = ProcessingConfig {
+            stages: vec![gain_stage(0.0).bypassed(true), gain_stage(2.0)],
+            ..Default::default()
+        };
+        let mut pipeline =
+            AudioProcessingPipeline::with_registry(config, &registry_with_gain()).unwrap();
+        assert_eq!(pipeline.get_total_latency_samples(), 7);
+
+        let mut buffer = create_test_buffer(44100, 1, 0.01, Some(0.25));
+        let original = buffer.data.clone();
+        pipeline.process_buffer(&mut buffer).unwrap();
+        assert!(
+            buffer
+                .data
+                .iter()
+                .zip(&original)
+                .all(|(a, b)| *a == b * 2.0)
+        );
+
+        let stage = &mut pipeline.stages_mut()[0];
+        stage.toggle_bypass();
+        assert!(!stage.is_bypassed());
+        assert_eq!(pipeline.get_total_latency_samples(), 14);
+    }
+
+    #[test]
+    fn test_stages_round_trip_through_serde() {
+        let config = ProcessingConfig::builder()
+            .with_stage(StageConfig::new(ProcessorRegistry::NORMALIZER))
+            .with_stage(StageConfig::new(ProcessorRegistry::SILENCE_DETECTOR).bypassed(true))
+            .build();
+        let json = serde_json::to_string(&config).unwrap();
+        let restored: ProcessingConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.stages, config.stages);
+
+        let pipeline = AudioProcessingPipeline::new(restored).unwrap();
+        assert_eq!(pipeline.stages().len(), 2);
+        assert!(pipeline.stages()[1].is_bypassed());
+    }
+
+    #[test]
+    fn test_component_configs_build_legacy_order() {
+        let config = ProcessingConfig::builder()
+            .with_silence_detector(SilenceDetectorConfig::default())
+            .with_target_sample_rate(22050)
+            .with_normalizer(NormalizerConfig::default())
+            .with_de_esser(DeEsserConfig::default())
+            .with_equalizer(EqualizerConfig::builder().for_voice().build())
+            .build();
+        let pipeline = AudioProcessingPipeline::new(config).unwrap();
+        assert_eq!(
+            pipeline
+                .stages()
+                .iter()
+                .map(PipelineStage::name)
+                .collect::<Vec<_>>(),
+            vec![
+                "resampler",
+                "equalizer",
+                "de_esser",
+                "normalizer",
+                "silence_detector"
+            ]
+        );
+    }
+}
@@ -1,14 +1,22 @@
 //! Audio channel mixing and conversion functionality
 //!
-//! This module provides channel conversion from stereo to mono audio only.
+//! This module downmixes mono, stereo, 2.1, 5.1 and 7.1 audio to smaller
+//! layouts using ITU-R BS.775 coefficients, mid/side voice extraction or an
+//! explicit mixing matrix. Upmixing is not supported.
 
 use super::{
-    config::ChannelMixerConfig,
+    config::{ChannelMixerConfig, MixingAlgorithm},
     error::{AudioProcessingError, Result},
-    traits::{AudioProcessor, Configurable, LatencyReporting, Validatable},
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
     validation::ConfigValidator,
 };
-use crate::audio::AudioBuffer;
+use crate::audio::{AudioBuffer, ChannelLayout, Speaker};
+
+/// -3 dB, the BS.775 level for folding centre and surround channels
+const MINUS_3DB: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Mixing matrix with one row of input coefficients per output channel
+type MixMatrix = Vec<Vec<f32>>;
 
 /// Channel mixing error type
 #[derive(Debug, thiserror::Error)]
@@ -27,10 +35,10 @@ pub enum ChannelMixerError {
     ProcessingError(String),
 }
 
-/// Audio channel mixer for converting stereo to mono
+/// Audio channel mixer for downmixing to fewer channels
 ///
-/// Supports conversion from stereo to mono audio formats using
-/// various mixing algorithms.
+/// Input and output layouts are taken from the configuration or inferred
+/// from the channel counts.
 #[derive(Debug, Clone)]
 pub struct ChannelMixer {
     config: ChannelMixerConfig,
@@ -44,6 +52,7 @@ impl ChannelMixer {
     /// Returns [`AudioProcessingError`] if the channel mixer configuration validation fails.
     pub fn new(config: ChannelMixerConfig) -> Result<Self> {
         ConfigValidator::validate_channel_mixer_config(&config)?;
+        config.validate()?;
         Ok(Self { config })
     }
 
@@ -61,9 +70,19 @@ impl ChannelMixer {
         Self::new(config)
     }
 
-    /// Converts the audio buffer to the target number of channels
-    fn convert_channels(&self, buffer: &mut AudioBuffer<f32>, target_channels: u16) -> Result<()> {
-        if buffer.channels == target_channels {
+    /// Converts the audio buffer to the configured output channels
+    fn convert_channels(&self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if let MixingAlgorithm::Custom { matrix } = &self.config.mix_algorithm {
+            return Self::apply_matrix(buffer, matrix);
+        }
+        let Some(target_channels) = self.config.output_channels() else {
+            return Ok(());
+        };
+        let voice_extract = matches!(
+            self.config.mix_algorithm,
+            MixingAlgorithm::CenterVoiceExtract { .. }
+        );
+        if buffer.channels == target_channels && !voice_extract {
             return Ok(());
         }
         log::debug!(
@@ -73,13 +92,204 @@ impl ChannelMixer {
             self.config.mix_algorithm
         );
 
-        match (buffer.channels, target_channels) {
-            (2, 1) => self.stereo_to_mono(buffer),
-            _ => Err(AudioProcessingError::ChannelMixer(format!(
+        let unsupported = || {
+            AudioProcessingError::ChannelMixer(format!(
                 "Unsupported channel conversion: {} -> {}",
                 buffer.channels, target_channels
-            ))),
+            ))
+        };
+        let input = self.input_layout(buffer.channels).ok_or_else(unsupported)?;
+        let output = self.config.output_layout().ok_or_else(unsupported)?;
+        if output.channel_count() > input.channel_count() {
+            return Err(unsupported());
+        }
+
+        if input == ChannelLayout::Stereo && output == ChannelLayout::Mono && !voice_extract {
+            // Fast path for the common audiobook case
+            return self.stereo_to_mono(buffer);
+        }
+        let matrix = self.mixing_matrix(input, output);
+        Self::apply_matrix(buffer, &matrix)
+    }
+
+    /// Layout of the input, checked against the buffer's channel count
+    fn input_layout(&self, channels: u16) -> Option<ChannelLayout> {
+        match self.config.input_layout {
+            Some(layout) if layout.channel_count() == channels => Some(layout),
+            Some(_) => None,
+            None => ChannelLayout::from_channel_count(channels),
+        }
+    }
+
+    /// Builds the mixing matrix for a downmix between two layouts
+    #[must_use]
+    pub fn mixing_matrix(&self, input: ChannelLayout, output: ChannelLayout) -> MixMatrix {
+        let mut matrix = match (&self.config.mix_algorithm, output) {
+            (MixingAlgorithm::Custom { matrix }, _) => return matrix.clone(),
+            (MixingAlgorithm::CenterVoiceExtract { side_level }, _) => {
+                Self::voice_extract_matrix(input, output, *side_level)
+            }
+            (MixingAlgorithm::Average, ChannelLayout::Mono) => {
+                let speakers = input.speakers();
+                let count = speakers
+                    .iter()
+                    .filter(|speaker| **speaker != Speaker::LowFrequency)
+                    .count();
+                #[allow(clippy::cast_precision_loss)]
+                let weight = 1.0 / count as f32;
+                vec![
+                    speakers
+                        .iter()
+                        .map(|speaker| {
+                            if *speaker == Speaker::LowFrequency {
+                                0.0
+                            } else {
+                                weight
+                            }
+                        })
+                        .collect(),
+                ]
+            }
+            (MixingAlgorithm::LeftOnly, ChannelLayout::Mono) => {
+                vec![Self::speaker_row(input, &[(Speaker::FrontLeft, 1.0)])]
+            }
+            (MixingAlgorithm::RightOnly, ChannelLayout::Mono) => {
+                vec![Self::speaker_row(input, &[(Speaker::FrontRight, 1.0)])]
+            }
+            (
+                MixingAlgorithm::WeightedSum {
+                    left_weight,
+                    right_weight,
+                },
+                ChannelLayout::Mono,
+            ) => vec![Self::speaker_row(
+                input,
+                &[
+                    (Speaker::FrontLeft, *left_weight),
+                    (Speaker::FrontRight, *right_weight),
+                ],
+            )],
+            _ => Self::itu_matrix(input, output),
+        };
+        Self::normalize_rows(&mut matrix);
+        matrix
+    }
+
+    /// ITU-R BS.775 downmix: speakers missing from the output fold into the
+    /// nearest ones at -3 dB (surrounds into mono at -6 dB) and LFE is dropped
+    fn itu_matrix(input: ChannelLayout, output: ChannelLayout) -> MixMatrix {
+        let mut matrix = vec![vec![0.0; input.speakers().len()]; output.speakers().len()];
+        for (column, speaker) in input.speakers().iter().enumerate() {
+            for (target, coefficient) in Self::fold(*speaker, output) {
+                if let Some(row) = output.position(target) {
+                    matrix[row][column] += coefficient;
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Output speakers and coefficients that receive one input speaker
+    fn fold(speaker: Speaker, output: ChannelLayout) -> Vec<(Speaker, f32)> {
+        use Speaker::{
+            FrontCenter, FrontLeft, FrontRight, LowFrequency, RearLeft, RearRight, SideLeft,
+            SideRight,
+        };
+        if output.position(speaker).is_some() {
+            return vec![(speaker, 1.0)];
+        }
+        let has_fronts = output.position(FrontLeft).is_some();
+        match speaker {
+            LowFrequency => Vec::new(),
+            FrontCenter => vec![(FrontLeft, MINUS_3DB), (FrontRight, MINUS_3DB)],
+            FrontLeft | FrontRight => vec![(FrontCenter, MINUS_3DB)],
+            RearLeft if output.position(SideLeft).is_some() => vec![(SideLeft, MINUS_3DB)],
+            RearRight if output.position(SideRight).is_some() => vec![(SideRight, MINUS_3DB)],
+            RearLeft | SideLeft if has_fronts => vec![(FrontLeft, MINUS_3DB)],
+            RearRight | SideRight if has_fronts => vec![(FrontRight, MINUS_3DB)],
+            RearLeft | RearRight | SideLeft | SideRight => vec![(FrontCenter, 0.5)],
+        }
+    }
+
+    /// Mid/side voice extraction; the centre channel is the voice when present
+    fn voice_extract_matrix(input: ChannelLayout, output: ChannelLayout, side: f32) -> MixMatrix {
+        let mid: &[(Speaker, f32)] = if input.position(Speaker::FrontCenter).is_some() {
+            &[(Speaker::FrontCenter, 1.0)]
+        } else {
+            &[(Speaker::FrontLeft, 0.5), (Speaker::FrontRight, 0.5)]
+        };
+        let mid_row = Self::speaker_row(input, mid);
+        if output == ChannelLayout::Mono || input == ChannelLayout::Mono {
+            return vec![mid_row; usize::from(output.channel_count())];
+        }
+
+        let side_row = Self::speaker_row(
+            input,
+            &[(Speaker::FrontLeft, 0.5), (Speaker::FrontRight, -0.5)],
+        );
+        let combine = |sign: f32| -> Vec<f32> {
+            mid_row
+                .iter()
+                .zip(&side_row)
+                .map(|(m, s)| (sign * side).mul_add(*s, *m))
+                .collect()
+        };
+        vec![combine(1.0), combine(-1.0)]
+    }
+
+    /// Matrix row with the given coefficients for speakers of the input layout
+    fn speaker_row(input: ChannelLayout, coefficients: &[(Speaker, f32)]) -> Vec<f32> {
+        let mut row = vec![0.0; input.speakers().len()];
+        for (speaker, coefficient) in coefficients {
+            if let Some(column) = input.position(*speaker) {
+                row[column] += coefficient;
+            }
+        }
+        row
+    }
+
+    /// Scales rows whose absolute coefficients sum above unity so full-scale
+    /// input on every channel cannot clip the output
+    fn normalize_rows(matrix: &mut MixMatrix) {
+        for row in matrix {
+            let gain: f32 = row.iter().map(|coefficient| coefficient.abs()).sum();
+            if gain > 1.0 {
+                for coefficient in row.iter_mut() {
+                    *coefficient /= gain;
+                }
+            }
+        }
+    }
+
+    /// Applies a mixing matrix to every interleaved frame
+    fn apply_matrix(buffer: &mut AudioBuffer<f32>, matrix: &[Vec<f32>]) -> Result<()> {
+        let input_channels = usize::from(buffer.channels);
+        if matrix.first().map(Vec::len) != Some(input_channels) {
+            return Err(AudioProcessingError::ChannelMixer(format!(
+                "Mixing matrix expects {} input channels, buffer has {input_channels}",
+                matrix.first().map_or(0, Vec::len)
+            )));
+        }
+        let output_channels = u16::try_from(matrix.len()).map_err(|_| {
+            AudioProcessingError::ChannelMixer("Mixing matrix has too many rows".to_string())
+        })?;
+
+        let frames = buffer.data.len() / input_channels;
+        let mut data = Vec::with_capacity(frames * matrix.len());
+        for frame in buffer.data.chunks_exact(input_channels) {
+            for row in matrix {
+                data.push(
+                    row.iter()
+                        .zip(frame)
+                        .map(|(coefficient, sample)| coefficient * sample)
+                        .sum(),
+                );
+            }
         }
+
+        buffer.data = data;
+        buffer.channels = output_channels;
+        Ok(())
     }
 
     // Mono to stereo conversion has been removed as per requirements
@@ -162,17 +372,16 @@ impl ChannelMixer {
             
             // Apply mixing algorithm using SIMD
             let mono_vec = match self.config.mix_algorithm {
-                super::config::MixingAlgorithm::Average => {
-                    (left_vec + right_vec) * f32x4::splat(0.5)
-                }
-                super::config::MixingAlgorithm::LeftOnly => left_vec,
-                super::config::MixingAlgorithm::RightOnly => right_vec,
-                super::config::MixingAlgorithm::WeightedSum {
+                MixingAlgorithm::LeftOnly => left_vec,
+                MixingAlgorithm::RightOnly => right_vec,
+                MixingAlgorithm::WeightedSum {
                     left_weight,
                     right_weight,
                 } => {
                     left_vec * f32x4::splat(left_weight) + right_vec * f32x4::splat(right_weight)
                 }
+                // Average, and the BS.775 stereo fold which equals it
+                _ => (left_vec + right_vec) * f32x4::splat(0.5),
             };
             
             // Store results
@@ -184,13 +393,14 @@ impl ChannelMixer {
         for chunk in stereo_data[remainder_start..].chunks(2) {
             if chunk.len() == 2 {
                 let mono_sample = match self.config.mix_algorithm {
-                    super::config::MixingAlgorithm::Average => (chunk[0] + chunk[1]) * 0.5,
-                    super::config::MixingAlgorithm::LeftOnly => chunk[0],
-                    super::config::MixingAlgorithm::RightOnly => chunk[1],
-                    super::config::MixingAlgorithm::WeightedSum {
+                    MixingAlgorithm::LeftOnly => chunk[0],
+                    MixingAlgorithm::RightOnly => chunk[1],
+                    MixingAlgorithm::WeightedSum {
                         left_weight,
                         right_weight,
                     } => chunk[0].mul_add(left_weight, chunk[1] * right_weight),
+                    // Average, and the BS.775 stereo fold which equals it
+                    _ => (chunk[0] + chunk[1]) * 0.5,
                 };
                 new_data.push(mono_sample);
             } else {
@@ -210,13 +420,14 @@ impl ChannelMixer {
         for chunk in stereo_data.chunks(2) {
             if chunk.len() == 2 {
                 let mono_sample = match self.config.mix_algorithm {
-                    super::config::MixingAlgorithm::Average => (chunk[0] + chunk[1]) * 0.5,
-                    super::config::MixingAlgorithm::LeftOnly => chunk[0],
-                    super::config::MixingAlgorithm::RightOnly => chunk[1],
-                    super::config::MixingAlgorithm::WeightedSum {
+                    MixingAlgorithm::LeftOnly => chunk[0],
+                    MixingAlgorithm::RightOnly => chunk[1],
+                    MixingAlgorithm::WeightedSum {
                         left_weight,
                         right_weight,
                     } => chunk[0].mul_add(left_weight, chunk[1] * right_weight),
+                    // Average, and the BS.775 stereo fold which equals it
+                    _ => (chunk[0] + chunk[1]) * 0.5,
                 };
                 new_data.push(mono_sample);
             } else {
@@ -231,10 +442,7 @@ impl ChannelMixer {
 
 impl AudioProcessor for ChannelMixer {
     fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
-        if let Some(target_channels) = self.config.target_channels {
-            self.convert_channels(buffer, target_channels)?;
-        }
-        Ok(())
+        self.convert_channels(buffer)
     }
 
     fn reset(&mut self) {
@@ -261,10 +469,12 @@ impl LatencyReporting for ChannelMixer {
     }
 }
 
+impl MeasurementReporting for ChannelMixer {}
+
 impl Validatable for ChannelMixer {
     fn validate(&self) -> Result<()> {
-        ConfigValidator::validate_channel_mixer_config(&self.config)
-            .map_err(std::convert::Into::into)
+        ConfigValidator::validate_channel_mixer_config(&self.config)?;
+        self.config.validate()
     }
 }
 
@@ -353,6 +563,153 @@ mod tests {
         );
     }
 
+    /// One frame holding a unit impulse on the given channel
+    fn impulse_frame(channels: u16, channel: usize) -> AudioBuffer<f32> {
+        let mut data = vec![0.0; usize::from(channels)];
+        data[channel] = 1.0;
+        AudioBuffer::new(data, crate::audio::SampleFormat::F32, 48000, channels)
+    }
+
+    fn downmix(config: ChannelMixerConfig, mut buffer: AudioBuffer<f32>) -> Vec<f32> {
+        ChannelMixer::new(config)
+            .unwrap()
+            .process(&mut buffer)
+            .unwrap();
+        buffer.data
+    }
+
+    #[test]
+    fn test_surround_to_stereo_uses_bs775_coefficients() {
+        let config = ChannelMixerConfig::builder()
+            .with_target_layout(ChannelLayout::Stereo)
+            .with_itu_downmix()
+            .build();
+        let matrix = ChannelMixer::new(config.clone())
+            .unwrap()
+            .mixing_matrix(ChannelLayout::FivePointOne, ChannelLayout::Stereo);
+        // L = FL + 0.707 C + 0.707 Ls, scaled so the row sums to one
+        let scale = 1.0 / (1.0 + 2.0 * MINUS_3DB);
+        let expected_left = [scale, 0.0, MINUS_3DB * scale, 0.0, MINUS_3DB * scale, 0.0];
+        for (actual, expected) in matrix[0].iter().zip(expected_left) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+
+        // Centre lands equally in both outputs, LFE is dropped
+        let centre = downmix(config.clone(), impulse_frame(6, 2));
+        assert!((centre[0] - centre[1]).abs() < f32::EPSILON);
+        assert!(centre[0] > 0.0);
+        assert_eq!(downmix(config, impulse_frame(6, 3)), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_seven_one_to_five_one() {
+        let config = ChannelMixerConfig::builder()
+            .with_target_layout(ChannelLayout::FivePointOne)
+            .with_itu_downmix()
+            .build();
+        // Rear left folds into the side-left surround of 5.1
+        let rear_left = downmix(config.clone(), impulse_frame(8, 4));
+        assert_eq!(rear_left.len(), 6);
+        assert!(rear_left[4] > 0.0);
+        assert!(rear_left.iter().enumerate().all(|(i, v)| i == 4 || *v == 0.0));
+        // LFE is kept when the output has one
+        assert!((downmix(config, impulse_frame(8, 3))[3] - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_surround_to_mono_average_skips_lfe() {
+        let mut buffer = AudioBuffer::new(
+            vec![1.0; 6],
+            crate::audio::SampleFormat::F32,
+            48000,
+            6,
+        );
+        let mut mixer = ChannelMixer::default();
+        mixer.process(&mut buffer).unwrap();
+        assert_eq!(buffer.channels, 1);
+        assert!((buffer.data[0] - 1.0).abs() < 1e-6);
+        assert_eq!(downmix(ChannelMixerConfig::default(), impulse_frame(6, 3)), vec![0.0]);
+    }
+
+    #[test]
+    fn test_center_voice_extract() {
+        // Stereo input: centred voice passes, side content is attenuated
+        let config = ChannelMixerConfig::builder()
+            .with_target_channels(2)
+            .with_center_voice_extract(0.0)
+            .build();
+        let voice = AudioBuffer::new(
+            vec![0.5, 0.5, 0.5, -0.5],
+            crate::audio::SampleFormat::F32,
+            48000,
+            2,
+        );
+        assert_eq!(downmix(config, voice), vec![0.5, 0.5, 0.0, 0.0]);
+
+        // Surround input: the centre channel is the voice
+        let config = ChannelMixerConfig::builder()
+            .with_target_channels(1)
+            .with_center_voice_extract(0.5)
+            .build();
+        assert_eq!(downmix(config.clone(), impulse_frame(6, 2)), vec![1.0]);
+        assert_eq!(downmix(config, impulse_frame(6, 0)), vec![0.0]);
+    }
+
+    #[test]
+    fn test_custom_matrix() {
+        let config = ChannelMixerConfig::builder()
+            .with_target_channels(2)
+            .with_custom_matrix(vec![vec![0.0, 0.0, 1.0, 0.0], vec![0.5, 0.5, 0.0, 0.0]])
+            .build();
+        let buffer = AudioBuffer::new(
+            vec![0.2, 0.4, 0.9, 1.0],
+            crate::audio::SampleFormat::F32,
+            48000,
+            4,
+        );
+        let mixed = downmix(config.clone(), buffer);
+        assert!((mixed[0] - 0.9).abs() < 1e-6);
+        assert!((mixed[1] - 0.3).abs() < 1e-6);
+
+        // Buffers that do not match the matrix width are rejected
+        let mut stereo = create_stereo_test_buffer(44100, 0.1);
+        assert!(ChannelMixer::new(config).unwrap().process(&mut stereo).is_err());
+    }
+
+    #[test]
+    fn test_layout_and_matrix_validation() {
+        let mismatched = ChannelMixerConfig {
+            target_channels: Some(2),
+            target_layout: Some(ChannelLayout::FivePointOne),
+            ..Default::default()
+        };
+        assert!(ChannelMixer::new(mismatched).is_err());
+
+        let ragged = ChannelMixerConfig::builder()
+            .with_target_channels(2)
+            .with_custom_matrix(vec![vec![0.5, 0.5], vec![1.0]])
+            .build();
+        assert!(ragged.validate().is_err());
+
+        let clipping = ChannelMixerConfig::builder()
+            .with_target_channels(1)
+            .with_custom_matrix(vec![vec![1.0, 1.0]])
+            .build();
+        assert!(clipping.validate().is_err());
+
+        let wrong_rows = ChannelMixerConfig::builder()
+            .with_target_channels(2)
+            .with_custom_matrix(vec![vec![0.5, 0.5]])
+            .build();
+        assert!(wrong_rows.validate().is_err());
+
+        let surround_voice = ChannelMixerConfig::builder()
+            .with_target_layout(ChannelLayout::FivePointOne)
+            .with_center_voice_extract(0.5)
+            .build();
+        assert!(surround_voice.validate().is_err());
+    }
+
     #[test]
     fn test_mixer_reset() {
         let mut mixer = ChannelMixer::default();
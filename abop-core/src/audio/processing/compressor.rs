@@ -0,0 +1,250 @@
+//! Dynamic range compression
+//!
+//! This module provides a feed-forward compressor with a soft-knee gain
+//! computer. Gain reduction is computed from the loudest channel of each
+//! frame so the stereo image stays put, and is smoothed in the dB domain with
+//! separate attack and release time constants.
+
+use super::{
+    config::CompressorConfig,
+    error::Result,
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
+    utils::gain::{db_to_linear, linear_to_db, smoothing_coefficient},
+};
+use crate::audio::AudioBuffer;
+
+/// Feed-forward dynamic range compressor
+///
+/// Gain reduction carries over between calls to [`AudioProcessor::process`],
+/// so consecutive blocks of a stream are compressed seamlessly.
+#[derive(Debug, Clone)]
+pub struct Compressor {
+    config: CompressorConfig,
+    /// Current smoothed gain reduction in dB (zero or negative)
+    gain_reduction_db: f32,
+}
+
+impl Compressor {
+    /// Creates a new compressor with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// configuration validation fails.
+    pub fn new(config: CompressorConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            gain_reduction_db: 0.0,
+        })
+    }
+
+    /// Returns the gain reduction currently applied, in dB
+    #[must_use]
+    pub const fn gain_reduction_db(&self) -> f32 {
+        self.gain_reduction_db
+    }
+
+    /// Static gain computer: the gain change in dB for an input level in dB
+    fn compute_gain_db(&self, level_db: f32) -> f32 {
+        let CompressorConfig {
+            threshold_db,
+            ratio,
+            knee_db,
+            ..
+        } = self.config;
+        let over = level_db - threshold_db;
+        let slope = 1.0 / ratio - 1.0;
+
+        if knee_db > 0.0 && over.abs() * 2.0 <= knee_db {
+            let knee_position = over + knee_db / 2.0;
+            slope * knee_position * knee_position / (2.0 * knee_db)
+        } else if over > 0.0 {
+            slope * over
+        } else {
+            0.0
+        }
+    }
+}
+
+impl AudioProcessor for Compressor {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        let channels = usize::from(buffer.channels.max(1));
+        let attack = smoothing_coefficient(self.config.attack_ms, buffer.sample_rate);
+        let release = smoothing_coefficient(self.config.release_ms, buffer.sample_rate);
+        let makeup_db = self.config.makeup_gain_db;
+
+        for frame in buffer.data.chunks_mut(channels) {
+            let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            let target_db = self.compute_gain_db(linear_to_db(peak));
+
+            let coefficient = if target_db < self.gain_reduction_db {
+                attack
+            } else {
+                release
+            };
+            self.gain_reduction_db =
+                coefficient.mul_add(self.gain_reduction_db - target_db, target_db);
+
+            let gain = db_to_linear(self.gain_reduction_db + makeup_db);
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.gain_reduction_db = 0.0;
+    }
+}
+
+impl Configurable<CompressorConfig> for Compressor {
+    fn configure(&mut self, config: CompressorConfig) -> Result<()> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &CompressorConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for Compressor {
+    fn get_latency_samples(&self) -> usize {
+        // Feed-forward detection works on the current sample
+        0
+    }
+}
+
+impl MeasurementReporting for Compressor {}
+
+impl Validatable for Compressor {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for Compressor {
+    /// Creates a new compressor with default configuration
+    fn default() -> Self {
+        Self {
+            config: CompressorConfig::default(),
+            gain_reduction_db: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+
+    fn square_wave(amplitude: f32, frames: usize) -> AudioBuffer<f32> {
+        AudioBuffer {
+            data: (0..frames)
+                .map(|i| {
+                    if (i / 50) % 2 == 0 {
+                        amplitude
+                    } else {
+                        -amplitude
+                    }
+                })
+                .collect(),
+            format: SampleFormat::F32,
+            sample_rate: 44100,
+            channels: 1,
+        }
+    }
+
+    fn hard_knee(threshold_db: f32, ratio: f32) -> Compressor {
+        Compressor::new(
+            CompressorConfig::builder()
+                .with_threshold(threshold_db)
+                .with_ratio(ratio)
+                .with_knee(0.0)
+                .build(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_steady_state_follows_ratio() {
+        let mut compressor = hard_knee(-20.0, 4.0);
+        // -6 dBFS input is 14 dB over the threshold, so 3.5 dB over after 4:1
+        let mut buffer = square_wave(db_to_linear(-6.0), 44100);
+        compressor.process(&mut buffer).unwrap();
+
+        let settled = buffer.data.last().unwrap().abs();
+        assert!((linear_to_db(settled) - -16.5).abs() < 0.01);
+        assert!((compressor.gain_reduction_db() - -10.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_below_threshold_is_untouched() {
+        let mut compressor = hard_knee(-20.0, 4.0);
+        let mut buffer = square_wave(db_to_linear(-30.0), 4410);
+        let original = buffer.data.clone();
+        compressor.process(&mut buffer).unwrap();
+        assert_eq!(buffer.data, original);
+    }
+
+    #[test]
+    fn test_attack_is_gradual_and_state_persists() {
+        let mut compressor = hard_knee(-20.0, 4.0);
+        let mut first = square_wave(db_to_linear(-6.0), 44);
+        compressor.process(&mut first).unwrap();
+
+        // After 1 ms of a 10 ms attack only part of the reduction is applied
+        let partial = compressor.gain_reduction_db();
+        assert!(partial < 0.0 && partial > -10.5 * 0.5);
+
+        let mut second = square_wave(db_to_linear(-6.0), 44);
+        compressor.process(&mut second).unwrap();
+        assert!(compressor.gain_reduction_db() < partial);
+
+        compressor.reset();
+        assert!(compressor.gain_reduction_db().abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_soft_knee_is_continuous() {
+        let compressor = Compressor::default();
+        let threshold = compressor.config.threshold_db;
+        let half_knee = compressor.config.knee_db / 2.0;
+
+        assert!(compressor.compute_gain_db(threshold - half_knee).abs() < 1e-6);
+        let hard = (1.0 / compressor.config.ratio - 1.0) * half_knee;
+        assert!((compressor.compute_gain_db(threshold + half_knee) - hard).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_makeup_gain() {
+        let mut compressor = Compressor::new(
+            CompressorConfig::builder()
+                .with_threshold(-20.0)
+                .with_makeup_gain(6.0)
+                .build(),
+        )
+        .unwrap();
+        let mut buffer = square_wave(0.01, 100);
+        compressor.process(&mut buffer).unwrap();
+        assert!((buffer.data[0] - 0.01 * db_to_linear(6.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = CompressorConfig {
+            ratio: 0.5,
+            ..Default::default()
+        };
+        assert!(Compressor::new(config).is_err());
+        assert!(
+            CompressorConfig::builder()
+                .with_attack(0.0)
+                .build_validated()
+                .is_err()
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use super::{
     ProcessingConfig,
     channel_mixer::{ChannelMixerConfig, MixingAlgorithm},
+    de_esser::DeEsserConfig,
+    equalizer::EqualizerConfig,
+    noise_reduction::NoiseReductionConfig,
     normalizer::{NormalizationAlgorithm, NormalizerConfig},
     output::{AudioFormat, BitDepth, OutputConfig},
     resampler::{ResampleQuality, ResamplerConfig},
     silence_detector::{SilenceDetectorConfig, SilenceRemovalMode},
+    stage::StageConfig,
 };
 use crate::audio::processing::error::Result;
 use crate::audio::processing::traits::Validatable;
@@ -15,11 +19,15 @@ use std::path::PathBuf;
 pub struct ProcessingConfigBuilder {
     resampler: Option<ResamplerConfig>,
     channel_mixer: Option<ChannelMixerConfig>,
+    noise_reduction: Option<NoiseReductionConfig>,
+    equalizer: Option<EqualizerConfig>,
+    de_esser: Option<DeEsserConfig>,
     normalizer: Option<NormalizerConfig>,
     silence_detector: Option<SilenceDetectorConfig>,
     output: Option<OutputConfig>,
     num_threads: Option<usize>,
     enable_parallel: Option<bool>,
+    stages: Vec<StageConfig>,
 }
 
 impl ProcessingConfigBuilder {
@@ -40,11 +48,32 @@ impl ProcessingConfigBuilder {
 
     /// Set channel mixer configuration
     #[must_use]
-    pub const fn with_channel_mixer(mut self, config: ChannelMixerConfig) -> Self {
+    pub fn with_channel_mixer(mut self, config: ChannelMixerConfig) -> Self {
         self.channel_mixer = Some(config);
         self
     }
 
+    /// Set noise reduction configuration
+    #[must_use]
+    pub const fn with_noise_reduction(mut self, config: NoiseReductionConfig) -> Self {
+        self.noise_reduction = Some(config);
+        self
+    }
+
+    /// Set equalizer configuration
+    #[must_use]
+    pub fn with_equalizer(mut self, config: EqualizerConfig) -> Self {
+        self.equalizer = Some(config);
+        self
+    }
+
+    /// Set de-esser configuration
+    #[must_use]
+    pub const fn with_de_esser(mut self, config: DeEsserConfig) -> Self {
+        self.de_esser = Some(config);
+        self
+    }
+
     /// Set normalizer configuration
     #[must_use]
     pub const fn with_normalizer(mut self, config: NormalizerConfig) -> Self {
@@ -196,6 +225,13 @@ impl ProcessingConfigBuilder {
         self
     }
 
+    /// Append a stage to the processing chain
+    #[must_use]
+    pub fn with_stage(mut self, stage: StageConfig) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
     /// Enable or disable parallel processing
     #[must_use]
     pub const fn with_parallel_processing(mut self, enable: bool) -> Self {
@@ -209,11 +245,15 @@ impl ProcessingConfigBuilder {
         ProcessingConfig {
             resampler: self.resampler,
             channel_mixer: self.channel_mixer,
+            noise_reduction: self.noise_reduction,
+            equalizer: self.equalizer,
+            de_esser: self.de_esser,
             normalizer: self.normalizer,
             silence_detector: self.silence_detector,
             output: self.output.unwrap_or_default(),
             num_threads: self.num_threads,
             enable_parallel: self.enable_parallel.unwrap_or(true),
+            stages: self.stages,
         }
     }
 
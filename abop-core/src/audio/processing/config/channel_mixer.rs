@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use crate::audio::processing::error::Result;
+use crate::audio::ChannelLayout;
+use crate::audio::processing::error::{AudioProcessingError, Result};
 use crate::audio::processing::traits::Validatable;
 
 /// Configuration for channel mixing
@@ -8,6 +9,12 @@ use crate::audio::processing::traits::Validatable;
 pub struct ChannelMixerConfig {
     /// Target number of output channels (optional)
     pub target_channels: Option<u16>,
+    /// Target speaker layout; defaults to the standard layout for `target_channels`
+    #[serde(default)]
+    pub target_layout: Option<ChannelLayout>,
+    /// Speaker layout of the input; defaults to the standard layout for its channel count
+    #[serde(default)]
+    pub input_layout: Option<ChannelLayout>,
     /// Algorithm to use for mixing channels
     pub mix_algorithm: MixingAlgorithm,
 }
@@ -15,7 +22,9 @@ pub struct ChannelMixerConfig {
 impl Default for ChannelMixerConfig {
     fn default() -> Self {
         Self {
-            target_channels: Some(1), // Mono (stereo to mono conversion only)
+            target_channels: Some(1), // Mono (downmix only)
+            target_layout: None,
+            input_layout: None,
             mix_algorithm: MixingAlgorithm::Average,
         }
     }
@@ -31,14 +40,90 @@ impl Validatable for ChannelMixerConfig {
             validation::range(&channels, &1, &32, "Target channels")?;
         }
 
-        // Validate weights for weighted sum algorithm
-        if let MixingAlgorithm::WeightedSum {
-            left_weight,
-            right_weight,
-        } = self.mix_algorithm
+        if let (Some(channels), Some(layout)) = (self.target_channels, self.target_layout)
+            && layout.channel_count() != channels
         {
-            validation::range(&left_weight, &0.0, &1.0, "Left weight")?;
-            validation::range(&right_weight, &0.0, &1.0, "Right weight")?;
+            return Err(AudioProcessingError::config(format!(
+                "Target layout {layout} has {} channels but {channels} target channels were requested",
+                layout.channel_count()
+            )));
+        }
+
+        match &self.mix_algorithm {
+            MixingAlgorithm::WeightedSum {
+                left_weight,
+                right_weight,
+            } => {
+                validation::range(left_weight, &0.0, &1.0, "Left weight")?;
+                validation::range(right_weight, &0.0, &1.0, "Right weight")?;
+            }
+            MixingAlgorithm::CenterVoiceExtract { side_level } => {
+                validation::range(side_level, &0.0, &1.0, "Side level")?;
+                if let Some(channels) = self.output_channels() {
+                    validation::range(&channels, &1, &2, "Center voice extract output channels")?;
+                }
+            }
+            MixingAlgorithm::Custom { matrix } => self.validate_matrix(matrix)?,
+            MixingAlgorithm::Average
+            | MixingAlgorithm::LeftOnly
+            | MixingAlgorithm::RightOnly
+            | MixingAlgorithm::Itu775 => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl ChannelMixerConfig {
+    /// Checks the shape and coefficients of a custom mixing matrix
+    fn validate_matrix(&self, matrix: &[Vec<f32>]) -> Result<()> {
+        use super::validation;
+
+        let rows = u16::try_from(matrix.len()).unwrap_or(u16::MAX);
+        validation::range(&rows, &1, &32, "Mixing matrix output channels")?;
+        if let Some(channels) = self.target_channels
+            && channels != rows
+        {
+            return Err(AudioProcessingError::config(format!(
+                "Mixing matrix has {rows} rows but {channels} target channels were requested"
+            )));
+        }
+
+        let columns = matrix[0].len();
+        let input_channels = u16::try_from(columns).unwrap_or(u16::MAX);
+        validation::range(&input_channels, &1, &32, "Mixing matrix input channels")?;
+        if let Some(layout) = self.input_layout
+            && layout.channel_count() != input_channels
+        {
+            return Err(AudioProcessingError::config(format!(
+                "Mixing matrix has {columns} columns but input layout {layout} has {} channels",
+                layout.channel_count()
+            )));
+        }
+
+        for (index, row) in matrix.iter().enumerate() {
+            if row.len() != columns {
+                return Err(AudioProcessingError::config(format!(
+                    "Mixing matrix row {index} has {} columns, expected {columns}",
+                    row.len()
+                )));
+            }
+            for coefficient in row {
+                if !coefficient.is_finite() {
+                    return Err(AudioProcessingError::config(format!(
+                        "Mixing matrix row {index} contains a non-finite coefficient"
+                    )));
+                }
+                validation::range(coefficient, &-1.0, &1.0, "Mixing matrix coefficient")?;
+            }
+            // Same clipping guard as the weighted sum: a full-scale input on
+            // every channel must not exceed full scale on the output
+            let gain: f32 = row.iter().map(|coefficient| coefficient.abs()).sum();
+            if gain > 1.0 + f32::EPSILON {
+                return Err(AudioProcessingError::config(format!(
+                    "Mixing matrix row {index} sums to {gain:.2}, which may cause clipping"
+                )));
+            }
         }
 
         Ok(())
@@ -46,9 +131,13 @@ impl Validatable for ChannelMixerConfig {
 }
 
 /// Algorithms for mixing channels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+///
+/// `Average`, `LeftOnly`, `RightOnly` and `WeightedSum` describe how a mono
+/// output is folded; any other downmix with them uses the ITU-R BS.775
+/// coefficients.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MixingAlgorithm {
-    /// Average left and right channels
+    /// Average the input channels, excluding LFE
     Average,
     /// Use only the left channel
     LeftOnly,
@@ -61,12 +150,30 @@ pub enum MixingAlgorithm {
         /// Weight for the right channel (0.0 to 1.0)
         right_weight: f32,
     },
+    /// ITU-R BS.775 downmix: centre and surrounds folded in at -3 dB, LFE dropped
+    Itu775,
+    /// Mid/side extraction of the centre-panned voice
+    ///
+    /// The mid signal (or the centre channel, when the input has one) is kept
+    /// and the side signal is attenuated; produces mono or stereo output.
+    CenterVoiceExtract {
+        /// Level of the side signal kept in stereo output (0.0 to 1.0)
+        side_level: f32,
+    },
+    /// Explicit mixing matrix with one row per output channel and one
+    /// coefficient per input channel
+    Custom {
+        /// Output rows of input channel coefficients
+        matrix: Vec<Vec<f32>>,
+    },
 }
 
 /// Builder for `ChannelMixerConfig`
 #[derive(Debug, Default)]
 pub struct ChannelMixerConfigBuilder {
     target_channels: Option<u16>,
+    target_layout: Option<ChannelLayout>,
+    input_layout: Option<ChannelLayout>,
     mix_algorithm: Option<MixingAlgorithm>,
 }
 
@@ -84,37 +191,52 @@ impl ChannelMixerConfigBuilder {
         self
     }
 
+    /// Set the target speaker layout and its channel count
+    #[must_use]
+    pub const fn with_target_layout(mut self, layout: ChannelLayout) -> Self {
+        self.target_layout = Some(layout);
+        self.target_channels = Some(layout.channel_count());
+        self
+    }
+
+    /// Set the speaker layout of the input
+    #[must_use]
+    pub const fn with_input_layout(mut self, layout: ChannelLayout) -> Self {
+        self.input_layout = Some(layout);
+        self
+    }
+
     /// Set the mixing algorithm
     #[must_use]
-    pub const fn with_mixing_algorithm(mut self, algorithm: MixingAlgorithm) -> Self {
+    pub fn with_mixing_algorithm(mut self, algorithm: MixingAlgorithm) -> Self {
         self.mix_algorithm = Some(algorithm);
         self
     }
 
     /// Use average mixing algorithm
     #[must_use]
-    pub const fn with_average_mixing(mut self) -> Self {
+    pub fn with_average_mixing(mut self) -> Self {
         self.mix_algorithm = Some(MixingAlgorithm::Average);
         self
     }
 
     /// Use left-only mixing algorithm
     #[must_use]
-    pub const fn with_left_only_mixing(mut self) -> Self {
+    pub fn with_left_only_mixing(mut self) -> Self {
         self.mix_algorithm = Some(MixingAlgorithm::LeftOnly);
         self
     }
 
     /// Use right-only mixing algorithm
     #[must_use]
-    pub const fn with_right_only_mixing(mut self) -> Self {
+    pub fn with_right_only_mixing(mut self) -> Self {
         self.mix_algorithm = Some(MixingAlgorithm::RightOnly);
         self
     }
 
     /// Use weighted sum mixing algorithm
     #[must_use]
-    pub const fn with_weighted_mixing(mut self, left_weight: f32, right_weight: f32) -> Self {
+    pub fn with_weighted_mixing(mut self, left_weight: f32, right_weight: f32) -> Self {
         self.mix_algorithm = Some(MixingAlgorithm::WeightedSum {
             left_weight,
             right_weight,
@@ -122,11 +244,34 @@ impl ChannelMixerConfigBuilder {
         self
     }
 
+    /// Use the ITU-R BS.775 downmix coefficients
+    #[must_use]
+    pub fn with_itu_downmix(mut self) -> Self {
+        self.mix_algorithm = Some(MixingAlgorithm::Itu775);
+        self
+    }
+
+    /// Extract the centre-panned voice, keeping `side_level` of the side signal
+    #[must_use]
+    pub fn with_center_voice_extract(mut self, side_level: f32) -> Self {
+        self.mix_algorithm = Some(MixingAlgorithm::CenterVoiceExtract { side_level });
+        self
+    }
+
+    /// Mix with an explicit matrix of output rows
+    #[must_use]
+    pub fn with_custom_matrix(mut self, matrix: Vec<Vec<f32>>) -> Self {
+        self.mix_algorithm = Some(MixingAlgorithm::Custom { matrix });
+        self
+    }
+
     /// Build the `ChannelMixerConfig`
     #[must_use]
     pub fn build(self) -> ChannelMixerConfig {
         ChannelMixerConfig {
             target_channels: self.target_channels,
+            target_layout: self.target_layout,
+            input_layout: self.input_layout,
             mix_algorithm: self.mix_algorithm.unwrap_or(MixingAlgorithm::Average),
         }
     }
@@ -150,4 +295,24 @@ impl ChannelMixerConfig {
     pub fn builder() -> ChannelMixerConfigBuilder {
         ChannelMixerConfigBuilder::new()
     }
+
+    /// Layout of the mixer output, if it is a standard one
+    #[must_use]
+    pub fn output_layout(&self) -> Option<ChannelLayout> {
+        self.target_layout.or_else(|| {
+            self.target_channels
+                .and_then(ChannelLayout::from_channel_count)
+        })
+    }
+
+    /// Number of output channels, if the mixer changes the channel count
+    #[must_use]
+    pub fn output_channels(&self) -> Option<u16> {
+        match &self.mix_algorithm {
+            MixingAlgorithm::Custom { matrix } => u16::try_from(matrix.len()).ok(),
+            _ => self
+                .target_channels
+                .or_else(|| self.target_layout.map(ChannelLayout::channel_count)),
+        }
+    }
 }
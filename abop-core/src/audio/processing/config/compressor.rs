@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::Result;
+use crate::audio::processing::traits::Validatable;
+
+/// Configuration for the feed-forward compressor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressorConfig {
+    /// Level above which gain reduction starts, in dBFS
+    pub threshold_db: f32,
+    /// Input to output level ratio above the threshold (1.0 disables compression)
+    pub ratio: f32,
+    /// Time for gain reduction to engage, in milliseconds
+    pub attack_ms: f32,
+    /// Time for gain reduction to recover, in milliseconds
+    pub release_ms: f32,
+    /// Width of the soft knee around the threshold, in dB
+    pub knee_db: f32,
+    /// Gain applied after compression, in dB
+    pub makeup_gain_db: f32,
+}
+
+impl Default for CompressorConfig {
+    fn default() -> Self {
+        Self {
+            threshold_db: -18.0,
+            ratio: 3.0,
+            attack_ms: 10.0,
+            release_ms: 100.0,
+            knee_db: 6.0,
+            makeup_gain_db: 0.0,
+        }
+    }
+}
+
+impl Validatable for CompressorConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.threshold_db, &-96.0, &0.0, "Compressor threshold")?;
+        validation::range(&self.ratio, &1.0, &100.0, "Compressor ratio")?;
+        validation::positive(&self.attack_ms, "Compressor attack")?;
+        validation::positive(&self.release_ms, "Compressor release")?;
+        validation::range(&self.knee_db, &0.0, &24.0, "Compressor knee")?;
+        validation::range(&self.makeup_gain_db, &-24.0, &24.0, "Makeup gain")?;
+
+        Ok(())
+    }
+}
+
+/// Builder for `CompressorConfig`
+#[derive(Debug, Default)]
+pub struct CompressorConfigBuilder {
+    threshold_db: Option<f32>,
+    ratio: Option<f32>,
+    attack_ms: Option<f32>,
+    release_ms: Option<f32>,
+    knee_db: Option<f32>,
+    makeup_gain_db: Option<f32>,
+}
+
+impl CompressorConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the threshold in dBFS
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold_db: f32) -> Self {
+        self.threshold_db = Some(threshold_db);
+        self
+    }
+
+    /// Set the compression ratio
+    #[must_use]
+    pub const fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = Some(ratio);
+        self
+    }
+
+    /// Set the attack time in milliseconds
+    #[must_use]
+    pub const fn with_attack(mut self, attack_ms: f32) -> Self {
+        self.attack_ms = Some(attack_ms);
+        self
+    }
+
+    /// Set the release time in milliseconds
+    #[must_use]
+    pub const fn with_release(mut self, release_ms: f32) -> Self {
+        self.release_ms = Some(release_ms);
+        self
+    }
+
+    /// Set the soft knee width in dB
+    #[must_use]
+    pub const fn with_knee(mut self, knee_db: f32) -> Self {
+        self.knee_db = Some(knee_db);
+        self
+    }
+
+    /// Set the makeup gain in dB
+    #[must_use]
+    pub const fn with_makeup_gain(mut self, makeup_gain_db: f32) -> Self {
+        self.makeup_gain_db = Some(makeup_gain_db);
+        self
+    }
+
+    /// Build the `CompressorConfig`
+    #[must_use]
+    pub fn build(self) -> CompressorConfig {
+        let defaults = CompressorConfig::default();
+        CompressorConfig {
+            threshold_db: self.threshold_db.unwrap_or(defaults.threshold_db),
+            ratio: self.ratio.unwrap_or(defaults.ratio),
+            attack_ms: self.attack_ms.unwrap_or(defaults.attack_ms),
+            release_ms: self.release_ms.unwrap_or(defaults.release_ms),
+            knee_db: self.knee_db.unwrap_or(defaults.knee_db),
+            makeup_gain_db: self.makeup_gain_db.unwrap_or(defaults.makeup_gain_db),
+        }
+    }
+
+    /// Build and validate the `CompressorConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<CompressorConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configure gentle levelling for spoken word
+    #[must_use]
+    pub const fn for_voice(mut self) -> Self {
+        self.threshold_db = Some(-20.0);
+        self.ratio = Some(3.0);
+        self.attack_ms = Some(5.0);
+        self.release_ms = Some(120.0);
+        self.knee_db = Some(6.0);
+        self
+    }
+}
+
+impl CompressorConfig {
+    /// Create a new builder for `CompressorConfig`
+    #[must_use]
+    pub fn builder() -> CompressorConfigBuilder {
+        CompressorConfigBuilder::new()
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::Result;
+use crate::audio::processing::traits::Validatable;
+
+/// Configuration for the split-band de-esser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeEsserConfig {
+    /// Crossover frequency above which sibilance is detected and reduced, in Hz
+    pub frequency_hz: f32,
+    /// High band level above which reduction starts, in dBFS
+    pub threshold_db: f32,
+    /// Compression ratio applied to the high band above the threshold
+    pub ratio: f32,
+    /// Maximum reduction of the high band, in dB (negative)
+    pub max_reduction_db: f32,
+    /// Time for reduction to engage, in milliseconds
+    pub attack_ms: f32,
+    /// Time for reduction to recover, in milliseconds
+    pub release_ms: f32,
+}
+
+impl Default for DeEsserConfig {
+    fn default() -> Self {
+        Self {
+            frequency_hz: 5500.0,
+            threshold_db: -30.0,
+            ratio: 4.0,
+            max_reduction_db: -12.0,
+            attack_ms: 1.0,
+            release_ms: 60.0,
+        }
+    }
+}
+
+impl Validatable for DeEsserConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.frequency_hz, &2000.0, &16000.0, "De-esser frequency")?;
+        validation::range(&self.threshold_db, &-80.0, &0.0, "De-esser threshold")?;
+        validation::range(&self.ratio, &1.0, &100.0, "De-esser ratio")?;
+        validation::range(&self.max_reduction_db, &-48.0, &0.0, "De-esser range")?;
+        validation::positive(&self.attack_ms, "De-esser attack")?;
+        validation::positive(&self.release_ms, "De-esser release")?;
+
+        Ok(())
+    }
+}
+
+/// Builder for `DeEsserConfig`
+#[derive(Debug, Default)]
+pub struct DeEsserConfigBuilder {
+    frequency_hz: Option<f32>,
+    threshold_db: Option<f32>,
+    ratio: Option<f32>,
+    max_reduction_db: Option<f32>,
+    attack_ms: Option<f32>,
+    release_ms: Option<f32>,
+}
+
+impl DeEsserConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the crossover frequency in Hz
+    #[must_use]
+    pub const fn with_frequency(mut self, frequency_hz: f32) -> Self {
+        self.frequency_hz = Some(frequency_hz);
+        self
+    }
+
+    /// Set the threshold in dBFS
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold_db: f32) -> Self {
+        self.threshold_db = Some(threshold_db);
+        self
+    }
+
+    /// Set the compression ratio
+    #[must_use]
+    pub const fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = Some(ratio);
+        self
+    }
+
+    /// Set the maximum reduction in dB
+    #[must_use]
+    pub const fn with_max_reduction(mut self, max_reduction_db: f32) -> Self {
+        self.max_reduction_db = Some(max_reduction_db);
+        self
+    }
+
+    /// Set the attack time in milliseconds
+    #[must_use]
+    pub const fn with_attack(mut self, attack_ms: f32) -> Self {
+        self.attack_ms = Some(attack_ms);
+        self
+    }
+
+    /// Set the release time in milliseconds
+    #[must_use]
+    pub const fn with_release(mut self, release_ms: f32) -> Self {
+        self.release_ms = Some(release_ms);
+        self
+    }
+
+    /// Build the `DeEsserConfig`
+    #[must_use]
+    pub fn build(self) -> DeEsserConfig {
+        let defaults = DeEsserConfig::default();
+        DeEsserConfig {
+            frequency_hz: self.frequency_hz.unwrap_or(defaults.frequency_hz),
+            threshold_db: self.threshold_db.unwrap_or(defaults.threshold_db),
+            ratio: self.ratio.unwrap_or(defaults.ratio),
+            max_reduction_db: self.max_reduction_db.unwrap_or(defaults.max_reduction_db),
+            attack_ms: self.attack_ms.unwrap_or(defaults.attack_ms),
+            release_ms: self.release_ms.unwrap_or(defaults.release_ms),
+        }
+    }
+
+    /// Build and validate the `DeEsserConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<DeEsserConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl DeEsserConfig {
+    /// Create a new builder for `DeEsserConfig`
+    #[must_use]
+    pub fn builder() -> DeEsserConfigBuilder {
+        DeEsserConfigBuilder::new()
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::{AudioProcessingError, Result};
+use crate::audio::processing::traits::Validatable;
+
+/// Filter shape of an equalizer band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EqBandType {
+    /// Removes content below the frequency (gain is ignored)
+    HighPass,
+    /// Boosts or cuts everything below the frequency
+    LowShelf,
+    /// Boosts or cuts a bell around the frequency
+    Peaking,
+    /// Boosts or cuts everything above the frequency
+    HighShelf,
+}
+
+/// A single band of the parametric equalizer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EqBand {
+    /// Filter shape
+    pub band_type: EqBandType,
+    /// Corner or centre frequency in Hz
+    pub frequency_hz: f32,
+    /// Boost or cut in dB (ignored for high-pass bands)
+    pub gain_db: f32,
+    /// Quality factor; 0.707 is a Butterworth response for pass and shelf bands
+    pub q: f32,
+}
+
+impl EqBand {
+    /// High-pass band with a Butterworth response
+    #[must_use]
+    pub const fn high_pass(frequency_hz: f32) -> Self {
+        Self {
+            band_type: EqBandType::HighPass,
+            frequency_hz,
+            gain_db: 0.0,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Low-shelf band
+    #[must_use]
+    pub const fn low_shelf(frequency_hz: f32, gain_db: f32) -> Self {
+        Self {
+            band_type: EqBandType::LowShelf,
+            frequency_hz,
+            gain_db,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Peaking band
+    #[must_use]
+    pub const fn peaking(frequency_hz: f32, gain_db: f32, q: f32) -> Self {
+        Self {
+            band_type: EqBandType::Peaking,
+            frequency_hz,
+            gain_db,
+            q,
+        }
+    }
+
+    /// High-shelf band
+    #[must_use]
+    pub const fn high_shelf(frequency_hz: f32, gain_db: f32) -> Self {
+        Self {
+            band_type: EqBandType::HighShelf,
+            frequency_hz,
+            gain_db,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+}
+
+impl Validatable for EqBand {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.frequency_hz, &10.0, &24000.0, "EQ band frequency")?;
+        validation::range(&self.gain_db, &-24.0, &24.0, "EQ band gain")?;
+        validation::range(&self.q, &0.1, &18.0, "EQ band Q")?;
+
+        Ok(())
+    }
+}
+
+/// Configuration for the parametric equalizer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EqualizerConfig {
+    /// Bands applied in order
+    pub bands: Vec<EqBand>,
+    /// Gain applied after all bands, in dB
+    pub output_gain_db: f32,
+}
+
+impl Default for EqualizerConfig {
+    fn default() -> Self {
+        Self {
+            bands: Vec::new(),
+            output_gain_db: 0.0,
+        }
+    }
+}
+
+impl Validatable for EqualizerConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        if self.bands.len() > 16 {
+            return Err(AudioProcessingError::config(format!(
+                "Equalizer supports at most 16 bands (got {})",
+                self.bands.len()
+            )));
+        }
+        for band in &self.bands {
+            band.validate()?;
+        }
+        validation::range(&self.output_gain_db, &-24.0, &24.0, "Equalizer output gain")?;
+
+        Ok(())
+    }
+}
+
+/// Builder for `EqualizerConfig`
+#[derive(Debug, Default)]
+pub struct EqualizerConfigBuilder {
+    bands: Vec<EqBand>,
+    output_gain_db: Option<f32>,
+}
+
+impl EqualizerConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a band
+    #[must_use]
+    pub fn with_band(mut self, band: EqBand) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// Append a high-pass band
+    #[must_use]
+    pub fn with_high_pass(self, frequency_hz: f32) -> Self {
+        self.with_band(EqBand::high_pass(frequency_hz))
+    }
+
+    /// Append a low-shelf band
+    #[must_use]
+    pub fn with_low_shelf(self, frequency_hz: f32, gain_db: f32) -> Self {
+        self.with_band(EqBand::low_shelf(frequency_hz, gain_db))
+    }
+
+    /// Append a peaking band
+    #[must_use]
+    pub fn with_peaking(self, frequency_hz: f32, gain_db: f32, q: f32) -> Self {
+        self.with_band(EqBand::peaking(frequency_hz, gain_db, q))
+    }
+
+    /// Append a high-shelf band
+    #[must_use]
+    pub fn with_high_shelf(self, frequency_hz: f32, gain_db: f32) -> Self {
+        self.with_band(EqBand::high_shelf(frequency_hz, gain_db))
+    }
+
+    /// Set the output gain in dB
+    #[must_use]
+    pub const fn with_output_gain(mut self, gain_db: f32) -> Self {
+        self.output_gain_db = Some(gain_db);
+        self
+    }
+
+    /// Build the `EqualizerConfig`
+    #[must_use]
+    pub fn build(self) -> EqualizerConfig {
+        EqualizerConfig {
+            bands: self.bands,
+            output_gain_db: self.output_gain_db.unwrap_or(0.0),
+        }
+    }
+
+    /// Build and validate the `EqualizerConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<EqualizerConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configure a voice clarity curve: rumble filter, mud cut, presence and air
+    #[must_use]
+    pub fn for_voice(self) -> Self {
+        self.with_high_pass(80.0)
+            .with_low_shelf(200.0, -2.0)
+            .with_peaking(3500.0, 3.0, 1.0)
+            .with_high_shelf(10000.0, 1.5)
+    }
+}
+
+impl EqualizerConfig {
+    /// Create a new builder for `EqualizerConfig`
+    #[must_use]
+    pub fn builder() -> EqualizerConfigBuilder {
+        EqualizerConfigBuilder::new()
+    }
+}
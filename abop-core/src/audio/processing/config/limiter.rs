@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::Result;
+use crate::audio::processing::traits::Validatable;
+
+/// Configuration for the look-ahead brickwall limiter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimiterConfig {
+    /// Maximum output peak level in dBFS
+    pub ceiling_db: f32,
+    /// Look-ahead time used to ramp the gain down before a peak, in milliseconds
+    pub lookahead_ms: f32,
+    /// Time for gain reduction to recover, in milliseconds
+    pub release_ms: f32,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self {
+            ceiling_db: -1.0,
+            lookahead_ms: 5.0,
+            release_ms: 50.0,
+        }
+    }
+}
+
+impl Validatable for LimiterConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.ceiling_db, &-60.0, &0.0, "Limiter ceiling")?;
+        validation::range(&self.lookahead_ms, &0.1, &50.0, "Limiter look-ahead")?;
+        validation::positive(&self.release_ms, "Limiter release")?;
+
+        Ok(())
+    }
+}
+
+/// Builder for `LimiterConfig`
+#[derive(Debug, Default)]
+pub struct LimiterConfigBuilder {
+    ceiling_db: Option<f32>,
+    lookahead_ms: Option<f32>,
+    release_ms: Option<f32>,
+}
+
+impl LimiterConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the output ceiling in dBFS
+    #[must_use]
+    pub const fn with_ceiling(mut self, ceiling_db: f32) -> Self {
+        self.ceiling_db = Some(ceiling_db);
+        self
+    }
+
+    /// Set the look-ahead time in milliseconds
+    #[must_use]
+    pub const fn with_lookahead(mut self, lookahead_ms: f32) -> Self {
+        self.lookahead_ms = Some(lookahead_ms);
+        self
+    }
+
+    /// Set the release time in milliseconds
+    #[must_use]
+    pub const fn with_release(mut self, release_ms: f32) -> Self {
+        self.release_ms = Some(release_ms);
+        self
+    }
+
+    /// Build the `LimiterConfig`
+    #[must_use]
+    pub fn build(self) -> LimiterConfig {
+        let defaults = LimiterConfig::default();
+        LimiterConfig {
+            ceiling_db: self.ceiling_db.unwrap_or(defaults.ceiling_db),
+            lookahead_ms: self.lookahead_ms.unwrap_or(defaults.lookahead_ms),
+            release_ms: self.release_ms.unwrap_or(defaults.release_ms),
+        }
+    }
+
+    /// Build and validate the `LimiterConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<LimiterConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configure the -3 dB peak ceiling required for audiobook distribution
+    #[must_use]
+    pub const fn for_audiobook(mut self) -> Self {
+        self.ceiling_db = Some(-3.0);
+        self
+    }
+}
+
+impl LimiterConfig {
+    /// Create a new builder for `LimiterConfig`
+    #[must_use]
+    pub fn builder() -> LimiterConfigBuilder {
+        LimiterConfigBuilder::new()
+    }
+}
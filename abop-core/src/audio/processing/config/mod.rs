@@ -54,15 +54,25 @@ use serde::{Deserialize, Serialize};
 // Re-export all public types
 pub use self::{
     channel_mixer::{ChannelMixerConfig, ChannelMixerConfigBuilder, MixingAlgorithm},
+    compressor::{CompressorConfig, CompressorConfigBuilder},
+    de_esser::{DeEsserConfig, DeEsserConfigBuilder},
+    equalizer::{EqBand, EqBandType, EqualizerConfig, EqualizerConfigBuilder},
+    limiter::{LimiterConfig, LimiterConfigBuilder},
+    noise_gate::{NoiseGateConfig, NoiseGateConfigBuilder},
+    noise_reduction::{
+        HumFilterConfig, MainsFrequency, NoiseProfileSource, NoiseReductionConfig,
+        NoiseReductionConfigBuilder,
+    },
     normalizer::{NormalizationAlgorithm, NormalizerConfig, NormalizerConfigBuilder},
     output::{AudioFormat, BitDepth, OutputConfig, OutputConfigBuilder},
     resampler::{ResampleQuality, ResamplerConfig, ResamplerConfigBuilder},
     silence_detector::{SilenceDetectorConfig, SilenceDetectorConfigBuilder, SilenceRemovalMode},
+    stage::StageConfig,
 };
 
 pub use builder::ProcessingConfigBuilder;
 
-use super::error::Result;
+use super::error::{AudioProcessingError, Result};
 use super::traits::Validatable;
 
 /// Configuration for audio processing operations
@@ -95,6 +105,21 @@ pub struct ProcessingConfig {
     /// When present, enables channel mixing operations (e.g., stereo to mono).
     pub channel_mixer: Option<ChannelMixerConfig>,
 
+    /// Spectral noise reduction configuration (optional)
+    /// When present, removes steady hiss and, optionally, mains hum.
+    #[serde(default)]
+    pub noise_reduction: Option<NoiseReductionConfig>,
+
+    /// Parametric equalizer configuration (optional)
+    /// When present, applies tonal shaping such as rumble filtering and presence boost.
+    #[serde(default)]
+    pub equalizer: Option<EqualizerConfig>,
+
+    /// De-esser configuration (optional)
+    /// When present, reduces harsh sibilance above the crossover frequency.
+    #[serde(default)]
+    pub de_esser: Option<DeEsserConfig>,
+
     /// Audio normalization configuration (optional)
     /// When present, enables loudness normalization and peak limiting.
     pub normalizer: Option<NormalizerConfig>,
@@ -114,6 +139,12 @@ pub struct ProcessingConfig {
     /// Whether to enable parallel processing
     /// When true, processing will use multiple threads when available.
     pub enable_parallel: bool,
+
+    /// Ordered processing chain (optional)
+    /// When non-empty, the pipeline is built from these stages instead of the
+    /// component configurations above, which must then be left unset.
+    #[serde(default)]
+    pub stages: Vec<StageConfig>,
 }
 
 impl Default for ProcessingConfig {
@@ -121,11 +152,15 @@ impl Default for ProcessingConfig {
         Self {
             resampler: None,
             channel_mixer: None,
+            noise_reduction: None,
+            equalizer: None,
+            de_esser: None,
             normalizer: None,
             silence_detector: None,
             output: OutputConfig::default(),
             num_threads: None,
             enable_parallel: true,
+            stages: Vec::new(),
         }
     }
 }
@@ -139,6 +174,15 @@ impl Validatable for ProcessingConfig {
         if let Some(ref channel_mixer) = self.channel_mixer {
             channel_mixer.validate()?;
         }
+        if let Some(ref noise_reduction) = self.noise_reduction {
+            noise_reduction.validate()?;
+        }
+        if let Some(ref equalizer) = self.equalizer {
+            equalizer.validate()?;
+        }
+        if let Some(ref de_esser) = self.de_esser {
+            de_esser.validate()?;
+        }
         if let Some(ref normalizer) = self.normalizer {
             normalizer.validate()?;
         }
@@ -146,6 +190,16 @@ impl Validatable for ProcessingConfig {
             silence_detector.validate()?;
         }
 
+        for stage in &self.stages {
+            stage.validate()?;
+        }
+        if !self.stages.is_empty() && !self.component_names().is_empty() {
+            return Err(AudioProcessingError::Configuration(format!(
+                "Processing stages cannot be combined with component configurations ({})",
+                self.component_names().join(", ")
+            )));
+        }
+
         // Output config is always required
         self.output.validate()?;
 
@@ -183,11 +237,43 @@ impl ProcessingConfig {
     pub fn builder() -> ProcessingConfigBuilder {
         ProcessingConfigBuilder::new()
     }
+
+    /// Names of the component configurations that are set
+    ///
+    /// These are ignored when explicit `stages` are listed, so a configuration
+    /// setting both is rejected by validation.
+    #[must_use]
+    pub fn component_names(&self) -> Vec<&'static str> {
+        [
+            ("resampler", self.resampler.is_some()),
+            ("channel_mixer", self.channel_mixer.is_some()),
+            ("noise_reduction", self.noise_reduction.is_some()),
+            ("equalizer", self.equalizer.is_some()),
+            ("de_esser", self.de_esser.is_some()),
+            ("normalizer", self.normalizer.is_some()),
+            ("silence_detector", self.silence_detector.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect()
+    }
 }
 
 // Submodules
 mod builder;
 mod channel_mixer;
+/// Compressor configuration module.
+mod compressor;
+/// De-esser configuration module.
+mod de_esser;
+/// Equalizer configuration module.
+mod equalizer;
+/// Limiter configuration module.
+mod limiter;
+/// Noise gate configuration module.
+mod noise_gate;
+/// Noise reduction configuration module.
+mod noise_reduction;
 /// Normalizer configuration module.
 mod normalizer;
 /// Output configuration module.
@@ -196,5 +282,7 @@ mod output;
 mod resampler;
 /// Silence detector configuration module.
 pub mod silence_detector;
+/// Processing chain stage configuration module.
+mod stage;
 /// Validation configuration module.
 pub mod validation;
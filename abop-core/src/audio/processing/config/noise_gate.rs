@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::Result;
+use crate::audio::processing::traits::Validatable;
+
+/// Configuration for the downward expander / noise gate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoiseGateConfig {
+    /// Level below which the signal is attenuated, in dBFS
+    pub threshold_db: f32,
+    /// Expansion ratio below the threshold (large values behave as a hard gate)
+    pub ratio: f32,
+    /// Maximum attenuation applied when closed, in dB (negative)
+    pub range_db: f32,
+    /// Time for the gate to open, in milliseconds
+    pub attack_ms: f32,
+    /// Time the gate stays open after the signal drops below the threshold, in milliseconds
+    pub hold_ms: f32,
+    /// Time for the gate to close, in milliseconds
+    pub release_ms: f32,
+}
+
+impl Default for NoiseGateConfig {
+    fn default() -> Self {
+        Self {
+            threshold_db: -50.0,
+            ratio: 4.0,
+            range_db: -30.0,
+            attack_ms: 1.0,
+            hold_ms: 50.0,
+            release_ms: 150.0,
+        }
+    }
+}
+
+impl Validatable for NoiseGateConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.threshold_db, &-96.0, &0.0, "Gate threshold")?;
+        validation::range(&self.ratio, &1.0, &100.0, "Expansion ratio")?;
+        validation::range(&self.range_db, &-96.0, &0.0, "Gate range")?;
+        validation::positive(&self.attack_ms, "Gate attack")?;
+        validation::range(&self.hold_ms, &0.0, &5000.0, "Gate hold")?;
+        validation::positive(&self.release_ms, "Gate release")?;
+
+        Ok(())
+    }
+}
+
+/// Builder for `NoiseGateConfig`
+#[derive(Debug, Default)]
+pub struct NoiseGateConfigBuilder {
+    threshold_db: Option<f32>,
+    ratio: Option<f32>,
+    range_db: Option<f32>,
+    attack_ms: Option<f32>,
+    hold_ms: Option<f32>,
+    release_ms: Option<f32>,
+}
+
+impl NoiseGateConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the threshold in dBFS
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold_db: f32) -> Self {
+        self.threshold_db = Some(threshold_db);
+        self
+    }
+
+    /// Set the expansion ratio
+    #[must_use]
+    pub const fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = Some(ratio);
+        self
+    }
+
+    /// Set the maximum attenuation in dB
+    #[must_use]
+    pub const fn with_range(mut self, range_db: f32) -> Self {
+        self.range_db = Some(range_db);
+        self
+    }
+
+    /// Set the attack time in milliseconds
+    #[must_use]
+    pub const fn with_attack(mut self, attack_ms: f32) -> Self {
+        self.attack_ms = Some(attack_ms);
+        self
+    }
+
+    /// Set the hold time in milliseconds
+    #[must_use]
+    pub const fn with_hold(mut self, hold_ms: f32) -> Self {
+        self.hold_ms = Some(hold_ms);
+        self
+    }
+
+    /// Set the release time in milliseconds
+    #[must_use]
+    pub const fn with_release(mut self, release_ms: f32) -> Self {
+        self.release_ms = Some(release_ms);
+        self
+    }
+
+    /// Build the `NoiseGateConfig`
+    #[must_use]
+    pub fn build(self) -> NoiseGateConfig {
+        let defaults = NoiseGateConfig::default();
+        NoiseGateConfig {
+            threshold_db: self.threshold_db.unwrap_or(defaults.threshold_db),
+            ratio: self.ratio.unwrap_or(defaults.ratio),
+            range_db: self.range_db.unwrap_or(defaults.range_db),
+            attack_ms: self.attack_ms.unwrap_or(defaults.attack_ms),
+            hold_ms: self.hold_ms.unwrap_or(defaults.hold_ms),
+            release_ms: self.release_ms.unwrap_or(defaults.release_ms),
+        }
+    }
+
+    /// Build and validate the `NoiseGateConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<NoiseGateConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configure a hard gate that fully mutes below the threshold
+    #[must_use]
+    pub const fn hard_gate(mut self) -> Self {
+        self.ratio = Some(100.0);
+        self.range_db = Some(-96.0);
+        self
+    }
+}
+
+impl NoiseGateConfig {
+    /// Create a new builder for `NoiseGateConfig`
+    #[must_use]
+    pub fn builder() -> NoiseGateConfigBuilder {
+        NoiseGateConfigBuilder::new()
+    }
+}
@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::{AudioProcessingError, Result};
+use crate::audio::processing::traits::Validatable;
+
+/// Where the noise reducer learns its noise profile from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum NoiseProfileSource {
+    /// Learn from the quietest segments found by the silence detector
+    #[default]
+    Auto,
+    /// Learn from a user-selected stretch of room tone, in seconds
+    Range {
+        /// Start of the noise-only region in seconds
+        start_secs: f64,
+        /// End of the noise-only region in seconds
+        end_secs: f64,
+    },
+}
+
+/// Mains frequency targeted by the hum filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MainsFrequency {
+    /// 50 Hz mains (Europe, Asia, Africa, Australia)
+    Hz50,
+    /// 60 Hz mains (North America, parts of South America and Asia)
+    Hz60,
+}
+
+impl MainsFrequency {
+    /// Fundamental frequency in Hz
+    #[must_use]
+    pub const fn hz(self) -> f32 {
+        match self {
+            Self::Hz50 => 50.0,
+            Self::Hz60 => 60.0,
+        }
+    }
+}
+
+/// Configuration for the mains hum notch filters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HumFilterConfig {
+    /// Mains frequency whose harmonics are removed
+    pub mains: MainsFrequency,
+    /// Number of harmonics to notch, including the fundamental
+    pub harmonics: usize,
+    /// Quality factor of each notch; higher is narrower
+    pub q: f32,
+}
+
+impl HumFilterConfig {
+    /// Notches the first eight harmonics of the given mains frequency
+    #[must_use]
+    pub const fn new(mains: MainsFrequency) -> Self {
+        Self {
+            mains,
+            harmonics: 8,
+            q: 30.0,
+        }
+    }
+}
+
+impl Validatable for HumFilterConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        validation::range(&self.harmonics, &1, &40, "Hum harmonics")?;
+        validation::range(&self.q, &1.0, &200.0, "Hum notch Q")?;
+
+        Ok(())
+    }
+}
+
+/// Configuration for the spectral noise reducer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoiseReductionConfig {
+    /// FFT frame size in samples (power of two)
+    pub fft_size: usize,
+    /// Attenuation applied to bins at or below the noise floor, in dB (negative;
+    /// zero disables spectral gating and leaves only the hum filter)
+    pub reduction_db: f32,
+    /// Margin above the learned noise floor treated as noise, in dB
+    pub threshold_db: f32,
+    /// How slowly bin gains recover between frames (0 = instant, below 1)
+    pub smoothing: f32,
+    /// Where the noise profile is learned from
+    pub profile: NoiseProfileSource,
+    /// Audio buffered to learn an automatic profile when streaming, in seconds
+    pub learning_window_secs: f32,
+    /// Mains hum removal (optional)
+    pub hum: Option<HumFilterConfig>,
+}
+
+impl Default for NoiseReductionConfig {
+    fn default() -> Self {
+        Self {
+            fft_size: 2048,
+            reduction_db: -18.0,
+            threshold_db: 6.0,
+            smoothing: 0.6,
+            profile: NoiseProfileSource::Auto,
+            learning_window_secs: 30.0,
+            hum: None,
+        }
+    }
+}
+
+impl Validatable for NoiseReductionConfig {
+    fn validate(&self) -> Result<()> {
+        use super::validation;
+
+        if !self.fft_size.is_power_of_two() || !(256..=16384).contains(&self.fft_size) {
+            return Err(AudioProcessingError::config(format!(
+                "FFT size must be a power of two between 256 and 16384 (got {})",
+                self.fft_size
+            )));
+        }
+        validation::range(&self.reduction_db, &-60.0, &0.0, "Noise reduction")?;
+        validation::range(&self.threshold_db, &0.0, &24.0, "Noise threshold")?;
+        validation::range(&self.smoothing, &0.0, &0.99, "Gain smoothing")?;
+        validation::positive(&self.learning_window_secs, "Learning window")?;
+        if let NoiseProfileSource::Range {
+            start_secs,
+            end_secs,
+        } = self.profile
+        {
+            if start_secs < 0.0 {
+                return Err(AudioProcessingError::config(
+                    "Noise profile start cannot be negative",
+                ));
+            }
+            validation::less_than(&start_secs, &end_secs, "Noise profile start")?;
+        }
+        if let Some(hum) = &self.hum {
+            hum.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for `NoiseReductionConfig`
+#[derive(Debug, Default)]
+pub struct NoiseReductionConfigBuilder {
+    fft_size: Option<usize>,
+    reduction_db: Option<f32>,
+    threshold_db: Option<f32>,
+    smoothing: Option<f32>,
+    profile: Option<NoiseProfileSource>,
+    learning_window_secs: Option<f32>,
+    hum: Option<HumFilterConfig>,
+}
+
+impl NoiseReductionConfigBuilder {
+    /// Create a new builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the FFT frame size in samples
+    #[must_use]
+    pub const fn with_fft_size(mut self, fft_size: usize) -> Self {
+        self.fft_size = Some(fft_size);
+        self
+    }
+
+    /// Set the attenuation of noise bins in dB
+    #[must_use]
+    pub const fn with_reduction(mut self, reduction_db: f32) -> Self {
+        self.reduction_db = Some(reduction_db);
+        self
+    }
+
+    /// Set the margin above the noise floor in dB
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold_db: f32) -> Self {
+        self.threshold_db = Some(threshold_db);
+        self
+    }
+
+    /// Set the gain smoothing between frames
+    #[must_use]
+    pub const fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = Some(smoothing);
+        self
+    }
+
+    /// Learn the noise profile from the given time range in seconds
+    #[must_use]
+    pub const fn with_profile_range(mut self, start_secs: f64, end_secs: f64) -> Self {
+        self.profile = Some(NoiseProfileSource::Range {
+            start_secs,
+            end_secs,
+        });
+        self
+    }
+
+    /// Set the streaming learning window in seconds
+    #[must_use]
+    pub const fn with_learning_window(mut self, seconds: f32) -> Self {
+        self.learning_window_secs = Some(seconds);
+        self
+    }
+
+    /// Enable the hum filter for the given mains frequency
+    #[must_use]
+    pub const fn with_hum_removal(mut self, mains: MainsFrequency) -> Self {
+        self.hum = Some(HumFilterConfig::new(mains));
+        self
+    }
+
+    /// Set the hum filter configuration
+    #[must_use]
+    pub const fn with_hum_filter(mut self, hum: HumFilterConfig) -> Self {
+        self.hum = Some(hum);
+        self
+    }
+
+    /// Build the `NoiseReductionConfig`
+    #[must_use]
+    pub fn build(self) -> NoiseReductionConfig {
+        let defaults = NoiseReductionConfig::default();
+        NoiseReductionConfig {
+            fft_size: self.fft_size.unwrap_or(defaults.fft_size),
+            reduction_db: self.reduction_db.unwrap_or(defaults.reduction_db),
+            threshold_db: self.threshold_db.unwrap_or(defaults.threshold_db),
+            smoothing: self.smoothing.unwrap_or(defaults.smoothing),
+            profile: self.profile.unwrap_or(defaults.profile),
+            learning_window_secs: self
+                .learning_window_secs
+                .unwrap_or(defaults.learning_window_secs),
+            hum: self.hum,
+        }
+    }
+
+    /// Build and validate the `NoiseReductionConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<NoiseReductionConfig> {
+        let config = self.build();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configure for restoring cassette rips: stronger reduction plus hum removal
+    #[must_use]
+    pub const fn for_cassette(self, mains: MainsFrequency) -> Self {
+        self.with_reduction(-24.0)
+            .with_threshold(8.0)
+            .with_hum_removal(mains)
+    }
+}
+
+impl NoiseReductionConfig {
+    /// Create a new builder for `NoiseReductionConfig`
+    #[must_use]
+    pub fn builder() -> NoiseReductionConfigBuilder {
+        NoiseReductionConfigBuilder::new()
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::processing::error::{AudioProcessingError, Result};
+use crate::audio::processing::traits::Validatable;
+
+/// Configuration for a single stage of the processing chain
+///
+/// Stages are looked up by `name` in the
+/// [`ProcessorRegistry`](crate::audio::processing::chain::ProcessorRegistry),
+/// which builds the processor from `params`. The parameter format is defined
+/// by each processor; the built-in stages take their usual component config.
+///
+/// # Examples
+///
+/// ```
+/// use abop_core::audio::processing::config::{NormalizerConfig, StageConfig};
+///
+/// let stage = StageConfig::with_params("normalizer", &NormalizerConfig::default())
+///     .unwrap()
+///     .bypassed(true);
+/// assert_eq!(stage.name, "normalizer");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StageConfig {
+    /// Registered processor name
+    pub name: String,
+
+    /// Whether the stage starts out bypassed
+    #[serde(default)]
+    pub bypassed: bool,
+
+    /// Processor-specific parameters
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub params: serde_json::Value,
+}
+
+impl StageConfig {
+    /// Creates a stage using the processor's default parameters
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            bypassed: false,
+            params: serde_json::Value::Null,
+        }
+    }
+
+    /// Creates a stage from a serializable parameter struct
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError::Configuration`] if the parameters
+    /// cannot be serialized.
+    pub fn with_params<T: Serialize>(name: impl Into<String>, params: &T) -> Result<Self> {
+        let params = serde_json::to_value(params)
+            .map_err(|e| AudioProcessingError::Configuration(e.to_string()))?;
+        Ok(Self {
+            params,
+            ..Self::new(name)
+        })
+    }
+
+    /// Sets the initial bypass state
+    #[must_use]
+    pub const fn bypassed(mut self, bypassed: bool) -> Self {
+        self.bypassed = bypassed;
+        self
+    }
+}
+
+impl Validatable for StageConfig {
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(AudioProcessingError::Configuration(
+                "Processing stage name cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! Split-band de-essing
+//!
+//! This module provides a de-esser that separates the signal at a crossover
+//! frequency, compresses only the upper band when sibilance pushes it over the
+//! threshold, and sums the bands back together. The upper band is derived as
+//! the input minus the low-pass band, so with no reduction applied the output
+//! is identical to the input. Detection runs on a separate high-pass sidechain,
+//! which rejects the phase residue the complementary band carries at low
+//! frequencies.
+
+use super::{
+    biquad::{Biquad, BiquadCoefficients},
+    config::DeEsserConfig,
+    error::Result,
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
+    utils::gain::{db_to_linear, linear_to_db, smoothing_coefficient},
+};
+use crate::audio::AudioBuffer;
+
+/// Split-band de-esser
+///
+/// Filter and gain state carry over between calls to
+/// [`AudioProcessor::process`], so consecutive blocks of a stream are
+/// processed seamlessly.
+#[derive(Debug, Clone)]
+pub struct DeEsser {
+    config: DeEsserConfig,
+    crossover: Biquad,
+    sidechain: Biquad,
+    /// Sample rate the filters were designed for
+    designed_rate: Option<u32>,
+    /// Current smoothed reduction of the upper band in dB (zero or negative)
+    gain_reduction_db: f32,
+}
+
+impl DeEsser {
+    /// Creates a new de-esser with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// configuration validation fails.
+    pub fn new(config: DeEsserConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    const fn from_config(config: DeEsserConfig) -> Self {
+        Self {
+            config,
+            crossover: Biquad::new(BiquadCoefficients::IDENTITY),
+            sidechain: Biquad::new(BiquadCoefficients::IDENTITY),
+            designed_rate: None,
+            gain_reduction_db: 0.0,
+        }
+    }
+
+    /// Returns the reduction currently applied to the upper band, in dB
+    #[must_use]
+    pub const fn gain_reduction_db(&self) -> f32 {
+        self.gain_reduction_db
+    }
+
+    /// Gain change in dB for an upper band level in dB
+    fn compute_gain_db(&self, level_db: f32) -> f32 {
+        let over = level_db - self.config.threshold_db;
+        if over <= 0.0 {
+            0.0
+        } else {
+            ((1.0 / self.config.ratio - 1.0) * over).max(self.config.max_reduction_db)
+        }
+    }
+}
+
+impl AudioProcessor for DeEsser {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        let channels = usize::from(buffer.channels.max(1));
+        if self.designed_rate != Some(buffer.sample_rate) {
+            let q = std::f32::consts::FRAC_1_SQRT_2;
+            self.crossover
+                .set_coefficients(BiquadCoefficients::low_pass(
+                    self.config.frequency_hz,
+                    q,
+                    buffer.sample_rate,
+                ));
+            self.sidechain
+                .set_coefficients(BiquadCoefficients::high_pass(
+                    self.config.frequency_hz,
+                    q,
+                    buffer.sample_rate,
+                ));
+            self.designed_rate = Some(buffer.sample_rate);
+        }
+        let attack = smoothing_coefficient(self.config.attack_ms, buffer.sample_rate);
+        let release = smoothing_coefficient(self.config.release_ms, buffer.sample_rate);
+
+        let mut high = vec![0.0f32; channels];
+        for frame in buffer.data.chunks_mut(channels) {
+            let mut peak = 0.0f32;
+            for (channel, sample) in frame.iter().enumerate() {
+                let low = self.crossover.process_sample(channel, *sample);
+                high[channel] = *sample - low;
+                let detected = self.sidechain.process_sample(channel, *sample);
+                peak = peak.max(detected.abs());
+            }
+
+            let target_db = self.compute_gain_db(linear_to_db(peak));
+            let coefficient = if target_db < self.gain_reduction_db {
+                attack
+            } else {
+                release
+            };
+            self.gain_reduction_db =
+                coefficient.mul_add(self.gain_reduction_db - target_db, target_db);
+
+            // Removing part of the upper band leaves low + high * gain
+            let removed = 1.0 - db_to_linear(self.gain_reduction_db);
+            for (sample, high) in frame.iter_mut().zip(&high) {
+                *sample -= high * removed;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.crossover.reset();
+        self.sidechain.reset();
+        self.gain_reduction_db = 0.0;
+    }
+}
+
+impl Configurable<DeEsserConfig> for DeEsser {
+    fn configure(&mut self, config: DeEsserConfig) -> Result<()> {
+        config.validate()?;
+        *self = Self::from_config(config);
+        Ok(())
+    }
+
+    fn get_config(&self) -> &DeEsserConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for DeEsser {
+    fn get_latency_samples(&self) -> usize {
+        // Detection works on the current sample
+        0
+    }
+}
+
+impl MeasurementReporting for DeEsser {}
+
+impl Validatable for DeEsser {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for DeEsser {
+    /// Creates a new de-esser with default configuration
+    fn default() -> Self {
+        Self::from_config(DeEsserConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::audio::{create_sine_buffer, settled_rms};
+
+    fn change_db(de_esser: &mut DeEsser, frequency: f32, amplitude: f32) -> f32 {
+        de_esser.reset();
+        let mut buffer = create_sine_buffer(44100, 1, frequency, 0.5, amplitude);
+        let input = settled_rms(&buffer.data);
+        de_esser.process(&mut buffer).unwrap();
+        linear_to_db(settled_rms(&buffer.data) / input)
+    }
+
+    #[test]
+    fn test_loud_sibilance_is_reduced() {
+        let mut de_esser = DeEsser::default();
+        let change = change_db(&mut de_esser, 8000.0, 0.5);
+        assert!(change < -6.0, "change {change} dB");
+        assert!(change >= -12.5, "change {change} dB");
+    }
+
+    #[test]
+    fn test_voice_band_is_untouched() {
+        let mut de_esser = DeEsser::default();
+        assert!(change_db(&mut de_esser, 300.0, 0.5).abs() < 0.05);
+        assert!(de_esser.gain_reduction_db().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_quiet_sibilance_below_threshold_passes() {
+        let mut de_esser = DeEsser::default();
+        let mut buffer = create_sine_buffer(44100, 2, 8000.0, 0.2, 0.005);
+        let original = buffer.data.clone();
+        de_esser.process(&mut buffer).unwrap();
+        for (a, b) in buffer.data.iter().zip(&original) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(
+            DeEsserConfig::builder()
+                .with_frequency(500.0)
+                .build_validated()
+                .is_err()
+        );
+        assert!(
+            DeEsser::new(DeEsserConfig {
+                ratio: 0.5,
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Parametric equalization
+//!
+//! This module provides a multi-band parametric equalizer built from cascaded
+//! biquad sections. Each configured band becomes one filter; coefficients are
+//! designed for the sample rate of the buffer being processed and recomputed
+//! whenever that rate changes.
+
+use super::{
+    biquad::{Biquad, BiquadCoefficients},
+    config::{EqBand, EqBandType, EqualizerConfig},
+    error::Result,
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
+    utils::gain::db_to_linear,
+};
+use crate::audio::AudioBuffer;
+
+/// Multi-band parametric equalizer
+///
+/// Filter state carries over between calls to [`AudioProcessor::process`],
+/// so consecutive blocks of a stream are filtered seamlessly.
+#[derive(Debug, Clone)]
+pub struct Equalizer {
+    config: EqualizerConfig,
+    filters: Vec<Biquad>,
+    /// Sample rate the current coefficients were designed for
+    designed_rate: Option<u32>,
+}
+
+impl Equalizer {
+    /// Creates a new equalizer with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// configuration validation fails.
+    pub fn new(config: EqualizerConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    fn from_config(config: EqualizerConfig) -> Self {
+        let filters = config
+            .bands
+            .iter()
+            .map(|_| Biquad::new(BiquadCoefficients::IDENTITY))
+            .collect();
+        Self {
+            config,
+            filters,
+            designed_rate: None,
+        }
+    }
+
+    /// Designs the coefficients of a single band
+    fn design(band: &EqBand, sample_rate: u32) -> BiquadCoefficients {
+        match band.band_type {
+            EqBandType::HighPass => {
+                BiquadCoefficients::high_pass(band.frequency_hz, band.q, sample_rate)
+            }
+            EqBandType::LowShelf => {
+                BiquadCoefficients::low_shelf(band.frequency_hz, band.gain_db, band.q, sample_rate)
+            }
+            EqBandType::Peaking => {
+                BiquadCoefficients::peaking(band.frequency_hz, band.gain_db, band.q, sample_rate)
+            }
+            EqBandType::HighShelf => {
+                BiquadCoefficients::high_shelf(band.frequency_hz, band.gain_db, band.q, sample_rate)
+            }
+        }
+    }
+
+    fn ensure_designed(&mut self, sample_rate: u32) {
+        if self.designed_rate == Some(sample_rate) {
+            return;
+        }
+        for (filter, band) in self.filters.iter_mut().zip(&self.config.bands) {
+            filter.set_coefficients(Self::design(band, sample_rate));
+        }
+        self.designed_rate = Some(sample_rate);
+    }
+}
+
+impl AudioProcessor for Equalizer {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        let channels = usize::from(buffer.channels.max(1));
+        self.ensure_designed(buffer.sample_rate);
+
+        for filter in &mut self.filters {
+            filter.process_interleaved(&mut buffer.data, channels);
+        }
+
+        if self.config.output_gain_db != 0.0 {
+            let gain = db_to_linear(self.config.output_gain_db);
+            for sample in &mut buffer.data {
+                *sample *= gain;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        for filter in &mut self.filters {
+            filter.reset();
+        }
+    }
+}
+
+impl Configurable<EqualizerConfig> for Equalizer {
+    fn configure(&mut self, config: EqualizerConfig) -> Result<()> {
+        config.validate()?;
+        *self = Self::from_config(config);
+        Ok(())
+    }
+
+    fn get_config(&self) -> &EqualizerConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for Equalizer {
+    fn get_latency_samples(&self) -> usize {
+        // IIR sections have no fixed delay
+        0
+    }
+}
+
+impl MeasurementReporting for Equalizer {}
+
+impl Validatable for Equalizer {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for Equalizer {
+    /// Creates a flat equalizer with no bands
+    fn default() -> Self {
+        Self::from_config(EqualizerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processing::utils::gain::linear_to_db;
+    use crate::test_utils::audio::{create_sine_buffer, settled_rms};
+
+    fn response_db(equalizer: &mut Equalizer, frequency: f32) -> f32 {
+        equalizer.reset();
+        let mut buffer = create_sine_buffer(44100, 2, frequency, 1.0, 0.25);
+        let input = settled_rms(&buffer.data);
+        equalizer.process(&mut buffer).unwrap();
+        linear_to_db(settled_rms(&buffer.data) / input)
+    }
+
+    #[test]
+    fn test_flat_equalizer_is_transparent() {
+        let mut equalizer = Equalizer::default();
+        let mut buffer = create_sine_buffer(44100, 2, 440.0, 0.1, 0.5);
+        let original = buffer.data.clone();
+        equalizer.process(&mut buffer).unwrap();
+        assert_eq!(buffer.data, original);
+    }
+
+    #[test]
+    fn test_voice_curve() {
+        let mut equalizer = Equalizer::new(EqualizerConfig::builder().for_voice().build()).unwrap();
+
+        assert!(response_db(&mut equalizer, 30.0) < -15.0);
+        assert!(response_db(&mut equalizer, 3500.0) > 2.0);
+        assert!(response_db(&mut equalizer, 1000.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_output_gain_and_rate_change() {
+        let mut equalizer = Equalizer::new(
+            EqualizerConfig::builder()
+                .with_peaking(1000.0, 6.0, 1.0)
+                .with_output_gain(-6.0)
+                .build(),
+        )
+        .unwrap();
+        assert!(response_db(&mut equalizer, 1000.0).abs() < 0.2);
+
+        // Coefficients follow the buffer sample rate
+        equalizer.reset();
+        let mut buffer = create_sine_buffer(22050, 1, 1000.0, 1.0, 0.25);
+        let input = settled_rms(&buffer.data);
+        equalizer.process(&mut buffer).unwrap();
+        assert!(linear_to_db(settled_rms(&buffer.data) / input).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(
+            EqualizerConfig::builder()
+                .with_peaking(1000.0, 40.0, 1.0)
+                .build_validated()
+                .is_err()
+        );
+        assert!(
+            Equalizer::new(
+                EqualizerConfig::builder()
+                    .with_band(EqBand::peaking(1000.0, 3.0, 0.0))
+                    .build()
+            )
+            .is_err()
+        );
+    }
+}
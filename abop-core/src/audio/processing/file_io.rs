@@ -27,12 +27,17 @@
 //! ```
 
 use super::pipeline::AudioProcessingPipeline;
-use crate::audio::{AudioBuffer, SampleFormat};
-use crate::error::Result;
+use super::report::FileProcessingReport;
+use crate::audio::{AudioBuffer, AudioMetadata, SampleFormat};
+use crate::error::{AppError, Result};
 use crate::utils::casting::domain::audio::{
     safe_i8_to_f32_sample, safe_i16_to_f32_sample, safe_i24_to_f32_sample, safe_i32_to_f32_sample,
 };
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// Options for file processing, such as output format and naming pattern.
 ///
@@ -52,7 +57,7 @@ use std::path::{Path, PathBuf};
 ///     naming_pattern: "{filename}_normalized".to_string(),
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileProcessingOptions {
     /// Output audio sample format (e.g., F32, S16)
     ///
@@ -147,6 +152,48 @@ impl AudioFileProcessor {
         Ok(())
     }
 
+    /// Process a single audio file to an auto-generated output path, measuring
+    /// the audio before and after every stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the input file cannot be read or the output file
+    /// cannot be written, or [`AppError::Audio`] if audio processing fails.
+    pub fn process_file_with_report<P: AsRef<Path>>(
+        &mut self,
+        input_path: P,
+    ) -> Result<FileProcessingReport> {
+        let input_path = input_path.as_ref();
+        let output_path = self.generate_output_path(input_path);
+        self.process_file_with_output_and_report(input_path, output_path)
+    }
+
+    /// Process a single audio file to the specified output path, measuring the
+    /// audio before and after every stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the input file cannot be read or the output file
+    /// cannot be written, or [`AppError::Audio`] if audio processing fails.
+    pub fn process_file_with_output_and_report<P: AsRef<Path>, Q: AsRef<Path>>(
+        &mut self,
+        input_path: P,
+        output_path: Q,
+    ) -> Result<FileProcessingReport> {
+        let started = Instant::now();
+        let input_path = input_path.as_ref();
+        let output_path = output_path.as_ref();
+        let mut buffer = Self::load_audio_file(input_path)?;
+        let report = self.pipeline.process_buffer_with_report(&mut buffer)?;
+        Self::save_audio_file(&buffer, output_path)?;
+        Ok(FileProcessingReport::new(
+            input_path.to_path_buf(),
+            output_path.to_path_buf(),
+            started.elapsed(),
+            report,
+        ))
+    }
+
     /// Load an audio file into a buffer, converting to f32 samples.
     fn load_audio_file(path: &Path) -> Result<AudioBuffer<f32>> {
         use hound::{SampleFormat as HoundSampleFormat, WavReader}; // Open the WAV file
@@ -226,32 +273,14 @@ impl AudioFileProcessor {
 
     /// Save an audio buffer to a file.
     fn save_audio_file(buffer: &AudioBuffer<f32>, path: &Path) -> Result<()> {
-        use hound::{SampleFormat as HoundSampleFormat, WavSpec, WavWriter};
-
-        // Create the WAV file with the appropriate spec
-        let spec = WavSpec {
-            channels: buffer.channels,
-            sample_rate: buffer.sample_rate,
-            bits_per_sample: 32,
-            sample_format: HoundSampleFormat::Float,
-        }; // Create the writer and write all samples
-        let mut writer = WavWriter::create(path, spec).map_err(|e| {
-            crate::error::AppError::Io(format!(
-                "Failed to create output file '{}': {}",
-                path.display(),
-                e
-            ))
-        })?; // Write all samples
-        for &sample in &buffer.data {
-            writer
-                .write_sample(sample)
-                .map_err(|e| crate::error::AppError::Io(format!("Error writing sample: {e}")))?;
-        }
-
-        // Finalize the writer (this is a no-op in newer hound versions but kept for compatibility)
-        drop(writer);
-
-        Ok(())
+        let mut writer = AudioFileWriter::create(
+            path,
+            buffer.sample_rate,
+            buffer.channels,
+            &OutputTags::default(),
+        )?;
+        writer.write_samples(&buffer.data)?;
+        writer.finalize()
     }
 
     /// Generate the output path for a processed file based on options and input path.
@@ -280,3 +309,224 @@ impl AudioFileProcessor {
         output_dir.join(output_filename)
     }
 }
+
+/// Tags written to the RIFF `INFO` chunk of output files
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputTags {
+    /// Track title
+    pub title: Option<String>,
+    /// Artist or author
+    pub artist: Option<String>,
+    /// Album or book title
+    pub album: Option<String>,
+    /// Genre
+    pub genre: Option<String>,
+    /// Release year
+    pub year: Option<i32>,
+    /// Track number
+    pub track: Option<u32>,
+    /// Total number of tracks
+    pub track_total: Option<u32>,
+}
+
+impl OutputTags {
+    /// Tags copied from the metadata of a source file
+    #[must_use]
+    pub fn from_metadata(metadata: &AudioMetadata) -> Self {
+        Self {
+            title: metadata.title.clone(),
+            artist: metadata.artist.clone(),
+            album: metadata.album.clone(),
+            genre: metadata.genre.clone(),
+            year: metadata.year,
+            track: metadata.track,
+            track_total: None,
+        }
+    }
+
+    /// INFO sub-chunk identifiers and values of the tags that are set
+    fn info_entries(&self) -> Vec<(&'static [u8; 4], String)> {
+        [
+            (b"INAM", self.title.clone()),
+            (b"IART", self.artist.clone()),
+            (b"IPRD", self.album.clone()),
+            (b"IGNR", self.genre.clone()),
+            (b"ICRD", self.year.map(|year| year.to_string())),
+            (b"IPRT", self.track.map(|track| track.to_string())),
+            (b"IFRM", self.track_total.map(|total| total.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(id, value)| value.filter(|v| !v.is_empty()).map(|v| (id, v)))
+        .collect()
+    }
+}
+
+/// Streaming 32-bit float WAV writer used for processed output
+///
+/// Samples are written as they arrive, so long recordings never have to be
+/// held in memory. Tags are stored in a `LIST`/`INFO` chunk ahead of the
+/// audio data, where common readers look for them.
+pub struct AudioFileWriter {
+    file: BufWriter<File>,
+    path: PathBuf,
+    data_size_offset: u64,
+    data_bytes: u64,
+}
+
+impl AudioFileWriter {
+    /// Creates the output file and writes its header and tags
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the file cannot be created or written.
+    pub fn create(path: &Path, sample_rate: u32, channels: u16, tags: &OutputTags) -> Result<Self> {
+        let file = File::create(path).map_err(|e| {
+            AppError::Io(format!(
+                "Failed to create output file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let mut header = Vec::with_capacity(128);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&0u32.to_le_bytes()); // patched by finalize
+        header.extend_from_slice(b"WAVE");
+
+        // IEEE float format chunk
+        let block_align = channels * 4;
+        header.extend_from_slice(b"fmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&3u16.to_le_bytes());
+        header.extend_from_slice(&channels.to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&32u16.to_le_bytes());
+
+        let entries = tags.info_entries();
+        if !entries.is_empty() {
+            let mut info = b"INFO".to_vec();
+            for (id, value) in entries {
+                // Values are NUL terminated and padded to an even length
+                let mut bytes = value.into_bytes();
+                bytes.push(0);
+                let size = u32::try_from(bytes.len())
+                    .map_err(|_| AppError::Io("Tag value is too long".to_string()))?;
+                if bytes.len() % 2 == 1 {
+                    bytes.push(0);
+                }
+                info.extend_from_slice(id);
+                info.extend_from_slice(&size.to_le_bytes());
+                info.extend_from_slice(&bytes);
+            }
+            let size = u32::try_from(info.len())
+                .map_err(|_| AppError::Io("Tag chunk is too long".to_string()))?;
+            header.extend_from_slice(b"LIST");
+            header.extend_from_slice(&size.to_le_bytes());
+            header.extend_from_slice(&info);
+        }
+
+        header.extend_from_slice(b"data");
+        let data_size_offset = header.len() as u64;
+        header.extend_from_slice(&0u32.to_le_bytes()); // patched by finalize
+
+        let mut file = BufWriter::new(file);
+        file.write_all(&header)
+            .map_err(|e| AppError::Io(format!("Error writing header: {e}")))?;
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            data_size_offset,
+            data_bytes: 0,
+        })
+    }
+
+    /// Appends interleaved samples
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the samples cannot be written or the file
+    /// would exceed the 4 GiB WAV limit.
+    pub fn write_samples(&mut self, samples: &[f32]) -> Result<()> {
+        let bytes = samples.len() as u64 * 4;
+        if self.data_bytes + bytes > u64::from(u32::MAX) - self.data_size_offset {
+            return Err(AppError::Io(format!(
+                "Output file '{}' exceeds the 4 GiB WAV size limit",
+                self.path.display()
+            )));
+        }
+        for sample in samples {
+            self.file
+                .write_all(&sample.to_le_bytes())
+                .map_err(|e| AppError::Io(format!("Error writing sample: {e}")))?;
+        }
+        self.data_bytes += bytes;
+        Ok(())
+    }
+
+    /// Path of the file being written
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Completes the chunk sizes and flushes the file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the file cannot be written.
+    pub fn finalize(mut self) -> Result<()> {
+        let io_error = |e: std::io::Error| AppError::Io(format!("Error finalizing output: {e}"));
+        #[allow(clippy::cast_possible_truncation)] // bounded by write_samples
+        let data_size = self.data_bytes as u32;
+        #[allow(clippy::cast_possible_truncation)]
+        let riff_size = (self.data_size_offset + 4 + self.data_bytes - 8) as u32;
+
+        self.file.seek(SeekFrom::Start(4)).map_err(io_error)?;
+        self.file
+            .write_all(&riff_size.to_le_bytes())
+            .map_err(io_error)?;
+        self.file
+            .seek(SeekFrom::Start(self.data_size_offset))
+            .map_err(io_error)?;
+        self.file
+            .write_all(&data_size.to_le_bytes())
+            .map_err(io_error)?;
+        self.file.flush().map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_round_trips_samples_and_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tagged.wav");
+        let tags = OutputTags {
+            title: Some("Chapter One".to_string()),
+            artist: Some("Jane Author".to_string()),
+            album: Some("The Book".to_string()),
+            track: Some(3),
+            track_total: Some(12),
+            ..Default::default()
+        };
+        let mut writer = AudioFileWriter::create(&path, 22050, 2, &tags).unwrap();
+        writer.write_samples(&[0.25, -0.25, 0.5]).unwrap();
+        writer.write_samples(&[-0.5]).unwrap();
+        writer.finalize().unwrap();
+
+        let buffer = AudioFileProcessor::load_audio_file(&path).unwrap();
+        assert_eq!(buffer.sample_rate, 22050);
+        assert_eq!(buffer.channels, 2);
+        assert_eq!(buffer.data, vec![0.25, -0.25, 0.5, -0.5]);
+
+        let metadata = AudioMetadata::from_file(&path).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Chapter One"));
+        assert_eq!(metadata.artist.as_deref(), Some("Jane Author"));
+        assert_eq!(metadata.album.as_deref(), Some("The Book"));
+        assert_eq!(metadata.track, Some(3));
+    }
+}
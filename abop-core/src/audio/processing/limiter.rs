@@ -0,0 +1,401 @@
+//! Look-ahead brickwall limiting
+//!
+//! This module provides a peak limiter that guarantees the output never
+//! exceeds the configured ceiling. The required gain for every frame is held
+//! for the look-ahead window with a sliding minimum, released with a one-pole
+//! filter and then averaged over the window, so the gain ramps down smoothly
+//! and reaches its target exactly when the peak leaves the delay line.
+
+use std::collections::VecDeque;
+
+use super::{
+    config::LimiterConfig,
+    error::Result,
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
+    utils::gain::{db_to_linear, ms_to_frames, smoothing_coefficient},
+};
+use crate::audio::AudioBuffer;
+use crate::utils::casting::domain::audio::safe_usize_to_f64_audio;
+
+/// Sample rate assumed for latency reporting before any audio is seen
+const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Running state of the limiter for one signal
+#[derive(Debug, Clone, Default)]
+struct LimiterState {
+    lookahead: usize,
+    channels: usize,
+    /// Interleaved input delayed by `lookahead` frames
+    delay: VecDeque<f32>,
+    /// Sliding minimum of the required gain as (frame index, gain)
+    minimum: VecDeque<(usize, f32)>,
+    /// Released gain values averaged over `lookahead + 1` frames
+    window: VecDeque<f32>,
+    window_sum: f64,
+    smoothed: f32,
+    frame: usize,
+}
+
+impl LimiterState {
+    fn new(lookahead: usize, channels: usize) -> Self {
+        let window_len = lookahead + 1;
+        Self {
+            lookahead,
+            channels,
+            delay: std::iter::repeat_n(0.0, lookahead * channels).collect(),
+            minimum: VecDeque::new(),
+            window: std::iter::repeat_n(1.0, window_len).collect(),
+            window_sum: safe_usize_to_f64_audio(window_len),
+            smoothed: 1.0,
+            frame: 0,
+        }
+    }
+
+    fn push_frame(&mut self, frame: &[f32], ceiling: f32, release: f32, output: &mut Vec<f32>) {
+        let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+
+        // Hold the smallest required gain over the look-ahead window
+        while self.minimum.back().is_some_and(|(_, g)| *g >= required) {
+            self.minimum.pop_back();
+        }
+        self.minimum.push_back((self.frame, required));
+        while self
+            .minimum
+            .front()
+            .is_some_and(|(index, _)| index + self.lookahead < self.frame)
+        {
+            self.minimum.pop_front();
+        }
+        let held = self.minimum.front().map_or(1.0, |(_, g)| *g);
+
+        // Drop instantly, recover with the release time constant
+        self.smoothed = if held < self.smoothed {
+            held
+        } else {
+            release.mul_add(self.smoothed - held, held)
+        };
+
+        self.window.push_back(self.smoothed);
+        self.window_sum += f64::from(self.smoothed);
+        if let Some(oldest) = self.window.pop_front() {
+            self.window_sum -= f64::from(oldest);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let gain = (self.window_sum / safe_usize_to_f64_audio(self.window.len())) as f32;
+
+        self.delay.extend(frame.iter().copied());
+        for _ in 0..self.channels {
+            let sample = self.delay.pop_front().unwrap_or(0.0) * gain;
+            output.push(sample.clamp(-ceiling, ceiling));
+        }
+        self.frame += 1;
+    }
+}
+
+/// Look-ahead brickwall peak limiter
+///
+/// [`AudioProcessor::process`] treats consecutive buffers as one stream: the
+/// delay line and gain envelope carry over between calls, and the output
+/// trails the input by [`LatencyReporting::get_latency_samples`] frames at the
+/// most recently processed sample rate. [`AudioProcessor::process_complete`]
+/// limits a whole signal in one go and compensates that delay instead.
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    config: LimiterConfig,
+    sample_rate: u32,
+    state: Option<LimiterState>,
+}
+
+impl Limiter {
+    /// Creates a new limiter with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// configuration validation fails.
+    pub fn new(config: LimiterConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            state: None,
+        })
+    }
+
+    /// Creates a new limiter with the given ceiling in dBFS
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// ceiling is out of range.
+    pub fn with_ceiling(ceiling_db: f32) -> Result<Self> {
+        Self::new(LimiterConfig {
+            ceiling_db,
+            ..Default::default()
+        })
+    }
+
+    fn lookahead_frames(&self, sample_rate: u32) -> usize {
+        ms_to_frames(self.config.lookahead_ms, sample_rate).max(1)
+    }
+
+    /// Limits `buffer` into `output`, followed by `tail` frames of silence
+    ///
+    /// The running state is rebuilt when the look-ahead or channel count no
+    /// longer matches the incoming audio.
+    fn run(&mut self, buffer: &AudioBuffer<f32>, tail: usize, output: &mut Vec<f32>) {
+        self.sample_rate = buffer.sample_rate;
+        let channels = usize::from(buffer.channels.max(1));
+        let lookahead = self.lookahead_frames(buffer.sample_rate);
+        let ceiling = db_to_linear(self.config.ceiling_db);
+        let release = smoothing_coefficient(self.config.release_ms, buffer.sample_rate);
+
+        let state = match &mut self.state {
+            Some(state) if state.lookahead == lookahead && state.channels == channels => state,
+            state => state.insert(LimiterState::new(lookahead, channels)),
+        };
+        for frame in buffer.data.chunks(channels) {
+            state.push_frame(frame, ceiling, release, output);
+        }
+        let silence = vec![0.0; channels];
+        for _ in 0..tail {
+            state.push_frame(&silence, ceiling, release, output);
+        }
+    }
+}
+
+impl AudioProcessor for Limiter {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if buffer.data.is_empty() {
+            return Ok(());
+        }
+        let mut output = Vec::with_capacity(buffer.data.len());
+        self.run(buffer, 0, &mut output);
+        buffer.data = output;
+        Ok(())
+    }
+
+    fn process_complete(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if buffer.data.is_empty() {
+            return Ok(());
+        }
+        let channels = usize::from(buffer.channels.max(1));
+        let lookahead = self.lookahead_frames(buffer.sample_rate);
+        let mut output = Vec::with_capacity(buffer.data.len() + lookahead * channels);
+
+        // Flush the delay line and drop the leading look-ahead
+        self.reset();
+        self.run(buffer, lookahead, &mut output);
+        self.reset();
+        output.drain(..lookahead * channels);
+        output.truncate(buffer.data.len());
+
+        buffer.data = output;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+impl Configurable<LimiterConfig> for Limiter {
+    fn configure(&mut self, config: LimiterConfig) -> Result<()> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &LimiterConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for Limiter {
+    fn get_latency_samples(&self) -> usize {
+        self.lookahead_frames(self.sample_rate)
+    }
+}
+
+impl MeasurementReporting for Limiter {}
+
+impl Validatable for Limiter {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for Limiter {
+    /// Creates a new limiter with default configuration
+    fn default() -> Self {
+        Self {
+            config: LimiterConfig::default(),
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            state: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+
+    /// Two-tone signal with a loud burst in the middle
+    fn bursty_signal(frames: usize) -> AudioBuffer<f32> {
+        let data = (0..frames)
+            .map(|i| {
+                let t = safe_usize_to_f64_audio(i) / 44100.0;
+                let level = if (frames / 3..2 * frames / 3).contains(&i) {
+                    1.6
+                } else {
+                    0.2
+                };
+                let value = level
+                    * 0.5
+                    * ((std::f64::consts::TAU * 220.0 * t).sin()
+                        + (std::f64::consts::TAU * 3130.0 * t).sin());
+                #[allow(clippy::cast_possible_truncation)]
+                let sample = value as f32;
+                sample
+            })
+            .collect();
+        AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate: 44100,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_output_never_exceeds_ceiling() {
+        let mut limiter = Limiter::with_ceiling(-3.0).unwrap();
+        let mut buffer = bursty_signal(44100);
+        let input_peak = buffer.data.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+        assert!(input_peak > 1.0);
+
+        limiter.process_complete(&mut buffer).unwrap();
+        let ceiling = db_to_linear(-3.0);
+        let output_peak = buffer.data.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+        assert!(output_peak <= ceiling);
+        // The burst is actually driven into the ceiling
+        assert!(output_peak > ceiling * 0.95);
+        assert_eq!(buffer.data.len(), 44100);
+    }
+
+    #[test]
+    fn test_quiet_signal_passes_unchanged() {
+        let mut limiter = Limiter::default();
+        let mut buffer = bursty_signal(3000);
+        for sample in &mut buffer.data {
+            *sample *= 0.5;
+        }
+        let original = buffer.data.clone();
+        limiter.process_complete(&mut buffer).unwrap();
+
+        for (a, b) in buffer.data.iter().zip(&original) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_gain_ramps_before_peak() {
+        let mut limiter = Limiter::with_ceiling(-6.0).unwrap();
+        let mut data = vec![0.25f32; 2000];
+        data[1000] = 1.0;
+        let mut buffer = AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate: 44100,
+            channels: 1,
+        };
+        limiter.process_complete(&mut buffer).unwrap();
+
+        let ceiling = db_to_linear(-6.0);
+        assert!((buffer.data[1000] - ceiling).abs() < 1e-4);
+        // Gain starts falling within the look-ahead window ahead of the peak
+        assert!(buffer.data[990] < 0.25);
+        assert!((buffer.data[700] - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_blocks_stream_like_one_buffer() {
+        let signal = bursty_signal(9000);
+        let mut whole = signal.clone();
+        Limiter::with_ceiling(-3.0)
+            .unwrap()
+            .process(&mut whole)
+            .unwrap();
+
+        let mut limiter = Limiter::with_ceiling(-3.0).unwrap();
+        let mut streamed = Vec::new();
+        for chunk in signal.data.chunks(512) {
+            let mut block = AudioBuffer {
+                data: chunk.to_vec(),
+                ..signal.clone()
+            };
+            limiter.process(&mut block).unwrap();
+            assert_eq!(block.data.len(), chunk.len());
+            streamed.extend(block.data);
+        }
+        assert_eq!(streamed, whole.data);
+
+        // The stream trails the compensated output by the reported latency
+        let mut aligned = signal;
+        limiter.process_complete(&mut aligned).unwrap();
+        let latency = limiter.get_latency_samples();
+        for (a, b) in streamed[latency..].iter().zip(&aligned.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_reset_clears_delay_line() {
+        let mut limiter = Limiter::default();
+        let mut loud = AudioBuffer {
+            data: vec![0.5; 1000],
+            format: SampleFormat::F32,
+            sample_rate: 44100,
+            channels: 1,
+        };
+        limiter.process(&mut loud).unwrap();
+
+        limiter.reset();
+        let mut silence = AudioBuffer {
+            data: vec![0.0; 1000],
+            ..loud
+        };
+        limiter.process(&mut silence).unwrap();
+        assert!(silence.data.iter().all(|s| *s == 0.0));
+    }
+
+    #[test]
+    fn test_latency_tracks_sample_rate() {
+        let mut limiter = Limiter::default();
+        // 5 ms at 44.1 kHz rounds to 221 frames
+        assert_eq!(limiter.get_latency_samples(), 221);
+
+        let mut buffer = AudioBuffer {
+            data: vec![0.0; 480],
+            format: SampleFormat::F32,
+            sample_rate: 48000,
+            channels: 2,
+        };
+        limiter.process(&mut buffer).unwrap();
+        assert_eq!(limiter.get_latency_samples(), 240);
+        assert_eq!(buffer.data.len(), 480);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(Limiter::with_ceiling(3.0).is_err());
+        assert!(
+            LimiterConfig::builder()
+                .with_lookahead(0.0)
+                .build_validated()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Live playback effects
+//!
+//! This module adapts processing stages to `rodio` so they can run as effects
+//! during playback. [`EffectSource`] pulls the decoded stream in small blocks,
+//! runs each block through the stages and hands the processed samples to the
+//! output. [`playback_stages`] picks the equalizer and de-esser out of a
+//! [`ProcessingConfig`]; their filters keep state between blocks, so the
+//! result matches offline processing of the whole file.
+//!
+//! Other stages can be added by hand with care. The compressor, noise gate and
+//! limiter also carry their state over, but the limiter delays the output by
+//! its look-ahead. Noise reduction treats every block as a complete signal and
+//! starts afresh at each block boundary.
+
+use std::time::Duration;
+
+use rodio::Source;
+use rodio::source::SeekError;
+
+use super::chain::{PipelineStage, ProcessorRegistry};
+use super::config::ProcessingConfig;
+use super::error;
+use super::pipeline::AudioProcessingPipeline;
+use super::traits::AudioProcessor;
+use crate::audio::{AudioBuffer, SampleFormat};
+
+/// Number of frames processed per block
+const BLOCK_FRAMES: usize = 1024;
+
+/// Stages that [`playback_stages`] takes from a processing configuration
+const PLAYBACK_STAGES: [&str; 2] = [ProcessorRegistry::EQUALIZER, ProcessorRegistry::DE_ESSER];
+
+/// Builds the playback effects configured in `config`
+///
+/// Only the equalizer and de-esser stages are kept, whether they come from the
+/// component configurations or the explicit `stages` list. The remaining
+/// stages change the format or need the whole file and are left out.
+///
+/// # Errors
+///
+/// Returns an error if the configuration is invalid or a stage cannot be built.
+pub fn playback_stages(config: &ProcessingConfig) -> error::Result<Vec<PipelineStage>> {
+    let pipeline = AudioProcessingPipeline::new(config.clone())?;
+    Ok(pipeline
+        .stages
+        .into_iter()
+        .filter(|stage| PLAYBACK_STAGES.contains(&stage.name()))
+        .collect())
+}
+
+/// A `rodio` source that applies processing stages to another source
+pub struct EffectSource<S>
+where
+    S: Source<Item = f32>,
+{
+    input: S,
+    stages: Vec<PipelineStage>,
+    block: Vec<f32>,
+    position: usize,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl<S> EffectSource<S>
+where
+    S: Source<Item = f32>,
+{
+    /// Wraps `input` so that every sample passes through `stages` in order
+    #[must_use]
+    pub fn new(input: S, stages: Vec<PipelineStage>) -> Self {
+        let channels = input.channels();
+        let sample_rate = input.sample_rate();
+        Self {
+            input,
+            stages,
+            block: Vec::with_capacity(BLOCK_FRAMES * usize::from(channels.max(1))),
+            position: 0,
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Returns the stages applied by this source
+    #[must_use]
+    pub fn stages(&self) -> &[PipelineStage] {
+        &self.stages
+    }
+
+    /// Reads and processes the next block, returning false at end of stream
+    fn fill_block(&mut self) -> bool {
+        self.channels = self.input.channels();
+        self.sample_rate = self.input.sample_rate();
+
+        // Never read across a format change of the input
+        let channels = usize::from(self.channels.max(1));
+        let mut wanted = BLOCK_FRAMES * channels;
+        if let Some(span) = self.input.current_frame_len()
+            && span > 0
+        {
+            wanted = wanted.min(span);
+        }
+
+        self.block.clear();
+        self.block.extend(self.input.by_ref().take(wanted));
+        self.position = 0;
+        if self.block.is_empty() {
+            return false;
+        }
+
+        let mut buffer = AudioBuffer {
+            data: std::mem::take(&mut self.block),
+            format: SampleFormat::F32,
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        };
+        let original_len = buffer.data.len();
+        for stage in &mut self.stages {
+            if let Err(e) = stage.process(&mut buffer) {
+                log::warn!("Playback effect '{}' failed: {e}", stage.name());
+            }
+        }
+        if buffer.data.len() != original_len {
+            log::warn!("Playback effects changed the block length; timing may drift");
+        }
+        self.block = buffer.data;
+        true
+    }
+}
+
+impl<S> Iterator for EffectSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position >= self.block.len() && !self.fill_block() {
+            return None;
+        }
+        let sample = self.block.get(self.position).copied();
+        self.position += 1;
+        sample
+    }
+}
+
+impl<S> Source for EffectSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        let remaining = self.block.len().saturating_sub(self.position);
+        if remaining > 0 {
+            Some(remaining)
+        } else {
+            self.input.current_frame_len()
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        if self.position < self.block.len() {
+            self.channels
+        } else {
+            self.input.channels()
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        if self.position < self.block.len() {
+            self.sample_rate
+        } else {
+            self.input.sample_rate()
+        }
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.block.clear();
+        self.position = 0;
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processing::config::{DeEsserConfig, EqualizerConfig, NormalizerConfig};
+    use crate::audio::processing::{DeEsser, Equalizer};
+    use crate::test_utils::audio::create_sine_buffer;
+    use rodio::buffer::SamplesBuffer;
+
+    fn voice_stages() -> Vec<PipelineStage> {
+        vec![
+            PipelineStage::new(
+                ProcessorRegistry::EQUALIZER,
+                Box::new(Equalizer::new(EqualizerConfig::builder().for_voice().build()).unwrap()),
+            ),
+            PipelineStage::new(
+                ProcessorRegistry::DE_ESSER,
+                Box::new(DeEsser::new(DeEsserConfig::default()).unwrap()),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_live_output_matches_offline() {
+        let mut offline = create_sine_buffer(44100, 2, 6000.0, 0.25, 0.5);
+        let input = offline.data.clone();
+        for stage in &mut voice_stages() {
+            stage.process(&mut offline).unwrap();
+        }
+
+        let source = EffectSource::new(SamplesBuffer::new(2, 44100, input), voice_stages());
+        assert_eq!(source.channels(), 2);
+        assert_eq!(source.sample_rate(), 44100);
+        let live: Vec<f32> = source.collect();
+
+        assert_eq!(live.len(), offline.data.len());
+        for (a, b) in live.iter().zip(&offline.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_playback_stages_keep_eq_and_de_esser() {
+        let config = ProcessingConfig {
+            equalizer: Some(EqualizerConfig::builder().for_voice().build()),
+            de_esser: Some(DeEsserConfig::default()),
+            normalizer: Some(NormalizerConfig::default()),
+            ..Default::default()
+        };
+        let stages = playback_stages(&config).unwrap();
+        assert_eq!(
+            stages.iter().map(PipelineStage::name).collect::<Vec<_>>(),
+            vec![ProcessorRegistry::EQUALIZER, ProcessorRegistry::DE_ESSER]
+        );
+        assert!(
+            playback_stages(&ProcessingConfig::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_without_stages_is_passthrough() {
+        let input = create_sine_buffer(22050, 1, 440.0, 0.1, 0.5).data;
+        let source = EffectSource::new(SamplesBuffer::new(1, 22050, input.clone()), Vec::new());
+        assert_eq!(source.collect::<Vec<_>>(), input);
+    }
+}
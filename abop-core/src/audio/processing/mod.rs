@@ -4,24 +4,50 @@
 //! manipulating audio buffers, including resampling, normalization,
 //! silence detection, and channel mixing.
 
+/// Audiobook distribution compliance checks.
+pub mod acx;
 /// Batch processor for handling multiple audio files.
 pub mod batch_processor;
+/// Biquad filter design and implementation.
+pub mod biquad;
 pub mod casting_utils;
+/// Composable processing chain and processor registry.
+pub mod chain;
 /// Channel mixer configuration and implementation.
 pub mod channel_mixer;
+/// Compressor implementation.
+pub mod compressor;
 /// Audio processing configuration module.
 pub mod config;
+/// Split-band de-esser implementation.
+pub mod de_esser;
+/// Parametric equalizer implementation.
+pub mod equalizer;
 pub mod error;
 /// File I/O operations for audio processing.
 pub mod file_io;
+/// Look-ahead brickwall limiter implementation.
+pub mod limiter;
+/// Live playback effects built from processing stages.
+pub mod live;
+/// Noise gate and downward expander implementation.
+pub mod noise_gate;
+/// Spectral noise reduction implementation.
+pub mod noise_reduction;
 /// Normalizer configuration and implementation.
 pub mod normalizer;
 /// Audio processing pipeline implementation.
 pub mod pipeline;
+/// Before/after measurements of processing runs.
+pub mod report;
 /// Resampler configuration and implementation.
 pub mod resampler;
 /// Silence detector configuration and implementation.
 pub mod silence_detector;
+/// Band-limited windowed-sinc resampler.
+pub mod sinc_resampler;
+/// Splitting long recordings into per-chapter files.
+pub mod splitter;
 /// Processing traits and common interfaces.
 pub mod traits;
 /// Utility functions for audio processing.
@@ -31,19 +57,43 @@ pub mod validation;
 
 // Re-export main types
 pub use self::config::silence_detector::SilenceRemovalMode;
+pub use self::chain::{PipelineStage, ProcessingStage, ProcessorRegistry};
 pub use self::pipeline::AudioProcessingPipeline;
+pub use self::report::{
+    BatchProcessingReport, BatchSummary, FileProcessingReport, LevelMeasurement, ProcessingReport,
+    StageMeasurements, StageReport,
+};
 
 // Re-export config types
 pub use self::config::{
-    ChannelMixerConfig, MixingAlgorithm, NormalizerConfig, OutputConfig, ProcessingConfig,
-    ResamplerConfig, SilenceDetectorConfig,
+    ChannelMixerConfig, CompressorConfig, DeEsserConfig, EqBand, EqBandType, EqualizerConfig,
+    HumFilterConfig, LimiterConfig, MainsFrequency, NoiseProfileSource, NoiseReductionConfig, MixingAlgorithm, NoiseGateConfig,
+    NormalizerConfig, OutputConfig, ProcessingConfig,
+    ResamplerConfig, SilenceDetectorConfig, StageConfig,
 };
 
 // Re-export processor types
+pub use self::acx::{
+    AcxAnalyzer, AcxCheck, AcxChecker, AcxFileReport, AcxFinding, AcxFix, AcxMeasurements,
+    AcxReport, AcxRequirements,
+};
+pub use self::biquad::{Biquad, BiquadCoefficients};
 pub use self::channel_mixer::ChannelMixer;
+pub use self::compressor::Compressor;
+pub use self::de_esser::DeEsser;
+pub use self::equalizer::Equalizer;
+pub use self::limiter::Limiter;
+pub use self::live::{EffectSource, playback_stages};
+pub use self::noise_gate::NoiseGate;
+pub use self::noise_reduction::{NoiseProfile, NoiseReducer};
 pub use self::normalizer::AudioNormalizer;
-pub use self::resampler::LinearResampler;
+pub use self::resampler::{LinearResampler, Resampler};
 pub use self::silence_detector::SilenceDetector;
+pub use self::sinc_resampler::{KaiserParameters, SincResampler};
+pub use self::splitter::{
+    ChapterMark, ChapterOrigin, ChapterSource, ChapterSplitter, SilenceSplitConfig, SplitConfig,
+    SplitPart, SplitReport,
+};
 
 // Re-export validation types
 pub use self::validation::ConfigValidator;
@@ -0,0 +1,273 @@
+//! Downward expansion and noise gating
+//!
+//! This module provides a downward expander that attenuates material below a
+//! threshold, such as room tone and hiss between phrases. With a high ratio
+//! and a deep range it behaves as a conventional noise gate. A hold time keeps
+//! the gate open through short pauses so word endings are not clipped.
+
+use super::{
+    config::NoiseGateConfig,
+    error::Result,
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
+    utils::gain::{db_to_linear, linear_to_db, ms_to_frames, smoothing_coefficient},
+};
+use crate::audio::AudioBuffer;
+
+/// Downward expander / noise gate
+///
+/// The gate state carries over between calls to [`AudioProcessor::process`],
+/// so consecutive blocks of a stream are gated seamlessly.
+#[derive(Debug, Clone)]
+pub struct NoiseGate {
+    config: NoiseGateConfig,
+    /// Current smoothed attenuation in dB (zero or negative)
+    gain_db: f32,
+    /// Frames left before the gate may start closing
+    hold_remaining: usize,
+}
+
+impl NoiseGate {
+    /// Creates a new noise gate with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`](super::error::AudioProcessingError) if the
+    /// configuration validation fails.
+    pub fn new(config: NoiseGateConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            gain_db: 0.0,
+            hold_remaining: 0,
+        })
+    }
+
+    /// Returns the attenuation currently applied, in dB
+    #[must_use]
+    pub const fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// Returns true while the gate is fully open
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.gain_db > -0.1
+    }
+
+    /// Static expansion curve: the gain change in dB for an input level in dB
+    fn compute_gain_db(&self, level_db: f32) -> f32 {
+        let under = level_db - self.config.threshold_db;
+        if under >= 0.0 {
+            0.0
+        } else {
+            (under * (self.config.ratio - 1.0)).max(self.config.range_db)
+        }
+    }
+}
+
+impl AudioProcessor for NoiseGate {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        let channels = usize::from(buffer.channels.max(1));
+        let attack = smoothing_coefficient(self.config.attack_ms, buffer.sample_rate);
+        let release = smoothing_coefficient(self.config.release_ms, buffer.sample_rate);
+        let hold_frames = ms_to_frames(self.config.hold_ms, buffer.sample_rate);
+        let threshold = db_to_linear(self.config.threshold_db);
+
+        for frame in buffer.data.chunks_mut(channels) {
+            let peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+
+            let target_db = if peak >= threshold {
+                self.hold_remaining = hold_frames;
+                0.0
+            } else if self.hold_remaining > 0 {
+                self.hold_remaining -= 1;
+                0.0
+            } else {
+                self.compute_gain_db(linear_to_db(peak))
+            };
+
+            let coefficient = if target_db > self.gain_db {
+                attack
+            } else {
+                release
+            };
+            self.gain_db = coefficient.mul_add(self.gain_db - target_db, target_db);
+
+            let gain = db_to_linear(self.gain_db);
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.gain_db = 0.0;
+        self.hold_remaining = 0;
+    }
+}
+
+impl Configurable<NoiseGateConfig> for NoiseGate {
+    fn configure(&mut self, config: NoiseGateConfig) -> Result<()> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &NoiseGateConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for NoiseGate {
+    fn get_latency_samples(&self) -> usize {
+        // Detection works on the current sample
+        0
+    }
+}
+
+impl MeasurementReporting for NoiseGate {}
+
+impl Validatable for NoiseGate {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for NoiseGate {
+    /// Creates a new noise gate with default configuration
+    fn default() -> Self {
+        Self {
+            config: NoiseGateConfig::default(),
+            gain_db: 0.0,
+            hold_remaining: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+    use crate::test_utils::audio::create_test_buffer;
+    use crate::utils::casting::domain::audio::safe_usize_to_f64_audio;
+
+    /// Deterministic pseudo-random noise at the given peak level
+    fn noise(amplitude: f32, frames: usize) -> Vec<f32> {
+        let mut seed = 0x2545_f491_u32;
+        (0..frames)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                let unit = f32::from(u16::try_from(seed >> 16).unwrap_or(0)) / 32768.0 - 1.0;
+                unit * amplitude
+            })
+            .collect()
+    }
+
+    fn rms(data: &[f32]) -> f32 {
+        let power: f64 = data.iter().map(|s| f64::from(*s).powi(2)).sum();
+        #[allow(clippy::cast_possible_truncation)]
+        let rms = (power / safe_usize_to_f64_audio(data.len())).sqrt() as f32;
+        rms
+    }
+
+    fn mono(data: Vec<f32>) -> AudioBuffer<f32> {
+        AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate: 44100,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_speech_level_passes() {
+        let mut gate = NoiseGate::default();
+        let mut buffer = create_test_buffer(44100, 1, 0.5, Some(0.1));
+        let original = buffer.data.clone();
+        gate.process(&mut buffer).unwrap();
+
+        assert!(gate.is_open());
+        for (a, b) in buffer.data.iter().zip(&original) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_noise_floor_is_attenuated_to_range() {
+        let mut gate = NoiseGate::new(
+            NoiseGateConfig::builder()
+                .with_threshold(-40.0)
+                .with_range(-30.0)
+                .with_release(20.0)
+                .build(),
+        )
+        .unwrap();
+        let input = noise(db_to_linear(-60.0), 22050);
+        let mut buffer = mono(input.clone());
+        gate.process(&mut buffer).unwrap();
+
+        // Compare the settled second half against the input
+        let reduction = linear_to_db(rms(&buffer.data[11025..]) / rms(&input[11025..]));
+        assert!((reduction - -30.0).abs() < 0.5, "reduction {reduction} dB");
+    }
+
+    #[test]
+    fn test_hold_keeps_gate_open_through_pause() {
+        let mut gate = NoiseGate::new(
+            NoiseGateConfig::builder()
+                .with_threshold(-40.0)
+                .with_hold(100.0)
+                .build(),
+        )
+        .unwrap();
+        let mut data = create_test_buffer(44100, 1, 0.1, Some(0.1)).data;
+        data.extend(noise(db_to_linear(-70.0), 3000));
+        let mut buffer = mono(data);
+        gate.process(&mut buffer).unwrap();
+
+        // A 68 ms pause fits within the hold time
+        assert!(gate.is_open());
+
+        let mut tail = mono(noise(db_to_linear(-70.0), 22050));
+        gate.process(&mut tail).unwrap();
+        assert!(!gate.is_open());
+
+        gate.reset();
+        assert!(gate.is_open());
+    }
+
+    #[test]
+    fn test_expander_curve() {
+        let gate = NoiseGate::new(
+            NoiseGateConfig::builder()
+                .with_threshold(-40.0)
+                .with_ratio(2.0)
+                .with_range(-20.0)
+                .build(),
+        )
+        .unwrap();
+        assert!(gate.compute_gain_db(-30.0).abs() < f32::EPSILON);
+        assert!((gate.compute_gain_db(-50.0) - -10.0).abs() < 1e-5);
+        assert!((gate.compute_gain_db(-90.0) - -20.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(
+            NoiseGateConfig::builder()
+                .with_ratio(0.5)
+                .build_validated()
+                .is_err()
+        );
+        assert!(
+            NoiseGate::new(NoiseGateConfig {
+                range_db: 6.0,
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,960 @@
+//! Spectral noise reduction
+//!
+//! This module provides an STFT-based spectral gate for steady broadband noise
+//! such as tape hiss. A noise profile (the average magnitude of each frequency
+//! bin) is learned either from the quietest stretches of the recording, found
+//! with [`SilenceDetector::detect_silence_segments`], or from a user-selected
+//! time range. Each analysis frame is then compared bin by bin against the
+//! profile, and bins that do not rise clearly above it are attenuated.
+//!
+//! Frames use a square-root Hann window for both analysis and synthesis at 50%
+//! overlap, which reconstructs the input exactly when no bin is attenuated. An
+//! optional cascade of notch filters removes mains hum at 50 or 60 Hz and its
+//! harmonics.
+
+use std::fmt;
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+
+use super::{
+    biquad::{Biquad, BiquadCoefficients},
+    casting_utils::error_conversion::cast_to_audio_error,
+    config::{NoiseProfileSource, NoiseReductionConfig},
+    error::{AudioProcessingError, Result},
+    silence_detector::SilenceDetector,
+    traits::{
+        AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, StreamingProcessor,
+        Validatable,
+    },
+    utils::gain::db_to_linear,
+};
+use crate::audio::AudioBuffer;
+use crate::utils::casting::domain::audio::{safe_f64_to_usize_samples, safe_usize_to_f64_audio};
+
+/// Silence thresholds tried, quietest first, when learning a profile automatically
+const AUTO_THRESHOLDS_DB: [f32; 11] = [
+    -70.0, -65.0, -60.0, -55.0, -50.0, -45.0, -40.0, -35.0, -30.0, -25.0, -20.0,
+];
+
+/// Minimum number of analysis frames per channel a learned profile must average
+const MIN_PROFILE_FRAMES: usize = 4;
+
+/// Highest hum harmonic frequency as a fraction of the sample rate
+const MAX_HUM_FRACTION: f32 = 0.45;
+
+/// Average noise magnitude of each frequency bin
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseProfile {
+    sample_rate: u32,
+    fft_size: usize,
+    magnitudes: Vec<f32>,
+}
+
+impl NoiseProfile {
+    /// Sample rate the profile was learned at
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// FFT frame size the profile was learned with
+    #[must_use]
+    pub const fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Mean magnitude of each bin from DC to Nyquist
+    #[must_use]
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+}
+
+/// Windowed FFT frames shared by analysis and synthesis
+#[derive(Clone)]
+struct Stft {
+    size: usize,
+    hop: usize,
+    window: Arc<[f32]>,
+    forward: Arc<dyn Fft<f32>>,
+    inverse: Arc<dyn Fft<f32>>,
+}
+
+impl fmt::Debug for Stft {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stft")
+            .field("size", &self.size)
+            .field("hop", &self.hop)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stft {
+    fn new(size: usize) -> Self {
+        let mut planner = FftPlanner::new();
+        let length = safe_usize_to_f64_audio(size);
+        let window = (0..size)
+            .map(|i| {
+                // Periodic Hann; its square root satisfies COLA at 50% overlap
+                let phase = std::f64::consts::TAU * safe_usize_to_f64_audio(i) / length;
+                #[allow(clippy::cast_possible_truncation)]
+                let value = 0.5f64.mul_add(-phase.cos(), 0.5).sqrt() as f32;
+                value
+            })
+            .collect();
+        Self {
+            size,
+            hop: size / 2,
+            window,
+            forward: planner.plan_fft_forward(size),
+            inverse: planner.plan_fft_inverse(size),
+        }
+    }
+
+    const fn bins(&self) -> usize {
+        self.size / 2 + 1
+    }
+
+    const fn latency(&self) -> usize {
+        self.size - self.hop
+    }
+
+    /// Windows a frame and transforms it into `spectrum`
+    fn analyse(&self, frame: impl Iterator<Item = f32>, spectrum: &mut Vec<Complex<f32>>) {
+        spectrum.clear();
+        spectrum.extend(
+            frame
+                .zip(self.window.iter())
+                .map(|(sample, window)| Complex::new(sample * window, 0.0)),
+        );
+        self.forward.process(spectrum);
+    }
+
+    /// Averages the spectra of every whole frame inside the given frame ranges
+    fn learn(&self, buffer: &AudioBuffer<f32>, ranges: &[(usize, usize)]) -> Option<NoiseProfile> {
+        let channels = usize::from(buffer.channels.max(1));
+        let mut sums = vec![0.0f64; self.bins()];
+        let mut spectrum = Vec::with_capacity(self.size);
+        let mut frames = 0usize;
+
+        for &(start, end) in ranges {
+            let mut position = start;
+            while position + self.size <= end {
+                for channel in 0..channels {
+                    let samples = (position..position + self.size)
+                        .map(|frame| buffer.data[frame * channels + channel]);
+                    self.analyse(samples, &mut spectrum);
+                    for (sum, bin) in sums.iter_mut().zip(&spectrum) {
+                        *sum += f64::from(bin.norm());
+                    }
+                }
+                frames += 1;
+                position += self.hop;
+            }
+        }
+
+        if frames < MIN_PROFILE_FRAMES {
+            return None;
+        }
+        let count = safe_usize_to_f64_audio(frames * channels);
+        Some(NoiseProfile {
+            sample_rate: buffer.sample_rate,
+            fft_size: self.size,
+            #[allow(clippy::cast_possible_truncation)]
+            magnitudes: sums.iter().map(|sum| (sum / count) as f32).collect(),
+        })
+    }
+}
+
+/// Spectral gate parameters derived from the configuration
+#[derive(Debug, Clone, Copy)]
+struct GateParams {
+    floor: f32,
+    margin: f32,
+    smoothing: f32,
+}
+
+impl GateParams {
+    fn from_config(config: &NoiseReductionConfig) -> Self {
+        Self {
+            floor: db_to_linear(config.reduction_db),
+            margin: db_to_linear(config.threshold_db),
+            smoothing: config.smoothing,
+        }
+    }
+}
+
+/// Per-channel STFT state
+#[derive(Debug, Clone)]
+struct Lane {
+    input: Vec<f32>,
+    overlap: Vec<f32>,
+    gains: Vec<f32>,
+}
+
+/// State of a stream being processed frame by frame
+#[derive(Debug, Clone)]
+struct StreamState {
+    channels: usize,
+    sample_rate: u32,
+    profile: Option<Arc<NoiseProfile>>,
+    lanes: Vec<Lane>,
+    hum: Vec<Biquad>,
+    spectrum: Vec<Complex<f32>>,
+    frames_in: usize,
+    frames_out: usize,
+}
+
+impl StreamState {
+    fn new(
+        stft: &Stft,
+        config: &NoiseReductionConfig,
+        channels: usize,
+        sample_rate: u32,
+        profile: Option<Arc<NoiseProfile>>,
+    ) -> Self {
+        let lane = Lane {
+            // Priming with zeros aligns the first output hop with the latency
+            input: vec![0.0; stft.latency()],
+            overlap: vec![0.0; stft.size],
+            gains: vec![1.0; stft.bins()],
+        };
+        let hum = config
+            .hum
+            .iter()
+            .flat_map(|hum| {
+                let fundamental = hum.mains.hz();
+                (1..=hum.harmonics)
+                    .filter_map(move |harmonic| u16::try_from(harmonic).ok())
+                    .map(move |harmonic| (fundamental * f32::from(harmonic), hum.q))
+            })
+            .take_while(|(frequency, _)| {
+                // Harmonics only rise, so stop at the first one past the limit
+                #[allow(clippy::cast_precision_loss)]
+                let limit = sample_rate as f32 * MAX_HUM_FRACTION;
+                *frequency < limit
+            })
+            .map(|(frequency, q)| Biquad::new(BiquadCoefficients::notch(frequency, q, sample_rate)))
+            .collect();
+
+        Self {
+            channels,
+            sample_rate,
+            profile,
+            lanes: vec![lane; channels],
+            hum,
+            spectrum: Vec::with_capacity(stft.size),
+            frames_in: 0,
+            frames_out: 0,
+        }
+    }
+
+    fn push(&mut self, stft: &Stft, params: GateParams, data: &[f32], out: &mut Vec<f32>) {
+        for frame in data.chunks_exact(self.channels) {
+            for (lane, sample) in self.lanes.iter_mut().zip(frame) {
+                lane.input.push(*sample);
+            }
+        }
+        self.frames_in += data.len() / self.channels;
+        self.drain(stft, params, out);
+    }
+
+    /// Processes every complete frame and appends the finished hops to `out`
+    fn drain(&mut self, stft: &Stft, params: GateParams, out: &mut Vec<f32>) {
+        #[allow(clippy::cast_precision_loss)]
+        let scale = 1.0 / stft.size as f32;
+
+        while self
+            .lanes
+            .first()
+            .is_some_and(|lane| lane.input.len() >= stft.size)
+        {
+            let start = out.len();
+            for lane in &mut self.lanes {
+                stft.analyse(lane.input[..stft.size].iter().copied(), &mut self.spectrum);
+                if let Some(profile) = &self.profile {
+                    apply_gate(
+                        &mut self.spectrum,
+                        &profile.magnitudes,
+                        &mut lane.gains,
+                        params,
+                    );
+                }
+                stft.inverse.process(&mut self.spectrum);
+                for ((overlap, bin), window) in lane
+                    .overlap
+                    .iter_mut()
+                    .zip(&self.spectrum)
+                    .zip(stft.window.iter())
+                {
+                    *overlap += bin.re * scale * window;
+                }
+            }
+
+            for i in 0..stft.hop {
+                out.extend(self.lanes.iter().map(|lane| lane.overlap[i]));
+            }
+            for lane in &mut self.lanes {
+                lane.overlap.drain(..stft.hop);
+                lane.overlap.resize(stft.size, 0.0);
+                lane.input.drain(..stft.hop);
+            }
+            for filter in &mut self.hum {
+                filter.process_interleaved(&mut out[start..], self.channels);
+            }
+            self.frames_out += stft.hop;
+        }
+    }
+
+    /// Flushes the frames still buffered at the end of the stream
+    fn finish(&mut self, stft: &Stft, params: GateParams, out: &mut Vec<f32>) {
+        let target = self.frames_in + stft.latency();
+        while self.frames_out < target {
+            for lane in &mut self.lanes {
+                lane.input.resize(lane.input.len() + stft.hop, 0.0);
+            }
+            self.drain(stft, params, out);
+        }
+        let excess = (self.frames_out - target) * self.channels;
+        out.truncate(out.len().saturating_sub(excess));
+    }
+}
+
+/// Attenuates the bins of a spectrum that do not rise above the noise profile
+fn apply_gate(spectrum: &mut [Complex<f32>], noise: &[f32], gains: &mut [f32], params: GateParams) {
+    let size = spectrum.len();
+    for (bin, (gain, noise)) in gains.iter_mut().zip(noise).enumerate() {
+        let magnitude = spectrum[bin].norm();
+        let level = noise * params.margin;
+        // Power subtraction keeps bins well above the floor nearly untouched
+        let target = if magnitude > level {
+            let ratio = level / magnitude;
+            ratio.mul_add(-ratio, 1.0).sqrt().max(params.floor)
+        } else {
+            params.floor
+        };
+        // Open immediately, close gradually to avoid musical noise
+        *gain = target.max(params.smoothing.mul_add(*gain - target, target));
+
+        spectrum[bin] *= *gain;
+        if bin != 0 && bin != size - bin {
+            spectrum[size - bin] *= *gain;
+        }
+    }
+}
+
+/// STFT spectral-gating noise reducer with optional hum removal
+///
+/// Whole buffers passed to [`AudioProcessor::process`] are processed
+/// independently and returned time-aligned with the input. For multi-hour
+/// files use [`StreamingProcessor::process_streaming`] followed by
+/// [`NoiseReducer::finish_streaming`]; the streamed output is delayed by
+/// [`LatencyReporting::get_latency_samples`] frames.
+#[derive(Debug, Clone)]
+pub struct NoiseReducer {
+    config: NoiseReductionConfig,
+    stft: Stft,
+    /// Profile set explicitly or learned by an earlier stream
+    profile: Option<Arc<NoiseProfile>>,
+    stream: Option<StreamState>,
+    /// Audio held back while a streaming profile is learned
+    pending: Option<AudioBuffer<f32>>,
+}
+
+impl NoiseReducer {
+    /// Creates a new noise reducer with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the configuration validation fails.
+    pub fn new(config: NoiseReductionConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    fn from_config(config: NoiseReductionConfig) -> Self {
+        Self {
+            stft: Stft::new(config.fft_size),
+            config,
+            profile: None,
+            stream: None,
+            pending: None,
+        }
+    }
+
+    /// Returns the current noise profile, if one is set
+    #[must_use]
+    pub fn profile(&self) -> Option<&NoiseProfile> {
+        self.profile.as_deref()
+    }
+
+    /// Uses the given noise profile instead of learning one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError::Configuration`] if the profile was
+    /// learned with a different FFT size.
+    pub fn set_profile(&mut self, profile: NoiseProfile) -> Result<()> {
+        if profile.fft_size != self.config.fft_size {
+            return Err(AudioProcessingError::config(format!(
+                "Noise profile uses FFT size {} but the reducer uses {}",
+                profile.fft_size, self.config.fft_size
+            )));
+        }
+        self.profile = Some(Arc::new(profile));
+        Ok(())
+    }
+
+    /// Forgets the current profile so the next buffer or stream learns a new one
+    pub fn clear_profile(&mut self) {
+        self.profile = None;
+    }
+
+    /// Learns a noise profile from a buffer using the configured source
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError::InvalidInput`] if the selected range lies
+    /// outside the buffer, or if no stretch quiet and long enough is found.
+    pub fn learn_profile(&self, buffer: &AudioBuffer<f32>) -> Result<NoiseProfile> {
+        let channels = usize::from(buffer.channels.max(1));
+        let frames = buffer.data.len() / channels;
+
+        match self.config.profile {
+            NoiseProfileSource::Range {
+                start_secs,
+                end_secs,
+            } => {
+                let rate = f64::from(buffer.sample_rate);
+                let start = safe_f64_to_usize_samples(start_secs * rate)
+                    .map_err(|e| cast_to_audio_error(e.into()))?;
+                let end = safe_f64_to_usize_samples(end_secs * rate)
+                    .map_err(|e| cast_to_audio_error(e.into()))?;
+                if end > frames {
+                    return Err(AudioProcessingError::InvalidInput(format!(
+                        "Noise profile range ends at {end_secs:.2}s, after the end of the audio"
+                    )));
+                }
+                self.stft.learn(buffer, &[(start, end)]).ok_or_else(|| {
+                    AudioProcessingError::InvalidInput(format!(
+                        "Noise profile range is too short; select at least {:.2}s",
+                        safe_usize_to_f64_audio(self.min_profile_frames()) / rate
+                    ))
+                })
+            }
+            NoiseProfileSource::Auto => {
+                let min_secs = safe_usize_to_f64_audio(self.min_profile_frames())
+                    / f64::from(buffer.sample_rate);
+                #[allow(clippy::cast_possible_truncation)]
+                let min_secs = min_secs as f32;
+                for threshold_db in AUTO_THRESHOLDS_DB {
+                    let detector = SilenceDetector::with_params(threshold_db, min_secs)?;
+                    let ranges: Vec<_> = detector
+                        .detect_silence_segments(buffer)?
+                        .iter()
+                        .map(|segment| (segment.start / channels, segment.end / channels))
+                        .collect();
+                    if let Some(profile) = self.stft.learn(buffer, &ranges) {
+                        log::debug!(
+                            "Learned noise profile from {} quiet segments below {threshold_db} dBFS",
+                            ranges.len()
+                        );
+                        return Ok(profile);
+                    }
+                }
+                Err(AudioProcessingError::InvalidInput(
+                    "No quiet segments found to learn a noise profile from".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Emits the frames still buffered at the end of a stream and resets it
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if a profile still has to be learned
+    /// and the streamed audio does not contain a usable noise region.
+    pub fn finish_streaming(&mut self, output: &mut AudioBuffer<f32>) -> Result<()> {
+        output.data.clear();
+        if let Some(pending) = self.pending.take() {
+            output.format = pending.format;
+            output.sample_rate = pending.sample_rate;
+            output.channels = pending.channels;
+            self.start_learned_stream(&pending, &mut output.data)?;
+        }
+        if let Some(mut stream) = self.stream.take() {
+            stream.finish(&self.stft, self.params(), &mut output.data);
+        }
+        Ok(())
+    }
+
+    fn params(&self) -> GateParams {
+        GateParams::from_config(&self.config)
+    }
+
+    const fn gating_enabled(&self) -> bool {
+        self.config.reduction_db < 0.0
+    }
+
+    const fn min_profile_frames(&self) -> usize {
+        self.stft.size + (MIN_PROFILE_FRAMES - 1) * self.stft.hop
+    }
+
+    /// Resolves the profile for a buffer, learning one when none is set
+    fn profile_for(&self, buffer: &AudioBuffer<f32>) -> Result<Option<Arc<NoiseProfile>>> {
+        if !self.gating_enabled() {
+            return Ok(None);
+        }
+        match &self.profile {
+            Some(profile) if profile.sample_rate == buffer.sample_rate => {
+                Ok(Some(Arc::clone(profile)))
+            }
+            Some(profile) => Err(AudioProcessingError::InvalidInput(format!(
+                "Noise profile was learned at {} Hz but the audio is {} Hz",
+                profile.sample_rate, buffer.sample_rate
+            ))),
+            None => Ok(Some(Arc::new(self.learn_profile(buffer)?))),
+        }
+    }
+
+    /// Seconds of streamed audio needed before a profile can be learned
+    fn learning_secs(&self) -> f64 {
+        match self.config.profile {
+            NoiseProfileSource::Auto => f64::from(self.config.learning_window_secs),
+            NoiseProfileSource::Range { end_secs, .. } => end_secs,
+        }
+    }
+
+    /// Learns a profile from held-back audio and starts streaming it
+    fn start_learned_stream(
+        &mut self,
+        pending: &AudioBuffer<f32>,
+        out: &mut Vec<f32>,
+    ) -> Result<()> {
+        let profile = Arc::new(self.learn_profile(pending)?);
+        self.profile = Some(Arc::clone(&profile));
+        let mut stream = StreamState::new(
+            &self.stft,
+            &self.config,
+            usize::from(pending.channels.max(1)),
+            pending.sample_rate,
+            Some(profile),
+        );
+        stream.push(&self.stft, self.params(), &pending.data, out);
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+impl AudioProcessor for NoiseReducer {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        if buffer.data.is_empty() || (!self.gating_enabled() && self.config.hum.is_none()) {
+            return Ok(());
+        }
+
+        let channels = usize::from(buffer.channels.max(1));
+        let profile = self.profile_for(buffer)?;
+        let params = self.params();
+        let mut stream = StreamState::new(
+            &self.stft,
+            &self.config,
+            channels,
+            buffer.sample_rate,
+            profile,
+        );
+
+        let latency = self.stft.latency() * channels;
+        let mut output = Vec::with_capacity(buffer.data.len() + latency);
+        stream.push(&self.stft, params, &buffer.data, &mut output);
+        stream.finish(&self.stft, params, &mut output);
+
+        // Drop the leading latency so the result lines up with the input
+        buffer.data = output.split_off(latency);
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.stream = None;
+        self.pending = None;
+    }
+}
+
+impl StreamingProcessor for NoiseReducer {
+    fn process_streaming(
+        &mut self,
+        input: &AudioBuffer<f32>,
+        output: &mut AudioBuffer<f32>,
+    ) -> Result<()> {
+        output.format = input.format;
+        output.sample_rate = input.sample_rate;
+        output.channels = input.channels;
+        output.data.clear();
+
+        let channels = usize::from(input.channels.max(1));
+        let format_changed = |rate: u32, stream_channels: usize| {
+            rate != input.sample_rate || stream_channels != channels
+        };
+        if let Some(stream) = &self.stream
+            && format_changed(stream.sample_rate, stream.channels)
+        {
+            return Err(AudioProcessingError::InvalidInput(
+                "Stream format changed; finish or reset the stream first".to_string(),
+            ));
+        }
+
+        if self.stream.is_none() {
+            if self.gating_enabled() && self.profile.is_none() {
+                // Hold audio back until there is enough to learn a profile from
+                let pending = self.pending.get_or_insert_with(|| AudioBuffer {
+                    data: Vec::new(),
+                    format: input.format,
+                    sample_rate: input.sample_rate,
+                    channels: input.channels,
+                });
+                if format_changed(pending.sample_rate, usize::from(pending.channels.max(1))) {
+                    return Err(AudioProcessingError::InvalidInput(
+                        "Stream format changed; finish or reset the stream first".to_string(),
+                    ));
+                }
+                pending.data.extend_from_slice(&input.data);
+
+                let held_secs = safe_usize_to_f64_audio(pending.data.len() / channels)
+                    / f64::from(input.sample_rate);
+                if held_secs < self.learning_secs() {
+                    return Ok(());
+                }
+                if let Some(pending) = self.pending.take() {
+                    self.start_learned_stream(&pending, &mut output.data)?;
+                }
+                return Ok(());
+            }
+
+            let profile = self.profile_for(input)?;
+            self.stream = Some(StreamState::new(
+                &self.stft,
+                &self.config,
+                channels,
+                input.sample_rate,
+                profile,
+            ));
+        }
+
+        let params = self.params();
+        if let Some(stream) = self.stream.as_mut() {
+            stream.push(&self.stft, params, &input.data, &mut output.data);
+        }
+        Ok(())
+    }
+
+    fn set_streaming_latency(&mut self, latency_samples: usize) -> Result<()> {
+        let required = self.get_latency_samples();
+        if latency_samples < required {
+            return Err(AudioProcessingError::InvalidInput(format!(
+                "Noise reducer needs at least {required} samples of latency, got {latency_samples}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stream = None;
+        self.pending = None;
+        Ok(())
+    }
+}
+
+impl Configurable<NoiseReductionConfig> for NoiseReducer {
+    fn configure(&mut self, config: NoiseReductionConfig) -> Result<()> {
+        config.validate()?;
+        let profile = self
+            .profile
+            .take()
+            .filter(|profile| profile.fft_size == config.fft_size);
+        *self = Self::from_config(config);
+        self.profile = profile;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &NoiseReductionConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for NoiseReducer {
+    fn get_latency_samples(&self) -> usize {
+        // Hum notches are IIR sections and add no fixed delay
+        self.stft.latency()
+    }
+}
+
+impl MeasurementReporting for NoiseReducer {}
+
+impl Validatable for NoiseReducer {
+    fn validate(&self) -> Result<()> {
+        self.config.validate()
+    }
+}
+
+impl Default for NoiseReducer {
+    /// Creates a new noise reducer with default configuration
+    fn default() -> Self {
+        Self::from_config(NoiseReductionConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+    use crate::audio::processing::config::{HumFilterConfig, MainsFrequency};
+    use crate::audio::processing::utils::gain::linear_to_db;
+    use crate::test_utils::audio::create_sine_buffer;
+
+    const RATE: u32 = 44100;
+
+    /// Deterministic white noise with the given RMS level
+    fn hiss(rms: f32, frames: usize) -> Vec<f32> {
+        let mut seed = 0x9e37_79b9_u32;
+        (0..frames)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                let unit = f32::from(u16::try_from(seed >> 16).unwrap_or(0)) / 32768.0 - 1.0;
+                // Uniform noise has an RMS of 1/sqrt(3)
+                unit * rms * 3f32.sqrt()
+            })
+            .collect()
+    }
+
+    fn rms(data: &[f32]) -> f32 {
+        let power: f64 = data.iter().map(|s| f64::from(*s).powi(2)).sum();
+        #[allow(clippy::cast_possible_truncation)]
+        let rms = (power / safe_usize_to_f64_audio(data.len().max(1))).sqrt() as f32;
+        rms
+    }
+
+    /// Amplitude of one frequency component, by projection onto sine and cosine
+    fn tone_level(data: &[f32], frequency: f32) -> f32 {
+        let step = std::f64::consts::TAU * f64::from(frequency) / f64::from(RATE);
+        let (mut re, mut im) = (0.0f64, 0.0f64);
+        for (i, sample) in data.iter().enumerate() {
+            let phase = step * safe_usize_to_f64_audio(i);
+            re += f64::from(*sample) * phase.cos();
+            im += f64::from(*sample) * phase.sin();
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let level = (2.0 * re.hypot(im) / safe_usize_to_f64_audio(data.len())) as f32;
+        level
+    }
+
+    fn mono(data: Vec<f32>) -> AudioBuffer<f32> {
+        AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate: RATE,
+            channels: 1,
+        }
+    }
+
+    /// One second of hiss, then one second of a 1 kHz tone over the same hiss
+    fn hiss_then_tone() -> AudioBuffer<f32> {
+        let frames = RATE as usize;
+        let mut data = hiss(db_to_linear(-45.0), frames * 2);
+        let tone = create_sine_buffer(RATE, 1, 1000.0, 1.0, 0.3).data;
+        for (sample, tone) in data[frames..].iter_mut().zip(tone) {
+            *sample += tone;
+        }
+        mono(data)
+    }
+
+    #[test]
+    fn test_hiss_is_reduced_and_speech_band_kept() {
+        let mut reducer = NoiseReducer::default();
+        let mut buffer = hiss_then_tone();
+        let original = buffer.data.clone();
+        reducer.process(&mut buffer).unwrap();
+        assert_eq!(buffer.data.len(), original.len());
+
+        // Skip the first frames while the gains settle
+        let noise_only = 8192..44100;
+        let reduction =
+            linear_to_db(rms(&buffer.data[noise_only.clone()]) / rms(&original[noise_only]));
+        assert!(reduction < -12.0, "noise reduced by {reduction} dB");
+
+        let tone_region = &buffer.data[48000..88000];
+        let tone = tone_level(tone_region, 1000.0);
+        assert!((linear_to_db(tone / 0.3)).abs() < 0.5, "tone level {tone}");
+        // Process leaves no profile behind for the next file
+        assert!(reducer.profile().is_none());
+    }
+
+    #[test]
+    fn test_no_reduction_reconstructs_input() {
+        let mut reducer = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_reduction(0.0)
+                .with_hum_filter(HumFilterConfig {
+                    harmonics: 1,
+                    ..HumFilterConfig::new(MainsFrequency::Hz50)
+                })
+                .build(),
+        )
+        .unwrap();
+        let mut buffer = create_sine_buffer(RATE, 2, 3000.0, 0.5, 0.4);
+        let original = buffer.data.clone();
+        reducer.process(&mut buffer).unwrap();
+        // Only the notch far below 3 kHz touches the signal
+        for (a, b) in buffer.data.iter().zip(&original).skip(4096) {
+            assert!((a - b).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_hum_harmonics_are_notched() {
+        let mut reducer = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_reduction(0.0)
+                .with_hum_removal(MainsFrequency::Hz60)
+                .build(),
+        )
+        .unwrap();
+        let frames = RATE as usize * 2;
+        let mut data = create_sine_buffer(RATE, 1, 1000.0, 2.0, 0.3).data;
+        for harmonic in [60.0, 120.0, 180.0] {
+            let hum = create_sine_buffer(RATE, 1, harmonic, 2.0, 0.05).data;
+            for (sample, hum) in data.iter_mut().zip(hum) {
+                *sample += hum;
+            }
+        }
+        let mut buffer = mono(data);
+        reducer.process(&mut buffer).unwrap();
+
+        let settled = &buffer.data[frames / 2..];
+        for harmonic in [60.0, 120.0, 180.0] {
+            let level = linear_to_db(tone_level(settled, harmonic) / 0.05);
+            assert!(level < -20.0, "{harmonic} Hz at {level} dB");
+        }
+        assert!(linear_to_db(tone_level(settled, 1000.0) / 0.3).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_streaming_matches_offline() {
+        let buffer = hiss_then_tone();
+        let learner = NoiseReducer::default();
+        let profile = learner.learn_profile(&buffer).unwrap();
+
+        let mut offline = NoiseReducer::default();
+        offline.set_profile(profile.clone()).unwrap();
+        let mut expected = buffer.clone();
+        offline.process(&mut expected).unwrap();
+
+        let mut streaming = NoiseReducer::default();
+        streaming.set_profile(profile).unwrap();
+        let mut streamed = Vec::new();
+        let mut output = mono(Vec::new());
+        for chunk in buffer.data.chunks(3000) {
+            streaming
+                .process_streaming(&mono(chunk.to_vec()), &mut output)
+                .unwrap();
+            streamed.extend_from_slice(&output.data);
+        }
+        streaming.finish_streaming(&mut output).unwrap();
+        streamed.extend_from_slice(&output.data);
+
+        let latency = streaming.get_latency_samples();
+        assert_eq!(streamed.len(), buffer.data.len() + latency);
+        for (a, b) in streamed[latency..].iter().zip(&expected.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_streaming_learns_profile_from_window() {
+        let buffer = hiss_then_tone();
+        let mut reducer = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_learning_window(1.5)
+                .build(),
+        )
+        .unwrap();
+        let mut output = mono(Vec::new());
+        let mut produced = 0;
+        for chunk in buffer.data.chunks(RATE as usize / 2) {
+            reducer
+                .process_streaming(&mono(chunk.to_vec()), &mut output)
+                .unwrap();
+            if produced == 0 {
+                assert!(output.data.is_empty() || reducer.profile().is_some());
+            }
+            produced += output.data.len();
+        }
+        assert!(reducer.profile().is_some());
+        reducer.finish_streaming(&mut output).unwrap();
+        produced += output.data.len();
+        assert_eq!(produced, buffer.data.len() + reducer.get_latency_samples());
+    }
+
+    #[test]
+    fn test_profile_from_range() {
+        let buffer = hiss_then_tone();
+        let reducer = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_profile_range(0.2, 0.8)
+                .build(),
+        )
+        .unwrap();
+        let profile = reducer.learn_profile(&buffer).unwrap();
+        assert_eq!(profile.magnitudes().len(), 2048 / 2 + 1);
+        assert_eq!(profile.sample_rate(), RATE);
+
+        let outside = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_profile_range(1.5, 3.0)
+                .build(),
+        )
+        .unwrap();
+        assert!(outside.learn_profile(&buffer).is_err());
+
+        let too_short = NoiseReducer::new(
+            NoiseReductionConfig::builder()
+                .with_profile_range(0.0, 0.01)
+                .build(),
+        )
+        .unwrap();
+        assert!(too_short.learn_profile(&buffer).is_err());
+    }
+
+    #[test]
+    fn test_no_quiet_segment_is_an_error() {
+        let mut reducer = NoiseReducer::default();
+        let mut buffer = create_sine_buffer(RATE, 1, 440.0, 1.0, 0.5);
+        assert!(reducer.process(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(
+            NoiseReductionConfig::builder()
+                .with_fft_size(1000)
+                .build_validated()
+                .is_err()
+        );
+        assert!(
+            NoiseReductionConfig::builder()
+                .with_profile_range(2.0, 1.0)
+                .build_validated()
+                .is_err()
+        );
+        let mut reducer = NoiseReducer::default();
+        let profile = NoiseProfile {
+            sample_rate: RATE,
+            fft_size: 1024,
+            magnitudes: vec![0.0; 513],
+        };
+        assert!(reducer.set_profile(profile).is_err());
+    }
+}
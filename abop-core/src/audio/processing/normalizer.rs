@@ -7,7 +7,8 @@
 use super::{
     config::NormalizerConfig,
     error::Result,
-    traits::{AudioProcessor, Configurable, LatencyReporting, Validatable},
+    report::{LevelMeasurement, StageMeasurements},
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
     validation::ConfigValidator,
 };
 use crate::audio::AudioBuffer;
@@ -31,6 +32,8 @@ pub enum NormalizerError {
 #[derive(Debug, Clone)]
 pub struct AudioNormalizer {
     config: NormalizerConfig,
+    /// Gain applied to the most recent buffer in dB
+    last_gain_db: Option<f32>,
 }
 
 impl AudioNormalizer {
@@ -41,7 +44,10 @@ impl AudioNormalizer {
     /// Returns [`AudioProcessingError`] if the normalizer configuration validation fails.
     pub fn new(config: NormalizerConfig) -> Result<Self> {
         ConfigValidator::validate_normalizer_config(&config)?;
-        Ok(Self { config })
+        Ok(Self {
+            config,
+            last_gain_db: None,
+        })
     }
 
     /// Creates a new audio normalizer with peak normalization to a specific level
@@ -78,28 +84,24 @@ impl AudioNormalizer {
     ///
     /// Note: The `Result` return type is maintained for compatibility with the `AudioProcessor` trait.
     #[allow(clippy::unnecessary_wraps)]
-    fn normalize_buffer(&self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+    fn normalize_buffer(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        self.last_gain_db = None;
         if buffer.data.is_empty() {
             return Ok(());
         }
 
-        match self.config.algorithm {
-            super::config::NormalizationAlgorithm::Peak => {
-                self.normalize_peak(buffer);
-            }
-            super::config::NormalizationAlgorithm::Rms => {
-                self.normalize_rms(buffer);
-            }
-            super::config::NormalizationAlgorithm::Lufs => {
-                self.normalize_lufs(buffer);
-            }
-        }
+        let gain = match self.config.algorithm {
+            super::config::NormalizationAlgorithm::Peak => self.normalize_peak(buffer),
+            super::config::NormalizationAlgorithm::Rms => self.normalize_rms(buffer),
+            super::config::NormalizationAlgorithm::Lufs => self.normalize_lufs(buffer),
+        };
+        self.last_gain_db = gain.map(|gain| 20.0 * gain.log10());
 
         Ok(())
     }
 
-    /// Applies peak normalization to the buffer
-    fn normalize_peak(&self, buffer: &mut AudioBuffer<f32>) {
+    /// Applies peak normalization to the buffer, returning the linear gain used
+    fn normalize_peak(&self, buffer: &mut AudioBuffer<f32>) -> Option<f32> {
         let max_sample = self.find_peak_simd(&buffer.data);
         if max_sample > 0.0 && max_sample < 1.0 {
             // Convert target dB to linear scale with headroom
@@ -126,11 +128,14 @@ impl AudioNormalizer {
             // - Should provide significant speedup for gain application
             // - Handle remainder samples with scalar code
             // - Maintains exact compatibility with current limiting behavior
+            Some(gain)
+        } else {
+            None
         }
     }
 
-    /// Applies RMS normalization to the buffer
-    fn normalize_rms(&self, buffer: &mut AudioBuffer<f32>) {
+    /// Applies RMS normalization to the buffer, returning the linear gain used
+    fn normalize_rms(&self, buffer: &mut AudioBuffer<f32>) -> Option<f32> {
         let rms = Self::calculate_rms_simd(&buffer.data);
         if rms > 0.0 {
             // Convert target dB to linear scale with headroom
@@ -146,12 +151,15 @@ impl AudioNormalizer {
 
             // Apply gain with SIMD-optimized limiter to prevent clipping
             Self::apply_gain_with_limiting_simd(&mut buffer.data, gain);
+            Some(gain)
+        } else {
+            None
         }
     }
 
     /// Applies LUFS normalization to the buffer (simplified implementation)
-    fn normalize_lufs(&self, buffer: &mut AudioBuffer<f32>) {
-        self.normalize_rms(buffer);
+    fn normalize_lufs(&self, buffer: &mut AudioBuffer<f32>) -> Option<f32> {
+        self.normalize_rms(buffer)
     }
 
     /// SIMD-optimized peak finding for audio data
@@ -389,7 +397,7 @@ impl AudioProcessor for AudioNormalizer {
     }
 
     fn reset(&mut self) {
-        // Normalizer is stateless, nothing to reset
+        self.last_gain_db = None;
     }
 }
 
@@ -419,11 +427,25 @@ impl Validatable for AudioNormalizer {
     }
 }
 
+impl MeasurementReporting for AudioNormalizer {
+    fn stage_measurements(
+        &self,
+        before: &LevelMeasurement,
+        after: &LevelMeasurement,
+    ) -> StageMeasurements {
+        StageMeasurements {
+            gain_db: self.last_gain_db,
+            ..StageMeasurements::from_levels(before, after)
+        }
+    }
+}
+
 impl Default for AudioNormalizer {
     /// Creates a new audio normalizer with default configuration
     fn default() -> Self {
         Self {
             config: NormalizerConfig::default(),
+            last_gain_db: None,
         }
     }
 }
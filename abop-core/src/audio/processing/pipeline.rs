@@ -1,57 +1,136 @@
 //! Audio processing pipeline
 //!
 //! This module contains the core `AudioProcessingPipeline` struct and its
-//! main coordination logic for combining multiple processing components.
+//! main coordination logic for running an ordered chain of processing stages.
 
+use super::chain::{PipelineStage, ProcessorRegistry};
 use super::error::{AudioProcessingError, Result};
-use super::traits::{AudioProcessor, LatencyReporting, Validatable};
+use super::report::{LevelMeasurement, ProcessingReport, StageReport};
+use super::traits::{
+    AudioProcessor, Bypassable, LatencyReporting, MeasurementReporting, Validatable,
+};
 use super::validation::ConfigValidator;
 use super::{
-    AudioNormalizer, ChannelMixer, ChannelMixerConfig, LinearResampler, NormalizerConfig,
-    ProcessingConfig, ResamplerConfig, SilenceDetector, SilenceDetectorConfig,
+    AudioNormalizer, ChannelMixer, ChannelMixerConfig, DeEsser, Equalizer, NoiseReducer,
+    NormalizerConfig, ProcessingConfig, Resampler, ResamplerConfig, SilenceDetector,
+    SilenceDetectorConfig,
 };
 use crate::audio::AudioBuffer;
 
 /// Main audio processor that orchestrates all processing components.
 ///
-/// This processor combines resampling, channel mixing, normalization, and
-/// silence detection into a single, configurable processing pipeline.
+/// The pipeline runs an ordered chain of [`PipelineStage`]s. When the
+/// configuration lists explicit `stages` they are built through the
+/// [`ProcessorRegistry`]; otherwise the chain is derived from the component
+/// configurations as resampling, channel mixing, noise reduction,
+/// equalization, de-essing, normalization and silence detection, in that order.
+/// A configuration that sets both is rejected rather than silently dropping
+/// the component settings.
 #[derive(Debug, Clone)]
 pub struct AudioProcessingPipeline {
-    pub(super) resampler: LinearResampler,
-    pub(super) channel_mixer: ChannelMixer,
-    pub(super) normalizer: AudioNormalizer,
-    pub(super) silence_detector: SilenceDetector,
+    pub(super) stages: Vec<PipelineStage>,
     pub(super) config: ProcessingConfig,
 }
 
 impl AudioProcessingPipeline {
     /// Creates a new audio processing pipeline with the specified configuration
     ///
+    /// Stages are looked up in the [global registry](ProcessorRegistry::global).
+    ///
     /// # Errors
     ///
     /// Returns [`AudioProcessingError`] if the configuration is invalid or if any processing component fails to initialize.
     /// This includes validation errors for sample rates, channel counts, or component-specific parameters.
     pub fn new(config: ProcessingConfig) -> Result<Self> {
+        let registry = ProcessorRegistry::global()
+            .read()
+            .map_err(|e| AudioProcessingError::Pipeline(e.to_string()))?;
+        Self::with_registry(config, &registry)
+    }
+
+    /// Creates a new pipeline, resolving named stages with the given registry
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the configuration is invalid, names
+    /// an unregistered stage, or any processing component fails to initialize.
+    pub fn with_registry(config: ProcessingConfig, registry: &ProcessorRegistry) -> Result<Self> {
         // Use centralized validation
         ConfigValidator::validate_config(&config)?;
 
-        let resampler = LinearResampler::new(config.resampler.clone().unwrap_or_default())
-            .map_err(|e| AudioProcessingError::Resampler(e.to_string()))?;
-        let channel_mixer = ChannelMixer::new(config.channel_mixer.clone().unwrap_or_default())
-            .map_err(|e| AudioProcessingError::ChannelMixer(e.to_string()))?;
-        let normalizer = AudioNormalizer::new(config.normalizer.clone().unwrap_or_default())
-            .map_err(|e| AudioProcessingError::Normalizer(e.to_string()))?;
-        let silence_detector =
-            SilenceDetector::new(config.silence_detector.clone().unwrap_or_default())
+        let stages = Self::build_stages(&config, registry)?;
+        Ok(Self { stages, config })
+    }
+
+    /// Builds the processing chain for a configuration
+    fn build_stages(
+        config: &ProcessingConfig,
+        registry: &ProcessorRegistry,
+    ) -> Result<Vec<PipelineStage>> {
+        if !config.stages.is_empty() {
+            return config
+                .stages
+                .iter()
+                .map(|stage| registry.create(stage))
+                .collect();
+        }
+
+        let mut stages = Vec::new();
+        if let Some(resampler_config) = &config.resampler
+            && resampler_config.target_sample_rate.is_some()
+        {
+            let resampler = Resampler::new(resampler_config.clone())
+                .map_err(|e| AudioProcessingError::Resampler(e.to_string()))?;
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::RESAMPLER,
+                Box::new(resampler),
+            ));
+        }
+        if let Some(mixer_config) = &config.channel_mixer
+            && mixer_config.target_channels.is_some()
+        {
+            let channel_mixer = ChannelMixer::new(mixer_config.clone())
+                .map_err(|e| AudioProcessingError::ChannelMixer(e.to_string()))?;
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::CHANNEL_MIXER,
+                Box::new(channel_mixer),
+            ));
+        }
+        if let Some(noise_config) = &config.noise_reduction {
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::NOISE_REDUCTION,
+                Box::new(NoiseReducer::new(noise_config.clone())?),
+            ));
+        }
+        if let Some(equalizer_config) = &config.equalizer {
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::EQUALIZER,
+                Box::new(Equalizer::new(equalizer_config.clone())?),
+            ));
+        }
+        if let Some(de_esser_config) = &config.de_esser {
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::DE_ESSER,
+                Box::new(DeEsser::new(de_esser_config.clone())?),
+            ));
+        }
+        if let Some(normalizer_config) = &config.normalizer {
+            let normalizer = AudioNormalizer::new(normalizer_config.clone())
+                .map_err(|e| AudioProcessingError::Normalizer(e.to_string()))?;
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::NORMALIZER,
+                Box::new(normalizer),
+            ));
+        }
+        if let Some(silence_config) = &config.silence_detector {
+            let silence_detector = SilenceDetector::new(silence_config.clone())
                 .map_err(|e| AudioProcessingError::SilenceDetector(e.to_string()))?;
-        Ok(Self {
-            resampler,
-            channel_mixer,
-            normalizer,
-            silence_detector,
-            config,
-        })
+            stages.push(PipelineStage::new(
+                ProcessorRegistry::SILENCE_DETECTOR,
+                Box::new(silence_detector),
+            ));
+        }
+        Ok(stages)
     }
 
     /// Process a file and save the result to the specified output path
@@ -132,7 +211,12 @@ impl AudioProcessingPipeline {
         };
         Self::new(config)
     }
-    /// Processes an audio buffer through the entire pipeline
+    /// Processes an audio buffer through every stage of the chain in order
+    ///
+    /// The buffer is treated as a complete signal, so stages with look-ahead
+    /// compensate their delay and the output lines up with the input.
+    ///
+    /// Bypassed stages leave the buffer untouched.
     ///
     /// # Errors
     ///
@@ -148,64 +232,77 @@ impl AudioProcessingPipeline {
             buffer.channels,
             buffer.data.len()
         );
-        // Apply resampling first if needed
-        if let Some(ref resampler_config) = self.config.resampler
-            && resampler_config.target_sample_rate.is_some()
-        {
-            self.resampler.process(buffer)?;
+        for stage in &mut self.stages {
+            stage.process_complete(buffer)?;
             log::debug!(
-                "After resampling: {} Hz, {} samples",
+                "After {}: {} Hz, {} channels, {} samples",
+                stage.name(),
                 buffer.sample_rate,
-                buffer.data.len()
-            );
-        }
-        // Apply channel mixing if needed
-        if let Some(ref mixer_config) = self.config.channel_mixer
-            && mixer_config.target_channels.is_some()
-        {
-            self.channel_mixer.process(buffer)?;
-            log::debug!(
-                "After channel mixing: {} channels, {} samples",
                 buffer.channels,
                 buffer.data.len()
             );
         }
-        // Apply normalization if enabled
-        if self.config.normalizer.is_some() {
-            self.normalizer.process(buffer)?;
-            log::debug!("After normalization: {} samples", buffer.data.len());
-        }
-        // Apply silence removal if enabled
-        if self.config.silence_detector.is_some() {
-            self.silence_detector.process(buffer)?;
-            log::debug!("After silence removal: {} samples", buffer.data.len());
-        }
         Ok(())
     }
-    /// Gets the total latency in samples for all components
+    /// Processes an audio buffer like [`Self::process_buffer`], measuring it
+    /// before and after every stage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if any processing step fails.
+    pub fn process_buffer_with_report(
+        &mut self,
+        buffer: &mut AudioBuffer<f32>,
+    ) -> Result<ProcessingReport> {
+        let input = LevelMeasurement::measure(buffer);
+        let mut before = input;
+        let mut stages = Vec::with_capacity(self.stages.len());
+        for stage in &mut self.stages {
+            stage.process_complete(buffer)?;
+            let after = LevelMeasurement::measure(buffer);
+            stages.push(StageReport {
+                name: stage.name().to_string(),
+                bypassed: stage.is_bypassed(),
+                measurements: stage.stage_measurements(&before, &after),
+            });
+            before = after;
+        }
+        Ok(ProcessingReport {
+            input,
+            output: before,
+            stages,
+        })
+    }
+    /// Gets the total latency in samples as the sum of all active stages
     #[must_use]
     pub fn get_total_latency_samples(&self) -> usize {
-        let mut total_latency = 0;
-        if self.config.resampler.is_some() {
-            total_latency += self.resampler.get_latency_samples();
-        }
-        if self.config.channel_mixer.is_some() {
-            total_latency += self.channel_mixer.get_latency_samples();
-        }
-        if self.config.normalizer.is_some() {
-            total_latency += self.normalizer.get_latency_samples();
-        }
-        if self.config.silence_detector.is_some() {
-            total_latency += self.silence_detector.get_latency_samples();
-        }
-        total_latency
+        self.stages
+            .iter()
+            .map(LatencyReporting::get_latency_samples)
+            .sum()
     }
     /// Resets all processing components to their initial state
     pub fn reset(&mut self) {
-        self.resampler.reset();
-        self.channel_mixer.reset();
-        self.normalizer.reset();
-        self.silence_detector.reset();
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+    /// Returns the stages of the processing chain in order
+    #[must_use]
+    pub fn stages(&self) -> &[PipelineStage] {
+        &self.stages
+    }
+    /// Returns the stages mutably, e.g. to toggle bypass
+    pub fn stages_mut(&mut self) -> &mut [PipelineStage] {
+        &mut self.stages
+    }
+    /// Returns the first stage with the given name
+    pub fn stage_mut(&mut self, name: &str) -> Option<&mut PipelineStage> {
+        self.stages.iter_mut().find(|stage| stage.name() == name)
+    }
+    /// Appends a stage to the end of the chain
+    pub fn push_stage(&mut self, stage: PipelineStage) {
+        self.stages.push(stage);
     }
     /// Gets the current configuration
     #[must_use]
@@ -219,19 +316,11 @@ impl AudioProcessingPipeline {
     /// Returns [`AudioProcessingError`] if the new configuration is invalid or if any processing component fails to reinitialize.
     pub fn configure(&mut self, config: ProcessingConfig) -> Result<()> {
         config.validate()?;
-        // Recreate components with new configuration
-        if let Some(ref resampler_config) = config.resampler {
-            self.resampler = LinearResampler::new(resampler_config.clone())?;
-        }
-        if let Some(ref mixer_config) = config.channel_mixer {
-            self.channel_mixer = ChannelMixer::new(mixer_config.clone())?;
-        }
-        if let Some(ref normalizer_config) = config.normalizer {
-            self.normalizer = AudioNormalizer::new(normalizer_config.clone())?;
-        }
-        if let Some(ref silence_config) = config.silence_detector {
-            self.silence_detector = SilenceDetector::new(silence_config.clone())?;
-        }
+        // Rebuild the chain with the new configuration
+        let registry = ProcessorRegistry::global()
+            .read()
+            .map_err(|e| AudioProcessingError::Pipeline(e.to_string()))?;
+        self.stages = Self::build_stages(&config, &registry)?;
         self.config = config;
         Ok(())
     }
@@ -242,7 +331,9 @@ impl AudioProcessingPipeline {
     /// Returns [`AudioProcessingError`] if the current configuration is invalid.
     pub fn validate(&self) -> Result<()> {
         self.config.validate()?;
-        // Additional pipeline-specific validation could go here
+        for stage in &self.stages {
+            stage.validate()?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,450 @@
+//! Before/after measurements of processing runs
+//!
+//! This module measures what a processing run actually changed in the audio.
+//! [`LevelMeasurement`] captures the peak, RMS and integrated loudness (ITU-R
+//! BS.1770 LUFS) of a buffer. The pipeline measures the buffer around every
+//! stage and asks the stage to describe its effect through
+//! [`MeasurementReporting`](super::traits::MeasurementReporting), producing a
+//! [`ProcessingReport`] per buffer. File and batch processors wrap these into
+//! [`FileProcessingReport`] and [`BatchProcessingReport`], which serialize to
+//! JSON for the CLI and GUI.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::biquad::{Biquad, BiquadCoefficients};
+use super::utils::gain::{linear_to_db, power_to_db};
+use crate::audio::AudioBuffer;
+use crate::utils::casting::domain::audio::{safe_f64_to_usize_samples, safe_usize_to_f64_audio};
+
+/// Length of a loudness gating block in seconds
+const GATING_BLOCK_SECS: f64 = 0.4;
+
+/// Number of hops per gating block (75% overlap)
+const GATING_BLOCK_HOPS: usize = 4;
+
+/// Absolute gate of integrated loudness in LUFS
+const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+
+/// Relative gate below the ungated loudness in LU
+const RELATIVE_GATE_LU: f32 = -10.0;
+
+/// Offset between K-weighted power in dB and LUFS
+const LUFS_OFFSET: f32 = -0.691;
+
+/// Duration changes below this are treated as rounding, in seconds
+const DURATION_EPSILON_SECS: f64 = 1e-3;
+
+/// Peak, RMS and loudness of an audio buffer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelMeasurement {
+    /// Duration in seconds
+    pub duration_secs: f64,
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    /// Number of channels
+    pub channels: u16,
+    /// Highest sample peak in dBFS
+    pub peak_db: f32,
+    /// RMS level over all channels in dBFS
+    pub rms_db: f32,
+    /// Integrated loudness in LUFS (ITU-R BS.1770, all channels weighted equally)
+    pub lufs: f32,
+}
+
+impl LevelMeasurement {
+    /// Measures a buffer
+    #[must_use]
+    pub fn measure(buffer: &AudioBuffer<f32>) -> Self {
+        let channels = usize::from(buffer.channels.max(1));
+        let frames = buffer.data.len() / channels;
+        let peak = buffer.data.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let sum_squares: f64 = buffer.data.iter().map(|s| f64::from(*s).powi(2)).sum();
+        let mean_power = sum_squares / safe_usize_to_f64_audio(buffer.data.len().max(1));
+
+        Self {
+            duration_secs: if buffer.sample_rate == 0 {
+                0.0
+            } else {
+                safe_usize_to_f64_audio(frames) / f64::from(buffer.sample_rate)
+            },
+            sample_rate: buffer.sample_rate,
+            channels: buffer.channels,
+            peak_db: linear_to_db(peak),
+            rms_db: power_to_db(mean_power),
+            lufs: integrated_loudness(buffer),
+        }
+    }
+}
+
+/// Integrated loudness of a buffer in LUFS
+///
+/// Follows ITU-R BS.1770: K-weighting, 400 ms blocks with 75% overlap, an
+/// absolute gate at -70 LUFS and a relative gate 10 LU below the ungated level.
+/// Buffers shorter than one block are measured as a single block.
+#[must_use]
+pub fn integrated_loudness(buffer: &AudioBuffer<f32>) -> f32 {
+    let floor = linear_to_db(0.0);
+    if buffer.data.is_empty() || buffer.sample_rate == 0 {
+        return floor;
+    }
+    let channels = usize::from(buffer.channels.max(1));
+    let hops_per_block = safe_usize_to_f64_audio(GATING_BLOCK_HOPS);
+    let hop_frames = safe_f64_to_usize_samples(
+        (f64::from(buffer.sample_rate) * GATING_BLOCK_SECS / hops_per_block).round(),
+    )
+    .unwrap_or(1)
+    .max(1);
+
+    let mut shelf = Biquad::new(BiquadCoefficients::k_weighting_shelf(buffer.sample_rate));
+    let mut high_pass = Biquad::new(BiquadCoefficients::k_weighting_high_pass(
+        buffer.sample_rate,
+    ));
+
+    // K-weighted energy summed over channels, per hop
+    let mut hops = Vec::new();
+    let mut energy = 0.0f64;
+    let mut filled = 0;
+    for frame in buffer.data.chunks(channels) {
+        for (channel, sample) in frame.iter().enumerate() {
+            let weighted =
+                high_pass.process_sample(channel, shelf.process_sample(channel, *sample));
+            energy += f64::from(weighted).powi(2);
+        }
+        filled += 1;
+        if filled == hop_frames {
+            hops.push(energy);
+            energy = 0.0;
+            filled = 0;
+        }
+    }
+
+    let block_frames = safe_usize_to_f64_audio(hop_frames * GATING_BLOCK_HOPS);
+    let blocks: Vec<f64> = if hops.len() < GATING_BLOCK_HOPS {
+        let frames = safe_usize_to_f64_audio(hops.len() * hop_frames + filled);
+        vec![(hops.iter().sum::<f64>() + energy) / frames]
+    } else {
+        hops.windows(GATING_BLOCK_HOPS)
+            .map(|window| window.iter().sum::<f64>() / block_frames)
+            .collect()
+    };
+
+    let loudness = |power: f64| power_to_db(power) + LUFS_OFFSET;
+    let gated_mean = |gate: f32| {
+        let (sum, count) = blocks
+            .iter()
+            .filter(|power| loudness(**power) > gate)
+            .fold((0.0, 0usize), |(sum, count), power| {
+                (sum + power, count + 1)
+            });
+        (count > 0).then(|| sum / safe_usize_to_f64_audio(count))
+    };
+
+    let Some(ungated) = gated_mean(ABSOLUTE_GATE_LUFS) else {
+        return floor;
+    };
+    let relative_gate = (loudness(ungated) + RELATIVE_GATE_LU).max(ABSOLUTE_GATE_LUFS);
+    gated_mean(relative_gate).map_or(floor, loudness)
+}
+
+/// What one stage changed in the audio
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageMeasurements {
+    /// Change of the RMS level across the stage in dB
+    pub level_change_db: f32,
+    /// Gain the stage deliberately applied in dB, if it applies one
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub gain_db: Option<f32>,
+    /// Output over input sample rate, if the stage changed it
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub resample_ratio: Option<f64>,
+    /// Audio removed by the stage in seconds, if any
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub silence_removed_secs: Option<f64>,
+}
+
+impl StageMeasurements {
+    /// Derives the measurements from the levels around a stage
+    #[must_use]
+    pub fn from_levels(before: &LevelMeasurement, after: &LevelMeasurement) -> Self {
+        let removed = before.duration_secs - after.duration_secs;
+        Self {
+            level_change_db: after.rms_db - before.rms_db,
+            gain_db: None,
+            resample_ratio: (before.sample_rate != after.sample_rate && before.sample_rate > 0)
+                .then(|| f64::from(after.sample_rate) / f64::from(before.sample_rate)),
+            silence_removed_secs: (removed > DURATION_EPSILON_SECS).then_some(removed),
+        }
+    }
+}
+
+/// Measurements reported by one stage of a processing run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    /// Registered name of the stage
+    pub name: String,
+    /// Whether the stage was bypassed
+    pub bypassed: bool,
+    /// What the stage changed
+    pub measurements: StageMeasurements,
+}
+
+/// Levels before and after a processing run, with per-stage measurements
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessingReport {
+    /// Levels of the unprocessed audio
+    pub input: LevelMeasurement,
+    /// Levels of the processed audio
+    pub output: LevelMeasurement,
+    /// Measurements of each stage, in chain order
+    pub stages: Vec<StageReport>,
+}
+
+impl ProcessingReport {
+    /// Total gain deliberately applied by the stages in dB, if any stage applied one
+    #[must_use]
+    pub fn gain_db(&self) -> Option<f32> {
+        self.stages
+            .iter()
+            .filter_map(|stage| stage.measurements.gain_db)
+            .reduce(|total, gain| total + gain)
+    }
+
+    /// Overall output over input sample rate
+    #[must_use]
+    pub fn resample_ratio(&self) -> f64 {
+        if self.input.sample_rate == 0 {
+            1.0
+        } else {
+            f64::from(self.output.sample_rate) / f64::from(self.input.sample_rate)
+        }
+    }
+
+    /// Total audio removed by the stages in seconds
+    #[must_use]
+    pub fn silence_removed_secs(&self) -> f64 {
+        self.stages
+            .iter()
+            .filter_map(|stage| stage.measurements.silence_removed_secs)
+            .sum()
+    }
+
+    /// Change of integrated loudness in LU
+    #[must_use]
+    pub fn loudness_change_lu(&self) -> f32 {
+        self.output.lufs - self.input.lufs
+    }
+}
+
+/// Report for one processed file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileProcessingReport {
+    /// File that was processed
+    pub input_path: PathBuf,
+    /// File the result was written to
+    pub output_path: PathBuf,
+    /// Wall-clock processing time in seconds
+    pub processing_secs: f64,
+    /// What processing changed
+    #[serde(flatten)]
+    pub report: ProcessingReport,
+}
+
+impl FileProcessingReport {
+    /// Creates a file report
+    #[must_use]
+    pub const fn new(
+        input_path: PathBuf,
+        output_path: PathBuf,
+        processing_time: Duration,
+        report: ProcessingReport,
+    ) -> Self {
+        Self {
+            input_path,
+            output_path,
+            processing_secs: processing_time.as_secs_f64(),
+            report,
+        }
+    }
+}
+
+/// Totals across every file of a batch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    /// Number of files processed successfully
+    pub files_processed: usize,
+    /// Number of files that failed
+    pub files_failed: usize,
+    /// Total duration of the inputs in seconds
+    pub input_duration_secs: f64,
+    /// Total duration of the outputs in seconds
+    pub output_duration_secs: f64,
+    /// Total audio removed in seconds
+    pub silence_removed_secs: f64,
+    /// Mean integrated loudness of the inputs in LUFS
+    pub mean_input_lufs: f32,
+    /// Mean integrated loudness of the outputs in LUFS
+    pub mean_output_lufs: f32,
+    /// Highest output peak in dBFS
+    pub max_output_peak_db: f32,
+    /// Mean gain applied per file in dB, over files where gain was applied
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub mean_gain_db: Option<f32>,
+}
+
+/// Per-file and per-batch report of a batch run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchProcessingReport {
+    /// Totals across the batch
+    pub summary: BatchSummary,
+    /// Reports of the successfully processed files
+    pub files: Vec<FileProcessingReport>,
+}
+
+impl BatchProcessingReport {
+    /// Aggregates file reports into a batch report
+    #[must_use]
+    pub fn new(files: Vec<FileProcessingReport>, files_failed: usize) -> Self {
+        let mean = |values: &[f32]| {
+            #[allow(clippy::cast_possible_truncation)]
+            let count = safe_usize_to_f64_audio(values.len()) as f32;
+            (!values.is_empty()).then(|| values.iter().sum::<f32>() / count)
+        };
+        let reports = || files.iter().map(|file| &file.report);
+        let input_lufs: Vec<f32> = reports().map(|report| report.input.lufs).collect();
+        let output_lufs: Vec<f32> = reports().map(|report| report.output.lufs).collect();
+        let gains: Vec<f32> = reports().filter_map(ProcessingReport::gain_db).collect();
+
+        let summary = BatchSummary {
+            files_processed: files.len(),
+            files_failed,
+            input_duration_secs: reports().map(|report| report.input.duration_secs).sum(),
+            output_duration_secs: reports().map(|report| report.output.duration_secs).sum(),
+            silence_removed_secs: reports().map(ProcessingReport::silence_removed_secs).sum(),
+            mean_input_lufs: mean(&input_lufs).unwrap_or_else(|| linear_to_db(0.0)),
+            mean_output_lufs: mean(&output_lufs).unwrap_or_else(|| linear_to_db(0.0)),
+            max_output_peak_db: reports()
+                .map(|report| report.output.peak_db)
+                .reduce(f32::max)
+                .unwrap_or_else(|| linear_to_db(0.0)),
+            mean_gain_db: mean(&gains),
+        };
+        Self { summary, files }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processing::config::{NormalizerConfig, SilenceDetectorConfig};
+    use crate::audio::processing::{AudioProcessingPipeline, ProcessingConfig};
+    use crate::test_utils::audio::{create_sine_buffer, create_test_buffer_with_silence};
+
+    #[test]
+    fn test_sine_loudness_matches_reference() {
+        // A full-scale 997 Hz sine in one channel reads -3.01 LUFS
+        let mono = create_sine_buffer(48000, 1, 997.0, 2.0, 0.5);
+        let lufs = integrated_loudness(&mono);
+        assert!((lufs - -9.03).abs() < 0.1, "mono {lufs} LUFS");
+
+        let stereo = create_sine_buffer(48000, 2, 997.0, 2.0, 0.5);
+        let lufs = integrated_loudness(&stereo);
+        assert!((lufs - -6.02).abs() < 0.1, "stereo {lufs} LUFS");
+
+        let resampled = create_sine_buffer(44100, 1, 997.0, 2.0, 0.5);
+        assert!((integrated_loudness(&resampled) - -9.03).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_silence_is_gated() {
+        let silent = create_sine_buffer(48000, 1, 997.0, 1.0, 0.0);
+        assert!(integrated_loudness(&silent) < ABSOLUTE_GATE_LUFS);
+
+        // Doubling the length with silence halves the power (-3 dB), but only
+        // the blocks straddling the edges lower the gated loudness
+        let tone = create_sine_buffer(48000, 1, 997.0, 2.0, 0.5);
+        let mut padded = silent.clone();
+        padded.data.extend_from_slice(&tone.data);
+        padded.data.extend_from_slice(&silent.data);
+        let drop = integrated_loudness(&tone) - integrated_loudness(&padded);
+        assert!((0.0..1.0).contains(&drop), "dropped {drop} LU");
+    }
+
+    #[test]
+    fn test_pipeline_reports_each_stage() {
+        let config = ProcessingConfig::builder()
+            .with_target_sample_rate(22050)
+            .with_normalizer(
+                NormalizerConfig::builder()
+                    .with_target_loudness(-6.0)
+                    .build(),
+            )
+            .with_silence_detector(SilenceDetectorConfig::default())
+            .build();
+        let mut pipeline = AudioProcessingPipeline::new(config).unwrap();
+        let mut buffer = create_test_buffer_with_silence(44100, 1, 2.0, 1.0, 1.0);
+
+        let report = pipeline.process_buffer_with_report(&mut buffer).unwrap();
+        let names: Vec<_> = report
+            .stages
+            .iter()
+            .map(|stage| stage.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["resampler", "normalizer", "silence_detector"]);
+
+        let resampler = &report.stages[0].measurements;
+        assert_eq!(resampler.resample_ratio, Some(0.5));
+        assert!(resampler.gain_db.is_none());
+        assert!((report.resample_ratio() - 0.5).abs() < f64::EPSILON);
+
+        let normalizer = &report.stages[1].measurements;
+        let gain = normalizer.gain_db.unwrap();
+        assert!((normalizer.level_change_db - gain).abs() < 0.1);
+        assert_eq!(report.gain_db(), Some(gain));
+
+        assert!(report.silence_removed_secs() > 0.5);
+        assert!(report.output.duration_secs < report.input.duration_secs);
+        assert_eq!(report.output, LevelMeasurement::measure(&buffer));
+    }
+
+    #[test]
+    fn test_batch_summary_and_json() {
+        let buffer = create_sine_buffer(44100, 1, 440.0, 1.0, 0.25);
+        let level = LevelMeasurement::measure(&buffer);
+        let file = |name: &str, gain: Option<f32>| {
+            FileProcessingReport::new(
+                PathBuf::from(format!("/in/{name}")),
+                PathBuf::from(format!("/out/{name}")),
+                Duration::from_millis(250),
+                ProcessingReport {
+                    input: level,
+                    output: level,
+                    stages: vec![StageReport {
+                        name: "normalizer".to_string(),
+                        bypassed: false,
+                        measurements: StageMeasurements {
+                            gain_db: gain,
+                            ..StageMeasurements::default()
+                        },
+                    }],
+                },
+            )
+        };
+
+        let report =
+            BatchProcessingReport::new(vec![file("a.wav", Some(2.0)), file("b.wav", None)], 1);
+        assert_eq!(report.summary.files_processed, 2);
+        assert_eq!(report.summary.files_failed, 1);
+        assert_eq!(report.summary.mean_gain_db, Some(2.0));
+        assert!((report.summary.input_duration_secs - 2.0).abs() < 1e-9);
+        assert!((report.summary.mean_output_lufs - level.lufs).abs() < f32::EPSILON);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"input_path\":\"/in/a.wav\""));
+        assert!(json.contains("\"lufs\""));
+        let parsed: BatchProcessingReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -8,9 +8,10 @@
 
 use super::{
     casting_utils::error_conversion::cast_to_audio_error,
-    config::ResamplerConfig,
+    config::{ResampleQuality, ResamplerConfig},
+    sinc_resampler::SincResampler,
     error::{AudioProcessingError, Result},
-    traits::{AudioProcessor, Configurable, LatencyReporting, Validatable},
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
     validation::ConfigValidator,
 };
 use crate::audio::AudioBuffer;
@@ -397,6 +398,95 @@ impl Default for LinearResampler {
     }
 }
 
+/// Resampler selected by [`ResampleQuality`]
+///
+/// [`ResampleQuality::High`] uses the band-limited [`SincResampler`]; the
+/// lower quality levels keep the cheaper [`LinearResampler`].
+#[derive(Debug, Clone)]
+pub enum Resampler {
+    /// Linear interpolation resampler
+    Linear(LinearResampler),
+    /// Windowed-sinc polyphase resampler
+    Sinc(SincResampler),
+}
+
+impl Resampler {
+    /// Creates the resampler matching the configured quality level
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the resampler configuration validation fails.
+    pub fn new(config: ResamplerConfig) -> Result<Self> {
+        if matches!(config.quality, ResampleQuality::High) {
+            SincResampler::new(config).map(Self::Sinc)
+        } else {
+            LinearResampler::new(config).map(Self::Linear)
+        }
+    }
+
+    /// Returns true if this is the band-limited sinc resampler
+    #[must_use]
+    pub const fn is_sinc(&self) -> bool {
+        matches!(self, Self::Sinc(_))
+    }
+}
+
+impl AudioProcessor for Resampler {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        match self {
+            Self::Linear(resampler) => resampler.process(buffer),
+            Self::Sinc(resampler) => resampler.process(buffer),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Linear(resampler) => resampler.reset(),
+            Self::Sinc(resampler) => resampler.reset(),
+        }
+    }
+}
+
+impl Configurable<ResamplerConfig> for Resampler {
+    fn configure(&mut self, config: ResamplerConfig) -> Result<()> {
+        *self = Self::new(config)?;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &ResamplerConfig {
+        match self {
+            Self::Linear(resampler) => resampler.get_config(),
+            Self::Sinc(resampler) => resampler.get_config(),
+        }
+    }
+}
+
+impl LatencyReporting for Resampler {
+    fn get_latency_samples(&self) -> usize {
+        match self {
+            Self::Linear(resampler) => resampler.get_latency_samples(),
+            Self::Sinc(resampler) => resampler.get_latency_samples(),
+        }
+    }
+}
+
+impl MeasurementReporting for Resampler {}
+
+impl Validatable for Resampler {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Linear(resampler) => resampler.validate(),
+            Self::Sinc(resampler) => resampler.validate(),
+        }
+    }
+}
+
+impl Default for Resampler {
+    fn default() -> Self {
+        Self::Linear(LinearResampler::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -636,4 +726,26 @@ mod tests {
         let resampler = LinearResampler::default();
         assert!(resampler.validate().is_ok());
     }
+
+    #[test]
+    fn test_pipeline_selects_resampler_by_quality() {
+        use crate::audio::processing::{AudioProcessingPipeline, ProcessingConfig};
+
+        let pipeline_for = |quality| {
+            AudioProcessingPipeline::new(ProcessingConfig {
+                resampler: Some(ResamplerConfig {
+                    target_sample_rate: Some(22050),
+                    quality,
+                    enable_anti_aliasing: true,
+                }),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        // Only the sinc filter reports more than one sample of latency
+        assert!(pipeline_for(ResampleQuality::High).get_total_latency_samples() > 1);
+        assert_eq!(pipeline_for(ResampleQuality::Medium).get_total_latency_samples(), 1);
+        assert_eq!(pipeline_for(ResampleQuality::Low).get_total_latency_samples(), 1);
+    }
 }
@@ -7,7 +7,7 @@ use super::{
     casting_utils::error_conversion::cast_to_audio_error,
     config::{SilenceDetectorConfig, SilenceRemovalMode},
     error::Result,
-    traits::{AudioProcessor, Configurable, LatencyReporting, Validatable},
+    traits::{AudioProcessor, Configurable, LatencyReporting, MeasurementReporting, Validatable},
     validation::ConfigValidator,
 };
 use crate::audio::AudioBuffer;
@@ -336,6 +336,8 @@ impl LatencyReporting for SilenceDetector {
     }
 }
 
+impl MeasurementReporting for SilenceDetector {}
+
 impl Validatable for SilenceDetector {
     fn validate(&self) -> Result<()> {
         ConfigValidator::validate_silence_detector_config(&self.config)
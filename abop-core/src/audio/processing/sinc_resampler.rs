@@ -0,0 +1,724 @@
+//! Band-limited sample rate conversion
+//!
+//! This module provides a windowed-sinc polyphase resampler. The conversion
+//! ratio is reduced to `up / down` and a bank of Kaiser-windowed sinc filters
+//! is precomputed, one per fractional phase, so every output sample is a short
+//! dot product against the input history. Filter length, stopband attenuation
+//! and passband rolloff are chosen from [`ResampleQuality`].
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::{
+    config::{ResampleQuality, ResamplerConfig},
+    error::{AudioProcessingError, Result},
+    traits::{AudioProcessor, Configurable, LatencyReporting, StreamingProcessor, Validatable},
+    validation::ConfigValidator,
+};
+use crate::audio::AudioBuffer;
+use crate::utils::casting::domain::audio::safe_usize_to_f64_audio;
+
+/// Upper bound on the number of precomputed filter phases.
+///
+/// Ratios whose reduced numerator exceeds this (e.g. 44101 Hz to 48000 Hz)
+/// use the nearest precomputed phase instead of the exact one.
+const MAX_PHASES: usize = 4096;
+
+/// Kaiser window design parameters for a resampling quality level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KaiserParameters {
+    /// Number of sinc zero crossings on each side of the filter centre
+    pub zero_crossings: usize,
+    /// Target stopband attenuation in dB
+    pub attenuation_db: f64,
+    /// Filter cutoff as a fraction of the lower of the two Nyquist frequencies
+    pub rolloff: f64,
+}
+
+impl KaiserParameters {
+    /// Returns the filter design used for the given quality level
+    ///
+    /// The rolloff leaves room for the transition band implied by the filter
+    /// length, so the stopband starts at or below the output Nyquist frequency.
+    #[must_use]
+    pub const fn for_quality(quality: ResampleQuality) -> Self {
+        match quality {
+            ResampleQuality::Low => Self {
+                zero_crossings: 8,
+                attenuation_db: 60.0,
+                rolloff: 0.80,
+            },
+            ResampleQuality::Medium => Self {
+                zero_crossings: 16,
+                attenuation_db: 80.0,
+                rolloff: 0.86,
+            },
+            ResampleQuality::High => Self {
+                zero_crossings: 32,
+                attenuation_db: 100.0,
+                rolloff: 0.90,
+            },
+        }
+    }
+
+    /// Kaiser window shape parameter for the target attenuation
+    #[must_use]
+    pub fn beta(&self) -> f64 {
+        let a = self.attenuation_db;
+        if a > 50.0 {
+            0.1102 * (a - 8.7)
+        } else if a >= 21.0 {
+            0.5842 * (a - 21.0).powf(0.4) + 0.078_86 * (a - 21.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Precomputed polyphase filter bank for one conversion ratio
+struct PolyphaseFilter {
+    source_rate: u32,
+    target_rate: u32,
+    up: usize,
+    down: usize,
+    phases: usize,
+    half_taps: usize,
+    coefficients: Vec<f32>,
+}
+
+impl fmt::Debug for PolyphaseFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolyphaseFilter")
+            .field("source_rate", &self.source_rate)
+            .field("target_rate", &self.target_rate)
+            .field("up", &self.up)
+            .field("down", &self.down)
+            .field("phases", &self.phases)
+            .field("taps", &self.taps())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PolyphaseFilter {
+    fn new(source_rate: u32, target_rate: u32, config: &ResamplerConfig) -> Result<Self> {
+        if source_rate == 0 {
+            return Err(AudioProcessingError::Resampler(
+                "Sample rate cannot be zero".to_string(),
+            ));
+        }
+        if target_rate == 0 {
+            return Err(AudioProcessingError::Resampler(
+                "Target sample rate cannot be zero".to_string(),
+            ));
+        }
+
+        let divisor = gcd(source_rate, target_rate);
+        let up = usize::try_from(target_rate / divisor)
+            .map_err(|e| AudioProcessingError::Resampler(e.to_string()))?;
+        let down = usize::try_from(source_rate / divisor)
+            .map_err(|e| AudioProcessingError::Resampler(e.to_string()))?;
+        let phases = up.min(MAX_PHASES);
+
+        let params = KaiserParameters::for_quality(config.quality);
+        let band_limit = if config.enable_anti_aliasing && up < down {
+            f64::from(target_rate) / f64::from(source_rate)
+        } else {
+            1.0
+        };
+        let cutoff = params.rolloff * band_limit;
+        let half_length = safe_usize_to_f64_audio(params.zero_crossings) / cutoff;
+        let half_taps = sample_count(half_length.ceil())?;
+        let taps = half_taps * 2;
+
+        let beta = params.beta();
+        let window_norm = bessel_i0(beta);
+        let mut coefficients = Vec::with_capacity(phases * taps);
+        let mut phase_taps = vec![0.0f64; taps];
+
+        for phase in 0..phases {
+            let fraction = safe_usize_to_f64_audio(phase) / safe_usize_to_f64_audio(phases);
+            for (tap, coefficient) in phase_taps.iter_mut().enumerate() {
+                // Distance from the output instant to this input sample
+                let t = fraction + safe_usize_to_f64_audio(half_taps - 1)
+                    - safe_usize_to_f64_audio(tap);
+                let x = t / half_length;
+                *coefficient = if x.abs() >= 1.0 {
+                    0.0
+                } else {
+                    let window = bessel_i0(beta * x.mul_add(-x, 1.0).sqrt()) / window_norm;
+                    cutoff * sinc(cutoff * t) * window
+                };
+            }
+
+            // Normalise each phase to unity DC gain
+            let sum: f64 = phase_taps.iter().sum();
+            #[allow(clippy::cast_possible_truncation)]
+            coefficients.extend(phase_taps.iter().map(|c| (c / sum) as f32));
+        }
+
+        Ok(Self {
+            source_rate,
+            target_rate,
+            up,
+            down,
+            phases,
+            half_taps,
+            coefficients,
+        })
+    }
+
+    const fn taps(&self) -> usize {
+        self.half_taps * 2
+    }
+
+    const fn output_frames(&self, input_frames: usize) -> usize {
+        input_frames * self.up / self.down
+    }
+
+    /// Index of the first padded input frame used by an output frame, and its phase
+    const fn position(&self, output_frame: usize) -> (usize, usize) {
+        let numerator = output_frame * self.down;
+        let remainder = numerator % self.up;
+        (numerator / self.up, remainder * self.phases / self.up)
+    }
+
+    fn phase(&self, phase: usize) -> &[f32] {
+        let taps = self.taps();
+        &self.coefficients[phase * taps..(phase + 1) * taps]
+    }
+}
+
+/// Per-stream history carried between streaming calls
+#[derive(Debug, Clone)]
+struct StreamState {
+    filter: Arc<PolyphaseFilter>,
+    channels: usize,
+    /// Interleaved input frames, prefixed with `half_taps - 1` zero frames
+    history: Vec<f32>,
+    /// Number of padded frames already dropped from the front of `history`
+    discarded_frames: usize,
+    /// Total real input frames received
+    input_frames: usize,
+    /// Next output frame to produce
+    next_output: usize,
+}
+
+impl StreamState {
+    fn new(filter: Arc<PolyphaseFilter>, channels: usize) -> Self {
+        let history = vec![0.0; (filter.half_taps - 1) * channels];
+        Self {
+            filter,
+            channels,
+            history,
+            discarded_frames: 0,
+            input_frames: 0,
+            next_output: 0,
+        }
+    }
+
+    fn push(&mut self, samples: &[f32]) {
+        self.history.extend_from_slice(samples);
+        self.input_frames += samples.len() / self.channels;
+    }
+
+    /// Produces every output frame whose filter support is fully buffered
+    fn drain_ready(&mut self, output: &mut Vec<f32>, limit: Option<usize>) {
+        let taps = self.filter.taps();
+        let channels = self.channels;
+        let available = self.discarded_frames + self.history.len() / channels;
+
+        loop {
+            if limit.is_some_and(|limit| self.next_output >= limit) {
+                break;
+            }
+            let (start, phase) = self.filter.position(self.next_output);
+            if start + taps > available {
+                break;
+            }
+
+            let coefficients = self.filter.phase(phase);
+            let base = (start - self.discarded_frames) * channels;
+            for channel in 0..channels {
+                let mut acc = 0.0f32;
+                for (tap, coefficient) in coefficients.iter().enumerate() {
+                    acc += self.history[base + tap * channels + channel] * coefficient;
+                }
+                output.push(acc);
+            }
+            self.next_output += 1;
+        }
+
+        // Drop frames no future output frame can reach
+        let (start, _) = self.filter.position(self.next_output);
+        let drop_frames = start
+            .saturating_sub(self.discarded_frames)
+            .min(self.history.len() / channels);
+        self.history.drain(..drop_frames * channels);
+        self.discarded_frames += drop_frames;
+    }
+
+    /// Pads the tail with silence and produces the remaining output frames
+    fn finish(&mut self, output: &mut Vec<f32>) {
+        let padding = vec![0.0; (self.filter.half_taps + 1) * self.channels];
+        self.history.extend_from_slice(&padding);
+        let total = self.filter.output_frames(self.input_frames);
+        self.drain_ready(output, Some(total));
+    }
+}
+
+/// Windowed-sinc polyphase resampler
+///
+/// Unlike [`LinearResampler`](super::LinearResampler), this resampler applies
+/// a proper anti-aliasing low-pass filter, so content above the output Nyquist
+/// frequency is rejected instead of folding back into the audible band. It is
+/// selected by the processing pipeline for [`ResampleQuality::High`].
+///
+/// The filter is zero-phase for whole-buffer processing. In streaming mode the
+/// output lags the input by [`LatencyReporting::get_latency_samples`] input
+/// frames; call [`SincResampler::finish_streaming`] at the end of a stream to
+/// emit the remaining frames.
+#[derive(Debug, Clone)]
+pub struct SincResampler {
+    config: ResamplerConfig,
+    filter: Option<Arc<PolyphaseFilter>>,
+    stream: Option<StreamState>,
+}
+
+impl SincResampler {
+    /// Creates a new sinc resampler with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the resampler configuration validation fails.
+    pub fn new(config: ResamplerConfig) -> Result<Self> {
+        ConfigValidator::validate_resampler_config(&config)?;
+        Ok(Self {
+            config,
+            filter: None,
+            stream: None,
+        })
+    }
+
+    /// Creates a new sinc resampler for a specific target sample rate and quality
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError`] if the target sample rate is invalid.
+    pub fn with_target_rate(target_rate: u32, quality: ResampleQuality) -> Result<Self> {
+        Self::new(ResamplerConfig {
+            target_sample_rate: Some(target_rate),
+            quality,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the Kaiser filter design used by the configured quality level
+    #[must_use]
+    pub const fn kaiser_parameters(&self) -> KaiserParameters {
+        KaiserParameters::for_quality(self.config.quality)
+    }
+
+    /// Returns the cached filter for a conversion, building it if needed
+    fn filter_for(&mut self, source_rate: u32, target_rate: u32) -> Result<Arc<PolyphaseFilter>> {
+        if let Some(filter) = &self.filter
+            && filter.source_rate == source_rate
+            && filter.target_rate == target_rate
+        {
+            return Ok(Arc::clone(filter));
+        }
+
+        let filter = Arc::new(PolyphaseFilter::new(
+            source_rate,
+            target_rate,
+            &self.config,
+        )?);
+        self.filter = Some(Arc::clone(&filter));
+        Ok(filter)
+    }
+
+    /// Emits the frames still buffered at the end of a stream and resets it
+    ///
+    /// # Errors
+    ///
+    /// This currently never fails but returns [`Result`] for symmetry with
+    /// [`StreamingProcessor::process_streaming`].
+    pub fn finish_streaming(&mut self, output: &mut AudioBuffer<f32>) -> Result<()> {
+        output.data.clear();
+        if let Some(mut stream) = self.stream.take() {
+            stream.finish(&mut output.data);
+            output.sample_rate = stream.filter.target_rate;
+            output.channels = u16::try_from(stream.channels)
+                .map_err(|e| AudioProcessingError::Resampler(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+impl AudioProcessor for SincResampler {
+    fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        let Some(target_rate) = self.config.target_sample_rate else {
+            return Ok(());
+        };
+        if buffer.sample_rate == target_rate {
+            return Ok(());
+        }
+
+        let filter = self.filter_for(buffer.sample_rate, target_rate)?;
+        let channels = usize::from(buffer.channels.max(1));
+        let mut stream = StreamState::new(Arc::clone(&filter), channels);
+        let mut resampled =
+            Vec::with_capacity(filter.output_frames(buffer.data.len() / channels) * channels);
+
+        stream.push(&buffer.data);
+        stream.drain_ready(&mut resampled, None);
+        stream.finish(&mut resampled);
+
+        log::debug!(
+            "Sinc resampled {} Hz to {} Hz ({} taps, {} phases)",
+            buffer.sample_rate,
+            target_rate,
+            filter.taps(),
+            filter.phases
+        );
+
+        buffer.data = resampled;
+        buffer.sample_rate = target_rate;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.stream = None;
+    }
+}
+
+impl StreamingProcessor for SincResampler {
+    fn process_streaming(
+        &mut self,
+        input: &AudioBuffer<f32>,
+        output: &mut AudioBuffer<f32>,
+    ) -> Result<()> {
+        output.format = input.format;
+        output.channels = input.channels;
+        output.data.clear();
+
+        let Some(target_rate) = self.config.target_sample_rate else {
+            output.sample_rate = input.sample_rate;
+            output.data.extend_from_slice(&input.data);
+            return Ok(());
+        };
+
+        let channels = usize::from(input.channels.max(1));
+        if let Some(stream) = &self.stream
+            && (stream.filter.source_rate != input.sample_rate || stream.channels != channels)
+        {
+            return Err(AudioProcessingError::Resampler(
+                "Stream format changed; finish or reset the stream first".to_string(),
+            ));
+        }
+        if self.stream.is_none() {
+            let filter = self.filter_for(input.sample_rate, target_rate)?;
+            self.stream = Some(StreamState::new(filter, channels));
+        }
+
+        if let Some(stream) = self.stream.as_mut() {
+            stream.push(&input.data);
+            stream.drain_ready(&mut output.data, None);
+        }
+        output.sample_rate = target_rate;
+        Ok(())
+    }
+
+    fn set_streaming_latency(&mut self, latency_samples: usize) -> Result<()> {
+        let required = self.get_latency_samples();
+        if latency_samples < required {
+            return Err(AudioProcessingError::Resampler(format!(
+                "Sinc resampler needs at least {required} samples of latency, got {latency_samples}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stream = None;
+        Ok(())
+    }
+}
+
+impl Configurable<ResamplerConfig> for SincResampler {
+    fn configure(&mut self, config: ResamplerConfig) -> Result<()> {
+        ConfigValidator::validate_resampler_config(&config)?;
+        self.config = config;
+        self.filter = None;
+        self.stream = None;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &ResamplerConfig {
+        &self.config
+    }
+}
+
+impl LatencyReporting for SincResampler {
+    fn get_latency_samples(&self) -> usize {
+        // Half the filter length, measured in input frames
+        self.stream
+            .as_ref()
+            .map(|stream| &stream.filter)
+            .or(self.filter.as_ref())
+            .map_or(self.kaiser_parameters().zero_crossings, |filter| {
+                filter.half_taps
+            })
+    }
+}
+
+impl Validatable for SincResampler {
+    fn validate(&self) -> Result<()> {
+        ConfigValidator::validate_resampler_config(&self.config).map_err(std::convert::Into::into)
+    }
+}
+
+impl Default for SincResampler {
+    /// Creates a new sinc resampler with default configuration
+    fn default() -> Self {
+        Self {
+            config: ResamplerConfig::default(),
+            filter: None,
+            stream: None,
+        }
+    }
+}
+
+const fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-12 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Zeroth-order modified Bessel function of the first kind
+fn bessel_i0(x: f64) -> f64 {
+    let half = x / 2.0;
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-12 {
+        term *= (half / k) * (half / k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+fn sample_count(value: f64) -> Result<usize> {
+    crate::utils::casting::domain::audio::safe_f64_to_usize_samples(value)
+        .map_err(|e| AudioProcessingError::Resampler(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::SampleFormat;
+    use crate::audio::processing::LinearResampler;
+
+    const SOURCE_RATE: u32 = 44100;
+    const TARGET_RATE: u32 = 22050;
+
+    fn tone(frequency: f64, sample_rate: u32, frames: usize) -> AudioBuffer<f32> {
+        let step = std::f64::consts::TAU * frequency / f64::from(sample_rate);
+        #[allow(clippy::cast_possible_truncation)]
+        let data = (0..frames)
+            .map(|i| (0.5 * (step * safe_usize_to_f64_audio(i)).sin()) as f32)
+            .collect();
+        AudioBuffer {
+            data,
+            format: SampleFormat::F32,
+            sample_rate,
+            channels: 1,
+        }
+    }
+
+    /// RMS of the buffer, skipping filter edge transients
+    fn rms(data: &[f32], skip: usize) -> f64 {
+        let body = &data[skip..data.len() - skip];
+        let power: f64 = body.iter().map(|s| f64::from(*s).powi(2)).sum();
+        (power / safe_usize_to_f64_audio(body.len())).sqrt()
+    }
+
+    /// Gain in dB for each tone of a stepped sine sweep between two frequencies
+    ///
+    /// Input and output RMS are measured over the same time span so partial
+    /// cycles at low frequencies do not show up as ripple.
+    fn sweep_gains_db<P: AudioProcessor>(
+        resampler: &mut P,
+        from_hz: f64,
+        to_hz: f64,
+        steps: usize,
+    ) -> Vec<(f64, f64)> {
+        let frames = 8192;
+        (0..steps)
+            .map(|step| {
+                let frequency = from_hz
+                    * (to_hz / from_hz)
+                        .powf(safe_usize_to_f64_audio(step) / safe_usize_to_f64_audio(steps - 1));
+                let mut buffer = tone(frequency, SOURCE_RATE, frames);
+                resampler.process(&mut buffer).unwrap();
+                let input_skip = 256 * (SOURCE_RATE / buffer.sample_rate) as usize;
+                let input_rms = rms(&tone(frequency, SOURCE_RATE, frames).data, input_skip);
+                let output_rms = rms(&buffer.data, 256);
+                (frequency, 20.0 * (output_rms / input_rms).log10())
+            })
+            .collect()
+    }
+
+    fn high_quality() -> SincResampler {
+        SincResampler::with_target_rate(TARGET_RATE, ResampleQuality::High).unwrap()
+    }
+
+    #[test]
+    fn test_passband_ripple() {
+        let mut resampler = high_quality();
+        let gains = sweep_gains_db(&mut resampler, 50.0, 8000.0, 24);
+
+        let max = gains.iter().map(|(_, g)| *g).fold(f64::MIN, f64::max);
+        let min = gains.iter().map(|(_, g)| *g).fold(f64::MAX, f64::min);
+        assert!(
+            max - min < 0.1,
+            "passband ripple {:.4} dB exceeds 0.1 dB: {gains:?}",
+            max - min
+        );
+        assert!(
+            max.abs() < 0.1 && min.abs() < 0.1,
+            "passband gain drifted: {gains:?}"
+        );
+    }
+
+    #[test]
+    fn test_stopband_rejection() {
+        let mut resampler = high_quality();
+        let gains = sweep_gains_db(&mut resampler, 11200.0, 21000.0, 16);
+
+        for (frequency, gain) in gains {
+            assert!(
+                gain < -80.0,
+                "{frequency:.0} Hz only attenuated by {:.1} dB",
+                -gain
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_aliasing_better_than_linear() {
+        let mut sinc = high_quality();
+        let mut linear = LinearResampler::with_target_rate(TARGET_RATE).unwrap();
+
+        let sinc_gain = sweep_gains_db(&mut sinc, 15000.0, 15000.0 * 1.0001, 2)[0].1;
+        let linear_gain = sweep_gains_db(&mut linear, 15000.0, 15000.0 * 1.0001, 2)[0].1;
+        assert!(sinc_gain < linear_gain - 40.0);
+    }
+
+    #[test]
+    fn test_quality_levels_scale_filter_length() {
+        let low = KaiserParameters::for_quality(ResampleQuality::Low);
+        let medium = KaiserParameters::for_quality(ResampleQuality::Medium);
+        let high = KaiserParameters::for_quality(ResampleQuality::High);
+
+        assert!(low.zero_crossings < medium.zero_crossings);
+        assert!(medium.zero_crossings < high.zero_crossings);
+        assert!(low.beta() < medium.beta() && medium.beta() < high.beta());
+    }
+
+    #[test]
+    fn test_output_length_and_upsampling() {
+        let mut resampler =
+            SincResampler::with_target_rate(48000, ResampleQuality::Medium).unwrap();
+        let mut buffer = tone(1000.0, SOURCE_RATE, 4410);
+        buffer.channels = 2;
+        buffer.data = buffer.data.iter().flat_map(|s| [*s, -*s]).collect();
+
+        resampler.process(&mut buffer).unwrap();
+        assert_eq!(buffer.sample_rate, 48000);
+        assert_eq!(buffer.data.len(), 4800 * 2);
+        // Channels stay independent
+        assert!(
+            buffer
+                .data
+                .chunks(2)
+                .all(|frame| (frame[0] + frame[1]).abs() < 1e-6)
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_whole_buffer() {
+        let input = tone(3000.0, SOURCE_RATE, 5000);
+        let mut whole = input.clone();
+        high_quality().process(&mut whole).unwrap();
+
+        let mut resampler = high_quality();
+        let mut streamed = Vec::new();
+        let mut output = AudioBuffer {
+            data: Vec::new(),
+            format: SampleFormat::F32,
+            sample_rate: 0,
+            channels: 0,
+        };
+        for chunk in input.data.chunks(777) {
+            let block = AudioBuffer {
+                data: chunk.to_vec(),
+                ..input.clone()
+            };
+            resampler.process_streaming(&block, &mut output).unwrap();
+            streamed.extend_from_slice(&output.data);
+        }
+        resampler.finish_streaming(&mut output).unwrap();
+        streamed.extend_from_slice(&output.data);
+
+        assert_eq!(output.sample_rate, TARGET_RATE);
+        assert_eq!(streamed.len(), whole.data.len());
+        for (a, b) in streamed.iter().zip(&whole.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_latency_reporting() {
+        let mut resampler = high_quality();
+        let latency = resampler.get_latency_samples();
+        assert!(latency >= resampler.kaiser_parameters().zero_crossings);
+
+        let mut buffer = tone(440.0, SOURCE_RATE, 1024);
+        resampler.process(&mut buffer).unwrap();
+        // Downsampling widens the filter to the output band
+        assert!(resampler.get_latency_samples() > latency);
+
+        assert!(resampler.set_streaming_latency(0).is_err());
+        assert!(
+            resampler
+                .set_streaming_latency(resampler.get_latency_samples())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_same_rate_and_invalid_rate() {
+        let mut resampler =
+            SincResampler::with_target_rate(SOURCE_RATE, ResampleQuality::High).unwrap();
+        let mut buffer = tone(440.0, SOURCE_RATE, 100);
+        let original = buffer.data.clone();
+        resampler.process(&mut buffer).unwrap();
+        assert_eq!(buffer.data, original);
+
+        buffer.sample_rate = 0;
+        assert!(resampler.process(&mut buffer).is_err());
+    }
+}
@@ -0,0 +1,813 @@
+//! Splitting long recordings into per-chapter files
+//!
+//! A [`ChapterSplitter`] finds chapter boundaries in a source file from its
+//! embedded cues, a cue sheet or gaps of silence, then streams the decoded
+//! audio into one output file per chapter. Each part is written through
+//! [`AudioFileWriter`] with the source tags, the chapter title and its track
+//! number, so the source never has to fit in memory.
+
+use super::error::{AudioProcessingError, Result};
+use super::file_io::{AudioFileWriter, OutputTags};
+use super::traits::Validatable;
+use super::utils::gain::power_to_db;
+use crate::audio::{AudioDecoder, AudioMetadata};
+use crate::utils::path::sanitize_file_name;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default filename template for split parts
+pub const DEFAULT_SPLIT_TEMPLATE: &str = "{track:02} - {chapter}";
+
+/// Cue sheet frames per second (CD sectors)
+const CUE_FRAMES_PER_SECOND: f64 = 75.0;
+
+/// Length of the analysis blocks used for silence detection
+const SILENCE_BLOCK_SECS: f64 = 0.05;
+
+/// Where chapter boundaries come from
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChapterSource {
+    /// Embedded chapters, then a cue sheet next to the file, then silence
+    #[default]
+    Auto,
+    /// Chapters embedded in the container
+    Embedded,
+    /// A cue sheet file
+    CueSheet(PathBuf),
+    /// Gaps of silence between chapters
+    Silence,
+}
+
+/// Which source the chapters of a split were taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChapterOrigin {
+    /// Chapters embedded in the container
+    Embedded,
+    /// A cue sheet file
+    CueSheet,
+    /// Detected gaps of silence
+    Silence,
+}
+
+/// Settings for finding chapters from silence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilenceSplitConfig {
+    /// Level below which audio counts as silence, in dBFS
+    pub threshold_db: f32,
+    /// Shortest gap that can separate two chapters
+    #[serde(with = "humantime_serde")]
+    pub min_silence: Duration,
+    /// Shortest chapter; gaps closer than this to the previous cut are ignored
+    #[serde(with = "humantime_serde")]
+    pub min_chapter: Duration,
+}
+
+impl Default for SilenceSplitConfig {
+    fn default() -> Self {
+        Self {
+            threshold_db: -50.0,
+            min_silence: Duration::from_secs(2),
+            min_chapter: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Configuration of a chapter split
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitConfig {
+    /// Where chapter boundaries come from
+    pub source: ChapterSource,
+    /// Output filename template without extension
+    ///
+    /// `{track}` is the track number (`{track:02}` zero-pads it), `{chapter}`
+    /// the chapter title and `{title}` the title of the book.
+    pub filename_template: String,
+    /// Output directory; defaults to a folder named after the source file
+    pub output_directory: Option<PathBuf>,
+    /// Whether existing output files may be replaced
+    pub overwrite: bool,
+    /// Silence detection settings
+    pub silence: SilenceSplitConfig,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            source: ChapterSource::Auto,
+            filename_template: DEFAULT_SPLIT_TEMPLATE.to_string(),
+            output_directory: None,
+            overwrite: false,
+            silence: SilenceSplitConfig::default(),
+        }
+    }
+}
+
+impl Validatable for SplitConfig {
+    fn validate(&self) -> Result<()> {
+        use super::config::validation;
+
+        validation::non_empty_string(&self.filename_template, "Filename template")?;
+        if !["{track", "{chapter}"]
+            .iter()
+            .any(|placeholder| self.filename_template.contains(placeholder))
+        {
+            return Err(AudioProcessingError::config(
+                "Filename template must contain {track} or {chapter}",
+            ));
+        }
+        validation::range(
+            &self.silence.threshold_db,
+            &-96.0,
+            &-20.0,
+            "Silence threshold",
+        )?;
+        validation::range(
+            &self.silence.min_silence.as_secs_f32(),
+            &0.1,
+            &60.0,
+            "Minimum silence",
+        )?;
+        Ok(())
+    }
+}
+
+impl SplitConfig {
+    /// Create a new builder for `SplitConfig`
+    #[must_use]
+    pub fn builder() -> SplitConfigBuilder {
+        SplitConfigBuilder::default()
+    }
+}
+
+/// Builder for `SplitConfig`
+#[derive(Debug, Default)]
+pub struct SplitConfigBuilder {
+    config: SplitConfig,
+}
+
+impl SplitConfigBuilder {
+    /// Set where chapter boundaries come from
+    #[must_use]
+    pub fn with_source(mut self, source: ChapterSource) -> Self {
+        self.config.source = source;
+        self
+    }
+
+    /// Set the output filename template
+    #[must_use]
+    pub fn with_filename_template(mut self, template: impl Into<String>) -> Self {
+        self.config.filename_template = template.into();
+        self
+    }
+
+    /// Set the output directory
+    #[must_use]
+    pub fn with_output_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.config.output_directory = Some(directory.into());
+        self
+    }
+
+    /// Allow replacing existing output files
+    #[must_use]
+    pub const fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.config.overwrite = overwrite;
+        self
+    }
+
+    /// Set the silence detection settings
+    #[must_use]
+    pub const fn with_silence(mut self, silence: SilenceSplitConfig) -> Self {
+        self.config.silence = silence;
+        self
+    }
+
+    /// Build the `SplitConfig`
+    #[must_use]
+    pub fn build(self) -> SplitConfig {
+        self.config
+    }
+
+    /// Build and validate the `SplitConfig`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn build_validated(self) -> Result<SplitConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Start of a chapter in the source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterMark {
+    /// Start time in seconds
+    pub start_secs: f64,
+    /// Chapter title
+    pub title: String,
+}
+
+/// One written chapter file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitPart {
+    /// Track number, starting at 1
+    pub track: u32,
+    /// Chapter title
+    pub title: String,
+    /// Start time in the source, in seconds
+    pub start_secs: f64,
+    /// End time in the source, in seconds
+    pub end_secs: f64,
+    /// Path of the written file
+    pub output_path: PathBuf,
+}
+
+/// Result of splitting one source file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitReport {
+    /// The source file
+    pub input_path: PathBuf,
+    /// Where the chapter boundaries came from
+    pub origin: ChapterOrigin,
+    /// The written parts in order
+    pub parts: Vec<SplitPart>,
+}
+
+/// Cuts a source file into per-chapter files
+#[derive(Debug, Clone, Default)]
+pub struct ChapterSplitter {
+    config: SplitConfig,
+}
+
+impl ChapterSplitter {
+    /// Creates a splitter with the given configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AudioProcessingError::Configuration`] if the configuration is invalid.
+    pub fn new(config: SplitConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// The splitter configuration
+    #[must_use]
+    pub const fn config(&self) -> &SplitConfig {
+        &self.config
+    }
+
+    /// Finds the chapters of a source file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be decoded, the cue sheet cannot
+    /// be read, or the chosen source yields no chapters.
+    pub fn find_chapters(&self, input: &Path) -> Result<(ChapterOrigin, Vec<ChapterMark>)> {
+        match &self.config.source {
+            ChapterSource::Embedded => {
+                let chapters = embedded_chapters(input)?;
+                require_chapters(ChapterOrigin::Embedded, chapters, input)
+            }
+            ChapterSource::CueSheet(path) => {
+                require_chapters(ChapterOrigin::CueSheet, read_cue_sheet(path)?, input)
+            }
+            ChapterSource::Silence => Ok((
+                ChapterOrigin::Silence,
+                silence_chapters(input, &self.config.silence)?,
+            )),
+            ChapterSource::Auto => {
+                let embedded = embedded_chapters(input)?;
+                if embedded.len() > 1 {
+                    return Ok((ChapterOrigin::Embedded, embedded));
+                }
+                let cue_path = input.with_extension("cue");
+                if cue_path.is_file() {
+                    let chapters = read_cue_sheet(&cue_path)?;
+                    if !chapters.is_empty() {
+                        return Ok((ChapterOrigin::CueSheet, chapters));
+                    }
+                }
+                Ok((
+                    ChapterOrigin::Silence,
+                    silence_chapters(input, &self.config.silence)?,
+                ))
+            }
+        }
+    }
+
+    /// Splits a source file into one output file per chapter
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no chapters are found, an output file already
+    /// exists and overwriting is disabled, or decoding or writing fails.
+    pub fn split(&self, input: &Path) -> Result<SplitReport> {
+        let (origin, chapters) = self.find_chapters(input)?;
+        let source_tags = AudioMetadata::from_file(input)
+            .map(|metadata| OutputTags::from_metadata(&metadata))
+            .unwrap_or_default();
+        let book_title = source_tags
+            .album
+            .clone()
+            .or_else(|| source_tags.title.clone())
+            .unwrap_or_else(|| file_stem(input));
+
+        let output_dir = self.config.output_directory.clone().unwrap_or_else(|| {
+            input
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(file_stem(input))
+        });
+        std::fs::create_dir_all(&output_dir)?;
+
+        let total = u32::try_from(chapters.len())
+            .map_err(|_| AudioProcessingError::InvalidInput("Too many chapters".to_string()))?;
+        let mut names = HashSet::new();
+        let mut parts = Vec::with_capacity(chapters.len());
+        for (track, chapter) in (1..=total).zip(&chapters) {
+            let name = render_template(
+                &self.config.filename_template,
+                track,
+                &chapter.title,
+                &book_title,
+            );
+            if !names.insert(name.clone()) {
+                return Err(AudioProcessingError::config(format!(
+                    "Filename template gives more than one part the name '{name}'"
+                )));
+            }
+            let output_path = output_dir.join(format!("{name}.wav"));
+            if output_path.exists() && !self.config.overwrite {
+                return Err(AudioProcessingError::FileIo(format!(
+                    "Output file already exists: {}",
+                    output_path.display()
+                )));
+            }
+            parts.push(SplitPart {
+                track,
+                title: chapter.title.clone(),
+                start_secs: chapter.start_secs,
+                end_secs: chapter.start_secs,
+                output_path,
+            });
+        }
+
+        let source_tags = OutputTags {
+            album: Some(book_title),
+            track_total: Some(total),
+            ..source_tags
+        };
+        write_parts(input, &mut parts, &source_tags)?;
+
+        log::info!(
+            "Split {} into {} parts from {:?} chapters",
+            input.display(),
+            parts.len(),
+            origin
+        );
+        Ok(SplitReport {
+            input_path: input.to_path_buf(),
+            origin,
+            parts,
+        })
+    }
+}
+
+/// Streams the source into the part files, filling in each part's end time
+fn write_parts(input: &Path, parts: &mut [SplitPart], source_tags: &OutputTags) -> Result<()> {
+    let mut decoder = AudioDecoder::open(input)?;
+    let sample_rate = decoder.sample_rate();
+    let channels = decoder.channels();
+    let frame_len = usize::from(channels.max(1));
+    let boundaries: Vec<u64> = parts
+        .iter()
+        .skip(1)
+        .map(|part| seconds_to_frames(part.start_secs, sample_rate))
+        .collect();
+
+    let open_part = |part: &SplitPart| {
+        let tags = OutputTags {
+            title: Some(part.title.clone()),
+            track: Some(part.track),
+            ..source_tags.clone()
+        };
+        AudioFileWriter::create(&part.output_path, sample_rate, channels, &tags)
+    };
+
+    let mut current = 0;
+    let mut writer = open_part(&parts[0])?;
+    let mut position: u64 = 0;
+    while let Some(buffer) = decoder.next_packet()? {
+        let mut samples = buffer.data.as_slice();
+        while !samples.is_empty() {
+            let frames = (samples.len() / frame_len) as u64;
+            let take = boundaries.get(current).map_or(frames, |boundary| {
+                boundary.saturating_sub(position).min(frames)
+            });
+            if take == 0 && current < boundaries.len() {
+                writer.finalize()?;
+                parts[current].end_secs = frames_to_seconds(position, sample_rate);
+                current += 1;
+                writer = open_part(&parts[current])?;
+                continue;
+            }
+            let (head, tail) =
+                samples.split_at(usize::try_from(take).unwrap_or(usize::MAX) * frame_len);
+            writer.write_samples(head)?;
+            position += take;
+            samples = tail;
+            if take == frames {
+                break;
+            }
+        }
+    }
+    writer.finalize()?;
+    parts[current].end_secs = frames_to_seconds(position, sample_rate);
+
+    if current + 1 < parts.len() {
+        // Chapters past the end of the audio would be empty files
+        for part in &parts[current + 1..] {
+            let _ = std::fs::remove_file(&part.output_path);
+        }
+        return Err(AudioProcessingError::InvalidInput(format!(
+            "Chapter '{}' starts after the end of the audio",
+            parts[current + 1].title
+        )));
+    }
+    Ok(())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn seconds_to_frames(seconds: f64, sample_rate: u32) -> u64 {
+    (seconds.max(0.0) * f64::from(sample_rate)).round() as u64
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn frames_to_seconds(frames: u64, sample_rate: u32) -> f64 {
+    frames as f64 / f64::from(sample_rate.max(1))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map_or_else(
+        || "audiobook".to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    )
+}
+
+fn require_chapters(
+    origin: ChapterOrigin,
+    chapters: Vec<ChapterMark>,
+    input: &Path,
+) -> Result<(ChapterOrigin, Vec<ChapterMark>)> {
+    if chapters.is_empty() {
+        return Err(AudioProcessingError::InvalidInput(format!(
+            "No {origin:?} chapters found for {}",
+            input.display()
+        )));
+    }
+    Ok((origin, chapters))
+}
+
+/// Sorts chapters, drops duplicate starts and makes the first start at zero
+fn normalize_chapters(mut chapters: Vec<ChapterMark>) -> Vec<ChapterMark> {
+    chapters.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+    chapters.dedup_by(|later, earlier| (later.start_secs - earlier.start_secs).abs() < 0.001);
+    if let Some(first) = chapters.first_mut() {
+        first.start_secs = 0.0;
+    }
+    chapters
+}
+
+/// Chapters from the cues embedded in the container
+fn embedded_chapters(input: &Path) -> Result<Vec<ChapterMark>> {
+    let decoder = AudioDecoder::open(input)?;
+    let chapters = decoder
+        .embedded_cues()
+        .into_iter()
+        .enumerate()
+        .map(|(index, cue)| ChapterMark {
+            start_secs: cue.start_secs,
+            title: cue
+                .title
+                .unwrap_or_else(|| format!("Chapter {}", index + 1)),
+        })
+        .collect();
+    Ok(normalize_chapters(chapters))
+}
+
+fn read_cue_sheet(path: &Path) -> Result<Vec<ChapterMark>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        AudioProcessingError::FileIo(format!("Cannot read cue sheet {}: {e}", path.display()))
+    })?;
+    parse_cue_sheet(&contents)
+}
+
+/// Parses the tracks of a cue sheet into chapter marks
+///
+/// Each `TRACK` starts at its `INDEX 01` and is named by its `TITLE`.
+///
+/// # Errors
+///
+/// Returns [`AudioProcessingError::InvalidInput`] if an index time is malformed.
+pub fn parse_cue_sheet(contents: &str) -> Result<Vec<ChapterMark>> {
+    let mut chapters = Vec::new();
+    let mut in_track = false;
+    for line in contents.lines().map(str::trim) {
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match command.to_ascii_uppercase().as_str() {
+            "TRACK" => {
+                in_track = true;
+                chapters.push(ChapterMark {
+                    start_secs: f64::NAN,
+                    title: format!("Chapter {}", chapters.len() + 1),
+                });
+            }
+            "TITLE" if in_track => {
+                if let Some(chapter) = chapters.last_mut() {
+                    chapter.title = rest.trim_matches('"').to_string();
+                }
+            }
+            "INDEX" if in_track => {
+                let (number, time) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                if number == "01"
+                    && let Some(chapter) = chapters.last_mut()
+                {
+                    chapter.start_secs = parse_cue_time(time.trim())?;
+                }
+            }
+            _ => {}
+        }
+    }
+    chapters.retain(|chapter| chapter.start_secs.is_finite());
+    Ok(normalize_chapters(chapters))
+}
+
+/// Parses an `mm:ss:ff` cue sheet time into seconds
+fn parse_cue_time(time: &str) -> Result<f64> {
+    let invalid = || AudioProcessingError::InvalidInput(format!("Invalid cue sheet time '{time}'"));
+    let fields: Vec<u32> = time
+        .split(':')
+        .map(|field| field.parse().map_err(|_| invalid()))
+        .collect::<Result<_>>()?;
+    let [minutes, seconds, frames] = fields[..] else {
+        return Err(invalid());
+    };
+    Ok(f64::from(minutes) * 60.0 + f64::from(seconds) + f64::from(frames) / CUE_FRAMES_PER_SECOND)
+}
+
+/// Chapters separated by gaps of silence, cut in the middle of each gap
+fn silence_chapters(input: &Path, config: &SilenceSplitConfig) -> Result<Vec<ChapterMark>> {
+    let mut decoder = AudioDecoder::open(input)?;
+    let sample_rate = decoder.sample_rate();
+    let frame_len = usize::from(decoder.channels().max(1));
+    let block_frames = seconds_to_frames(SILENCE_BLOCK_SECS, sample_rate).max(1);
+    let min_silence = seconds_to_frames(config.min_silence.as_secs_f64(), sample_rate);
+    let min_chapter = seconds_to_frames(config.min_chapter.as_secs_f64(), sample_rate);
+
+    let mut cuts = Vec::new();
+    let mut last_cut = 0u64;
+    let mut position = 0u64;
+    let mut silence_start: Option<u64> = None;
+    let mut block_energy = 0.0f64;
+    let mut block_samples = 0u64;
+
+    let mut close_gap = |start: u64, end: u64, last_cut: &mut u64| {
+        let cut = start + (end - start) / 2;
+        if end - start >= min_silence && cut >= *last_cut + min_chapter {
+            cuts.push(cut);
+            *last_cut = cut;
+        }
+    };
+
+    while let Some(buffer) = decoder.next_packet()? {
+        for frame in buffer.data.chunks_exact(frame_len) {
+            block_energy += frame.iter().map(|s| f64::from(*s).powi(2)).sum::<f64>();
+            block_samples += frame.len() as u64;
+            position += 1;
+            if !position.is_multiple_of(block_frames) {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let level = power_to_db(block_energy / block_samples as f64);
+            let block_start = position - block_frames;
+            if level < config.threshold_db {
+                silence_start.get_or_insert(block_start);
+            } else if let Some(start) = silence_start.take() {
+                close_gap(start, block_start, &mut last_cut);
+            }
+            block_energy = 0.0;
+            block_samples = 0;
+        }
+    }
+    // Trailing silence is the end of the last chapter, not a boundary
+
+    Ok(std::iter::once(0)
+        .chain(cuts)
+        .enumerate()
+        .map(|(index, frame)| ChapterMark {
+            start_secs: frames_to_seconds(frame, sample_rate),
+            title: format!("Chapter {}", index + 1),
+        })
+        .collect())
+}
+
+/// Renders the filename template for one part
+///
+/// The result is safe to use as a file name on every platform.
+#[must_use]
+pub fn render_template(template: &str, track: u32, chapter: &str, title: &str) -> String {
+    let mut rendered = String::with_capacity(template.len() + chapter.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        let placeholder = &rest[open + 1..open + close];
+        let (name, format) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+        match name {
+            "track" => {
+                let width = format.trim_start_matches('0').parse().unwrap_or(0);
+                rendered.push_str(&format!("{track:0width$}"));
+            }
+            "chapter" => rendered.push_str(chapter),
+            "title" => rendered.push_str(title),
+            _ => rendered.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    rendered.push_str(rest);
+
+    sanitize_file_name(&rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::audio::create_sine_buffer;
+
+    #[test]
+    fn test_render_template() {
+        assert_eq!(
+            render_template(DEFAULT_SPLIT_TEMPLATE, 3, "The Storm", "Book"),
+            "03 - The Storm"
+        );
+        assert_eq!(
+            render_template("{title} {track} {chapter}", 12, "A/B: C?", "Book"),
+            "Book 12 A_B_ C_"
+        );
+        assert_eq!(
+            render_template("{track:03}{unknown}", 7, "", ""),
+            "007{unknown}"
+        );
+    }
+
+    #[test]
+    fn test_parse_cue_sheet() {
+        let cue = r#"
+PERFORMER "Jane Author"
+TITLE "The Book"
+FILE "book.mp3" MP3
+  TRACK 01 AUDIO
+    TITLE "Opening"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "The Middle"
+    INDEX 00 09:58:00
+    INDEX 01 10:00:37
+  TRACK 03 AUDIO
+    INDEX 01 75:30:00
+"#;
+        let chapters = parse_cue_sheet(cue).unwrap();
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[0].title, "Opening");
+        assert!((chapters[1].start_secs - 600.493_333).abs() < 1e-3);
+        assert_eq!(chapters[1].title, "The Middle");
+        assert!((chapters[2].start_secs - 4530.0).abs() < 1e-9);
+        assert_eq!(chapters[2].title, "Chapter 3");
+
+        assert!(parse_cue_sheet("TRACK 01 AUDIO\nINDEX 01 1:2").is_err());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(SplitConfig::default().validate().is_ok());
+        assert!(
+            SplitConfig::builder()
+                .with_filename_template("part")
+                .build_validated()
+                .is_err()
+        );
+    }
+
+    /// Writes tone, gap, tone, gap, tone as a tagged WAV file
+    fn write_source(path: &Path) {
+        let tone = create_sine_buffer(8000, 1, 440.0, 1.5, 0.5);
+        let gap = vec![0.0; 8000];
+        let tags = OutputTags {
+            title: Some("The Book".to_string()),
+            artist: Some("Jane Author".to_string()),
+            ..Default::default()
+        };
+        let mut writer = AudioFileWriter::create(path, 8000, 1, &tags).unwrap();
+        writer.write_samples(&tone.data).unwrap();
+        writer.write_samples(&gap).unwrap();
+        writer.write_samples(&tone.data).unwrap();
+        writer.write_samples(&gap).unwrap();
+        writer.write_samples(&tone.data).unwrap();
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_split_on_silence_writes_tagged_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("book.wav");
+        write_source(&source);
+
+        let config = SplitConfig::builder()
+            .with_source(ChapterSource::Silence)
+            .with_silence(SilenceSplitConfig {
+                threshold_db: -50.0,
+                min_silence: Duration::from_millis(500),
+                min_chapter: Duration::from_secs(1),
+            })
+            .build_validated()
+            .unwrap();
+        let report = ChapterSplitter::new(config)
+            .unwrap()
+            .split(&source)
+            .unwrap();
+
+        assert_eq!(report.origin, ChapterOrigin::Silence);
+        assert_eq!(report.parts.len(), 3);
+        assert!((report.parts[1].start_secs - 2.0).abs() < 0.1);
+        assert!((report.parts[2].end_secs - 6.5).abs() < 0.01);
+
+        let second = &report.parts[1];
+        assert_eq!(
+            second.output_path,
+            dir.path().join("book/02 - Chapter 2.wav")
+        );
+        let metadata = AudioMetadata::from_file(&second.output_path).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Chapter 2"));
+        assert_eq!(metadata.artist.as_deref(), Some("Jane Author"));
+        assert_eq!(metadata.album.as_deref(), Some("The Book"));
+        assert_eq!(metadata.track, Some(2));
+
+        // Parts cover the whole source without gaps or overlap
+        let frames: u64 = report
+            .parts
+            .iter()
+            .map(|part| {
+                AudioDecoder::open(&part.output_path)
+                    .unwrap()
+                    .total_frames()
+                    .unwrap()
+            })
+            .sum();
+        assert_eq!(frames, 52000);
+
+        // A second run refuses to replace the parts
+        let splitter = ChapterSplitter::new(SplitConfig {
+            source: ChapterSource::Silence,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(splitter.split(&source).is_err());
+    }
+
+    #[test]
+    fn test_split_with_cue_sheet_next_to_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("book.wav");
+        write_source(&source);
+        std::fs::write(
+            dir.path().join("book.cue"),
+            "TRACK 01 AUDIO\n TITLE \"Intro\"\n INDEX 01 00:00:00\n\
+             TRACK 02 AUDIO\n TITLE \"Rest\"\n INDEX 01 00:03:00\n",
+        )
+        .unwrap();
+
+        let config = SplitConfig::builder()
+            .with_output_directory(dir.path().join("parts"))
+            .build();
+        let report = ChapterSplitter::new(config)
+            .unwrap()
+            .split(&source)
+            .unwrap();
+        assert_eq!(report.origin, ChapterOrigin::CueSheet);
+        let names: Vec<_> = report
+            .parts
+            .iter()
+            .map(|part| part.output_path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["01 - Intro.wav", "02 - Rest.wav"]);
+        assert!((report.parts[0].end_secs - 3.0).abs() < 1e-9);
+    }
+}
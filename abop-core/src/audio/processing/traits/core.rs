@@ -22,6 +22,19 @@ pub trait AudioProcessor {
     /// or [`AudioProcessingError::InvalidBuffer`] if the buffer is invalid.
     fn process(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()>;
 
+    /// Process a buffer that holds a complete signal
+    ///
+    /// Processors that delay their output flush what they still hold and
+    /// drop the leading delay, so the result lines up with the input. The
+    /// default forwards to [`process`](Self::process).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if processing the buffer fails.
+    fn process_complete(&mut self, buffer: &mut AudioBuffer<f32>) -> Result<()> {
+        self.process(buffer)
+    }
+
     /// Reset any internal state (useful for streaming processing)
     fn reset(&mut self);
 }
@@ -18,6 +18,8 @@ pub mod specialized;
 // Re-export all traits for backward compatibility and convenience
 pub use core::{AudioProcessor, Bypassable, Configurable, ThreadSafe, Validatable};
 pub use lifecycle::{ProcessorLifecycle, Serializable};
-pub use reporting::{LatencyReporting, ProcessorInfo, ProgressReporting, ResourceEstimation};
+pub use reporting::{
+    LatencyReporting, MeasurementReporting, ProcessorInfo, ProgressReporting, ResourceEstimation,
+};
 pub use specialized::{FileWriter, StreamingProcessor};
 
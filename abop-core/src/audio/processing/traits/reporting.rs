@@ -3,6 +3,7 @@
 //! This module provides traits for processors that can report on their
 //! performance, resource usage, and operational status.
 
+use crate::audio::processing::report::{LevelMeasurement, StageMeasurements};
 use crate::utils::casting::domain::audio::safe_usize_to_f64_audio;
 use std::time::Duration;
 
@@ -19,6 +20,22 @@ pub trait LatencyReporting {
     }
 }
 
+/// Trait for processors that can describe what their last run changed
+///
+/// The pipeline measures the buffer before and after each stage and passes
+/// both measurements in. The default derives everything from the levels;
+/// processors that know more, such as the gain they applied, override it.
+pub trait MeasurementReporting {
+    /// Describes the effect of the most recent call to `process`
+    fn stage_measurements(
+        &self,
+        before: &LevelMeasurement,
+        after: &LevelMeasurement,
+    ) -> StageMeasurements {
+        StageMeasurements::from_levels(before, after)
+    }
+}
+
 /// Progress reporting trait for long-running operations
 pub trait ProgressReporting {
     /// Progress information type
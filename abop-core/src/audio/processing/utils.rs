@@ -261,6 +261,55 @@ pub mod memory {
     }
 }
 
+/// Gain and envelope utilities shared by dynamics and filter processors
+pub mod gain {
+    use crate::utils::casting::domain::audio::safe_f64_to_usize_samples;
+
+    /// Smallest level considered when converting to decibels
+    const MIN_LEVEL: f32 = 1e-9;
+
+    /// Converts a level in dB to a linear gain
+    #[must_use]
+    pub fn db_to_linear(db: f32) -> f32 {
+        10.0f32.powf(db / 20.0)
+    }
+
+    /// Converts a linear level to dB, flooring silence at -180 dB
+    #[must_use]
+    pub fn linear_to_db(level: f32) -> f32 {
+        20.0 * level.abs().max(MIN_LEVEL).log10()
+    }
+
+    /// Converts a mean square power to dB, flooring silence like [`linear_to_db`]
+    #[must_use]
+    pub fn power_to_db(power: f64) -> f32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let level = power.sqrt() as f32;
+        linear_to_db(level)
+    }
+
+    /// One-pole smoothing coefficient for a time constant in milliseconds
+    ///
+    /// A zero time constant yields an instantaneous response.
+    #[must_use]
+    pub fn smoothing_coefficient(time_ms: f32, sample_rate: u32) -> f32 {
+        if time_ms <= 0.0 || sample_rate == 0 {
+            return 0.0;
+        }
+        let samples = f64::from(time_ms) * 0.001 * f64::from(sample_rate);
+        #[allow(clippy::cast_possible_truncation)]
+        let coefficient = (-1.0 / samples).exp() as f32;
+        coefficient
+    }
+
+    /// Number of frames covered by a duration in milliseconds, rounded to nearest
+    #[must_use]
+    pub fn ms_to_frames(time_ms: f32, sample_rate: u32) -> usize {
+        let frames = f64::from(time_ms) * f64::from(sample_rate) / 1000.0;
+        safe_f64_to_usize_samples(frames.round()).unwrap_or(0)
+    }
+}
+
 /// Performance timing utilities
 pub mod timing {
     // Re-export the unified Timer from utils
@@ -207,6 +207,16 @@ impl ConfigValidator {
     ///
     /// Returns [`AppError::Audio`] if processor configurations are incompatible with each other.
     pub fn validate_config_compatibility(config: &ProcessingConfig) -> AppResult<()> {
+        // Component configurations are ignored when explicit stages are listed
+        let components = config.component_names();
+        if !config.stages.is_empty() && !components.is_empty() {
+            return Err(AppError::Audio(format!(
+                "Processing stages cannot be combined with component configurations ({}); \
+                 add them as stages instead",
+                components.join(", ")
+            )));
+        }
+
         // Check resampler and normalizer compatibility
         if let (Some(resampler), Some(_normalizer)) = (&config.resampler, &config.normalizer)
             && let Some(target_rate) = resampler.target_sample_rate
@@ -339,6 +349,33 @@ mod tests {
         assert!(ConfigValidator::validate_processing_config(&config).is_err());
     }
 
+    #[test]
+    fn test_stages_cannot_be_combined_with_components() {
+        use super::super::{AudioProcessingPipeline, ProcessorRegistry, StageConfig};
+
+        let config = ProcessingConfig::builder()
+            .with_stage(StageConfig::new(ProcessorRegistry::NORMALIZER))
+            .with_channel_mixer(ChannelMixerConfig {
+                target_channels: Some(1),
+                ..Default::default()
+            })
+            .with_silence_detector(SilenceDetectorConfig::default())
+            .build();
+
+        let error = ConfigValidator::validate_processing_config(&config).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("channel_mixer, silence_detector")
+        );
+        assert!(AudioProcessingPipeline::new(config).is_err());
+
+        let stages_only = ProcessingConfig::builder()
+            .with_stage(StageConfig::new(ProcessorRegistry::NORMALIZER))
+            .build();
+        assert!(ConfigValidator::validate_processing_config(&stages_only).is_ok());
+    }
+
     #[test]
     fn test_validate_resampler_config() {
         let valid_config = ResamplerConfig {
@@ -361,6 +398,7 @@ mod tests {
                 left_weight: 0.7,
                 right_weight: 0.3,
             },
+            ..Default::default()
         };
         assert!(ConfigValidator::validate_channel_mixer_config(&valid_config).is_ok());
         let invalid_config = ChannelMixerConfig {
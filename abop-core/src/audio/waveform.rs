@@ -0,0 +1,651 @@
+//! Waveform and loudness overview generation
+//!
+//! This module decodes an audio file with [`AudioDecoder`] and reduces it to a
+//! compact multi-resolution summary of peak and RMS values, similar in spirit to
+//! the `.dat` files produced by audiowaveform. The finest level stores a fixed
+//! number of points per second of audio; every following level halves the
+//! resolution so that a scrubber of any width can pick a level without
+//! re-reading the full summary.
+
+use std::path::Path;
+
+use super::AudioDecoder;
+use crate::db::repositories::WaveformRepository;
+use crate::error::{AppError, Result};
+use crate::models::Audiobook;
+
+/// Magic bytes at the start of a serialized waveform summary
+const WAVEFORM_MAGIC: &[u8; 4] = b"ABWF";
+
+/// Version of the serialized waveform format
+pub const WAVEFORM_FORMAT_VERSION: u32 = 1;
+
+/// Size in bytes of the fixed serialized header
+const HEADER_SIZE: usize = 4 + 4 + 4 + 2 + 8 + 4 + 4 + 4 + 4;
+
+/// Size in bytes of a single serialized point
+const POINT_SIZE: usize = 3;
+
+/// Configuration for waveform generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaveformConfig {
+    /// Number of points per second of audio at the finest level
+    pub points_per_second: u32,
+    /// Coarser levels are generated until a level has at most this many points
+    pub min_level_points: usize,
+}
+
+impl Default for WaveformConfig {
+    fn default() -> Self {
+        Self {
+            points_per_second: 4,
+            min_level_points: 512,
+        }
+    }
+}
+
+/// A single point of a waveform level
+///
+/// Values are quantized to 8 bits to keep summaries of long audiobooks small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WaveformPoint {
+    /// Minimum sample value in the range, scaled to `-128..=127`
+    pub min: i8,
+    /// Maximum sample value in the range, scaled to `-128..=127`
+    pub max: i8,
+    /// RMS level of the range, scaled to `0..=255`
+    pub rms: u8,
+}
+
+impl WaveformPoint {
+    /// Peak amplitude of this point in the range `0.0..=1.0`
+    #[must_use]
+    pub fn peak(&self) -> f32 {
+        let min = f32::from(self.min).abs() / 128.0;
+        let max = f32::from(self.max).abs() / 127.0;
+        min.max(max).min(1.0)
+    }
+
+    /// RMS amplitude of this point in the range `0.0..=1.0`
+    #[must_use]
+    pub fn rms_amplitude(&self) -> f32 {
+        f32::from(self.rms) / 255.0
+    }
+}
+
+/// One resolution level of a waveform summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveformLevel {
+    /// Number of audio frames summarized by each point
+    pub frames_per_point: u32,
+    /// Points in playback order
+    pub points: Vec<WaveformPoint>,
+}
+
+/// Multi-resolution peak/RMS summary of an audio file
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveformSummary {
+    /// Sample rate of the source audio in Hz
+    pub sample_rate: u32,
+    /// Number of channels in the source audio
+    pub channels: u16,
+    /// Total number of decoded frames
+    pub total_frames: u64,
+    /// Overall peak amplitude in the range `0.0..=1.0`
+    pub peak: f32,
+    /// Overall RMS amplitude in the range `0.0..=1.0`
+    pub rms: f32,
+    /// Resolution levels ordered from finest to coarsest
+    pub levels: Vec<WaveformLevel>,
+}
+
+impl WaveformSummary {
+    /// Duration of the summarized audio in seconds
+    #[must_use]
+    pub fn duration(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let frames = self.total_frames as f64;
+        frames / f64::from(self.sample_rate)
+    }
+
+    /// Overall peak level in dBFS
+    #[must_use]
+    pub fn peak_dbfs(&self) -> f32 {
+        amplitude_to_dbfs(self.peak)
+    }
+
+    /// Overall RMS level in dBFS
+    #[must_use]
+    pub fn rms_dbfs(&self) -> f32 {
+        amplitude_to_dbfs(self.rms)
+    }
+
+    /// Returns the coarsest level that still has at least `width` points
+    ///
+    /// Falls back to the finest level when no level is wide enough.
+    #[must_use]
+    pub fn level_for_width(&self, width: usize) -> Option<&WaveformLevel> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| level.points.len() >= width)
+            .or_else(|| self.levels.first())
+    }
+
+    /// Serializes the summary into its compact binary representation
+    ///
+    /// All integers are little endian. The layout is a fixed header followed by
+    /// each level's `frames_per_point`, point count and `min`/`max`/`rms` triples.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let point_count: usize = self.levels.iter().map(|l| l.points.len()).sum();
+        let mut bytes =
+            Vec::with_capacity(HEADER_SIZE + self.levels.len() * 8 + point_count * POINT_SIZE);
+
+        bytes.extend_from_slice(WAVEFORM_MAGIC);
+        bytes.extend_from_slice(&WAVEFORM_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&self.channels.to_le_bytes());
+        bytes.extend_from_slice(&self.total_frames.to_le_bytes());
+        bytes.extend_from_slice(&self.peak.to_le_bytes());
+        bytes.extend_from_slice(&self.rms.to_le_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.extend_from_slice(&(self.levels.len() as u32).to_le_bytes());
+        // Reserved for future flags
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+
+        for level in &self.levels {
+            bytes.extend_from_slice(&level.frames_per_point.to_le_bytes());
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.extend_from_slice(&(level.points.len() as u32).to_le_bytes());
+            for point in &level.points {
+                bytes.extend_from_slice(&point.min.to_le_bytes());
+                bytes.extend_from_slice(&point.max.to_le_bytes());
+                bytes.push(point.rms);
+            }
+        }
+
+        bytes
+    }
+
+    /// Parses a summary previously produced by [`Self::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::InvalidData`] if the data is truncated, has the wrong
+    /// magic bytes or uses an unsupported format version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+
+        if reader.take(4)? != WAVEFORM_MAGIC {
+            return Err(AppError::InvalidData(
+                "Not a waveform summary (bad magic)".to_string(),
+            ));
+        }
+        let version = reader.u32()?;
+        if version != WAVEFORM_FORMAT_VERSION {
+            return Err(AppError::InvalidData(format!(
+                "Unsupported waveform format version {version}"
+            )));
+        }
+
+        let sample_rate = reader.u32()?;
+        let channels = reader.u16()?;
+        let total_frames = reader.u64()?;
+        let peak = f32::from_bits(reader.u32()?);
+        let rms = f32::from_bits(reader.u32()?);
+        let level_count = reader.u32()? as usize;
+        let _flags = reader.u32()?;
+
+        let mut levels = Vec::with_capacity(level_count.min(64));
+        for _ in 0..level_count {
+            let frames_per_point = reader.u32()?;
+            let len = reader.u32()? as usize;
+            let raw = reader.take(len.saturating_mul(POINT_SIZE))?;
+            let points = raw
+                .chunks_exact(POINT_SIZE)
+                .map(|chunk| WaveformPoint {
+                    min: i8::from_le_bytes([chunk[0]]),
+                    max: i8::from_le_bytes([chunk[1]]),
+                    rms: chunk[2],
+                })
+                .collect();
+            levels.push(WaveformLevel {
+                frames_per_point,
+                points,
+            });
+        }
+
+        Ok(Self {
+            sample_rate,
+            channels,
+            total_frames,
+            peak,
+            rms,
+            levels,
+        })
+    }
+}
+
+/// Converts a linear amplitude to dBFS, clamping silence to -96 dB
+fn amplitude_to_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        -96.0
+    } else {
+        (20.0 * amplitude.log10()).max(-96.0)
+    }
+}
+
+/// Minimal little-endian reader used by [`WaveformSummary::from_bytes`]
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| AppError::InvalidData("Truncated waveform summary".to_string()))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let mut buf = [0u8; 2];
+        buf.copy_from_slice(self.take(2)?);
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+/// Running min/max/sum-of-squares accumulator for a single point
+#[derive(Debug, Clone, Copy)]
+struct PointAccumulator {
+    min: f32,
+    max: f32,
+    sum_squares: f64,
+    frames: u32,
+}
+
+impl PointAccumulator {
+    const fn new() -> Self {
+        Self {
+            min: 0.0,
+            max: 0.0,
+            sum_squares: 0.0,
+            frames: 0,
+        }
+    }
+
+    fn push(&mut self, min: f32, max: f32, square: f64) {
+        self.min = self.min.min(min);
+        self.max = self.max.max(max);
+        self.sum_squares += square;
+        self.frames += 1;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn finish(&self) -> WaveformPoint {
+        let rms = if self.frames == 0 {
+            0.0
+        } else {
+            (self.sum_squares / f64::from(self.frames)).sqrt() as f32
+        };
+        WaveformPoint {
+            min: (self.min.clamp(-1.0, 1.0) * 128.0)
+                .round()
+                .clamp(-128.0, 127.0) as i8,
+            max: (self.max.clamp(-1.0, 1.0) * 127.0).round() as i8,
+            rms: (rms.clamp(0.0, 1.0) * 255.0).round() as u8,
+        }
+    }
+}
+
+/// Incremental builder that turns interleaved samples into a [`WaveformSummary`]
+///
+/// The builder keeps only the finest level in memory while samples are pushed,
+/// so arbitrarily long files can be summarized with bounded memory per point.
+#[derive(Debug, Clone)]
+pub struct WaveformBuilder {
+    config: WaveformConfig,
+    sample_rate: u32,
+    channels: u16,
+    frames_per_point: u32,
+    current: PointAccumulator,
+    points: Vec<WaveformPoint>,
+    total_frames: u64,
+    peak: f32,
+    sum_squares: f64,
+}
+
+impl WaveformBuilder {
+    /// Creates a new builder for audio with the given sample rate and channel count
+    #[must_use]
+    pub fn new(config: WaveformConfig, sample_rate: u32, channels: u16) -> Self {
+        let frames_per_point = (sample_rate / config.points_per_second.max(1)).max(1);
+        Self {
+            config,
+            sample_rate,
+            channels: channels.max(1),
+            frames_per_point,
+            current: PointAccumulator::new(),
+            points: Vec::new(),
+            total_frames: 0,
+            peak: 0.0,
+            sum_squares: 0.0,
+        }
+    }
+
+    /// Adds a block of interleaved samples
+    ///
+    /// Channels are folded together: each frame contributes its lowest and highest
+    /// channel sample to the point's min/max and the mean of its squares to the RMS.
+    pub fn push_interleaved(&mut self, samples: &[f32]) {
+        let channels = usize::from(self.channels);
+        for frame in samples.chunks_exact(channels) {
+            let mut min = f32::MAX;
+            let mut max = f32::MIN;
+            let mut square = 0.0f64;
+            for &sample in frame {
+                min = min.min(sample);
+                max = max.max(sample);
+                square += f64::from(sample) * f64::from(sample);
+            }
+            square /= f64::from(self.channels);
+
+            self.peak = self.peak.max(min.abs()).max(max.abs());
+            self.sum_squares += square;
+            self.total_frames += 1;
+
+            self.current.push(min, max, square);
+            if self.current.frames >= self.frames_per_point {
+                self.points.push(self.current.finish());
+                self.current = PointAccumulator::new();
+            }
+        }
+    }
+
+    /// Completes the summary, flushing any partial point and building coarser levels
+    #[must_use]
+    pub fn finish(mut self) -> WaveformSummary {
+        if self.current.frames > 0 {
+            self.points.push(self.current.finish());
+        }
+
+        let rms = if self.total_frames == 0 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+            let rms = (self.sum_squares / self.total_frames as f64).sqrt() as f32;
+            rms
+        };
+
+        let mut levels = vec![WaveformLevel {
+            frames_per_point: self.frames_per_point,
+            points: self.points,
+        }];
+        while let Some(last) = levels.last() {
+            if last.points.len() <= self.config.min_level_points.max(1) {
+                break;
+            }
+            let next = downsample_level(last);
+            levels.push(next);
+        }
+
+        WaveformSummary {
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            total_frames: self.total_frames,
+            peak: self.peak.min(1.0),
+            rms: rms.min(1.0),
+            levels,
+        }
+    }
+}
+
+/// Merges adjacent pairs of points into a level with half the resolution
+fn downsample_level(level: &WaveformLevel) -> WaveformLevel {
+    let points = level
+        .points
+        .chunks(2)
+        .map(|pair| {
+            let min = pair.iter().map(|p| p.min).min().unwrap_or_default();
+            let max = pair.iter().map(|p| p.max).max().unwrap_or_default();
+            let mean_square = pair
+                .iter()
+                .map(|p| f32::from(p.rms) * f32::from(p.rms))
+                .sum::<f32>()
+                / pair.len() as f32;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let rms = mean_square.sqrt().round().min(255.0) as u8;
+            WaveformPoint { min, max, rms }
+        })
+        .collect();
+
+    WaveformLevel {
+        frames_per_point: level.frames_per_point.saturating_mul(2),
+        points,
+    }
+}
+
+/// Analysis job that decodes a file and produces its [`WaveformSummary`]
+#[derive(Debug, Clone, Default)]
+pub struct WaveformGenerator {
+    config: WaveformConfig,
+}
+
+impl WaveformGenerator {
+    /// Creates a generator with the given configuration
+    #[must_use]
+    pub const fn new(config: WaveformConfig) -> Self {
+        Self { config }
+    }
+
+    /// Gets the generator configuration
+    #[must_use]
+    pub const fn config(&self) -> &WaveformConfig {
+        &self.config
+    }
+
+    /// Decodes the file at `path` and summarizes it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or a packet fails to decode.
+    pub fn generate<P: AsRef<Path>>(&self, path: P) -> Result<WaveformSummary> {
+        let mut decoder = AudioDecoder::open(path.as_ref())?;
+        self.generate_from_decoder(&mut decoder)
+    }
+
+    /// Summarizes the remaining packets of an already opened decoder
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a packet fails to decode.
+    pub fn generate_from_decoder(&self, decoder: &mut AudioDecoder) -> Result<WaveformSummary> {
+        let mut builder =
+            WaveformBuilder::new(self.config, decoder.sample_rate(), decoder.channels());
+        while let Some(buffer) = decoder.next_packet()? {
+            builder.push_interleaved(&buffer.data);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Returns the cached summary for an audiobook, generating and storing it if needed
+    ///
+    /// A cached summary is reused only when it was generated with the same
+    /// `points_per_second` and, if known, the same source file size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read or written, or if decoding fails.
+    pub fn load_or_generate(
+        &self,
+        repository: &WaveformRepository,
+        audiobook: &Audiobook,
+    ) -> Result<WaveformSummary> {
+        let source_size = std::fs::metadata(&audiobook.path)
+            .map(|m| m.len())
+            .ok()
+            .or(audiobook.size_bytes);
+
+        if let Some(cached) = repository.find_by_audiobook(&audiobook.id)?
+            && cached.is_current(&self.config, source_size)
+        {
+            log::debug!("Using cached waveform for audiobook {}", audiobook.id);
+            return Ok(cached.summary);
+        }
+
+        log::debug!("Generating waveform for {}", audiobook.path.display());
+        let summary = self.generate(&audiobook.path)?;
+        repository.upsert(
+            &audiobook.id,
+            self.config.points_per_second,
+            source_size,
+            &summary,
+        )?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(sample_rate: u32, seconds: f32, amplitude: f32) -> Vec<f32> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let frames = (sample_rate as f32 * seconds) as usize;
+        (0..frames)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (2.0 * std::f32::consts::PI * 440.0 * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builder_produces_expected_points() {
+        let config = WaveformConfig {
+            points_per_second: 10,
+            min_level_points: 4,
+        };
+        let mut builder = WaveformBuilder::new(config, 8000, 1);
+        builder.push_interleaved(&sine(8000, 2.0, 0.5));
+        let summary = builder.finish();
+
+        assert_eq!(summary.total_frames, 16000);
+        assert_eq!(summary.levels[0].frames_per_point, 800);
+        assert_eq!(summary.levels[0].points.len(), 20);
+        assert!((summary.peak - 0.5).abs() < 0.01);
+        assert!((summary.rms - 0.5 / 2f32.sqrt()).abs() < 0.01);
+
+        let coarsest = summary.levels.last().unwrap();
+        assert!(coarsest.points.len() <= 4);
+        assert_eq!(
+            coarsest.frames_per_point,
+            800 * 2u32.pow(summary.levels.len() as u32 - 1)
+        );
+    }
+
+    #[test]
+    fn test_silence_is_flat() {
+        let mut builder = WaveformBuilder::new(WaveformConfig::default(), 44100, 2);
+        builder.push_interleaved(&vec![0.0; 44100 * 2]);
+        let summary = builder.finish();
+
+        assert!(
+            summary.levels[0]
+                .points
+                .iter()
+                .all(|p| *p == WaveformPoint::default())
+        );
+        assert_eq!(summary.rms_dbfs(), -96.0);
+    }
+
+    #[test]
+    fn test_level_for_width() {
+        let config = WaveformConfig {
+            points_per_second: 100,
+            min_level_points: 10,
+        };
+        let mut builder = WaveformBuilder::new(config, 1000, 1);
+        builder.push_interleaved(&sine(1000, 4.0, 0.8));
+        let summary = builder.finish();
+
+        let level = summary.level_for_width(50).unwrap();
+        assert!(level.points.len() >= 50);
+        assert!(level.points.len() < 100);
+        assert_eq!(
+            summary.level_for_width(10_000).unwrap().points.len(),
+            summary.levels[0].points.len()
+        );
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let mut builder = WaveformBuilder::new(WaveformConfig::default(), 22050, 2);
+        builder.push_interleaved(&sine(22050, 3.0, 0.25));
+        let summary = builder.finish();
+
+        let decoded = WaveformSummary::from_bytes(&summary.to_bytes()).unwrap();
+        assert_eq!(decoded, summary);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_invalid_data() {
+        assert!(matches!(
+            WaveformSummary::from_bytes(b"nope"),
+            Err(AppError::InvalidData(_))
+        ));
+
+        let summary = WaveformBuilder::new(WaveformConfig::default(), 8000, 1).finish();
+        let bytes = summary.to_bytes();
+        assert!(WaveformSummary::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_generate_from_wav_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tone.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for sample in sine(8000, 1.0, 0.5) {
+            #[allow(clippy::cast_possible_truncation)]
+            writer.write_sample((sample * 32767.0) as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let summary = WaveformGenerator::default().generate(&path).unwrap();
+        assert_eq!(summary.sample_rate, 8000);
+        assert_eq!(summary.total_frames, 8000);
+        assert!((summary.duration() - 1.0).abs() < 1e-6);
+        assert!((summary.peak - 0.5).abs() < 0.01);
+    }
+}
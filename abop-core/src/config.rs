@@ -3,6 +3,7 @@
 //! This module provides a modular configuration system that maintains backward
 //! compatibility while offering enhanced validation and organization.
 
+use crate::audio::processing::ProcessingConfig;
 use crate::error::{AppError, Result};
 #[cfg(windows)]
 use crate::platform::env_utils;
@@ -37,6 +38,10 @@ pub struct Config {
     /// UI behavior and preferences (new modular structure)
     #[serde(default)]
     pub ui: UiConfig,
+    /// Audio processing settings; the equalizer and de-esser also run live
+    /// during playback
+    #[serde(default)]
+    pub processing: ProcessingConfig,
 }
 
 impl Config {
@@ -145,6 +150,7 @@ impl Default for Config {
             data_dir: dirs::data_dir().unwrap_or_else(|| PathBuf::from("./data")),
             app: AppConfig::default(),
             ui: UiConfig::default(),
+            processing: ProcessingConfig::default(),
         }
     }
 }
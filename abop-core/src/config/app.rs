@@ -4,6 +4,7 @@
 //! more specific categories like audio, database, or UI configuration.
 
 use crate::config::validation::{ConfigValidation, ValidationResult};
+use crate::models::DEFAULT_PROFILE_ID;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -31,6 +32,9 @@ const fn default_auto_save_interval() -> u64 {
 const fn default_crash_reporting() -> bool {
     true
 }
+fn default_active_profile_id() -> String {
+    DEFAULT_PROFILE_ID.to_string()
+}
 
 /// Application-level configuration settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +60,9 @@ pub struct AppConfig {
     /// Enable crash reporting
     #[serde(default = "default_crash_reporting")]
     pub crash_reporting: bool,
+    /// Listener profile that was active when the app was last used
+    #[serde(default = "default_active_profile_id")]
+    pub active_profile_id: String,
 }
 
 impl Default for AppConfig {
@@ -70,6 +77,7 @@ impl Default for AppConfig {
             max_recent_files: 10,
             auto_save_interval: 300, // 5 minutes
             crash_reporting: true,
+            active_profile_id: DEFAULT_PROFILE_ID.to_string(),
         }
     }
 }
@@ -146,6 +154,20 @@ mod tests {
         assert_eq!(config.app_name, "ABOP Iced");
         assert!(config.max_recent_files <= 100);
         assert!(config.auto_save_interval >= 60 || config.auto_save_interval == 0);
+        assert_eq!(config.active_profile_id, DEFAULT_PROFILE_ID);
+    }
+
+    #[test]
+    fn test_active_profile_defaults_when_missing() {
+        let config: AppConfig = toml::from_str("app_name = \"ABOP\"").unwrap();
+        assert_eq!(config.active_profile_id, DEFAULT_PROFILE_ID);
+
+        let saved = AppConfig {
+            active_profile_id: "sam".to_string(),
+            ..Default::default()
+        };
+        let loaded: AppConfig = toml::from_str(&toml::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(loaded.active_profile_id, "sam");
     }
 
     #[test]
@@ -0,0 +1,194 @@
+//! Links between audiobooks and their authors, narrators and series
+//!
+//! The free-text author and narrator columns stay the source of truth for
+//! tags. Every write of an audiobook re-derives its person links from them,
+//! while series links parsed from the title are only added when the book has
+//! no series yet, so series assigned by hand survive a rescan.
+
+use rusqlite::{Connection, OptionalExtension, Row, params};
+
+use super::mappers::RowMappers;
+use crate::library::names::{PersonName, parse_people, parse_series, series_key};
+use crate::models::{Audiobook, PersonRole};
+
+/// Tables that store the people of one role
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PersonTables {
+    /// Table of the people themselves
+    pub people: &'static str,
+    /// Table mapping alias keys to people
+    pub aliases: &'static str,
+    /// Table linking audiobooks to people
+    pub links: &'static str,
+    /// Column referencing the person in the alias and link tables
+    pub column: &'static str,
+}
+
+impl PersonTables {
+    pub(crate) const fn for_role(role: PersonRole) -> Self {
+        match role {
+            PersonRole::Author => Self {
+                people: "authors",
+                aliases: "author_aliases",
+                links: "audiobook_authors",
+                column: "author_id",
+            },
+            PersonRole::Narrator => Self {
+                people: "narrators",
+                aliases: "narrator_aliases",
+                links: "audiobook_narrators",
+                column: "narrator_id",
+            },
+        }
+    }
+}
+
+/// Rebuild the person links of one audiobook and add its parsed series
+pub(crate) fn sync_audiobook_links(
+    conn: &Connection,
+    audiobook_id: &str,
+    title: Option<&str>,
+    author: Option<&str>,
+    narrator: Option<&str>,
+) -> rusqlite::Result<()> {
+    for (role, tag) in [
+        (PersonRole::Author, author),
+        (PersonRole::Narrator, narrator),
+    ] {
+        let tables = PersonTables::for_role(role);
+        conn.execute(
+            &format!("DELETE FROM {} WHERE audiobook_id = ?1", tables.links),
+            [audiobook_id],
+        )?;
+        for (position, person) in parse_people(tag.unwrap_or_default()).iter().enumerate() {
+            let person_id = resolve_person(conn, role, person)?;
+            conn.execute(
+                &format!(
+                    "INSERT OR IGNORE INTO {} (audiobook_id, {}, position) VALUES (?1, ?2, ?3)",
+                    tables.links, tables.column
+                ),
+                params![audiobook_id, person_id, position],
+            )?;
+        }
+    }
+
+    let Some(series) = title.and_then(parse_series) else {
+        return Ok(());
+    };
+    let has_series: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM audiobook_series WHERE audiobook_id = ?1)",
+        [audiobook_id],
+        |row| row.get(0),
+    )?;
+    if !has_series {
+        let series_id = resolve_series(conn, &series.name)?;
+        conn.execute(
+            "INSERT INTO audiobook_series (audiobook_id, series_id, series_index)
+             VALUES (?1, ?2, ?3)",
+            params![audiobook_id, series_id, series.index],
+        )?;
+    }
+    Ok(())
+}
+
+/// Link every stored audiobook, used when the link tables are first created
+pub(crate) fn backfill_links(conn: &Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("SELECT id, title, author, narrator FROM audiobooks")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (id, title, author, narrator) in rows {
+        sync_audiobook_links(
+            conn,
+            &id,
+            title.as_deref(),
+            author.as_deref(),
+            narrator.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// ID of the person with the given name, created on first sight
+pub(crate) fn resolve_person(
+    conn: &Connection,
+    role: PersonRole,
+    person: &PersonName,
+) -> rusqlite::Result<String> {
+    let tables = PersonTables::for_role(role);
+    let existing: Option<String> = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM {} WHERE alias_key = ?1",
+                tables.column, tables.aliases
+            ),
+            [&person.key],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        &format!(
+            "INSERT INTO {} (id, name, sort_name) VALUES (?1, ?2, ?3)",
+            tables.people
+        ),
+        params![id, person.name, person.sort_name],
+    )?;
+    conn.execute(
+        &format!(
+            "INSERT INTO {} (alias_key, {}) VALUES (?1, ?2)",
+            tables.aliases, tables.column
+        ),
+        params![person.key, id],
+    )?;
+    Ok(id)
+}
+
+/// ID of the series with the given name, created on first sight
+pub(crate) fn resolve_series(conn: &Connection, name: &str) -> rusqlite::Result<String> {
+    let key = series_key(name);
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT series_id FROM series_aliases WHERE alias_key = ?1",
+            [&key],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO series (id, name) VALUES (?1, ?2)",
+        params![id, name.trim()],
+    )?;
+    conn.execute(
+        "INSERT INTO series_aliases (alias_key, series_id) VALUES (?1, ?2)",
+        params![key, id],
+    )?;
+    Ok(id)
+}
+
+/// Audiobook columns in [`RowMappers::audiobook_from_row`] order, for joins on `a`
+pub(crate) const JOINED_AUDIOBOOK_COLUMNS: &str = "a.id, a.library_id, a.path, a.title, a.author,
+     a.narrator, a.description, a.duration_seconds, a.size_bytes, a.cover_art, a.created_at,
+     a.updated_at, a.selected";
+
+/// Maps an audiobook row selected with [`JOINED_AUDIOBOOK_COLUMNS`]
+pub(crate) fn map_audiobook(row: &Row<'_>) -> rusqlite::Result<Audiobook> {
+    RowMappers::audiobook_from_row(row).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
@@ -158,7 +158,9 @@ impl EnhancedConnection {
 
     /// Establish a new database connection
     fn establish_connection(&self) -> DbResult<Connection> {
-        let flags = OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE;
+        let flags = OpenFlags::SQLITE_OPEN_READ_WRITE
+            | OpenFlags::SQLITE_OPEN_CREATE
+            | OpenFlags::SQLITE_OPEN_URI;
         let conn = Connection::open_with_flags(&self.config.path, flags).map_err(|e| {
             DatabaseError::ConnectionFailed(format!("Failed to open database: {e}"))
         })?;
@@ -95,15 +95,16 @@ impl RowMappers {
     pub fn progress_from_row(row: &Row) -> DbResult<Progress> {
         use crate::db::datetime_serde::SqliteDateTime;
 
-        let last_played: Option<SqliteDateTime> = get_field!(row, 4, "last_played", optional);
-        let created_at: SqliteDateTime = get_field!(row, 5, "created_at");
-        let updated_at: SqliteDateTime = get_field!(row, 6, "updated_at");
+        let last_played: Option<SqliteDateTime> = get_field!(row, 5, "last_played", optional);
+        let created_at: SqliteDateTime = get_field!(row, 6, "created_at");
+        let updated_at: SqliteDateTime = get_field!(row, 7, "updated_at");
 
         Ok(Progress {
             id: get_field!(row, 0, "progress id"),
-            audiobook_id: get_field!(row, 1, "audiobook_id"),
-            position_seconds: get_field!(row, 2, "position_seconds"),
-            completed: get_field!(row, 3, "completed"),
+            profile_id: get_field!(row, 1, "profile_id"),
+            audiobook_id: get_field!(row, 2, "audiobook_id"),
+            position_seconds: get_field!(row, 3, "position_seconds"),
+            completed: get_field!(row, 4, "completed"),
             last_played: last_played.map(|dt| dt.into()),
             created_at: created_at.into(),
             updated_at: updated_at.into(),
@@ -240,7 +241,7 @@ impl SqlQueries {
 
     /// Standard progress SELECT columns
     pub const PROGRESS_COLUMNS: &'static str =
-        "id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at";
+        "id, profile_id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at";
 
     /// Generate a standard audiobook SELECT query with optional WHERE clause
     #[must_use]
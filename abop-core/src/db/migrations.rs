@@ -17,6 +17,8 @@ pub struct Migration {
     pub up_sql: &'static str,
     /// Description of what this migration does
     pub description: &'static str,
+    /// Data step run after `up_sql` in the same transaction
+    pub backfill: Option<fn(&Connection) -> rusqlite::Result<()>>,
 }
 
 /// Migration execution result
@@ -166,6 +168,16 @@ impl MigrationManager {
             });
         }
 
+        if let Some(backfill) = migration.backfill
+            && let Err(e) = backfill(&tx)
+        {
+            error!("Failed to backfill migration {}: {}", migration.version, e);
+            return Err(DatabaseError::MigrationFailed {
+                version: migration.version,
+                message: format!("Failed to backfill: {e}"),
+            });
+        }
+
         // Update migration tracking
         tx.execute(
             "INSERT OR REPLACE INTO migrations (version, description, applied, applied_at) VALUES (?, ?, 1, CURRENT_TIMESTAMP)",
@@ -205,11 +217,71 @@ fn get_migrations() -> Vec<Migration> {
             version: 1,
             up_sql: include_str!("migrations/001_initial_schema.sql"),
             description: "Initial database schema with libraries, audiobooks, and progress tracking",
+            backfill: None,
+        },
+        // Version 2 used to add the selected column, which now lives in the initial
+        // schema. Its number was reused for waveform summaries, so databases that
+        // recorded the old version 2 skip it; version 11 creates the table for them.
+        Migration {
+            version: 2,
+            up_sql: include_str!("migrations/002_waveform_summaries.sql"),
+            description: "Cached waveform summaries for the player scrubber",
+            backfill: None,
+        },
+        Migration {
+            version: 3,
+            up_sql: include_str!("migrations/003_file_health.sql"),
+            description: "Per-file health status from integrity verification",
+            backfill: None,
+        },
+        Migration {
+            version: 4,
+            up_sql: include_str!("migrations/004_jobs.sql"),
+            description: "Persisted scan and processing jobs with per-file resume cursor",
+            backfill: None,
+        },
+        Migration {
+            version: 5,
+            up_sql: include_str!("migrations/005_people_and_series.sql"),
+            description: "Authors, narrators and series linked to audiobooks",
+            backfill: Some(super::catalog::backfill_links),
+        },
+        Migration {
+            version: 6,
+            up_sql: include_str!("migrations/006_collections_and_tags.sql"),
+            description: "User collections and tags",
+            backfill: None,
+        },
+        Migration {
+            version: 7,
+            up_sql: include_str!("migrations/007_smart_collections.sql"),
+            description: "Smart collections defined by saved filter rules",
+            backfill: None,
+        },
+        Migration {
+            version: 8,
+            up_sql: include_str!("migrations/008_profiles.sql"),
+            description: "Listener profiles scoping progress, bookmarks and playback preferences",
+            backfill: None,
+        },
+        Migration {
+            version: 9,
+            up_sql: include_str!("migrations/009_sync.sql"),
+            description: "Content hashes and device identity for progress sync",
+            backfill: None,
+        },
+        Migration {
+            version: 10,
+            up_sql: include_str!("migrations/010_metadata_origins.sql"),
+            description: "Source of each merged audiobook metadata field",
+            backfill: None,
+        },
+        Migration {
+            version: 11,
+            up_sql: include_str!("migrations/002_waveform_summaries.sql"),
+            description: "Waveform summaries for databases that recorded the removed version 2",
+            backfill: None,
         },
-        // Migration version 2 (add selected column) was removed during schema consolidation.
-        // The selected column functionality is now included in the initial schema (version 1)
-        // to reduce complexity for new installations. This avoids the need for a separate
-        // migration step for basic UI state tracking functionality.
     ]
 }
 
@@ -284,4 +356,54 @@ mod tests {
         let pending = manager.pending_migrations(&conn).unwrap();
         assert!(pending.is_empty(), "Should have no pending migrations");
     }
+
+    #[test]
+    fn test_waveform_summaries_after_removed_version_2() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        MigrationManager::setup_migrations_table(&conn).unwrap();
+        MigrationManager::apply_migration(&mut conn, &get_migrations()[0]).unwrap();
+        conn.execute(
+            "INSERT INTO migrations (version, description, applied) VALUES (2, 'Add selected column', 1)",
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='waveform_summaries'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_people_and_series_backfill() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        MigrationManager::setup_migrations_table(&conn).unwrap();
+        for migration in get_migrations().iter().filter(|m| m.version < 5) {
+            MigrationManager::apply_migration(&mut conn, migration).unwrap();
+        }
+        conn.execute_batch(
+            "INSERT INTO libraries (id, name, path) VALUES ('lib', 'Books', '/books');
+             INSERT INTO audiobooks (id, library_id, path, title, author, narrator) VALUES
+                ('a', 'lib', '/books/a.mp3', 'Mort (Discworld #4)', 'Pratchett, Terry', 'Nigel Planer'),
+                ('b', 'lib', '/books/b.mp3', 'Good Omens', 'Terry Pratchett & Neil Gaiman', NULL);",
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM authors"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM audiobook_authors"), 3);
+        assert_eq!(count("SELECT COUNT(*) FROM narrators"), 1);
+        assert_eq!(
+            count("SELECT COUNT(*) FROM audiobook_series WHERE series_index = 4"),
+            1
+        );
+    }
 }
@@ -0,0 +1,16 @@
+-- Cached waveform and loudness overviews for the player scrubber
+--
+-- Also run as version 11 for databases that recorded the removed version 2,
+-- so the table is only created when missing.
+
+CREATE TABLE IF NOT EXISTS waveform_summaries (
+    audiobook_id TEXT PRIMARY KEY,
+    -- Points per second of the finest level, used to detect stale caches
+    points_per_second INTEGER NOT NULL,
+    -- Source file size when the summary was generated
+    source_size_bytes INTEGER,
+    -- Serialized WaveformSummary (see audio::waveform)
+    data BLOB NOT NULL,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Rollback waveform summary cache
+
+DROP TABLE IF EXISTS waveform_summaries;
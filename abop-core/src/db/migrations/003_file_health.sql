@@ -0,0 +1,19 @@
+-- Per-file health status produced by full-decode integrity verification
+
+CREATE TABLE file_health (
+    audiobook_id TEXT PRIMARY KEY,
+    -- One of: healthy, degraded, corrupt, unreadable (see audio::integrity)
+    status TEXT NOT NULL,
+    decode_errors INTEGER NOT NULL DEFAULT 0,
+    expected_frames INTEGER,
+    decoded_frames INTEGER NOT NULL DEFAULT 0,
+    truncated_frames INTEGER NOT NULL DEFAULT 0,
+    header_duration_seconds REAL,
+    decoded_duration_seconds REAL NOT NULL DEFAULT 0,
+    -- Last decoder error message, if any
+    message TEXT,
+    checked_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_file_health_status ON file_health(status);
@@ -0,0 +1,4 @@
+-- Rollback file health table
+
+DROP INDEX IF EXISTS idx_file_health_status;
+DROP TABLE IF EXISTS file_health;
@@ -0,0 +1,29 @@
+-- Persisted scan and processing jobs so interrupted work can be resumed
+
+CREATE TABLE jobs (
+    id TEXT PRIMARY KEY,
+    -- One of: scan, process (see models::job)
+    job_type TEXT NOT NULL,
+    -- One of: pending, running, completed, failed, cancelled
+    status TEXT NOT NULL DEFAULT 'pending',
+    library_id TEXT,
+    -- Job type specific parameters as JSON
+    parameters TEXT NOT NULL DEFAULT '{}',
+    total_items INTEGER NOT NULL DEFAULT 0,
+    processed_items INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    FOREIGN KEY (library_id) REFERENCES libraries(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_jobs_status ON jobs(status);
+
+-- Files a job has already committed; acts as the per-file resume cursor
+CREATE TABLE job_items (
+    job_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    committed_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (job_id, path),
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Rollback persisted jobs tables
+
+DROP TABLE IF EXISTS job_items;
+DROP INDEX IF EXISTS idx_jobs_status;
+DROP TABLE IF EXISTS jobs;
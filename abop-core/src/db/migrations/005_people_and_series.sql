@@ -0,0 +1,86 @@
+-- Authors, narrators and series as entities linked to audiobooks
+--
+-- Existing audiobooks are linked from their author, narrator and title
+-- columns right after this script runs (see db::catalog).
+
+CREATE TABLE authors (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    sort_name TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- Every spelling that maps to an author, keyed by library::names::person_key
+CREATE TABLE author_aliases (
+    alias_key TEXT PRIMARY KEY,
+    author_id TEXT NOT NULL,
+    FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+);
+
+CREATE TABLE audiobook_authors (
+    audiobook_id TEXT NOT NULL,
+    author_id TEXT NOT NULL,
+    -- Order of the author within the original tag
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (audiobook_id, author_id),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_authors_sort_name ON authors(sort_name);
+CREATE INDEX idx_author_aliases_author ON author_aliases(author_id);
+CREATE INDEX idx_audiobook_authors_author ON audiobook_authors(author_id);
+
+CREATE TABLE narrators (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    sort_name TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE narrator_aliases (
+    alias_key TEXT PRIMARY KEY,
+    narrator_id TEXT NOT NULL,
+    FOREIGN KEY (narrator_id) REFERENCES narrators(id) ON DELETE CASCADE
+);
+
+CREATE TABLE audiobook_narrators (
+    audiobook_id TEXT NOT NULL,
+    narrator_id TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (audiobook_id, narrator_id),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE,
+    FOREIGN KEY (narrator_id) REFERENCES narrators(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_narrators_sort_name ON narrators(sort_name);
+CREATE INDEX idx_narrator_aliases_narrator ON narrator_aliases(narrator_id);
+CREATE INDEX idx_audiobook_narrators_narrator ON audiobook_narrators(narrator_id);
+
+CREATE TABLE series (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- Every spelling that maps to a series, keyed by library::names::series_key
+CREATE TABLE series_aliases (
+    alias_key TEXT PRIMARY KEY,
+    series_id TEXT NOT NULL,
+    FOREIGN KEY (series_id) REFERENCES series(id) ON DELETE CASCADE
+);
+
+CREATE TABLE audiobook_series (
+    audiobook_id TEXT NOT NULL,
+    series_id TEXT NOT NULL,
+    -- Position within the series; fractional for novellas such as 2.5
+    series_index REAL,
+    PRIMARY KEY (audiobook_id, series_id),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE,
+    FOREIGN KEY (series_id) REFERENCES series(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_series_name ON series(name);
+CREATE INDEX idx_series_aliases_series ON series_aliases(series_id);
+-- Series index: books of a series in reading order
+CREATE INDEX idx_audiobook_series_order ON audiobook_series(series_id, series_index);
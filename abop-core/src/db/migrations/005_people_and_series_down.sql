@@ -0,0 +1,11 @@
+-- Rollback authors, narrators and series tables
+
+DROP TABLE IF EXISTS audiobook_series;
+DROP TABLE IF EXISTS series_aliases;
+DROP TABLE IF EXISTS series;
+DROP TABLE IF EXISTS audiobook_narrators;
+DROP TABLE IF EXISTS narrator_aliases;
+DROP TABLE IF EXISTS narrators;
+DROP TABLE IF EXISTS audiobook_authors;
+DROP TABLE IF EXISTS author_aliases;
+DROP TABLE IF EXISTS authors;
@@ -0,0 +1,39 @@
+-- User-defined collections (ordered lists of audiobooks) and free-form tags
+
+CREATE TABLE collections (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    description TEXT,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE collection_items (
+    collection_id TEXT NOT NULL,
+    audiobook_id TEXT NOT NULL,
+    -- Zero-based position of the audiobook within the collection
+    position INTEGER NOT NULL,
+    added_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (collection_id, audiobook_id),
+    FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE,
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_collection_items_order ON collection_items(collection_id, position);
+CREATE INDEX idx_collection_items_audiobook ON collection_items(audiobook_id);
+
+CREATE TABLE tags (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE audiobook_tags (
+    audiobook_id TEXT NOT NULL,
+    tag_id TEXT NOT NULL,
+    PRIMARY KEY (audiobook_id, tag_id),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_audiobook_tags_tag ON audiobook_tags(tag_id);
@@ -0,0 +1,6 @@
+-- Rollback collections and tags tables
+
+DROP TABLE IF EXISTS audiobook_tags;
+DROP TABLE IF EXISTS tags;
+DROP TABLE IF EXISTS collection_items;
+DROP TABLE IF EXISTS collections;
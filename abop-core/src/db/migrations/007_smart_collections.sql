@@ -0,0 +1,21 @@
+-- Smart collections: named filter rules and their evaluated members
+
+CREATE TABLE smart_collections (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    -- Rule tree serialized as JSON
+    rule TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- Audiobooks matching the rule at its last evaluation
+CREATE TABLE smart_collection_items (
+    smart_collection_id TEXT NOT NULL,
+    audiobook_id TEXT NOT NULL,
+    PRIMARY KEY (smart_collection_id, audiobook_id),
+    FOREIGN KEY (smart_collection_id) REFERENCES smart_collections(id) ON DELETE CASCADE,
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_smart_collection_items_audiobook ON smart_collection_items(audiobook_id);
@@ -0,0 +1,4 @@
+-- Rollback smart collection tables
+
+DROP TABLE IF EXISTS smart_collection_items;
+DROP TABLE IF EXISTS smart_collections;
@@ -0,0 +1,54 @@
+-- Listener profiles with their own progress, bookmarks and playback preferences
+
+CREATE TABLE profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    -- Playback preferences as JSON; NULL uses the application defaults
+    playback_config TEXT,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+INSERT INTO profiles (id, name) VALUES ('default', 'Default');
+
+-- Progress was unique per audiobook; rebuild it as unique per profile and audiobook
+CREATE TABLE progress_by_profile (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL DEFAULT 'default',
+    audiobook_id TEXT NOT NULL,
+    position_seconds INTEGER NOT NULL DEFAULT 0,
+    completed BOOLEAN NOT NULL DEFAULT 0,
+    last_played TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (profile_id, audiobook_id),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE,
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+INSERT INTO progress_by_profile (
+    id, profile_id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
+)
+SELECT id, 'default', audiobook_id, position_seconds, completed, last_played, created_at, updated_at
+FROM progress;
+
+DROP TABLE progress;
+ALTER TABLE progress_by_profile RENAME TO progress;
+CREATE INDEX idx_progress_audiobook_id ON progress(audiobook_id);
+
+CREATE TABLE bookmarks (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    audiobook_id TEXT NOT NULL,
+    position_seconds INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE,
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_bookmarks_profile_audiobook ON bookmarks(profile_id, audiobook_id, position_seconds);
+
+-- Progress conditions in smart collection rules are evaluated for one profile
+ALTER TABLE smart_collections ADD COLUMN profile_id TEXT NOT NULL DEFAULT 'default';
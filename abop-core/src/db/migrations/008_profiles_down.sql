@@ -0,0 +1,27 @@
+-- Rollback listener profiles, keeping the default profile's progress
+
+DROP TABLE IF EXISTS bookmarks;
+
+CREATE TABLE progress_single (
+    id TEXT PRIMARY KEY,
+    audiobook_id TEXT NOT NULL UNIQUE,
+    position_seconds INTEGER NOT NULL DEFAULT 0,
+    completed BOOLEAN NOT NULL DEFAULT 0,
+    last_played TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+INSERT INTO progress_single (
+    id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
+)
+SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
+FROM progress WHERE profile_id = 'default';
+
+DROP TABLE progress;
+ALTER TABLE progress_single RENAME TO progress;
+CREATE INDEX idx_progress_audiobook_id ON progress(audiobook_id);
+
+ALTER TABLE smart_collections DROP COLUMN profile_id;
+DROP TABLE IF EXISTS profiles;
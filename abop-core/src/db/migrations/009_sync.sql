@@ -0,0 +1,31 @@
+-- Progress sync: content hashes matching audiobooks across devices and the
+-- identity of this device
+
+-- Hash of each audiobook file, reused until its size or modification time changes
+CREATE TABLE audiobook_hashes (
+    audiobook_id TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    -- Modification time in seconds since the Unix epoch
+    modified_secs INTEGER NOT NULL,
+    computed_at TIMESTAMP DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_audiobook_hashes_hash ON audiobook_hashes(content_hash);
+
+-- Event each book's progress was last merged from, per profile; a change
+-- recorded after it is based on that event
+CREATE TABLE sync_heads (
+    profile_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    PRIMARY KEY (profile_id, content_hash),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+
+-- Key-value settings of the sync module, such as the device ID
+CREATE TABLE sync_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
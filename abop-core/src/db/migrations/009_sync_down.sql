@@ -0,0 +1,5 @@
+-- Rollback progress sync tables
+
+DROP TABLE IF EXISTS sync_settings;
+DROP TABLE IF EXISTS sync_heads;
+DROP TABLE IF EXISTS audiobook_hashes;
@@ -0,0 +1,10 @@
+-- Metadata origins: which source each merged audiobook field was taken from
+
+-- Source of a field, e.g. 'opf', 'audiobookshelf', 'text', 'tag' or 'filename'
+CREATE TABLE metadata_origins (
+    audiobook_id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    source TEXT NOT NULL,
+    PRIMARY KEY (audiobook_id, field),
+    FOREIGN KEY (audiobook_id) REFERENCES audiobooks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Rollback metadata origins table
+
+DROP TABLE IF EXISTS metadata_origins;
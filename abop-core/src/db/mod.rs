@@ -3,6 +3,7 @@
 //! This module provides database functionality for storing and retrieving
 //! audiobook metadata and library information.
 
+mod catalog;
 pub mod connection;
 pub mod datetime_serde;
 pub mod error;
@@ -11,6 +12,7 @@ pub mod helpers;
 pub mod mappers;
 mod migrations;
 pub mod operations;
+mod query_builder;
 pub mod repositories;
 pub mod retry;
 pub mod statistics;
@@ -31,17 +33,43 @@ pub use self::helpers::{
 };
 pub use self::mappers::{AudiobookColumnIndices, RowMappers, SqlQueries};
 pub use self::migrations::{Migration, MigrationManager, MigrationResult};
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use self::migrations::run_migrations;
 pub use self::operations::DatabaseOperations;
 pub use self::repositories::{
-    AudiobookRepository, LibraryRepository, ProgressRepository, Repository, RepositoryManager,
+    AudiobookRepository, BookmarkRepository, CollectionRepository, FileHealthRepository, JobCursor,
+    JobRepository, LibraryRepository, MetadataOriginRepository, PersonRepository,
+    ProfileRepository, ProgressRepository, Repository, RepositoryManager, SeriesRepository,
+    SmartCollectionRepository, SyncRepository, TagRepository, WaveformRepository,
 };
 pub use self::retry::{RetryExecutor, RetryPolicy};
 pub use self::statistics::ConnectionStats;
 use crate::{
     error::{AppError, Result},
-    models::{Audiobook, Library},
+    models::{Audiobook, Library, PersonRole},
 };
 
+/// Inserts an audiobook or refreshes the row already stored for its path
+///
+/// A rescan builds fresh [`Audiobook`] values, so the existing row keeps its id
+/// (and with it tags, bookmarks, progress and other child rows) and only the
+/// scanned fields change. Returns the id of the stored row.
+const UPSERT_AUDIOBOOK_BY_PATH: &str = "INSERT INTO audiobooks
+    (id, library_id, path, title, author, narrator, description,
+     duration_seconds, size_bytes, cover_art, created_at, updated_at)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+    ON CONFLICT(path) DO UPDATE SET
+        library_id = excluded.library_id,
+        title = excluded.title,
+        author = excluded.author,
+        narrator = excluded.narrator,
+        description = excluded.description,
+        duration_seconds = excluded.duration_seconds,
+        size_bytes = excluded.size_bytes,
+        cover_art = excluded.cover_art,
+        updated_at = excluded.updated_at
+    RETURNING id";
+
 /// Database connection pool configuration
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -132,10 +160,14 @@ impl Database {
     }
 
     /// Creates a new in-memory database for testing
+    ///
+    /// The database lives in SQLite's `memdb` VFS under a unique name, so the
+    /// repositories, which open their own connections to the same path, see
+    /// the same data as the pool. It disappears when the last connection closes.
     #[must_use = "Database should be used or an error handled"]
     pub fn in_memory() -> Result<Self> {
         let config = PoolConfig {
-            path: ":memory:".to_string(),
+            path: format!("file:/abop-{}?vfs=memdb", uuid::Uuid::new_v4().simple()),
             max_connections: 1,
             ..Default::default()
         };
@@ -263,14 +295,9 @@ impl Database {
         let library_id_clone = library_id.clone();
         self.operations
             .execute(move |conn| {
-                let mut stmt = conn.prepare(
-                    "INSERT OR REPLACE INTO audiobooks 
-                (id, library_id, path, title, author, narrator, description, 
-                 duration_seconds, size_bytes, cover_art, created_at, updated_at) 
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                )?;
+                let mut stmt = conn.prepare(UPSERT_AUDIOBOOK_BY_PATH)?;
 
-                stmt.execute(rusqlite::params![
+                let id: String = stmt.query_row(rusqlite::params![
                     audiobook_clone.id,
                     library_id_clone,
                     audiobook_clone.path.to_string_lossy(),
@@ -283,10 +310,17 @@ impl Database {
                     audiobook_clone.cover_art,
                     audiobook_clone.created_at.to_rfc3339(),
                     audiobook_clone.updated_at.to_rfc3339(),
-                ])
+                ], |row| row.get(0))
                 .map_err(|e| DatabaseError::ExecutionFailed {
                     message: format!("Failed to insert audiobook: {e}"),
                 })?;
+                catalog::sync_audiobook_links(
+                    conn,
+                    &id,
+                    audiobook_clone.title.as_deref(),
+                    audiobook_clone.author.as_deref(),
+                    audiobook_clone.narrator.as_deref(),
+                )?;
 
                 Ok(())
             })
@@ -312,15 +346,11 @@ impl Database {
         // Process each library's audiobooks
         for (library_id, audiobooks_for_library) in library_groups {
             self.operations.execute_transaction(move |tx| {
-                let mut stmt = tx.prepare(
-                    "INSERT OR REPLACE INTO audiobooks 
-                    (id, library_id, path, title, author, narrator, description, 
-                     duration_seconds, size_bytes, cover_art, created_at, updated_at) 
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                )?;
+                let mut stmt = tx.prepare(UPSERT_AUDIOBOOK_BY_PATH)?;
 
+                let mut ids = Vec::with_capacity(audiobooks_for_library.len());
                 for audiobook in &audiobooks_for_library {
-                    stmt.execute(rusqlite::params![
+                    let id: String = stmt.query_row(rusqlite::params![
                         audiobook.id,
                         &library_id,
                         audiobook.path.to_string_lossy(),
@@ -333,12 +363,23 @@ impl Database {
                         &audiobook.cover_art,
                         audiobook.created_at.to_rfc3339(),
                         audiobook.updated_at.to_rfc3339(),
-                    ])
+                    ], |row| row.get(0))
                     .map_err(|e| DatabaseError::ExecutionFailed {
                         message: format!("Failed to insert audiobook: {e}"),
                     })?;
+                    catalog::sync_audiobook_links(
+                        tx,
+                        &id,
+                        audiobook.title.as_deref(),
+                        audiobook.author.as_deref(),
+                        audiobook.narrator.as_deref(),
+                    )?;
+                    ids.push(id);
                 }
 
+                // Keep smart collections current without re-evaluating the whole library
+                repositories::smart_collection::refresh_membership(tx, Some(&ids))?;
+
                 Ok(())
             })?;
         }
@@ -515,6 +556,136 @@ impl Database {
         ProgressRepository::new(Arc::new(EnhancedConnection::with_config(config)))
     }
 
+    /// Get the waveform repository
+    #[must_use]
+    pub fn waveform_repository(&self) -> WaveformRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        WaveformRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the file health repository
+    #[must_use]
+    pub fn file_health_repository(&self) -> FileHealthRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        FileHealthRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the job repository
+    #[must_use]
+    pub fn job_repository(&self) -> JobRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        JobRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the author repository
+    #[must_use]
+    pub fn author_repository(&self) -> PersonRepository {
+        self.person_repository(PersonRole::Author)
+    }
+
+    /// Get the narrator repository
+    #[must_use]
+    pub fn narrator_repository(&self) -> PersonRepository {
+        self.person_repository(PersonRole::Narrator)
+    }
+
+    fn person_repository(&self, role: PersonRole) -> PersonRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        PersonRepository::new(Arc::new(EnhancedConnection::with_config(config)), role)
+    }
+
+    /// Get the series repository
+    #[must_use]
+    pub fn series_repository(&self) -> SeriesRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        SeriesRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the collection repository
+    #[must_use]
+    pub fn collection_repository(&self) -> CollectionRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        CollectionRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the tag repository
+    #[must_use]
+    pub fn tag_repository(&self) -> TagRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        TagRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the smart collection repository
+    #[must_use]
+    pub fn smart_collection_repository(&self) -> SmartCollectionRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        SmartCollectionRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the profile repository
+    #[must_use]
+    pub fn profile_repository(&self) -> ProfileRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        ProfileRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the bookmark repository
+    #[must_use]
+    pub fn bookmark_repository(&self) -> BookmarkRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        BookmarkRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the sync repository
+    #[must_use]
+    pub fn sync_repository(&self) -> SyncRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        SyncRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
+    /// Get the metadata origin repository
+    #[must_use]
+    pub fn metadata_origin_repository(&self) -> MetadataOriginRepository {
+        let config = ConnectionConfig {
+            path: self.db_path.clone(),
+            ..Default::default()
+        };
+        MetadataOriginRepository::new(Arc::new(EnhancedConnection::with_config(config)))
+    }
+
     /// Opens a database at the specified path
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         let config = PoolConfig {
@@ -1,5 +1,6 @@
 SELECT 
     id,
+    profile_id,
     audiobook_id,
     position_seconds,
     completed,
@@ -9,4 +10,5 @@ SELECT
 FROM 
     progress 
 WHERE 
-    audiobook_id = ?1
+    profile_id = ?1
+    AND audiobook_id = ?2
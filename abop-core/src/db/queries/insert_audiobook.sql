@@ -1,4 +1,4 @@
-INSERT OR REPLACE INTO audiobooks (
+INSERT INTO audiobooks (
     id, 
     library_id, 
     path, 
@@ -13,6 +13,16 @@ INSERT OR REPLACE INTO audiobooks (
     updated_at
 ) VALUES (
     ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
-    COALESCE((SELECT created_at FROM audiobooks WHERE id = ?1), CURRENT_TIMESTAMP),
+    CURRENT_TIMESTAMP,
     CURRENT_TIMESTAMP
 )
+ON CONFLICT(path) DO UPDATE SET
+    library_id = excluded.library_id,
+    title = excluded.title,
+    author = excluded.author,
+    narrator = excluded.narrator,
+    description = excluded.description,
+    duration_seconds = excluded.duration_seconds,
+    size_bytes = excluded.size_bytes,
+    cover_art = excluded.cover_art,
+    updated_at = CURRENT_TIMESTAMP
@@ -1,5 +1,6 @@
 INSERT INTO progress (
     id,
+    profile_id,
     audiobook_id,
     position_seconds,
     completed,
@@ -7,11 +8,10 @@ INSERT INTO progress (
     created_at,
     updated_at
 ) VALUES (
-    ?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+    ?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
 )
-ON CONFLICT(audiobook_id) DO UPDATE SET
+ON CONFLICT(profile_id, audiobook_id) DO UPDATE SET
     position_seconds = excluded.position_seconds,
     completed = excluded.completed,
     last_played = excluded.last_played,
     updated_at = CURRENT_TIMESTAMP
-WHERE audiobook_id = ?2
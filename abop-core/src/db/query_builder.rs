@@ -0,0 +1,292 @@
+//! Parameterized SQL for smart collection rules and searches
+//!
+//! Values are never formatted into SQL text: every one is bound as a numbered
+//! parameter, and column names only come from the fixed mapping of
+//! [`RuleField`] below. Compiled rules expect the audiobook as `a` and its
+//! progress row as `p`, which [`rule_source`] provides.
+
+use rusqlite::types::Value;
+
+use super::error::{DatabaseError, DbResult};
+use crate::models::{Condition, Rule, RuleField, RuleOperator, RuleValue};
+
+/// `FROM` clause that compiled rules are evaluated against
+///
+/// Progress is per listener profile, so `profile` is the placeholder of the
+/// bound profile ID whose progress the rule sees.
+pub fn rule_source(profile: &str) -> String {
+    format!(
+        "FROM audiobooks a
+         LEFT JOIN progress p ON p.audiobook_id = a.id AND p.profile_id = {profile}"
+    )
+}
+
+/// Tags of the audiobook `a`
+const AUDIOBOOK_TAGS: &str = "SELECT 1 FROM audiobook_tags l JOIN tags t ON t.id = l.tag_id
+     WHERE l.audiobook_id = a.id";
+
+/// Wrap text in `%` for a `LIKE ... ESCAPE '\'` match, escaping wildcards in it
+pub fn contains_pattern(text: &str) -> String {
+    format!("%{}%", escape_like(text))
+}
+
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// SQL expression for a field; the tag field has none as it is a subquery
+const fn column(field: RuleField) -> &'static str {
+    match field {
+        RuleField::Title => "a.title",
+        RuleField::Author => "a.author",
+        RuleField::Narrator => "a.narrator",
+        RuleField::Description => "a.description",
+        RuleField::Path => "a.path",
+        RuleField::Library => "a.library_id",
+        RuleField::Duration => "a.duration_seconds",
+        RuleField::Size => "a.size_bytes",
+        RuleField::Added => "a.created_at",
+        RuleField::Position => "COALESCE(p.position_seconds, 0)",
+        RuleField::Completed => "COALESCE(p.completed, 0)",
+        RuleField::LastPlayed => "p.last_played",
+        RuleField::Tag => "",
+    }
+}
+
+fn to_integer(value: u64) -> Value {
+    Value::Integer(i64::try_from(value).unwrap_or(i64::MAX))
+}
+
+/// Collects bound parameters while SQL fragments are built
+#[derive(Debug, Default)]
+pub struct QueryBuilder {
+    params: Vec<Value>,
+}
+
+impl QueryBuilder {
+    /// Create a builder with no parameters
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a value and return its placeholder
+    pub fn bind(&mut self, value: impl Into<Value>) -> String {
+        self.params.push(value.into());
+        format!("?{}", self.params.len())
+    }
+
+    /// Values bound so far, in placeholder order
+    #[must_use]
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+
+    /// Take the bound values, in placeholder order
+    #[must_use]
+    pub fn into_params(self) -> Vec<Value> {
+        self.params
+    }
+
+    /// Compile a rule to a boolean SQL expression
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if a condition combines a
+    /// field with an operator or value it does not support.
+    pub fn compile_rule(&mut self, rule: &Rule) -> DbResult<String> {
+        match rule {
+            Rule::All(rules) if rules.is_empty() => Ok("1".to_string()),
+            Rule::Any(rules) if rules.is_empty() => Ok("0".to_string()),
+            Rule::All(rules) => self.compile_group(rules, " AND "),
+            Rule::Any(rules) => self.compile_group(rules, " OR "),
+            Rule::Condition(condition) => {
+                condition
+                    .validate()
+                    .map_err(|e| DatabaseError::validation_failed("rule", &e.to_string()))?;
+                Ok(self.compile_condition(condition))
+            }
+        }
+    }
+
+    fn compile_group(&mut self, rules: &[Rule], separator: &str) -> DbResult<String> {
+        let parts = rules
+            .iter()
+            .map(|rule| self.compile_rule(rule))
+            .collect::<DbResult<Vec<_>>>()?;
+        Ok(format!("({})", parts.join(separator)))
+    }
+
+    /// Compile a condition that has already been validated
+    fn compile_condition(&mut self, condition: &Condition) -> String {
+        let Condition {
+            field,
+            operator,
+            value,
+        } = condition;
+        let col = column(*field);
+
+        if *field == RuleField::Tag {
+            return match (operator, value) {
+                (RuleOperator::IsSet, _) => {
+                    "EXISTS (SELECT 1 FROM audiobook_tags l WHERE l.audiobook_id = a.id)"
+                        .to_string()
+                }
+                (RuleOperator::IsNotSet, _) => {
+                    "NOT EXISTS (SELECT 1 FROM audiobook_tags l WHERE l.audiobook_id = a.id)"
+                        .to_string()
+                }
+                (operator, RuleValue::Text(name)) => {
+                    let placeholder = self.bind(name.trim().to_string());
+                    let negate = if *operator == RuleOperator::NotEquals {
+                        "NOT "
+                    } else {
+                        ""
+                    };
+                    format!("{negate}EXISTS ({AUDIOBOOK_TAGS} AND t.name = {placeholder})")
+                }
+                _ => "0".to_string(),
+            };
+        }
+
+        match (operator, value) {
+            (RuleOperator::IsSet, _) => match field {
+                RuleField::Duration | RuleField::Size | RuleField::Position => {
+                    format!("COALESCE({col}, 0) > 0")
+                }
+                RuleField::Added | RuleField::LastPlayed => format!("{col} IS NOT NULL"),
+                _ => format!("COALESCE({col}, '') <> ''"),
+            },
+            (RuleOperator::IsNotSet, _) => match field {
+                RuleField::Duration | RuleField::Size | RuleField::Position => {
+                    format!("COALESCE({col}, 0) = 0")
+                }
+                RuleField::Added | RuleField::LastPlayed => format!("{col} IS NULL"),
+                _ => format!("COALESCE({col}, '') = ''"),
+            },
+            (RuleOperator::WithinDays, RuleValue::Number(days)) => {
+                let modifier = self.bind(format!("-{days} days"));
+                format!("julianday({col}) >= julianday('now', {modifier})")
+            }
+            (operator, RuleValue::Date(date)) => {
+                // A date covers the whole day, so "after" starts the next one
+                let day = self.bind(date.format("%Y-%m-%d").to_string());
+                let (op, bound) = match operator {
+                    RuleOperator::GreaterThan => (">=", format!("julianday({day}, '+1 day')")),
+                    RuleOperator::AtLeast => (">=", format!("julianday({day})")),
+                    RuleOperator::LessThan => ("<", format!("julianday({day})")),
+                    _ => ("<", format!("julianday({day}, '+1 day')")),
+                };
+                format!("julianday({col}) {op} {bound}")
+            }
+            (operator, RuleValue::Text(text)) => match operator {
+                RuleOperator::Equals => {
+                    format!("{col} = {} COLLATE NOCASE", self.bind(text.clone()))
+                }
+                RuleOperator::NotEquals => format!(
+                    "({col} IS NULL OR {col} <> {} COLLATE NOCASE)",
+                    self.bind(text.clone())
+                ),
+                RuleOperator::Contains => {
+                    format!(
+                        "{col} LIKE {} ESCAPE '\\'",
+                        self.bind(contains_pattern(text))
+                    )
+                }
+                RuleOperator::NotContains => format!(
+                    "({col} IS NULL OR {col} NOT LIKE {} ESCAPE '\\')",
+                    self.bind(contains_pattern(text))
+                ),
+                _ => format!(
+                    "{col} LIKE {} ESCAPE '\\'",
+                    self.bind(format!("{}%", escape_like(text)))
+                ),
+            },
+            (operator, RuleValue::Number(number)) => {
+                let placeholder = self.bind(to_integer(*number));
+                match operator {
+                    RuleOperator::NotEquals => {
+                        format!("({col} IS NULL OR {col} <> {placeholder})")
+                    }
+                    operator => {
+                        let op = match operator {
+                            RuleOperator::GreaterThan => ">",
+                            RuleOperator::AtLeast => ">=",
+                            RuleOperator::LessThan => "<",
+                            RuleOperator::AtMost => "<=",
+                            _ => "=",
+                        };
+                        format!("{col} {op} {placeholder}")
+                    }
+                }
+            }
+            (operator, RuleValue::Bool(value)) => {
+                let placeholder = self.bind(i64::from(*value));
+                let op = if *operator == RuleOperator::NotEquals {
+                    "<>"
+                } else {
+                    "="
+                };
+                format!("{col} {op} {placeholder}")
+            }
+            (_, RuleValue::None) => "0".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_binds_every_value() {
+        let rule = Rule::parse(
+            r#"completed = false and (narrator contains "50%_off" or tag != "it's")
+               and added within 30d"#,
+        )
+        .unwrap();
+        let mut builder = QueryBuilder::new();
+        let sql = builder.compile_rule(&rule).unwrap();
+
+        assert!(!sql.contains("50%"));
+        assert!(!sql.contains("it's"));
+        assert!(sql.starts_with('(') && sql.contains(" OR "));
+        assert_eq!(
+            builder.params(),
+            [
+                Value::Integer(0),
+                Value::Text("%50\\%\\_off%".to_string()),
+                Value::Text("it's".to_string()),
+                Value::Text("-30 days".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_empty_groups() {
+        let mut builder = QueryBuilder::new();
+        assert_eq!(builder.compile_rule(&Rule::All(Vec::new())).unwrap(), "1");
+        assert_eq!(builder.compile_rule(&Rule::Any(Vec::new())).unwrap(), "0");
+        assert!(builder.params().is_empty());
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_condition() {
+        let rule = Rule::Condition(Condition {
+            field: RuleField::Duration,
+            operator: RuleOperator::Contains,
+            value: RuleValue::Text("10h".to_string()),
+        });
+        assert!(matches!(
+            QueryBuilder::new().compile_rule(&rule),
+            Err(DatabaseError::ValidationFailed { .. })
+        ));
+    }
+}
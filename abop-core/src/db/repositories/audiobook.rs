@@ -10,10 +10,12 @@ use super::super::error::{DatabaseError, DbResult};
 use super::{EnhancedRepository, Repository, RepositoryBase};
 use crate::db::{
     EnhancedConnection,
+    catalog::{JOINED_AUDIOBOOK_COLUMNS, map_audiobook, sync_audiobook_links},
     datetime_serde::{SqliteDateTime, datetime_to_sql},
+    query_builder::{QueryBuilder, contains_pattern},
 };
 use crate::error::{AppError, Result};
-use crate::models::Audiobook;
+use crate::models::{Audiobook, DEFAULT_PROFILE_ID, SearchQuery};
 
 /// Repository for audiobook-related database operations
 pub struct AudiobookRepository {
@@ -72,7 +74,13 @@ impl AudiobookRepository {
                     audiobook.selected,
                 ],
             )?;
-            Ok(())
+            sync_audiobook_links(
+                conn,
+                &audiobook.id,
+                audiobook.title.as_deref(),
+                audiobook.author.as_deref(),
+                audiobook.narrator.as_deref(),
+            )
         })
         .map_err(Into::into)
     }
@@ -470,10 +478,71 @@ impl AudiobookRepository {
                     selected,
                 ],
             )?;
+            if rows_affected > 0 {
+                sync_audiobook_links(
+                    conn,
+                    &id,
+                    title.as_deref(),
+                    author.as_deref(),
+                    narrator.as_deref(),
+                )?;
+            }
             Ok(rows_affected > 0)
         })
     }
 
+    /// Change the paths of several audiobooks in one transaction
+    ///
+    /// Either every path is updated or none is. Rows are keyed by ID, so
+    /// progress and other data referencing the audiobooks is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if an update fails, for example on a duplicate
+    /// path, or if one of the audiobooks doesn't exist.
+    pub fn update_paths(&self, paths: &[(String, PathBuf)]) -> DbResult<usize> {
+        let paths: Vec<(String, String)> = paths
+            .iter()
+            .map(|(id, path)| (id.clone(), path.to_string_lossy().to_string()))
+            .collect();
+        self.execute_transaction(move |tx| {
+            let mut stmt = tx.prepare("UPDATE audiobooks SET path = ?2 WHERE id = ?1")?;
+            let mut updated = 0;
+            for (id, path) in &paths {
+                updated += stmt.execute(rusqlite::params![id, path])?;
+            }
+            if updated != paths.len() {
+                // An unknown ID fails the transaction so that it is rolled back
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Replace the persisted selection with the given audiobooks
+    ///
+    /// The `selected` flag is cleared on every other audiobook, so commands
+    /// that work on the selection see what is selected in the GUI. Unknown
+    /// IDs are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn set_selection(&self, ids: &[String]) -> DbResult<usize> {
+        let ids = ids.to_vec();
+        self.execute_transaction(move |tx| {
+            tx.execute("UPDATE audiobooks SET selected = 0 WHERE selected = 1", [])?;
+            let mut stmt = tx.prepare("UPDATE audiobooks SET selected = 1 WHERE id = ?1")?;
+            let mut selected = 0;
+            for id in &ids {
+                selected += stmt.execute([id])?;
+            }
+            Ok(selected)
+        })
+    }
+
     /// Delete an audiobook by its ID
     ///
     /// # Errors
@@ -536,6 +605,88 @@ impl AudiobookRepository {
             Ok(exists)
         })
     }
+
+    /// Find audiobooks matching a search query
+    ///
+    /// The query text matches title, author, narrator or description. Results
+    /// are ordered by title, or by position when filtering by collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<Audiobook>> {
+        let mut sql = format!("SELECT {JOINED_AUDIOBOOK_COLUMNS} FROM audiobooks a");
+        let mut conditions = Vec::new();
+        let mut builder = QueryBuilder::new();
+
+        if let Some(collection_id) = &query.collection_id {
+            sql.push_str(" JOIN collection_items ci ON ci.audiobook_id = a.id");
+            conditions.push(format!(
+                "ci.collection_id = {}",
+                builder.bind(collection_id.clone())
+            ));
+        }
+        let text = query.query.trim();
+        if !text.is_empty() {
+            let n = builder.bind(contains_pattern(text));
+            conditions.push(format!(
+                "(a.title LIKE {n} ESCAPE '\\' OR a.author LIKE {n} ESCAPE '\\'
+                  OR a.narrator LIKE {n} ESCAPE '\\' OR a.description LIKE {n} ESCAPE '\\')"
+            ));
+        }
+        if let Some(library_id) = &query.library_id {
+            conditions.push(format!("a.library_id = {}", builder.bind(library_id.clone())));
+        }
+        for (column, value) in [("a.author", &query.author), ("a.narrator", &query.narrator)] {
+            if let Some(value) = value {
+                let placeholder = builder.bind(contains_pattern(value));
+                conditions.push(format!("{column} LIKE {placeholder} ESCAPE '\\'"));
+            }
+        }
+        for (operator, seconds) in [(">=", query.min_duration), ("<=", query.max_duration)] {
+            if let Some(seconds) = seconds {
+                let placeholder = builder.bind(i64::try_from(seconds).unwrap_or(i64::MAX));
+                conditions.push(format!("a.duration_seconds {operator} {placeholder}"));
+            }
+        }
+        if let Some(tag) = &query.tag {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM audiobook_tags l JOIN tags t ON t.id = l.tag_id
+                         WHERE l.audiobook_id = a.id AND t.name = {})",
+                builder.bind(tag.trim().to_string())
+            ));
+        }
+        if !query.include_completed {
+            let profile = query.profile_id.as_deref().unwrap_or(DEFAULT_PROFILE_ID);
+            conditions.push(format!(
+                "NOT EXISTS (SELECT 1 FROM progress p WHERE p.audiobook_id = a.id
+                             AND p.profile_id = {} AND p.completed = 1)",
+                builder.bind(profile.to_string())
+            ));
+        }
+
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(if query.collection_id.is_some() {
+            " ORDER BY ci.position"
+        } else {
+            " ORDER BY a.title COLLATE NOCASE"
+        });
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+
+        let params = builder.into_params();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let audiobooks = stmt.query_map(rusqlite::params_from_iter(&params), map_audiobook)?;
+            audiobooks.collect()
+        })
+        .map_err(AppError::from)
+    }
 }
 
 impl RepositoryBase for AudiobookRepository {
@@ -676,3 +676,131 @@ fn test_repository_basic_operations() {
         "Should handle special characters in ID"
     );
 }
+
+#[test]
+fn test_update_paths_is_atomic() {
+    let (repo, _temp_file) = setup_test_db();
+    let first = create_test_audiobook("test-library-1", "/test/library/path/a.mp3");
+    let second = create_test_audiobook("test-library-1", "/test/library/path/b.mp3");
+    repo.upsert(&first)
+        .expect("Failed to insert first audiobook");
+    repo.upsert(&second)
+        .expect("Failed to insert second audiobook");
+
+    let moved = vec![
+        (
+            first.id.clone(),
+            PathBuf::from("/test/library/path/A/a.mp3"),
+        ),
+        (
+            second.id.clone(),
+            PathBuf::from("/test/library/path/B/b.mp3"),
+        ),
+    ];
+    assert_eq!(repo.update_paths(&moved).expect("update_paths failed"), 2);
+    let stored = repo.find_by_id(&first.id).unwrap().unwrap();
+    assert_eq!(stored.path, PathBuf::from("/test/library/path/A/a.mp3"));
+
+    // An unknown ID rolls back the whole batch
+    let failing = vec![
+        (first.id.clone(), PathBuf::from("/test/library/path/a.mp3")),
+        (
+            "missing".to_string(),
+            PathBuf::from("/test/library/path/c.mp3"),
+        ),
+    ];
+    assert!(repo.update_paths(&failing).is_err());
+    let stored = repo.find_by_id(&first.id).unwrap().unwrap();
+    assert_eq!(stored.path, PathBuf::from("/test/library/path/A/a.mp3"));
+}
+
+#[test]
+fn test_set_selection_replaces_selected_flags() {
+    let (repo, _temp_file) = setup_test_db();
+    let mut ids = Vec::new();
+    for name in ["a", "b", "c"] {
+        let audiobook =
+            create_test_audiobook("test-library-1", &format!("/test/library/path/{name}.mp3"));
+        repo.upsert(&audiobook).expect("Failed to insert audiobook");
+        ids.push(audiobook.id);
+    }
+    let selected = |repo: &AudiobookRepository| -> Vec<String> {
+        let mut selected: Vec<String> = repo
+            .find_all()
+            .unwrap()
+            .into_iter()
+            .filter(|audiobook| audiobook.selected)
+            .map(|audiobook| audiobook.id)
+            .collect();
+        selected.sort();
+        selected
+    };
+
+    assert_eq!(repo.set_selection(&ids[..2]).unwrap(), 2);
+    let mut expected = ids[..2].to_vec();
+    expected.sort();
+    assert_eq!(selected(&repo), expected);
+
+    // A new selection clears the old one and ignores unknown IDs
+    assert_eq!(
+        repo.set_selection(&[ids[2].clone(), "missing".to_string()])
+            .unwrap(),
+        1
+    );
+    assert_eq!(selected(&repo), vec![ids[2].clone()]);
+}
+
+#[test]
+fn test_search_with_collection_and_tag_filters() {
+    use crate::db::repositories::{CollectionRepository, TagRepository};
+    use crate::models::SearchQuery;
+
+    let (repo, _temp_file) = setup_test_db();
+    let mut ids = Vec::new();
+    for (title, author) in [
+        ("Dune", "Frank Herbert"),
+        ("Emma", "Jane Austen"),
+        ("100%_Done", "Test Author"),
+    ] {
+        let mut audiobook =
+            create_test_audiobook("test-library-1", &format!("/test/library/path/{title}.mp3"));
+        audiobook.title = Some(title.to_string());
+        audiobook.author = Some(author.to_string());
+        repo.upsert(&audiobook).expect("Failed to insert audiobook");
+        ids.push(audiobook.id);
+    }
+
+    let titles = |query: &SearchQuery| -> Vec<String> {
+        repo.search(query)
+            .expect("Search failed")
+            .into_iter()
+            .filter_map(|audiobook| audiobook.title)
+            .collect()
+    };
+    assert_eq!(titles(&SearchQuery::new("austen")), ["Emma"]);
+    // Wildcards in the query text match literally
+    assert_eq!(titles(&SearchQuery::new("%_")), ["100%_Done"]);
+    assert_eq!(titles(&SearchQuery::new("").limit(2)).len(), 2);
+
+    let collections = CollectionRepository::new(repo.connect().clone());
+    let queue = collections.create("Commute queue", None).unwrap();
+    collections
+        .add_audiobooks(&queue.id, &[ids[1].clone(), ids[0].clone()])
+        .unwrap();
+    assert_eq!(
+        titles(&SearchQuery::new("").in_collection(&queue.id)),
+        ["Emma", "Dune"]
+    );
+
+    TagRepository::new(repo.connect().clone())
+        .tag_audiobooks("Classic", &ids[1..2])
+        .unwrap();
+    assert_eq!(
+        titles(
+            &SearchQuery::new("e")
+                .in_collection(&queue.id)
+                .tagged("classic")
+        ),
+        ["Emma"]
+    );
+}
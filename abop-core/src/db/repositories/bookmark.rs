@@ -0,0 +1,133 @@
+//! Bookmark repository for database operations
+//!
+//! Bookmarks belong to a listener profile, so each person only sees their own.
+
+use rusqlite::{Row, params};
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::datetime_serde::SqliteDateTime;
+use crate::models::Bookmark;
+
+const SELECT_COLUMNS: &str = "SELECT id, profile_id, audiobook_id, position_seconds, name, note,
+        created_at
+     FROM bookmarks";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Bookmark> {
+    let created_at: SqliteDateTime = row.get(6)?;
+    Ok(Bookmark {
+        id: row.get(0)?,
+        profile_id: row.get(1)?,
+        audiobook_id: row.get(2)?,
+        position_seconds: row.get(3)?,
+        name: row.get(4)?,
+        note: row.get(5)?,
+        created_at: created_at.into(),
+    })
+}
+
+/// Repository for profile-scoped bookmarks
+pub struct BookmarkRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl BookmarkRepository {
+    /// Create a new bookmark repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Save a bookmark
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the profile or audiobook does not exist.
+    pub fn add(&self, bookmark: &Bookmark) -> DbResult<()> {
+        let bookmark = bookmark.clone();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT INTO bookmarks
+                    (id, profile_id, audiobook_id, position_seconds, name, note, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    &bookmark.id,
+                    &bookmark.profile_id,
+                    &bookmark.audiobook_id,
+                    bookmark.position_seconds,
+                    &bookmark.name,
+                    &bookmark.note,
+                    SqliteDateTime::from(bookmark.created_at),
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Find a profile's bookmarks in an audiobook, ordered by position
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_audiobook(
+        &self,
+        profile_id: &str,
+        audiobook_id: &str,
+    ) -> DbResult<Vec<Bookmark>> {
+        let profile_id = profile_id.to_string();
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} WHERE profile_id = ?1 AND audiobook_id = ?2
+                 ORDER BY position_seconds"
+            ))?;
+            let bookmarks = stmt.query_map([&profile_id, &audiobook_id], map_row)?;
+            bookmarks.collect()
+        })
+    }
+
+    /// Find all bookmarks of a profile, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_profile(&self, profile_id: &str) -> DbResult<Vec<Bookmark>> {
+        let profile_id = profile_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} WHERE profile_id = ?1 ORDER BY created_at DESC"
+            ))?;
+            let bookmarks = stmt.query_map([&profile_id], map_row)?;
+            bookmarks.collect()
+        })
+    }
+
+    /// Delete a bookmark
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete(&self, id: &str) -> DbResult<bool> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let deleted = conn.execute("DELETE FROM bookmarks WHERE id = ?1", [&id])?;
+            Ok(deleted > 0)
+        })
+    }
+}
+
+impl RepositoryBase for BookmarkRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for BookmarkRepository {}
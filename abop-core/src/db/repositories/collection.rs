@@ -0,0 +1,467 @@
+//! Collection repository for database operations
+//!
+//! Collections are ordered: every audiobook has a position, new audiobooks
+//! are appended, and positions are kept contiguous when books are removed or
+//! moved.
+
+use chrono::Utc;
+use rusqlite::{OptionalExtension, Row, Transaction, params};
+use std::sync::Arc;
+
+use super::super::error::{DatabaseError, DbResult};
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::catalog::{JOINED_AUDIOBOOK_COLUMNS, map_audiobook};
+use crate::db::datetime_serde::SqliteDateTime;
+use crate::models::{Audiobook, Collection};
+
+const SELECT_COLUMNS: &str = "SELECT c.id, c.name, c.description,
+        (SELECT COUNT(*) FROM collection_items i WHERE i.collection_id = c.id),
+        c.created_at, c.updated_at
+     FROM collections c";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Collection> {
+    let created_at: SqliteDateTime = row.get(4)?;
+    let updated_at: SqliteDateTime = row.get(5)?;
+    Ok(Collection {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        audiobook_count: row.get(3)?,
+        created_at: created_at.into(),
+        updated_at: updated_at.into(),
+    })
+}
+
+/// Append audiobooks to the end of a collection, skipping ones already in it
+fn append_items(
+    tx: &Transaction<'_>,
+    collection_id: &str,
+    ids: &[String],
+) -> rusqlite::Result<usize> {
+    let mut next: i64 = tx.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM collection_items WHERE collection_id = ?1",
+        [collection_id],
+        |row| row.get(0),
+    )?;
+    let mut insert = tx.prepare(
+        "INSERT OR IGNORE INTO collection_items (collection_id, audiobook_id, position)
+         VALUES (?1, ?2, ?3)",
+    )?;
+    let mut added = 0;
+    for id in ids {
+        if insert.execute(params![collection_id, id, next])? > 0 {
+            next += 1;
+            added += 1;
+        }
+    }
+    touch(tx, collection_id)?;
+    Ok(added)
+}
+
+/// Renumber the positions of a collection from zero, keeping their order
+fn compact_positions(tx: &Transaction<'_>, collection_id: &str) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE collection_items SET position = (
+            SELECT COUNT(*) FROM collection_items other
+            WHERE other.collection_id = collection_items.collection_id
+              AND other.position < collection_items.position
+         )
+         WHERE collection_id = ?1",
+        [collection_id],
+    )?;
+    touch(tx, collection_id)
+}
+
+fn touch(tx: &Transaction<'_>, collection_id: &str) -> rusqlite::Result<()> {
+    tx.execute(
+        "UPDATE collections SET updated_at = ?2 WHERE id = ?1",
+        params![collection_id, SqliteDateTime::from(Utc::now())],
+    )?;
+    Ok(())
+}
+
+fn validate_name(name: &str) -> DbResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::validation_failed(
+            "name",
+            "Collection name cannot be empty",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Repository for user-defined collections
+pub struct CollectionRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl CollectionRepository {
+    /// Create a new collection repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Create an empty collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn create(&self, name: &str, description: Option<&str>) -> DbResult<Collection> {
+        let now = Utc::now();
+        let collection = Collection {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: validate_name(name)?,
+            description: description.map(str::to_string),
+            audiobook_count: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        let row = collection.clone();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT INTO collections (id, name, description, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    &row.id,
+                    &row.name,
+                    &row.description,
+                    SqliteDateTime::from(row.created_at),
+                    SqliteDateTime::from(row.updated_at),
+                ],
+            )?;
+            Ok(())
+        })?;
+        Ok(collection)
+    }
+
+    /// Find all collections, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<Collection>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!("{SELECT_COLUMNS} ORDER BY c.name"))?;
+            let collections = stmt.query_map([], map_row)?;
+            collections.collect()
+        })
+    }
+
+    /// Find a collection by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_id(&self, id: &str) -> DbResult<Option<Collection>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(&format!("{SELECT_COLUMNS} WHERE c.id = ?1"), [&id], map_row)
+                .optional()
+        })
+    }
+
+    /// Find a collection by name, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<Collection>> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!("{SELECT_COLUMNS} WHERE c.name = ?1"),
+                [&name],
+                map_row,
+            )
+            .optional()
+        })
+    }
+
+    /// Find the collections an audiobook belongs to, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_audiobook(&self, audiobook_id: &str) -> DbResult<Vec<Collection>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS}
+                 WHERE EXISTS (SELECT 1 FROM collection_items i
+                               WHERE i.collection_id = c.id AND i.audiobook_id = ?1)
+                 ORDER BY c.name"
+            ))?;
+            let collections = stmt.query_map([&audiobook_id], map_row)?;
+            collections.collect()
+        })
+    }
+
+    /// Find the audiobooks of a collection in collection order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_audiobooks(&self, collection_id: &str) -> DbResult<Vec<Audiobook>> {
+        let collection_id = collection_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {JOINED_AUDIOBOOK_COLUMNS} FROM audiobooks a
+                 JOIN collection_items i ON i.audiobook_id = a.id
+                 WHERE i.collection_id = ?1
+                 ORDER BY i.position"
+            ))?;
+            let audiobooks = stmt.query_map([&collection_id], map_audiobook)?;
+            audiobooks.collect()
+        })
+    }
+
+    /// Rename a collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn rename(&self, id: &str, name: &str) -> DbResult<bool> {
+        let id = id.to_string();
+        let name = validate_name(name)?;
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            let renamed = conn.execute(
+                "UPDATE collections SET name = ?2, updated_at = ?3 WHERE id = ?1",
+                params![&id, &name, updated_at],
+            )?;
+            Ok(renamed > 0)
+        })
+    }
+
+    /// Delete a collection; its audiobooks are not affected
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete(&self, id: &str) -> DbResult<bool> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let deleted = conn.execute("DELETE FROM collections WHERE id = ?1", [&id])?;
+            Ok(deleted > 0)
+        })
+    }
+
+    /// Append audiobooks to a collection
+    ///
+    /// Audiobooks already in the collection keep their position. Returns the
+    /// number of audiobooks added.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. an audiobook does not exist.
+    pub fn add_audiobooks(&self, collection_id: &str, audiobook_ids: &[String]) -> DbResult<usize> {
+        let collection_id = collection_id.to_string();
+        let audiobook_ids = audiobook_ids.to_vec();
+        self.execute_transaction(move |tx| append_items(tx, &collection_id, &audiobook_ids))
+    }
+
+    /// Append every selected audiobook to a collection, ordered by title
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn add_selected(&self, collection_id: &str) -> DbResult<usize> {
+        let collection_id = collection_id.to_string();
+        self.execute_transaction(move |tx| {
+            let ids = {
+                let mut stmt = tx.prepare(
+                    "SELECT id FROM audiobooks WHERE selected = 1 ORDER BY title COLLATE NOCASE",
+                )?;
+                stmt.query_map([], |row| row.get(0))?
+                    .collect::<rusqlite::Result<Vec<String>>>()?
+            };
+            append_items(tx, &collection_id, &ids)
+        })
+    }
+
+    /// Remove audiobooks from a collection
+    ///
+    /// Returns the number of audiobooks removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn remove_audiobooks(
+        &self,
+        collection_id: &str,
+        audiobook_ids: &[String],
+    ) -> DbResult<usize> {
+        let collection_id = collection_id.to_string();
+        let audiobook_ids = audiobook_ids.to_vec();
+        self.execute_transaction(move |tx| {
+            let mut delete = tx.prepare(
+                "DELETE FROM collection_items WHERE collection_id = ?1 AND audiobook_id = ?2",
+            )?;
+            let mut removed = 0;
+            for id in &audiobook_ids {
+                removed += delete.execute(params![&collection_id, id])?;
+            }
+            compact_positions(tx, &collection_id)?;
+            Ok(removed)
+        })
+    }
+
+    /// Remove every selected audiobook from a collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn remove_selected(&self, collection_id: &str) -> DbResult<usize> {
+        let collection_id = collection_id.to_string();
+        self.execute_transaction(move |tx| {
+            let removed = tx.execute(
+                "DELETE FROM collection_items WHERE collection_id = ?1
+                 AND audiobook_id IN (SELECT id FROM audiobooks WHERE selected = 1)",
+                [&collection_id],
+            )?;
+            compact_positions(tx, &collection_id)?;
+            Ok(removed)
+        })
+    }
+
+    /// Move an audiobook to a new position within a collection
+    ///
+    /// Positions past the end move the audiobook to the end. Returns `false`
+    /// if the audiobook is not in the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn move_audiobook(
+        &self,
+        collection_id: &str,
+        audiobook_id: &str,
+        position: usize,
+    ) -> DbResult<bool> {
+        let collection_id = collection_id.to_string();
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_transaction(move |tx| {
+            let mut order: Vec<String> = {
+                let mut stmt = tx.prepare(
+                    "SELECT audiobook_id FROM collection_items
+                     WHERE collection_id = ?1 ORDER BY position",
+                )?;
+                stmt.query_map([&collection_id], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?
+            };
+            let Some(current) = order.iter().position(|id| *id == audiobook_id) else {
+                return Ok(false);
+            };
+            let moved = order.remove(current);
+            order.insert(position.min(order.len()), moved);
+
+            let mut update = tx.prepare(
+                "UPDATE collection_items SET position = ?3
+                 WHERE collection_id = ?1 AND audiobook_id = ?2",
+            )?;
+            for (position, id) in order.iter().enumerate() {
+                update.execute(params![&collection_id, id, position])?;
+            }
+            touch(tx, &collection_id)?;
+            Ok(true)
+        })
+    }
+}
+
+impl RepositoryBase for CollectionRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for CollectionRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::AudiobookRepository;
+    use crate::test_utils::TestDatabase;
+
+    fn setup() -> (TestDatabase, Vec<String>) {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        let ids = ["A", "B", "C", "D"]
+            .iter()
+            .map(|title| {
+                let mut audiobook = Audiobook::new(&db.library_id, format!("/books/{title}.mp3"));
+                audiobook.title = Some((*title).to_string());
+                audiobook.selected = matches!(*title, "B" | "D");
+                audiobooks.upsert(&audiobook).unwrap();
+                audiobook.id
+            })
+            .collect();
+        (db, ids)
+    }
+
+    fn order(repo: &CollectionRepository, id: &str) -> Vec<String> {
+        repo.find_audiobooks(id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|audiobook| audiobook.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_collection_keeps_order() {
+        let (db, ids) = setup();
+        let repo = CollectionRepository::new(db.connection.clone());
+        let queue = repo.create("Commute queue", None).unwrap();
+        assert!(repo.create("commute QUEUE", None).is_err());
+        assert!(repo.create("  ", None).is_err());
+
+        assert_eq!(
+            repo.add_audiobooks(&queue.id, &[ids[2].clone(), ids[0].clone()])
+                .unwrap(),
+            2
+        );
+        // Already present books are skipped, selected ones appended by title
+        assert_eq!(repo.add_selected(&queue.id).unwrap(), 2);
+        assert_eq!(repo.add_audiobooks(&queue.id, &ids[..1]).unwrap(), 0);
+        assert_eq!(order(&repo, &queue.id), ["C", "A", "B", "D"]);
+
+        assert!(repo.move_audiobook(&queue.id, &ids[3], 0).unwrap());
+        assert_eq!(order(&repo, &queue.id), ["D", "C", "A", "B"]);
+
+        assert_eq!(
+            repo.remove_audiobooks(&queue.id, &[ids[2].clone()])
+                .unwrap(),
+            1
+        );
+        assert!(repo.move_audiobook(&queue.id, &ids[0], 99).unwrap());
+        assert_eq!(order(&repo, &queue.id), ["D", "B", "A"]);
+
+        assert_eq!(repo.remove_selected(&queue.id).unwrap(), 2);
+        let found = repo.find_by_name("COMMUTE queue").unwrap().unwrap();
+        assert_eq!(found.audiobook_count, 1);
+        assert_eq!(repo.find_for_audiobook(&ids[0]).unwrap().len(), 1);
+
+        assert!(repo.delete(&queue.id).unwrap());
+        assert!(repo.find_all().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+//! File health repository for database operations
+//!
+//! This module stores the outcome of full-decode integrity verification per
+//! audiobook so corrupt files can be listed without decoding them again.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, Row, params};
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::audio::integrity::{HealthStatus, IntegrityReport};
+use crate::db::EnhancedConnection;
+use crate::db::datetime_serde::SqliteDateTime;
+
+/// Stored health status of a single audiobook file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileHealthRecord {
+    /// ID of the audiobook the record belongs to
+    pub audiobook_id: String,
+    /// Overall health status
+    pub status: HealthStatus,
+    /// Number of packets that failed to read or decode
+    pub decode_errors: u32,
+    /// Number of frames declared by the container header, if known
+    pub expected_frames: Option<u64>,
+    /// Number of frames actually decoded
+    pub decoded_frames: u64,
+    /// Frames declared by the header that could not be decoded
+    pub truncated_frames: u64,
+    /// Duration declared by the container header in seconds, if known
+    pub header_duration_seconds: Option<f64>,
+    /// Duration of the decoded audio in seconds
+    pub decoded_duration_seconds: f64,
+    /// Last decoder error message, if any
+    pub message: Option<String>,
+    /// When the file was verified
+    pub checked_at: DateTime<Utc>,
+}
+
+const SELECT_COLUMNS: &str = "SELECT audiobook_id, status, decode_errors, expected_frames,
+        decoded_frames, truncated_frames, header_duration_seconds,
+        decoded_duration_seconds, message, checked_at
+     FROM file_health";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<FileHealthRecord> {
+    let status: String = row.get(1)?;
+    let status = status.parse::<HealthStatus>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let checked_at: SqliteDateTime = row.get(9)?;
+    Ok(FileHealthRecord {
+        audiobook_id: row.get(0)?,
+        status,
+        decode_errors: row.get(2)?,
+        expected_frames: row.get(3)?,
+        decoded_frames: row.get(4)?,
+        truncated_frames: row.get(5)?,
+        header_duration_seconds: row.get(6)?,
+        decoded_duration_seconds: row.get(7)?,
+        message: row.get(8)?,
+        checked_at: checked_at.into(),
+    })
+}
+
+/// Repository for per-file health status
+pub struct FileHealthRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl FileHealthRepository {
+    /// Create a new file health repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Save or replace the health status of an audiobook from a verification report
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the audiobook does not exist.
+    pub fn upsert(&self, audiobook_id: &str, report: &IntegrityReport) -> DbResult<()> {
+        let audiobook_id = audiobook_id.to_string();
+        let report = report.clone();
+        let checked_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO file_health (
+                    audiobook_id, status, decode_errors, expected_frames, decoded_frames,
+                    truncated_frames, header_duration_seconds, decoded_duration_seconds,
+                    message, checked_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    &audiobook_id,
+                    report.status.as_str(),
+                    report.decode_errors,
+                    report.expected_frames,
+                    report.decoded_frames,
+                    report.truncated_frames,
+                    report.header_duration_secs,
+                    report.decoded_duration_secs,
+                    report.message,
+                    checked_at,
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Find the stored health status of an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_audiobook(&self, audiobook_id: &str) -> DbResult<Option<FileHealthRecord>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!("{SELECT_COLUMNS} WHERE audiobook_id = ?1"),
+                [&audiobook_id],
+                map_row,
+            )
+            .optional()
+        })
+    }
+
+    /// Find all audiobooks with the given health status
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_status(&self, status: HealthStatus) -> DbResult<Vec<FileHealthRecord>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} WHERE status = ?1 ORDER BY checked_at DESC"
+            ))?;
+            let records = stmt.query_map([status.as_str()], map_row)?;
+            records.collect()
+        })
+    }
+
+    /// Delete the stored health status of an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete_by_audiobook(&self, audiobook_id: &str) -> DbResult<bool> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let rows_affected = conn.execute(
+                "DELETE FROM file_health WHERE audiobook_id = ?1",
+                [&audiobook_id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+}
+
+impl RepositoryBase for FileHealthRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for FileHealthRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestDatabase;
+
+    fn corrupt_report() -> IntegrityReport {
+        IntegrityReport {
+            path: "book.mp3".into(),
+            status: HealthStatus::Corrupt,
+            decode_errors: 3,
+            expected_frames: Some(44100),
+            decoded_frames: 22050,
+            truncated_frames: 22050,
+            header_duration_secs: Some(1.0),
+            decoded_duration_secs: 0.5,
+            duration_delta_secs: Some(-0.5),
+            aborted: false,
+            message: Some("Failed to decode packet".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_find() {
+        let db = TestDatabase::new();
+        let audiobook_id = db.add_audiobook();
+        let repo = FileHealthRepository::new(db.connection.clone());
+
+        repo.upsert(&audiobook_id, &corrupt_report()).unwrap();
+        let record = repo.find_by_audiobook(&audiobook_id).unwrap().unwrap();
+
+        assert_eq!(record.status, HealthStatus::Corrupt);
+        assert_eq!(record.decode_errors, 3);
+        assert_eq!(record.truncated_frames, 22050);
+        assert_eq!(record.header_duration_seconds, Some(1.0));
+
+        let corrupt = repo.find_by_status(HealthStatus::Corrupt).unwrap();
+        assert_eq!(corrupt.len(), 1);
+        assert!(
+            repo.find_by_status(HealthStatus::Healthy)
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(repo.delete_by_audiobook(&audiobook_id).unwrap());
+        assert!(repo.find_by_audiobook(&audiobook_id).unwrap().is_none());
+    }
+}
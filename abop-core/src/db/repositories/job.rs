@@ -0,0 +1,392 @@
+//! Job repository for database operations
+//!
+//! This module persists scan and processing jobs together with the files each
+//! job has already committed, so interrupted jobs can resume where they stopped.
+
+use chrono::Utc;
+use rusqlite::{OptionalExtension, Row, params};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::datetime_serde::SqliteDateTime;
+use crate::models::job::{Job, JobStatus, JobType};
+
+const SELECT_COLUMNS: &str = "SELECT id, job_type, status, library_id, parameters, total_items,
+        processed_items, error, created_at, updated_at
+     FROM jobs";
+
+fn parse_column<T: std::str::FromStr<Err = crate::error::AppError>>(
+    row: &Row<'_>,
+    index: usize,
+) -> rusqlite::Result<T> {
+    let value: String = row.get(index)?;
+    value.parse::<T>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Job> {
+    let created_at: SqliteDateTime = row.get(8)?;
+    let updated_at: SqliteDateTime = row.get(9)?;
+    Ok(Job {
+        id: row.get(0)?,
+        job_type: parse_column(row, 1)?,
+        status: parse_column(row, 2)?,
+        library_id: row.get(3)?,
+        parameters: row.get(4)?,
+        total_items: row.get(5)?,
+        processed_items: row.get(6)?,
+        error: row.get(7)?,
+        created_at: created_at.into(),
+        updated_at: updated_at.into(),
+    })
+}
+
+/// Repository for persisted jobs and their per-file cursor
+pub struct JobRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl JobRepository {
+    /// Create a new job repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Create a new pending job
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the library does not exist.
+    pub fn create(
+        &self,
+        job_type: JobType,
+        library_id: Option<&str>,
+        parameters: &str,
+    ) -> DbResult<Job> {
+        let now = Utc::now();
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            job_type,
+            status: JobStatus::Pending,
+            library_id: library_id.map(str::to_string),
+            parameters: parameters.to_string(),
+            total_items: 0,
+            processed_items: 0,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let row = job.clone();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT INTO jobs (
+                    id, job_type, status, library_id, parameters, created_at, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    &row.id,
+                    row.job_type.as_str(),
+                    row.status.as_str(),
+                    &row.library_id,
+                    &row.parameters,
+                    SqliteDateTime::from(row.created_at),
+                    SqliteDateTime::from(row.updated_at),
+                ],
+            )?;
+            Ok(())
+        })?;
+        Ok(job)
+    }
+
+    /// Find a job by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_id(&self, id: &str) -> DbResult<Option<Job>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(&format!("{SELECT_COLUMNS} WHERE id = ?1"), [&id], map_row)
+                .optional()
+        })
+    }
+
+    /// Find jobs of the given type that were never finished, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_resumable(&self, job_type: JobType) -> DbResult<Vec<Job>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} WHERE job_type = ?1 AND status IN (?2, ?3)
+                 ORDER BY created_at ASC"
+            ))?;
+            let jobs = stmt.query_map(
+                params![
+                    job_type.as_str(),
+                    JobStatus::Pending.as_str(),
+                    JobStatus::Running.as_str()
+                ],
+                map_row,
+            )?;
+            jobs.collect()
+        })
+    }
+
+    /// Find the most recently created jobs, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_recent(&self, limit: usize) -> DbResult<Vec<Job>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} ORDER BY created_at DESC LIMIT ?1"
+            ))?;
+            let jobs = stmt.query_map([limit], map_row)?;
+            jobs.collect()
+        })
+    }
+
+    /// Mark a job as running with the given number of files to handle
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn start(&self, id: &str, total_items: u64) -> DbResult<()> {
+        let id = id.to_string();
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            conn.execute(
+                "UPDATE jobs SET status = ?2, total_items = ?3, error = NULL, updated_at = ?4
+                 WHERE id = ?1",
+                params![&id, JobStatus::Running.as_str(), total_items, updated_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Record files as committed and advance the processed counter
+    ///
+    /// Files that were already committed are ignored, so retrying a batch is safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn commit_items(&self, id: &str, paths: &[PathBuf]) -> DbResult<()> {
+        let id = id.to_string();
+        let paths: Vec<String> = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_transaction(move |tx| {
+            let mut insert =
+                tx.prepare("INSERT OR IGNORE INTO job_items (job_id, path) VALUES (?1, ?2)")?;
+            for path in &paths {
+                insert.execute(params![&id, path])?;
+            }
+            tx.execute(
+                "UPDATE jobs SET
+                    processed_items = (SELECT COUNT(*) FROM job_items WHERE job_id = ?1),
+                    updated_at = ?2
+                 WHERE id = ?1",
+                params![&id, updated_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Load the set of files a job has already committed
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn committed_items(&self, id: &str) -> DbResult<HashSet<PathBuf>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare("SELECT path FROM job_items WHERE job_id = ?1")?;
+            let paths = stmt.query_map([&id], |row| row.get::<_, String>(0).map(PathBuf::from))?;
+            paths.collect()
+        })
+    }
+
+    /// Mark a job as finished with the given status
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn finish(&self, id: &str, status: JobStatus, error: Option<&str>) -> DbResult<()> {
+        let id = id.to_string();
+        let error = error.map(str::to_string);
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            conn.execute(
+                "UPDATE jobs SET status = ?2, error = ?3, updated_at = ?4 WHERE id = ?1",
+                params![&id, status.as_str(), &error, updated_at],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+impl RepositoryBase for JobRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for JobRepository {}
+
+/// Resume cursor of a single running job
+///
+/// Holds the files committed before the job was interrupted so workers can
+/// skip them, and records newly committed files as work progresses.
+pub struct JobCursor {
+    repository: Arc<JobRepository>,
+    job: Job,
+    committed: HashSet<PathBuf>,
+}
+
+impl JobCursor {
+    /// Load the cursor of an existing job
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ExecutionFailed`] if the job does not exist, or
+    /// any error from loading the committed files.
+    pub fn load(repository: Arc<JobRepository>, job_id: &str) -> DbResult<Self> {
+        let job = repository.find_by_id(job_id)?.ok_or_else(|| {
+            crate::db::DatabaseError::ExecutionFailed {
+                message: format!("Job not found: {job_id}"),
+            }
+        })?;
+        let committed = repository.committed_items(job_id)?;
+        Ok(Self {
+            repository,
+            job,
+            committed,
+        })
+    }
+
+    /// The job this cursor belongs to, as it was when the cursor was loaded
+    #[must_use]
+    pub const fn job(&self) -> &Job {
+        &self.job
+    }
+
+    /// ID of the job this cursor belongs to
+    #[must_use]
+    pub fn job_id(&self) -> &str {
+        &self.job.id
+    }
+
+    /// Whether the file was committed before the cursor was loaded
+    #[must_use]
+    pub fn is_committed(&self, path: &Path) -> bool {
+        self.committed.contains(path)
+    }
+
+    /// Number of files committed before the cursor was loaded
+    #[must_use]
+    pub fn committed_count(&self) -> usize {
+        self.committed.len()
+    }
+
+    /// Mark the job as running with the given total number of files
+    ///
+    /// # Errors
+    ///
+    /// Returns any database error from updating the job.
+    pub fn start(&self, total_items: usize) -> DbResult<()> {
+        self.repository
+            .start(&self.job.id, u64::try_from(total_items).unwrap_or(u64::MAX))
+    }
+
+    /// Record files as committed
+    ///
+    /// # Errors
+    ///
+    /// Returns any database error from recording the files.
+    pub fn commit(&self, paths: &[PathBuf]) -> DbResult<()> {
+        self.repository.commit_items(&self.job.id, paths)
+    }
+
+    /// Mark the job as finished
+    ///
+    /// # Errors
+    ///
+    /// Returns any database error from updating the job.
+    pub fn finish(&self, status: JobStatus, error: Option<&str>) -> DbResult<()> {
+        self.repository.finish(&self.job.id, status, error)
+    }
+}
+
+impl std::fmt::Debug for JobCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobCursor")
+            .field("job", &self.job)
+            .field("committed", &self.committed.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_job_lifecycle_and_cursor() {
+        let db = TestDatabase::new();
+        let repo = Arc::new(JobRepository::new(db.connection.clone()));
+        let library_id = db.library_id.clone();
+
+        let job = repo
+            .create(
+                JobType::Scan,
+                Some(&library_id),
+                r#"{"library_path":"/books"}"#,
+            )
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(repo.find_resumable(JobType::Scan).unwrap().len(), 1);
+        assert!(repo.find_resumable(JobType::Process).unwrap().is_empty());
+
+        repo.start(&job.id, 3).unwrap();
+        let first = vec![PathBuf::from("/books/a.mp3"), PathBuf::from("/books/b.mp3")];
+        repo.commit_items(&job.id, &first).unwrap();
+        // Committing the same file twice must not advance the counter
+        repo.commit_items(&job.id, &first[..1]).unwrap();
+
+        let cursor = JobCursor::load(repo.clone(), &job.id).unwrap();
+        assert_eq!(cursor.job().status, JobStatus::Running);
+        assert_eq!(cursor.job().processed_items, 2);
+        assert_eq!(cursor.job().total_items, 3);
+        assert!(cursor.is_committed(Path::new("/books/a.mp3")));
+        assert!(!cursor.is_committed(Path::new("/books/c.mp3")));
+
+        cursor.finish(JobStatus::Completed, None).unwrap();
+        assert!(repo.find_resumable(JobType::Scan).unwrap().is_empty());
+        let recent = repo.find_recent(10).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].status, JobStatus::Completed);
+    }
+}
@@ -0,0 +1,131 @@
+//! Metadata origin repository for database operations
+//!
+//! Records which source, a sidecar file, the embedded tags or the file name,
+//! each merged field of an audiobook was taken from during a scan.
+
+use rusqlite::params;
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::sidecar::{FieldOrigins, MetadataField, MetadataSource};
+
+/// Repository for the origins of audiobook metadata fields
+pub struct MetadataOriginRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl MetadataOriginRepository {
+    /// Create a new metadata origin repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Replace the recorded origins of an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the audiobook does not exist.
+    pub fn replace(&self, audiobook_id: &str, origins: &FieldOrigins) -> DbResult<()> {
+        self.replace_many(&[(audiobook_id.to_string(), origins.clone())])
+    }
+
+    /// Replace the recorded origins of several audiobooks in one transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. an audiobook does not exist.
+    pub fn replace_many(&self, origins: &[(String, FieldOrigins)]) -> DbResult<()> {
+        let rows: Vec<(String, Vec<(MetadataField, MetadataSource)>)> = origins
+            .iter()
+            .map(|(audiobook_id, origins)| (audiobook_id.clone(), origins.iter().collect()))
+            .collect();
+        self.execute_transaction(move |tx| {
+            let mut delete = tx.prepare("DELETE FROM metadata_origins WHERE audiobook_id = ?1")?;
+            let mut insert = tx.prepare(
+                "INSERT INTO metadata_origins (audiobook_id, field, source) VALUES (?1, ?2, ?3)",
+            )?;
+            for (audiobook_id, fields) in &rows {
+                delete.execute([audiobook_id])?;
+                for (field, source) in fields {
+                    insert.execute(params![audiobook_id, field.as_str(), source.as_str()])?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Find the recorded origins of an audiobook
+    ///
+    /// Rows naming an unknown field or source are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_audiobook(&self, audiobook_id: &str) -> DbResult<FieldOrigins> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt =
+                conn.prepare("SELECT field, source FROM metadata_origins WHERE audiobook_id = ?1")?;
+            let rows = stmt.query_map([&audiobook_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            let mut origins = FieldOrigins::default();
+            for row in rows {
+                let (field, source) = row?;
+                if let (Ok(field), Ok(source)) = (field.parse(), source.parse()) {
+                    origins.insert(field, source);
+                }
+            }
+            Ok(origins)
+        })
+    }
+}
+
+impl RepositoryBase for MetadataOriginRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for MetadataOriginRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sidecar::SidecarFormat;
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_replace_and_find() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+        let audiobook_id = db.add_audiobook();
+        let repo = MetadataOriginRepository::new(enhanced);
+
+        let origins: FieldOrigins = [
+            (
+                MetadataField::Title,
+                MetadataSource::Sidecar(SidecarFormat::Opf),
+            ),
+            (MetadataField::Author, MetadataSource::Tag),
+        ]
+        .into_iter()
+        .collect();
+        repo.replace(&audiobook_id, &origins).unwrap();
+        assert_eq!(repo.find_by_audiobook(&audiobook_id).unwrap(), origins);
+
+        let rescanned: FieldOrigins = [(MetadataField::Title, MetadataSource::Filename)]
+            .into_iter()
+            .collect();
+        repo.replace(&audiobook_id, &rescanned).unwrap();
+        assert_eq!(repo.find_by_audiobook(&audiobook_id).unwrap(), rescanned);
+    }
+}
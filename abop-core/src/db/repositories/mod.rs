@@ -4,15 +4,40 @@
 //! using the repository pattern for better organization and testability.
 
 pub mod audiobook;
+pub mod bookmark;
+pub mod collection;
+pub mod file_health;
+pub mod job;
 pub mod library;
+pub mod metadata_origin;
+pub mod person;
+pub mod profile;
 pub mod progress;
+pub mod series;
+pub mod smart_collection;
+pub mod sync;
+pub mod tag;
+pub mod waveform;
 
 pub use audiobook::AudiobookRepository;
+pub use bookmark::BookmarkRepository;
+pub use collection::CollectionRepository;
+pub use file_health::{FileHealthRecord, FileHealthRepository};
+pub use job::{JobCursor, JobRepository};
 pub use library::LibraryRepository;
+pub use metadata_origin::MetadataOriginRepository;
+pub use person::PersonRepository;
+pub use profile::ProfileRepository;
 pub use progress::ProgressRepository;
+pub use series::SeriesRepository;
+pub use smart_collection::SmartCollectionRepository;
+pub use sync::SyncRepository;
+pub use tag::TagRepository;
+pub use waveform::{CachedWaveform, WaveformRepository};
 
 use super::connection::EnhancedConnection;
 use super::error::{DatabaseError, DbResult};
+use crate::models::PersonRole;
 use rusqlite::Connection;
 use std::sync::Arc;
 use tracing::{debug, error, warn};
@@ -337,6 +362,19 @@ pub struct RepositoryManager {
     audiobook_repo: AudiobookRepository,
     library_repo: LibraryRepository,
     progress_repo: ProgressRepository,
+    waveform_repo: WaveformRepository,
+    file_health_repo: FileHealthRepository,
+    job_repo: JobRepository,
+    author_repo: PersonRepository,
+    narrator_repo: PersonRepository,
+    series_repo: SeriesRepository,
+    collection_repo: CollectionRepository,
+    tag_repo: TagRepository,
+    smart_collection_repo: SmartCollectionRepository,
+    profile_repo: ProfileRepository,
+    bookmark_repo: BookmarkRepository,
+    sync_repo: SyncRepository,
+    metadata_origin_repo: MetadataOriginRepository,
 }
 
 impl RepositoryManager {
@@ -347,6 +385,22 @@ impl RepositoryManager {
             audiobook_repo: AudiobookRepository::new(enhanced_connection.clone()),
             library_repo: LibraryRepository::new(enhanced_connection.clone()),
             progress_repo: ProgressRepository::new(enhanced_connection.clone()),
+            waveform_repo: WaveformRepository::new(enhanced_connection.clone()),
+            file_health_repo: FileHealthRepository::new(enhanced_connection.clone()),
+            job_repo: JobRepository::new(enhanced_connection.clone()),
+            author_repo: PersonRepository::new(enhanced_connection.clone(), PersonRole::Author),
+            narrator_repo: PersonRepository::new(
+                enhanced_connection.clone(),
+                PersonRole::Narrator,
+            ),
+            series_repo: SeriesRepository::new(enhanced_connection.clone()),
+            collection_repo: CollectionRepository::new(enhanced_connection.clone()),
+            tag_repo: TagRepository::new(enhanced_connection.clone()),
+            smart_collection_repo: SmartCollectionRepository::new(enhanced_connection.clone()),
+            profile_repo: ProfileRepository::new(enhanced_connection.clone()),
+            bookmark_repo: BookmarkRepository::new(enhanced_connection.clone()),
+            sync_repo: SyncRepository::new(enhanced_connection.clone()),
+            metadata_origin_repo: MetadataOriginRepository::new(enhanced_connection.clone()),
             enhanced_connection,
         }
     }
@@ -369,6 +423,84 @@ impl RepositoryManager {
         &self.progress_repo
     }
 
+    /// Get the waveform repository
+    #[must_use]
+    pub const fn waveforms(&self) -> &WaveformRepository {
+        &self.waveform_repo
+    }
+
+    /// Get the file health repository
+    #[must_use]
+    pub const fn file_health(&self) -> &FileHealthRepository {
+        &self.file_health_repo
+    }
+
+    /// Get the job repository
+    #[must_use]
+    pub const fn jobs(&self) -> &JobRepository {
+        &self.job_repo
+    }
+
+    /// Get the author repository
+    #[must_use]
+    pub const fn authors(&self) -> &PersonRepository {
+        &self.author_repo
+    }
+
+    /// Get the narrator repository
+    #[must_use]
+    pub const fn narrators(&self) -> &PersonRepository {
+        &self.narrator_repo
+    }
+
+    /// Get the series repository
+    #[must_use]
+    pub const fn series(&self) -> &SeriesRepository {
+        &self.series_repo
+    }
+
+    /// Get the collection repository
+    #[must_use]
+    pub const fn collections(&self) -> &CollectionRepository {
+        &self.collection_repo
+    }
+
+    /// Get the tag repository
+    #[must_use]
+    pub const fn tags(&self) -> &TagRepository {
+        &self.tag_repo
+    }
+
+    /// Get the smart collection repository
+    #[must_use]
+    pub const fn smart_collections(&self) -> &SmartCollectionRepository {
+        &self.smart_collection_repo
+    }
+
+    /// Get the profile repository
+    #[must_use]
+    pub const fn profiles(&self) -> &ProfileRepository {
+        &self.profile_repo
+    }
+
+    /// Get the bookmark repository
+    #[must_use]
+    pub const fn bookmarks(&self) -> &BookmarkRepository {
+        &self.bookmark_repo
+    }
+
+    /// Get the sync repository
+    #[must_use]
+    pub const fn sync(&self) -> &SyncRepository {
+        &self.sync_repo
+    }
+
+    /// Get the metadata origin repository
+    #[must_use]
+    pub const fn metadata_origins(&self) -> &MetadataOriginRepository {
+        &self.metadata_origin_repo
+    }
+
     /// Get access to the enhanced connection
     #[must_use]
     pub const fn enhanced_connection(&self) -> &Arc<EnhancedConnection> {
@@ -432,6 +564,25 @@ impl Clone for RepositoryManager {
             audiobook_repo: AudiobookRepository::new(self.enhanced_connection.clone()),
             library_repo: LibraryRepository::new(self.enhanced_connection.clone()),
             progress_repo: ProgressRepository::new(self.enhanced_connection.clone()),
+            waveform_repo: WaveformRepository::new(self.enhanced_connection.clone()),
+            file_health_repo: FileHealthRepository::new(self.enhanced_connection.clone()),
+            job_repo: JobRepository::new(self.enhanced_connection.clone()),
+            author_repo: PersonRepository::new(
+                self.enhanced_connection.clone(),
+                PersonRole::Author,
+            ),
+            narrator_repo: PersonRepository::new(
+                self.enhanced_connection.clone(),
+                PersonRole::Narrator,
+            ),
+            series_repo: SeriesRepository::new(self.enhanced_connection.clone()),
+            collection_repo: CollectionRepository::new(self.enhanced_connection.clone()),
+            tag_repo: TagRepository::new(self.enhanced_connection.clone()),
+            smart_collection_repo: SmartCollectionRepository::new(self.enhanced_connection.clone()),
+            profile_repo: ProfileRepository::new(self.enhanced_connection.clone()),
+            bookmark_repo: BookmarkRepository::new(self.enhanced_connection.clone()),
+            sync_repo: SyncRepository::new(self.enhanced_connection.clone()),
+            metadata_origin_repo: MetadataOriginRepository::new(self.enhanced_connection.clone()),
             enhanced_connection: self.enhanced_connection.clone(),
         }
     }
@@ -0,0 +1,323 @@
+//! Author and narrator repository for database operations
+//!
+//! Authors and narrators share one repository type; the [`PersonRole`] it was
+//! created with selects the tables. People are found through their alias keys,
+//! so any spelling of a name resolves to the same person.
+
+use rusqlite::{OptionalExtension, Row, params};
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::catalog::{JOINED_AUDIOBOOK_COLUMNS, PersonTables, map_audiobook};
+use crate::library::names::person_key;
+use crate::models::{Audiobook, Person, PersonRole};
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Person> {
+    Ok(Person {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        sort_name: row.get(2)?,
+        audiobook_count: row.get(3)?,
+    })
+}
+
+/// Repository for the authors or narrators of audiobooks
+pub struct PersonRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+    role: PersonRole,
+}
+
+impl PersonRepository {
+    /// Create a new repository for people with the given role
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>, role: PersonRole) -> Self {
+        Self {
+            enhanced_connection,
+            role,
+        }
+    }
+
+    /// Role of the people in this repository
+    #[must_use]
+    pub const fn role(&self) -> PersonRole {
+        self.role
+    }
+
+    fn select(&self) -> String {
+        let tables = PersonTables::for_role(self.role);
+        format!(
+            "SELECT p.id, p.name, p.sort_name,
+                (SELECT COUNT(*) FROM {links} l WHERE l.{column} = p.id)
+             FROM {people} p",
+            links = tables.links,
+            column = tables.column,
+            people = tables.people,
+        )
+    }
+
+    /// Find every person linked to at least one audiobook, ordered by sort name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<Person>> {
+        let tables = PersonTables::for_role(self.role);
+        let query = format!(
+            "{} WHERE EXISTS (SELECT 1 FROM {} l WHERE l.{} = p.id)
+             ORDER BY p.sort_name COLLATE NOCASE",
+            self.select(),
+            tables.links,
+            tables.column
+        );
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&query)?;
+            let people = stmt.query_map([], map_row)?;
+            people.collect()
+        })
+    }
+
+    /// Find a person by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_id(&self, id: &str) -> DbResult<Option<Person>> {
+        let query = format!("{} WHERE p.id = ?1", self.select());
+        let id = id.to_string();
+        self.execute_query(move |conn| conn.query_row(&query, [&id], map_row).optional())
+    }
+
+    /// Find a person by any known spelling of their name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<Person>> {
+        let tables = PersonTables::for_role(self.role);
+        let query = format!(
+            "{} JOIN {} alias ON alias.{} = p.id WHERE alias.alias_key = ?1",
+            self.select(),
+            tables.aliases,
+            tables.column
+        );
+        let key = person_key(name);
+        self.execute_query(move |conn| conn.query_row(&query, [&key], map_row).optional())
+    }
+
+    /// Find the people of an audiobook in tag order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_audiobook(&self, audiobook_id: &str) -> DbResult<Vec<Person>> {
+        let tables = PersonTables::for_role(self.role);
+        let query = format!(
+            "{} JOIN {} link ON link.{} = p.id WHERE link.audiobook_id = ?1
+             ORDER BY link.position",
+            self.select(),
+            tables.links,
+            tables.column
+        );
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&query)?;
+            let people = stmt.query_map([&audiobook_id], map_row)?;
+            people.collect()
+        })
+    }
+
+    /// Find the audiobooks of a person, ordered by title
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_audiobooks(&self, person_id: &str) -> DbResult<Vec<Audiobook>> {
+        let tables = PersonTables::for_role(self.role);
+        let query = format!(
+            "SELECT {JOINED_AUDIOBOOK_COLUMNS} FROM audiobooks a
+             JOIN {} link ON link.audiobook_id = a.id
+             WHERE link.{} = ?1
+             ORDER BY a.title COLLATE NOCASE",
+            tables.links, tables.column
+        );
+        let person_id = person_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&query)?;
+            let audiobooks = stmt.query_map([&person_id], map_audiobook)?;
+            audiobooks.collect()
+        })
+    }
+
+    /// Record another spelling of a person's name
+    ///
+    /// An alias that pointed at a different person is moved to this one, so
+    /// future scans link that spelling here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the person does not exist.
+    pub fn add_alias(&self, person_id: &str, alias: &str) -> DbResult<()> {
+        let tables = PersonTables::for_role(self.role);
+        let query = format!(
+            "INSERT OR REPLACE INTO {} (alias_key, {}) VALUES (?1, ?2)",
+            tables.aliases, tables.column
+        );
+        let person_id = person_id.to_string();
+        let key = person_key(alias);
+        self.execute_query(move |conn| {
+            conn.execute(&query, params![&key, &person_id])?;
+            Ok(())
+        })
+    }
+
+    /// Merge a duplicate person into another
+    ///
+    /// Audiobook links and aliases of `duplicate_id` move to `keep_id`, then
+    /// the duplicate is deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails; nothing is changed in that case.
+    pub fn merge(&self, keep_id: &str, duplicate_id: &str) -> DbResult<()> {
+        if keep_id == duplicate_id {
+            return Ok(());
+        }
+        let tables = PersonTables::for_role(self.role);
+        let keep_id = keep_id.to_string();
+        let duplicate_id = duplicate_id.to_string();
+        self.execute_transaction(move |tx| {
+            // Books linked to both keep their existing link to the kept person
+            for table in [tables.links, tables.aliases] {
+                tx.execute(
+                    &format!(
+                        "UPDATE OR IGNORE {table} SET {column} = ?1 WHERE {column} = ?2",
+                        column = tables.column
+                    ),
+                    params![&keep_id, &duplicate_id],
+                )?;
+            }
+            tx.execute(
+                &format!("DELETE FROM {} WHERE id = ?1", tables.people),
+                [&duplicate_id],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+impl RepositoryBase for PersonRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for PersonRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::AudiobookRepository;
+    use crate::test_utils::TestDatabase;
+
+    fn book(library_id: &str, title: &str, author: &str, narrator: &str) -> Audiobook {
+        let mut audiobook = Audiobook::new(library_id, format!("/books/{title}.mp3"));
+        audiobook.title = Some(title.to_string());
+        audiobook.author = Some(author.to_string());
+        audiobook.narrator = Some(narrator.to_string());
+        audiobook
+    }
+
+    #[test]
+    fn test_browse_by_person_across_spellings() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+        let library_id = db.library_id.clone();
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        audiobooks
+            .upsert(&book(
+                &library_id,
+                "The Hobbit",
+                "Tolkien, J.R.R.",
+                "Andy Serkis",
+            ))
+            .unwrap();
+        audiobooks
+            .upsert(&book(
+                &library_id,
+                "The Silmarillion",
+                "J. R. R. Tolkien; Christopher Tolkien",
+                "Martin Shaw",
+            ))
+            .unwrap();
+
+        let authors = PersonRepository::new(enhanced.clone(), PersonRole::Author);
+        let all = authors.find_all().unwrap();
+        let names: Vec<_> = all.iter().map(|person| person.sort_name.as_str()).collect();
+        assert_eq!(names, ["Tolkien, Christopher", "Tolkien, J.R.R."]);
+
+        let tolkien = authors.find_by_name("J.R.R. Tolkien").unwrap().unwrap();
+        assert_eq!(tolkien.audiobook_count, 2);
+        let titles: Vec<_> = authors
+            .find_audiobooks(&tolkien.id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|audiobook| audiobook.title)
+            .collect();
+        assert_eq!(titles, ["The Hobbit", "The Silmarillion"]);
+
+        let narrators = PersonRepository::new(enhanced, PersonRole::Narrator);
+        assert_eq!(narrators.find_all().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_alias_and_merge() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+        let library_id = db.library_id.clone();
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        audiobooks
+            .upsert(&book(&library_id, "Dune", "Frank Herbert", "Scott Brick"))
+            .unwrap();
+        audiobooks
+            .upsert(&book(
+                &library_id,
+                "Dune Messiah",
+                "F. Herbert",
+                "Scott Brick",
+            ))
+            .unwrap();
+
+        let authors = PersonRepository::new(enhanced.clone(), PersonRole::Author);
+        let frank = authors.find_by_name("Frank Herbert").unwrap().unwrap();
+        let initial = authors.find_by_name("F. Herbert").unwrap().unwrap();
+        assert_ne!(frank.id, initial.id);
+
+        authors.merge(&frank.id, &initial.id).unwrap();
+        assert!(authors.find_by_id(&initial.id).unwrap().is_none());
+        let merged = authors.find_by_name("F. Herbert").unwrap().unwrap();
+        assert_eq!(merged.id, frank.id);
+        assert_eq!(merged.audiobook_count, 2);
+
+        // A new spelling links to the existing person on the next write
+        authors.add_alias(&frank.id, "Herbert, Franklin").unwrap();
+        audiobooks
+            .upsert(&book(
+                &library_id,
+                "Children of Dune",
+                "Herbert, Franklin",
+                "Scott Brick",
+            ))
+            .unwrap();
+        assert_eq!(authors.find_all().unwrap().len(), 1);
+        assert_eq!(authors.find_audiobooks(&frank.id).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,286 @@
+//! Profile repository for database operations
+//!
+//! The default profile is created by the migration and cannot be deleted, so
+//! there is always somewhere to record progress. Deleting any other profile
+//! removes its progress and bookmarks with it.
+
+use chrono::Utc;
+use rusqlite::types::Type;
+use rusqlite::{OptionalExtension, Row, params};
+use std::sync::Arc;
+
+use super::super::error::{DatabaseError, DbResult};
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::datetime_serde::SqliteDateTime;
+use crate::models::{DEFAULT_PROFILE_ID, PlaybackConfig, Profile};
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, name, playback_config, created_at, updated_at FROM profiles";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Profile> {
+    let playback: Option<String> = row.get(2)?;
+    let created_at: SqliteDateTime = row.get(3)?;
+    let updated_at: SqliteDateTime = row.get(4)?;
+    Ok(Profile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        playback: playback
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?,
+        created_at: created_at.into(),
+        updated_at: updated_at.into(),
+    })
+}
+
+fn validate_name(name: &str) -> DbResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::validation_failed(
+            "name",
+            "Profile name cannot be empty",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Repository for listener profiles
+pub struct ProfileRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl ProfileRepository {
+    /// Create a new profile repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Create a profile that uses the application playback defaults
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn create(&self, name: &str) -> DbResult<Profile> {
+        let profile = Profile::new(&validate_name(name)?);
+        let row = profile.clone();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT INTO profiles (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    &row.id,
+                    &row.name,
+                    SqliteDateTime::from(row.created_at),
+                    SqliteDateTime::from(row.updated_at),
+                ],
+            )?;
+            Ok(())
+        })?;
+        Ok(profile)
+    }
+
+    /// Find all profiles, default first and the rest by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<Profile>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} ORDER BY id <> ?1, name COLLATE NOCASE"
+            ))?;
+            let profiles = stmt.query_map([DEFAULT_PROFILE_ID], map_row)?;
+            profiles.collect()
+        })
+    }
+
+    /// Find a profile by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_id(&self, id: &str) -> DbResult<Option<Profile>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(&format!("{SELECT_COLUMNS} WHERE id = ?1"), [&id], map_row)
+                .optional()
+        })
+    }
+
+    /// Find a profile by name, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<Profile>> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!("{SELECT_COLUMNS} WHERE name = ?1"),
+                [&name],
+                map_row,
+            )
+            .optional()
+        })
+    }
+
+    /// Rename a profile
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn rename(&self, id: &str, name: &str) -> DbResult<bool> {
+        let id = id.to_string();
+        let name = validate_name(name)?;
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            let renamed = conn.execute(
+                "UPDATE profiles SET name = ?2, updated_at = ?3 WHERE id = ?1",
+                params![&id, &name, updated_at],
+            )?;
+            Ok(renamed > 0)
+        })
+    }
+
+    /// Set the playback preferences of a profile, or `None` for the defaults
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn set_playback(&self, id: &str, playback: Option<&PlaybackConfig>) -> DbResult<bool> {
+        let id = id.to_string();
+        let json = playback
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DatabaseError::validation_failed("playback_config", &e.to_string()))?;
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_query(move |conn| {
+            let updated = conn.execute(
+                "UPDATE profiles SET playback_config = ?2, updated_at = ?3 WHERE id = ?1",
+                params![&id, &json, updated_at],
+            )?;
+            Ok(updated > 0)
+        })
+    }
+
+    /// Delete a profile with its progress and bookmarks
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] for the default profile.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete(&self, id: &str) -> DbResult<bool> {
+        if id == DEFAULT_PROFILE_ID {
+            return Err(DatabaseError::validation_failed(
+                "id",
+                "The default profile cannot be deleted",
+            ));
+        }
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let deleted = conn.execute("DELETE FROM profiles WHERE id = ?1", [&id])?;
+            Ok(deleted > 0)
+        })
+    }
+}
+
+impl RepositoryBase for ProfileRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for ProfileRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{AudiobookRepository, BookmarkRepository, ProgressRepository};
+    use crate::models::{Audiobook, Bookmark, Progress};
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_profiles_keep_separate_progress() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+        let audiobook = Audiobook::new(&db.library_id, "/books/dune.mp3");
+        AudiobookRepository::new(enhanced.clone())
+            .upsert(&audiobook)
+            .unwrap();
+
+        let profiles = ProfileRepository::new(enhanced.clone());
+        let sam = profiles.create("Sam").unwrap();
+        assert!(profiles.create("sam").is_err());
+        let all = profiles.find_all().unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all[0].is_default());
+
+        let progress = ProgressRepository::new(enhanced.clone());
+        progress.upsert(&Progress::new(&audiobook.id, 100)).unwrap();
+        progress
+            .upsert(&Progress::new(&audiobook.id, 900).with_profile(&sam.id))
+            .unwrap();
+        assert!(
+            progress
+                .update_position_for_profile(&sam.id, &audiobook.id, 1200)
+                .unwrap()
+        );
+        assert_eq!(
+            progress
+                .find_by_audiobook(&audiobook.id)
+                .unwrap()
+                .unwrap()
+                .position_seconds,
+            100
+        );
+        assert_eq!(
+            progress
+                .find_for_profile(&sam.id, &audiobook.id)
+                .unwrap()
+                .unwrap()
+                .position_seconds,
+            1200
+        );
+
+        let playback = PlaybackConfig {
+            speed: 1.5,
+            ..PlaybackConfig::default()
+        };
+        assert!(profiles.set_playback(&sam.id, Some(&playback)).unwrap());
+        let stored = profiles.find_by_name("SAM").unwrap().unwrap();
+        assert!((stored.playback_config().speed - 1.5).abs() < f32::EPSILON);
+
+        let bookmarks = BookmarkRepository::new(enhanced.clone());
+        bookmarks
+            .add(&Bookmark::new(&sam.id, &audiobook.id, 600, "Important"))
+            .unwrap();
+        assert!(
+            bookmarks
+                .find_for_audiobook(DEFAULT_PROFILE_ID, &audiobook.id)
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(profiles.delete(DEFAULT_PROFILE_ID).is_err());
+        assert!(profiles.delete(&sam.id).unwrap());
+        assert!(
+            progress
+                .find_for_profile(&sam.id, &audiobook.id)
+                .unwrap()
+                .is_none()
+        );
+        assert!(bookmarks.find_for_profile(&sam.id).unwrap().is_empty());
+    }
+}
@@ -1,15 +1,37 @@
 //! Progress repository for database operations
 //!
 //! This module handles all database operations related to audiobook progress tracking.
+//! Progress is kept per listener profile; methods without a profile argument
+//! read and update the default profile unless documented otherwise.
 
-use rusqlite::{OptionalExtension, params};
+use rusqlite::{OptionalExtension, Row, params};
 use std::sync::Arc;
 
 use super::super::error::DbResult;
 use super::{EnhancedRepository, Repository, RepositoryBase};
 use crate::db::EnhancedConnection;
 use crate::db::datetime_serde::SqliteDateTime;
-use crate::models::Progress;
+use crate::models::{DEFAULT_PROFILE_ID, Progress};
+
+const SELECT_COLUMNS: &str = "SELECT id, profile_id, audiobook_id, position_seconds, completed,
+        last_played, created_at, updated_at
+     FROM progress";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Progress> {
+    let last_played: Option<SqliteDateTime> = row.get(5)?;
+    let created_at: SqliteDateTime = row.get(6)?;
+    let updated_at: SqliteDateTime = row.get(7)?;
+    Ok(Progress {
+        id: row.get(0)?,
+        profile_id: row.get(1)?,
+        audiobook_id: row.get(2)?,
+        position_seconds: row.get(3)?,
+        completed: row.get(4)?,
+        last_played: last_played.map(Into::into),
+        created_at: created_at.into(),
+        updated_at: updated_at.into(),
+    })
+}
 
 /// Repository for progress-related database operations
 pub struct ProgressRepository {
@@ -24,7 +46,19 @@ impl ProgressRepository {
             enhanced_connection,
         }
     }
-    /// Save or update progress for an audiobook
+
+    fn query_list<P>(&self, clause: &'static str, params: P) -> DbResult<Vec<Progress>>
+    where
+        P: rusqlite::Params + Clone + Send + 'static,
+    {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!("{SELECT_COLUMNS} {clause}"))?;
+            let progress_list = stmt.query_map(params.clone(), map_row)?;
+            progress_list.collect()
+        })
+    }
+
+    /// Save or update progress for an audiobook in the record's profile
     ///
     /// # Errors
     ///
@@ -39,10 +73,12 @@ impl ProgressRepository {
             let updated_at_sql = SqliteDateTime::from(progress.updated_at);
             conn.execute(
                 "INSERT OR REPLACE INTO progress (
-                    id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    id, profile_id, audiobook_id, position_seconds, completed, last_played,
+                    created_at, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                 params![
                     &progress.id,
+                    &progress.profile_id,
                     &progress.audiobook_id,
                     progress.position_seconds,
                     progress.completed,
@@ -55,35 +91,36 @@ impl ProgressRepository {
         })
     }
 
-    /// Find progress by audiobook ID
+    /// Find progress by audiobook ID in the default profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
     pub fn find_by_audiobook(&self, audiobook_id: &str) -> DbResult<Option<Progress>> {
+        self.find_for_profile(DEFAULT_PROFILE_ID, audiobook_id)
+    }
+
+    /// Find a profile's progress in an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
+    pub fn find_for_profile(
+        &self,
+        profile_id: &str,
+        audiobook_id: &str,
+    ) -> DbResult<Option<Progress>> {
+        let profile_id = profile_id.to_string();
         let audiobook_id = audiobook_id.to_string();
         self.execute_query(move |conn| {
-            let audiobook_id = audiobook_id.clone();
             conn.query_row(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress WHERE audiobook_id = ?1",
-                [audiobook_id],
-                |row| {
-                    let last_played: Option<SqliteDateTime> = row.get(4)?;
-                    let created_at: SqliteDateTime = row.get(5)?;
-                    let updated_at: SqliteDateTime = row.get(6)?;
-                    Ok(Progress {
-                        id: row.get(0)?,
-                        audiobook_id: row.get(1)?,
-                        position_seconds: row.get(2)?,
-                        completed: row.get(3)?,
-                        last_played: last_played.map(|dt| dt.into()),
-                        created_at: created_at.into(),
-                        updated_at: updated_at.into(),
-                    })
-                },
-            ).optional()
+                &format!("{SELECT_COLUMNS} WHERE profile_id = ?1 AND audiobook_id = ?2"),
+                [&profile_id, &audiobook_id],
+                map_row,
+            )
+            .optional()
         })
     }
 
@@ -96,197 +133,162 @@ impl ProgressRepository {
     pub fn find_by_id(&self, id: &str) -> DbResult<Option<Progress>> {
         let id = id.to_string();
         self.execute_query(move |conn| {
-            let id = id.clone();
-            conn.query_row(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress WHERE id = ?1",
-                [id],
-                |row| {
-                    let last_played: Option<SqliteDateTime> = row.get(4)?;
-                    let created_at: SqliteDateTime = row.get(5)?;
-                    let updated_at: SqliteDateTime = row.get(6)?;
-                    Ok(Progress {
-                        id: row.get(0)?,
-                        audiobook_id: row.get(1)?,
-                        position_seconds: row.get(2)?,
-                        completed: row.get(3)?,
-                        last_played: last_played.map(|dt| dt.into()),
-                        created_at: created_at.into(),
-                        updated_at: updated_at.into(),
-                    })
-                },
-            ).optional()
+            conn.query_row(&format!("{SELECT_COLUMNS} WHERE id = ?1"), [&id], map_row)
+                .optional()
         })
     }
 
-    /// Get all progress records
+    /// Get all progress records of every profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
     pub fn find_all(&self) -> DbResult<Vec<Progress>> {
-        self.execute_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress ORDER BY updated_at DESC"
-            )?;
-            let progress_list = stmt.query_map([], |row| {
-                let last_played: Option<SqliteDateTime> = row.get(4)?;
-                let created_at: SqliteDateTime = row.get(5)?;
-                let updated_at: SqliteDateTime = row.get(6)?;
-                Ok(Progress {
-                    id: row.get(0)?,
-                    audiobook_id: row.get(1)?,
-                    position_seconds: row.get(2)?,
-                    completed: row.get(3)?,
-                    last_played: last_played.map(|dt| dt.into()),
-                    created_at: created_at.into(),
-                    updated_at: updated_at.into(),
-                })
-            })?.collect::<Result<Vec<_>, _>>()?;
-            Ok(progress_list)
-        })
+        self.query_list("ORDER BY updated_at DESC", [])
     }
 
-    /// Get recently played audiobooks (within specified days)
+    /// Get all progress records of a profile, most recently updated first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
+    pub fn find_all_for_profile(&self, profile_id: &str) -> DbResult<Vec<Progress>> {
+        self.query_list(
+            "WHERE profile_id = ?1 ORDER BY updated_at DESC",
+            [profile_id.to_string()],
+        )
+    }
+
+    /// Get recently played audiobooks (within specified days) of every profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
     pub fn get_recently_played(&self, days: i32) -> DbResult<Vec<Progress>> {
-        self.execute_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress 
-                 WHERE last_played IS NOT NULL 
-                   AND last_played >= datetime('now', '-' || ?1 || ' days')
-                 ORDER BY last_played DESC"
-            )?;
-            let progress_list = stmt.query_map([days], |row| {
-                let last_played: Option<SqliteDateTime> = row.get(4)?;
-                let created_at: SqliteDateTime = row.get(5)?;
-                let updated_at: SqliteDateTime = row.get(6)?;
-                Ok(Progress {
-                    id: row.get(0)?,
-                    audiobook_id: row.get(1)?,
-                    position_seconds: row.get(2)?,
-                    completed: row.get(3)?,
-                    last_played: last_played.map(|dt| dt.into()),
-                    created_at: created_at.into(),
-                    updated_at: updated_at.into(),
-                })
-            })?.collect::<Result<Vec<_>, _>>()?;
-            Ok(progress_list)
-        })
+        self.query_list(
+            "WHERE last_played IS NOT NULL
+               AND last_played >= datetime('now', '-' || ?1 || ' days')
+             ORDER BY last_played DESC",
+            [days],
+        )
     }
 
-    /// Get completed audiobooks
+    /// Get completed audiobooks of every profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
     pub fn get_completed(&self) -> DbResult<Vec<Progress>> {
-        self.execute_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress WHERE completed = 1 ORDER BY updated_at DESC"
-            )?;
-            let progress_list = stmt.query_map([], |row| {
-                let last_played: Option<SqliteDateTime> = row.get(4)?;
-                let created_at: SqliteDateTime = row.get(5)?;
-                let updated_at: SqliteDateTime = row.get(6)?;
-                Ok(Progress {
-                    id: row.get(0)?,
-                    audiobook_id: row.get(1)?,
-                    position_seconds: row.get(2)?,
-                    completed: row.get(3)?,
-                    last_played: last_played.map(|dt| dt.into()),
-                    created_at: created_at.into(),
-                    updated_at: updated_at.into(),
-                })
-            })?.collect::<Result<Vec<_>, _>>()?;
-            Ok(progress_list)
-        })
+        self.query_list("WHERE completed = 1 ORDER BY updated_at DESC", [])
     }
 
-    /// Get in-progress audiobooks (not completed)
+    /// Get in-progress audiobooks (not completed) of every profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
     pub fn get_in_progress(&self) -> DbResult<Vec<Progress>> {
-        self.execute_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, audiobook_id, position_seconds, completed, last_played, created_at, updated_at
-                 FROM progress WHERE completed = 0 AND position_seconds > 0 
-                 ORDER BY updated_at DESC"
-            )?;
-            let progress_list = stmt.query_map([], |row| {
-                let last_played: Option<SqliteDateTime> = row.get(4)?;
-                let created_at: SqliteDateTime = row.get(5)?;
-                let updated_at: SqliteDateTime = row.get(6)?;
-                Ok(Progress {
-                    id: row.get(0)?,
-                    audiobook_id: row.get(1)?,
-                    position_seconds: row.get(2)?,
-                    completed: row.get(3)?,
-                    last_played: last_played.map(|dt| dt.into()),
-                    created_at: created_at.into(),
-                    updated_at: updated_at.into(),
-                })
-            })?.collect::<Result<Vec<_>, _>>()?;
-            Ok(progress_list)
-        })
+        self.query_list(
+            "WHERE completed = 0 AND position_seconds > 0 ORDER BY updated_at DESC",
+            [],
+        )
     }
-    /// Update position for an audiobook
+
+    /// Get the audiobooks a profile has started but not finished
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query execution fails.
+    pub fn get_in_progress_for_profile(&self, profile_id: &str) -> DbResult<Vec<Progress>> {
+        self.query_list(
+            "WHERE profile_id = ?1 AND completed = 0 AND position_seconds > 0
+             ORDER BY updated_at DESC",
+            [profile_id.to_string()],
+        )
+    }
+
+    /// Update position for an audiobook in the default profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
     pub fn update_position(&self, audiobook_id: &str, position_seconds: i64) -> DbResult<bool> {
+        self.update_position_for_profile(DEFAULT_PROFILE_ID, audiobook_id, position_seconds)
+    }
+
+    /// Update a profile's position in an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn update_position_for_profile(
+        &self,
+        profile_id: &str,
+        audiobook_id: &str,
+        position_seconds: i64,
+    ) -> DbResult<bool> {
+        let profile_id = profile_id.to_string();
         let audiobook_id = audiobook_id.to_string();
         self.execute_query(move |conn| {
-            let audiobook_id = audiobook_id.clone();
             let now = SqliteDateTime::from(chrono::Utc::now());
             let rows_affected = conn.execute(
                 "UPDATE progress SET 
                     position_seconds = ?1,
                     updated_at = ?2,
-                    last_played = ?3
-                 WHERE audiobook_id = ?4",
-                params![position_seconds, now, now, audiobook_id],
+                    last_played = ?2
+                 WHERE profile_id = ?3 AND audiobook_id = ?4",
+                params![position_seconds, now, &profile_id, &audiobook_id],
             )?;
             Ok(rows_affected > 0)
         })
     }
-    /// Mark an audiobook as completed or not completed
+
+    /// Mark an audiobook as completed or not completed in the default profile
     ///
     /// # Errors
     ///
     /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
     /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
     pub fn mark_completed(&self, audiobook_id: &str, completed: bool) -> DbResult<bool> {
+        self.mark_completed_for_profile(DEFAULT_PROFILE_ID, audiobook_id, completed)
+    }
+
+    /// Mark an audiobook as completed or not completed for a profile
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn mark_completed_for_profile(
+        &self,
+        profile_id: &str,
+        audiobook_id: &str,
+        completed: bool,
+    ) -> DbResult<bool> {
+        let profile_id = profile_id.to_string();
         let audiobook_id = audiobook_id.to_string();
         self.execute_query(move |conn| {
-            let audiobook_id = audiobook_id.clone();
             let now = SqliteDateTime::from(chrono::Utc::now());
             let rows_affected = conn.execute(
                 "UPDATE progress SET 
                     completed = ?1,
                     updated_at = ?2
-                 WHERE audiobook_id = ?3",
-                params![completed, now, audiobook_id],
+                 WHERE profile_id = ?3 AND audiobook_id = ?4",
+                params![completed, now, &profile_id, &audiobook_id],
             )?;
             Ok(rows_affected > 0)
         })
     }
 
-    /// Delete progress by audiobook ID
+    /// Delete the progress of every profile in an audiobook
     ///
     /// # Errors
     ///
@@ -295,10 +297,27 @@ impl ProgressRepository {
     pub fn delete_by_audiobook(&self, audiobook_id: &str) -> DbResult<bool> {
         let audiobook_id = audiobook_id.to_string();
         self.execute_query(move |conn| {
-            let audiobook_id = audiobook_id.clone();
             let rows_affected = conn.execute(
                 "DELETE FROM progress WHERE audiobook_id = ?1",
-                [audiobook_id],
+                [&audiobook_id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// Delete a profile's progress in an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete_for_profile(&self, profile_id: &str, audiobook_id: &str) -> DbResult<bool> {
+        let profile_id = profile_id.to_string();
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let rows_affected = conn.execute(
+                "DELETE FROM progress WHERE profile_id = ?1 AND audiobook_id = ?2",
+                [&profile_id, &audiobook_id],
             )?;
             Ok(rows_affected > 0)
         })
@@ -313,13 +332,12 @@ impl ProgressRepository {
     pub fn delete(&self, id: &str) -> DbResult<bool> {
         let id = id.to_string();
         self.execute_query(move |conn| {
-            let id = id.clone();
-            let rows_affected = conn.execute("DELETE FROM progress WHERE id = ?1", [id])?;
+            let rows_affected = conn.execute("DELETE FROM progress WHERE id = ?1", [&id])?;
             Ok(rows_affected > 0)
         })
     }
 
-    /// Get statistics about progress
+    /// Get statistics about progress across every profile
     ///
     /// # Errors
     ///
@@ -343,7 +361,7 @@ impl ProgressRepository {
         })
     }
 
-    /// Check if progress exists for an audiobook
+    /// Check if any profile has progress in an audiobook
     ///
     /// # Errors
     ///
@@ -352,10 +370,9 @@ impl ProgressRepository {
     pub fn exists_for_audiobook(&self, audiobook_id: &str) -> DbResult<bool> {
         let audiobook_id = audiobook_id.to_string();
         self.execute_query(move |conn| {
-            let audiobook_id = audiobook_id.clone();
             let count: i64 = conn.query_row(
                 "SELECT COUNT(*) FROM progress WHERE audiobook_id = ?1",
-                [audiobook_id],
+                [&audiobook_id],
                 |row| row.get(0),
             )?;
             Ok(count > 0)
@@ -3,7 +3,7 @@ mod progress_tests {
     use super::super::ProgressRepository;
     use crate::db::repositories::{AudiobookRepository, LibraryRepository};
     use crate::db::{connection::EnhancedConnection, migrations::run_migrations};
-    use crate::models::Progress;
+    use crate::models::{DEFAULT_PROFILE_ID, Progress};
     use crate::test_utils::TestDataFactory;
     use chrono::Utc;
     use rusqlite::Connection;
@@ -73,6 +73,7 @@ mod progress_tests {
     fn create_test_progress(audiobook_id: &str, position: u64, completed: bool) -> Progress {
         Progress {
             id: format!("progress-{audiobook_id}"),
+            profile_id: DEFAULT_PROFILE_ID.to_string(),
             audiobook_id: audiobook_id.to_string(),
             position_seconds: position,
             completed,
@@ -111,6 +112,7 @@ mod progress_tests {
         // Update the progress
         let updated_progress = Progress {
             id: initial_progress.id.clone(),
+            profile_id: DEFAULT_PROFILE_ID.to_string(),
             audiobook_id: "audiobook-1".to_string(),
             position_seconds: 600,
             completed: true,
@@ -1,7 +1,10 @@
 //! Tests for the progress repository
 
 use super::*;
-use crate::{db::test_utils::create_test_database, models::Progress};
+use crate::{
+    db::test_utils::create_test_database,
+    models::{DEFAULT_PROFILE_ID, Progress},
+};
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -10,6 +13,7 @@ use uuid::Uuid;
 fn create_test_progress(audiobook_id: &str) -> Progress {
     Progress {
         id: Uuid::new_v4().to_string(),
+        profile_id: DEFAULT_PROFILE_ID.to_string(),
         audiobook_id: audiobook_id.to_string(),
         position_seconds: 300, // 5 minutes
         completed: false,
@@ -0,0 +1,306 @@
+//! Series repository for database operations
+//!
+//! Series are found through their alias keys like people are. Books of a
+//! series are returned in reading order using the series index, with books
+//! of unknown position last.
+
+use rusqlite::{OptionalExtension, Row, params};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::catalog::{JOINED_AUDIOBOOK_COLUMNS, map_audiobook, resolve_series};
+use crate::library::names::series_key;
+use crate::models::{Audiobook, Series, SeriesMembership};
+
+const SELECT_SERIES: &str = "SELECT s.id, s.name,
+        (SELECT COUNT(*) FROM audiobook_series m WHERE m.series_id = s.id)
+     FROM series s";
+
+const SELECT_MEMBERSHIPS: &str = "SELECT m.audiobook_id, m.series_id, s.name, m.series_index
+     FROM audiobook_series m JOIN series s ON s.id = m.series_id";
+
+fn map_series(row: &Row<'_>) -> rusqlite::Result<Series> {
+    Ok(Series {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        audiobook_count: row.get(2)?,
+    })
+}
+
+fn map_membership(row: &Row<'_>) -> rusqlite::Result<SeriesMembership> {
+    Ok(SeriesMembership {
+        audiobook_id: row.get(0)?,
+        series_id: row.get(1)?,
+        series_name: row.get(2)?,
+        series_index: row.get(3)?,
+    })
+}
+
+/// Repository for book series and their members
+pub struct SeriesRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl SeriesRepository {
+    /// Create a new series repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Find every series with at least one audiobook, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<Series>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_SERIES}
+                 WHERE EXISTS (SELECT 1 FROM audiobook_series m WHERE m.series_id = s.id)
+                 ORDER BY s.name COLLATE NOCASE"
+            ))?;
+            let series = stmt.query_map([], map_series)?;
+            series.collect()
+        })
+    }
+
+    /// Find a series by any known spelling of its name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<Series>> {
+        let key = series_key(name);
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!(
+                    "{SELECT_SERIES} JOIN series_aliases alias ON alias.series_id = s.id
+                     WHERE alias.alias_key = ?1"
+                ),
+                [&key],
+                map_series,
+            )
+            .optional()
+        })
+    }
+
+    /// Find the audiobooks of a series in reading order
+    ///
+    /// Books are ordered by series index; books without an index follow,
+    /// ordered by title.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_audiobooks(&self, series_id: &str) -> DbResult<Vec<(Audiobook, Option<f64>)>> {
+        let series_id = series_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {JOINED_AUDIOBOOK_COLUMNS}, m.series_index FROM audiobooks a
+                 JOIN audiobook_series m ON m.audiobook_id = a.id
+                 WHERE m.series_id = ?1
+                 ORDER BY m.series_index IS NULL, m.series_index, a.title COLLATE NOCASE"
+            ))?;
+            let audiobooks =
+                stmt.query_map([&series_id], |row| Ok((map_audiobook(row)?, row.get(13)?)))?;
+            audiobooks.collect()
+        })
+    }
+
+    /// Find the series an audiobook belongs to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_audiobook(&self, audiobook_id: &str) -> DbResult<Vec<SeriesMembership>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_MEMBERSHIPS} WHERE m.audiobook_id = ?1 ORDER BY s.name COLLATE NOCASE"
+            ))?;
+            let memberships = stmt.query_map([&audiobook_id], map_membership)?;
+            memberships.collect()
+        })
+    }
+
+    /// Find the series membership of every audiobook in a library
+    ///
+    /// Books in several series map to the first by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_memberships_in_library(
+        &self,
+        library_id: &str,
+    ) -> DbResult<HashMap<String, SeriesMembership>> {
+        let library_id = library_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_MEMBERSHIPS} JOIN audiobooks a ON a.id = m.audiobook_id
+                 WHERE a.library_id = ?1 ORDER BY s.name COLLATE NOCASE"
+            ))?;
+            let mut memberships = HashMap::new();
+            for membership in stmt.query_map([&library_id], map_membership)? {
+                let membership = membership?;
+                memberships
+                    .entry(membership.audiobook_id.clone())
+                    .or_insert(membership);
+            }
+            Ok(memberships)
+        })
+    }
+
+    /// Put an audiobook into a series, creating the series if needed
+    ///
+    /// Assigning the same series again updates the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the audiobook does not exist.
+    pub fn set_series(
+        &self,
+        audiobook_id: &str,
+        name: &str,
+        index: Option<f64>,
+    ) -> DbResult<SeriesMembership> {
+        let audiobook_id = audiobook_id.to_string();
+        let name = name.to_string();
+        self.execute_transaction(move |tx| {
+            let series_id = resolve_series(tx, &name)?;
+            tx.execute(
+                "INSERT INTO audiobook_series (audiobook_id, series_id, series_index)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(audiobook_id, series_id) DO UPDATE SET
+                    series_index = excluded.series_index",
+                params![&audiobook_id, &series_id, index],
+            )?;
+            tx.query_row(
+                &format!("{SELECT_MEMBERSHIPS} WHERE m.audiobook_id = ?1 AND m.series_id = ?2"),
+                params![&audiobook_id, &series_id],
+                map_membership,
+            )
+        })
+    }
+
+    /// Remove an audiobook from a series
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn remove_from_series(&self, audiobook_id: &str, series_id: &str) -> DbResult<bool> {
+        let audiobook_id = audiobook_id.to_string();
+        let series_id = series_id.to_string();
+        self.execute_query(move |conn| {
+            let removed = conn.execute(
+                "DELETE FROM audiobook_series WHERE audiobook_id = ?1 AND series_id = ?2",
+                params![&audiobook_id, &series_id],
+            )?;
+            Ok(removed > 0)
+        })
+    }
+
+    /// Record another spelling of a series name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the series does not exist.
+    pub fn add_alias(&self, series_id: &str, alias: &str) -> DbResult<()> {
+        let series_id = series_id.to_string();
+        let key = series_key(alias);
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO series_aliases (alias_key, series_id) VALUES (?1, ?2)",
+                params![&key, &series_id],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+impl RepositoryBase for SeriesRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for SeriesRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::AudiobookRepository;
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_series_in_reading_order() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        let mut ids = Vec::new();
+        for title in [
+            "The Well of Ascension (Mistborn #2)",
+            "Secret History",
+            "The Final Empire (Mistborn, Book 1)",
+            "The Eleventh Metal (Mistborn #0.5)",
+        ] {
+            let mut audiobook = Audiobook::new(&db.library_id, format!("/books/{title}.mp3"));
+            audiobook.title = Some(title.to_string());
+            audiobooks.upsert(&audiobook).unwrap();
+            ids.push(audiobook.id);
+        }
+
+        let repo = SeriesRepository::new(enhanced);
+        let mistborn = repo.find_by_name("mistborn").unwrap().unwrap();
+        assert_eq!(mistborn.audiobook_count, 3);
+
+        // Books without an index go last
+        repo.set_series(&ids[1], "Mistborn", None).unwrap();
+        let order: Vec<_> = repo
+            .find_audiobooks(&mistborn.id)
+            .unwrap()
+            .into_iter()
+            .map(|(audiobook, index)| (audiobook.id, index))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (ids[3].clone(), Some(0.5)),
+                (ids[2].clone(), Some(1.0)),
+                (ids[0].clone(), Some(2.0)),
+                (ids[1].clone(), None),
+            ]
+        );
+
+        // A manual index survives re-saving the book
+        let membership = repo.set_series(&ids[0], "Mistborn", Some(3.0)).unwrap();
+        assert_eq!(membership.index_label().as_deref(), Some("3"));
+        let mut audiobook = audiobooks.find_by_id(&ids[0]).unwrap().unwrap();
+        audiobook.narrator = Some("Michael Kramer".to_string());
+        audiobooks.upsert(&audiobook).unwrap();
+        assert_eq!(
+            repo.find_for_audiobook(&ids[0]).unwrap()[0].series_index,
+            Some(3.0)
+        );
+
+        let in_library = repo.find_memberships_in_library(&db.library_id).unwrap();
+        assert_eq!(in_library.len(), 4);
+        assert!(repo.remove_from_series(&ids[1], &mistborn.id).unwrap());
+        assert_eq!(repo.find_all().unwrap()[0].audiobook_count, 3);
+    }
+}
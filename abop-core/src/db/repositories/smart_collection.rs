@@ -0,0 +1,461 @@
+//! Smart collection repository for database operations
+//!
+//! A smart collection stores its rule as JSON and caches the audiobooks that
+//! matched it at the last evaluation. Membership is recomputed in full when
+//! the rule changes or [`SmartCollectionRepository::evaluate`] is called, and
+//! only for the new audiobooks when a scan commits a batch.
+
+use chrono::Utc;
+use rusqlite::types::Type;
+use rusqlite::{Connection, OptionalExtension, Row, params, params_from_iter};
+use std::sync::Arc;
+
+use super::super::error::{DatabaseError, DbResult};
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::catalog::{JOINED_AUDIOBOOK_COLUMNS, map_audiobook};
+use crate::db::datetime_serde::SqliteDateTime;
+use crate::db::query_builder::{QueryBuilder, rule_source};
+use crate::models::{Audiobook, DEFAULT_PROFILE_ID, Rule, SmartCollection};
+
+const SELECT_COLUMNS: &str = "SELECT s.id, s.name, s.profile_id, s.rule,
+        (SELECT COUNT(*) FROM smart_collection_items i WHERE i.smart_collection_id = s.id),
+        s.created_at, s.updated_at
+     FROM smart_collections s";
+
+fn parse_rule(column: usize, json: &str) -> rusqlite::Result<Rule> {
+    serde_json::from_str(json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(e)))
+}
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<SmartCollection> {
+    let rule: String = row.get(3)?;
+    let created_at: SqliteDateTime = row.get(5)?;
+    let updated_at: SqliteDateTime = row.get(6)?;
+    Ok(SmartCollection {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        profile_id: row.get(2)?,
+        rule: parse_rule(3, &rule)?,
+        audiobook_count: row.get(4)?,
+        created_at: created_at.into(),
+        updated_at: updated_at.into(),
+    })
+}
+
+/// Recompute which audiobooks of `only`, or all audiobooks, match a rule
+///
+/// Progress conditions are tested against the progress of `profile_id`.
+fn evaluate_rule(
+    conn: &Connection,
+    collection_id: &str,
+    profile_id: &str,
+    rule: &Rule,
+    only: Option<&[String]>,
+) -> rusqlite::Result<()> {
+    let mut builder = QueryBuilder::new();
+    let collection = builder.bind(collection_id.to_string());
+    let source = rule_source(&builder.bind(profile_id.to_string()));
+    let condition = builder
+        .compile_rule(rule)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+    let Some(audiobook_ids) = only else {
+        conn.execute(
+            "DELETE FROM smart_collection_items WHERE smart_collection_id = ?1",
+            [collection_id],
+        )?;
+        conn.execute(
+            &format!(
+                "INSERT INTO smart_collection_items (smart_collection_id, audiobook_id)
+                 SELECT {collection}, a.id {source} WHERE {condition}"
+            ),
+            params_from_iter(builder.params()),
+        )?;
+        return Ok(());
+    };
+
+    let audiobook = builder.bind(rusqlite::types::Value::Null);
+    let mut params = builder.into_params();
+    let mut delete = conn.prepare(
+        "DELETE FROM smart_collection_items
+         WHERE smart_collection_id = ?1 AND audiobook_id = ?2",
+    )?;
+    let mut insert = conn.prepare(&format!(
+        "INSERT OR IGNORE INTO smart_collection_items (smart_collection_id, audiobook_id)
+         SELECT {collection}, a.id {source} WHERE a.id = {audiobook} AND {condition}"
+    ))?;
+    for id in audiobook_ids {
+        delete.execute(params![collection_id, id])?;
+        if let Some(last) = params.last_mut() {
+            *last = id.clone().into();
+        }
+        insert.execute(params_from_iter(&params))?;
+    }
+    Ok(())
+}
+
+/// Re-evaluate every smart collection, for the given audiobooks or for all
+///
+/// Called inside the scanner's batch transaction so smart collections pick up
+/// new and changed audiobooks as soon as they are committed.
+pub(crate) fn refresh_membership(
+    conn: &Connection,
+    only: Option<&[String]>,
+) -> rusqlite::Result<()> {
+    let rules = {
+        let mut stmt = conn.prepare("SELECT id, profile_id, rule FROM smart_collections")?;
+        stmt.query_map([], |row| {
+            let rule: String = row.get(2)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                parse_rule(2, &rule)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+    for (id, profile_id, rule) in &rules {
+        evaluate_rule(conn, id, profile_id, rule, only)?;
+    }
+    Ok(())
+}
+
+fn validate(name: &str, rule: &Rule) -> DbResult<(String, String)> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::validation_failed(
+            "name",
+            "Smart collection name cannot be empty",
+        ));
+    }
+    QueryBuilder::new().compile_rule(rule)?;
+    let json = serde_json::to_string(rule)
+        .map_err(|e| DatabaseError::validation_failed("rule", &e.to_string()))?;
+    Ok((name.to_string(), json))
+}
+
+/// Repository for rule-based smart collections
+pub struct SmartCollectionRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl SmartCollectionRepository {
+    /// Create a new smart collection repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Create a smart collection for the default profile and evaluate its rule
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty or the rule is invalid.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn create(&self, name: &str, rule: &Rule) -> DbResult<SmartCollection> {
+        self.create_for_profile(DEFAULT_PROFILE_ID, name, rule)
+    }
+
+    /// Create a smart collection whose progress conditions use a profile's progress
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty or the rule is invalid.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the name is taken.
+    pub fn create_for_profile(
+        &self,
+        profile_id: &str,
+        name: &str,
+        rule: &Rule,
+    ) -> DbResult<SmartCollection> {
+        let (name, json) = validate(name, rule)?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let profile_id = profile_id.to_string();
+        let now = SqliteDateTime::from(Utc::now());
+        let rule = rule.clone();
+        self.execute_transaction(move |tx| {
+            tx.execute(
+                "INSERT INTO smart_collections (id, name, profile_id, rule, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                params![&id, &name, &profile_id, &json, &now],
+            )?;
+            evaluate_rule(tx, &id, &profile_id, &rule, None)?;
+            tx.query_row(&format!("{SELECT_COLUMNS} WHERE s.id = ?1"), [&id], map_row)
+        })
+    }
+
+    /// Find all smart collections, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<SmartCollection>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!("{SELECT_COLUMNS} ORDER BY s.name"))?;
+            let collections = stmt.query_map([], map_row)?;
+            collections.collect()
+        })
+    }
+
+    /// Find a smart collection by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_id(&self, id: &str) -> DbResult<Option<SmartCollection>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(&format!("{SELECT_COLUMNS} WHERE s.id = ?1"), [&id], map_row)
+                .optional()
+        })
+    }
+
+    /// Find a smart collection by name, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<SmartCollection>> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!("{SELECT_COLUMNS} WHERE s.name = ?1"),
+                [&name],
+                map_row,
+            )
+            .optional()
+        })
+    }
+
+    /// Find the members of a smart collection as of its last evaluation,
+    /// ordered by title
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_audiobooks(&self, id: &str) -> DbResult<Vec<Audiobook>> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {JOINED_AUDIOBOOK_COLUMNS} FROM audiobooks a
+                 JOIN smart_collection_items i ON i.audiobook_id = a.id
+                 WHERE i.smart_collection_id = ?1
+                 ORDER BY a.title COLLATE NOCASE"
+            ))?;
+            let audiobooks = stmt.query_map([&id], map_audiobook)?;
+            audiobooks.collect()
+        })
+    }
+
+    /// Re-evaluate a smart collection now and return its members, ordered by title
+    ///
+    /// Progress changes such as finishing a book are picked up here rather
+    /// than as they happen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn evaluate(&self, id: &str) -> DbResult<Vec<Audiobook>> {
+        let collection_id = id.to_string();
+        self.execute_transaction(move |tx| {
+            let stored: Option<(String, String)> = tx
+                .query_row(
+                    "SELECT profile_id, rule FROM smart_collections WHERE id = ?1",
+                    [&collection_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if let Some((profile_id, rule)) = stored {
+                let rule = parse_rule(1, &rule)?;
+                evaluate_rule(tx, &collection_id, &profile_id, &rule, None)?;
+            }
+            Ok(())
+        })?;
+        self.find_audiobooks(id)
+    }
+
+    /// Re-evaluate every smart collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn evaluate_all(&self) -> DbResult<()> {
+        self.execute_transaction(move |tx| refresh_membership(tx, None))
+    }
+
+    /// Replace the rule of a smart collection and re-evaluate it
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the rule is invalid.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn update_rule(&self, id: &str, rule: &Rule) -> DbResult<bool> {
+        let (_, json) = validate("rule", rule)?;
+        let id = id.to_string();
+        let rule = rule.clone();
+        let updated_at = SqliteDateTime::from(Utc::now());
+        self.execute_transaction(move |tx| {
+            let profile_id: Option<String> = tx
+                .query_row(
+                    "UPDATE smart_collections SET rule = ?2, updated_at = ?3 WHERE id = ?1
+                     RETURNING profile_id",
+                    params![&id, &json, &updated_at],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(profile_id) = &profile_id {
+                evaluate_rule(tx, &id, profile_id, &rule, None)?;
+            }
+            Ok(profile_id.is_some())
+        })
+    }
+
+    /// Delete a smart collection; its audiobooks are not affected
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete(&self, id: &str) -> DbResult<bool> {
+        let id = id.to_string();
+        self.execute_query(move |conn| {
+            let deleted = conn.execute("DELETE FROM smart_collections WHERE id = ?1", [&id])?;
+            Ok(deleted > 0)
+        })
+    }
+}
+
+impl RepositoryBase for SmartCollectionRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for SmartCollectionRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::{AudiobookRepository, ProgressRepository, TagRepository};
+    use crate::models::Progress;
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_smart_collection_evaluation() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        let mut ids = Vec::new();
+        for (title, narrator, hours) in [
+            ("Dune", "Scott Brick", 21),
+            ("Emma", "Juliet Stevenson", 16),
+            ("Hyperion", "Victor Bevine", 9),
+        ] {
+            let mut audiobook = Audiobook::new(&db.library_id, format!("/books/{title}.mp3"));
+            audiobook.title = Some(title.to_string());
+            audiobook.narrator = Some(narrator.to_string());
+            audiobook.duration_seconds = Some(hours * 3600);
+            audiobooks.upsert(&audiobook).unwrap();
+            ids.push(audiobook.id);
+        }
+        let mut finished = Progress::new(&ids[1], 100);
+        finished.completed = true;
+        ProgressRepository::new(enhanced.clone())
+            .upsert(&finished)
+            .unwrap();
+        TagRepository::new(enhanced.clone())
+            .tag_audiobooks("Favourite", &ids[2..])
+            .unwrap();
+
+        let repo = SmartCollectionRepository::new(enhanced.clone());
+        let long = repo
+            .create(
+                "Long and unfinished",
+                &Rule::parse("completed = false and duration > 10h and added within 30d").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(long.audiobook_count, 1);
+        let picks = repo
+            .create(
+                "Picks",
+                &Rule::parse("tag = favourite or narrator contains brick").unwrap(),
+            )
+            .unwrap();
+        let titles: Vec<_> = repo
+            .find_audiobooks(&picks.id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|audiobook| audiobook.title)
+            .collect();
+        assert_eq!(titles, ["Dune", "Hyperion"]);
+        assert!(repo.create(" ", &Rule::All(Vec::new())).is_err());
+
+        // Incremental refresh only touches the given audiobooks
+        let changed = ids[2..].to_vec();
+        enhanced
+            .with_connection(move |conn| {
+                conn.execute(
+                    "UPDATE audiobooks SET duration_seconds = 72000 WHERE id = ?1",
+                    [&changed[0]],
+                )?;
+                Ok(refresh_membership(conn, Some(&changed))?)
+            })
+            .unwrap();
+        let long = repo.find_by_name("long AND unfinished").unwrap().unwrap();
+        assert_eq!(long.audiobook_count, 2);
+
+        assert!(
+            repo.update_rule(&long.id, &Rule::parse("completed = true").unwrap())
+                .unwrap()
+        );
+        let members = repo.evaluate(&long.id).unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].id, ids[1]);
+
+        assert!(repo.delete(&picks.id).unwrap());
+        assert_eq!(repo.find_all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_scan_batch_updates_membership() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db = crate::db::Database::open(dir.path().join("scan.db")).unwrap();
+        let library = db
+            .libraries()
+            .create("Books", dir.path().to_path_buf())
+            .unwrap();
+        let repo = db.smart_collection_repository();
+        let long = repo
+            .create("Long", &Rule::parse("duration >= 10h").unwrap())
+            .unwrap();
+        assert_eq!(long.audiobook_count, 0);
+
+        let batch: Vec<_> = [("Dune", 21), ("Novella", 2)]
+            .into_iter()
+            .map(|(title, hours)| {
+                let mut audiobook = Audiobook::new(&library.id, dir.path().join(title));
+                audiobook.title = Some(title.to_string());
+                audiobook.duration_seconds = Some(hours * 3600);
+                audiobook
+            })
+            .collect();
+        db.add_audiobooks_bulk(&batch).unwrap();
+
+        let members = repo.find_audiobooks(&long.id).unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].title.as_deref(), Some("Dune"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Sync repository for database operations
+//!
+//! Stores what progress sync needs to remember between runs: the ID this
+//! device writes its events under and the cached content hash of each file.
+
+use rusqlite::{OptionalExtension, params};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+
+/// Settings key of the device ID
+const DEVICE_ID_KEY: &str = "device_id";
+
+/// Repository for progress sync state
+pub struct SyncRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl SyncRepository {
+    /// Create a new sync repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// ID of this device, generated on first use
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn device_id(&self) -> DbResult<String> {
+        let generated = uuid::Uuid::new_v4().to_string();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT OR IGNORE INTO sync_settings (key, value) VALUES (?1, ?2)",
+                [DEVICE_ID_KEY, &generated],
+            )?;
+            conn.query_row(
+                "SELECT value FROM sync_settings WHERE key = ?1",
+                [DEVICE_ID_KEY],
+                |row| row.get(0),
+            )
+        })
+    }
+
+    /// Event each book was last merged from for a profile, by content hash
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn merged_heads(&self, profile_id: &str) -> DbResult<HashMap<String, String>> {
+        let profile_id = profile_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn
+                .prepare("SELECT content_hash, event_id FROM sync_heads WHERE profile_id = ?1")?;
+            let heads = stmt.query_map([&profile_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            heads.collect()
+        })
+    }
+
+    /// Remember the events books were merged from for a profile
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn set_merged_heads(&self, profile_id: &str, heads: &[(String, String)]) -> DbResult<()> {
+        let profile_id = profile_id.to_string();
+        let heads = heads.to_vec();
+        self.execute_transaction(move |tx| {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO sync_heads (profile_id, content_hash, event_id)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for (content_hash, event_id) in &heads {
+                stmt.execute([&profile_id, content_hash, event_id])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Cached content hash of an audiobook, if the file has not changed since
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn cached_hash(
+        &self,
+        audiobook_id: &str,
+        size_bytes: u64,
+        modified_secs: i64,
+    ) -> DbResult<Option<String>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                "SELECT content_hash FROM audiobook_hashes
+                 WHERE audiobook_id = ?1 AND size_bytes = ?2 AND modified_secs = ?3",
+                params![&audiobook_id, size_bytes, modified_secs],
+                |row| row.get(0),
+            )
+            .optional()
+        })
+    }
+
+    /// Save the content hash of an audiobook for its current size and modification time
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn store_hash(
+        &self,
+        audiobook_id: &str,
+        content_hash: &str,
+        size_bytes: u64,
+        modified_secs: i64,
+    ) -> DbResult<()> {
+        let audiobook_id = audiobook_id.to_string();
+        let content_hash = content_hash.to_string();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO audiobook_hashes
+                    (audiobook_id, content_hash, size_bytes, modified_secs)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![&audiobook_id, &content_hash, size_bytes, modified_secs],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+impl RepositoryBase for SyncRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for SyncRepository {}
@@ -0,0 +1,300 @@
+//! Tag repository for database operations
+//!
+//! Tags are addressed by name. Tagging creates the tag on first use, and
+//! names match ignoring case so `Sci-Fi` and `sci-fi` are the same tag.
+
+use rusqlite::{OptionalExtension, Row, Transaction, params};
+use std::sync::Arc;
+
+use super::super::error::{DatabaseError, DbResult};
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::db::EnhancedConnection;
+use crate::db::catalog::{JOINED_AUDIOBOOK_COLUMNS, map_audiobook};
+use crate::models::{Audiobook, Tag};
+
+const SELECT_COLUMNS: &str = "SELECT t.id, t.name,
+        (SELECT COUNT(*) FROM audiobook_tags l WHERE l.tag_id = t.id)
+     FROM tags t";
+
+fn map_row(row: &Row<'_>) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        audiobook_count: row.get(2)?,
+    })
+}
+
+/// ID of the tag with the given name, created on first use
+fn resolve_tag(tx: &Transaction<'_>, name: &str) -> rusqlite::Result<String> {
+    let existing: Option<String> = tx
+        .query_row("SELECT id FROM tags WHERE name = ?1", [name], |row| {
+            row.get(0)
+        })
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    tx.execute(
+        "INSERT INTO tags (id, name) VALUES (?1, ?2)",
+        params![&id, name],
+    )?;
+    Ok(id)
+}
+
+fn validate_name(name: &str) -> DbResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::validation_failed(
+            "name",
+            "Tag name cannot be empty",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Repository for free-form audiobook tags
+pub struct TagRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl TagRepository {
+    /// Create a new tag repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Find all tags that are in use, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_all(&self) -> DbResult<Vec<Tag>> {
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS}
+                 WHERE EXISTS (SELECT 1 FROM audiobook_tags l WHERE l.tag_id = t.id)
+                 ORDER BY t.name COLLATE NOCASE"
+            ))?;
+            let tags = stmt.query_map([], map_row)?;
+            tags.collect()
+        })
+    }
+
+    /// Find a tag by name, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_by_name(&self, name: &str) -> DbResult<Option<Tag>> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                &format!("{SELECT_COLUMNS} WHERE t.name = ?1"),
+                [&name],
+                map_row,
+            )
+            .optional()
+        })
+    }
+
+    /// Find the tags of an audiobook, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_for_audiobook(&self, audiobook_id: &str) -> DbResult<Vec<Tag>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{SELECT_COLUMNS} JOIN audiobook_tags l ON l.tag_id = t.id
+                 WHERE l.audiobook_id = ?1 ORDER BY t.name COLLATE NOCASE"
+            ))?;
+            let tags = stmt.query_map([&audiobook_id], map_row)?;
+            tags.collect()
+        })
+    }
+
+    /// Find the audiobooks with a tag, ordered by title
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails.
+    pub fn find_audiobooks(&self, name: &str) -> DbResult<Vec<Audiobook>> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {JOINED_AUDIOBOOK_COLUMNS} FROM audiobooks a
+                 JOIN audiobook_tags l ON l.audiobook_id = a.id
+                 JOIN tags t ON t.id = l.tag_id
+                 WHERE t.name = ?1
+                 ORDER BY a.title COLLATE NOCASE"
+            ))?;
+            let audiobooks = stmt.query_map([&name], map_audiobook)?;
+            audiobooks.collect()
+        })
+    }
+
+    /// Add a tag to audiobooks, creating the tag if needed
+    ///
+    /// Returns the number of audiobooks that did not have the tag yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. an audiobook does not exist.
+    pub fn tag_audiobooks(&self, name: &str, audiobook_ids: &[String]) -> DbResult<usize> {
+        let name = validate_name(name)?;
+        let audiobook_ids = audiobook_ids.to_vec();
+        self.execute_transaction(move |tx| {
+            let tag_id = resolve_tag(tx, &name)?;
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO audiobook_tags (audiobook_id, tag_id) VALUES (?1, ?2)",
+            )?;
+            let mut tagged = 0;
+            for id in &audiobook_ids {
+                tagged += insert.execute(params![id, &tag_id])?;
+            }
+            Ok(tagged)
+        })
+    }
+
+    /// Add a tag to every selected audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ValidationFailed`] if the name is empty.
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn tag_selected(&self, name: &str) -> DbResult<usize> {
+        let name = validate_name(name)?;
+        self.execute_transaction(move |tx| {
+            let tag_id = resolve_tag(tx, &name)?;
+            tx.execute(
+                "INSERT OR IGNORE INTO audiobook_tags (audiobook_id, tag_id)
+                 SELECT id, ?1 FROM audiobooks WHERE selected = 1",
+                [&tag_id],
+            )
+        })
+    }
+
+    /// Remove a tag from audiobooks
+    ///
+    /// Returns the number of audiobooks that had the tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn untag_audiobooks(&self, name: &str, audiobook_ids: &[String]) -> DbResult<usize> {
+        let name = name.trim().to_string();
+        let audiobook_ids = audiobook_ids.to_vec();
+        self.execute_transaction(move |tx| {
+            let mut delete = tx.prepare(
+                "DELETE FROM audiobook_tags WHERE audiobook_id = ?1
+                 AND tag_id = (SELECT id FROM tags WHERE name = ?2)",
+            )?;
+            let mut untagged = 0;
+            for id in &audiobook_ids {
+                untagged += delete.execute(params![id, &name])?;
+            }
+            Ok(untagged)
+        })
+    }
+
+    /// Remove a tag from every selected audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn untag_selected(&self, name: &str) -> DbResult<usize> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "DELETE FROM audiobook_tags
+                 WHERE tag_id = (SELECT id FROM tags WHERE name = ?1)
+                   AND audiobook_id IN (SELECT id FROM audiobooks WHERE selected = 1)",
+                [&name],
+            )
+        })
+    }
+
+    /// Delete a tag from all audiobooks
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete(&self, name: &str) -> DbResult<bool> {
+        let name = name.trim().to_string();
+        self.execute_query(move |conn| {
+            let deleted = conn.execute("DELETE FROM tags WHERE name = ?1", [&name])?;
+            Ok(deleted > 0)
+        })
+    }
+}
+
+impl RepositoryBase for TagRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for TagRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::AudiobookRepository;
+    use crate::test_utils::TestDatabase;
+
+    #[test]
+    fn test_bulk_tagging() {
+        let db = TestDatabase::new();
+        let enhanced = db.connection.clone();
+
+        let audiobooks = AudiobookRepository::new(enhanced.clone());
+        let mut ids = Vec::new();
+        for (title, selected) in [("Dune", true), ("Emma", false), ("Hyperion", true)] {
+            let mut audiobook = Audiobook::new(&db.library_id, format!("/books/{title}.mp3"));
+            audiobook.title = Some(title.to_string());
+            audiobook.selected = selected;
+            audiobooks.upsert(&audiobook).unwrap();
+            ids.push(audiobook.id);
+        }
+
+        let repo = TagRepository::new(enhanced);
+        assert_eq!(repo.tag_selected("Sci-Fi").unwrap(), 2);
+        assert_eq!(repo.tag_audiobooks("sci-fi", &ids).unwrap(), 1);
+        assert!(repo.tag_audiobooks(" ", &ids).is_err());
+        assert_eq!(repo.tag_audiobooks("Classic", &ids[1..2]).unwrap(), 1);
+
+        let tags = repo.find_all().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[1].name, "Sci-Fi");
+        assert_eq!(tags[1].audiobook_count, 3);
+
+        assert_eq!(repo.untag_selected("SCI-FI").unwrap(), 2);
+        let titles: Vec<_> = repo
+            .find_audiobooks("Sci-Fi")
+            .unwrap()
+            .into_iter()
+            .filter_map(|audiobook| audiobook.title)
+            .collect();
+        assert_eq!(titles, ["Emma"]);
+        assert_eq!(repo.find_for_audiobook(&ids[1]).unwrap().len(), 2);
+
+        assert_eq!(repo.untag_audiobooks("Classic", &ids).unwrap(), 1);
+        assert!(repo.delete("Sci-Fi").unwrap());
+        assert!(repo.find_all().unwrap().is_empty());
+    }
+}
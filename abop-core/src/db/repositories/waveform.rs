@@ -0,0 +1,174 @@
+//! Waveform repository for database operations
+//!
+//! This module caches serialized waveform summaries per audiobook so the
+//! player scrubber does not need to decode a file every time it is shown.
+
+use rusqlite::{OptionalExtension, params};
+use std::sync::Arc;
+
+use super::super::error::DbResult;
+use super::{EnhancedRepository, Repository, RepositoryBase};
+use crate::audio::waveform::{WaveformConfig, WaveformSummary};
+use crate::db::EnhancedConnection;
+
+/// A waveform summary stored in the database together with its cache key
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedWaveform {
+    /// ID of the audiobook the summary belongs to
+    pub audiobook_id: String,
+    /// Points per second the summary was generated with
+    pub points_per_second: u32,
+    /// Size of the source file when the summary was generated
+    pub source_size_bytes: Option<u64>,
+    /// The cached summary
+    pub summary: WaveformSummary,
+}
+
+impl CachedWaveform {
+    /// Checks whether the cached summary matches the given configuration and file size
+    #[must_use]
+    pub fn is_current(&self, config: &WaveformConfig, source_size_bytes: Option<u64>) -> bool {
+        self.points_per_second == config.points_per_second
+            && (source_size_bytes.is_none() || self.source_size_bytes == source_size_bytes)
+    }
+}
+
+/// Repository for cached waveform summaries
+pub struct WaveformRepository {
+    enhanced_connection: Arc<EnhancedConnection>,
+}
+
+impl WaveformRepository {
+    /// Create a new waveform repository
+    #[must_use]
+    pub const fn new(enhanced_connection: Arc<EnhancedConnection>) -> Self {
+        Self {
+            enhanced_connection,
+        }
+    }
+
+    /// Save or replace the waveform summary for an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails, e.g. the audiobook does not exist.
+    pub fn upsert(
+        &self,
+        audiobook_id: &str,
+        points_per_second: u32,
+        source_size_bytes: Option<u64>,
+        summary: &WaveformSummary,
+    ) -> DbResult<()> {
+        let audiobook_id = audiobook_id.to_string();
+        let data = summary.to_bytes();
+        self.execute_query(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO waveform_summaries (
+                    audiobook_id, points_per_second, source_size_bytes, data
+                ) VALUES (?1, ?2, ?3, ?4)",
+                params![&audiobook_id, points_per_second, source_size_bytes, &data],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Find the cached waveform for an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL query fails or the stored data cannot be parsed.
+    pub fn find_by_audiobook(&self, audiobook_id: &str) -> DbResult<Option<CachedWaveform>> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            conn.query_row(
+                "SELECT audiobook_id, points_per_second, source_size_bytes, data
+                 FROM waveform_summaries WHERE audiobook_id = ?1",
+                [&audiobook_id],
+                |row| {
+                    let data: Vec<u8> = row.get(3)?;
+                    let summary = WaveformSummary::from_bytes(&data).map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            3,
+                            rusqlite::types::Type::Blob,
+                            Box::new(e),
+                        )
+                    })?;
+                    Ok(CachedWaveform {
+                        audiobook_id: row.get(0)?,
+                        points_per_second: row.get(1)?,
+                        source_size_bytes: row.get(2)?,
+                        summary,
+                    })
+                },
+            )
+            .optional()
+        })
+    }
+
+    /// Delete the cached waveform for an audiobook
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::ConnectionFailed`] if unable to acquire database connection.
+    /// Returns [`DatabaseError::Sqlite`] if the SQL execution fails.
+    pub fn delete_by_audiobook(&self, audiobook_id: &str) -> DbResult<bool> {
+        let audiobook_id = audiobook_id.to_string();
+        self.execute_query(move |conn| {
+            let rows_affected = conn.execute(
+                "DELETE FROM waveform_summaries WHERE audiobook_id = ?1",
+                [&audiobook_id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+}
+
+impl RepositoryBase for WaveformRepository {
+    fn connect(&self) -> &Arc<EnhancedConnection> {
+        &self.enhanced_connection
+    }
+}
+
+impl EnhancedRepository for WaveformRepository {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::waveform::WaveformBuilder;
+    use crate::test_utils::TestDatabase;
+
+    fn summary() -> WaveformSummary {
+        let mut builder = WaveformBuilder::new(WaveformConfig::default(), 8000, 1);
+        builder.push_interleaved(&[0.5, -0.5, 0.25, -0.25].repeat(4000));
+        builder.finish()
+    }
+
+    #[test]
+    fn test_upsert_and_find() {
+        let db = TestDatabase::new();
+        let audiobook_id = db.add_audiobook();
+        let repo = WaveformRepository::new(db.connection.clone());
+        let summary = summary();
+
+        repo.upsert(&audiobook_id, 4, Some(1234), &summary).unwrap();
+        let cached = repo.find_by_audiobook(&audiobook_id).unwrap().unwrap();
+
+        assert_eq!(cached.summary, summary);
+        assert!(cached.is_current(&WaveformConfig::default(), Some(1234)));
+        assert!(!cached.is_current(&WaveformConfig::default(), Some(99)));
+    }
+
+    #[test]
+    fn test_delete() {
+        let db = TestDatabase::new();
+        let audiobook_id = db.add_audiobook();
+        let repo = WaveformRepository::new(db.connection.clone());
+
+        repo.upsert(&audiobook_id, 4, None, &summary()).unwrap();
+        assert!(repo.delete_by_audiobook(&audiobook_id).unwrap());
+        assert!(repo.find_by_audiobook(&audiobook_id).unwrap().is_none());
+        assert!(!repo.delete_by_audiobook(&audiobook_id).unwrap());
+    }
+}
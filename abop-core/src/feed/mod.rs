@@ -0,0 +1,435 @@
+//! Private podcast feeds of a library or collection
+//!
+//! A podcast app is the easiest way to get audiobooks onto a phone, so a
+//! library or collection can be published as an RSS 2.0 feed with the iTunes
+//! namespace. Every audiobook file becomes an episode. Files that share a
+//! folder and a title are the parts of one multi-file book and are numbered
+//! as such.
+//!
+//! Enclosure and cover URLs start from a configurable base URL and follow
+//! an [`EnclosureLayout`]: either the routes of the ABOP server, or the
+//! library folder served as static files with the covers written next to the
+//! feed. Feeds are marked serial and blocked from podcast directories, as
+//! they are meant for the listener alone.
+
+mod rss;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::audio::AudioFormat;
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::Audiobook;
+
+/// Folder, relative to the feed, that covers are written to in the
+/// [`EnclosureLayout::Files`] layout
+pub const COVERS_DIR: &str = "covers";
+
+/// How enclosure and cover URLs are built from the base URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnclosureLayout {
+    /// The base URL is an ABOP server: audio and covers come from its
+    /// `/api/books/{id}/stream` and `/api/books/{id}/cover` routes
+    #[default]
+    Server,
+    /// The base URL serves the library folder as static files, and covers
+    /// from [`COVERS_DIR`] next to the feed
+    Files,
+}
+
+impl fmt::Display for EnclosureLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Server => "server",
+            Self::Files => "files",
+        })
+    }
+}
+
+impl FromStr for EnclosureLayout {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "server" => Ok(Self::Server),
+            "files" | "static" => Ok(Self::Files),
+            other => Err(AppError::Parse(format!(
+                "Unknown enclosure layout '{other}', expected server or files"
+            ))),
+        }
+    }
+}
+
+/// What a feed publishes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedSource {
+    /// Every audiobook in a library, by library ID
+    Library(String),
+    /// The audiobooks of a collection in collection order, by collection ID
+    Collection(String),
+}
+
+/// Options of a generated feed
+#[derive(Debug, Clone)]
+pub struct FeedOptions {
+    /// URL that enclosure and cover URLs start from
+    pub base_url: String,
+    /// How URLs are built from the base URL
+    pub layout: EnclosureLayout,
+    /// Server token added to URLs in the [`EnclosureLayout::Server`] layout
+    pub token: Option<String>,
+    /// Channel title, instead of the library or collection name
+    pub title: Option<String>,
+}
+
+impl FeedOptions {
+    /// Options for the server layout at `base_url`
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            layout: EnclosureLayout::default(),
+            token: None,
+            title: None,
+        }
+    }
+
+    /// Use another URL layout
+    #[must_use]
+    pub const fn with_layout(mut self, layout: EnclosureLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Add the server token to URLs
+    #[must_use]
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Override the channel title
+    #[must_use]
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+}
+
+/// One episode of a feed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Episode {
+    /// Stable identifier, the audiobook ID
+    pub guid: String,
+    /// Episode title
+    pub title: String,
+    /// Author of the book
+    pub author: Option<String>,
+    /// Episode description
+    pub description: String,
+    /// Duration in seconds, if known
+    pub duration_seconds: Option<u64>,
+    /// URL of the audio file
+    pub enclosure_url: String,
+    /// Size of the audio file in bytes
+    pub enclosure_length: u64,
+    /// MIME type of the audio file
+    pub enclosure_type: String,
+    /// URL of the cover, if the book has one
+    pub image_url: Option<String>,
+    /// Position of the episode in the feed, starting at 1
+    pub number: usize,
+    /// Publication date; increases with the episode number
+    pub published: DateTime<Utc>,
+}
+
+/// A cover image to write next to the feed in the [`EnclosureLayout::Files`] layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverFile {
+    /// File name within [`COVERS_DIR`]
+    pub file_name: String,
+    /// Image data
+    pub data: Vec<u8>,
+}
+
+/// A podcast feed ready to be rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// Channel title
+    pub title: String,
+    /// Channel link, the base URL
+    pub link: String,
+    /// Channel description
+    pub description: String,
+    /// Author shown for the channel, when every book has the same one
+    pub author: Option<String>,
+    /// Channel artwork, the first episode cover
+    pub image_url: Option<String>,
+    /// When the newest book was last changed
+    pub updated: DateTime<Utc>,
+    /// Episodes in listening order
+    pub episodes: Vec<Episode>,
+    /// Covers to write next to the feed
+    pub covers: Vec<CoverFile>,
+}
+
+impl Feed {
+    /// Build the feed of a library or collection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library or collection does not exist or the
+    /// database cannot be read.
+    pub fn build(db: &Database, source: &FeedSource, options: &FeedOptions) -> Result<Self> {
+        let (name, description, audiobooks) = match source {
+            FeedSource::Library(id) => {
+                let library = db.library_repository().find_by_id(id)?.ok_or_else(|| {
+                    AppError::ValidationFailed(format!("Library does not exist: {id}"))
+                })?;
+                let mut audiobooks = db.get_audiobooks_in_library(id)?;
+                audiobooks.sort_by_cached_key(|book| {
+                    (
+                        book.display_author().to_lowercase(),
+                        book.display_title().to_lowercase(),
+                        book.path.clone(),
+                    )
+                });
+                let description = format!("Audiobooks in {}", library.name);
+                (library.name, description, audiobooks)
+            }
+            FeedSource::Collection(id) => {
+                let repo = db.collection_repository();
+                let collection = repo.find_by_id(id)?.ok_or_else(|| {
+                    AppError::ValidationFailed(format!("Collection does not exist: {id}"))
+                })?;
+                let description = collection
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| format!("Audiobooks in the {} collection", collection.name));
+                (collection.name, description, repo.find_audiobooks(id)?)
+            }
+        };
+
+        let roots = db
+            .get_libraries()?
+            .into_iter()
+            .map(|library| (library.id, library.path))
+            .collect::<HashMap<_, _>>();
+        let title = options.title.clone().unwrap_or(name);
+        Ok(Self::from_audiobooks(
+            &title,
+            &description,
+            &audiobooks,
+            &roots,
+            options,
+        ))
+    }
+
+    /// Build a feed from audiobooks already in listening order
+    ///
+    /// `library_roots` maps library IDs to their folders, which the
+    /// [`EnclosureLayout::Files`] layout builds relative URLs from.
+    #[must_use]
+    pub fn from_audiobooks<P: AsRef<Path>>(
+        title: &str,
+        description: &str,
+        audiobooks: &[Audiobook],
+        library_roots: &HashMap<String, P>,
+        options: &FeedOptions,
+    ) -> Self {
+        let urls = UrlBuilder {
+            options,
+            library_roots,
+        };
+        let parts = part_counts(audiobooks);
+        let first_published = audiobooks
+            .iter()
+            .map(|book| book.created_at)
+            .min()
+            .unwrap_or_else(Utc::now);
+
+        let mut covers = Vec::new();
+        let episodes: Vec<Episode> = audiobooks
+            .iter()
+            .enumerate()
+            .map(|(index, book)| {
+                let image_url = urls.cover(book);
+                if image_url.is_some()
+                    && options.layout == EnclosureLayout::Files
+                    && let Some(data) = &book.cover_art
+                {
+                    covers.push(CoverFile {
+                        file_name: cover_file_name(book),
+                        data: data.clone(),
+                    });
+                }
+                let is_part = parts.get(&part_key(book)).is_some_and(|count| *count > 1);
+                Episode {
+                    guid: book.id.clone(),
+                    title: episode_title(book, is_part),
+                    author: book.author.clone(),
+                    description: episode_description(book),
+                    duration_seconds: book.duration_seconds,
+                    enclosure_url: urls.enclosure(book),
+                    enclosure_length: book
+                        .size_bytes
+                        .or_else(|| std::fs::metadata(&book.path).ok().map(|meta| meta.len()))
+                        .unwrap_or_default(),
+                    enclosure_type: AudioFormat::from_path(&book.path)
+                        .map_or("application/octet-stream", |format| format.mime_type())
+                        .to_string(),
+                    image_url,
+                    number: index + 1,
+                    // A minute apart, so apps sorting by date keep the order
+                    published: first_published
+                        + Duration::minutes(i64::try_from(index).unwrap_or(i64::MAX / 60)),
+                }
+            })
+            .collect();
+
+        let mut authors = audiobooks.iter().map(|book| book.author.as_deref());
+        let author = match authors.next() {
+            Some(first) if authors.all(|author| author == first) => first.map(str::to_string),
+            _ => None,
+        };
+        Self {
+            title: title.to_string(),
+            link: options.base_url.clone(),
+            description: description.to_string(),
+            author,
+            image_url: episodes
+                .iter()
+                .find_map(|episode| episode.image_url.clone()),
+            updated: audiobooks
+                .iter()
+                .map(|book| book.updated_at)
+                .max()
+                .unwrap_or(first_published),
+            episodes,
+            covers,
+        }
+    }
+
+    /// Render the feed as RSS 2.0 with the iTunes namespace
+    #[must_use]
+    pub fn to_xml(&self) -> String {
+        rss::render(self)
+    }
+}
+
+struct UrlBuilder<'a, P> {
+    options: &'a FeedOptions,
+    library_roots: &'a HashMap<String, P>,
+}
+
+impl<P: AsRef<Path>> UrlBuilder<'_, P> {
+    fn enclosure(&self, book: &Audiobook) -> String {
+        let base = &self.options.base_url;
+        match self.options.layout {
+            EnclosureLayout::Server => {
+                self.with_token(format!("{base}/api/books/{}/stream", book.id))
+            }
+            EnclosureLayout::Files => {
+                let relative = self
+                    .library_roots
+                    .get(&book.library_id)
+                    .and_then(|root| book.path.strip_prefix(root).ok())
+                    .unwrap_or_else(|| Path::new(book.file_name().unwrap_or_default()));
+                let path = relative
+                    .components()
+                    .map(|part| encode_path_segment(&part.as_os_str().to_string_lossy()))
+                    .collect::<Vec<_>>()
+                    .join("/");
+                format!("{base}/{path}")
+            }
+        }
+    }
+
+    fn cover(&self, book: &Audiobook) -> Option<String> {
+        book.cover_art.as_ref()?;
+        let base = &self.options.base_url;
+        Some(match self.options.layout {
+            EnclosureLayout::Server => {
+                self.with_token(format!("{base}/api/books/{}/cover", book.id))
+            }
+            EnclosureLayout::Files => format!("{base}/{COVERS_DIR}/{}", cover_file_name(book)),
+        })
+    }
+
+    fn with_token(&self, url: String) -> String {
+        match &self.options.token {
+            Some(token) => format!("{url}?token={}", encode_path_segment(token)),
+            None => url,
+        }
+    }
+}
+
+/// Files that share a folder and a title form one multi-file book
+fn part_key(book: &Audiobook) -> (String, Option<&Path>, String) {
+    (
+        book.library_id.clone(),
+        book.path.parent(),
+        book.display_title().to_lowercase(),
+    )
+}
+
+fn part_counts(audiobooks: &[Audiobook]) -> HashMap<(String, Option<&Path>, String), usize> {
+    let mut counts = HashMap::new();
+    for book in audiobooks {
+        *counts.entry(part_key(book)).or_default() += 1;
+    }
+    counts
+}
+
+/// Parts of a multi-file book are told apart by their file name
+fn episode_title(book: &Audiobook, is_part: bool) -> String {
+    let title = book.display_title();
+    match book.path.file_stem() {
+        Some(stem) if is_part => format!("{title}: {}", stem.to_string_lossy()),
+        _ => title,
+    }
+}
+
+fn episode_description(book: &Audiobook) -> String {
+    if let Some(description) = book.description.as_deref().map(str::trim)
+        && !description.is_empty()
+    {
+        return description.to_string();
+    }
+    match &book.narrator {
+        Some(narrator) => format!("By {}, read by {narrator}", book.display_author()),
+        None => format!("By {}", book.display_author()),
+    }
+}
+
+fn cover_file_name(book: &Audiobook) -> String {
+    let extension = match book.cover_mime_type() {
+        Some("image/png") => "png",
+        Some("image/gif") => "gif",
+        Some("image/webp") => "webp",
+        _ => "jpg",
+    };
+    format!("{}.{extension}", book.id)
+}
+
+/// Percent-encode everything but unreserved URL characters
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+//! RSS 2.0 rendering with the iTunes podcast namespace
+
+use super::{Episode, Feed};
+use crate::utils::time::{TimeFormat, format_seconds};
+use crate::utils::xml::XmlWriter;
+
+const ITUNES_NAMESPACE: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+
+/// Render a feed as an RSS document
+pub(super) fn render(feed: &Feed) -> String {
+    let mut xml = XmlWriter::new();
+    xml.open(
+        "rss",
+        &[("version", "2.0"), ("xmlns:itunes", ITUNES_NAMESPACE)],
+    );
+    xml.open("channel", &[]);
+    xml.text("title", &feed.title);
+    xml.text("link", &feed.link);
+    xml.text("description", &feed.description);
+    xml.text("generator", "ABOP");
+    xml.text("lastBuildDate", &feed.updated.to_rfc2822());
+    if let Some(author) = &feed.author {
+        xml.text("itunes:author", author);
+    }
+    xml.text("itunes:summary", &feed.description);
+    xml.text("itunes:type", "serial");
+    xml.text("itunes:explicit", "false");
+    // Private feed: keep it out of podcast directories
+    xml.text("itunes:block", "yes");
+    if let Some(image) = &feed.image_url {
+        xml.empty("itunes:image", &[("href", image)]);
+    }
+    for episode in &feed.episodes {
+        render_episode(&mut xml, episode);
+    }
+    xml.close("channel");
+    xml.close("rss");
+    xml.finish()
+}
+
+fn render_episode(xml: &mut XmlWriter, episode: &Episode) {
+    xml.open("item", &[]);
+    xml.text("title", &episode.title);
+    xml.text("description", &episode.description);
+    xml.empty(
+        "enclosure",
+        &[
+            ("url", &episode.enclosure_url),
+            ("length", &episode.enclosure_length.to_string()),
+            ("type", &episode.enclosure_type),
+        ],
+    );
+    xml.element("guid", &[("isPermaLink", "false")], &episode.guid);
+    xml.text("pubDate", &episode.published.to_rfc2822());
+    if let Some(author) = &episode.author {
+        xml.text("itunes:author", author);
+    }
+    if let Some(duration) = episode.duration_seconds {
+        xml.text(
+            "itunes:duration",
+            &format_seconds(duration, TimeFormat::AlwaysHours),
+        );
+    }
+    if let Some(image) = &episode.image_url {
+        xml.empty("itunes:image", &[("href", image)]);
+    }
+    xml.text("itunes:episode", &episode.number.to_string());
+    xml.text("itunes:episodeType", "full");
+    xml.close("item");
+}
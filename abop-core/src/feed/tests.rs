@@ -0,0 +1,234 @@
+use super::*;
+use roxmltree::{Document, Node};
+use std::path::PathBuf;
+
+const ITUNES: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+
+fn book(library_id: &str, path: PathBuf, title: &str, size: u64) -> Audiobook {
+    let mut audiobook = Audiobook::new(library_id, path);
+    audiobook.title = Some(title.to_string());
+    audiobook.author = Some("J. R. R. Tolkien".to_string());
+    audiobook.size_bytes = Some(size);
+    audiobook
+}
+
+fn child<'a>(node: Node<'a, 'a>, name: &str) -> Node<'a, 'a> {
+    child_in(node, "", name)
+}
+
+fn child_in<'a>(node: Node<'a, 'a>, namespace: &str, name: &str) -> Node<'a, 'a> {
+    node.children()
+        .find(|child| {
+            child.tag_name().name() == name
+                && child.tag_name().namespace().unwrap_or("") == namespace
+        })
+        .unwrap_or_else(|| panic!("missing <{name}> in <{}>", node.tag_name().name()))
+}
+
+fn text<'a>(node: Node<'a, 'a>, name: &str) -> &'a str {
+    child(node, name).text().unwrap_or_default()
+}
+
+#[test]
+fn test_library_feed_is_valid_rss() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::in_memory().unwrap();
+    let library = db
+        .libraries()
+        .create("Fantasy & <Myth>", dir.path().to_path_buf())
+        .unwrap();
+
+    let mut silmarillion = book(
+        &library.id,
+        dir.path().join("silmarillion.m4b"),
+        "The Silmarillion",
+        900,
+    );
+    silmarillion.description = Some("Tales of the First Age".to_string());
+    silmarillion.duration_seconds = Some(3661);
+    silmarillion.cover_art = Some(vec![0xFF, 0xD8, 0xFF, 0xE0]);
+    let hobbit_dir = dir.path().join("hobbit");
+    let part_two = book(&library.id, hobbit_dir.join("02.mp3"), "The Hobbit", 200);
+    let mut part_one = book(&library.id, hobbit_dir.join("01.mp3"), "The Hobbit", 100);
+    part_one.narrator = Some("Andy Serkis".to_string());
+    for audiobook in [&silmarillion, &part_two, &part_one] {
+        db.audiobook_repository().upsert(audiobook).unwrap();
+    }
+
+    let options = FeedOptions::new("http://phone.lan:8765/").with_token("a&b");
+    let feed = Feed::build(&db, &FeedSource::Library(library.id.clone()), &options).unwrap();
+    let xml = feed.to_xml();
+    let document = Document::parse(&xml).unwrap();
+
+    let rss = document.root_element();
+    assert_eq!(rss.tag_name().name(), "rss");
+    assert_eq!(rss.attribute("version"), Some("2.0"));
+    let channel = child(rss, "channel");
+    assert_eq!(text(channel, "title"), "Fantasy & <Myth>");
+    assert_eq!(text(channel, "link"), "http://phone.lan:8765");
+    assert_eq!(
+        child_in(channel, ITUNES, "author").text(),
+        Some("J. R. R. Tolkien")
+    );
+    assert_eq!(child_in(channel, ITUNES, "type").text(), Some("serial"));
+    assert_eq!(child_in(channel, ITUNES, "block").text(), Some("yes"));
+    let cover_url = format!(
+        "http://phone.lan:8765/api/books/{}/cover?token=a%26b",
+        silmarillion.id
+    );
+    assert_eq!(
+        child_in(channel, ITUNES, "image").attribute("href"),
+        Some(cover_url.as_str())
+    );
+
+    // Sorted by author then title, with the parts of a book in file order
+    let items: Vec<Node> = channel
+        .children()
+        .filter(|node| node.has_tag_name("item"))
+        .collect();
+    let titles: Vec<&str> = items.iter().map(|item| text(*item, "title")).collect();
+    assert_eq!(
+        titles,
+        ["The Hobbit: 01", "The Hobbit: 02", "The Silmarillion"]
+    );
+
+    let first = items[0];
+    assert_eq!(text(first, "guid"), part_one.id);
+    assert_eq!(child(first, "guid").attribute("isPermaLink"), Some("false"));
+    assert_eq!(
+        text(first, "description"),
+        "By J. R. R. Tolkien, read by Andy Serkis"
+    );
+    let enclosure = child(first, "enclosure");
+    assert_eq!(
+        enclosure.attribute("url"),
+        Some(
+            format!(
+                "http://phone.lan:8765/api/books/{}/stream?token=a%26b",
+                part_one.id
+            )
+            .as_str()
+        )
+    );
+    assert_eq!(enclosure.attribute("length"), Some("100"));
+    assert_eq!(enclosure.attribute("type"), Some("audio/mpeg"));
+    assert_eq!(child_in(first, ITUNES, "episode").text(), Some("1"));
+
+    let last = items[2];
+    assert_eq!(text(last, "description"), "Tales of the First Age");
+    assert_eq!(
+        child(last, "enclosure").attribute("type"),
+        Some("audio/mp4")
+    );
+    assert_eq!(child_in(last, ITUNES, "duration").text(), Some("01:01:01"));
+    assert_eq!(
+        child_in(last, ITUNES, "image").attribute("href"),
+        Some(cover_url.as_str())
+    );
+
+    let dates: Vec<_> = items
+        .iter()
+        .map(|item| chrono::DateTime::parse_from_rfc2822(text(*item, "pubDate")).unwrap())
+        .collect();
+    assert!(dates.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(feed.covers.is_empty());
+}
+
+#[test]
+fn test_collection_feed_with_static_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::in_memory().unwrap();
+    let root = dir.path().join("books");
+    let library = db.libraries().create("Books", root.clone()).unwrap();
+
+    let mut first = book(
+        &library.id,
+        root.join("Tolkien/The Hobbit.mp3"),
+        "The Hobbit",
+        10,
+    );
+    first.cover_art = Some(b"\x89PNG\r\n\x1a\n".to_vec());
+    let second = book(
+        &library.id,
+        root.join("Tolkien/Farmer Giles.mp3"),
+        "Farmer Giles",
+        20,
+    );
+    let outside = book(&library.id, root.join("Other/unlisted.mp3"), "Unlisted", 30);
+    for audiobook in [&first, &second, &outside] {
+        db.audiobook_repository().upsert(audiobook).unwrap();
+    }
+    let collections = db.collection_repository();
+    let queue = collections.create("Commute", None).unwrap();
+    collections
+        .add_audiobooks(&queue.id, &[second.id.clone(), first.id.clone()])
+        .unwrap();
+
+    let options = FeedOptions::new("https://example.org/audio")
+        .with_layout(EnclosureLayout::Files)
+        .with_title("My queue");
+    let feed = Feed::build(&db, &FeedSource::Collection(queue.id.clone()), &options).unwrap();
+    let xml = feed.to_xml();
+    let document = Document::parse(&xml).unwrap();
+    let channel = child(document.root_element(), "channel");
+    assert_eq!(text(channel, "title"), "My queue");
+    assert_eq!(
+        text(channel, "description"),
+        "Audiobooks in the Commute collection"
+    );
+
+    let urls: Vec<&str> = channel
+        .children()
+        .filter(|node| node.has_tag_name("item"))
+        .map(|item| child(item, "enclosure").attribute("url").unwrap())
+        .collect();
+    assert_eq!(
+        urls,
+        [
+            "https://example.org/audio/Tolkien/Farmer%20Giles.mp3",
+            "https://example.org/audio/Tolkien/The%20Hobbit.mp3",
+        ]
+    );
+
+    let cover_name = format!("{}.png", first.id);
+    assert_eq!(
+        feed.image_url,
+        Some(format!("https://example.org/audio/covers/{cover_name}"))
+    );
+    assert_eq!(feed.covers.len(), 1);
+    assert_eq!(feed.covers[0].file_name, cover_name);
+
+    let missing = Feed::build(&db, &FeedSource::Collection("nope".to_string()), &options);
+    assert!(missing.is_err());
+}
+
+#[test]
+fn test_layout_names_and_escaping() {
+    assert_eq!(
+        "files".parse::<EnclosureLayout>().unwrap(),
+        EnclosureLayout::Files
+    );
+    assert_eq!(
+        "Server".parse::<EnclosureLayout>().unwrap(),
+        EnclosureLayout::Server
+    );
+    assert!("ftp".parse::<EnclosureLayout>().is_err());
+    assert_eq!(EnclosureLayout::Files.to_string(), "files");
+    assert_eq!(encode_path_segment("a b/ü"), "a%20b%2F%C3%BC");
+
+    let mut audiobook = Audiobook::new("lib", "/books/odd.mp3");
+    audiobook.title = Some("Control\u{1}chars \"quoted\"".to_string());
+    let roots: HashMap<String, PathBuf> = HashMap::new();
+    let feed = Feed::from_audiobooks(
+        "t",
+        "d",
+        &[audiobook],
+        &roots,
+        &FeedOptions::new("http://x"),
+    );
+    let xml = feed.to_xml();
+    let document = Document::parse(&xml).unwrap();
+    let item = child(child(document.root_element(), "channel"), "item");
+    assert_eq!(text(item, "title"), "Controlchars \"quoted\"");
+    assert_eq!(child(item, "enclosure").attribute("length"), Some("0"));
+}
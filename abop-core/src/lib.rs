@@ -15,10 +15,15 @@ pub mod config;
 pub mod constants;
 pub mod db;
 pub mod error;
+pub mod feed;
+pub mod library;
 pub mod message;
 pub mod models;
 pub mod scanner;
+pub mod provider;
 pub mod services;
+pub mod sidecar;
+pub mod sync;
 /// Test utilities module
 /// 
 /// **Warning**: This module contains test-only utilities and should not be used in production.
@@ -0,0 +1,507 @@
+//! Duplicate audiobook detection
+//!
+//! Libraries that grew over years often hold the same book several times:
+//! different rips, bitrates or folder names. Detection runs in three passes:
+//!
+//! 1. Group by normalized title and author, so `"The Hobbit (Unabridged)"` by
+//!    `"Tolkien, J.R.R."` and `"Hobbit"` by `"J. R. R. Tolkien"` land together.
+//! 2. Split each group into runs of similar duration.
+//! 3. Optionally confirm each run acoustically with a [`ChromaFingerprint`],
+//!    dropping members that do not sound like the recommended keeper.
+//!
+//! Every resulting [`DuplicateCluster`] names a keeper: the copy with the
+//! highest bitrate, with tag completeness breaking near ties.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audio::fingerprint::{ChromaFingerprint, ChromaFingerprinter, FingerprintConfig};
+use crate::models::Audiobook;
+
+/// Words dropped from titles because rips add them inconsistently
+const TITLE_NOISE_WORDS: &[&str] = &["unabridged", "abridged", "audiobook", "audio", "book"];
+
+/// Leading articles dropped from titles
+const TITLE_ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// Bitrates within the same bucket (in kbps) are considered equal when picking a keeper
+const BITRATE_BUCKET_KBPS: u32 = 16;
+
+/// Configuration for duplicate detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuplicateConfig {
+    /// Absolute duration difference in seconds that still counts as the same book
+    pub duration_tolerance_secs: u64,
+    /// Relative duration difference that still counts as the same book
+    ///
+    /// The larger of the absolute and relative tolerance applies.
+    pub duration_tolerance_ratio: f64,
+    /// Whether to confirm candidates with acoustic fingerprints
+    pub use_fingerprints: bool,
+    /// Minimum fingerprint similarity for a candidate to stay in a cluster
+    pub similarity_threshold: f32,
+    /// Fingerprint extraction settings
+    pub fingerprint: FingerprintConfig,
+}
+
+impl Default for DuplicateConfig {
+    fn default() -> Self {
+        Self {
+            duration_tolerance_secs: 60,
+            duration_tolerance_ratio: 0.01,
+            use_fingerprints: true,
+            similarity_threshold: 0.75,
+            fingerprint: FingerprintConfig::default(),
+        }
+    }
+}
+
+impl DuplicateConfig {
+    /// Returns the duration tolerance in seconds for a book of the given length
+    #[must_use]
+    pub fn tolerance_for(&self, duration_seconds: u64) -> u64 {
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let relative = (duration_seconds as f64 * self.duration_tolerance_ratio) as u64;
+        self.duration_tolerance_secs.max(relative)
+    }
+}
+
+/// A single audiobook within a duplicate cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    /// ID of the audiobook
+    pub audiobook_id: String,
+    /// Path of the audiobook file
+    pub path: PathBuf,
+    /// Title as stored in the library
+    pub title: Option<String>,
+    /// Author as stored in the library
+    pub author: Option<String>,
+    /// Duration in seconds, if known
+    pub duration_seconds: Option<u64>,
+    /// File size in bytes, if known
+    pub size_bytes: Option<u64>,
+    /// Average bitrate in kbps derived from size and duration
+    pub bitrate_kbps: Option<u32>,
+    /// Number of populated tags (title, author, narrator, description, cover art)
+    pub tag_score: u8,
+    /// Fingerprint similarity to the keeper, if it was computed
+    pub similarity: Option<f32>,
+}
+
+impl DuplicateCandidate {
+    /// Builds a candidate from an audiobook
+    #[must_use]
+    pub fn from_audiobook(audiobook: &Audiobook) -> Self {
+        let bitrate_kbps = audiobook
+            .size_bytes
+            .zip(audiobook.duration_seconds)
+            .filter(|(_, duration)| *duration > 0)
+            .and_then(|(size, duration)| u32::try_from(size * 8 / duration / 1000).ok());
+
+        let has_text =
+            |value: &Option<String>| value.as_ref().is_some_and(|v| !v.trim().is_empty());
+        let tag_score = [
+            has_text(&audiobook.title),
+            has_text(&audiobook.author),
+            has_text(&audiobook.narrator),
+            has_text(&audiobook.description),
+            audiobook
+                .cover_art
+                .as_ref()
+                .is_some_and(|art| !art.is_empty()),
+        ]
+        .into_iter()
+        .map(u8::from)
+        .sum();
+
+        Self {
+            audiobook_id: audiobook.id.clone(),
+            path: audiobook.path.clone(),
+            title: audiobook.title.clone(),
+            author: audiobook.author.clone(),
+            duration_seconds: audiobook.duration_seconds,
+            size_bytes: audiobook.size_bytes,
+            bitrate_kbps,
+            tag_score,
+            similarity: None,
+        }
+    }
+
+    /// Ranking key used to pick the keeper, higher is better
+    fn keeper_rank(&self) -> (u32, u8, u64) {
+        (
+            self.bitrate_kbps.unwrap_or(0) / BITRATE_BUCKET_KBPS,
+            self.tag_score,
+            self.size_bytes.unwrap_or(0),
+        )
+    }
+}
+
+/// A group of audiobooks that appear to be the same book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateCluster {
+    /// Display title of the cluster
+    pub title: String,
+    /// Display author of the cluster
+    pub author: String,
+    /// ID of the recommended copy to keep
+    pub keeper_id: String,
+    /// Whether every candidate was confirmed by fingerprint comparison
+    pub fingerprint_verified: bool,
+    /// All copies, keeper first
+    pub candidates: Vec<DuplicateCandidate>,
+}
+
+impl DuplicateCluster {
+    /// Returns the recommended copy to keep
+    #[must_use]
+    pub fn keeper(&self) -> Option<&DuplicateCandidate> {
+        self.candidates
+            .iter()
+            .find(|candidate| candidate.audiobook_id == self.keeper_id)
+    }
+
+    /// Returns the copies that could be removed
+    pub fn extras(&self) -> impl Iterator<Item = &DuplicateCandidate> {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.audiobook_id != self.keeper_id)
+    }
+}
+
+/// Finds duplicate audiobooks in a library
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateDetector {
+    config: DuplicateConfig,
+}
+
+impl DuplicateDetector {
+    /// Creates a detector with the given configuration
+    #[must_use]
+    pub const fn new(config: DuplicateConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the detector configuration
+    #[must_use]
+    pub const fn config(&self) -> &DuplicateConfig {
+        &self.config
+    }
+
+    /// Finds duplicate clusters, fingerprinting files if enabled in the configuration
+    #[must_use]
+    pub fn find_duplicates(&self, audiobooks: &[Audiobook]) -> Vec<DuplicateCluster> {
+        if !self.config.use_fingerprints {
+            return self.find_duplicates_with(audiobooks, |_| None);
+        }
+
+        let fingerprinter = ChromaFingerprinter::new(self.config.fingerprint);
+        self.find_duplicates_with(audiobooks, |audiobook| {
+            fingerprinter
+                .fingerprint(&audiobook.path)
+                .map_err(|e| {
+                    log::warn!("Could not fingerprint {}: {e}", audiobook.path.display());
+                })
+                .ok()
+        })
+    }
+
+    /// Finds duplicate clusters using the given fingerprint source
+    ///
+    /// `fingerprint` is only called for books that already share a normalized
+    /// title, author and duration. Returning `None` leaves a candidate in its
+    /// cluster but marks the cluster as not fingerprint verified.
+    pub fn find_duplicates_with<F>(
+        &self,
+        audiobooks: &[Audiobook],
+        mut fingerprint: F,
+    ) -> Vec<DuplicateCluster>
+    where
+        F: FnMut(&Audiobook) -> Option<ChromaFingerprint>,
+    {
+        let mut groups: HashMap<(String, String), Vec<&Audiobook>> = HashMap::new();
+        for audiobook in audiobooks {
+            let title = audiobook.title.clone().unwrap_or_else(|| {
+                audiobook
+                    .path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+            let title = normalize_title(&title);
+            if title.is_empty() {
+                continue;
+            }
+            let author = audiobook
+                .author
+                .as_deref()
+                .map(normalize_author)
+                .unwrap_or_default();
+            groups.entry((title, author)).or_default().push(audiobook);
+        }
+
+        let mut clusters = Vec::new();
+        for group in groups.into_values().filter(|group| group.len() > 1) {
+            for run in self.split_by_duration(group) {
+                if let Some(cluster) = self.confirm_cluster(&run, &mut fingerprint) {
+                    clusters.push(cluster);
+                }
+            }
+        }
+
+        clusters.sort_by(|a, b| a.title.cmp(&b.title).then_with(|| a.author.cmp(&b.author)));
+        clusters
+    }
+
+    /// Splits a title group into runs whose neighbouring durations are within tolerance
+    ///
+    /// Books without a known duration join the largest run.
+    fn split_by_duration<'a>(&self, mut group: Vec<&'a Audiobook>) -> Vec<Vec<&'a Audiobook>> {
+        group.sort_by_key(|audiobook| audiobook.duration_seconds);
+        let (unknown, known): (Vec<_>, Vec<_>) = group
+            .into_iter()
+            .partition(|audiobook| audiobook.duration_seconds.is_none());
+
+        let mut runs: Vec<Vec<&Audiobook>> = Vec::new();
+        let mut previous: Option<u64> = None;
+        for audiobook in known {
+            let duration = audiobook.duration_seconds.unwrap_or(0);
+            let continues =
+                previous.is_some_and(|prev| duration - prev <= self.config.tolerance_for(duration));
+            match runs.last_mut() {
+                Some(run) if continues => run.push(audiobook),
+                _ => runs.push(vec![audiobook]),
+            }
+            previous = Some(duration);
+        }
+
+        if !unknown.is_empty() {
+            match runs.iter_mut().max_by_key(|run| run.len()) {
+                Some(run) => run.extend(unknown),
+                None => runs.push(unknown),
+            }
+        }
+
+        runs.retain(|run| run.len() > 1);
+        runs
+    }
+
+    /// Picks a keeper and drops candidates whose fingerprint does not match it
+    fn confirm_cluster<F>(
+        &self,
+        run: &[&Audiobook],
+        fingerprint: &mut F,
+    ) -> Option<DuplicateCluster>
+    where
+        F: FnMut(&Audiobook) -> Option<ChromaFingerprint>,
+    {
+        let mut candidates: Vec<(&Audiobook, DuplicateCandidate)> = run
+            .iter()
+            .map(|audiobook| (*audiobook, DuplicateCandidate::from_audiobook(audiobook)))
+            .collect();
+        candidates.sort_by_key(|(_, candidate)| std::cmp::Reverse(candidate.keeper_rank()));
+
+        let mut verified = false;
+        if self.config.use_fingerprints {
+            let keeper_print = fingerprint(candidates[0].0);
+            verified = keeper_print.is_some();
+            let mut confirmed = vec![candidates.remove(0)];
+            for (audiobook, mut candidate) in candidates {
+                match (&keeper_print, fingerprint(audiobook)) {
+                    (Some(keeper), Some(print)) => {
+                        let similarity =
+                            keeper.similarity(&print, self.config.fingerprint.max_offset_seconds);
+                        if similarity < self.config.similarity_threshold {
+                            log::debug!(
+                                "Dropping {} from duplicate cluster, similarity {similarity:.2}",
+                                audiobook.path.display()
+                            );
+                            continue;
+                        }
+                        candidate.similarity = Some(similarity);
+                    }
+                    _ => verified = false,
+                }
+                confirmed.push((audiobook, candidate));
+            }
+            candidates = confirmed;
+        }
+
+        if candidates.len() < 2 {
+            return None;
+        }
+
+        let (keeper_book, keeper) = &candidates[0];
+        Some(DuplicateCluster {
+            title: keeper_book.display_title(),
+            author: keeper_book.display_author().to_string(),
+            keeper_id: keeper.audiobook_id.clone(),
+            fingerprint_verified: verified,
+            candidates: candidates
+                .into_iter()
+                .map(|(_, candidate)| candidate)
+                .collect(),
+        })
+    }
+}
+
+/// Splits text into lowercase alphanumeric words, dropping bracketed segments
+fn words(text: &str) -> Vec<String> {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            c if c.is_alphanumeric() => cleaned.extend(c.to_lowercase()),
+            _ => cleaned.push(' '),
+        }
+    }
+    cleaned.split_whitespace().map(str::to_string).collect()
+}
+
+/// Normalizes a title for duplicate grouping
+///
+/// Bracketed segments such as `(Unabridged)` or `[64kbps]`, punctuation, noise
+/// words and a leading article are removed.
+#[must_use]
+pub fn normalize_title(title: &str) -> String {
+    let mut words: Vec<String> = words(title)
+        .into_iter()
+        .filter(|word| !TITLE_NOISE_WORDS.contains(&word.as_str()))
+        .collect();
+    if words.len() > 1 && TITLE_ARTICLES.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    words.join(" ")
+}
+
+/// Normalizes an author name for duplicate grouping
+///
+/// Name parts are sorted so `"Tolkien, J.R.R."` and `"J. R. R. Tolkien"` match.
+#[must_use]
+pub fn normalize_author(author: &str) -> String {
+    let mut words = words(author);
+    words.sort_unstable();
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(id: &str, title: &str, author: &str, duration: u64, size: u64) -> Audiobook {
+        let mut audiobook = Audiobook::new("lib", format!("/books/{id}.mp3"));
+        audiobook.id = id.to_string();
+        audiobook.title = Some(title.to_string());
+        audiobook.author = Some(author.to_string());
+        audiobook.duration_seconds = Some(duration);
+        audiobook.size_bytes = Some(size);
+        audiobook
+    }
+
+    fn print(codes: &[u32]) -> ChromaFingerprint {
+        ChromaFingerprint {
+            codes: codes.iter().map(|code| code | (1 << 31)).collect(),
+            frames_per_second: 1.0,
+        }
+    }
+
+    fn no_fingerprints() -> DuplicateDetector {
+        DuplicateDetector::new(DuplicateConfig {
+            use_fingerprints: false,
+            ..DuplicateConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(normalize_title("The Hobbit (Unabridged)"), "hobbit");
+        assert_eq!(normalize_title("Hobbit [64kbps] - Audiobook"), "hobbit");
+        assert_eq!(normalize_title("The"), "the");
+        assert_eq!(
+            normalize_author("Tolkien, J.R.R."),
+            normalize_author("J. R. R. Tolkien")
+        );
+    }
+
+    #[test]
+    fn test_groups_by_title_author_and_duration() {
+        let books = vec![
+            book("low", "The Hobbit", "J.R.R. Tolkien", 40_000, 160_000_000),
+            book(
+                "high",
+                "Hobbit (Unabridged)",
+                "Tolkien, J.R.R.",
+                40_030,
+                640_000_000,
+            ),
+            book(
+                "abridged",
+                "The Hobbit",
+                "J.R.R. Tolkien",
+                18_000,
+                80_000_000,
+            ),
+            book("other", "Dune", "Frank Herbert", 40_000, 300_000_000),
+        ];
+
+        let clusters = no_fingerprints().find_duplicates(&books);
+        assert_eq!(clusters.len(), 1);
+        let cluster = &clusters[0];
+        assert_eq!(cluster.candidates.len(), 2);
+        assert_eq!(cluster.keeper_id, "high");
+        assert_eq!(cluster.extras().next().unwrap().audiobook_id, "low");
+        assert!(!cluster.fingerprint_verified);
+    }
+
+    #[test]
+    fn test_tags_break_bitrate_ties() {
+        let mut tagged = book("tagged", "Dune", "Frank Herbert", 72_000, 576_000_000);
+        tagged.narrator = Some("Scott Brick".to_string());
+        let plain = book("plain", "Dune", "Frank Herbert", 72_000, 580_000_000);
+
+        let clusters = no_fingerprints().find_duplicates(&[plain, tagged]);
+        assert_eq!(clusters[0].keeper_id, "tagged");
+    }
+
+    #[test]
+    fn test_fingerprint_mismatch_splits_cluster() {
+        let books = vec![
+            book("a", "Dune", "Frank Herbert", 72_000, 576_000_000),
+            book("b", "Dune", "Frank Herbert", 72_010, 288_000_000),
+            book("c", "Dune", "Frank Herbert", 72_020, 144_000_000),
+        ];
+        let same: Vec<u32> = (0..64u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> 8)
+            .collect();
+        let different: Vec<u32> = same.iter().map(|code| !code & 0x00FF_FFFF).collect();
+
+        let detector = DuplicateDetector::default();
+        let clusters = detector.find_duplicates_with(&books, |audiobook| {
+            Some(if audiobook.id == "c" {
+                print(&different)
+            } else {
+                print(&same)
+            })
+        });
+
+        assert_eq!(clusters.len(), 1);
+        let ids: Vec<_> = clusters[0]
+            .candidates
+            .iter()
+            .map(|c| c.audiobook_id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert!(clusters[0].fingerprint_verified);
+        assert_eq!(clusters[0].candidates[1].similarity, Some(1.0));
+    }
+}
@@ -0,0 +1,18 @@
+//! Library-wide maintenance operations
+//!
+//! Unlike the scanner, which looks at one file at a time, the operations in
+//! this module work across all audiobooks of a library.
+
+pub mod duplicates;
+pub mod names;
+pub mod organizer;
+
+pub use duplicates::{
+    DuplicateCandidate, DuplicateCluster, DuplicateConfig, DuplicateDetector, normalize_author,
+    normalize_title,
+};
+pub use names::{PersonName, SeriesName, parse_people, parse_series, person_key, series_key};
+pub use organizer::{
+    Collision, CollisionKind, LibraryOrganizer, OrganizeFields, OrganizePlan, OrganizerConfig,
+    PlannedMove,
+};
@@ -0,0 +1,308 @@
+//! Name parsing for authors, narrators and series
+//!
+//! Tags store people and series as free text: several authors in one field,
+//! names in `Last, First` order, the series hidden in the title. This module
+//! splits those strings into single entries and derives the keys that match
+//! different spellings of the same person or series.
+
+use super::duplicates::{normalize_author, normalize_title};
+
+/// Characters that separate people within one tag
+const PERSON_SEPARATORS: &[char] = &[';', '&', '/'];
+
+/// Words that join people within one tag, e.g. `Brandon Sanderson and Robert Jordan`
+const PERSON_JOINERS: &[&str] = &["and", "with"];
+
+/// Name suffixes that follow the family name
+const NAME_SUFFIXES: &[&str] = &["jr", "sr", "ii", "iii", "iv", "phd"];
+
+/// Words that introduce a series position, e.g. `Book 3`
+const SERIES_INDEX_WORDS: &[&str] = &["book", "bk", "vol", "volume", "part", "no"];
+
+/// One person parsed from a tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonName {
+    /// Display name in natural order, e.g. `Ursula K. Le Guin`
+    pub name: String,
+    /// Name used for sorting, e.g. `Le Guin, Ursula K.`
+    pub sort_name: String,
+    /// Alias key shared by all spellings of the name
+    pub key: String,
+}
+
+/// A series and position parsed from a title
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesName {
+    /// Display name of the series
+    pub name: String,
+    /// Position of the book in the series
+    pub index: Option<f64>,
+}
+
+/// Alias key of a person's name
+///
+/// Word order, case and punctuation are ignored, so `Tolkien, J.R.R.` and
+/// `J. R. R. Tolkien` share a key.
+#[must_use]
+pub fn person_key(name: &str) -> String {
+    normalize_author(name)
+}
+
+/// Alias key of a series name
+///
+/// Case, punctuation and a leading article are ignored.
+#[must_use]
+pub fn series_key(name: &str) -> String {
+    normalize_title(name)
+}
+
+/// Splits an author or narrator tag into the people it names
+///
+/// People are separated by `;`, `&`, `/`, `and` or `with`. A single comma is
+/// read as `Last, First` when either side is one word or the first names end
+/// in an initial; otherwise commas separate people as well. Bracketed notes
+/// such as `(Narrator)` are dropped, and repeated people are only returned once.
+#[must_use]
+pub fn parse_people(tag: &str) -> Vec<PersonName> {
+    let mut people: Vec<PersonName> = Vec::new();
+    for part in tag.split(PERSON_SEPARATORS) {
+        for part in split_on_joiners(&strip_brackets(part)) {
+            for person in parse_comma_names(&part) {
+                if !person.key.is_empty() && people.iter().all(|p| p.key != person.key) {
+                    people.push(person);
+                }
+            }
+        }
+    }
+    people
+}
+
+/// Parses the series from a title such as `The Final Empire (Mistborn #1)`
+///
+/// Recognizes a trailing bracketed `Series #N` or `Series, Book N`, and a
+/// leading `Series #N:` or `Series, Book N -` prefix.
+#[must_use]
+pub fn parse_series(title: &str) -> Option<SeriesName> {
+    let title = title.trim();
+    if let Some(series) = trailing_bracket(title).and_then(parse_series_segment) {
+        return Some(series);
+    }
+    let prefix_end = title.find(':').or_else(|| title.find(" - "))?;
+    parse_series_segment(&title[..prefix_end])
+}
+
+fn parse_series_segment(segment: &str) -> Option<SeriesName> {
+    let (name, index) = if let Some((name, index)) = segment.rsplit_once('#') {
+        (name.to_string(), index.trim())
+    } else {
+        let words: Vec<&str> = segment.split_whitespace().collect();
+        let [name @ .., marker, index] = words.as_slice() else {
+            return None;
+        };
+        let marker = marker.trim_end_matches('.').to_lowercase();
+        if !SERIES_INDEX_WORDS.contains(&marker.as_str()) {
+            return None;
+        }
+        (name.join(" "), *index)
+    };
+    let index: f64 = index.parse().ok().filter(|index: &f64| index.is_finite())?;
+    let name = name.trim().trim_end_matches(',').trim();
+    (!name.is_empty()).then(|| SeriesName {
+        name: name.to_string(),
+        index: Some(index),
+    })
+}
+
+/// Content of a bracketed segment that ends the text
+fn trailing_bracket(text: &str) -> Option<&str> {
+    let close = text.chars().last()?;
+    let open = match close {
+        ')' => '(',
+        ']' => '[',
+        _ => return None,
+    };
+    let start = text.rfind(open)?;
+    Some(&text[start + 1..text.len() - 1])
+}
+
+fn strip_brackets(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            c => cleaned.push(c),
+        }
+    }
+    cleaned
+}
+
+fn split_on_joiners(text: &str) -> Vec<String> {
+    let mut parts = vec![Vec::new()];
+    for word in text.split_whitespace() {
+        if PERSON_JOINERS.contains(&word.to_lowercase().as_str()) {
+            parts.push(Vec::new());
+        } else if let Some(current) = parts.last_mut() {
+            current.push(word);
+        }
+    }
+    parts
+        .into_iter()
+        .filter(|words| !words.is_empty())
+        .map(|words| words.join(" "))
+        .collect()
+}
+
+fn parse_comma_names(text: &str) -> Vec<PersonName> {
+    let parts: Vec<&str> = text
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    match parts.as_slice() {
+        [family, given] if is_suffix(given) => vec![natural_name(&format!("{family} {given}"))],
+        [family, given] if is_inverted(family, given) => vec![inverted_name(family, given)],
+        parts => parts.iter().map(|part| natural_name(part)).collect(),
+    }
+}
+
+/// Whether `family, given` is one name written family name first
+fn is_inverted(family: &str, given: &str) -> bool {
+    let family_words = family.split_whitespace().count();
+    let given_words: Vec<&str> = given.split_whitespace().collect();
+    family_words == 1 || given_words.len() == 1 || given_words.last().is_some_and(|w| is_initial(w))
+}
+
+/// Whether a word is an initial such as `K.` or `J.R.R.`
+fn is_initial(word: &str) -> bool {
+    word.split('.')
+        .filter(|part| !part.is_empty())
+        .all(|part| part.chars().count() == 1)
+}
+
+fn is_suffix(word: &str) -> bool {
+    NAME_SUFFIXES.contains(&word.trim_end_matches('.').to_lowercase().as_str())
+}
+
+fn inverted_name(family: &str, given: &str) -> PersonName {
+    let family = collapse_whitespace(family);
+    let given = collapse_whitespace(given);
+    let name = format!("{given} {family}");
+    PersonName {
+        key: person_key(&name),
+        sort_name: format!("{family}, {given}"),
+        name,
+    }
+}
+
+fn natural_name(text: &str) -> PersonName {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let name = words.join(" ");
+    // The family name is the last word, skipping a trailing suffix
+    let family_at = match words.as_slice() {
+        [.., last] if words.len() > 2 && is_suffix(last) => words.len() - 2,
+        _ => words.len().saturating_sub(1),
+    };
+    let sort_name = if family_at == 0 {
+        name.clone()
+    } else {
+        let mut sort_name = format!("{}, {}", words[family_at], words[..family_at].join(" "));
+        for suffix in &words[family_at + 1..] {
+            sort_name.push_str(", ");
+            sort_name.push_str(suffix);
+        }
+        sort_name
+    };
+    PersonName {
+        key: person_key(&name),
+        sort_name,
+        name,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(tag: &str) -> Vec<(String, String)> {
+        parse_people(tag)
+            .into_iter()
+            .map(|person| (person.name, person.sort_name))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_people_splits_and_inverts() {
+        assert_eq!(
+            names("Sanderson, Brandon"),
+            vec![("Brandon Sanderson".into(), "Sanderson, Brandon".into())]
+        );
+        assert_eq!(
+            names("Le Guin, Ursula K."),
+            vec![("Ursula K. Le Guin".into(), "Le Guin, Ursula K.".into())]
+        );
+        assert_eq!(
+            names("Brandon Sanderson, Robert Jordan"),
+            vec![
+                ("Brandon Sanderson".into(), "Sanderson, Brandon".into()),
+                ("Robert Jordan".into(), "Jordan, Robert".into()),
+            ]
+        );
+        assert_eq!(
+            names("Michael Kramer & Kate Reading (Narrators); Kate Reading"),
+            vec![
+                ("Michael Kramer".into(), "Kramer, Michael".into()),
+                ("Kate Reading".into(), "Reading, Kate".into()),
+            ]
+        );
+        assert_eq!(
+            names("Martin Luther King, Jr."),
+            vec![(
+                "Martin Luther King Jr.".into(),
+                "King, Martin Luther, Jr.".into()
+            )]
+        );
+        assert!(parse_people("  ").is_empty());
+    }
+
+    #[test]
+    fn test_person_keys_match_aliases() {
+        let inverted = parse_people("Tolkien, J.R.R.");
+        let natural = parse_people("J. R. R. Tolkien");
+        assert_eq!(inverted[0].key, natural[0].key);
+        assert_eq!(series_key("The Wheel of Time"), series_key("wheel of time"));
+    }
+
+    #[test]
+    fn test_parse_series_from_title() {
+        assert_eq!(
+            parse_series("The Final Empire (Mistborn #1)"),
+            Some(SeriesName {
+                name: "Mistborn".into(),
+                index: Some(1.0)
+            })
+        );
+        assert_eq!(
+            parse_series("Edgedancer [The Stormlight Archive, Book 2.5]"),
+            Some(SeriesName {
+                name: "The Stormlight Archive".into(),
+                index: Some(2.5)
+            })
+        );
+        assert_eq!(
+            parse_series("Discworld Vol. 3 - Equal Rites"),
+            Some(SeriesName {
+                name: "Discworld".into(),
+                index: Some(3.0)
+            })
+        );
+        assert_eq!(parse_series("The Hobbit (Unabridged)"), None);
+        assert_eq!(parse_series("Dune"), None);
+    }
+}
@@ -0,0 +1,670 @@
+//! Template-driven library organization
+//!
+//! The organizer computes where each audiobook file should live from a path
+//! template such as `{author}/{series}/{series_index} - {title}/{part}`, then
+//! moves the files there. Planning never touches the filesystem beyond
+//! checking for existing targets, so a plan doubles as a dry-run preview.
+//!
+//! Applying a plan is all-or-nothing: if a move or the database update fails,
+//! every file already moved is put back. Audiobooks keep their IDs, so
+//! progress and other records survive the move.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::repositories::AudiobookRepository;
+use crate::error::{AppError, Result};
+use crate::models::audiobook::fallbacks;
+use crate::models::{Audiobook, SeriesMembership};
+use crate::utils::path::{normalize_path_for_comparison, sanitize_file_name};
+
+/// Default organizer template
+pub const DEFAULT_ORGANIZE_TEMPLATE: &str = "{author}/{series}/{series_index} - {title}/{part}";
+
+/// Placeholders understood by organizer templates
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "author",
+    "narrator",
+    "title",
+    "series",
+    "series_index",
+    "part",
+];
+
+/// Characters trimmed from a path segment once empty placeholders are removed
+const SEGMENT_TRIM_CHARS: &[char] = &[' ', '-', '_', ',', '.', '(', ')', '[', ']'];
+
+/// Configuration for library organization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrganizerConfig {
+    /// Target path template relative to the library root, without extension
+    ///
+    /// Segments are separated by `/`. A segment whose placeholders are all
+    /// empty is dropped, so books without a series skip the series folder.
+    pub template: String,
+}
+
+impl Default for OrganizerConfig {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_ORGANIZE_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Values substituted into an organizer template
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrganizeFields {
+    /// Author name
+    pub author: String,
+    /// Narrator name
+    pub narrator: Option<String>,
+    /// Book title
+    pub title: String,
+    /// Series name
+    pub series: Option<String>,
+    /// Position in the series, e.g. `3` or `2.5`
+    pub series_index: Option<String>,
+    /// Name of this file within the book, without extension
+    pub part: String,
+}
+
+impl OrganizeFields {
+    /// Collect template values from an audiobook
+    #[must_use]
+    pub fn from_audiobook(audiobook: &Audiobook) -> Self {
+        let part = audiobook
+            .path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        Self {
+            author: non_empty(audiobook.author.as_deref())
+                .unwrap_or(fallbacks::UNKNOWN_AUTHOR)
+                .to_string(),
+            narrator: non_empty(audiobook.narrator.as_deref()).map(str::to_string),
+            title: non_empty(audiobook.title.as_deref())
+                .map_or_else(|| part.clone(), str::to_string),
+            series: None,
+            series_index: None,
+            part,
+        }
+    }
+
+    /// Fill the series placeholders from a series membership
+    #[must_use]
+    pub fn with_series(mut self, membership: &SeriesMembership) -> Self {
+        self.series = Some(membership.series_name.clone());
+        self.series_index = membership.index_label();
+        self
+    }
+
+    fn get(&self, placeholder: &str) -> Option<&str> {
+        match placeholder {
+            "author" => Some(&self.author),
+            "narrator" => self.narrator.as_deref(),
+            "title" => Some(&self.title),
+            "series" => self.series.as_deref(),
+            "series_index" => self.series_index.as_deref(),
+            "part" => Some(&self.part),
+            _ => None,
+        }
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// One file move in an organization plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedMove {
+    /// ID of the audiobook being moved
+    pub audiobook_id: String,
+    /// Current path
+    pub from: PathBuf,
+    /// Target path
+    pub to: PathBuf,
+}
+
+/// Why a target path cannot be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionKind {
+    /// Several audiobooks would be moved to the same path
+    SharedTarget,
+    /// A file or directory already exists at the target
+    TargetExists,
+}
+
+/// A target path that blocks the plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collision {
+    /// The contested target path
+    pub target: PathBuf,
+    /// Current paths of the audiobooks that want the target
+    pub sources: Vec<PathBuf>,
+    /// Why the target cannot be used
+    pub kind: CollisionKind,
+}
+
+/// Preview of an organization run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrganizePlan {
+    /// Library root the template is applied under
+    pub library_root: PathBuf,
+    /// Files that would be moved
+    pub moves: Vec<PlannedMove>,
+    /// Number of files already at their target path
+    pub unchanged: usize,
+    /// Targets that block the plan; the plan cannot be applied while any exist
+    pub collisions: Vec<Collision>,
+}
+
+impl OrganizePlan {
+    /// Whether the plan can be applied
+    #[must_use]
+    pub fn has_collisions(&self) -> bool {
+        !self.collisions.is_empty()
+    }
+}
+
+/// Moves audiobook files into a template-driven folder layout
+#[derive(Debug, Clone, Default)]
+pub struct LibraryOrganizer {
+    config: OrganizerConfig,
+}
+
+impl LibraryOrganizer {
+    /// Create an organizer
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Config`] if the template is empty, uses an unknown
+    /// placeholder, has an unclosed brace or has no placeholder in its last segment.
+    pub fn new(config: OrganizerConfig) -> Result<Self> {
+        validate_template(&config.template)?;
+        Ok(Self { config })
+    }
+
+    /// The organizer configuration
+    #[must_use]
+    pub const fn config(&self) -> &OrganizerConfig {
+        &self.config
+    }
+
+    /// Compute the target path of one audiobook file
+    #[must_use]
+    pub fn target_path(&self, library_root: &Path, audiobook: &Audiobook) -> PathBuf {
+        self.target_path_with(
+            library_root,
+            audiobook,
+            &OrganizeFields::from_audiobook(audiobook),
+        )
+    }
+
+    /// Compute the target path of one audiobook file from explicit template values
+    #[must_use]
+    pub fn target_path_with(
+        &self,
+        library_root: &Path,
+        audiobook: &Audiobook,
+        fields: &OrganizeFields,
+    ) -> PathBuf {
+        let mut segments: Vec<String> = self
+            .config
+            .template
+            .split('/')
+            .map(|segment| render_segment(segment, fields))
+            .collect();
+        // The file name always needs a value, even if its placeholders were empty
+        if let Some(last) = segments.last_mut()
+            && last.is_empty()
+        {
+            *last = sanitize_file_name(&fields.part);
+        }
+
+        let mut target = library_root.to_path_buf();
+        target.extend(segments.iter().filter(|segment| !segment.is_empty()));
+        if let Some(extension) = audiobook.path.extension() {
+            let mut file_name = target.file_name().unwrap_or_default().to_os_string();
+            file_name.push(".");
+            file_name.push(extension);
+            target.set_file_name(file_name);
+        }
+        target
+    }
+
+    /// Plan the moves for a set of audiobooks without touching any file
+    #[must_use]
+    pub fn plan(&self, library_root: &Path, audiobooks: &[Audiobook]) -> OrganizePlan {
+        self.plan_with(library_root, audiobooks, OrganizeFields::from_audiobook)
+    }
+
+    /// Plan the moves, filling the series placeholders from `series`
+    ///
+    /// `series` maps audiobook IDs to their membership, as returned by
+    /// [`SeriesRepository::find_memberships_in_library`](crate::db::repositories::SeriesRepository::find_memberships_in_library).
+    #[must_use]
+    pub fn plan_with_series(
+        &self,
+        library_root: &Path,
+        audiobooks: &[Audiobook],
+        series: &HashMap<String, SeriesMembership>,
+    ) -> OrganizePlan {
+        self.plan_with(library_root, audiobooks, |audiobook| {
+            let fields = OrganizeFields::from_audiobook(audiobook);
+            match series.get(&audiobook.id) {
+                Some(membership) => fields.with_series(membership),
+                None => fields,
+            }
+        })
+    }
+
+    /// Plan the moves, taking template values from `fields`
+    #[must_use]
+    pub fn plan_with<F>(
+        &self,
+        library_root: &Path,
+        audiobooks: &[Audiobook],
+        mut fields: F,
+    ) -> OrganizePlan
+    where
+        F: FnMut(&Audiobook) -> OrganizeFields,
+    {
+        let mut moves = Vec::new();
+        let mut unchanged = 0;
+        // Targets in first-claimed order, with every file that wants them
+        let mut claims: Vec<(PathBuf, Vec<PathBuf>)> = Vec::new();
+        let mut claim_index: HashMap<String, usize> = HashMap::new();
+        for audiobook in audiobooks {
+            let target = self.target_path_with(library_root, audiobook, &fields(audiobook));
+            let key = comparison_key(&target);
+            let index = *claim_index.entry(key.clone()).or_insert_with(|| {
+                claims.push((target.clone(), Vec::new()));
+                claims.len() - 1
+            });
+            claims[index].1.push(audiobook.path.clone());
+            if key == comparison_key(&audiobook.path) {
+                unchanged += 1;
+                continue;
+            }
+            moves.push(PlannedMove {
+                audiobook_id: audiobook.id.clone(),
+                from: audiobook.path.clone(),
+                to: target,
+            });
+        }
+
+        let mut collisions: Vec<Collision> = claims
+            .iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(target, sources)| Collision {
+                target: target.clone(),
+                sources: sources.clone(),
+                kind: CollisionKind::SharedTarget,
+            })
+            .collect();
+        collisions.extend(
+            moves
+                .iter()
+                .filter(|planned| {
+                    claims[claim_index[&comparison_key(&planned.to)]].1.len() == 1
+                        && planned.to.exists()
+                })
+                .map(|planned| Collision {
+                    target: planned.to.clone(),
+                    sources: vec![planned.from.clone()],
+                    kind: CollisionKind::TargetExists,
+                }),
+        );
+
+        OrganizePlan {
+            library_root: library_root.to_path_buf(),
+            moves,
+            unchanged,
+            collisions,
+        }
+    }
+
+    /// Move the files of a plan and record the new paths in the database
+    ///
+    /// Source directories left empty are removed up to the library root.
+    /// Returns the number of moved files.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Library`] if the plan has collisions, and
+    /// [`AppError::Io`] or [`AppError::Database`] if a move or the database
+    /// update fails. Every file is back in its original place in these cases.
+    pub fn apply(&self, plan: &OrganizePlan, repository: &AudiobookRepository) -> Result<usize> {
+        if plan.has_collisions() {
+            return Err(AppError::Library(format!(
+                "Cannot organize library: {} target paths collide",
+                plan.collisions.len()
+            )));
+        }
+
+        let mut journal = MoveJournal::default();
+        for planned in &plan.moves {
+            if let Err(e) = journal.move_file(&planned.from, &planned.to) {
+                journal.roll_back();
+                return Err(e);
+            }
+        }
+
+        let paths: Vec<(String, PathBuf)> = plan
+            .moves
+            .iter()
+            .map(|planned| (planned.audiobook_id.clone(), planned.to.clone()))
+            .collect();
+        if let Err(e) = repository.update_paths(&paths) {
+            journal.roll_back();
+            return Err(AppError::Database(e));
+        }
+
+        for planned in &plan.moves {
+            remove_empty_dirs(planned.from.parent(), &plan.library_root);
+        }
+        log::info!(
+            "Organized {} files under {}",
+            plan.moves.len(),
+            plan.library_root.display()
+        );
+        Ok(plan.moves.len())
+    }
+}
+
+/// Record of completed moves so that a failed run can be undone
+#[derive(Debug, Default)]
+struct MoveJournal {
+    moved: Vec<(PathBuf, PathBuf)>,
+    created_dirs: Vec<PathBuf>,
+}
+
+impl MoveJournal {
+    fn move_file(&mut self, from: &Path, to: &Path) -> Result<()> {
+        if to.exists() {
+            return Err(AppError::Io(format!(
+                "Target appeared after planning: {}",
+                to.display()
+            )));
+        }
+        if let Some(parent) = to.parent() {
+            let missing: Vec<PathBuf> = parent
+                .ancestors()
+                .take_while(|dir| !dir.exists())
+                .map(Path::to_path_buf)
+                .collect();
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AppError::Io(format!("Cannot create {}: {e}", parent.display())))?;
+            self.created_dirs.extend(missing.into_iter().rev());
+        }
+        std::fs::rename(from, to).map_err(|e| {
+            AppError::Io(format!(
+                "Cannot move {} to {}: {e}",
+                from.display(),
+                to.display()
+            ))
+        })?;
+        self.moved.push((from.to_path_buf(), to.to_path_buf()));
+        Ok(())
+    }
+
+    fn roll_back(self) {
+        for (from, to) in self.moved.iter().rev() {
+            if let Err(e) = std::fs::rename(to, from) {
+                log::error!(
+                    "Failed to move {} back to {}: {e}",
+                    to.display(),
+                    from.display()
+                );
+            }
+        }
+        for dir in self.created_dirs.iter().rev() {
+            // Only empty directories are removed; anything else is left alone
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+/// Remove `dir` and its ancestors while they are empty, stopping at `root`
+fn remove_empty_dirs(mut dir: Option<&Path>, root: &Path) {
+    while let Some(current) = dir {
+        if !current.starts_with(root) || current == root || std::fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
+
+/// Key under which two paths count as the same file on this platform
+fn comparison_key(path: &Path) -> String {
+    if cfg!(windows) {
+        normalize_path_for_comparison(path)
+    } else {
+        path.to_string_lossy().into_owned()
+    }
+}
+
+/// Substitute the placeholders of one template segment and make it a safe name
+fn render_segment(segment: &str, fields: &OrganizeFields) -> String {
+    let mut rendered = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .map_or(rest.len(), |close| open + close);
+        let placeholder = rest.get(open + 1..close).unwrap_or_default();
+        if let Some(value) = fields.get(placeholder) {
+            rendered.push_str(&sanitize_file_name(value));
+        }
+        rest = rest.get(close + 1..).unwrap_or_default();
+    }
+    rendered.push_str(rest);
+    sanitize_file_name(rendered.trim_matches(SEGMENT_TRIM_CHARS))
+}
+
+fn validate_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(AppError::Config("Organizer template is empty".to_string()));
+    }
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            return Err(AppError::Config(format!(
+                "Unclosed placeholder in organizer template '{template}'"
+            )));
+        };
+        let placeholder = &rest[open + 1..open + close];
+        if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            return Err(AppError::Config(format!(
+                "Unknown placeholder '{{{placeholder}}}' in organizer template; expected one of {}",
+                TEMPLATE_PLACEHOLDERS.join(", ")
+            )));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    if !template
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .contains('{')
+    {
+        return Err(AppError::Config(
+            "The last segment of an organizer template must contain a placeholder".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::Progress;
+    use crate::test_utils::TestDataFactory;
+    use tempfile::TempDir;
+
+    fn book(root: &Path, id: &str, file: &str, title: &str, author: &str) -> Audiobook {
+        TestDataFactory::audiobook_with_path(id, "library", &root.join(file), title, author)
+    }
+
+    #[test]
+    fn test_template_validation() {
+        assert!(LibraryOrganizer::new(OrganizerConfig::default()).is_ok());
+        for template in ["", "{author}/{year}", "{author}/{title", "{author}/static"] {
+            let config = OrganizerConfig {
+                template: template.to_string(),
+            };
+            assert!(LibraryOrganizer::new(config).is_err(), "{template}");
+        }
+    }
+
+    #[test]
+    fn test_target_path_drops_empty_segments_and_sanitizes() {
+        let organizer = LibraryOrganizer::default();
+        let root = Path::new("/library");
+        let audiobook = book(root, "1", "misc/track01.mp3", "Why? A/B", "Jane Doe");
+
+        assert_eq!(
+            organizer.target_path(root, &audiobook),
+            root.join("Jane Doe/Why_ A_B/track01.mp3")
+        );
+
+        let fields = OrganizeFields {
+            series: Some("The Saga".to_string()),
+            series_index: Some("2".to_string()),
+            ..OrganizeFields::from_audiobook(&audiobook)
+        };
+        assert_eq!(
+            organizer.target_path_with(root, &audiobook, &fields),
+            root.join("Jane Doe/The Saga/2 - Why_ A_B/track01.mp3")
+        );
+    }
+
+    #[test]
+    fn test_plan_reports_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Author/Taken")).unwrap();
+        std::fs::write(root.join("Author/Taken/taken.mp3"), b"").unwrap();
+
+        let audiobooks = vec![
+            book(root, "1", "a/part.mp3", "Same", "Author"),
+            book(root, "2", "b/part.mp3", "Same", "Author"),
+            book(root, "3", "taken.mp3", "Taken", "Author"),
+            book(root, "4", "Author/Done/done.mp3", "Done", "Author"),
+        ];
+        let plan = LibraryOrganizer::default().plan(root, &audiobooks);
+
+        assert_eq!(plan.moves.len(), 3);
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.collisions.len(), 2);
+        assert_eq!(plan.collisions[0].kind, CollisionKind::SharedTarget);
+        assert_eq!(plan.collisions[0].sources.len(), 2);
+        assert_eq!(plan.collisions[1].kind, CollisionKind::TargetExists);
+    }
+
+    fn setup() -> (TempDir, Database, Vec<Audiobook>) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("organize.db")).unwrap();
+
+        let root = dir.path().join("library");
+        let library_id = db.add_library_with_path("Library", root.clone()).unwrap();
+        let repository = db.audiobook_repository();
+        let mut audiobooks = Vec::new();
+        for (id, file, title) in [("1", "incoming/one.mp3", "One"), ("2", "two.mp3", "Two")] {
+            let mut audiobook = book(&root, id, file, title, "Author");
+            audiobook.library_id.clone_from(&library_id);
+            std::fs::create_dir_all(audiobook.path.parent().unwrap()).unwrap();
+            std::fs::write(&audiobook.path, id).unwrap();
+            repository.upsert(&audiobook).unwrap();
+            audiobooks.push(audiobook);
+        }
+        db.progress_repository()
+            .upsert(&Progress::new("1", 1234))
+            .unwrap();
+        (dir, db, audiobooks)
+    }
+
+    #[test]
+    fn test_apply_moves_files_and_keeps_progress() {
+        let (dir, db, audiobooks) = setup();
+        let root = dir.path().join("library");
+        let organizer = LibraryOrganizer::default();
+        let plan = organizer.plan(&root, &audiobooks);
+        let repository = db.audiobook_repository();
+
+        assert_eq!(organizer.apply(&plan, &repository).unwrap(), 2);
+
+        let moved = root.join("Author/One/one.mp3");
+        assert_eq!(std::fs::read_to_string(&moved).unwrap(), "1");
+        assert!(
+            !root.join("incoming").exists(),
+            "empty source folder removed"
+        );
+        let stored = repository.find_by_id("1").unwrap().unwrap();
+        assert_eq!(stored.path, moved);
+        let progress = db
+            .progress_repository()
+            .find_by_audiobook("1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(progress.position_seconds, 1234);
+
+        // Organizing again is a no-op
+        let plan = organizer.plan(
+            &root,
+            &repository.find_by_library(&stored.library_id).unwrap(),
+        );
+        assert!(plan.moves.is_empty());
+        assert_eq!(plan.unchanged, 2);
+    }
+
+    #[test]
+    fn test_plan_with_series_from_database() {
+        let (dir, db, audiobooks) = setup();
+        let root = dir.path().join("library");
+        let series = db.series_repository();
+        series.set_series("2", "The Saga", Some(1.5)).unwrap();
+
+        let memberships = series
+            .find_memberships_in_library(&audiobooks[0].library_id)
+            .unwrap();
+        let plan = LibraryOrganizer::default().plan_with_series(&root, &audiobooks, &memberships);
+        let two = plan
+            .moves
+            .iter()
+            .find(|planned| planned.audiobook_id == "2")
+            .unwrap();
+        assert_eq!(two.to, root.join("Author/The Saga/1.5 - Two/two.mp3"));
+    }
+
+    #[test]
+    fn test_apply_rolls_back_on_failure() {
+        let (dir, db, audiobooks) = setup();
+        let root = dir.path().join("library");
+        let organizer = LibraryOrganizer::default();
+        let plan = organizer.plan(&root, &audiobooks);
+        // A file where the second book's folder should go makes its move fail
+        std::fs::create_dir_all(root.join("Author")).unwrap();
+        std::fs::write(root.join("Author/Two"), b"").unwrap();
+        let repository = db.audiobook_repository();
+
+        assert!(organizer.apply(&plan, &repository).is_err());
+
+        for audiobook in &audiobooks {
+            assert!(
+                audiobook.path.exists(),
+                "{} restored",
+                audiobook.path.display()
+            );
+            let stored = repository.find_by_id(&audiobook.id).unwrap().unwrap();
+            assert_eq!(stored.path, audiobook.path);
+        }
+        assert!(!root.join("Author/One").exists());
+    }
+}
@@ -92,6 +92,36 @@ impl Audiobook {
         self.author.as_deref().unwrap_or(fallbacks::UNKNOWN_AUTHOR)
     }
 
+    /// MIME type of the cover art, detected from its leading bytes
+    ///
+    /// Returns `None` without cover art, and `application/octet-stream` for
+    /// an image format that is not recognised.
+    #[must_use]
+    pub fn cover_mime_type(&self) -> Option<&'static str> {
+        let mime_type = match self.cover_art.as_deref()? {
+            [0x89, b'P', b'N', b'G', ..] => "image/png",
+            [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+            [b'G', b'I', b'F', b'8', ..] => "image/gif",
+            [
+                b'R',
+                b'I',
+                b'F',
+                b'F',
+                _,
+                _,
+                _,
+                _,
+                b'W',
+                b'E',
+                b'B',
+                b'P',
+                ..,
+            ] => "image/webp",
+            _ => "application/octet-stream",
+        };
+        Some(mime_type)
+    }
+
     /// Updates the last modified timestamp
     pub fn touch(&mut self) {
         self.updated_at = Utc::now();
@@ -207,4 +237,20 @@ mod tests {
 
         assert!(audiobook.updated_at > original_time);
     }
+
+    #[test]
+    fn test_cover_mime_type() {
+        let mut audiobook = Audiobook::new(library::TEST_ID, audiobook::TEST_PATH);
+        assert_eq!(audiobook.cover_mime_type(), None);
+
+        audiobook.cover_art = Some(vec![0xFF, 0xD8, 0xFF, 0xE0]);
+        assert_eq!(audiobook.cover_mime_type(), Some("image/jpeg"));
+        audiobook.cover_art = Some(b"\x89PNG\r\n".to_vec());
+        assert_eq!(audiobook.cover_mime_type(), Some("image/png"));
+        audiobook.cover_art = Some(b"not an image".to_vec());
+        assert_eq!(
+            audiobook.cover_mime_type(),
+            Some("application/octet-stream")
+        );
+    }
 }
@@ -0,0 +1,39 @@
+//! Bookmark models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A saved position in an audiobook, owned by a listener profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// Unique identifier
+    pub id: String,
+    /// ID of the profile the bookmark belongs to
+    pub profile_id: String,
+    /// ID of the audiobook
+    pub audiobook_id: String,
+    /// Bookmarked position in seconds
+    pub position_seconds: u64,
+    /// Short label such as "Important"
+    pub name: String,
+    /// Optional longer note
+    pub note: Option<String>,
+    /// When the bookmark was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl Bookmark {
+    /// Creates a bookmark for a profile at a position
+    #[must_use]
+    pub fn new(profile_id: &str, audiobook_id: &str, position_seconds: u64, name: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            profile_id: profile_id.to_string(),
+            audiobook_id: audiobook_id.to_string(),
+            position_seconds,
+            name: name.to_string(),
+            note: None,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Collection and tag models
+//!
+//! Unlike libraries, which mirror a folder on disk, collections and tags are
+//! groupings the listener makes: a collection is an ordered list such as a
+//! commute queue, a tag is a free-form label shared by any number of books.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A user-defined, ordered list of audiobooks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    /// Unique identifier
+    pub id: String,
+    /// Display name, unique ignoring case
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Number of audiobooks in the collection
+    pub audiobook_count: usize,
+    /// When the collection was created
+    pub created_at: DateTime<Utc>,
+    /// When the collection was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A free-form label attached to audiobooks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    /// Unique identifier
+    pub id: String,
+    /// Label text, unique ignoring case
+    pub name: String,
+    /// Number of audiobooks with this tag
+    pub audiobook_count: usize,
+}
@@ -0,0 +1,183 @@
+//! Persisted background job model
+//!
+//! Long running scans and processing batches are recorded as jobs so they can
+//! be resumed after a crash or after the application was closed mid-run.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AppError;
+
+/// Kind of work a job performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobType {
+    /// Library scan
+    Scan,
+    /// Audio processing batch
+    Process,
+}
+
+impl JobType {
+    /// Returns the string stored in the database for this job type
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Scan => "scan",
+            Self::Process => "process",
+        }
+    }
+}
+
+impl fmt::Display for JobType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scan" => Ok(Self::Scan),
+            "process" => Ok(Self::Process),
+            other => Err(AppError::InvalidData(format!("Unknown job type: {other}"))),
+        }
+    }
+}
+
+/// Lifecycle status of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Created but not started yet
+    Pending,
+    /// Currently running, or interrupted while running
+    Running,
+    /// Finished successfully
+    Completed,
+    /// Stopped because of an error
+    Failed,
+    /// Stopped at the user's request
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns the string stored in the database for this status
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether a job with this status should be resumed on startup
+    ///
+    /// A job that is still marked running when the application starts was
+    /// interrupted by a crash or by closing the application.
+    #[must_use]
+    pub const fn is_resumable(self) -> bool {
+        matches!(self, Self::Pending | Self::Running)
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            "cancelled" => Ok(Self::Cancelled),
+            other => Err(AppError::InvalidData(format!(
+                "Unknown job status: {other}"
+            ))),
+        }
+    }
+}
+
+/// A persisted scan or processing job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    /// Unique identifier for the job
+    pub id: String,
+    /// Kind of work the job performs
+    pub job_type: JobType,
+    /// Current lifecycle status
+    pub status: JobStatus,
+    /// Library the job belongs to, if any
+    pub library_id: Option<String>,
+    /// Job type specific parameters as JSON
+    pub parameters: String,
+    /// Number of files the job has to handle
+    pub total_items: u64,
+    /// Number of files already committed
+    pub processed_items: u64,
+    /// Error message if the job failed
+    pub error: Option<String>,
+    /// When the job was created
+    pub created_at: DateTime<Utc>,
+    /// When the job was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Job {
+    /// Fraction of files already committed, between 0.0 and 1.0
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        if self.total_items == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let progress = self.processed_items as f32 / self.total_items as f32;
+        progress.clamp(0.0, 1.0)
+    }
+
+    /// Deserializes the job parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::InvalidData`] if the stored parameters do not match `T`.
+    pub fn parameters<T: for<'de> Deserialize<'de>>(&self) -> Result<T, AppError> {
+        serde_json::from_str(&self.parameters).map_err(|e| {
+            AppError::InvalidData(format!("Invalid parameters for job {}: {e}", self.id))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            JobStatus::Pending,
+            JobStatus::Running,
+            JobStatus::Completed,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+        ] {
+            assert_eq!(status.as_str().parse::<JobStatus>().unwrap(), status);
+        }
+        assert!(JobStatus::Running.is_resumable());
+        assert!(!JobStatus::Cancelled.is_resumable());
+        assert_eq!("process".parse::<JobType>().unwrap(), JobType::Process);
+        assert!("export".parse::<JobType>().is_err());
+    }
+}
@@ -6,18 +6,32 @@
 //! - Configuration models (user preferences, themes)
 
 pub mod audiobook;
+pub mod bookmark;
+pub mod collection;
 pub mod core;
+pub mod job;
 pub mod library;
+pub mod person;
+pub mod profile;
 pub mod progress;
 pub mod search;
+pub mod series;
+pub mod smart_collection;
 pub mod ui;
 
 // Re-export commonly used types for convenience
 pub use audiobook::Audiobook;
+pub use bookmark::Bookmark;
+pub use collection::{Collection, Tag};
 pub use core::Chapter;
+pub use job::{Job, JobStatus, JobType};
 pub use library::Library;
+pub use person::{Person, PersonRole};
+pub use profile::{DEFAULT_PROFILE_ID, Profile};
 pub use progress::Progress;
 pub use search::{SearchQuery, SearchResult};
+pub use series::{Series, SeriesMembership};
+pub use smart_collection::{Condition, Rule, RuleField, RuleOperator, RuleValue, SmartCollection};
 pub use ui::{
     AppData, AppState, PlaybackConfig, ThemeConfig, UserPreferences, ViewType, WindowConfig,
 };
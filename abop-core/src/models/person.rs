@@ -0,0 +1,47 @@
+//! Author and narrator models
+//!
+//! People are stored once and linked to every audiobook they wrote or read,
+//! so the library can be browsed by person instead of by free-text field.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Role a person has on an audiobook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PersonRole {
+    /// Wrote the book
+    Author,
+    /// Reads the audiobook
+    Narrator,
+}
+
+impl PersonRole {
+    /// Returns the lowercase name of the role
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Author => "author",
+            Self::Narrator => "narrator",
+        }
+    }
+}
+
+impl fmt::Display for PersonRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An author or narrator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+    /// Unique identifier
+    pub id: String,
+    /// Display name in natural order, e.g. `Ursula K. Le Guin`
+    pub name: String,
+    /// Name used for sorting, e.g. `Le Guin, Ursula K.`
+    pub sort_name: String,
+    /// Number of audiobooks linked to this person
+    pub audiobook_count: usize,
+}
@@ -0,0 +1,60 @@
+//! Listener profile models
+//!
+//! Several people can share one library. Each listener has a profile that
+//! owns their progress, bookmarks and playback preferences, so nobody
+//! overwrites anyone else's position.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ui::PlaybackConfig;
+
+/// ID of the profile created by the migration, used when none is chosen
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// Serde default for records written before profiles existed
+pub(crate) fn default_profile_id() -> String {
+    DEFAULT_PROFILE_ID.to_string()
+}
+
+/// A listener sharing the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// Unique identifier
+    pub id: String,
+    /// Display name, unique ignoring case
+    pub name: String,
+    /// Playback preferences, or `None` to use the application defaults
+    pub playback: Option<PlaybackConfig>,
+    /// When the profile was created
+    pub created_at: DateTime<Utc>,
+    /// When the profile was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Profile {
+    /// Creates a new profile that uses the application playback defaults
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            playback: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether this is the default profile
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self.id == DEFAULT_PROFILE_ID
+    }
+
+    /// Playback preferences of the profile, falling back to the defaults
+    #[must_use]
+    pub fn playback_config(&self) -> PlaybackConfig {
+        self.playback.clone().unwrap_or_default()
+    }
+}
@@ -3,11 +3,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::profile::default_profile_id;
+
 /// Represents playback progress for an audiobook
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Progress {
     /// Unique identifier for the progress record
     pub id: String,
+    /// ID of the listener profile the progress belongs to
+    #[serde(default = "default_profile_id")]
+    pub profile_id: String,
     /// ID of the audiobook
     pub audiobook_id: String,
     /// Current playback position in seconds
@@ -23,12 +28,13 @@ pub struct Progress {
 }
 
 impl Progress {
-    /// Creates a new progress record for an audiobook
+    /// Creates a new progress record for an audiobook in the default profile
     #[must_use]
     pub fn new(audiobook_id: &str, position_seconds: u64) -> Self {
         let now = Utc::now();
         Self {
             id: uuid::Uuid::new_v4().to_string(),
+            profile_id: default_profile_id(),
             audiobook_id: audiobook_id.to_string(),
             position_seconds,
             completed: false,
@@ -38,6 +44,13 @@ impl Progress {
         }
     }
 
+    /// Moves the record to another listener profile
+    #[must_use]
+    pub fn with_profile(mut self, profile_id: &str) -> Self {
+        self.profile_id = profile_id.to_string();
+        self
+    }
+
     /// Updates the current position and marks as played
     pub fn update_position(&mut self, position_seconds: u64) {
         self.position_seconds = position_seconds;
@@ -97,12 +110,14 @@ impl Progress {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::DEFAULT_PROFILE_ID;
     use crate::test_constants::*;
 
     #[test]
     fn test_progress_creation() {
         let progress = Progress::new(audiobook::TEST_ID, 300);
         assert_eq!(progress.audiobook_id, audiobook::TEST_ID);
+        assert_eq!(progress.profile_id, DEFAULT_PROFILE_ID);
         assert_eq!(progress.position_seconds, 300);
         assert!(!progress.completed);
         assert!(progress.last_played.is_some());
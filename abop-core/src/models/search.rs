@@ -98,8 +98,14 @@ pub struct SearchQuery {
     pub min_duration: Option<u64>,
     /// Maximum duration in seconds (optional)
     pub max_duration: Option<u64>,
+    /// Filter by collection ID, ordering results as in the collection (optional)
+    pub collection_id: Option<String>,
+    /// Filter by tag name (optional)
+    pub tag: Option<String>,
     /// Include completed audiobooks in results
     pub include_completed: bool,
+    /// Profile whose progress decides completion (default profile if `None`)
+    pub profile_id: Option<String>,
     /// Maximum number of results to return
     pub limit: Option<usize>,
 }
@@ -115,7 +121,10 @@ impl SearchQuery {
             narrator: None,
             min_duration: None,
             max_duration: None,
+            collection_id: None,
+            tag: None,
             include_completed: true,
+            profile_id: None,
             limit: None,
         }
     }
@@ -153,6 +162,20 @@ impl SearchQuery {
         self
     }
 
+    /// Sets the collection filter
+    #[must_use]
+    pub fn in_collection(mut self, collection_id: &str) -> Self {
+        self.collection_id = Some(collection_id.to_string());
+        self
+    }
+
+    /// Sets the tag filter
+    #[must_use]
+    pub fn tagged(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
     /// Sets whether to include completed audiobooks
     #[must_use]
     pub const fn include_completed(mut self, include: bool) -> Self {
@@ -160,6 +183,13 @@ impl SearchQuery {
         self
     }
 
+    /// Sets the profile whose progress decides completion
+    #[must_use]
+    pub fn for_profile(mut self, profile_id: &str) -> Self {
+        self.profile_id = Some(profile_id.to_string());
+        self
+    }
+
     /// Sets the maximum number of results
     #[must_use]
     pub const fn limit(mut self, limit: usize) -> Self {
@@ -175,6 +205,8 @@ impl SearchQuery {
             || self.narrator.is_some()
             || self.min_duration.is_some()
             || self.max_duration.is_some()
+            || self.collection_id.is_some()
+            || self.tag.is_some()
             || !self.include_completed
     }
 
@@ -208,6 +240,12 @@ impl SearchQuery {
                 filters.push(format!("max duration: {}min", max / 60));
             }
         }
+        if let Some(collection_id) = &self.collection_id {
+            filters.push(format!("collection: {collection_id}"));
+        }
+        if let Some(tag) = &self.tag {
+            filters.push(format!("tag: {tag}"));
+        }
         if !self.include_completed {
             filters.push("incomplete only".to_string());
         }
@@ -319,4 +357,11 @@ mod tests {
         assert!(description.contains("min duration: 2h"));
         assert!(description.contains("incomplete only"));
     }
+
+    #[test]
+    fn test_collection_and_tag_filters() {
+        let query = SearchQuery::new("").in_collection("col-1").tagged("Sci-Fi");
+        assert!(query.has_filters());
+        assert_eq!(query.filter_description(), "collection: col-1, tag: Sci-Fi");
+    }
 }
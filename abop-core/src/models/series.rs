@@ -0,0 +1,38 @@
+//! Series models
+//!
+//! A series groups audiobooks in reading order. Each membership carries the
+//! position of the book, which may be fractional for novellas such as `2.5`.
+
+use serde::{Deserialize, Serialize};
+
+/// A book series
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Series {
+    /// Unique identifier
+    pub id: String,
+    /// Display name, e.g. `The Stormlight Archive`
+    pub name: String,
+    /// Number of audiobooks in the series
+    pub audiobook_count: usize,
+}
+
+/// Membership of one audiobook in a series
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesMembership {
+    /// ID of the audiobook
+    pub audiobook_id: String,
+    /// ID of the series
+    pub series_id: String,
+    /// Display name of the series
+    pub series_name: String,
+    /// Position of the book in the series, if known
+    pub series_index: Option<f64>,
+}
+
+impl SeriesMembership {
+    /// Formats the series index for display, e.g. `3` or `2.5`
+    #[must_use]
+    pub fn index_label(&self) -> Option<String> {
+        self.series_index.map(|index| index.to_string())
+    }
+}
@@ -0,0 +1,825 @@
+//! Smart collection and filter rule models
+//!
+//! A smart collection is a named [`Rule`] whose members are whatever
+//! audiobooks currently match it, such as "unfinished, longer than 10h,
+//! narrated by X, added this month". Rules are trees of conditions combined
+//! with `and`/`or` and have a small text syntax:
+//!
+//! ```text
+//! completed = false and duration > 10h and narrator contains "Kramer"
+//!     and (added within 30d or tag = favourite)
+//! ```
+//!
+//! `and` binds tighter than `or`. Text values may be bare words or double
+//! quoted. Durations take `h`, `m` and `s` units, sizes `kb`, `mb` and `gb`,
+//! dates are written `YYYY-MM-DD` and `within` takes a number of days.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{AppError, Result};
+
+/// A named, self-updating list of the audiobooks matching a rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartCollection {
+    /// Unique identifier
+    pub id: String,
+    /// Display name, unique ignoring case
+    pub name: String,
+    /// Listener profile whose progress the rule's progress conditions test
+    #[serde(default = "super::profile::default_profile_id")]
+    pub profile_id: String,
+    /// Rule an audiobook must match to be a member
+    pub rule: Rule,
+    /// Number of members at the last evaluation
+    pub audiobook_count: usize,
+    /// When the collection was created
+    pub created_at: DateTime<Utc>,
+    /// When the rule was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Audiobook, progress or tag property a condition tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleField {
+    /// Audiobook title
+    Title,
+    /// Author tag
+    Author,
+    /// Narrator tag
+    Narrator,
+    /// Description
+    Description,
+    /// File path
+    Path,
+    /// ID of the library the audiobook belongs to
+    Library,
+    /// Duration in seconds
+    Duration,
+    /// File size in bytes
+    Size,
+    /// When the audiobook was added to the database
+    Added,
+    /// Playback position in seconds
+    Position,
+    /// Whether the audiobook was listened to the end
+    Completed,
+    /// When the audiobook was last played
+    LastPlayed,
+    /// Name of a tag on the audiobook
+    Tag,
+}
+
+/// Kind of value a [`RuleField`] holds, which decides the valid operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Free text
+    Text,
+    /// Whole number
+    Number,
+    /// Point in time
+    Date,
+    /// True or false
+    Bool,
+    /// Set of tag names
+    Tags,
+}
+
+impl RuleField {
+    /// All fields, in the order they are documented
+    pub const ALL: [Self; 13] = [
+        Self::Title,
+        Self::Author,
+        Self::Narrator,
+        Self::Description,
+        Self::Path,
+        Self::Library,
+        Self::Duration,
+        Self::Size,
+        Self::Added,
+        Self::Position,
+        Self::Completed,
+        Self::LastPlayed,
+        Self::Tag,
+    ];
+
+    /// Name of the field in rule text
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Author => "author",
+            Self::Narrator => "narrator",
+            Self::Description => "description",
+            Self::Path => "path",
+            Self::Library => "library",
+            Self::Duration => "duration",
+            Self::Size => "size",
+            Self::Added => "added",
+            Self::Position => "position",
+            Self::Completed => "completed",
+            Self::LastPlayed => "last_played",
+            Self::Tag => "tag",
+        }
+    }
+
+    /// Kind of value the field holds
+    #[must_use]
+    pub const fn kind(self) -> FieldKind {
+        match self {
+            Self::Title | Self::Author | Self::Narrator | Self::Description | Self::Path => {
+                FieldKind::Text
+            }
+            Self::Library => FieldKind::Text,
+            Self::Duration | Self::Size | Self::Position => FieldKind::Number,
+            Self::Added | Self::LastPlayed => FieldKind::Date,
+            Self::Completed => FieldKind::Bool,
+            Self::Tag => FieldKind::Tags,
+        }
+    }
+}
+
+impl fmt::Display for RuleField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RuleField {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| AppError::Parse(format!("Unknown rule field: {s}")))
+    }
+}
+
+/// Comparison a condition applies to a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOperator {
+    /// Equal, ignoring case for text; for tags, has the tag
+    Equals,
+    /// Not equal, or not set; for tags, does not have the tag
+    NotEquals,
+    /// Text contains the value, ignoring case
+    Contains,
+    /// Text does not contain the value, or is not set
+    NotContains,
+    /// Text starts with the value, ignoring case
+    StartsWith,
+    /// Greater than, or after for dates
+    GreaterThan,
+    /// Greater than or equal
+    AtLeast,
+    /// Less than, or before for dates
+    LessThan,
+    /// Less than or equal
+    AtMost,
+    /// Date within the given number of days before now
+    WithinDays,
+    /// Has a non-empty value; for tags, has any tag
+    IsSet,
+    /// Has no value; for tags, has no tags
+    IsNotSet,
+}
+
+impl RuleOperator {
+    /// Operator as written in rule text
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Equals => "=",
+            Self::NotEquals => "!=",
+            Self::Contains => "contains",
+            Self::NotContains => "!contains",
+            Self::StartsWith => "starts_with",
+            Self::GreaterThan => ">",
+            Self::AtLeast => ">=",
+            Self::LessThan => "<",
+            Self::AtMost => "<=",
+            Self::WithinDays => "within",
+            Self::IsSet => "is set",
+            Self::IsNotSet => "is not set",
+        }
+    }
+
+    /// Whether the operator is used without a value
+    #[must_use]
+    pub const fn is_unary(self) -> bool {
+        matches!(self, Self::IsSet | Self::IsNotSet)
+    }
+
+    /// Whether the operator can be applied to a field of the given kind
+    #[must_use]
+    pub const fn applies_to(self, kind: FieldKind) -> bool {
+        match kind {
+            FieldKind::Text => matches!(
+                self,
+                Self::Equals
+                    | Self::NotEquals
+                    | Self::Contains
+                    | Self::NotContains
+                    | Self::StartsWith
+                    | Self::IsSet
+                    | Self::IsNotSet
+            ),
+            FieldKind::Number => matches!(
+                self,
+                Self::Equals
+                    | Self::NotEquals
+                    | Self::GreaterThan
+                    | Self::AtLeast
+                    | Self::LessThan
+                    | Self::AtMost
+                    | Self::IsSet
+                    | Self::IsNotSet
+            ),
+            FieldKind::Date => matches!(
+                self,
+                Self::GreaterThan
+                    | Self::AtLeast
+                    | Self::LessThan
+                    | Self::AtMost
+                    | Self::WithinDays
+                    | Self::IsSet
+                    | Self::IsNotSet
+            ),
+            FieldKind::Bool => matches!(self, Self::Equals | Self::NotEquals),
+            FieldKind::Tags => {
+                matches!(
+                    self,
+                    Self::Equals | Self::NotEquals | Self::IsSet | Self::IsNotSet
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for RuleOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Value a condition compares against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum RuleValue {
+    /// No value, for `is set` and `is not set`
+    None,
+    /// Text, tag name or library ID
+    Text(String),
+    /// Seconds, bytes, or days for `within`
+    Number(u64),
+    /// Calendar date, compared at midnight UTC
+    Date(NaiveDate),
+    /// True or false
+    Bool(bool),
+}
+
+/// A single field, operator and value test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Condition {
+    /// Field to test
+    pub field: RuleField,
+    /// Comparison to apply
+    pub operator: RuleOperator,
+    /// Value to compare against
+    pub value: RuleValue,
+}
+
+impl Condition {
+    /// Create a condition, checking that the operator and value suit the field
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ValidationFailed`] if the operator cannot be used
+    /// with the field or the value has the wrong type.
+    pub fn new(field: RuleField, operator: RuleOperator, value: RuleValue) -> Result<Self> {
+        let condition = Self {
+            field,
+            operator,
+            value,
+        };
+        condition.validate()?;
+        Ok(condition)
+    }
+
+    /// Check that the operator and value suit the field
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ValidationFailed`] if the operator cannot be used
+    /// with the field or the value has the wrong type.
+    pub fn validate(&self) -> Result<()> {
+        let kind = self.field.kind();
+        if !self.operator.applies_to(kind) {
+            return Err(AppError::ValidationFailed(format!(
+                "Operator '{}' cannot be used with {}",
+                self.operator, self.field
+            )));
+        }
+        let value_fits = match (&self.value, self.operator) {
+            (RuleValue::None, operator) => operator.is_unary(),
+            (_, operator) if operator.is_unary() => false,
+            (RuleValue::Number(_), RuleOperator::WithinDays) => true,
+            (_, RuleOperator::WithinDays) => false,
+            (RuleValue::Text(_), _) => matches!(kind, FieldKind::Text | FieldKind::Tags),
+            (RuleValue::Number(_), _) => kind == FieldKind::Number,
+            (RuleValue::Date(_), _) => kind == FieldKind::Date,
+            (RuleValue::Bool(_), _) => kind == FieldKind::Bool,
+        };
+        if value_fits {
+            Ok(())
+        } else {
+            Err(AppError::ValidationFailed(format!(
+                "Invalid value for {} {}",
+                self.field, self.operator
+            )))
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.operator)?;
+        match &self.value {
+            RuleValue::None => Ok(()),
+            RuleValue::Text(text) => write!(f, " {}", quote(text)),
+            RuleValue::Number(days) if self.operator == RuleOperator::WithinDays => {
+                write!(f, " {days}d")
+            }
+            RuleValue::Number(seconds)
+                if matches!(self.field, RuleField::Duration | RuleField::Position) =>
+            {
+                write!(f, " {}", format_seconds(*seconds))
+            }
+            RuleValue::Number(number) => write!(f, " {number}"),
+            RuleValue::Date(date) => write!(f, " {date}"),
+            RuleValue::Bool(value) => write!(f, " {value}"),
+        }
+    }
+}
+
+/// Conditions combined with `and`/`or`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// Matches when every rule matches; an empty list matches everything
+    All(Vec<Rule>),
+    /// Matches when any rule matches; an empty list matches nothing
+    Any(Vec<Rule>),
+    /// A single test
+    Condition(Condition),
+}
+
+impl Rule {
+    /// Parse a rule from its text syntax
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Parse`] if the text is not a valid rule, or
+    /// [`AppError::ValidationFailed`] if a condition combines a field with an
+    /// operator or value it does not support.
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        if parser.tokens.is_empty() {
+            return Ok(Self::All(Vec::new()));
+        }
+        let rule = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(rule),
+            Some(token) => Err(AppError::Parse(format!("Unexpected {token} in rule"))),
+        }
+    }
+
+    /// Check every condition of the rule
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ValidationFailed`] for the first invalid condition.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::All(rules) | Self::Any(rules) => rules.iter().try_for_each(Self::validate),
+            Self::Condition(condition) => condition.validate(),
+        }
+    }
+
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>, in_all: bool) -> fmt::Result {
+        let (rules, separator) = match self {
+            Self::Condition(condition) => return write!(f, "{condition}"),
+            Self::All(rules) => (rules, " and "),
+            Self::Any(rules) => (rules, " or "),
+        };
+        // `or` inside `and`, and any group of one or none, needs parentheses
+        let parenthesize = in_all && matches!(self, Self::Any(_)) || rules.len() < 2;
+        if rules.is_empty() {
+            return f.write_str(if matches!(self, Self::All(_)) {
+                ""
+            } else {
+                "()"
+            });
+        }
+        if parenthesize && rules.len() > 1 {
+            f.write_str("(")?;
+        }
+        for (i, rule) in rules.iter().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+            rule.fmt_nested(f, matches!(self, Self::All(_)))?;
+        }
+        if parenthesize && rules.len() > 1 {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_nested(f, false)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<Condition> for Rule {
+    fn from(condition: Condition) -> Self {
+        Self::Condition(condition)
+    }
+}
+
+/// Quote text if it would not read back as a single bare word
+fn quote(text: &str) -> String {
+    let bare = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.:/".contains(c))
+        && !["and", "or", "is", "not", "set"]
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(text));
+    if bare {
+        text.to_string()
+    } else {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Seconds in the largest whole unit
+fn format_seconds(seconds: u64) -> String {
+    if seconds > 0 && seconds.is_multiple_of(3600) {
+        format!("{}h", seconds / 3600)
+    } else if seconds > 0 && seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Symbol(&'static str),
+    Word(String),
+    Quoted(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("'('"),
+            Self::Close => f.write_str("')'"),
+            Self::Symbol(symbol) => write!(f, "'{symbol}'"),
+            Self::Word(word) => write!(f, "'{word}'"),
+            Self::Quoted(text) => write!(f, "\"{text}\""),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    const SYMBOLS: [&str; 6] = ["!=", ">=", "<=", "=", ">", "<"];
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            rest = &rest[1..];
+        } else if c == '"' {
+            let mut value = String::new();
+            let mut chars = rest[1..].char_indices();
+            let end = loop {
+                match chars.next() {
+                    Some((i, '"')) => break i + 2,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, escaped)) => value.push(escaped),
+                        None => break 0,
+                    },
+                    Some((_, c)) => value.push(c),
+                    None => break 0,
+                }
+            };
+            if end == 0 {
+                return Err(AppError::Parse("Unterminated quote in rule".to_string()));
+            }
+            tokens.push(Token::Quoted(value));
+            rest = &rest[end..];
+        } else if let Some(symbol) = SYMBOLS.into_iter().find(|s| rest.starts_with(s)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "()\"=<>".contains(c) || c == '!')
+                .unwrap_or(rest.len());
+            // A lone `!` only starts `!=` or `!contains`
+            let end = if end == 0 {
+                rest[1..]
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .map_or(rest.len(), |i| i + 1)
+            } else {
+                end
+            };
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Rule> {
+        let mut rules = vec![self.parse_and()?];
+        while self.eat_keyword("or") {
+            rules.push(self.parse_and()?);
+        }
+        Ok(if rules.len() == 1 {
+            rules.remove(0)
+        } else {
+            Rule::Any(rules)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Rule> {
+        let mut rules = vec![self.parse_atom()?];
+        while self.eat_keyword("and") {
+            rules.push(self.parse_atom()?);
+        }
+        Ok(if rules.len() == 1 {
+            rules.remove(0)
+        } else {
+            Rule::All(rules)
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Rule> {
+        match self.next() {
+            Some(Token::Open) => {
+                let rule = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(rule),
+                    _ => Err(AppError::Parse("Missing ')' in rule".to_string())),
+                }
+            }
+            Some(Token::Word(field)) => self.parse_condition(field.parse()?),
+            Some(token) => Err(AppError::Parse(format!(
+                "Expected a field name, found {token}"
+            ))),
+            None => Err(AppError::Parse("Rule ends too early".to_string())),
+        }
+    }
+
+    fn parse_condition(&mut self, field: RuleField) -> Result<Rule> {
+        let operator = match self.next() {
+            Some(Token::Symbol(symbol)) => match symbol {
+                "=" => RuleOperator::Equals,
+                "!=" => RuleOperator::NotEquals,
+                ">" => RuleOperator::GreaterThan,
+                ">=" => RuleOperator::AtLeast,
+                "<" => RuleOperator::LessThan,
+                _ => RuleOperator::AtMost,
+            },
+            Some(Token::Word(word)) => match word.to_ascii_lowercase().as_str() {
+                "contains" => RuleOperator::Contains,
+                "!contains" => RuleOperator::NotContains,
+                "starts_with" => RuleOperator::StartsWith,
+                "within" => RuleOperator::WithinDays,
+                "is" if self.eat_keyword("set") => RuleOperator::IsSet,
+                "is" if self.eat_keyword("not") && self.eat_keyword("set") => {
+                    RuleOperator::IsNotSet
+                }
+                _ => {
+                    return Err(AppError::Parse(format!(
+                        "Unknown operator '{word}' after {field}"
+                    )));
+                }
+            },
+            _ => {
+                return Err(AppError::Parse(format!(
+                    "Expected an operator after {field}"
+                )));
+            }
+        };
+
+        let value = if operator.is_unary() {
+            RuleValue::None
+        } else {
+            let text = match self.next() {
+                Some(Token::Word(text) | Token::Quoted(text)) => text,
+                _ => {
+                    return Err(AppError::Parse(format!(
+                        "Expected a value after {field} {operator}"
+                    )));
+                }
+            };
+            parse_value(field, operator, &text)?
+        };
+        Ok(Condition::new(field, operator, value)?.into())
+    }
+}
+
+fn parse_value(field: RuleField, operator: RuleOperator, text: &str) -> Result<RuleValue> {
+    let invalid = || AppError::Parse(format!("Invalid value '{text}' for {field} {operator}"));
+    if operator == RuleOperator::WithinDays {
+        let days = text.strip_suffix(['d', 'D']).unwrap_or(text);
+        return days.parse().map(RuleValue::Number).map_err(|_| invalid());
+    }
+    match field.kind() {
+        FieldKind::Text | FieldKind::Tags => Ok(RuleValue::Text(text.to_string())),
+        FieldKind::Bool => match text.to_ascii_lowercase().as_str() {
+            "true" | "yes" => Ok(RuleValue::Bool(true)),
+            "false" | "no" => Ok(RuleValue::Bool(false)),
+            _ => Err(invalid()),
+        },
+        FieldKind::Date => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .map(RuleValue::Date)
+            .map_err(|_| invalid()),
+        FieldKind::Number if field == RuleField::Size => {
+            parse_size(text).map(RuleValue::Number).ok_or_else(invalid)
+        }
+        FieldKind::Number => parse_duration(text)
+            .map(RuleValue::Number)
+            .ok_or_else(invalid),
+    }
+}
+
+/// Seconds from text like `10h`, `1h30m`, `90m`, `45s` or a plain number
+fn parse_duration(text: &str) -> Option<u64> {
+    if let Ok(seconds) = text.parse() {
+        return Some(seconds);
+    }
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in text.to_ascii_lowercase().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let amount: u64 = digits.parse().ok()?;
+        total = total.checked_add(amount.checked_mul(unit)?)?;
+        digits.clear();
+    }
+    digits.is_empty().then_some(total)
+}
+
+/// Bytes from text like `500mb`, `1gb` or a plain number, using 1024-based units
+fn parse_size(text: &str) -> Option<u64> {
+    let lower = text.to_ascii_lowercase();
+    let (number, unit) = [("gb", 1 << 30), ("mb", 1 << 20), ("kb", 1 << 10)]
+        .into_iter()
+        .find_map(|(suffix, unit)| lower.strip_suffix(suffix).map(|number| (number, unit)))
+        .unwrap_or((lower.as_str(), 1));
+    number.parse::<u64>().ok()?.checked_mul(unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: RuleField, operator: RuleOperator, value: RuleValue) -> Rule {
+        Condition::new(field, operator, value).unwrap().into()
+    }
+
+    #[test]
+    fn test_parse_precedence_and_values() {
+        let rule = Rule::parse(
+            r#"completed = false and duration > 10h and narrator contains "Michael Kramer"
+               or tag = favourite"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rule,
+            Rule::Any(vec![
+                Rule::All(vec![
+                    condition(
+                        RuleField::Completed,
+                        RuleOperator::Equals,
+                        RuleValue::Bool(false)
+                    ),
+                    condition(
+                        RuleField::Duration,
+                        RuleOperator::GreaterThan,
+                        RuleValue::Number(36_000)
+                    ),
+                    condition(
+                        RuleField::Narrator,
+                        RuleOperator::Contains,
+                        RuleValue::Text("Michael Kramer".to_string())
+                    ),
+                ]),
+                condition(
+                    RuleField::Tag,
+                    RuleOperator::Equals,
+                    RuleValue::Text("favourite".to_string())
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for text in [
+            "duration >= 1h30m and (added within 30d or last_played is not set)",
+            r#"title !contains "The \"End\"" and size < 500mb"#,
+            "added > 2026-01-31 and tag is set",
+            "author starts_with Tolk or (narrator = \"Andy Serkis\" and completed != true)",
+        ] {
+            let rule = Rule::parse(text).unwrap();
+            let printed = rule.to_string();
+            assert_eq!(Rule::parse(&printed).unwrap(), rule, "{printed}");
+        }
+        let rule = Rule::parse("position > 90m and library = lib-1").unwrap();
+        assert_eq!(rule.to_string(), "position > 90m and library = lib-1");
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for text in [
+            "duration contains 10h",
+            "completed = maybe",
+            "added within soon",
+            "rating > 3",
+            "title =",
+            "(title = a",
+            "title = \"open",
+            "title = a and",
+            "tag > 2",
+        ] {
+            assert!(Rule::parse(text).is_err(), "{text}");
+        }
+        assert_eq!(Rule::parse("  ").unwrap(), Rule::All(Vec::new()));
+    }
+
+    #[test]
+    fn test_rule_serialization() {
+        let rule = Rule::parse("duration > 10h and tag = sci-fi").unwrap();
+        let json = serde_json::to_string(&rule).unwrap();
+        let restored: Rule = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, rule);
+    }
+}
@@ -0,0 +1,160 @@
+//! HTTP transport for metadata providers
+
+use async_trait::async_trait;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::error::{AppError, Result};
+
+/// Default time allowed for a whole request, including redirects
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Largest response body accepted
+const MAX_RESPONSE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Redirects followed before a request is given up
+pub(super) const MAX_REDIRECTS: u32 = 5;
+
+/// A response to a GET request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Whether the status is 2xx
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+
+    /// Parse the body as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Metadata`] if the status is not successful or the
+    /// body is not the expected JSON.
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        if !self.is_success() {
+            return Err(AppError::Metadata(format!(
+                "Provider answered with HTTP {}",
+                self.status
+            )));
+        }
+        serde_json::from_slice(&self.body)
+            .map_err(|e| AppError::Metadata(format!("Invalid provider response: {e}")))
+    }
+}
+
+/// Performs the HTTP requests of a provider
+///
+/// [`DefaultHttpClient`] is used unless an application supplies its own
+/// implementation, for example to share its HTTP stack or to serve canned
+/// responses in tests.
+#[async_trait]
+pub trait HttpClient: Send + Sync {
+    /// Send a GET request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be sent or the response
+    /// cannot be read. HTTP error statuses are returned as responses.
+    async fn get(&self, url: &str) -> Result<HttpResponse>;
+}
+
+/// HTTP client for `http://` and `https://` URLs
+///
+/// Requests are made with [`ureq`] on the blocking thread pool, which is
+/// plenty for the handful of requests a metadata lookup makes. TLS uses
+/// rustls with the Mozilla root certificates, and redirects are followed.
+#[derive(Debug, Clone)]
+pub struct DefaultHttpClient {
+    agent: ureq::Agent,
+}
+
+impl DefaultHttpClient {
+    /// Create a client with the default timeout
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            agent: agent(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Set the time allowed for each request, including its redirects
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = agent(timeout);
+        self
+    }
+}
+
+impl Default for DefaultHttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HttpClient for DefaultHttpClient {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        let agent = self.agent.clone();
+        let url = url.to_string();
+        tokio::task::spawn_blocking(move || get_blocking(&agent, &url))
+            .await
+            .map_err(|e| AppError::TaskJoin(e.to_string()))?
+    }
+}
+
+/// Agent answering error statuses as responses so providers can report them
+fn agent(timeout: Duration) -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .max_redirects(MAX_REDIRECTS)
+        .http_status_as_error(false)
+        .user_agent(concat!("abop/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .into()
+}
+
+/// Send a GET request and read the whole response
+fn get_blocking(agent: &ureq::Agent, url: &str) -> Result<HttpResponse> {
+    let request_error = |e: ureq::Error| match e {
+        ureq::Error::Io(e) => AppError::Io(format!("Request to {url} failed: {e}")),
+        ureq::Error::TooManyRedirects => {
+            AppError::Metadata(format!("Too many redirects requesting {url}"))
+        }
+        e => AppError::Metadata(format!("Request to {url} failed: {e}")),
+    };
+    let mut response = agent
+        .get(url)
+        .header("Accept", "application/json")
+        .call()
+        .map_err(request_error)?;
+    let status = response.status().as_u16();
+    let body = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_RESPONSE_BYTES)
+        .read_to_vec()
+        .map_err(request_error)?;
+    Ok(HttpResponse { status, body })
+}
+
+/// Percent-encode a query parameter value
+pub(super) fn encode_query(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else if byte == b' ' {
+            encoded.push('+');
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
@@ -0,0 +1,115 @@
+//! Scoring provider results against a search query
+
+use std::collections::HashSet;
+
+use super::{MetadataProvider, ProviderBook, SearchQuery};
+use crate::error::Result;
+use crate::library::{normalize_author, normalize_title};
+use crate::sidecar::SidecarMetadata;
+
+/// Share of the score given to the title when the query names an author
+const TITLE_WEIGHT: f32 = 0.7;
+
+/// A provider result with its match score
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredMatch {
+    /// The provider record
+    pub book: ProviderBook,
+    /// How well it matches, from 0.0 to 1.0
+    pub score: f32,
+}
+
+/// Ranks provider results by how well they match a query
+///
+/// A matching ASIN is a certain match. Otherwise titles and authors are
+/// compared word by word after the normalization used for duplicate
+/// detection, so case, punctuation, bracketed notes such as `(Unabridged)`
+/// and name order do not matter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matcher {
+    /// Results scoring below this are discarded
+    pub min_score: f32,
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Self { min_score: 0.5 }
+    }
+}
+
+impl Matcher {
+    /// Create a matcher keeping results that score at least `min_score`
+    #[must_use]
+    pub const fn new(min_score: f32) -> Self {
+        Self { min_score }
+    }
+
+    /// Score a record against a query, from 0.0 to 1.0
+    #[must_use]
+    pub fn score(&self, query: &SearchQuery, candidate: &SidecarMetadata) -> f32 {
+        if let (Some(asin), Some(candidate_asin)) = (&query.asin, &candidate.asin)
+            && asin.trim().eq_ignore_ascii_case(candidate_asin.trim())
+        {
+            return 1.0;
+        }
+
+        let title = match (&query.title, &candidate.title) {
+            (Some(title), Some(candidate_title)) => {
+                word_similarity(&normalize_title(title), &normalize_title(candidate_title))
+            }
+            _ => 0.0,
+        };
+        let Some(author) = &query.author else {
+            return title;
+        };
+        let author = normalize_author(author);
+        let author_score = candidate
+            .authors
+            .iter()
+            .map(|candidate| word_similarity(&author, &normalize_author(candidate)))
+            .fold(0.0, f32::max);
+        TITLE_WEIGHT.mul_add(title, (1.0 - TITLE_WEIGHT) * author_score)
+    }
+
+    /// Score records against a query, best first, dropping weak matches
+    #[must_use]
+    pub fn rank(&self, query: &SearchQuery, books: Vec<ProviderBook>) -> Vec<ScoredMatch> {
+        let mut matches: Vec<ScoredMatch> = books
+            .into_iter()
+            .map(|book| ScoredMatch {
+                score: self.score(query, &book.metadata),
+                book,
+            })
+            .filter(|scored| scored.score >= self.min_score)
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches
+    }
+
+    /// Search a provider and return its best match, if any is good enough
+    ///
+    /// # Errors
+    ///
+    /// Returns the provider's error if the search fails.
+    pub async fn best_match(
+        &self,
+        provider: &dyn MetadataProvider,
+        query: &SearchQuery,
+    ) -> Result<Option<ScoredMatch>> {
+        let results = provider.search(query).await?;
+        Ok(self.rank(query, results).into_iter().next())
+    }
+}
+
+/// Dice coefficient of the word sets of two normalized strings
+fn word_similarity(a: &str, b: &str) -> f32 {
+    let a: HashSet<&str> = a.split_whitespace().collect();
+    let b: HashSet<&str> = b.split_whitespace().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(&b).count();
+    #[allow(clippy::cast_precision_loss)]
+    let score = (2 * shared) as f32 / (a.len() + b.len()) as f32;
+    score
+}
@@ -0,0 +1,139 @@
+//! Online metadata providers
+//!
+//! Catalog services such as Open Library know the title, authors, series and
+//! description of most published books. A [`MetadataProvider`] searches such
+//! a service by title, author or ASIN and fetches the full record of a hit.
+//! The [`Matcher`] scores search results against what the library already
+//! knows, and a [`MetadataReview`] lists the field-level differences so that
+//! nothing is overwritten before the listener accepts it.
+//!
+//! Providers talk HTTP through the [`HttpClient`] trait. The built-in
+//! [`DefaultHttpClient`] speaks `http://` and `https://`, so the public Open
+//! Library service at [`OPEN_LIBRARY_URL`] is reached directly.
+
+mod http;
+mod matcher;
+mod open_library;
+mod review;
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::models::Audiobook;
+use crate::sidecar::SidecarMetadata;
+
+pub use http::{DefaultHttpClient, HttpClient, HttpResponse};
+pub use matcher::{Matcher, ScoredMatch};
+pub use open_library::{OPEN_LIBRARY_URL, OpenLibraryProvider};
+pub use review::{FieldChange, MetadataReview};
+
+/// What to search a catalog for
+///
+/// Providers use whichever fields they support; an ASIN identifies a book
+/// exactly, while title and author are matched loosely.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    /// Book title
+    pub title: Option<String>,
+    /// Author name
+    pub author: Option<String>,
+    /// Audible ASIN
+    pub asin: Option<String>,
+}
+
+impl SearchQuery {
+    /// Search by title
+    #[must_use]
+    pub fn title(title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Search by ASIN
+    #[must_use]
+    pub fn asin(asin: impl Into<String>) -> Self {
+        Self {
+            asin: Some(asin.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Narrow the search to an author
+    #[must_use]
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Search for the title and first author of an audiobook
+    #[must_use]
+    pub fn from_audiobook(audiobook: &Audiobook) -> Self {
+        let author = audiobook.author.as_deref().and_then(|author| {
+            crate::library::parse_people(author)
+                .into_iter()
+                .next()
+                .map(|person| person.name)
+        });
+        Self {
+            title: audiobook.title.clone(),
+            author,
+            asin: None,
+        }
+    }
+
+    /// Whether there is nothing to search for
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        [&self.title, &self.author, &self.asin]
+            .iter()
+            .all(|value| value.as_deref().is_none_or(|value| value.trim().is_empty()))
+    }
+}
+
+/// A book record from a provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderBook {
+    /// Name of the provider the record came from
+    pub provider: String,
+    /// ID of the record at the provider, used to fetch its details
+    pub id: String,
+    /// The book metadata; search results may only carry part of it
+    pub metadata: SidecarMetadata,
+}
+
+/// A catalog service that can be searched for book metadata
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Short name of the provider, e.g. `openlibrary`
+    fn name(&self) -> &str;
+
+    /// Search the catalog
+    ///
+    /// Results are in the provider's order; rank them with a [`Matcher`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the service cannot be reached, or
+    /// [`AppError::Metadata`] if it fails or answers with an invalid response.
+    ///
+    /// [`AppError::Io`]: crate::error::AppError::Io
+    /// [`AppError::Metadata`]: crate::error::AppError::Metadata
+    async fn search(&self, query: &SearchQuery) -> Result<Vec<ProviderBook>>;
+
+    /// Fetch the full record of a search result by its ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if the service cannot be reached, or
+    /// [`AppError::Metadata`] if the record does not exist or the response
+    /// is invalid.
+    ///
+    /// [`AppError::Io`]: crate::error::AppError::Io
+    /// [`AppError::Metadata`]: crate::error::AppError::Metadata
+    async fn fetch(&self, id: &str) -> Result<ProviderBook>;
+}
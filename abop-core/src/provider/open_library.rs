@@ -0,0 +1,207 @@
+//! Open Library provider
+//!
+//! Searches go to `/search.json` and details come from the work record at
+//! `/works/{id}.json`, whose authors are resolved through `/authors/{id}.json`.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::http::encode_query;
+use super::{DefaultHttpClient, HttpClient, MetadataProvider, ProviderBook, SearchQuery};
+use crate::error::{AppError, Result};
+use crate::sidecar::SidecarMetadata;
+
+/// Address of the public Open Library service
+pub const OPEN_LIBRARY_URL: &str = "https://openlibrary.org";
+
+/// Provider name recorded on results
+const PROVIDER_NAME: &str = "openlibrary";
+
+/// Number of search results requested
+const SEARCH_LIMIT: usize = 10;
+
+/// Fields requested from the search API
+const SEARCH_FIELDS: &str =
+    "key,title,subtitle,author_name,first_publish_year,publisher,language,isbn,id_amazon";
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    docs: Vec<SearchDoc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SearchDoc {
+    key: String,
+    title: Option<String>,
+    subtitle: Option<String>,
+    author_name: Vec<String>,
+    first_publish_year: Option<i32>,
+    publisher: Vec<String>,
+    language: Vec<String>,
+    isbn: Vec<String>,
+    id_amazon: Vec<String>,
+}
+
+/// Text fields of Open Library records are either plain strings or
+/// `{"type": "/type/text", "value": "..."}` objects
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Text {
+    Plain(String),
+    Typed { value: String },
+}
+
+impl Text {
+    fn into_string(self) -> String {
+        match self {
+            Self::Plain(value) | Self::Typed { value } => value,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Work {
+    title: Option<String>,
+    subtitle: Option<String>,
+    description: Option<Text>,
+    subjects: Vec<String>,
+    first_publish_date: Option<String>,
+    authors: Vec<WorkAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkAuthor {
+    author: KeyRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyRef {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    name: Option<String>,
+}
+
+/// Metadata provider backed by the Open Library API
+pub struct OpenLibraryProvider {
+    base_url: String,
+    client: Arc<dyn HttpClient>,
+}
+
+impl OpenLibraryProvider {
+    /// Create a provider for the service at `base_url` using the
+    /// [`DefaultHttpClient`]
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        Self::with_client(base_url, Arc::new(DefaultHttpClient::new()))
+    }
+
+    /// Create a provider for the service at `base_url` using `client`
+    #[must_use]
+    pub fn with_client(base_url: &str, client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{path}", self.base_url);
+        log::debug!("Open Library request: {url}");
+        self.client.get(&url).await?.json()
+    }
+}
+
+/// Strip the `/works/` or `/books/` prefix from a key
+fn bare_key(key: &str) -> &str {
+    key.rsplit('/').next().unwrap_or(key)
+}
+
+#[async_trait]
+impl MetadataProvider for OpenLibraryProvider {
+    fn name(&self) -> &str {
+        PROVIDER_NAME
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<Vec<ProviderBook>> {
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut path = format!("/search.json?limit={SEARCH_LIMIT}&fields={SEARCH_FIELDS}");
+        if let Some(title) = &query.title {
+            path.push_str(&format!("&title={}", encode_query(title)));
+        }
+        if let Some(author) = &query.author {
+            path.push_str(&format!("&author={}", encode_query(author)));
+        }
+        if let Some(asin) = &query.asin {
+            path.push_str(&format!(
+                "&q={}",
+                encode_query(&format!("id_amazon:{asin}"))
+            ));
+        }
+
+        let response: SearchResponse = self.get_json(&path).await?;
+        Ok(response
+            .docs
+            .into_iter()
+            .filter(|doc| !doc.key.is_empty())
+            .map(|doc| ProviderBook {
+                provider: PROVIDER_NAME.to_string(),
+                id: bare_key(&doc.key).to_string(),
+                metadata: SidecarMetadata {
+                    title: doc.title,
+                    subtitle: doc.subtitle,
+                    authors: doc.author_name,
+                    published_year: doc.first_publish_year.map(|year| year.to_string()),
+                    publisher: doc.publisher.into_iter().next(),
+                    language: doc.language.into_iter().next(),
+                    isbn: doc.isbn.into_iter().next(),
+                    asin: doc.id_amazon.into_iter().next(),
+                    ..SidecarMetadata::default()
+                },
+            })
+            .collect())
+    }
+
+    async fn fetch(&self, id: &str) -> Result<ProviderBook> {
+        let id = bare_key(id);
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(AppError::Metadata(format!(
+                "Invalid Open Library ID '{id}'"
+            )));
+        }
+        let work: Work = self.get_json(&format!("/works/{id}.json")).await?;
+
+        let mut authors = Vec::new();
+        for entry in &work.authors {
+            let author: Author = self.get_json(&format!("{}.json", entry.author.key)).await?;
+            authors.extend(author.name);
+        }
+        let published_year = work.first_publish_date.as_deref().and_then(|date| {
+            date.split(|c: char| !c.is_ascii_digit())
+                .find(|part| part.len() == 4)
+                .map(str::to_string)
+        });
+
+        Ok(ProviderBook {
+            provider: PROVIDER_NAME.to_string(),
+            id: id.to_string(),
+            metadata: SidecarMetadata {
+                title: work.title,
+                subtitle: work.subtitle,
+                authors,
+                description: work.description.map(Text::into_string),
+                genres: work.subjects,
+                published_year,
+                ..SidecarMetadata::default()
+            },
+        })
+    }
+}
@@ -0,0 +1,116 @@
+//! Field-level review of provider metadata before it is applied
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::ProviderBook;
+use crate::models::Audiobook;
+use crate::sidecar::MetadataField;
+
+/// A field a provider record would change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// The audiobook field
+    pub field: MetadataField,
+    /// Value in the library
+    pub current: Option<String>,
+    /// Value from the provider
+    pub proposed: String,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.current {
+            Some(current) => write!(f, "{}: {current:?} -> {:?}", self.field, self.proposed),
+            None => write!(f, "{}: (none) -> {:?}", self.field, self.proposed),
+        }
+    }
+}
+
+/// The changes a provider record would make to an audiobook
+///
+/// Fields the provider leaves empty are never cleared, and fields that
+/// already hold the same value are not listed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataReview {
+    /// ID of the audiobook under review
+    pub audiobook_id: String,
+    /// Provider the record came from
+    pub provider: String,
+    /// ID of the record at the provider
+    pub provider_id: String,
+    /// Fields that would change
+    pub changes: Vec<FieldChange>,
+}
+
+impl MetadataReview {
+    /// Compare an audiobook with a provider record
+    #[must_use]
+    pub fn new(audiobook: &Audiobook, book: &ProviderBook) -> Self {
+        let changes = MetadataField::ALL
+            .into_iter()
+            .filter_map(|field| {
+                let proposed = field.in_sidecar(&book.metadata)?;
+                let current = field.in_audiobook(audiobook);
+                (current != Some(proposed.as_str())).then(|| FieldChange {
+                    field,
+                    current: current.map(str::to_string),
+                    proposed,
+                })
+            })
+            .collect();
+        Self {
+            audiobook_id: audiobook.id.clone(),
+            provider: book.provider.clone(),
+            provider_id: book.id.clone(),
+            changes,
+        }
+    }
+
+    /// Whether the record would change nothing
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Apply the accepted fields to an audiobook, returning those changed
+    ///
+    /// Fields not under review are ignored.
+    pub fn apply(
+        &self,
+        audiobook: &mut Audiobook,
+        accepted: &[MetadataField],
+    ) -> Vec<MetadataField> {
+        let mut applied = Vec::new();
+        for change in self
+            .changes
+            .iter()
+            .filter(|change| accepted.contains(&change.field))
+        {
+            *change.field.slot(audiobook) = Some(change.proposed.clone());
+            applied.push(change.field);
+        }
+        if !applied.is_empty() {
+            audiobook.updated_at = chrono::Utc::now();
+        }
+        applied
+    }
+
+    /// Apply every change to an audiobook
+    pub fn apply_all(&self, audiobook: &mut Audiobook) -> Vec<MetadataField> {
+        self.apply(audiobook, &MetadataField::ALL)
+    }
+}
+
+impl fmt::Display for MetadataReview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.provider, self.provider_id)?;
+        if self.changes.is_empty() {
+            return write!(f, ": no changes");
+        }
+        for change in &self.changes {
+            write!(f, "\n  {change}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use super::http::MAX_REDIRECTS;
+use super::*;
+use crate::error::AppError;
+use crate::sidecar::MetadataField;
+
+/// Open Library stand-in serving canned JSON on a local port
+struct MockServer {
+    server: Arc<tiny_http::Server>,
+    requests: Arc<Mutex<Vec<String>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Serve `routes`, keyed by path without the query string
+    fn start(routes: &[(&str, &str)]) -> Self {
+        let server = Arc::new(tiny_http::Server::http("127.0.0.1:0").unwrap());
+        let routes: HashMap<String, String> = routes
+            .iter()
+            .map(|(path, body)| ((*path).to_string(), (*body).to_string()))
+            .collect();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let thread = {
+            let server = server.clone();
+            let requests = requests.clone();
+            std::thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let url = request.url().to_string();
+                    requests.lock().unwrap().push(url.clone());
+                    let path = url.split('?').next().unwrap_or_default();
+                    let response = match routes.get(path) {
+                        Some(body) => tiny_http::Response::from_string(body.clone()),
+                        None => tiny_http::Response::from_string("{}").with_status_code(404),
+                    };
+                    let _ = request.respond(response);
+                }
+            })
+        };
+        Self {
+            server,
+            requests,
+            thread: Some(thread),
+        }
+    }
+
+    fn url(&self) -> String {
+        let port = self.server.server_addr().to_ip().unwrap().port();
+        format!("http://127.0.0.1:{port}")
+    }
+
+    fn provider(&self) -> OpenLibraryProvider {
+        OpenLibraryProvider::new(&self.url())
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+const SEARCH: &str = r#"{
+    "numFound": 2,
+    "docs": [
+        {
+            "key": "/works/OL2W",
+            "title": "Hyperion and Other Poems",
+            "author_name": ["John Keats"],
+            "first_publish_year": 1820
+        },
+        {
+            "key": "/works/OL1W",
+            "title": "Hyperion",
+            "author_name": ["Dan Simmons"],
+            "first_publish_year": 1989,
+            "publisher": ["Doubleday"],
+            "isbn": ["9780385249492"],
+            "id_amazon": ["B0042XA3YA"]
+        }
+    ]
+}"#;
+
+const WORK: &str = r#"{
+    "key": "/works/OL1W",
+    "title": "Hyperion",
+    "description": {"type": "/type/text", "value": "Pilgrims journey to the Time Tombs."},
+    "subjects": ["Science fiction", "Space opera"],
+    "first_publish_date": "May 1989",
+    "authors": [{"author": {"key": "/authors/OL1A"}, "type": {"key": "/type/author_role"}}]
+}"#;
+
+const AUTHOR: &str = r#"{"key": "/authors/OL1A", "name": "Dan Simmons"}"#;
+
+#[tokio::test]
+async fn test_search_and_rank() {
+    let server = MockServer::start(&[("/search.json", SEARCH)]);
+    let provider = server.provider();
+    let query = SearchQuery::title("Hyperion (Unabridged)").with_author("Simmons, Dan");
+
+    let results = provider.search(&query).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].id, "OL1W");
+    assert_eq!(results[1].metadata.asin.as_deref(), Some("B0042XA3YA"));
+    assert_eq!(results[1].metadata.published_year.as_deref(), Some("1989"));
+
+    let requests = server.requests();
+    assert!(requests[0].contains("title=Hyperion+%28Unabridged%29"));
+    assert!(requests[0].contains("author=Simmons%2C+Dan"));
+
+    let ranked = Matcher::default().rank(&query, results);
+    assert_eq!(ranked[0].book.id, "OL1W");
+    assert!((ranked[0].score - 1.0).abs() < f32::EPSILON);
+    assert!(ranked.iter().all(|scored| scored.book.id != "OL2W"));
+}
+
+#[tokio::test]
+async fn test_best_match_by_asin() {
+    let server = MockServer::start(&[("/search.json", SEARCH)]);
+    let provider = server.provider();
+    let query = SearchQuery::asin("b0042xa3ya");
+
+    let best = Matcher::default()
+        .best_match(&provider, &query)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(best.book.id, "OL1W");
+    assert!(server.requests()[0].contains("q=id_amazon%3Ab0042xa3ya"));
+}
+
+#[tokio::test]
+async fn test_fetch_review_and_apply() {
+    let server = MockServer::start(&[("/works/OL1W.json", WORK), ("/authors/OL1A.json", AUTHOR)]);
+    let provider = server.provider();
+
+    let book = provider.fetch("/works/OL1W").await.unwrap();
+    assert_eq!(book.provider, "openlibrary");
+    assert_eq!(book.metadata.authors, ["Dan Simmons"]);
+    assert_eq!(book.metadata.published_year.as_deref(), Some("1989"));
+    assert_eq!(
+        book.metadata.description.as_deref(),
+        Some("Pilgrims journey to the Time Tombs.")
+    );
+
+    let mut audiobook = Audiobook::new("library", "/books/hyperion.m4b");
+    audiobook.title = Some("Hyperion (Unabridged)".to_string());
+    audiobook.author = Some("Dan Simmons".to_string());
+    audiobook.narrator = Some("Victor Bevine".to_string());
+
+    let review = MetadataReview::new(&audiobook, &book);
+    let fields: Vec<MetadataField> = review.changes.iter().map(|change| change.field).collect();
+    assert_eq!(fields, [MetadataField::Title, MetadataField::Description]);
+    assert_eq!(
+        review.changes[0].to_string(),
+        r#"title: "Hyperion (Unabridged)" -> "Hyperion""#
+    );
+
+    let applied = review.apply(&mut audiobook, &[MetadataField::Description]);
+    assert_eq!(applied, [MetadataField::Description]);
+    assert_eq!(audiobook.title.as_deref(), Some("Hyperion (Unabridged)"));
+    assert_eq!(audiobook.narrator.as_deref(), Some("Victor Bevine"));
+    assert_eq!(MetadataReview::new(&audiobook, &book).changes.len(), 1);
+}
+
+#[tokio::test]
+async fn test_missing_record_is_an_error() {
+    let server = MockServer::start(&[]);
+    let provider = server.provider();
+
+    let error = provider.fetch("OL9W").await.unwrap_err();
+    assert!(matches!(error, AppError::Metadata(message) if message.contains("404")));
+    assert!(provider.fetch("OL1W.json?admin=1").await.is_err());
+    assert!(
+        provider
+            .search(&SearchQuery::default())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_default_client_rejects_unsupported_schemes() {
+    let error = DefaultHttpClient::new()
+        .get("ftp://openlibrary.org/search.json")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Metadata(_)));
+}
+
+/// Answer each request with a redirect to the next of `locations`, then
+/// with an empty JSON object, and return the URLs requested
+fn redirect_server(locations: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    let thread = std::thread::spawn(move || {
+        let mut urls = Vec::new();
+        for location in locations.iter().map(Some).chain([None]) {
+            let Ok(Some(request)) = server.recv_timeout(std::time::Duration::from_secs(5)) else {
+                break;
+            };
+            urls.push(request.url().to_string());
+            let response = match location {
+                Some(location) => tiny_http::Response::from_string("")
+                    .with_status_code(302)
+                    .with_header(
+                        tiny_http::Header::from_bytes("Location", location.as_bytes()).unwrap(),
+                    ),
+                None => tiny_http::Response::from_string("{}"),
+            };
+            let _ = request.respond(response);
+        }
+        urls
+    });
+    (format!("http://127.0.0.1:{port}"), thread)
+}
+
+#[tokio::test]
+async fn test_default_client_follows_redirects() {
+    let (url, thread) = redirect_server(vec![
+        "/v2/search".to_string(),
+        "search.json?q=hyperion".to_string(),
+    ]);
+
+    let response = DefaultHttpClient::new()
+        .get(&format!("{url}/search?q=hyperion"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, b"{}");
+    assert_eq!(
+        thread.join().unwrap(),
+        [
+            "/search?q=hyperion",
+            "/v2/search",
+            "/v2/search.json?q=hyperion"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_default_client_stops_at_redirect_loops() {
+    let (url, thread) = redirect_server(vec!["/loop".to_string(); MAX_REDIRECTS as usize + 1]);
+    let error = DefaultHttpClient::new().get(&url).await.unwrap_err();
+    assert!(matches!(error, AppError::Metadata(message) if message.contains("redirects")));
+    drop(thread);
+}
+
+#[tokio::test]
+async fn test_default_client_returns_error_statuses() {
+    let server = MockServer::start(&[]);
+    let response = DefaultHttpClient::new()
+        .get(&format!("{}/works/OL9W.json", server.url()))
+        .await
+        .unwrap();
+    assert_eq!(response.status, 404);
+    assert!(!response.is_success());
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use super::constants::*;
+use crate::sidecar::MetadataPrecedence;
 
 /// Configuration for the library scanner
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +36,14 @@ pub struct ScannerConfig {
     /// Maximum file size to process (in bytes)
     #[serde(default = "default_max_file_size")]
     pub max_file_size: u64,
+
+    /// Whether to read sidecar metadata files next to the audio
+    #[serde(default = "default_true")]
+    pub read_sidecars: bool,
+
+    /// Order in which sidecars, tags and the file name are trusted
+    #[serde(default)]
+    pub metadata_precedence: MetadataPrecedence,
 }
 
 impl Default for ScannerConfig {
@@ -47,6 +56,8 @@ impl Default for ScannerConfig {
             use_mmap: true,
             extensions: default_extensions(),
             max_file_size: default_max_file_size(),
+            read_sidecars: true,
+            metadata_precedence: MetadataPrecedence::default(),
         }
     }
 }
@@ -102,6 +113,8 @@ impl ScannerConfig {
             use_mmap: true,
             extensions: default_extensions(),
             max_file_size: DEFAULT_MAX_FILE_SIZE * 2,
+            read_sidecars: true,
+            metadata_precedence: MetadataPrecedence::default(),
         }
     }
 
@@ -116,6 +129,8 @@ impl ScannerConfig {
             use_mmap: true,
             extensions: default_extensions(),
             max_file_size: DEFAULT_MAX_FILE_SIZE,
+            read_sidecars: true,
+            metadata_precedence: MetadataPrecedence::default(),
         }
     }
 
@@ -130,6 +145,8 @@ impl ScannerConfig {
             use_mmap: false,
             extensions: default_extensions(),
             max_file_size: DEFAULT_MAX_FILE_SIZE / 2,
+            read_sidecars: true,
+            metadata_precedence: MetadataPrecedence::default(),
         }
     }
 }
@@ -18,6 +18,7 @@ use crate::{
         error::ScanResult,
         performance::{OperationType, PerformanceMonitor},
     },
+    sidecar::{FieldOrigins, SidecarCache, Sidecars, merge_metadata},
 };
 
 /// Core scanner responsible for file discovery and metadata extraction
@@ -63,7 +64,30 @@ impl CoreScanner {
 
     /// Extracts metadata from an audio file (synchronous)
     pub fn extract_metadata(&self, library_id: &str, path: &Path) -> Result<Audiobook> {
-        Self::extract_audiobook_metadata(library_id, path)
+        self.extract_metadata_with_origins(library_id, path)
+            .map(|(audiobook, _)| audiobook)
+    }
+
+    /// Extracts metadata from an audio file along with the source of each
+    /// merged field (synchronous)
+    pub fn extract_metadata_with_origins(
+        &self,
+        library_id: &str,
+        path: &Path,
+    ) -> Result<(Audiobook, FieldOrigins)> {
+        self.extract_metadata_cached(library_id, path, &mut SidecarCache::new())
+    }
+
+    /// Extracts metadata like [`Self::extract_metadata_with_origins`],
+    /// reading the sidecars of each directory only once per `sidecars` cache
+    /// (synchronous)
+    pub fn extract_metadata_cached(
+        &self,
+        library_id: &str,
+        path: &Path,
+        sidecars: &mut SidecarCache,
+    ) -> Result<(Audiobook, FieldOrigins)> {
+        Self::extract_audiobook_metadata(library_id, path, &self.config, sidecars)
     }
 
     /// Extracts metadata with performance monitoring (synchronous)
@@ -81,7 +105,13 @@ impl CoreScanner {
             )
         });
 
-        let result = Self::extract_audiobook_metadata(library_id, path);
+        let result = Self::extract_audiobook_metadata(
+            library_id,
+            path,
+            &self.config,
+            &mut SidecarCache::new(),
+        )
+        .map(|(audiobook, _)| audiobook);
 
         // Record the operation result
         if let Some(monitor) = monitor {
@@ -151,7 +181,12 @@ impl CoreScanner {
     }
 
     /// Internal helper to extract audiobook metadata (pure function)
-    fn extract_audiobook_metadata(library_id: &str, path: &Path) -> Result<Audiobook> {
+    fn extract_audiobook_metadata(
+        library_id: &str,
+        path: &Path,
+        config: &ScannerConfig,
+        sidecar_cache: &mut SidecarCache,
+    ) -> Result<(Audiobook, FieldOrigins)> {
         // Try to extract metadata, but fall back to basic file info if it fails
         let metadata = match AudioMetadata::from_file(path) {
             Ok(metadata) => Some(metadata),
@@ -178,23 +213,22 @@ impl CoreScanner {
 
         let mut audiobook = Audiobook::new(library_id, path);
 
-        // Extract title - prefer metadata, fall back to filename
-        audiobook.title = Some(
-            metadata
-                .as_ref()
-                .and_then(|m| m.title.clone())
-                .unwrap_or_else(|| {
-                    path.file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or(crate::models::audiobook::fallbacks::UNKNOWN_TITLE)
-                        .to_string()
-                }),
+        // Merge title, author, narrator and description from sidecars, tags
+        // and the file name in the configured order
+        let no_sidecars = Sidecars::default();
+        let sidecars = match path.parent() {
+            Some(dir) if config.read_sidecars => sidecar_cache.get(dir),
+            _ => &no_sidecars,
+        };
+        let origins = merge_metadata(
+            &mut audiobook,
+            metadata.as_ref(),
+            sidecars,
+            &config.metadata_precedence,
         );
 
         // Set other metadata fields if available
         if let Some(ref meta) = metadata {
-            audiobook.author = meta.artist.clone();
-            audiobook.narrator = meta.narrator.clone();
             audiobook.duration_seconds = meta.duration_seconds.map(|d| {
                 if d.is_nan() || d < 0.0 {
                     0
@@ -216,7 +250,7 @@ impl CoreScanner {
             audiobook.size_bytes = Some(meta.len());
         }
 
-        Ok(audiobook)
+        Ok((audiobook, origins))
     }
 }
 
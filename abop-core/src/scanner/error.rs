@@ -134,6 +134,12 @@ impl From<tokio::sync::AcquireError> for ScanError {
     }
 }
 
+impl From<DatabaseError> for ScanError {
+    fn from(err: DatabaseError) -> Self {
+        Self::Database(err.to_string())
+    }
+}
+
 impl From<rusqlite::Error> for ScanError {
     fn from(err: rusqlite::Error) -> Self {
         Self::Database(err.to_string())
@@ -9,8 +9,8 @@ use std::sync::{
 };
 
 use crate::{
-    db::Database,
-    models::Library,
+    db::{Database, JobCursor},
+    models::{Job, JobStatus, JobType, Library},
     scanner::{
         config::ScannerConfig,
         error::ScanResult,
@@ -37,6 +37,8 @@ pub struct LibraryScanner {
     performance_monitor: Option<Arc<PerformanceMonitor>>,
     /// Flag to indicate if the scan should be cancelled
     cancelled: Arc<AtomicBool>,
+    /// Persisted job tracking this scan, if enabled
+    job: Option<Arc<JobCursor>>,
 }
 
 impl LibraryScanner {
@@ -54,6 +56,7 @@ impl LibraryScanner {
             config: ScannerConfig::default(),
             performance_monitor: Some(Arc::new(PerformanceMonitor::new())),
             cancelled: Arc::new(AtomicBool::new(false)),
+            job: None,
         }
     }
 
@@ -78,6 +81,83 @@ impl LibraryScanner {
         self
     }
 
+    /// Records this scan as a persisted job so it can resume after a crash
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error if the job cannot be created.
+    pub fn with_job_tracking(mut self) -> ScanResult<Self> {
+        let repository = Arc::new(self.db.job_repository());
+        let parameters = serde_json::json!({ "library_path": self.library.path }).to_string();
+        let job = repository
+            .create(JobType::Scan, Some(&self.library.id), &parameters)
+            .and_then(|job| JobCursor::load(repository, &job.id))?;
+        self.job = Some(Arc::new(job));
+        Ok(self)
+    }
+
+    /// ID of the persisted job tracking this scan, if any
+    #[must_use]
+    pub fn job_id(&self) -> Option<&str> {
+        self.job.as_deref().map(JobCursor::job_id)
+    }
+
+    /// Creates the scanner of a persisted scan job
+    ///
+    /// The scanner skips the files the job already committed. Returns `None`
+    /// and marks the job as failed if its library no longer exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error if the job or its library cannot be loaded.
+    pub fn from_job(db: &Database, job: &Job) -> ScanResult<Option<Self>> {
+        let repository = Arc::new(db.job_repository());
+        let library = match &job.library_id {
+            Some(library_id) => db.libraries().find_by_id(library_id)?,
+            None => None,
+        };
+        let Some(library) = library else {
+            log::warn!("Library of scan job {} no longer exists", job.id);
+            repository.finish(&job.id, JobStatus::Failed, Some("Library no longer exists"))?;
+            return Ok(None);
+        };
+
+        let cursor = JobCursor::load(repository, &job.id)?;
+        log::info!(
+            "Resuming scan job {} for library '{}' ({} files already committed)",
+            job.id,
+            library.name,
+            cursor.committed_count()
+        );
+        let mut scanner = Self::new(db.clone(), library);
+        scanner.job = Some(Arc::new(cursor));
+        Ok(Some(scanner))
+    }
+
+    /// Creates scanners for all scan jobs that were interrupted before finishing
+    ///
+    /// Each returned scanner skips the files its job already committed. Jobs
+    /// whose library no longer exists are marked as failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a database error if the unfinished jobs cannot be loaded.
+    pub fn resume_unfinished(db: &Database) -> ScanResult<Vec<Self>> {
+        let jobs = db.job_repository().find_resumable(JobType::Scan)?;
+
+        let mut scanners = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            scanners.extend(Self::from_job(db, &job)?);
+        }
+        Ok(scanners)
+    }
+
+    /// The library being scanned
+    #[must_use]
+    pub const fn library(&self) -> &Library {
+        &self.library
+    }
+
     /// Gets the performance monitor if enabled
     #[must_use]
     pub fn get_performance_monitor(&self) -> Option<Arc<PerformanceMonitor>> {
@@ -192,6 +272,10 @@ impl LibraryScanner {
             orchestrator = orchestrator.with_performance_monitor(monitor.clone());
         }
 
+        if let Some(job) = &self.job {
+            orchestrator = orchestrator.with_job(job.clone());
+        }
+
         orchestrator = orchestrator.with_cancellation_token(self.cancelled.clone());
         orchestrator
     }
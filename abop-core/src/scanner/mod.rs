@@ -18,6 +18,7 @@ pub mod progress;
 mod result;
 mod state;
 mod task_manager;
+mod watcher;
 
 pub use config::*;
 pub use constants::*;
@@ -31,6 +32,7 @@ pub use progress::{ChannelReporter, ProgressReporter, ScanProgress};
 pub use result::*;
 pub use state::ScannerState;
 pub use task_manager::TaskManager;
+pub use watcher::{LibraryWatcher, WatchBackend, WatchConfig, WatchUpdate};
 
 // Re-export common types for convenience
 pub use crate::db::Database;
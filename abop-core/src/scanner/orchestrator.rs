@@ -3,6 +3,7 @@
 //! This module provides high-level coordination between core scanning,
 //! database operations, progress reporting, and performance monitoring.
 
+use std::path::Path;
 use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
@@ -11,8 +12,8 @@ use std::time::Instant;
 use tracing::{error, info, warn};
 
 use crate::{
-    db::Database,
-    models::{Audiobook, Library},
+    db::{Database, JobCursor},
+    models::{Audiobook, JobStatus, Library},
     scanner::{
         config::ScannerConfig,
         core_scanner::CoreScanner,
@@ -21,6 +22,7 @@ use crate::{
         progress::ProgressReporter,
         result::ScanSummary,
     },
+    sidecar::{FieldOrigins, SidecarCache},
 };
 
 /// Options for scan operations
@@ -66,6 +68,8 @@ pub struct ScanOrchestrator {
     cancelled: Arc<AtomicBool>,
     /// Configuration
     config: ScannerConfig,
+    /// Persisted job used to skip files committed by an earlier run
+    job: Option<Arc<JobCursor>>,
 }
 
 impl ScanOrchestrator {
@@ -82,6 +86,7 @@ impl ScanOrchestrator {
             performance_monitor: None,
             cancelled: Arc::new(AtomicBool::new(false)),
             config,
+            job: None,
         }
     }
 
@@ -104,9 +109,62 @@ impl ScanOrchestrator {
         self
     }
 
+    /// Tracks the scan in a persisted job
+    ///
+    /// Files the job already committed are skipped, and each persisted batch is
+    /// recorded so an interrupted scan can resume where it stopped.
+    #[must_use]
+    pub fn with_job(mut self, job: Arc<JobCursor>) -> Self {
+        self.job = Some(job);
+        self
+    }
+
+    /// Records the final status of the tracked job, if any
+    fn finish_job(&self, status: JobStatus, error: Option<&str>) {
+        if let Some(job) = &self.job
+            && let Err(e) = job.finish(status, error)
+        {
+            warn!("Failed to record status of job {}: {}", job.job_id(), e);
+        }
+    }
+
+    /// Extracts metadata, keeping the identity of an existing audiobook
+    ///
+    /// Rescanned files keep their id so tags, bookmarks and progress stay
+    /// attached to them.
+    fn extract(
+        &self,
+        path: &Path,
+        sidecars: &mut SidecarCache,
+    ) -> crate::error::Result<(Audiobook, FieldOrigins)> {
+        let (mut audiobook, origins) =
+            self.core_scanner
+                .extract_metadata_cached(&self.library.id, path, sidecars)?;
+        if let Some(existing) = self.database.get_audiobook(path)? {
+            audiobook.id = existing.id;
+            audiobook.created_at = existing.created_at;
+        }
+        Ok((audiobook, origins))
+    }
+
     /// Persists a batch of audiobooks to the database
-    fn persist_batch(&self, audiobooks: &[Audiobook]) -> crate::error::Result<()> {
-        self.database.add_audiobooks_bulk(audiobooks)
+    ///
+    /// Field origins are informational, so failing to record them is logged
+    /// rather than failing the batch.
+    fn persist_batch(
+        &self,
+        audiobooks: &[Audiobook],
+        origins: &[(String, FieldOrigins)],
+    ) -> crate::error::Result<()> {
+        self.database.add_audiobooks_bulk(audiobooks)?;
+        if let Err(e) = self
+            .database
+            .metadata_origin_repository()
+            .replace_many(origins)
+        {
+            warn!("Failed to record metadata origins: {}", e);
+        }
+        Ok(())
     }
 
     /// Performs a complete scan operation (synchronous)
@@ -120,11 +178,33 @@ impl ScanOrchestrator {
         let start_time = Instant::now();
 
         // Discover all audio files
-        let files = self.core_scanner.discover_files(&self.library.path)?;
+        let mut files = match self.core_scanner.discover_files(&self.library.path) {
+            Ok(files) => files,
+            Err(e) => {
+                self.finish_job(JobStatus::Failed, Some(&e.to_string()));
+                return Err(e);
+            }
+        };
         let total_files = files.len();
 
         info!("Discovered {} files to process", total_files);
 
+        if let Some(job) = &self.job {
+            if let Err(e) = job.start(total_files) {
+                warn!("Failed to start job {}: {}", job.job_id(), e);
+            }
+            files.retain(|path| !job.is_committed(path));
+            let skipped = total_files - files.len();
+            if skipped > 0 {
+                info!(
+                    "Resuming job {}: skipping {} already committed files",
+                    job.job_id(),
+                    skipped
+                );
+            }
+        }
+        let pending_files = files.len();
+
         // Report scan start
         if options.enable_progress
             && let Some(reporter) = &self.progress_reporter
@@ -138,6 +218,8 @@ impl ScanOrchestrator {
 
         let mut processed_audiobooks = Vec::new();
         let mut error_count = 0;
+        // Files of one book share a directory, so read its sidecars once
+        let mut sidecars = SidecarCache::new();
 
         // Determine batch size
         let batch_size = options.batch_size.unwrap_or(self.config.batch_size);
@@ -147,6 +229,7 @@ impl ScanOrchestrator {
         for (batch_index, file_chunk) in files.chunks(batch_size).enumerate() {
             if self.cancelled.load(Ordering::Relaxed) {
                 info!("Scan operation was cancelled");
+                self.finish_job(JobStatus::Cancelled, None);
                 return Err(ScanError::Cancelled);
             }
 
@@ -155,6 +238,7 @@ impl ScanOrchestrator {
 
             // Process files sequentially within the batch (truly synchronous)
             let mut batch_audiobooks = Vec::new();
+            let mut batch_origins = Vec::new();
             for (file_index, path) in file_chunk.iter().enumerate() {
                 let overall_index = batch_index * batch_size + file_index;
 
@@ -162,16 +246,17 @@ impl ScanOrchestrator {
                 if options.enable_progress
                     && let Some(reporter) = &self.progress_reporter
                 {
-                    let progress = overall_index as f32 / total_files as f32;
+                    let progress = overall_index as f32 / pending_files as f32;
                     let rt = tokio::runtime::Handle::try_current();
                     if let Ok(rt) = rt {
                         rt.block_on(async { reporter.report_progress(progress).await });
                     }
                 }
 
-                // Process the file using extract_metadata (now synchronous)
-                match self.core_scanner.extract_metadata(&self.library.id, path) {
-                    Ok(audiobook) => {
+                // Process the file, merging sidecars, tags and file name
+                match self.extract(path, &mut sidecars) {
+                    Ok((audiobook, origins)) => {
+                        batch_origins.push((audiobook.id.clone(), origins));
                         batch_audiobooks.push(audiobook);
                     }
                     Err(e) => {
@@ -195,7 +280,7 @@ impl ScanOrchestrator {
 
             // Persist the batch to the database
             if !batch_audiobooks.is_empty() {
-                if let Err(e) = self.persist_batch(&batch_audiobooks) {
+                if let Err(e) = self.persist_batch(&batch_audiobooks, &batch_origins) {
                     error!("Error persisting batch: {}", e);
                     error_count += batch_audiobooks.len(); // Consider all items in batch as errors
                     continue;
@@ -203,6 +288,12 @@ impl ScanOrchestrator {
                 processed_audiobooks.extend(batch_audiobooks);
             }
 
+            if let Some(job) = &self.job
+                && let Err(e) = job.commit(file_chunk)
+            {
+                warn!("Failed to record progress of job {}: {}", job.job_id(), e);
+            }
+
             info!(
                 "Processed batch {} in {:.2?} ({} items)",
                 batch_index + 1,
@@ -212,6 +303,8 @@ impl ScanOrchestrator {
             );
         }
 
+        self.finish_job(JobStatus::Completed, None);
+
         // Report completion
         let duration = start_time.elapsed();
         if options.enable_progress
@@ -238,8 +331,63 @@ impl ScanOrchestrator {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_scan_orchestrator() {
         // Test setup would go here
     }
+
+    #[test]
+    fn test_rescan_keeps_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Arc::new(Database::open(dir.path().join("scan.db")).unwrap());
+        let library_root = dir.path().join("books");
+        std::fs::create_dir(&library_root).unwrap();
+        let library_id = database
+            .add_library_with_path("Books", library_root.clone())
+            .unwrap();
+        let library = database
+            .libraries()
+            .find_by_id(&library_id)
+            .unwrap()
+            .unwrap();
+
+        let book = library_root.join("book.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&book, spec).unwrap();
+        for _ in 0..8000 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let options = ScanOptions {
+            enable_progress: false,
+            ..ScanOptions::default()
+        };
+        let orchestrator =
+            ScanOrchestrator::new(database.clone(), library, ScannerConfig::default());
+        orchestrator.scan(options.clone()).unwrap();
+        let scanned = database.get_audiobook(&book).unwrap().unwrap();
+        database
+            .tag_repository()
+            .tag_audiobooks("favourite", std::slice::from_ref(&scanned.id))
+            .unwrap();
+
+        orchestrator.scan(options).unwrap();
+
+        let rescanned = database.get_audiobook(&book).unwrap().unwrap();
+        assert_eq!(rescanned.id, scanned.id);
+        let tags = database
+            .tag_repository()
+            .find_for_audiobook(&scanned.id)
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "favourite");
+    }
 }
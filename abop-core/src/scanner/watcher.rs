@@ -0,0 +1,600 @@
+//! Filesystem watch mode for automatic library updates
+//!
+//! This module monitors library roots for changes and keeps the database in
+//! sync without a full rescan. Events are debounced, files still being copied
+//! are given time to settle, and only the affected paths are re-extracted.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+    mpsc,
+};
+use std::time::{Duration, Instant, SystemTime};
+
+use notify::{EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, info, warn};
+
+use crate::{
+    db::Database,
+    models::{Audiobook, Library},
+    scanner::{
+        config::ScannerConfig,
+        core_scanner::CoreScanner,
+        error::{ScanError, ScanResult},
+    },
+    sidecar::{FieldOrigins, SidecarCache},
+};
+
+/// How often the watch loop checks for cancellation and settled events
+const TICK: Duration = Duration::from_millis(200);
+
+/// Configuration for library watching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchConfig {
+    /// Quiet period after the last event before changes are applied
+    pub debounce: Duration,
+    /// Interval between size and mtime checks while a file settles
+    pub settle_interval: Duration,
+    /// Maximum time to wait for a file to stop changing
+    pub settle_timeout: Duration,
+    /// Interval between directory scans when polling
+    pub poll_interval: Duration,
+    /// Always use polling instead of native notifications
+    pub force_polling: bool,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_secs(2),
+            settle_interval: Duration::from_secs(1),
+            settle_timeout: Duration::from_secs(600),
+            poll_interval: Duration::from_secs(30),
+            force_polling: false,
+        }
+    }
+}
+
+/// Mechanism used to receive filesystem events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchBackend {
+    /// Native notifications (inotify on Linux)
+    Native,
+    /// Periodic directory scans
+    Polling,
+}
+
+/// Changes applied to one library after a burst of filesystem events
+#[derive(Debug, Clone, Default)]
+pub struct WatchUpdate {
+    /// Library the changes belong to
+    pub library_id: String,
+    /// Audiobooks that were added or re-extracted
+    pub updated: Vec<Audiobook>,
+    /// Paths of audiobooks that were removed from the database
+    pub removed: Vec<PathBuf>,
+    /// Number of files that could not be processed
+    pub errors: usize,
+}
+
+impl WatchUpdate {
+    /// Whether the update changed nothing
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.removed.is_empty() && self.errors == 0
+    }
+}
+
+/// Collects event paths until no new events arrived for the debounce window
+#[derive(Debug)]
+struct EventDebouncer {
+    window: Duration,
+    pending: HashSet<PathBuf>,
+    last_event: Option<Instant>,
+}
+
+impl EventDebouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashSet::new(),
+            last_event: None,
+        }
+    }
+
+    fn record(&mut self, path: PathBuf, now: Instant) {
+        self.pending.insert(path);
+        self.last_event = Some(now);
+    }
+
+    /// Takes all pending paths once the burst has been quiet long enough
+    fn take_ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        match self.last_event {
+            Some(last) if now.duration_since(last) >= self.window => {
+                self.last_event = None;
+                let mut paths: Vec<PathBuf> = self.pending.drain().collect();
+                paths.sort();
+                paths
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Size and modification time of a file, if it exists
+fn file_signature(path: &Path) -> Option<(u64, SystemTime)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// A changed file waiting for its size and modification time to settle
+#[derive(Debug)]
+struct PendingFile {
+    signature: Option<(u64, SystemTime)>,
+    checked_at: Instant,
+    deadline: Instant,
+}
+
+/// Tracks changed files until they stop changing
+///
+/// Every file has its own settle deadline and is checked on each tick, so a
+/// file that is still being copied never holds up the others.
+#[derive(Debug)]
+struct SettleTracker {
+    interval: Duration,
+    timeout: Duration,
+    pending: HashMap<PathBuf, PendingFile>,
+}
+
+impl SettleTracker {
+    fn new(interval: Duration, timeout: Duration) -> Self {
+        Self {
+            interval,
+            timeout,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Starts tracking a file, or restarts the check of a tracked one
+    ///
+    /// A tracked file keeps its original deadline.
+    fn track(&mut self, path: PathBuf, now: Instant) {
+        let signature = file_signature(&path);
+        let deadline = now + self.timeout;
+        let pending = self.pending.entry(path).or_insert(PendingFile {
+            signature,
+            checked_at: now,
+            deadline,
+        });
+        pending.signature = signature;
+        pending.checked_at = now;
+    }
+
+    /// Takes the files that did not change during the last settle interval
+    ///
+    /// Files that disappeared or kept changing past their deadline are dropped.
+    fn take_settled(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut settled = Vec::new();
+        self.pending.retain(|path, pending| {
+            if now.duration_since(pending.checked_at) < self.interval {
+                return true;
+            }
+            let current = file_signature(path);
+            if current.is_none() {
+                return false;
+            }
+            if current == pending.signature {
+                settled.push(path.clone());
+                return false;
+            }
+            if now >= pending.deadline {
+                debug!("Skipping unsettled file {}", path.display());
+                return false;
+            }
+            pending.signature = current;
+            pending.checked_at = now;
+            true
+        });
+        settled.sort();
+        settled
+    }
+}
+
+/// Watches library roots and applies changes to the database
+pub struct LibraryWatcher {
+    /// The database connection
+    db: Database,
+    /// Libraries being watched
+    libraries: Vec<Library>,
+    /// Watch timing configuration
+    config: WatchConfig,
+    /// Scanner configuration used for extension filtering
+    scanner_config: ScannerConfig,
+    /// Flag to indicate if watching should stop
+    cancelled: Arc<AtomicBool>,
+}
+
+impl LibraryWatcher {
+    /// Creates a watcher for the given libraries
+    #[must_use]
+    pub fn new(db: Database, libraries: Vec<Library>) -> Self {
+        Self {
+            db,
+            libraries,
+            config: WatchConfig::default(),
+            scanner_config: ScannerConfig::default(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Sets the watch timing configuration
+    #[must_use]
+    pub fn with_config(mut self, config: WatchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the scanner configuration used for extension filtering
+    #[must_use]
+    pub fn with_scanner_config(mut self, config: ScannerConfig) -> Self {
+        self.scanner_config = config;
+        self
+    }
+
+    /// Token that stops [`Self::run`] when set
+    #[must_use]
+    pub fn cancellation_token(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    /// Stops a running watch loop at its next tick
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Watches all library roots until cancelled
+    ///
+    /// Blocks the calling thread. `on_update` is called once per library for
+    /// every burst of changes that was applied to the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Io`] if no watcher could be created for the
+    /// library roots, or [`ScanError::Channel`] if the event channel closes.
+    pub fn run<F>(&self, mut on_update: F) -> ScanResult<()>
+    where
+        F: FnMut(WatchUpdate),
+    {
+        let (tx, rx) = mpsc::channel();
+        let (_watcher, backend) = self.create_watcher(tx)?;
+        info!(
+            "Watching {} libraries using {:?} backend",
+            self.libraries.len(),
+            backend
+        );
+
+        let core_scanner = CoreScanner::with_config(self.scanner_config.clone());
+        let mut debouncer = EventDebouncer::new(self.config.debounce);
+        let mut settling =
+            SettleTracker::new(self.config.settle_interval, self.config.settle_timeout);
+        while !self.is_cancelled() {
+            match rx.recv_timeout(TICK) {
+                Ok(Ok(event)) => {
+                    if matches!(event.kind, EventKind::Access(_)) {
+                        continue;
+                    }
+                    let now = Instant::now();
+                    for path in event.paths {
+                        debouncer.record(path, now);
+                    }
+                }
+                Ok(Err(e)) => warn!("Filesystem watch error: {}", e),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(ScanError::Channel("Filesystem event channel closed".into()));
+                }
+            }
+
+            // Removals apply right away, changed audio files once they settled
+            let now = Instant::now();
+            let mut ready = Vec::new();
+            for path in debouncer.take_ready(now) {
+                if path.exists() {
+                    for file in self.audio_files(&core_scanner, &path) {
+                        settling.track(file, now);
+                    }
+                } else {
+                    ready.push(path);
+                }
+            }
+            ready.extend(settling.take_settled(now));
+            if !ready.is_empty() {
+                debug!("Applying {} changed paths", ready.len());
+                for update in self.apply_changes(&ready) {
+                    on_update(update);
+                }
+            }
+        }
+
+        info!("Stopped watching libraries");
+        Ok(())
+    }
+
+    /// Creates a native watcher, falling back to polling if it is unavailable
+    fn create_watcher(
+        &self,
+        tx: mpsc::Sender<notify::Result<notify::Event>>,
+    ) -> ScanResult<(Box<dyn Watcher + Send>, WatchBackend)> {
+        if !self.config.force_polling {
+            match RecommendedWatcher::new(tx.clone(), notify::Config::default())
+                .map_err(|e| e.to_string())
+                .and_then(|watcher| self.watch_roots(watcher))
+            {
+                Ok(watcher) => return Ok((watcher, WatchBackend::Native)),
+                Err(e) => warn!(
+                    "Native filesystem watching unavailable, polling instead: {}",
+                    e
+                ),
+            }
+        }
+
+        let config = notify::Config::default().with_poll_interval(self.config.poll_interval);
+        let watcher = PollWatcher::new(tx, config)
+            .map_err(|e| e.to_string())
+            .and_then(|watcher| self.watch_roots(watcher))
+            .map_err(|e| ScanError::Io(std::io::Error::other(e)))?;
+        Ok((watcher, WatchBackend::Polling))
+    }
+
+    /// Registers every library root with the watcher
+    fn watch_roots<W: Watcher + Send + 'static>(
+        &self,
+        mut watcher: W,
+    ) -> Result<Box<dyn Watcher + Send>, String> {
+        for library in &self.libraries {
+            watcher
+                .watch(&library.path, RecursiveMode::Recursive)
+                .map_err(|e| format!("{}: {e}", library.path.display()))?;
+        }
+        Ok(Box::new(watcher))
+    }
+
+    /// Re-extracts or removes the audiobooks affected by the changed paths
+    ///
+    /// Directories are expanded to the audio files they contain and paths that
+    /// no longer exist remove their audiobooks. Files are extracted as they
+    /// are, so [`Self::run`] only passes files that stopped changing.
+    #[must_use]
+    pub fn apply_changes(&self, paths: &[PathBuf]) -> Vec<WatchUpdate> {
+        let mut updates: HashMap<String, WatchUpdate> = HashMap::new();
+        let mut origins: Vec<(String, FieldOrigins)> = Vec::new();
+        let core_scanner = CoreScanner::with_config(self.scanner_config.clone());
+        let mut sidecars = SidecarCache::new();
+
+        for path in paths {
+            let Some(library) = self.library_for(path) else {
+                continue;
+            };
+            let update = updates
+                .entry(library.id.clone())
+                .or_insert_with(|| WatchUpdate {
+                    library_id: library.id.clone(),
+                    ..WatchUpdate::default()
+                });
+
+            if !path.exists() {
+                self.remove_missing(library, path, update);
+                continue;
+            }
+
+            for file in self.audio_files(&core_scanner, path) {
+                match self.extract(&core_scanner, library, &file, &mut sidecars) {
+                    Ok((audiobook, field_origins)) => {
+                        origins.push((audiobook.id.clone(), field_origins));
+                        update.updated.push(audiobook);
+                    }
+                    Err(e) => {
+                        warn!("Failed to extract metadata from {}: {}", file.display(), e);
+                        update.errors += 1;
+                    }
+                }
+            }
+        }
+
+        let mut updates: Vec<WatchUpdate> = updates.into_values().collect();
+        for update in &mut updates {
+            if let Err(e) = self.db.add_audiobooks_bulk(&update.updated) {
+                warn!("Failed to store watched changes: {}", e);
+                update.errors += update.updated.len();
+                update.updated.clear();
+            }
+        }
+        let stored: HashSet<&str> = updates
+            .iter()
+            .flat_map(|update| update.updated.iter().map(|audiobook| audiobook.id.as_str()))
+            .collect();
+        origins.retain(|(audiobook_id, _)| stored.contains(audiobook_id.as_str()));
+        if let Err(e) = self.db.metadata_origin_repository().replace_many(&origins) {
+            warn!("Failed to record metadata origins: {}", e);
+        }
+        updates.retain(|update| !update.is_empty());
+        updates
+    }
+
+    /// Library whose root contains the path, preferring the deepest root
+    fn library_for(&self, path: &Path) -> Option<&Library> {
+        self.libraries
+            .iter()
+            .filter(|library| path.starts_with(&library.path))
+            .max_by_key(|library| library.path.components().count())
+    }
+
+    fn is_supported(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.scanner_config
+                    .extensions
+                    .iter()
+                    .any(|supported| supported.eq_ignore_ascii_case(ext))
+            })
+    }
+
+    /// Audio files at or below an existing path
+    fn audio_files(&self, core_scanner: &CoreScanner, path: &Path) -> Vec<PathBuf> {
+        if path.is_dir() {
+            core_scanner.discover_files(path).unwrap_or_default()
+        } else if self.is_supported(path) {
+            vec![path.to_path_buf()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Extracts metadata, keeping the identity of an existing audiobook
+    fn extract(
+        &self,
+        core_scanner: &CoreScanner,
+        library: &Library,
+        path: &Path,
+        sidecars: &mut SidecarCache,
+    ) -> crate::error::Result<(Audiobook, FieldOrigins)> {
+        let (mut audiobook, origins) =
+            core_scanner.extract_metadata_cached(&library.id, path, sidecars)?;
+        if let Some(existing) = self.db.get_audiobook(path)? {
+            audiobook.id = existing.id;
+            audiobook.created_at = existing.created_at;
+        }
+        Ok((audiobook, origins))
+    }
+
+    /// Removes audiobooks at or below a path that no longer exists
+    fn remove_missing(&self, library: &Library, path: &Path, update: &mut WatchUpdate) {
+        let audiobooks = match self.db.get_audiobooks_in_library(&library.id) {
+            Ok(audiobooks) => audiobooks,
+            Err(e) => {
+                warn!("Failed to load audiobooks of library {}: {}", library.id, e);
+                update.errors += 1;
+                return;
+            }
+        };
+
+        for audiobook in audiobooks
+            .into_iter()
+            .filter(|audiobook| audiobook.path.starts_with(path))
+        {
+            match self.db.delete_audiobook(&audiobook.path) {
+                Ok(()) => update.removed.push(audiobook.path),
+                Err(e) => {
+                    warn!("Failed to remove {}: {}", audiobook.path.display(), e);
+                    update.errors += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let window = Duration::from_millis(500);
+        let mut debouncer = EventDebouncer::new(window);
+        let start = Instant::now();
+
+        debouncer.record(PathBuf::from("/books/a.mp3"), start);
+        debouncer.record(
+            PathBuf::from("/books/a.mp3"),
+            start + Duration::from_millis(300),
+        );
+        debouncer.record(
+            PathBuf::from("/books/b.mp3"),
+            start + Duration::from_millis(400),
+        );
+
+        // Still inside the burst
+        assert!(
+            debouncer
+                .take_ready(start + Duration::from_millis(600))
+                .is_empty()
+        );
+
+        let ready = debouncer.take_ready(start + Duration::from_millis(900));
+        assert_eq!(
+            ready,
+            vec![PathBuf::from("/books/a.mp3"), PathBuf::from("/books/b.mp3")]
+        );
+        assert!(
+            debouncer
+                .take_ready(start + Duration::from_secs(5))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_settle_tracker_checks_files_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let copied = dir.path().join("copied.mp3");
+        let copying = dir.path().join("copying.mp3");
+        std::fs::write(&copied, b"done").unwrap();
+        std::fs::write(&copying, b"part").unwrap();
+
+        let interval = Duration::from_secs(1);
+        let mut tracker = SettleTracker::new(interval, Duration::from_secs(3));
+        let start = Instant::now();
+        tracker.track(copied.clone(), start);
+        tracker.track(copying.clone(), start);
+        assert!(tracker.take_settled(start).is_empty());
+
+        // The file still growing does not hold back the finished one
+        std::fs::write(&copying, b"partial").unwrap();
+        assert_eq!(tracker.take_settled(start + interval), vec![copied]);
+
+        std::fs::write(&copying, b"partial data").unwrap();
+        assert!(tracker.take_settled(start + interval * 2).is_empty());
+
+        // Past its deadline a file that keeps changing is dropped
+        std::fs::write(&copying, b"partial data, more").unwrap();
+        assert!(tracker.take_settled(start + interval * 3).is_empty());
+        assert!(tracker.pending.is_empty());
+    }
+
+    #[test]
+    fn test_apply_changes_removes_deleted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("watch.db")).unwrap();
+        let library_root = dir.path().join("books");
+        std::fs::create_dir(&library_root).unwrap();
+        let library_id = db
+            .add_library_with_path("Books", library_root.clone())
+            .unwrap();
+        let library = db.libraries().find_by_id(&library_id).unwrap().unwrap();
+
+        let gone = library_root.join("gone.mp3");
+        db.add_audiobook(&Audiobook::new(&library_id, &gone))
+            .unwrap();
+
+        let notes = library_root.join("notes.txt");
+        std::fs::write(&notes, "not audio").unwrap();
+
+        let watcher = LibraryWatcher::new(db.clone(), vec![library]).with_config(WatchConfig {
+            settle_interval: Duration::from_millis(10),
+            ..WatchConfig::default()
+        });
+        let updates = watcher.apply_changes(&[gone.clone(), notes]);
+
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].library_id, library_id);
+        assert_eq!(updates[0].removed, vec![gone.clone()]);
+        assert!(updates[0].updated.is_empty());
+        assert!(db.get_audiobook(&gone).unwrap().is_none());
+    }
+}
@@ -0,0 +1,108 @@
+//! Audiobookshelf `metadata.json` files
+//!
+//! Audiobookshelf stores series as `"Name #sequence"` strings. Fields it
+//! writes that ABOP does not model, such as tags and chapters, are carried
+//! over when a file is rewritten.
+
+use serde::{Deserialize, Serialize};
+
+use super::{SeriesEntry, SidecarMetadata, non_empty};
+use crate::error::{AppError, Result};
+
+/// The fields of `metadata.json` ABOP reads and writes
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AbsMetadata {
+    title: Option<String>,
+    subtitle: Option<String>,
+    authors: Vec<String>,
+    narrators: Vec<String>,
+    series: Vec<String>,
+    genres: Vec<String>,
+    published_year: Option<String>,
+    publisher: Option<String>,
+    description: Option<String>,
+    isbn: Option<String>,
+    asin: Option<String>,
+    language: Option<String>,
+}
+
+/// Parse an Audiobookshelf metadata file
+pub(super) fn parse(content: &str) -> Result<SidecarMetadata> {
+    let abs: AbsMetadata = serde_json::from_str(content)
+        .map_err(|e| AppError::Parse(format!("Invalid Audiobookshelf metadata: {e}")))?;
+    let list = |values: Vec<String>| values.iter().filter_map(|value| non_empty(value)).collect();
+    let text = |value: Option<String>| value.as_deref().and_then(non_empty);
+    Ok(SidecarMetadata {
+        title: text(abs.title),
+        subtitle: text(abs.subtitle),
+        authors: list(abs.authors),
+        narrators: list(abs.narrators),
+        description: text(abs.description),
+        series: abs
+            .series
+            .iter()
+            .filter_map(|series| parse_series(series))
+            .collect(),
+        genres: list(abs.genres),
+        publisher: text(abs.publisher),
+        published_year: text(abs.published_year),
+        language: text(abs.language),
+        isbn: text(abs.isbn),
+        asin: text(abs.asin),
+    })
+}
+
+/// Split `"Name #3"` into the series name and position
+fn parse_series(series: &str) -> Option<SeriesEntry> {
+    let series = series.trim();
+    let (name, sequence) = match series.rsplit_once(" #") {
+        Some((name, sequence)) if !sequence.trim().is_empty() => {
+            (name, Some(sequence.trim().to_string()))
+        }
+        _ => (series, None),
+    };
+    Some(SeriesEntry {
+        name: non_empty(name)?,
+        sequence,
+    })
+}
+
+/// Render metadata as `metadata.json`, keeping unknown fields of `existing`
+pub(super) fn render(metadata: &SidecarMetadata, existing: Option<&str>) -> Result<String> {
+    let abs = AbsMetadata {
+        title: metadata.title.clone(),
+        subtitle: metadata.subtitle.clone(),
+        authors: metadata.authors.clone(),
+        narrators: metadata.narrators.clone(),
+        series: metadata
+            .series
+            .iter()
+            .map(|series| match &series.sequence {
+                Some(sequence) => format!("{} #{sequence}", series.name),
+                None => series.name.clone(),
+            })
+            .collect(),
+        genres: metadata.genres.clone(),
+        published_year: metadata.published_year.clone(),
+        publisher: metadata.publisher.clone(),
+        description: metadata.description.clone(),
+        isbn: metadata.isbn.clone(),
+        asin: metadata.asin.clone(),
+        language: metadata.language.clone(),
+    };
+    let mut document = existing
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(content).ok())
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+    let serialize_error = |e: serde_json::Error| {
+        AppError::Parse(format!("Cannot serialize Audiobookshelf metadata: {e}"))
+    };
+    if let (Some(fields), serde_json::Value::Object(known)) = (
+        document.as_object_mut(),
+        serde_json::to_value(abs).map_err(serialize_error)?,
+    ) {
+        fields.extend(known);
+    }
+    serde_json::to_string_pretty(&document).map_err(serialize_error)
+}
@@ -0,0 +1,345 @@
+//! Merging sidecars, embedded tags and file names into an audiobook
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{SidecarFormat, SidecarMetadata, Sidecars, non_empty};
+use crate::audio::AudioMetadata;
+use crate::error::{AppError, Result};
+use crate::models::Audiobook;
+use crate::models::audiobook::fallbacks::UNKNOWN_TITLE;
+
+/// Audiobook field filled in from metadata sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetadataField {
+    /// Book title
+    Title,
+    /// Author
+    Author,
+    /// Narrator
+    Narrator,
+    /// Description
+    Description,
+}
+
+impl MetadataField {
+    /// All merged fields
+    pub const ALL: [Self; 4] = [Self::Title, Self::Author, Self::Narrator, Self::Description];
+
+    /// Returns the lowercase name of the field
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Author => "author",
+            Self::Narrator => "narrator",
+            Self::Description => "description",
+        }
+    }
+
+    /// The value of this field in sidecar metadata
+    pub(crate) fn in_sidecar(self, metadata: &SidecarMetadata) -> Option<String> {
+        match self {
+            Self::Title => metadata.title.clone(),
+            Self::Author => metadata.author(),
+            Self::Narrator => metadata.narrator(),
+            Self::Description => metadata.description.clone(),
+        }
+    }
+
+    /// The value of this field in embedded tags
+    fn in_tags(self, tags: &AudioMetadata) -> Option<String> {
+        let value = match self {
+            Self::Title => tags.title.as_deref(),
+            Self::Author => tags.artist.as_deref(),
+            Self::Narrator => tags.narrator.as_deref(),
+            Self::Description => tags.description.as_deref(),
+        };
+        value.and_then(non_empty)
+    }
+
+    /// The value of this field derived from the file name
+    fn in_filename(self, audiobook: &Audiobook) -> Option<String> {
+        match self {
+            Self::Title => audiobook
+                .path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(non_empty),
+            Self::Author | Self::Narrator | Self::Description => None,
+        }
+    }
+
+    /// The value of this field in an audiobook
+    pub(crate) fn in_audiobook(self, audiobook: &Audiobook) -> Option<&str> {
+        match self {
+            Self::Title => audiobook.title.as_deref(),
+            Self::Author => audiobook.author.as_deref(),
+            Self::Narrator => audiobook.narrator.as_deref(),
+            Self::Description => audiobook.description.as_deref(),
+        }
+    }
+
+    /// The audiobook field to fill in
+    pub(crate) fn slot(self, audiobook: &mut Audiobook) -> &mut Option<String> {
+        match self {
+            Self::Title => &mut audiobook.title,
+            Self::Author => &mut audiobook.author,
+            Self::Narrator => &mut audiobook.narrator,
+            Self::Description => &mut audiobook.description,
+        }
+    }
+}
+
+impl fmt::Display for MetadataField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MetadataField {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str() == s)
+            .ok_or_else(|| AppError::Parse(format!("Unknown metadata field '{s}'")))
+    }
+}
+
+/// Kind of metadata source, ranked by [`MetadataPrecedence`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceKind {
+    /// Sidecar files next to the audio
+    Sidecar,
+    /// Tags embedded in the audio file
+    Tag,
+    /// The file name
+    Filename,
+}
+
+impl SourceKind {
+    /// Returns the lowercase name of the kind
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sidecar => "sidecar",
+            Self::Tag => "tag",
+            Self::Filename => "filename",
+        }
+    }
+}
+
+impl FromStr for SourceKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "sidecar" => Ok(Self::Sidecar),
+            "tag" | "tags" => Ok(Self::Tag),
+            "filename" => Ok(Self::Filename),
+            other => Err(AppError::Parse(format!(
+                "Unknown metadata source '{other}', expected sidecar, tag or filename"
+            ))),
+        }
+    }
+}
+
+/// Where the value of a field came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetadataSource {
+    /// A sidecar file of the given format
+    Sidecar(SidecarFormat),
+    /// Tags embedded in the audio file
+    Tag,
+    /// The file name
+    Filename,
+}
+
+impl MetadataSource {
+    /// The kind of source
+    #[must_use]
+    pub const fn kind(self) -> SourceKind {
+        match self {
+            Self::Sidecar(_) => SourceKind::Sidecar,
+            Self::Tag => SourceKind::Tag,
+            Self::Filename => SourceKind::Filename,
+        }
+    }
+
+    /// Returns the name stored in the database, e.g. `opf` or `tag`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sidecar(format) => format.as_str(),
+            Self::Tag => "tag",
+            Self::Filename => "filename",
+        }
+    }
+}
+
+impl fmt::Display for MetadataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MetadataSource {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tag" => Ok(Self::Tag),
+            "filename" => Ok(Self::Filename),
+            other => other.parse().map(Self::Sidecar),
+        }
+    }
+}
+
+/// Order in which metadata sources are consulted for each field
+///
+/// The first source with a value wins. Sources left out are never used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<SourceKind>")]
+pub struct MetadataPrecedence(Vec<SourceKind>);
+
+impl MetadataPrecedence {
+    /// Create a precedence from the most to the least trusted source
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::ValidationFailed`] if `sources` is empty or lists
+    /// a source twice.
+    pub fn new(sources: Vec<SourceKind>) -> Result<Self> {
+        if sources.is_empty() {
+            return Err(AppError::ValidationFailed(
+                "Metadata precedence needs at least one source".to_string(),
+            ));
+        }
+        if let Some(duplicate) = sources
+            .iter()
+            .enumerate()
+            .find_map(|(i, source)| sources[..i].contains(source).then_some(source))
+        {
+            return Err(AppError::ValidationFailed(format!(
+                "Metadata source '{}' is listed twice",
+                duplicate.as_str()
+            )));
+        }
+        Ok(Self(sources))
+    }
+
+    /// The sources from the most to the least trusted
+    #[must_use]
+    pub fn sources(&self) -> &[SourceKind] {
+        &self.0
+    }
+}
+
+impl Default for MetadataPrecedence {
+    /// Sidecars over tags over the file name
+    fn default() -> Self {
+        Self(vec![
+            SourceKind::Sidecar,
+            SourceKind::Tag,
+            SourceKind::Filename,
+        ])
+    }
+}
+
+impl TryFrom<Vec<SourceKind>> for MetadataPrecedence {
+    type Error = AppError;
+
+    fn try_from(sources: Vec<SourceKind>) -> Result<Self> {
+        Self::new(sources)
+    }
+}
+
+impl FromStr for MetadataPrecedence {
+    type Err = AppError;
+
+    /// Parse a comma separated list such as `tag,sidecar,filename`
+    fn from_str(s: &str) -> Result<Self> {
+        let sources = s
+            .split(',')
+            .filter(|source| !source.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<SourceKind>>>()?;
+        Self::new(sources)
+    }
+}
+
+/// The source each merged field was taken from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldOrigins(BTreeMap<MetadataField, MetadataSource>);
+
+impl FieldOrigins {
+    /// Where a field came from, if it has a value
+    #[must_use]
+    pub fn get(&self, field: MetadataField) -> Option<MetadataSource> {
+        self.0.get(&field).copied()
+    }
+
+    /// Record where a field came from
+    pub fn insert(&mut self, field: MetadataField, source: MetadataSource) {
+        self.0.insert(field, source);
+    }
+
+    /// Fields and their sources, in field order
+    pub fn iter(&self) -> impl Iterator<Item = (MetadataField, MetadataSource)> + '_ {
+        self.0.iter().map(|(field, source)| (*field, *source))
+    }
+
+    /// Whether no field has a recorded source
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(MetadataField, MetadataSource)> for FieldOrigins {
+    fn from_iter<I: IntoIterator<Item = (MetadataField, MetadataSource)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Fill in the title, author, narrator and description of an audiobook
+///
+/// Each field takes the value of the first source in `precedence` that has
+/// one. A book without any title is given the placeholder title, which has
+/// no recorded origin. Returns where each field came from.
+pub fn merge_metadata(
+    audiobook: &mut Audiobook,
+    tags: Option<&AudioMetadata>,
+    sidecars: &Sidecars,
+    precedence: &MetadataPrecedence,
+) -> FieldOrigins {
+    let mut origins = FieldOrigins::default();
+    for field in MetadataField::ALL {
+        let found = precedence.sources().iter().find_map(|kind| match kind {
+            SourceKind::Sidecar => sidecars
+                .find(|metadata| field.in_sidecar(metadata))
+                .map(|(value, format)| (value, MetadataSource::Sidecar(format))),
+            SourceKind::Tag => tags
+                .and_then(|tags| field.in_tags(tags))
+                .map(|value| (value, MetadataSource::Tag)),
+            SourceKind::Filename => field
+                .in_filename(audiobook)
+                .map(|value| (value, MetadataSource::Filename)),
+        });
+        let value = found.map(|(value, source)| {
+            origins.insert(field, source);
+            value
+        });
+        *field.slot(audiobook) = value;
+    }
+    if audiobook.title.is_none() {
+        audiobook.title = Some(UNKNOWN_TITLE.to_string());
+    }
+    origins
+}
@@ -0,0 +1,359 @@
+//! Sidecar metadata files stored next to audiobooks
+//!
+//! Books managed by other tools often carry their metadata in files beside
+//! the audio: `metadata.opf` from Calibre and Readarr, `metadata.json` from
+//! Audiobookshelf, and plain `desc.txt` and `reader.txt` files. The scanner
+//! reads them and merges them with the embedded tags and the file name by a
+//! configurable [`MetadataPrecedence`], recording where each field came
+//! from. The same formats can be written back so that edits made in ABOP
+//! reach the other tools.
+
+mod audiobookshelf;
+mod merge;
+mod opf;
+#[cfg(test)]
+mod tests;
+mod text;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{AppError, Result};
+use crate::library::names::parse_people;
+use crate::models::Audiobook;
+
+pub use merge::{
+    FieldOrigins, MetadataField, MetadataPrecedence, MetadataSource, SourceKind, merge_metadata,
+};
+
+/// Calibre and Readarr metadata file
+pub const OPF_FILE: &str = "metadata.opf";
+/// Audiobookshelf metadata file
+pub const AUDIOBOOKSHELF_FILE: &str = "metadata.json";
+/// Plain text description
+pub const DESCRIPTION_FILE: &str = "desc.txt";
+/// Plain text narrator names, one per line
+pub const READER_FILE: &str = "reader.txt";
+
+/// Separator between several people in an author or narrator field
+const PEOPLE_SEPARATOR: &str = "; ";
+
+/// Sidecar file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SidecarFormat {
+    /// Audiobookshelf `metadata.json`
+    Audiobookshelf,
+    /// OPF package metadata in `metadata.opf`
+    Opf,
+    /// `desc.txt` and `reader.txt`
+    Text,
+}
+
+impl SidecarFormat {
+    /// All formats, from the most to the least trusted
+    ///
+    /// Audiobookshelf files are written for audiobooks specifically, while
+    /// OPF files usually describe the printed book.
+    pub const ALL: [Self; 3] = [Self::Audiobookshelf, Self::Opf, Self::Text];
+
+    /// Returns the lowercase name of the format
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Audiobookshelf => "audiobookshelf",
+            Self::Opf => "opf",
+            Self::Text => "text",
+        }
+    }
+
+    /// Read the sidecar of this format in `dir`
+    ///
+    /// Returns `Ok(None)` if the directory has no such sidecar.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if a sidecar exists but cannot be read, or
+    /// [`AppError::Parse`] if it is malformed.
+    pub fn read(self, dir: &Path) -> Result<Option<SidecarMetadata>> {
+        match self {
+            Self::Audiobookshelf => {
+                read_file(&dir.join(AUDIOBOOKSHELF_FILE), audiobookshelf::parse)
+            }
+            Self::Opf => read_file(&dir.join(OPF_FILE), opf::parse),
+            Self::Text => text::read(dir),
+        }
+    }
+
+    /// Write `metadata` as a sidecar of this format in `dir`
+    ///
+    /// Returns the files written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Io`] if a file cannot be written, or
+    /// [`AppError::Parse`] if an existing OPF file cannot be edited.
+    pub fn write(self, dir: &Path, metadata: &SidecarMetadata) -> Result<Vec<PathBuf>> {
+        let (path, content) = match self {
+            Self::Audiobookshelf => {
+                let path = dir.join(AUDIOBOOKSHELF_FILE);
+                let existing = read_optional(&path)?;
+                let content = audiobookshelf::render(metadata, existing.as_deref())?;
+                (path, content)
+            }
+            Self::Opf => {
+                let path = dir.join(OPF_FILE);
+                let existing = read_optional(&path)?;
+                let content = opf::render(metadata, existing.as_deref())?;
+                (path, content)
+            }
+            Self::Text => return text::write(dir, metadata),
+        };
+        std::fs::write(&path, content)?;
+        Ok(vec![path])
+    }
+}
+
+impl fmt::Display for SidecarFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SidecarFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "audiobookshelf" | "abs" | "json" => Ok(Self::Audiobookshelf),
+            "opf" => Ok(Self::Opf),
+            "text" | "txt" => Ok(Self::Text),
+            other => Err(AppError::Parse(format!(
+                "Unknown sidecar format '{other}', expected audiobookshelf, opf or text"
+            ))),
+        }
+    }
+}
+
+/// Read and parse a sidecar file if it exists
+fn read_file(
+    path: &Path,
+    parse: fn(&str) -> Result<SidecarMetadata>,
+) -> Result<Option<SidecarMetadata>> {
+    read_optional(path)?
+        .map(|content| {
+            parse(&content).map_err(|e| AppError::Parse(format!("{}: {e}", path.display())))
+        })
+        .transpose()
+}
+
+/// Read a file, treating a missing file as `None`
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A book series with the position of the book in it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesEntry {
+    /// Series name
+    pub name: String,
+    /// Position in the series as written, e.g. `3` or `2.5`
+    pub sequence: Option<String>,
+}
+
+/// Book metadata read from a sidecar file
+///
+/// Fields ABOP does not store, such as genres and identifiers, are kept so
+/// that writing a sidecar back does not lose them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SidecarMetadata {
+    /// Book title
+    pub title: Option<String>,
+    /// Book subtitle
+    pub subtitle: Option<String>,
+    /// Authors in credit order
+    pub authors: Vec<String>,
+    /// Narrators in credit order
+    pub narrators: Vec<String>,
+    /// Description or synopsis
+    pub description: Option<String>,
+    /// Series the book belongs to
+    pub series: Vec<SeriesEntry>,
+    /// Genres and subjects
+    pub genres: Vec<String>,
+    /// Publisher
+    pub publisher: Option<String>,
+    /// Year of publication
+    pub published_year: Option<String>,
+    /// Language, as written in the file
+    pub language: Option<String>,
+    /// ISBN
+    pub isbn: Option<String>,
+    /// Audible ASIN
+    pub asin: Option<String>,
+}
+
+impl SidecarMetadata {
+    /// Whether no field is set
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The authors as one audiobook field
+    #[must_use]
+    pub fn author(&self) -> Option<String> {
+        join_people(&self.authors)
+    }
+
+    /// The narrators as one audiobook field
+    #[must_use]
+    pub fn narrator(&self) -> Option<String> {
+        join_people(&self.narrators)
+    }
+
+    /// Take the fields ABOP edits from an audiobook, keeping all others
+    pub fn apply_audiobook(&mut self, audiobook: &Audiobook) {
+        self.title.clone_from(&audiobook.title);
+        self.authors = split_people(audiobook.author.as_deref());
+        self.narrators = split_people(audiobook.narrator.as_deref());
+        self.description.clone_from(&audiobook.description);
+    }
+}
+
+fn join_people(people: &[String]) -> Option<String> {
+    (!people.is_empty()).then(|| people.join(PEOPLE_SEPARATOR))
+}
+
+fn split_people(field: Option<&str>) -> Vec<String> {
+    field
+        .map(|field| {
+            parse_people(field)
+                .into_iter()
+                .map(|person| person.name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Trim a value, treating blank text as missing
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// The sidecars found in one directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sidecars {
+    /// Metadata of each format found, from the most to the least trusted
+    pub entries: Vec<(SidecarFormat, SidecarMetadata)>,
+}
+
+impl Sidecars {
+    /// Read every sidecar in `dir`
+    ///
+    /// A malformed sidecar should not stop a scan, so unreadable files are
+    /// logged and skipped.
+    #[must_use]
+    pub fn read_dir(dir: &Path) -> Self {
+        let entries = SidecarFormat::ALL
+            .into_iter()
+            .filter_map(|format| match format.read(dir) {
+                Ok(metadata) => metadata
+                    .filter(|metadata| !metadata.is_empty())
+                    .map(|metadata| (format, metadata)),
+                Err(e) => {
+                    log::warn!("Ignoring {format} sidecar in {}: {e}", dir.display());
+                    None
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Whether no sidecar was found
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The first value of a field in order of trust, with its format
+    pub fn find<T>(
+        &self,
+        field: impl Fn(&SidecarMetadata) -> Option<T>,
+    ) -> Option<(T, SidecarFormat)> {
+        self.entries
+            .iter()
+            .find_map(|(format, metadata)| field(metadata).map(|value| (value, *format)))
+    }
+}
+
+/// Sidecars already read during one scan, keyed by directory
+///
+/// Every audio file of a book shares the sidecars of its directory, so a scan
+/// keeps one cache and parses each directory once. Create a new cache for
+/// every scan so that edited sidecars are picked up.
+#[derive(Debug, Clone, Default)]
+pub struct SidecarCache {
+    dirs: HashMap<PathBuf, Sidecars>,
+}
+
+impl SidecarCache {
+    /// Creates an empty cache
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The sidecars of `dir`, read on first use
+    pub fn get(&mut self, dir: &Path) -> &Sidecars {
+        self.dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| Sidecars::read_dir(dir))
+    }
+
+    /// Number of directories read so far
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dirs.len()
+    }
+
+    /// Whether no directory was read yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+}
+
+/// Write sidecars of the given formats for an audiobook into its directory
+///
+/// Existing sidecars are read first and only the fields ABOP edits are
+/// replaced, so genres, identifiers and the like survive. Returns the files
+/// written.
+///
+/// # Errors
+///
+/// Returns [`AppError::Io`] if a file cannot be written, or
+/// [`AppError::Parse`] if an existing sidecar is malformed and would be lost.
+pub fn write_sidecars(audiobook: &Audiobook, formats: &[SidecarFormat]) -> Result<Vec<PathBuf>> {
+    let dir = audiobook.path.parent().ok_or_else(|| {
+        AppError::InvalidData(format!(
+            "Audiobook has no directory: {}",
+            audiobook.path.display()
+        ))
+    })?;
+    let mut written = Vec::new();
+    for &format in formats {
+        let mut metadata = format.read(dir)?.unwrap_or_default();
+        metadata.apply_audiobook(audiobook);
+        written.extend(format.write(dir, &metadata)?);
+    }
+    Ok(written)
+}
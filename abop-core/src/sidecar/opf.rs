@@ -0,0 +1,593 @@
+//! OPF package metadata as written by Calibre and Readarr
+//!
+//! Both OPF 2 (`opf:role`, `calibre:series`) and EPUB 3 (`refines` metadata,
+//! `belongs-to-collection`) conventions are understood. Files are written in
+//! OPF 2 with the EPUB 3 series and subtitle metadata alongside. An existing
+//! file is edited in place: only the metadata elements of changed fields are
+//! replaced, and the manifest, spine, guide and other metadata are kept.
+
+use roxmltree::{Document, Node};
+
+use super::{SeriesEntry, SidecarMetadata, non_empty};
+use crate::error::{AppError, Result};
+use crate::utils::xml::XmlWriter;
+
+const OPF_NS: &str = "http://www.idpf.org/2007/opf";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+
+/// MARC relator code for authors
+const AUTHOR_ROLE: &str = "aut";
+/// MARC relator code for narrators
+const NARRATOR_ROLE: &str = "nrt";
+
+/// Parse an OPF document
+pub(super) fn parse(content: &str) -> Result<SidecarMetadata> {
+    let document = parse_document(content)?;
+    let metadata = metadata_element(&document)?;
+    let elements: Vec<Node<'_, '_>> = metadata.children().filter(Node::is_element).collect();
+
+    let refinements = Refinements::new(&elements);
+    let refinement = |node: &Node<'_, '_>, property: &str| refinements.get(*node, property);
+
+    let mut result = SidecarMetadata::default();
+    let mut calibre_series = None;
+    let mut calibre_index = None;
+    for node in &elements {
+        let name = node.tag_name().name();
+        if node.tag_name().namespace() == Some(DC_NS) {
+            let Some(value) = non_empty(&text_of(*node)) else {
+                continue;
+            };
+            match name {
+                "title" => {
+                    if refinement(node, "title-type") == Some("subtitle") {
+                        result.subtitle.get_or_insert(value);
+                    } else {
+                        result.title.get_or_insert(value);
+                    }
+                }
+                "creator" | "contributor" => match person_field(*node, &refinements) {
+                    Some(Field::Narrators) => result.narrators.push(value),
+                    Some(Field::Authors) => result.authors.push(value),
+                    _ => {}
+                },
+                "description" => {
+                    result.description.get_or_insert(value);
+                }
+                "publisher" => {
+                    result.publisher.get_or_insert(value);
+                }
+                "date" => {
+                    if result.published_year.is_none() {
+                        result.published_year = year(&value);
+                    }
+                }
+                "language" => {
+                    result.language.get_or_insert(value);
+                }
+                "subject" => result.genres.push(value),
+                "identifier" => identifier(*node, &value, &mut result),
+                _ => {}
+            }
+        } else if name == "meta" {
+            match (node.attribute("name"), node.attribute("content")) {
+                (Some("calibre:series"), Some(content)) => calibre_series = non_empty(content),
+                (Some("calibre:series_index"), Some(content)) => calibre_index = non_empty(content),
+                _ => {}
+            }
+            if node.attribute("property") == Some("belongs-to-collection")
+                && refinement(node, "collection-type").is_none_or(|kind| kind == "series")
+                && let Some(name) = non_empty(&text_of(*node))
+            {
+                let sequence = refinement(node, "group-position").map(str::to_string);
+                push_series(&mut result.series, name, sequence);
+            }
+        }
+    }
+    if let Some(name) = calibre_series {
+        push_series(
+            &mut result.series,
+            name,
+            calibre_index.map(|index| trim_index(&index)),
+        );
+    }
+    Ok(result)
+}
+
+/// All text inside a node
+fn text_of(node: Node<'_, '_>) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect()
+}
+
+/// The year at the start of a date such as `2019-05-07T00:00:00+00:00`
+fn year(date: &str) -> Option<String> {
+    let year = date.get(..4)?;
+    year.chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| year.to_string())
+}
+
+/// Record an ISBN or ASIN identifier
+fn identifier(node: Node<'_, '_>, value: &str, result: &mut SidecarMetadata) {
+    let code = || value.rsplit(':').next().unwrap_or(value).trim().to_string();
+    match identifier_field(node, value) {
+        Some(Field::Isbn) => {
+            result.isbn.get_or_insert_with(code);
+        }
+        Some(Field::Asin) => {
+            result.asin.get_or_insert_with(code);
+        }
+        _ => {}
+    }
+}
+
+/// Whether an identifier holds an ISBN or an ASIN
+fn identifier_field(node: Node<'_, '_>, value: &str) -> Option<Field> {
+    let scheme = node
+        .attribute((OPF_NS, "scheme"))
+        .or_else(|| node.attribute("scheme"))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let lower = value.to_ascii_lowercase();
+    if scheme == "isbn" || lower.starts_with("urn:isbn:") || lower.starts_with("isbn:") {
+        Some(Field::Isbn)
+    } else if matches!(scheme.as_str(), "asin" | "amazon" | "audible")
+        || lower.starts_with("urn:asin:")
+        || lower.starts_with("asin:")
+    {
+        Some(Field::Asin)
+    } else {
+        None
+    }
+}
+
+/// Whether a creator or contributor is an author or a narrator
+fn person_field(node: Node<'_, '_>, refinements: &Refinements<'_>) -> Option<Field> {
+    let role = node
+        .attribute((OPF_NS, "role"))
+        .or_else(|| node.attribute("role"))
+        .or_else(|| refinements.get(node, "role"));
+    match role {
+        Some(NARRATOR_ROLE) => Some(Field::Narrators),
+        Some(AUTHOR_ROLE) => Some(Field::Authors),
+        None if node.tag_name().name() == "creator" => Some(Field::Authors),
+        _ => None,
+    }
+}
+
+/// Calibre writes series positions as floats, so `3.0` is the third book
+fn trim_index(index: &str) -> String {
+    index.strip_suffix(".0").unwrap_or(index).to_string()
+}
+
+fn push_series(series: &mut Vec<SeriesEntry>, name: String, sequence: Option<String>) {
+    if let Some(existing) = series.iter_mut().find(|entry| entry.name == name) {
+        if existing.sequence.is_none() {
+            existing.sequence = sequence;
+        }
+    } else {
+        series.push(SeriesEntry { name, sequence });
+    }
+}
+
+fn parse_document(content: &str) -> Result<Document<'_>> {
+    Document::parse(content).map_err(|e| AppError::Parse(format!("Invalid OPF document: {e}")))
+}
+
+fn metadata_element<'a, 'input>(document: &'a Document<'input>) -> Result<Node<'a, 'input>> {
+    document
+        .descendants()
+        .find(|node| node.has_tag_name((OPF_NS, "metadata")) || node.has_tag_name("metadata"))
+        .ok_or_else(|| AppError::Parse("OPF document has no metadata element".to_string()))
+}
+
+/// EPUB 3 metadata attached to other elements through `refines="#id"`
+///
+/// Roles, title types and series positions are stored this way.
+struct Refinements<'a> {
+    entries: Vec<(&'a str, &'a str, String)>,
+}
+
+impl<'a> Refinements<'a> {
+    fn new(elements: &[Node<'a, '_>]) -> Self {
+        let mut entries = Vec::new();
+        for meta in elements
+            .iter()
+            .filter(|node| node.tag_name().name() == "meta")
+        {
+            if let (Some(target), Some(property)) =
+                (meta.attribute("refines"), meta.attribute("property"))
+                && let Some(value) = non_empty(&text_of(*meta))
+            {
+                entries.push((target.trim_start_matches('#'), property, value));
+            }
+        }
+        Self { entries }
+    }
+
+    /// The value of `property` refining `node`
+    fn get(&self, node: Node<'_, '_>, property: &str) -> Option<&str> {
+        let id = node.attribute("id")?;
+        self.entries
+            .iter()
+            .find(|(target, name, _)| *target == id && *name == property)
+            .map(|(_, _, value)| value.as_str())
+    }
+}
+
+/// Metadata fields ABOP writes, in the order they are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Subtitle,
+    Authors,
+    Narrators,
+    Description,
+    Publisher,
+    Date,
+    Language,
+    Isbn,
+    Asin,
+    Genres,
+    Series,
+}
+
+impl Field {
+    const ALL: [Self; 12] = [
+        Self::Title,
+        Self::Subtitle,
+        Self::Authors,
+        Self::Narrators,
+        Self::Description,
+        Self::Publisher,
+        Self::Date,
+        Self::Language,
+        Self::Isbn,
+        Self::Asin,
+        Self::Genres,
+        Self::Series,
+    ];
+
+    /// Whether the field has the same value in both
+    fn unchanged(self, old: &SidecarMetadata, new: &SidecarMetadata) -> bool {
+        match self {
+            Self::Title => old.title == new.title,
+            Self::Subtitle => old.subtitle == new.subtitle,
+            Self::Authors => old.authors == new.authors,
+            Self::Narrators => old.narrators == new.narrators,
+            Self::Description => old.description == new.description,
+            Self::Publisher => old.publisher == new.publisher,
+            Self::Date => old.published_year == new.published_year,
+            Self::Language => old.language == new.language,
+            Self::Isbn => old.isbn == new.isbn,
+            Self::Asin => old.asin == new.asin,
+            Self::Genres => old.genres == new.genres,
+            Self::Series => old.series == new.series,
+        }
+    }
+
+    /// Write the elements holding this field
+    fn write(self, xml: &mut XmlWriter, metadata: &SidecarMetadata) {
+        match self {
+            Self::Title => {
+                if let Some(title) = &metadata.title {
+                    xml.text("dc:title", title);
+                }
+            }
+            Self::Subtitle => {
+                if let Some(subtitle) = &metadata.subtitle {
+                    xml.element("dc:title", &[("id", "subtitle")], subtitle);
+                    xml.element(
+                        "meta",
+                        &[("refines", "#subtitle"), ("property", "title-type")],
+                        "subtitle",
+                    );
+                }
+            }
+            Self::Authors => {
+                for author in &metadata.authors {
+                    xml.element("dc:creator", &[("opf:role", AUTHOR_ROLE)], author);
+                }
+            }
+            Self::Narrators => {
+                for narrator in &metadata.narrators {
+                    xml.element("dc:contributor", &[("opf:role", NARRATOR_ROLE)], narrator);
+                }
+            }
+            Self::Description => {
+                if let Some(description) = &metadata.description {
+                    xml.text("dc:description", description);
+                }
+            }
+            Self::Publisher => {
+                if let Some(publisher) = &metadata.publisher {
+                    xml.text("dc:publisher", publisher);
+                }
+            }
+            Self::Date => {
+                if let Some(year) = &metadata.published_year {
+                    xml.text("dc:date", year);
+                }
+            }
+            Self::Language => {
+                if let Some(language) = &metadata.language {
+                    xml.text("dc:language", language);
+                }
+            }
+            Self::Isbn => {
+                if let Some(isbn) = &metadata.isbn {
+                    xml.element("dc:identifier", &[("opf:scheme", "ISBN")], isbn);
+                }
+            }
+            Self::Asin => {
+                if let Some(asin) = &metadata.asin {
+                    xml.element("dc:identifier", &[("opf:scheme", "ASIN")], asin);
+                }
+            }
+            Self::Genres => {
+                for genre in &metadata.genres {
+                    xml.text("dc:subject", genre);
+                }
+            }
+            Self::Series => write_series(xml, &metadata.series),
+        }
+    }
+}
+
+fn write_series(xml: &mut XmlWriter, series: &[SeriesEntry]) {
+    if let Some(first) = series.first() {
+        xml.empty(
+            "meta",
+            &[("name", "calibre:series"), ("content", &first.name)],
+        );
+        if let Some(sequence) = &first.sequence {
+            xml.empty(
+                "meta",
+                &[("name", "calibre:series_index"), ("content", sequence)],
+            );
+        }
+    }
+    for (index, entry) in series.iter().enumerate() {
+        let id = format!("series-{}", index + 1);
+        xml.element(
+            "meta",
+            &[("property", "belongs-to-collection"), ("id", &id)],
+            &entry.name,
+        );
+        if let Some(sequence) = &entry.sequence {
+            let target = format!("#{id}");
+            xml.element(
+                "meta",
+                &[("refines", &target), ("property", "group-position")],
+                sequence,
+            );
+        }
+    }
+}
+
+/// The field a metadata element holds, if ABOP writes it
+///
+/// Refinements belong to the field of the element they refine.
+fn element_field(node: Node<'_, '_>, refinements: &Refinements<'_>) -> Option<Field> {
+    if node.tag_name().namespace() == Some(DC_NS) {
+        let value = non_empty(&text_of(node))?;
+        return match node.tag_name().name() {
+            "title" if refinements.get(node, "title-type") == Some("subtitle") => {
+                Some(Field::Subtitle)
+            }
+            "title" => Some(Field::Title),
+            "creator" | "contributor" => person_field(node, refinements),
+            "description" => Some(Field::Description),
+            "publisher" => Some(Field::Publisher),
+            "date" => Some(Field::Date),
+            "language" => Some(Field::Language),
+            "subject" => Some(Field::Genres),
+            "identifier" => identifier_field(node, &value),
+            _ => None,
+        };
+    }
+    if node.tag_name().name() != "meta" {
+        return None;
+    }
+    let calibre_series = matches!(
+        node.attribute("name"),
+        Some("calibre:series" | "calibre:series_index")
+    );
+    let collection = node.attribute("property") == Some("belongs-to-collection")
+        && refinements
+            .get(node, "collection-type")
+            .is_none_or(|kind| kind == "series");
+    (calibre_series || collection).then_some(Field::Series)
+}
+
+/// Render metadata as an OPF document, editing `existing` in place
+///
+/// Elements of fields whose value did not change are left exactly as they
+/// were, so Calibre's sort names and full dates survive a write.
+///
+/// # Errors
+///
+/// Returns [`AppError::Parse`] if `existing` is not an OPF document.
+pub(super) fn render(metadata: &SidecarMetadata, existing: Option<&str>) -> Result<String> {
+    let Some(content) = existing else {
+        return Ok(render_new(metadata));
+    };
+    let old = parse(content)?;
+    let document = parse_document(content)?;
+    let metadata_node = metadata_element(&document)?;
+    let elements: Vec<Node<'_, '_>> = metadata_node.children().filter(Node::is_element).collect();
+    let refinements = Refinements::new(&elements);
+
+    let mut fields: Vec<(Node<'_, '_>, Field)> = elements
+        .iter()
+        .filter(|node| node.attribute("refines").is_none())
+        .filter_map(|node| Some((*node, element_field(*node, &refinements)?)))
+        .collect();
+    for meta in elements
+        .iter()
+        .filter(|node| node.tag_name().name() == "meta")
+    {
+        let Some(target) = meta.attribute("refines") else {
+            continue;
+        };
+        let target = target.trim_start_matches('#');
+        if let Some(field) = fields
+            .iter()
+            .find(|(node, _)| node.attribute("id") == Some(target))
+            .map(|(_, field)| *field)
+        {
+            fields.push((*meta, field));
+        }
+    }
+
+    let changed: Vec<Field> = Field::ALL
+        .into_iter()
+        .filter(|field| !field.unchanged(&old, metadata))
+        .collect();
+    let mut edits: Vec<(usize, usize, String)> = fields
+        .iter()
+        .filter(|(_, field)| changed.contains(field))
+        .map(|(node, _)| {
+            (
+                line_start(content, node.range().start),
+                node.range().end,
+                String::new(),
+            )
+        })
+        .collect();
+
+    let mut xml = XmlWriter::fragment(0);
+    for field in &changed {
+        field.write(&mut xml, metadata);
+    }
+    let added = xml.finish();
+    if !added.is_empty() {
+        edits.push(insertion(content, metadata_node, &added)?);
+        edits.extend(namespace_declarations(content, metadata_node)?);
+    }
+
+    edits.sort_by_key(|(start, end, _)| (*start, *end));
+    let mut result = String::with_capacity(content.len() + added.len());
+    let mut cursor = 0;
+    for (start, end, replacement) in edits {
+        result.push_str(&content[cursor..start]);
+        result.push_str(&replacement);
+        cursor = end;
+    }
+    result.push_str(&content[cursor..]);
+    Ok(result)
+}
+
+/// Start of the indentation before `position`, if only whitespace precedes
+/// it on its line
+fn line_start(content: &str, position: usize) -> usize {
+    let before = content[..position].trim_end_matches([' ', '\t']);
+    match before.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line).len(),
+        None => position,
+    }
+}
+
+/// Edit adding rendered elements at the end of the metadata element
+///
+/// The elements are indented like the existing metadata elements.
+fn insertion(content: &str, metadata: Node<'_, '_>, added: &str) -> Result<(usize, usize, String)> {
+    let range = metadata.range();
+    let source = &content[range.clone()];
+    let outer = indentation(content, range.start);
+    let children: Vec<Node<'_, '_>> = metadata.children().filter(Node::is_element).collect();
+    let inner = children.first().map_or_else(
+        || format!("{outer}  "),
+        |first| indentation(content, first.range().start).to_string(),
+    );
+    let lines = added
+        .lines()
+        .map(|line| format!("\n{inner}{line}"))
+        .collect::<String>();
+
+    if let Some(last) = children.last() {
+        let end = last.range().end;
+        return Ok((end, end, lines));
+    }
+    if let Some(close) = source.rfind("</") {
+        let position = range.start + close;
+        return Ok((
+            line_start(content, position),
+            position,
+            format!("{lines}\n{outer}"),
+        ));
+    }
+    // A self-closing `<metadata/>` is reopened to hold the elements
+    let name = qualified_name(source);
+    if !source.ends_with("/>") {
+        return Err(AppError::Parse(
+            "Malformed OPF metadata element".to_string(),
+        ));
+    }
+    Ok((
+        range.end - 2,
+        range.end,
+        format!(">{lines}\n{outer}</{name}>"),
+    ))
+}
+
+/// Whitespace indenting the line that `position` is on, if nothing else
+/// precedes it
+fn indentation(content: &str, position: usize) -> &str {
+    let line = content[..position]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+    let indent = &content[line..position];
+    if indent.trim().is_empty() { indent } else { "" }
+}
+
+/// Edits declaring the `dc` and `opf` prefixes the written elements use
+fn namespace_declarations(
+    content: &str,
+    metadata: Node<'_, '_>,
+) -> Result<Vec<(usize, usize, String)>> {
+    let start = metadata.range().start;
+    let position = start + 1 + qualified_name(&content[start..]).len();
+    let mut edits = Vec::new();
+    for (prefix, namespace) in [("dc", DC_NS), ("opf", OPF_NS)] {
+        match metadata.lookup_namespace_uri(Some(prefix)) {
+            Some(uri) if uri == namespace => {}
+            Some(uri) => {
+                return Err(AppError::Parse(format!(
+                    "OPF document binds the {prefix} prefix to {uri}"
+                )));
+            }
+            None => edits.push((
+                position,
+                position,
+                format!(" xmlns:{prefix}=\"{namespace}\""),
+            )),
+        }
+    }
+    Ok(edits)
+}
+
+/// Name of the element whose start tag begins `source`
+fn qualified_name(source: &str) -> &str {
+    let name = source.trim_start_matches('<');
+    let end = name
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(name.len());
+    &name[..end]
+}
+
+/// Render metadata as a new OPF document
+fn render_new(metadata: &SidecarMetadata) -> String {
+    let mut xml = XmlWriter::new();
+    xml.open("package", &[("xmlns", OPF_NS), ("version", "2.0")]);
+    xml.open("metadata", &[("xmlns:dc", DC_NS), ("xmlns:opf", OPF_NS)]);
+    for field in Field::ALL {
+        field.write(&mut xml, metadata);
+    }
+    xml.close("metadata");
+    xml.close("package");
+    xml.finish()
+}
@@ -0,0 +1,361 @@
+use std::fs;
+
+use super::*;
+use crate::audio::AudioMetadata;
+
+const CALIBRE_OPF: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" unique-identifier="uuid_id" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+    <dc:identifier opf:scheme="calibre" id="calibre_id">42</dc:identifier>
+    <dc:identifier opf:scheme="ISBN">9780553283686</dc:identifier>
+    <dc:title>Hyperion</dc:title>
+    <dc:creator opf:file-as="Simmons, Dan" opf:role="aut">Dan Simmons</dc:creator>
+    <dc:contributor opf:role="nrt">Victor Bevine</dc:contributor>
+    <dc:contributor opf:role="bkp">calibre (5.0)</dc:contributor>
+    <dc:date>1989-05-26T00:00:00+00:00</dc:date>
+    <dc:description>Pilgrims &amp; the Shrike.</dc:description>
+    <dc:publisher>Brilliance Audio</dc:publisher>
+    <dc:language>eng</dc:language>
+    <dc:subject>Science Fiction</dc:subject>
+    <meta name="calibre:series" content="Hyperion Cantos"/>
+    <meta name="calibre:series_index" content="1.0"/>
+  </metadata>
+</package>"#;
+
+#[test]
+fn test_parse_calibre_opf() {
+    let metadata = opf::parse(CALIBRE_OPF).unwrap();
+
+    assert_eq!(metadata.title.as_deref(), Some("Hyperion"));
+    assert_eq!(metadata.authors, ["Dan Simmons"]);
+    assert_eq!(metadata.narrators, ["Victor Bevine"]);
+    assert_eq!(
+        metadata.description.as_deref(),
+        Some("Pilgrims & the Shrike.")
+    );
+    assert_eq!(metadata.published_year.as_deref(), Some("1989"));
+    assert_eq!(metadata.isbn.as_deref(), Some("9780553283686"));
+    assert_eq!(metadata.genres, ["Science Fiction"]);
+    assert_eq!(
+        metadata.series,
+        [SeriesEntry {
+            name: "Hyperion Cantos".to_string(),
+            sequence: Some("1".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_parse_epub3_refines() {
+    let content = r##"<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title id="main">The Fall of Hyperion</dc:title>
+    <dc:creator id="c1">Dan Simmons</dc:creator>
+    <meta refines="#c1" property="role" scheme="marc:relators">aut</meta>
+    <dc:creator id="c2">Victor Bevine</dc:creator>
+    <meta refines="#c2" property="role" scheme="marc:relators">nrt</meta>
+    <meta property="belongs-to-collection" id="s1">Hyperion Cantos</meta>
+    <meta refines="#s1" property="collection-type">series</meta>
+    <meta refines="#s1" property="group-position">2</meta>
+  </metadata>
+</package>"##;
+    let metadata = opf::parse(content).unwrap();
+
+    assert_eq!(metadata.title.as_deref(), Some("The Fall of Hyperion"));
+    assert_eq!(metadata.authors, ["Dan Simmons"]);
+    assert_eq!(metadata.narrators, ["Victor Bevine"]);
+    assert_eq!(metadata.series[0].sequence.as_deref(), Some("2"));
+}
+
+fn full_metadata() -> SidecarMetadata {
+    SidecarMetadata {
+        title: Some("Words <&> Symbols".to_string()),
+        subtitle: Some("A Novel".to_string()),
+        authors: vec!["Ann Author".to_string(), "Bob Writer".to_string()],
+        narrators: vec!["Nora Reader".to_string()],
+        description: Some("First line.\nSecond \"quoted\" line.".to_string()),
+        series: vec![
+            SeriesEntry {
+                name: "Main Series".to_string(),
+                sequence: Some("2.5".to_string()),
+            },
+            SeriesEntry {
+                name: "Universe".to_string(),
+                sequence: None,
+            },
+        ],
+        genres: vec!["Fantasy".to_string(), "Humour".to_string()],
+        publisher: Some("Publisher".to_string()),
+        published_year: Some("2020".to_string()),
+        language: Some("en".to_string()),
+        isbn: Some("9781234567897".to_string()),
+        asin: Some("B012345678".to_string()),
+    }
+}
+
+#[test]
+fn test_opf_round_trip() {
+    let metadata = full_metadata();
+    assert_eq!(
+        opf::parse(&opf::render(&metadata, None).unwrap()).unwrap(),
+        metadata
+    );
+}
+
+/// `metadata.opf` as Calibre writes it next to a book in its library
+const CALIBRE_LIBRARY_OPF: &str = r#"<?xml version='1.0' encoding='utf-8'?>
+<package xmlns="http://www.idpf.org/2007/opf" unique-identifier="uuid_id" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+        <dc:identifier opf:scheme="calibre" id="calibre_id">42</dc:identifier>
+        <dc:identifier opf:scheme="uuid" id="uuid_id">0b6a3c1e-5d1f-4c3a-9a43-1c2d8f1e7b90</dc:identifier>
+        <dc:title>Hyperion</dc:title>
+        <dc:creator opf:file-as="Simmons, Dan" opf:role="aut">Dan Simmons</dc:creator>
+        <dc:contributor opf:file-as="calibre" opf:role="bkp">calibre (7.2.0) [https://calibre-ebook.com]</dc:contributor>
+        <dc:date>1989-05-26T00:00:00+00:00</dc:date>
+        <dc:description>&lt;p&gt;Pilgrims &amp;amp; the Shrike.&lt;/p&gt;</dc:description>
+        <dc:publisher>Brilliance Audio</dc:publisher>
+        <dc:identifier opf:scheme="ISBN">9780553283686</dc:identifier>
+        <dc:language>eng</dc:language>
+        <dc:subject>Science Fiction</dc:subject>
+        <meta name="calibre:series" content="Hyperion Cantos"/>
+        <meta name="calibre:series_index" content="1.0"/>
+        <meta name="calibre:rating" content="10"/>
+        <meta name="calibre:timestamp" content="2023-01-14T09:12:44.512000+00:00"/>
+        <meta name="calibre:title_sort" content="Hyperion"/>
+        <meta name="cover" content="cover"/>
+    </metadata>
+    <manifest>
+        <item id="cover" href="cover.jpg" media-type="image/jpeg"/>
+        <item id="audio" href="Hyperion.m4b" media-type="audio/mp4"/>
+    </manifest>
+    <spine>
+        <itemref idref="audio"/>
+    </spine>
+    <guide>
+        <reference type="cover" title="Cover" href="cover.jpg"/>
+    </guide>
+</package>
+"#;
+
+#[test]
+fn test_opf_round_trip_keeps_calibre_document() {
+    // Writing back what was read leaves the file untouched
+    let metadata = opf::parse(CALIBRE_LIBRARY_OPF).unwrap();
+    assert_eq!(
+        opf::render(&metadata, Some(CALIBRE_LIBRARY_OPF)).unwrap(),
+        CALIBRE_LIBRARY_OPF
+    );
+
+    let mut changed = metadata.clone();
+    changed.title = Some("Hyperion (Unabridged)".to_string());
+    changed.narrators = vec!["Victor Bevine".to_string()];
+    changed.series[0].sequence = Some("1.5".to_string());
+    let rendered = opf::render(&changed, Some(CALIBRE_LIBRARY_OPF)).unwrap();
+    assert_eq!(opf::parse(&rendered).unwrap(), changed);
+
+    for kept in [
+        r#"<dc:identifier opf:scheme="uuid" id="uuid_id">"#,
+        r#"<dc:creator opf:file-as="Simmons, Dan" opf:role="aut">Dan Simmons</dc:creator>"#,
+        r#"<dc:contributor opf:file-as="calibre" opf:role="bkp">"#,
+        "<dc:date>1989-05-26T00:00:00+00:00</dc:date>",
+        r#"<meta name="calibre:rating" content="10"/>"#,
+        r#"<meta name="cover" content="cover"/>"#,
+        r#"<item id="cover" href="cover.jpg" media-type="image/jpeg"/>"#,
+        r#"<itemref idref="audio"/>"#,
+        r#"<reference type="cover" title="Cover" href="cover.jpg"/>"#,
+    ] {
+        assert!(rendered.contains(kept), "lost {kept}");
+    }
+    assert!(!rendered.contains("<dc:title>Hyperion</dc:title>"));
+    assert!(!rendered.contains(r#"content="1.0""#));
+}
+
+#[test]
+fn test_opf_render_replaces_epub3_refinements() {
+    let content = r##"<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="pub-id">urn:uuid:1234</dc:identifier>
+    <dc:title id="main">The Fall of Hyperion</dc:title>
+    <dc:creator id="c1">Dan Simmons</dc:creator>
+    <meta refines="#c1" property="role" scheme="marc:relators">aut</meta>
+    <meta refines="#c1" property="file-as">Simmons, Dan</meta>
+    <meta property="dcterms:modified">2024-01-01T00:00:00Z</meta>
+  </metadata>
+</package>"##;
+    let mut metadata = opf::parse(content).unwrap();
+    metadata.authors = vec!["D. Simmons".to_string()];
+    metadata.narrators = vec!["Victor Bevine".to_string()];
+
+    let rendered = opf::render(&metadata, Some(content)).unwrap();
+    assert_eq!(opf::parse(&rendered).unwrap(), metadata);
+    assert!(!rendered.contains(r##"refines="#c1""##));
+    assert!(rendered.contains(r#"<dc:title id="main">The Fall of Hyperion</dc:title>"#));
+    assert!(rendered.contains(r#"<meta property="dcterms:modified">"#));
+    assert!(rendered.contains(r#"xmlns:opf="http://www.idpf.org/2007/opf""#));
+}
+
+#[test]
+fn test_audiobookshelf_round_trip_keeps_unknown_fields() {
+    let existing = r#"{
+        "tags": ["favourite"],
+        "chapters": [{"id": 0, "start": 0, "end": 60, "title": "One"}],
+        "title": "Old Title",
+        "series": ["Old Series #1"],
+        "explicit": false
+    }"#;
+    let parsed = audiobookshelf::parse(existing).unwrap();
+    assert_eq!(parsed.title.as_deref(), Some("Old Title"));
+    assert_eq!(parsed.series[0].name, "Old Series");
+    assert_eq!(parsed.series[0].sequence.as_deref(), Some("1"));
+
+    let metadata = full_metadata();
+    let rendered = audiobookshelf::render(&metadata, Some(existing)).unwrap();
+    assert_eq!(audiobookshelf::parse(&rendered).unwrap(), metadata);
+
+    let document: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+    assert_eq!(document["tags"][0], "favourite");
+    assert_eq!(document["chapters"][0]["title"], "One");
+    assert_eq!(document["series"][0], "Main Series #2.5");
+}
+
+#[test]
+fn test_read_text_sidecars() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(DESCRIPTION_FILE), "  A quiet story.\n").unwrap();
+    fs::write(
+        dir.path().join(READER_FILE),
+        "Nora Reader\nPaul Voice & Quinn Tone\n",
+    )
+    .unwrap();
+
+    let metadata = SidecarFormat::Text.read(dir.path()).unwrap().unwrap();
+    assert_eq!(metadata.description.as_deref(), Some("A quiet story."));
+    assert_eq!(
+        metadata.narrators,
+        ["Nora Reader", "Paul Voice", "Quinn Tone"]
+    );
+    assert!(SidecarFormat::Opf.read(dir.path()).unwrap().is_none());
+}
+
+fn tagged() -> AudioMetadata {
+    AudioMetadata {
+        title: Some("Tag Title".to_string()),
+        artist: Some("Tag Author".to_string()),
+        narrator: Some("Tag Narrator".to_string()),
+        ..AudioMetadata::default()
+    }
+}
+
+#[test]
+fn test_cache_reads_each_directory_once() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(OPF_FILE), CALIBRE_OPF).unwrap();
+    let mut cache = SidecarCache::new();
+
+    assert_eq!(cache.get(dir.path()).entries.len(), 1);
+    fs::remove_file(dir.path().join(OPF_FILE)).unwrap();
+
+    assert_eq!(cache.get(dir.path()).entries.len(), 1);
+    assert_eq!(cache.len(), 1);
+    assert!(SidecarCache::new().get(dir.path()).entries.is_empty());
+}
+
+#[test]
+fn test_merge_default_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(OPF_FILE), CALIBRE_OPF).unwrap();
+    fs::write(
+        dir.path().join(AUDIOBOOKSHELF_FILE),
+        r#"{"title": "Shelf Title", "authors": []}"#,
+    )
+    .unwrap();
+    let sidecars = Sidecars::read_dir(dir.path());
+    let mut audiobook = Audiobook::new("library", dir.path().join("book.m4b"));
+
+    let origins = merge_metadata(
+        &mut audiobook,
+        Some(&tagged()),
+        &sidecars,
+        &MetadataPrecedence::default(),
+    );
+
+    assert_eq!(audiobook.title.as_deref(), Some("Shelf Title"));
+    assert_eq!(audiobook.author.as_deref(), Some("Dan Simmons"));
+    assert_eq!(audiobook.narrator.as_deref(), Some("Victor Bevine"));
+    assert_eq!(
+        origins.get(MetadataField::Title),
+        Some(MetadataSource::Sidecar(SidecarFormat::Audiobookshelf))
+    );
+    assert_eq!(
+        origins.get(MetadataField::Author),
+        Some(MetadataSource::Sidecar(SidecarFormat::Opf))
+    );
+}
+
+#[test]
+fn test_merge_custom_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(OPF_FILE), CALIBRE_OPF).unwrap();
+    let sidecars = Sidecars::read_dir(dir.path());
+    let precedence: MetadataPrecedence = "tag,sidecar".parse().unwrap();
+
+    let mut audiobook = Audiobook::new("library", dir.path().join("book.m4b"));
+    let origins = merge_metadata(&mut audiobook, Some(&tagged()), &sidecars, &precedence);
+    assert_eq!(audiobook.title.as_deref(), Some("Tag Title"));
+    assert_eq!(
+        audiobook.description.as_deref(),
+        Some("Pilgrims & the Shrike.")
+    );
+    assert_eq!(origins.get(MetadataField::Title), Some(MetadataSource::Tag));
+
+    let mut untagged = Audiobook::new("library", dir.path().join("Chapter 01.mp3"));
+    let origins = merge_metadata(
+        &mut untagged,
+        None,
+        &Sidecars::default(),
+        &MetadataPrecedence::default(),
+    );
+    assert_eq!(untagged.title.as_deref(), Some("Chapter 01"));
+    assert_eq!(
+        origins.get(MetadataField::Title),
+        Some(MetadataSource::Filename)
+    );
+    assert_eq!(origins.get(MetadataField::Author), None);
+
+    assert!("tag,tag".parse::<MetadataPrecedence>().is_err());
+    assert!("tag,network".parse::<MetadataPrecedence>().is_err());
+}
+
+#[test]
+fn test_write_sidecars_keeps_other_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(OPF_FILE), CALIBRE_OPF).unwrap();
+    let mut audiobook = Audiobook::new("library", dir.path().join("book.m4b"));
+    audiobook.title = Some("Hyperion (Unabridged)".to_string());
+    audiobook.author = Some("Dan Simmons".to_string());
+    audiobook.narrator = Some("Victor Bevine; Allyson Johnson".to_string());
+
+    let written = write_sidecars(
+        &audiobook,
+        &[SidecarFormat::Opf, SidecarFormat::Audiobookshelf],
+    )
+    .unwrap();
+    assert_eq!(written.len(), 2);
+
+    let opf = SidecarFormat::Opf.read(dir.path()).unwrap().unwrap();
+    assert_eq!(opf.title.as_deref(), Some("Hyperion (Unabridged)"));
+    assert_eq!(opf.narrators, ["Victor Bevine", "Allyson Johnson"]);
+    assert_eq!(opf.series[0].name, "Hyperion Cantos");
+    assert_eq!(opf.isbn.as_deref(), Some("9780553283686"));
+
+    let sidecars = Sidecars::read_dir(dir.path());
+    let mut rescanned = Audiobook::new("library", dir.path().join("book.m4b"));
+    merge_metadata(
+        &mut rescanned,
+        None,
+        &sidecars,
+        &MetadataPrecedence::default(),
+    );
+    assert_eq!(rescanned.title, audiobook.title);
+    assert_eq!(rescanned.narrator, audiobook.narrator);
+}
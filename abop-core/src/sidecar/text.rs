@@ -0,0 +1,47 @@
+//! Plain text sidecars: `desc.txt` holds the description and `reader.txt`
+//! the narrators
+
+use std::path::{Path, PathBuf};
+
+use super::{
+    DESCRIPTION_FILE, READER_FILE, SidecarMetadata, non_empty, read_optional, split_people,
+};
+use crate::error::Result;
+
+/// Read `desc.txt` and `reader.txt` if either exists
+pub(super) fn read(dir: &Path) -> Result<Option<SidecarMetadata>> {
+    let description = read_optional(&dir.join(DESCRIPTION_FILE))?;
+    let reader = read_optional(&dir.join(READER_FILE))?;
+    if description.is_none() && reader.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(SidecarMetadata {
+        description: description.as_deref().and_then(non_empty),
+        narrators: reader
+            .as_deref()
+            .map(|reader| {
+                reader
+                    .lines()
+                    .flat_map(|line| split_people(Some(line)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..SidecarMetadata::default()
+    }))
+}
+
+/// Write whichever of `desc.txt` and `reader.txt` has content
+pub(super) fn write(dir: &Path, metadata: &SidecarMetadata) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    if let Some(description) = &metadata.description {
+        let path = dir.join(DESCRIPTION_FILE);
+        std::fs::write(&path, format!("{description}\n"))?;
+        written.push(path);
+    }
+    if !metadata.narrators.is_empty() {
+        let path = dir.join(READER_FILE);
+        std::fs::write(&path, format!("{}\n", metadata.narrators.join("\n")))?;
+        written.push(path);
+    }
+    Ok(written)
+}
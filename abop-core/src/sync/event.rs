@@ -0,0 +1,137 @@
+//! Append-only progress event files
+//!
+//! Every device appends to its own file in the sync directory, so a file
+//! synchronizer never has to merge concurrent writes to one file. A device
+//! only ever reads the files of other devices. Conflict copies made by the
+//! synchronizer are read like any other file; duplicate events are dropped
+//! by ID.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, Result};
+
+/// File name prefix of event files
+const FILE_PREFIX: &str = "progress-";
+/// File name extension of event files
+const FILE_EXTENSION: &str = "jsonl";
+
+/// Progress of one book on one device at one point in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressEvent {
+    /// Unique identifier of the event
+    pub event_id: String,
+    /// Device that recorded the progress
+    pub device_id: String,
+    /// Name of the listener profile, which is the same on every device
+    pub profile: String,
+    /// Content hash of the audiobook file
+    pub content_hash: String,
+    /// Title of the audiobook, for reports only
+    pub title: Option<String>,
+    /// Playback position in seconds
+    pub position_seconds: u64,
+    /// Whether the audiobook was finished
+    pub completed: bool,
+    /// When the progress was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Event the device had merged for this book when recording this one
+    pub based_on: Option<String>,
+}
+
+impl ProgressEvent {
+    /// Whether two events describe the same listening state
+    #[must_use]
+    pub fn same_state(&self, position_seconds: u64, completed: bool) -> bool {
+        self.position_seconds == position_seconds && self.completed == completed
+    }
+}
+
+/// Event file a device appends to
+#[must_use]
+pub fn device_file(directory: &Path, device_id: &str) -> PathBuf {
+    directory.join(format!("{FILE_PREFIX}{device_id}.{FILE_EXTENSION}"))
+}
+
+/// Read the events of every device, skipping lines that cannot be parsed
+///
+/// A file that is still being synchronized may end in a partial line, so
+/// unreadable lines are reported as warnings rather than failing the sync.
+///
+/// # Errors
+///
+/// Returns [`AppError::Io`] if the directory or an event file cannot be read.
+pub fn read_events(directory: &Path) -> Result<(Vec<ProgressEvent>, Vec<String>)> {
+    let entries = fs::read_dir(directory)
+        .map_err(|e| AppError::Io(format!("Cannot read {}: {e}", directory.display())))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == FILE_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect();
+    files.sort();
+
+    let mut seen = HashSet::new();
+    let mut events = Vec::new();
+    let mut warnings = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .map_err(|e| AppError::Io(format!("Cannot read {}: {e}", file.display())))?;
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ProgressEvent>(line) {
+                Ok(event) => {
+                    if seen.insert(event.event_id.clone()) {
+                        events.push(event);
+                    }
+                }
+                Err(e) => warnings.push(format!(
+                    "Skipped line {} of {}: {e}",
+                    index + 1,
+                    file.display()
+                )),
+            }
+        }
+    }
+    Ok((events, warnings))
+}
+
+/// Append events to the file of a device
+///
+/// # Errors
+///
+/// Returns [`AppError::Io`] if the file cannot be written.
+pub fn append_events(directory: &Path, device_id: &str, events: &[ProgressEvent]) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let path = device_file(directory, device_id);
+    let write_error =
+        |e: std::io::Error| AppError::Io(format!("Cannot write {}: {e}", path.display()));
+    let mut lines = String::new();
+    for event in events {
+        let line = serde_json::to_string(event)
+            .map_err(|e| AppError::Parse(format!("Cannot serialize progress event: {e}")))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(write_error)?;
+    // One write keeps a crash from leaving half of a batch behind
+    file.write_all(lines.as_bytes()).map_err(write_error)?;
+    file.sync_all().map_err(write_error)
+}
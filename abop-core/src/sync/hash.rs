@@ -0,0 +1,77 @@
+//! Content hashes that identify an audiobook file on any device
+//!
+//! Paths differ between machines, so sync matches books by content instead.
+//! Hashing multi-gigabyte files in full would make every sync slow, so large
+//! files are identified by their size plus their first and last mebibyte,
+//! which differ between any two real recordings.
+
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::error::{AppError, Result};
+
+/// Bytes hashed from each end of a large file
+const SAMPLE_BYTES: u64 = 1024 * 1024;
+
+/// Hash the content of an audio file as lowercase hex
+///
+/// # Errors
+///
+/// Returns [`AppError::Io`] if the file cannot be opened or read.
+pub fn content_hash(path: &Path) -> Result<String> {
+    let read_error =
+        |e: std::io::Error| AppError::Io(format!("Cannot hash {}: {e}", path.display()));
+    let mut file = File::open(path).map_err(read_error)?;
+    let size = file.metadata().map_err(read_error)?.len();
+
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+    let mut buffer = Vec::new();
+    if size <= 2 * SAMPLE_BYTES {
+        file.read_to_end(&mut buffer).map_err(read_error)?;
+        hasher.update(&buffer);
+    } else {
+        (&mut file)
+            .take(SAMPLE_BYTES)
+            .read_to_end(&mut buffer)
+            .map_err(read_error)?;
+        hasher.update(&buffer);
+        buffer.clear();
+        file.seek(SeekFrom::End(
+            -i64::try_from(SAMPLE_BYTES).unwrap_or(i64::MAX),
+        ))
+        .map_err(read_error)?;
+        file.read_to_end(&mut buffer).map_err(read_error)?;
+        hasher.update(&buffer);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_ignores_path_but_not_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            content_hash(&path).unwrap()
+        };
+
+        let small = write("a.mp3", b"chapter one");
+        assert_eq!(small, write("copy of a.mp3", b"chapter one"));
+        assert_ne!(small, write("b.mp3", b"chapter two"));
+        assert_eq!(small.len(), 64);
+
+        // Large files are sampled at both ends
+        let size = usize::try_from(3 * SAMPLE_BYTES).unwrap();
+        let mut large = vec![0_u8; size];
+        let original = write("large.m4b", &large);
+        large[size - 1] = 1;
+        assert_ne!(original, write("large-tail.m4b", &large));
+    }
+}
@@ -0,0 +1,411 @@
+//! Progress sync between devices through a shared folder
+//!
+//! Listening positions follow a listener from machine to machine without a
+//! server: each device appends its progress changes as events to its own file
+//! in a directory shared by a file synchronizer such as Syncthing, and merges
+//! the events of all devices back into its database. Books are matched by
+//! [`content_hash`], as their paths differ between machines, and listeners by
+//! profile name.
+//!
+//! Each event records which event its device had last merged for the book.
+//! Two devices that changed a book without seeing each other's change
+//! are in conflict; the [`SyncPolicy`] picks a winner and the conflict is
+//! reported so the listener can check it.
+
+pub mod event;
+pub mod hash;
+
+pub use event::{ProgressEvent, append_events, device_file, read_events};
+pub use hash::content_hash;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+
+use crate::db::Database;
+use crate::error::{AppError, Result};
+use crate::models::{Audiobook, DEFAULT_PROFILE_ID, Progress};
+
+/// Default position difference below which devices are not in conflict
+pub const DEFAULT_CONFLICT_TOLERANCE_SECONDS: u64 = 30;
+
+/// How the progress of a book is chosen when devices disagree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncPolicy {
+    /// The most recently recorded progress wins
+    #[default]
+    LastWriterWins,
+    /// The furthest position wins, and finished beats unfinished
+    FurthestPosition,
+}
+
+impl SyncPolicy {
+    /// Pick the winning event, breaking ties by device and event ID so every
+    /// device picks the same one
+    #[must_use]
+    pub fn winner<'a>(
+        self,
+        events: impl IntoIterator<Item = &'a ProgressEvent>,
+    ) -> Option<&'a ProgressEvent> {
+        let recency = |event: &'a ProgressEvent| {
+            (
+                event.timestamp,
+                event.device_id.as_str(),
+                event.event_id.as_str(),
+            )
+        };
+        match self {
+            Self::LastWriterWins => events.into_iter().max_by_key(|event| recency(event)),
+            Self::FurthestPosition => events
+                .into_iter()
+                .max_by_key(|event| (event.completed, event.position_seconds, recency(event))),
+        }
+    }
+}
+
+impl fmt::Display for SyncPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::LastWriterWins => "last-writer-wins",
+            Self::FurthestPosition => "furthest-position",
+        })
+    }
+}
+
+impl FromStr for SyncPolicy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "last-writer-wins" | "lww" | "latest" => Ok(Self::LastWriterWins),
+            "furthest-position" | "furthest" => Ok(Self::FurthestPosition),
+            other => Err(AppError::Parse(format!(
+                "Unknown sync policy '{other}', expected last-writer-wins or furthest-position"
+            ))),
+        }
+    }
+}
+
+/// Options of a sync run
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Shared directory holding the event files of all devices
+    pub directory: PathBuf,
+    /// Listener profile whose progress is synced
+    pub profile_id: String,
+    /// How the progress of a book is chosen when devices disagree
+    pub policy: SyncPolicy,
+    /// Position difference in seconds below which devices are not in conflict
+    pub conflict_tolerance_seconds: u64,
+}
+
+impl SyncOptions {
+    /// Sync the default profile through a directory with the default policy
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            profile_id: DEFAULT_PROFILE_ID.to_string(),
+            policy: SyncPolicy::default(),
+            conflict_tolerance_seconds: DEFAULT_CONFLICT_TOLERANCE_SECONDS,
+        }
+    }
+}
+
+/// Devices that changed a book without seeing each other's change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    /// Content hash of the audiobook
+    pub content_hash: String,
+    /// Title of the audiobook, if any device knew it
+    pub title: Option<String>,
+    /// Event chosen by the policy
+    pub chosen: ProgressEvent,
+    /// Latest events of other devices that disagree with the chosen one
+    pub competing: Vec<ProgressEvent>,
+}
+
+/// Outcome of a sync run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// ID this device writes its events under
+    pub device_id: String,
+    /// Name of the synced profile
+    pub profile: String,
+    /// Policy used to resolve disagreements
+    pub policy: SyncPolicy,
+    /// Number of local changes written to the sync directory
+    pub exported: usize,
+    /// Number of audiobooks whose local progress was updated
+    pub applied: usize,
+    /// Number of books with events that are not in the local library
+    pub unmatched: usize,
+    /// Books whose devices disagreed
+    pub conflicts: Vec<SyncConflict>,
+    /// Files or events that could not be used
+    pub warnings: Vec<String>,
+}
+
+/// Exports local progress changes and merges the changes of other devices
+#[derive(Debug, Clone)]
+pub struct ProgressSync {
+    options: SyncOptions,
+}
+
+impl ProgressSync {
+    /// Create a sync run with the given options
+    #[must_use]
+    pub const fn new(options: SyncOptions) -> Self {
+        Self { options }
+    }
+
+    /// Export this device's changes, then merge the events of every device
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile does not exist, the sync directory
+    /// cannot be read or written, or a database operation fails.
+    pub fn run(&self, db: &Database) -> Result<SyncReport> {
+        let options = &self.options;
+        let profile = db
+            .profile_repository()
+            .find_by_id(&options.profile_id)?
+            .ok_or_else(|| {
+                AppError::ValidationFailed(format!(
+                    "Profile does not exist: {}",
+                    options.profile_id
+                ))
+            })?;
+        let sync_repo = db.sync_repository();
+        let device_id = sync_repo.device_id()?;
+        let merged_heads = sync_repo.merged_heads(&profile.id)?;
+        std::fs::create_dir_all(&options.directory).map_err(|e| {
+            AppError::Io(format!(
+                "Cannot create {}: {e}",
+                options.directory.display()
+            ))
+        })?;
+
+        let (events, mut warnings) = read_events(&options.directory)?;
+        let mut by_hash: HashMap<String, Vec<ProgressEvent>> = HashMap::new();
+        for event in events {
+            if event.profile.eq_ignore_ascii_case(&profile.name) {
+                by_hash
+                    .entry(event.content_hash.clone())
+                    .or_default()
+                    .push(event);
+            }
+        }
+
+        let audiobooks = db.audiobook_repository().find_all()?;
+        let hashes = hash_audiobooks(db, &audiobooks, &mut warnings)?;
+        let titles: HashMap<&str, Option<&String>> = audiobooks
+            .iter()
+            .map(|audiobook| (audiobook.id.as_str(), audiobook.title.as_ref()))
+            .collect();
+
+        // Export local changes that no event describes yet
+        let progress_repo = db.progress_repository();
+        let mut local = progress_repo.find_all_for_profile(&profile.id)?;
+        local.sort_by_key(|progress| std::cmp::Reverse(progress.updated_at));
+        let mut exported = Vec::new();
+        let mut exported_hashes = HashSet::new();
+        for progress in &local {
+            let Some(hash) = hashes.get(&progress.audiobook_id) else {
+                continue;
+            };
+            // Copies of one book share a hash; the most recent progress speaks for all
+            if !exported_hashes.insert(hash.clone()) {
+                continue;
+            }
+            let group = by_hash.entry(hash.clone()).or_default();
+            // Exported and merged progress keeps the time of its event
+            if group.iter().any(|event| {
+                event.timestamp == progress.updated_at
+                    && event.same_state(progress.position_seconds, progress.completed)
+            }) {
+                continue;
+            }
+            let event = ProgressEvent {
+                event_id: uuid::Uuid::new_v4().to_string(),
+                device_id: device_id.clone(),
+                profile: profile.name.clone(),
+                content_hash: hash.clone(),
+                title: titles
+                    .get(progress.audiobook_id.as_str())
+                    .copied()
+                    .flatten()
+                    .cloned(),
+                position_seconds: progress.position_seconds,
+                completed: progress.completed,
+                timestamp: progress.updated_at,
+                based_on: merged_heads.get(hash).cloned(),
+            };
+            group.push(event.clone());
+            exported.push(event);
+        }
+        append_events(&options.directory, &device_id, &exported)?;
+
+        // Merge the events of every device into the local progress
+        let mut books_by_hash: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (audiobook_id, hash) in &hashes {
+            books_by_hash
+                .entry(hash.as_str())
+                .or_default()
+                .push(audiobook_id.as_str());
+        }
+        let local: HashMap<&str, &Progress> = local
+            .iter()
+            .map(|progress| (progress.audiobook_id.as_str(), progress))
+            .collect();
+        let mut applied = 0;
+        let mut unmatched = 0;
+        let mut conflicts = Vec::new();
+        let mut heads = Vec::new();
+        let mut groups: Vec<_> = by_hash.iter().collect();
+        groups.sort_by(|a, b| a.0.cmp(b.0));
+        for (hash, group) in groups {
+            let Some(winner) = options.policy.winner(group) else {
+                continue;
+            };
+            let competing = competing_events(group, winner, options.conflict_tolerance_seconds);
+            if !competing.is_empty() {
+                conflicts.push(SyncConflict {
+                    content_hash: hash.clone(),
+                    title: group.iter().find_map(|event| event.title.clone()),
+                    chosen: winner.clone(),
+                    competing,
+                });
+            }
+
+            let Some(audiobook_ids) = books_by_hash.get(hash.as_str()) else {
+                unmatched += 1;
+                continue;
+            };
+            heads.push((hash.clone(), winner.event_id.clone()));
+            for audiobook_id in audiobook_ids {
+                let current = local.get(audiobook_id).copied();
+                if current.is_some_and(|p| winner.same_state(p.position_seconds, p.completed)) {
+                    continue;
+                }
+                let mut progress = current
+                    .cloned()
+                    .unwrap_or_else(|| Progress::new(audiobook_id, 0).with_profile(&profile.id));
+                progress.position_seconds = winner.position_seconds;
+                progress.completed = winner.completed;
+                progress.last_played = Some(winner.timestamp);
+                // Keeping the event's time stops the next run from exporting it again
+                progress.updated_at = winner.timestamp;
+                progress_repo.upsert(&progress)?;
+                applied += 1;
+            }
+        }
+
+        sync_repo.set_merged_heads(&profile.id, &heads)?;
+
+        log::info!(
+            "Synced profile {} as device {device_id}: {} exported, {applied} applied, {} conflicts",
+            profile.name,
+            exported.len(),
+            conflicts.len()
+        );
+        Ok(SyncReport {
+            device_id,
+            profile: profile.name,
+            policy: options.policy,
+            exported: exported.len(),
+            applied,
+            unmatched,
+            conflicts,
+            warnings,
+        })
+    }
+}
+
+/// Content hash of every audiobook whose file exists, by audiobook ID
+fn hash_audiobooks(
+    db: &Database,
+    audiobooks: &[Audiobook],
+    warnings: &mut Vec<String>,
+) -> Result<HashMap<String, String>> {
+    let repo = db.sync_repository();
+    let mut hashes = HashMap::new();
+    for audiobook in audiobooks {
+        let Some((size, modified)) = file_stamp(&audiobook.path) else {
+            log::debug!("Not syncing missing file {}", audiobook.path.display());
+            continue;
+        };
+        let hash = match repo.cached_hash(&audiobook.id, size, modified)? {
+            Some(hash) => hash,
+            None => match content_hash(&audiobook.path) {
+                Ok(hash) => {
+                    repo.store_hash(&audiobook.id, &hash, size, modified)?;
+                    hash
+                }
+                Err(e) => {
+                    warnings.push(e.to_string());
+                    continue;
+                }
+            },
+        };
+        hashes.insert(audiobook.id.clone(), hash);
+    }
+    Ok(hashes)
+}
+
+/// Size and modification time of a file, used to reuse its cached hash
+fn file_stamp(path: &Path) -> Option<(u64, i64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |age| i64::try_from(age.as_secs()).unwrap_or(i64::MAX));
+    Some((metadata.len(), modified))
+}
+
+/// Latest events of other devices that the winner never saw and that disagree with it
+fn competing_events(
+    group: &[ProgressEvent],
+    winner: &ProgressEvent,
+    tolerance_seconds: u64,
+) -> Vec<ProgressEvent> {
+    let by_id: HashMap<&str, &ProgressEvent> = group
+        .iter()
+        .map(|event| (event.event_id.as_str(), event))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut ancestor = winner.based_on.as_deref();
+    while let Some(id) = ancestor {
+        if !seen.insert(id) {
+            break;
+        }
+        ancestor = by_id.get(id).and_then(|event| event.based_on.as_deref());
+    }
+
+    let mut heads: HashMap<&str, &ProgressEvent> = HashMap::new();
+    for event in group {
+        let head = heads.entry(event.device_id.as_str()).or_insert(event);
+        if (event.timestamp, &event.event_id) > (head.timestamp, &head.event_id) {
+            *head = event;
+        }
+    }
+    let mut competing: Vec<ProgressEvent> = heads
+        .into_values()
+        .filter(|head| head.event_id != winner.event_id && !seen.contains(head.event_id.as_str()))
+        .filter(|head| {
+            head.completed != winner.completed
+                || head.position_seconds.abs_diff(winner.position_seconds) > tolerance_seconds
+        })
+        .cloned()
+        .collect();
+    competing.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+    competing
+}
+
+#[cfg(test)]
+mod tests;
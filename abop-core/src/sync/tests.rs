@@ -0,0 +1,166 @@
+//! Tests for progress sync between devices
+
+use super::*;
+use chrono::{DateTime, Duration, Utc};
+use tempfile::TempDir;
+
+/// A device with its own database and a copy of the same book at its own path
+struct Device {
+    db: Database,
+    audiobook: Audiobook,
+}
+
+impl Device {
+    fn new(root: &TempDir, name: &str) -> Self {
+        let library_path = root.path().join(name);
+        std::fs::create_dir_all(&library_path).unwrap();
+        let path = library_path.join(format!("{name} copy.m4b"));
+        std::fs::write(&path, b"the same recording on every device").unwrap();
+
+        let db = Database::open(root.path().join(format!("{name}.db"))).unwrap();
+        let library = db.libraries().create(name, library_path).unwrap();
+        let mut audiobook = Audiobook::new(&library.id, &path);
+        audiobook.title = Some("Dune".to_string());
+        db.add_audiobook(&audiobook).unwrap();
+        Self { db, audiobook }
+    }
+
+    fn listen(&self, position_seconds: u64, at: DateTime<Utc>) {
+        let mut progress = Progress::new(&self.audiobook.id, position_seconds);
+        progress.updated_at = at;
+        self.db.progress_repository().upsert(&progress).unwrap();
+    }
+
+    fn position(&self) -> Option<u64> {
+        self.db
+            .progress_repository()
+            .find_by_audiobook(&self.audiobook.id)
+            .unwrap()
+            .map(|progress| progress.position_seconds)
+    }
+
+    fn sync(&self, directory: &Path, policy: SyncPolicy) -> SyncReport {
+        let options = SyncOptions {
+            policy,
+            ..SyncOptions::new(directory)
+        };
+        ProgressSync::new(options).run(&self.db).unwrap()
+    }
+}
+
+fn event(device_id: &str, position_seconds: u64, minutes: i64) -> ProgressEvent {
+    ProgressEvent {
+        event_id: format!("{device_id}-{minutes}"),
+        device_id: device_id.to_string(),
+        profile: "Default".to_string(),
+        content_hash: "hash".to_string(),
+        title: None,
+        position_seconds,
+        completed: false,
+        timestamp: DateTime::UNIX_EPOCH + Duration::minutes(minutes),
+        based_on: None,
+    }
+}
+
+#[test]
+fn test_policies_pick_the_same_winner_everywhere() {
+    let events = [event("laptop", 3000, 10), event("phone", 1200, 20)];
+    let latest = SyncPolicy::LastWriterWins.winner(&events).unwrap();
+    assert_eq!(latest.device_id, "phone");
+    let furthest = SyncPolicy::FurthestPosition.winner(&events).unwrap();
+    assert_eq!(furthest.device_id, "laptop");
+
+    let mut finished = event("tablet", 0, 5);
+    finished.completed = true;
+    let events = [events[0].clone(), finished];
+    let furthest = SyncPolicy::FurthestPosition.winner(&events).unwrap();
+    assert_eq!(furthest.device_id, "tablet");
+
+    assert_eq!(
+        "furthest".parse::<SyncPolicy>().unwrap(),
+        SyncPolicy::FurthestPosition
+    );
+    assert!("closest".parse::<SyncPolicy>().is_err());
+}
+
+#[test]
+fn test_progress_follows_the_listener() {
+    let root = tempfile::tempdir().unwrap();
+    let shared = root.path().join("shared");
+    let laptop = Device::new(&root, "laptop");
+    let phone = Device::new(&root, "phone");
+    let start = Utc::now() - Duration::hours(2);
+
+    laptop.listen(600, start);
+    let report = laptop.sync(&shared, SyncPolicy::LastWriterWins);
+    assert_eq!((report.exported, report.applied), (1, 0));
+    // Nothing new to write on a second run
+    assert_eq!(laptop.sync(&shared, SyncPolicy::LastWriterWins).exported, 0);
+
+    let report = phone.sync(&shared, SyncPolicy::LastWriterWins);
+    assert_eq!((report.exported, report.applied), (0, 1));
+    assert_eq!(phone.position(), Some(600));
+    assert!(report.conflicts.is_empty());
+
+    phone.listen(900, start + Duration::minutes(30));
+    assert_eq!(phone.sync(&shared, SyncPolicy::LastWriterWins).exported, 1);
+    let report = laptop.sync(&shared, SyncPolicy::LastWriterWins);
+    assert_eq!((report.exported, report.applied), (0, 1));
+    assert_eq!(laptop.position(), Some(900));
+    assert!(report.conflicts.is_empty());
+    assert_ne!(
+        report.device_id,
+        phone.sync(&shared, SyncPolicy::LastWriterWins).device_id
+    );
+}
+
+#[test]
+fn test_concurrent_changes_are_reported() {
+    let root = tempfile::tempdir().unwrap();
+    let shared = root.path().join("shared");
+    let laptop = Device::new(&root, "laptop");
+    let phone = Device::new(&root, "phone");
+    let start = Utc::now() - Duration::hours(2);
+
+    laptop.listen(600, start);
+    laptop.sync(&shared, SyncPolicy::LastWriterWins);
+    phone.sync(&shared, SyncPolicy::LastWriterWins);
+
+    // Both listen before syncing again
+    laptop.listen(5000, start + Duration::minutes(10));
+    phone.listen(1200, start + Duration::minutes(20));
+    laptop.sync(&shared, SyncPolicy::LastWriterWins);
+
+    let report = phone.sync(&shared, SyncPolicy::LastWriterWins);
+    assert_eq!(report.conflicts.len(), 1);
+    let conflict = &report.conflicts[0];
+    assert_eq!(conflict.title.as_deref(), Some("Dune"));
+    assert_eq!(conflict.chosen.position_seconds, 1200);
+    assert_eq!(conflict.competing[0].position_seconds, 5000);
+    assert_eq!(phone.position(), Some(1200));
+
+    let report = laptop.sync(&shared, SyncPolicy::FurthestPosition);
+    assert_eq!(report.conflicts[0].chosen.position_seconds, 5000);
+    assert_eq!(laptop.position(), Some(5000));
+}
+
+#[test]
+fn test_unknown_books_and_damaged_lines() {
+    let root = tempfile::tempdir().unwrap();
+    let shared = root.path().join("shared");
+    std::fs::create_dir_all(&shared).unwrap();
+    append_events(&shared, "tablet", &[event("tablet", 60, 1)]).unwrap();
+    let file = device_file(&shared, "tablet");
+    let mut content = std::fs::read_to_string(&file).unwrap();
+    content.push_str("{\"event_id\": \"trunc");
+    std::fs::write(&file, content).unwrap();
+    // Files of other tools in the directory are ignored
+    std::fs::write(shared.join("notes.txt"), "not an event").unwrap();
+
+    let laptop = Device::new(&root, "laptop");
+    let report = laptop.sync(&shared, SyncPolicy::LastWriterWins);
+    assert_eq!(report.unmatched, 1);
+    assert_eq!(report.applied, 0);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(laptop.position(), None);
+}
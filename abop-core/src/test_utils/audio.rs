@@ -6,7 +6,9 @@
 
 use crate::audio::AudioBuffer;
 use crate::audio::SampleFormat;
-use crate::utils::casting::domain::audio::{safe_duration_to_samples, safe_samples_to_duration};
+use crate::utils::casting::domain::audio::{
+    safe_duration_to_samples, safe_samples_to_duration, safe_usize_to_f64_audio,
+};
 
 /// Creates a test audio buffer with a sine wave
 ///
@@ -130,6 +132,45 @@ pub fn create_stereo_test_buffer(sample_rate: u32, duration_secs: f32) -> AudioB
     }
 }
 
+/// Creates a buffer with a sine wave at an arbitrary frequency
+///
+/// Every channel carries the same signal. Phase is computed in `f64` so long
+/// buffers stay accurate enough for filter response measurements.
+#[must_use]
+pub fn create_sine_buffer(
+    sample_rate: u32,
+    channels: u16,
+    frequency: f32,
+    duration_secs: f32,
+    amplitude: f32,
+) -> AudioBuffer<f32> {
+    let num_samples = safe_duration_to_samples(duration_secs, sample_rate).unwrap_or(0);
+    let step = std::f64::consts::TAU * f64::from(frequency) / f64::from(sample_rate);
+    let mut data = Vec::with_capacity(num_samples * usize::from(channels));
+    for i in 0..num_samples {
+        #[allow(clippy::cast_possible_truncation)]
+        let sample = ((step * safe_usize_to_f64_audio(i)).sin() * f64::from(amplitude)) as f32;
+        data.extend(std::iter::repeat_n(sample, usize::from(channels)));
+    }
+
+    AudioBuffer {
+        data,
+        format: SampleFormat::F32,
+        sample_rate,
+        channels,
+    }
+}
+
+/// RMS level of the second half of a signal, after filter transients settle
+#[must_use]
+pub fn settled_rms(data: &[f32]) -> f32 {
+    let tail = &data[data.len() / 2..];
+    let power: f64 = tail.iter().map(|s| f64::from(*s).powi(2)).sum();
+    #[allow(clippy::cast_possible_truncation)]
+    let rms = (power / safe_usize_to_f64_audio(tail.len().max(1))).sqrt() as f32;
+    rms
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
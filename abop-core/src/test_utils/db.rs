@@ -0,0 +1,78 @@
+//! Database fixtures for repository tests
+//!
+//! Repository tests need a migrated database file with a library to attach
+//! audiobooks to. [`TestDatabase`] sets that up in a temporary directory that
+//! is removed when the fixture is dropped.
+
+use std::sync::Arc;
+
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+use super::data::TestDataFactory;
+use crate::db::connection::EnhancedConnection;
+use crate::db::repositories::{AudiobookRepository, LibraryRepository};
+
+/// Migrated database in a temporary directory with one library
+pub struct TestDatabase {
+    /// Directory holding the database file, also the library path
+    pub dir: TempDir,
+    /// Connection to the database
+    pub connection: Arc<EnhancedConnection>,
+    /// ID of the library created for the test
+    pub library_id: String,
+}
+
+impl TestDatabase {
+    /// Creates the database, runs all migrations and adds a library
+    ///
+    /// # Panics
+    ///
+    /// Panics if the database cannot be created or migrated
+    #[must_use]
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db_path = dir.path().join("test.db");
+        let mut conn = Connection::open(&db_path).expect("Failed to open database");
+        crate::db::run_migrations(&mut conn).expect("Failed to run migrations");
+
+        let connection = Arc::new(EnhancedConnection::new(&db_path));
+        connection.connect().expect("Failed to connect");
+
+        let library = LibraryRepository::new(connection.clone())
+            .create("Test Library", dir.path().to_path_buf())
+            .expect("Failed to create library");
+
+        Self {
+            dir,
+            connection,
+            library_id: library.id,
+        }
+    }
+
+    /// Adds the audiobook `book-1` at `book.mp3` in the library and returns its ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if the audiobook cannot be inserted
+    #[must_use]
+    pub fn add_audiobook(&self) -> String {
+        let audiobook = TestDataFactory::audiobook_with_path(
+            "book-1",
+            &self.library_id,
+            &self.dir.path().join("book.mp3"),
+            "Book",
+            "Author",
+        );
+        AudiobookRepository::new(self.connection.clone())
+            .upsert(&audiobook)
+            .expect("Failed to insert audiobook");
+        audiobook.id
+    }
+}
+
+impl Default for TestDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
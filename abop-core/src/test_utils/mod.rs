@@ -5,7 +5,9 @@
 
 pub mod audio;
 pub mod data;
+pub mod db;
 
 // Re-export commonly used test utilities
 pub use audio::*;
 pub use data::TestDataFactory;
+pub use db::TestDatabase;
@@ -16,6 +16,7 @@ pub mod enhanced;
 pub mod path;
 pub mod time;
 pub mod timer;
+pub(crate) mod xml;
 
 // Re-export commonly used utilities (specific items)
 pub use casting::{
@@ -25,6 +26,7 @@ pub use casting::{
 pub use enhanced::{audio, database, file, ui};
 pub use path::{
     extension_matches, normalize_path_for_comparison, paths_equal, paths_equal_case_insensitive,
+    sanitize_file_name,
 };
 pub use time::{TimeFormat, format_duration, format_seconds};
 pub use timer::Timer;
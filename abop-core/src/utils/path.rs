@@ -125,6 +125,41 @@ pub fn extension_matches(path: &Path, expected_ext: &str) -> bool {
     get_extension_case_insensitive(path).is_some_and(|ext| ext == expected_ext.to_lowercase())
 }
 
+/// Characters that are not allowed in file names on at least one platform
+const RESERVED_FILE_NAME_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Longest file name component produced by [`sanitize_file_name`], in bytes
+pub const MAX_FILE_NAME_BYTES: usize = 240;
+
+/// Make a string safe to use as a single file or directory name
+///
+/// Path separators, characters reserved on Windows and control characters
+/// become `_`. Surrounding whitespace and trailing dots (which Windows drops)
+/// are trimmed and the result is cut to [`MAX_FILE_NAME_BYTES`] on a character
+/// boundary. The result may be empty, and never is `.` or `..`.
+#[must_use]
+pub fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || RESERVED_FILE_NAME_CHARS.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let mut sanitized = replaced.trim().trim_end_matches('.').trim_end();
+    if sanitized.len() > MAX_FILE_NAME_BYTES {
+        let mut end = MAX_FILE_NAME_BYTES;
+        while !sanitized.is_char_boundary(end) {
+            end -= 1;
+        }
+        sanitized = sanitized[..end].trim_end().trim_end_matches('.');
+    }
+    sanitized.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +192,15 @@ mod tests {
         let normalized = normalize_path(path);
         assert!(!normalized.to_string_lossy().contains(".."));
     }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("A/B: C?"), "A_B_ C_");
+        assert_eq!(sanitize_file_name("  Title\twith tab. "), "Title_with tab");
+        assert_eq!(sanitize_file_name(".."), "");
+        let long = "é".repeat(200);
+        let sanitized = sanitize_file_name(&long);
+        assert!(sanitized.len() <= MAX_FILE_NAME_BYTES);
+        assert!(sanitized.chars().all(|c| c == 'é'));
+    }
 }
@@ -0,0 +1,89 @@
+//! Minimal XML writing for generated documents such as feeds and OPF files
+
+use std::fmt::Write as _;
+
+/// Minimal indenting XML writer
+pub(crate) struct XmlWriter {
+    out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    /// Start a document with the XML declaration
+    pub(crate) fn new() -> Self {
+        Self {
+            out: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+            depth: 0,
+        }
+    }
+
+    /// Start a fragment of elements nested `depth` levels deep
+    pub(crate) fn fragment(depth: usize) -> Self {
+        Self {
+            out: String::new(),
+            depth,
+        }
+    }
+
+    fn start_tag(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        let _ = write!(self.out, "{:indent$}<{name}", "", indent = self.depth * 2);
+        for (key, value) in attributes {
+            let _ = write!(self.out, " {key}=\"{}\"", escape(value));
+        }
+    }
+
+    /// Open an element that will contain other elements
+    pub(crate) fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes);
+        self.out.push_str(">\n");
+        self.depth += 1;
+    }
+
+    /// Close the element opened last
+    pub(crate) fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        let _ = writeln!(self.out, "{:indent$}</{name}>", "", indent = self.depth * 2);
+    }
+
+    /// Write an element without content
+    pub(crate) fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes);
+        self.out.push_str("/>\n");
+    }
+
+    /// Write an element with attributes and text content
+    pub(crate) fn element(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.start_tag(name, attributes);
+        let _ = writeln!(self.out, ">{}</{name}>", escape(text));
+    }
+
+    /// Write an element with text content
+    pub(crate) fn text(&mut self, name: &str, text: &str) {
+        self.element(name, &[], text);
+    }
+
+    /// The finished document
+    pub(crate) fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape text for element content and attribute values
+///
+/// Characters XML 1.0 does not allow at all are dropped.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' || matches!(c, '\u{FFFE}' | '\u{FFFF}') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
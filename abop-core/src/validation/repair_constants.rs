@@ -11,6 +11,8 @@ pub mod error_patterns {
     pub const NO_LONGER_EXISTS: &str = "no longer exists";
     pub const TOO_SMALL: &str = "too small";
     pub const DUPLICATE: &str = "duplicate";
+    pub const CORRUPT_AUDIO: &str = "corrupt audio";
+    pub const DURATION_MISMATCH: &str = "duration mismatch";
 }
 
 /// Default values for repair operations
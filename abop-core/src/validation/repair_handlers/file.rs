@@ -12,14 +12,38 @@ pub struct FileRepairHandler;
 
 impl RepairHandler for FileRepairHandler {
     fn can_handle(&self, pattern: &IssuePattern) -> bool {
-        matches!(pattern, IssuePattern::FileNotExists)
+        matches!(
+            pattern,
+            IssuePattern::FileNotExists
+                | IssuePattern::CorruptAudio
+                | IssuePattern::DurationMismatch
+        )
     }
 
     fn name(&self) -> &'static str {
         "File Repair Handler"
     }
 
-    fn repair(&self, _state: &mut AppState, _issue: &ValidationError) -> Vec<RepairAction> {
+    fn repair(&self, _state: &mut AppState, issue: &ValidationError) -> Vec<RepairAction> {
+        // Damaged audio cannot be fixed in place; surface the suggestion instead
+        if matches!(
+            IssuePattern::from_message(&issue.message),
+            IssuePattern::CorruptAudio | IssuePattern::DurationMismatch
+        ) {
+            return vec![RepairAction::failure(
+                RepairActionType::Update,
+                issue.message.clone(),
+                issue
+                    .file_path
+                    .as_ref()
+                    .map_or_else(|| "files".to_string(), |path| path.display().to_string()),
+                issue
+                    .suggestion
+                    .clone()
+                    .unwrap_or_else(|| "Manual repair required".to_string()),
+            )];
+        }
+
         // File issues are typically handled by other repair methods
         // (e.g., removing audiobooks with missing files)
         // This method can be extended for file-specific repairs like:
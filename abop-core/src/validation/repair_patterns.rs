@@ -21,6 +21,10 @@ pub enum IssuePattern {
     TooSmall,
     /// Issue where duplicate entities are detected
     Duplicate,
+    /// Issue where an audio file failed full-decode verification
+    CorruptAudio,
+    /// Issue where the decoded duration differs from the container header
+    DurationMismatch,
     /// Unknown issue pattern that doesn't match predefined categories
     Unknown(String),
 }
@@ -47,6 +51,10 @@ impl IssuePattern {
             Self::TooSmall
         } else if message.contains(patterns::DUPLICATE) {
             Self::Duplicate
+        } else if message.contains(patterns::CORRUPT_AUDIO) {
+            Self::CorruptAudio
+        } else if message.contains(patterns::DURATION_MISMATCH) {
+            Self::DurationMismatch
         } else {
             Self::Unknown(message.to_string())
         }
@@ -5,6 +5,7 @@
 //! - Audio file paths and formats
 //! - Metadata fields (title, author, duration, etc.)
 //! - Library structure and organization
+//! - Audio health reports from full-decode integrity verification
 //!
 //! # Examples
 //! ```
@@ -23,6 +24,7 @@
 
 use super::error::{ValidationError, ValidationResult};
 use super::state_validator::ValidationConfig;
+use crate::audio::integrity::{HealthStatus, IntegrityConfig, IntegrityReport};
 use crate::models::{AppState, Audiobook};
 use std::collections::HashSet;
 use std::path::Path;
@@ -323,6 +325,117 @@ impl IntegrityValidator {
     }
 }
 
+/// Turns integrity verification reports into validation issues with repair suggestions
+#[derive(Debug, Clone)]
+pub struct AudioHealthValidator {
+    /// Configuration for audio health validation
+    _config: ValidationConfig,
+    /// Tolerances used to judge duration mismatches
+    integrity: IntegrityConfig,
+}
+
+impl AudioHealthValidator {
+    /// Create a new `AudioHealthValidator` with the given configuration
+    #[must_use]
+    pub fn new(config: &ValidationConfig) -> Self {
+        Self {
+            _config: config.clone(),
+            integrity: IntegrityConfig::default(),
+        }
+    }
+
+    /// Use custom integrity tolerances when judging reports
+    #[must_use]
+    pub const fn with_integrity_config(mut self, integrity: IntegrityConfig) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Validate an integrity report and add issues for any detected damage
+    pub fn validate_integrity_report(
+        &self,
+        report: &IntegrityReport,
+        result: &mut ValidationResult,
+    ) {
+        match report.status {
+            HealthStatus::Healthy => return,
+            HealthStatus::Unreadable => {
+                result.add_issue(
+                    ValidationError::error(
+                        "audio_health",
+                        "Audio file could not be opened, possible corrupt audio",
+                    )
+                    .with_file_path(report.path.clone())
+                    .with_suggestion("Restore the file from a backup or re-download it"),
+                );
+                return;
+            }
+            HealthStatus::Corrupt | HealthStatus::Degraded => {}
+        }
+
+        if report.aborted {
+            result.add_issue(
+                ValidationError::error(
+                    "audio_health",
+                    "Decoding aborted after repeated errors, file contains corrupt audio",
+                )
+                .with_file_path(report.path.clone())
+                .with_suggestion("Restore the file from a backup or re-download it"),
+            );
+        } else if report.status == HealthStatus::Corrupt && report.truncated_frames > 0 {
+            result.add_issue(
+                ValidationError::error(
+                    "audio_health",
+                    &format!(
+                        "File is truncated, corrupt audio: {} frames missing",
+                        report.truncated_frames
+                    ),
+                )
+                .with_file_path(report.path.clone())
+                .with_field("truncated_frames")
+                .with_suggestion("Re-download the file or restore it from a backup"),
+            );
+        } else if report.status == HealthStatus::Corrupt {
+            result.add_issue(
+                ValidationError::error(
+                    "audio_health",
+                    "No audio could be decoded, file contains corrupt audio",
+                )
+                .with_file_path(report.path.clone())
+                .with_suggestion("Restore the file from a backup or re-download it"),
+            );
+        }
+
+        if report.decode_errors > 0 && !report.aborted {
+            result.add_issue(
+                ValidationError::warning(
+                    "audio_health",
+                    &format!(
+                        "{} packets failed to decode, file contains corrupt audio",
+                        report.decode_errors
+                    ),
+                )
+                .with_file_path(report.path.clone())
+                .with_field("decode_errors")
+                .with_suggestion("Re-encode the file to drop damaged frames"),
+            );
+        }
+
+        if report.status == HealthStatus::Degraded && report.has_duration_mismatch(&self.integrity)
+        {
+            result.add_issue(
+                ValidationError::warning(
+                    "audio_health",
+                    "Header and decoded audio have a duration mismatch",
+                )
+                .with_file_path(report.path.clone())
+                .with_field("duration_seconds")
+                .with_suggestion("Remux the file to rewrite the container header"),
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::error::ValidationSeverity;
@@ -401,4 +514,35 @@ mod tests {
                     && issue.message.contains("Duplicate"))
         );
     }
+
+    #[test]
+    fn test_audio_health_validator_truncated_file() {
+        let config = ValidationConfig::default();
+        let validator = AudioHealthValidator::new(&config);
+        let mut result = ValidationResult::new();
+
+        let report = IntegrityReport {
+            path: PathBuf::from("/book.mp3"),
+            status: HealthStatus::Corrupt,
+            decode_errors: 2,
+            expected_frames: Some(88200),
+            decoded_frames: 44100,
+            truncated_frames: 44100,
+            header_duration_secs: Some(2.0),
+            decoded_duration_secs: 1.0,
+            duration_delta_secs: Some(-1.0),
+            aborted: false,
+            message: None,
+        };
+        validator.validate_integrity_report(&report, &mut result);
+
+        assert!(!result.is_valid());
+        let issues = result.issues_by_category("audio_health");
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|issue| issue.suggestion.is_some()));
+        assert!(issues.iter().all(|issue| {
+            super::super::IssuePattern::from_validation_error(issue)
+                == Some(super::super::IssuePattern::CorruptAudio)
+        }));
+    }
 }
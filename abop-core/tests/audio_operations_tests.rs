@@ -50,6 +50,7 @@ mod audio_operations_tests {
             channel_mixer: Some(ChannelMixerConfig {
                 target_channels: Some(1),
                 mix_algorithm: MixingAlgorithm::Average,
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -310,6 +311,13 @@ mod audio_operations_tests {
                     "Should process the valid file"
                 );
 
+                // The batch report covers the processed file and counts the failures
+                assert_eq!(result.report.files.len(), 1);
+                assert_eq!(result.report.files[0].input_path, valid_path);
+                assert_eq!(result.report.summary.files_processed, 1);
+                assert_eq!(result.report.summary.files_failed, 2);
+                assert!(result.report.summary.input_duration_secs > 0.0);
+
                 // Check that we have the expected failures
                 let failed_paths: Vec<_> = result.failed.iter().map(|(path, _)| path).collect();
 
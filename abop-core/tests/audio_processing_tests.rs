@@ -15,6 +15,7 @@ fn test_audio_processing_pipeline_creation() {
         channel_mixer: Some(ChannelMixerConfig {
             target_channels: Some(1), // Convert to mono
             mix_algorithm: MixingAlgorithm::Average,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -35,6 +36,7 @@ fn test_audio_processing_error_handling() {
         channel_mixer: Some(ChannelMixerConfig {
             target_channels: Some(0), // Invalid: 0 channels
             mix_algorithm: MixingAlgorithm::Average,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -1,5 +1,6 @@
 // Tests for config file persistence and recovery.
 
+use abop_core::audio::processing::{EqualizerConfig, ProcessingConfig, StageConfig};
 use abop_core::config::{AppConfig, Config, UiConfig, WindowConfig};
 use abop_core::models::ui::ThemeConfig;
 use std::fs;
@@ -28,6 +29,10 @@ fn test_save_and_load_config() {
         data_dir: PathBuf::from("/tmp/abop_data"),
         app: AppConfig::default(),
         ui: UiConfig::default(),
+        processing: ProcessingConfig {
+            equalizer: Some(EqualizerConfig::builder().for_voice().build()),
+            ..Default::default()
+        },
     };
 
     // Save config to file using TOML format (as the actual Config uses)
@@ -43,6 +48,10 @@ fn test_save_and_load_config() {
     assert_eq!(loaded.window.min_height, config.window.min_height);
     assert_eq!(loaded.theme, config.theme);
     assert_eq!(loaded.data_dir, config.data_dir);
+    assert_eq!(
+        loaded.processing.equalizer.map(|eq| eq.bands.len()),
+        config.processing.equalizer.map(|eq| eq.bands.len())
+    );
 }
 
 #[test]
@@ -99,6 +108,7 @@ fn test_different_theme_configurations() {
             data_dir: PathBuf::from("/tmp/test"),
             app: AppConfig::default(),
             ui: UiConfig::default(),
+            processing: ProcessingConfig::default(),
         };
 
         // Serialize and deserialize to test all theme variants
@@ -108,3 +118,33 @@ fn test_different_theme_configurations() {
         assert_eq!(deserialized.theme.display_name(), theme.display_name());
     }
 }
+
+#[test]
+fn test_parameterless_stage_round_trip() {
+    // A stage written by hand without any parameters
+    let stage: StageConfig =
+        toml::from_str("name = \"equalizer\"\n").expect("Should parse a stage without params");
+    assert!(stage.params.is_null());
+
+    let mut config = Config::default();
+    config.processing.stages = vec![stage, StageConfig::new("de_esser")];
+
+    let serialized = toml::to_string_pretty(&config).expect("Should serialize config to TOML");
+    assert!(serialized.contains("[[processing.stages]]"));
+
+    let loaded: Config = toml::from_str(&serialized).expect("Should deserialize config from TOML");
+    let names: Vec<_> = loaded
+        .processing
+        .stages
+        .iter()
+        .map(|stage| stage.name.as_str())
+        .collect();
+    assert_eq!(names, ["equalizer", "de_esser"]);
+    assert!(
+        loaded
+            .processing
+            .stages
+            .iter()
+            .all(|stage| stage.params.is_null())
+    );
+}
@@ -74,6 +74,10 @@ fn setup_test_db() -> Arc<EnhancedConnection> {
 
             // Execute the initial schema (includes all necessary columns and migrations)
             conn.execute_batch(include_str!("../src/db/migrations/001_initial_schema.sql"))?;
+            // Audiobook writes also maintain the author, narrator and series links
+            conn.execute_batch(include_str!(
+                "../src/db/migrations/005_people_and_series.sql"
+            ))?;
 
             Ok(())
         })
@@ -12,11 +12,12 @@ use tokio::sync::mpsc::{self, UnboundedSender};
 
 use crate::{
     handlers,
-    messages::Message,
+    messages::{Command, Message},
     router::{self, Route},
     state::AppState,
     views,
 };
+use abop_core::Config;
 use abop_core::services::ServiceContainer;
 
 /// Messages for task management
@@ -171,8 +172,21 @@ impl App {
         // Initialize the application with default state and router
         let app = Self::new();
 
-        // Return the app with a no-op task
-        (app, Task::none())
+        // Resume jobs interrupted by a crash or shutdown
+        (app, Task::done(Message::command(Command::ResumeJobs)))
+    }
+
+    /// Initialize the application with the settings of a loaded configuration
+    ///
+    /// Restores the listener profile that was active in the last session.
+    /// Loading the profiles falls back to the default one if it was deleted.
+    pub fn initial_with_config(config: &Config) -> (Self, Task<Message>) {
+        let (mut app, task) = Self::initial();
+        app.state
+            .library
+            .switch_profile(config.app.active_profile_id.clone());
+        let profiles = Task::done(Message::command(Command::LoadProfiles));
+        (app, Task::batch([task, profiles]))
     }
 
     /// Get the application title
@@ -244,7 +258,7 @@ impl App {
     pub fn subscription(&self) -> Subscription<Message> {
         use keyboard::key::Key;
 
-        iced::event::listen_with(|event, _status, _window| {
+        let keyboard = iced::event::listen_with(|event, _status, _window| {
             if let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) = event {
                 match key.as_ref() {
                     Key::Named(keyboard::key::Named::Space) => Some(Message::PlayPause),
@@ -256,6 +270,25 @@ impl App {
             } else {
                 None
             }
-        })
+        });
+
+        let watchers = self
+            .state
+            .library
+            .watched_libraries
+            .iter()
+            .cloned()
+            .map(crate::library::watch_library);
+
+        // Keep the scrubber's playhead moving while a track plays
+        let ticks = self.state.player.is_playing().then(|| {
+            iced::time::every(std::time::Duration::from_millis(250)).map(|_| Message::PlaybackTick)
+        });
+
+        let controls = std::iter::once(keyboard).chain(ticks);
+        #[cfg(target_os = "linux")]
+        let controls = controls.chain(std::iter::once(crate::mpris::media_controls()));
+
+        Subscription::batch(controls.chain(watchers))
     }
 }
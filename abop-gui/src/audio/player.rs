@@ -1,9 +1,16 @@
 //! Audio player management and global player instance
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use abop_core::PlayerState;
 use abop_core::audio::player::ThreadSafeAudioPlayer;
+use abop_core::audio::processing::playback_stages;
+use abop_core::audio::waveform::{WaveformGenerator, WaveformSummary};
+use abop_core::db::Database;
+use abop_core::models::{Audiobook, Bookmark, PlaybackConfig, Progress};
+use abop_core::{PlayerState, ProcessingConfig};
+use parking_lot::RwLock;
 
 // ================================================================================================
 // GLOBAL AUDIO PLAYER
@@ -14,14 +21,44 @@ static AUDIO_PLAYER: std::sync::LazyLock<ThreadSafeAudioPlayer> = std::sync::Laz
     ThreadSafeAudioPlayer::new().expect("Failed to create audio player")
 });
 
+/// Audiobook loaded in the global audio player
+///
+/// The player only knows file paths, so this keeps the metadata that media
+/// controls outside the window display.
+static NOW_PLAYING: RwLock<Option<Audiobook>> = RwLock::new(None);
+
 /// Get a reference to the global audio player
 #[must_use]
 pub fn get_audio_player() -> &'static ThreadSafeAudioPlayer {
     &AUDIO_PLAYER
 }
 
+/// Get the audiobook loaded in the global audio player
+#[must_use]
+pub fn now_playing() -> Option<Audiobook> {
+    NOW_PLAYING.read().clone()
+}
+
+/// Apply the equalizer and de-esser from `config` to playback
+///
+/// The effects take effect from the next file that starts playing.
+///
+/// # Errors
+///
+/// Returns an error if the processing configuration is invalid
+pub fn set_playback_effects(config: &ProcessingConfig) -> Result<(), String> {
+    let stages = playback_stages(config).map_err(|e| format!("Invalid playback effects: {e}"))?;
+    log::info!("Applying {} playback effect(s)", stages.len());
+    AUDIO_PLAYER.set_effects(stages);
+    Ok(())
+}
+
 /// Play selected audio files
 ///
+/// Playback uses the speed and volume of the listener profile and, when the
+/// profile resumes automatically, continues from its saved position. The
+/// position in the audiobook that was playing before is saved first.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -32,6 +69,7 @@ pub fn get_audio_player() -> &'static ThreadSafeAudioPlayer {
 pub async fn play_selected_audio(
     selected_ids: Vec<String>,
     audiobooks: Vec<abop_core::models::Audiobook>,
+    profile_id: String,
 ) -> Result<String, String> {
     if selected_ids.is_empty() {
         return Err("No audiobooks selected for playback".to_string());
@@ -51,9 +89,41 @@ pub async fn play_selected_audio(
         ));
     }
 
+    let previous = playing_position();
+    let audiobook_id = audiobook.id.clone();
+    let (playback, resume_at) = tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        if let Some((previous, position)) = previous {
+            save_progress(&db, &profile_id, &previous.id, position)?;
+        }
+        let playback = profile_playback(&db, &profile_id)?;
+        let resume_at = db
+            .progress_repository()
+            .find_for_profile(&profile_id, &audiobook_id)
+            .map_err(|e| e.to_string())?
+            .filter(|progress| playback.auto_resume && !progress.completed)
+            .map(|progress| Duration::from_secs(progress.position_seconds))
+            .filter(|position| !position.is_zero());
+        Ok::<_, String>((playback, resume_at))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .unwrap_or_else(|e| {
+        log::warn!("Playing without profile preferences: {e}");
+        (PlaybackConfig::default(), None)
+    });
+
     // Play the audio file using the global player
+    AUDIO_PLAYER.set_rate(playback.speed);
+    AUDIO_PLAYER.set_volume(playback.volume);
     match AUDIO_PLAYER.play(&audiobook.path) {
         Ok(()) => {
+            *NOW_PLAYING.write() = Some(audiobook.clone());
+            if let Some(position) = resume_at
+                && let Err(e) = AUDIO_PLAYER.seek(position)
+            {
+                log::warn!("Could not resume at {}s: {e}", position.as_secs());
+            }
             let title = audiobook.title.as_deref().unwrap_or("Unknown");
             Ok(format!("Started playing: {title}"))
         }
@@ -62,8 +132,118 @@ pub async fn play_selected_audio(
 }
 
 /// Stop audio playback
-pub fn stop_audio() {
+///
+/// Returns the audiobook that was loaded and the position it stopped at, so
+/// the caller can save it with [`save_playback_progress`].
+pub fn stop_audio() -> Option<(Audiobook, Duration)> {
+    let stopped = playing_position();
     AUDIO_PLAYER.stop();
+    *NOW_PLAYING.write() = None;
+    stopped
+}
+
+/// Save the position a listener profile reached in an audiobook
+///
+/// Adds a bookmark as well when the profile bookmarks automatically.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or written
+pub async fn save_playback_progress(
+    profile_id: String,
+    audiobook_id: String,
+    position: Duration,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        save_progress(&db, &profile_id, &audiobook_id, position)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The loaded audiobook and the current position, if anything is loaded
+fn playing_position() -> Option<(Audiobook, Duration)> {
+    if AUDIO_PLAYER.get_state() == PlayerState::Stopped {
+        return None;
+    }
+    now_playing().map(|audiobook| (audiobook, AUDIO_PLAYER.position()))
+}
+
+/// Playback preferences of a listener profile, or the defaults if it is gone
+fn profile_playback(db: &Database, profile_id: &str) -> Result<PlaybackConfig, String> {
+    Ok(db
+        .profile_repository()
+        .find_by_id(profile_id)
+        .map_err(|e| e.to_string())?
+        .map(|profile| profile.playback_config())
+        .unwrap_or_default())
+}
+
+/// Stores a position and, if the profile asks for one, a bookmark
+fn save_progress(
+    db: &Database,
+    profile_id: &str,
+    audiobook_id: &str,
+    position: Duration,
+) -> Result<(), String> {
+    let repo = db.progress_repository();
+    let mut progress = repo
+        .find_for_profile(profile_id, audiobook_id)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(|| Progress::new(audiobook_id, 0).with_profile(profile_id));
+    progress.update_position(position.as_secs());
+    repo.upsert(&progress).map_err(|e| e.to_string())?;
+
+    if profile_playback(db, profile_id)?.auto_bookmark {
+        let bookmark = Bookmark::new(profile_id, audiobook_id, position.as_secs(), "Stopped");
+        db.bookmark_repository()
+            .add(&bookmark)
+            .map_err(|e| e.to_string())?;
+    }
+    log::debug!(
+        "Saved position {}s in {audiobook_id} for profile {profile_id}",
+        position.as_secs()
+    );
+    Ok(())
+}
+
+/// Move playback to a position in the current file
+///
+/// # Errors
+///
+/// Returns an error if nothing is playing or the file cannot be seeked
+pub fn seek_audio(position: Duration) -> Result<(), String> {
+    AUDIO_PLAYER
+        .seek(position)
+        .map_err(|e| format!("Failed to seek: {e}"))
+}
+
+/// Current position in the loaded file
+#[must_use]
+pub fn playback_position() -> Duration {
+    AUDIO_PLAYER.position()
+}
+
+/// Waveform overview of an audiobook for the player scrubber
+///
+/// Reads the cached summary, or decodes the file and caches it when there is
+/// none or the file changed. Returns the audiobook ID with the summary so
+/// results for a track that is no longer loaded can be told apart.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or the file cannot be decoded
+pub async fn load_waveform(audiobook: Audiobook) -> Result<(String, Arc<WaveformSummary>), String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        let summary = WaveformGenerator::default()
+            .load_or_generate(&db.waveform_repository(), &audiobook)
+            .map_err(|e| e.to_string())?;
+        Ok((audiobook.id, Arc::new(summary)))
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 /// Get current player state
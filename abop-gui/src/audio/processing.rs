@@ -1,104 +1,124 @@
 //! Audio processing functionality for conversions and transformations
 
-use abop_core::audio::processing::file_io::{AudioFileProcessor, FileProcessingOptions};
-use abop_core::{
-    AudioProcessingPipeline, ChannelMixerConfig, MixingAlgorithm, ProcessingConfig,
-    models::Audiobook,
-};
+use abop_core::audio::processing::BatchProcessingReport;
+use abop_core::audio::processing::batch_processor::{BatchProcessor, ProcessingJobParameters};
+use abop_core::audio::processing::file_io::FileProcessingOptions;
+use abop_core::db::Database;
+use abop_core::{ChannelMixerConfig, MixingAlgorithm, ProcessingConfig, models::Audiobook};
 use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Async function to convert selected audiobooks to mono
 ///
+/// The conversion is recorded as a persisted processing job so it can resume
+/// after a crash. Returns the before/after report of every converted file.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - No audiobooks are selected for conversion
 /// - Audio processing pipeline creation fails
-/// - File processing fails for any selected audiobook
-/// - Output file creation fails
+/// - File processing fails for every selected audiobook
 pub async fn convert_selected_to_mono(
     selected_ids: HashSet<String>,
     audiobooks: Vec<Audiobook>,
-) -> Result<String, String> {
+) -> Result<BatchProcessingReport, String> {
     if selected_ids.is_empty() {
         return Err("No audiobooks selected for conversion".to_string());
     }
 
-    // Create audio processing pipeline with mono conversion configuration
-    let config = ProcessingConfig {
-        channel_mixer: Some(ChannelMixerConfig {
-            target_channels: Some(1), // Convert to mono
-            mix_algorithm: MixingAlgorithm::Average,
-        }),
-        ..Default::default()
-    };
-    let pipeline = AudioProcessingPipeline::new(config)
-        .map_err(|e| format!("Failed to create audio pipeline: {e}"))?;
-    let options = FileProcessingOptions::default();
-    let mut processor = AudioFileProcessor::new(pipeline, options);
-
-    // Filter to get selected audiobooks
-    let selected_audiobooks: Vec<_> = audiobooks
+    let input_paths: Vec<PathBuf> = audiobooks
         .iter()
         .filter(|audiobook| selected_ids.contains(&audiobook.id))
+        .map(|audiobook| audiobook.path.clone())
         .collect();
-
-    if selected_audiobooks.is_empty() {
+    if input_paths.is_empty() {
         return Err("Selected audiobooks not found".to_string());
     }
 
-    let mut processed_count = 0;
-    let mut failed_count = 0;
-
-    // Process each selected audiobook
-    for audiobook in selected_audiobooks {
-        let input_path = PathBuf::from(&audiobook.path);
-
-        // Generate output path (same directory, with "_mono" suffix)
-        let output_path = input_path.parent().map_or_else(
-            || {
-                let title = audiobook.title.as_deref().unwrap_or("unknown");
-                PathBuf::from(format!("{title}_mono.wav"))
-            },
-            |parent| {
-                let stem = input_path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown");
-                let extension = input_path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("wav");
-                parent.join(format!("{stem}_mono.{extension}"))
-            },
-        );
+    // Create audio processing pipeline with mono conversion configuration
+    let parameters = ProcessingJobParameters {
+        config: ProcessingConfig {
+            channel_mixer: Some(ChannelMixerConfig {
+                target_channels: Some(1), // Convert to mono
+                mix_algorithm: MixingAlgorithm::Average,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        options: FileProcessingOptions {
+            naming_pattern: "{filename}_mono".to_string(),
+            ..Default::default()
+        },
+        input_paths,
+    };
 
-        // Perform the conversion using the file processor
-        match processor.process_file_with_output(&input_path, &output_path) {
-            Ok(()) => {
-                let title = audiobook.title.as_deref().unwrap_or("Unknown");
-                log::info!("Successfully converted '{title}' to mono");
-                processed_count += 1;
-            }
+    tokio::task::spawn_blocking(move || {
+        let tracked = Database::open_app_database()
+            .map_err(|e| e.to_string())
+            .and_then(|db| {
+                BatchProcessor::start_job(Arc::new(db.job_repository()), parameters.clone())
+                    .map_err(|e| e.to_string())
+            });
+        let (processor, input_paths) = match tracked {
+            Ok(batch) => batch,
             Err(e) => {
-                let title = audiobook.title.as_deref().unwrap_or("Unknown");
-                log::error!("Failed to convert '{title}': {e}");
-                failed_count += 1;
+                log::warn!("Converting without job tracking: {e}");
+                let processor = BatchProcessor::new(parameters.config, parameters.options)
+                    .map_err(|e| format!("Failed to create audio pipeline: {e}"))?;
+                (processor, parameters.input_paths)
             }
-        }
+        };
+        run_batch(&processor, &input_paths)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Resumes an interrupted processing job
+///
+/// Files the job already processed are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the job cannot be loaded or every remaining file fails.
+pub async fn resume_processing_job(job_id: String) -> Result<BatchProcessingReport, String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        let repository = Arc::new(db.job_repository());
+        let job = repository
+            .find_by_id(&job_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Processing job {job_id} not found"))?;
+        let (processor, input_paths) =
+            BatchProcessor::from_job(repository, &job).map_err(|e| e.to_string())?;
+        log::info!("Resuming processing job {job_id}");
+        run_batch(&processor, &input_paths)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Processes a batch and returns its report, failing only if every file failed
+fn run_batch(
+    processor: &BatchProcessor,
+    input_paths: &[PathBuf],
+) -> Result<BatchProcessingReport, String> {
+    let result = processor
+        .process_files_detailed(input_paths)
+        .map_err(|e| e.to_string())?;
+    for (path, error) in &result.failed {
+        log::error!("Failed to convert '{}': {error}", path.display());
     }
 
-    // Return result summary
-    if failed_count == 0 {
-        Ok(format!(
-            "Successfully converted {processed_count} audiobook(s) to mono"
-        ))
-    } else if processed_count == 0 {
-        Err(format!("Failed to convert all {failed_count} audiobook(s)"))
-    } else {
-        Ok(format!(
-            "Converted {processed_count} audiobook(s) to mono ({failed_count} failed)"
-        ))
+    let failed_count = result.failed.len();
+    if result.successful.is_empty() && failed_count > 0 {
+        return Err(format!("Failed to convert all {failed_count} audiobook(s)"));
     }
+    log::info!(
+        "Converted {} audiobook(s) ({failed_count} failed)",
+        result.successful.len()
+    );
+    Ok(result.report)
 }
@@ -3,7 +3,10 @@
 use iced::Task;
 use std::collections::HashSet;
 
-use crate::audio::{convert_selected_to_mono, play_selected_audio, stop_audio};
+use crate::audio::{
+    convert_selected_to_mono, play_selected_audio, resume_processing_job, save_playback_progress,
+    stop_audio,
+};
 use crate::messages::{Command as GuiCommand, Message};
 use crate::state::AppState;
 
@@ -29,6 +32,17 @@ pub fn handle_audio_command(state: &mut AppState, command: GuiCommand) -> Option
                 Message::AudioProcessingComplete,
             ))
         }
+        GuiCommand::ResumeProcessingJob { job_id } => {
+            state
+                .player
+                .start_processing(Some("Resuming interrupted processing job...".to_string()));
+            state.progress_cache.clear_processing_cache();
+            log::info!("Executing ResumeProcessingJob command for job {job_id}");
+            Some(Task::perform(
+                resume_processing_job(job_id),
+                Message::AudioProcessingComplete,
+            ))
+        }
         GuiCommand::PlayAudio {
             selected_ids,
             audiobooks,
@@ -38,15 +52,31 @@ pub fn handle_audio_command(state: &mut AppState, command: GuiCommand) -> Option
                 selected_ids.len()
             );
             Some(Task::perform(
-                play_selected_audio(selected_ids, audiobooks),
+                play_selected_audio(
+                    selected_ids,
+                    audiobooks,
+                    state.library.active_profile_id.clone(),
+                ),
                 Message::PlaybackStarted,
             ))
         }
         GuiCommand::StopAudio => {
             log::info!("Executing StopAudio command");
-            stop_audio();
+            let save = stop_audio().map_or_else(Task::none, |(audiobook, position)| {
+                Task::perform(
+                    save_playback_progress(
+                        state.library.active_profile_id.clone(),
+                        audiobook.id,
+                        position,
+                    ),
+                    Message::PlaybackProgressSaved,
+                )
+            });
             // Return a task that will trigger the PlaybackStopped message
-            Some(Task::perform(async {}, |()| Message::PlaybackStopped))
+            Some(Task::batch([
+                save,
+                Task::perform(async {}, |()| Message::PlaybackStopped),
+            ]))
         }
         _ => None, // Not an audio command
     }
@@ -1,12 +1,19 @@
 //! Library scanning command handlers
 
+use abop_core::Config;
+use abop_core::db::Database;
+use abop_core::library::{DuplicateDetector, LibraryOrganizer};
+use abop_core::models::{Collection, Job, JobType, Profile, Tag};
 use iced::Task;
+use std::collections::HashMap;
 
-use crate::library::{open_directory_dialog, scan_library};
-use crate::messages::{Command as GuiCommand, Message};
+use crate::library::{open_directory_dialog, resume_scan_job, scan_library};
+use crate::messages::{Command as GuiCommand, Message, SelectionTarget};
 use crate::state::{AppState, DirectoryInfo};
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::sync::{LazyLock, mpsc};
+use std::time::{Duration, SystemTime};
+use tokio::sync::oneshot;
 
 /// Scans a directory asynchronously and returns metadata about the scan
 pub async fn scan_directory_async(path: PathBuf) -> Result<DirectoryInfo, String> {
@@ -31,6 +38,203 @@ pub async fn scan_directory_async(path: PathBuf) -> Result<DirectoryInfo, String
     })
 }
 
+/// Number of persisted jobs shown in the task history
+const JOB_HISTORY_LIMIT: usize = 50;
+
+/// Loads the most recent persisted jobs from the application database
+async fn load_job_history() -> Result<Vec<Job>, String> {
+    tokio::task::spawn_blocking(|| {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        db.job_repository()
+            .find_recent(JOB_HISTORY_LIMIT)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Loads the user collections and tags from the application database
+async fn load_collections() -> Result<(Vec<Collection>, Vec<Tag>), String> {
+    tokio::task::spawn_blocking(|| {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        let collections = db
+            .collection_repository()
+            .find_all()
+            .map_err(|e| e.to_string())?;
+        let tags = db.tag_repository().find_all().map_err(|e| e.to_string())?;
+        Ok((collections, tags))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Loads the listener profiles
+async fn load_profiles() -> Result<Vec<Profile>, String> {
+    tokio::task::spawn_blocking(|| {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        db.profile_repository()
+            .find_all()
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Remembers the active listener profile for the next start
+async fn save_active_profile(profile_id: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let mut config = Config::load().map_err(|e| e.to_string())?;
+        config.app.active_profile_id = profile_id;
+        config.save().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Creates a listener profile
+async fn create_profile(name: String) -> Result<Profile, String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        db.profile_repository()
+            .create(&name)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Adds audiobooks to a collection or tag, or removes them
+///
+/// Adding to a collection that does not exist yet creates it.
+async fn assign_selection(
+    target: SelectionTarget,
+    audiobook_ids: Vec<String>,
+    assign: bool,
+) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        match target {
+            SelectionTarget::Collection(name) => {
+                let repo = db.collection_repository();
+                let existing = repo.find_by_name(&name).map_err(|e| e.to_string())?;
+                let collection = match existing {
+                    Some(collection) => collection,
+                    None if assign => repo.create(&name, None).map_err(|e| e.to_string())?,
+                    None => return Ok(0),
+                };
+                if assign {
+                    repo.add_audiobooks(&collection.id, &audiobook_ids)
+                } else {
+                    repo.remove_audiobooks(&collection.id, &audiobook_ids)
+                }
+            }
+            SelectionTarget::Tag(name) => {
+                let repo = db.tag_repository();
+                if assign {
+                    repo.tag_audiobooks(&name, &audiobook_ids)
+                } else {
+                    repo.untag_audiobooks(&name, &audiobook_ids)
+                }
+            }
+        }
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// A selection waiting to be stored by the selection writer
+struct SelectionWrite {
+    audiobook_ids: Vec<String>,
+    reply: oneshot::Sender<Result<usize, String>>,
+}
+
+/// How long the selection writer waits for further changes before storing
+const SELECTION_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Queue of the thread that stores selections
+///
+/// Every toggle sends its selection here, and one thread stores them in order
+/// on a single database handle, so an older selection can never land after a
+/// newer one.
+static SELECTION_WRITER: LazyLock<mpsc::Sender<SelectionWrite>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("selection-writer".to_string())
+        .spawn(move || {
+            let mut db: Option<Database> = None;
+            run_selection_writer(&receiver, SELECTION_DEBOUNCE, |audiobook_ids| {
+                let db = match &db {
+                    Some(db) => db,
+                    None => db.insert(Database::open_app_database().map_err(|e| e.to_string())?),
+                };
+                db.audiobook_repository()
+                    .set_selection(audiobook_ids)
+                    .map_err(|e| e.to_string())
+            });
+        })
+        .expect("Failed to start the selection writer");
+    sender
+});
+
+/// Stores queued selections until every sender is gone
+///
+/// Selections arriving within `debounce` of each other are coalesced: only the
+/// newest is stored and every waiting request is answered with its result.
+fn run_selection_writer(
+    receiver: &mpsc::Receiver<SelectionWrite>,
+    debounce: Duration,
+    mut store: impl FnMut(&[String]) -> Result<usize, String>,
+) {
+    while let Ok(first) = receiver.recv() {
+        let mut pending = vec![first];
+        while let Ok(next) = receiver.recv_timeout(debounce) {
+            pending.push(next);
+        }
+
+        let Some(latest) = pending.last() else {
+            continue;
+        };
+        let result = store(&latest.audiobook_ids);
+        for write in pending {
+            let _ = write.reply.send(result.clone());
+        }
+    }
+}
+
+/// Stores the selection so that commands working on it see the same audiobooks
+async fn save_selection(audiobook_ids: Vec<String>) -> Result<usize, String> {
+    let (reply, result) = oneshot::channel();
+    SELECTION_WRITER
+        .send(SelectionWrite {
+            audiobook_ids,
+            reply,
+        })
+        .map_err(|_| "The selection writer has stopped".to_string())?;
+    result
+        .await
+        .map_err(|_| "The selection writer has stopped".to_string())?
+}
+
+/// Loads the scan and processing jobs that were interrupted before finishing
+async fn find_resumable_jobs() -> Result<Vec<Job>, String> {
+    tokio::task::spawn_blocking(|| {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        let repository = db.job_repository();
+        let mut jobs = repository
+            .find_resumable(JobType::Scan)
+            .map_err(|e| e.to_string())?;
+        jobs.extend(
+            repository
+                .find_resumable(JobType::Process)
+                .map_err(|e| e.to_string())?,
+        );
+        Ok(jobs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Handles library-related commands
 #[must_use]
 pub fn handle_library_command(state: &mut AppState, command: GuiCommand) -> Option<Task<Message>> {
@@ -112,6 +316,138 @@ pub fn handle_library_command(state: &mut AppState, command: GuiCommand) -> Opti
                 Message::ScanComplete,
             ))
         }
+        GuiCommand::FindDuplicates { audiobooks } => {
+            log::info!(
+                "Executing FindDuplicates command for {} audiobooks",
+                audiobooks.len()
+            );
+            Some(Task::perform(
+                async move {
+                    // Fingerprinting decodes audio, keep it off the async executor
+                    tokio::task::spawn_blocking(move || {
+                        DuplicateDetector::default().find_duplicates(&audiobooks)
+                    })
+                    .await
+                    .map_err(|e| e.to_string())
+                },
+                Message::DuplicatesFound,
+            ))
+        }
+        GuiCommand::PlanOrganize {
+            library_path,
+            audiobooks,
+        } => {
+            log::info!(
+                "Executing PlanOrganize command for {}",
+                library_path.display()
+            );
+            Some(Task::perform(
+                async move {
+                    // Planning checks every target on disk
+                    tokio::task::spawn_blocking(move || {
+                        let audiobooks: Vec<_> = audiobooks
+                            .into_iter()
+                            .filter(|audiobook| audiobook.path.starts_with(&library_path))
+                            .collect();
+                        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+                        let series = match db
+                            .libraries()
+                            .find_by_path(&library_path)
+                            .map_err(|e| e.to_string())?
+                        {
+                            Some(library) => db
+                                .series_repository()
+                                .find_memberships_in_library(&library.id)
+                                .map_err(|e| e.to_string())?,
+                            None => HashMap::new(),
+                        };
+                        Ok(LibraryOrganizer::default().plan_with_series(
+                            &library_path,
+                            &audiobooks,
+                            &series,
+                        ))
+                    })
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|plan| plan)
+                },
+                Message::OrganizePlanned,
+            ))
+        }
+        GuiCommand::ApplyOrganize { plan } => {
+            log::info!(
+                "Executing ApplyOrganize command for {} files",
+                plan.moves.len()
+            );
+            Some(Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+                        LibraryOrganizer::default()
+                            .apply(&plan, &db.audiobook_repository())
+                            .map_err(|e| e.to_string())
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?
+                },
+                Message::OrganizeComplete,
+            ))
+        }
+        GuiCommand::LoadCollections => Some(Task::perform(
+            load_collections(),
+            Message::CollectionsLoaded,
+        )),
+        GuiCommand::AssignSelection {
+            target,
+            audiobook_ids,
+            assign,
+        } => {
+            log::info!(
+                "Executing AssignSelection command for {} audiobooks",
+                audiobook_ids.len()
+            );
+            Some(Task::perform(
+                assign_selection(target, audiobook_ids, assign),
+                Message::SelectionAssigned,
+            ))
+        }
+        GuiCommand::SaveSelection { audiobook_ids } => Some(Task::perform(
+            save_selection(audiobook_ids),
+            Message::SelectionSaved,
+        )),
+        GuiCommand::LoadProfiles => Some(Task::perform(load_profiles(), Message::ProfilesLoaded)),
+        GuiCommand::SaveActiveProfile { profile_id } => Some(Task::perform(
+            save_active_profile(profile_id),
+            Message::ActiveProfileSaved,
+        )),
+        GuiCommand::CreateProfile { name } => {
+            log::info!("Executing CreateProfile command for {name}");
+            Some(Task::perform(create_profile(name), Message::ProfileCreated))
+        }
+        GuiCommand::ResumeJobs => {
+            log::info!("Executing ResumeJobs command");
+            Some(Task::batch([
+                Task::perform(load_job_history(), Message::JobHistoryLoaded),
+                Task::perform(find_resumable_jobs(), Message::ResumableJobsFound),
+            ]))
+        }
+        GuiCommand::ResumeScanJob { job_id } => {
+            log::info!("Executing ResumeScanJob command for job {job_id}");
+            state.library.start_scanning();
+            Some(Task::perform(
+                async move {
+                    let db = tokio::task::spawn_blocking(Database::open_app_database)
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .map_err(|e| e.to_string())?;
+                    resume_scan_job(db, job_id).await.map_err(|e| e.to_string())
+                },
+                Message::ScanComplete,
+            ))
+        }
+        GuiCommand::LoadJobHistory => {
+            Some(Task::perform(load_job_history(), Message::JobHistoryLoaded))
+        }
         GuiCommand::BrowseDirectory => {
             log::info!("Executing BrowseDirectory command");
             Some(Task::perform(
@@ -132,3 +468,36 @@ pub fn handle_library_command(state: &mut AppState, command: GuiCommand) -> Opti
         _ => None, // Not a library command
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_writer_stores_newest_selection() {
+        let (sender, receiver) = mpsc::channel();
+        let mut replies = Vec::new();
+        for selection in [vec!["a"], vec!["a", "b"], vec!["b"]] {
+            let (reply, result) = oneshot::channel();
+            sender
+                .send(SelectionWrite {
+                    audiobook_ids: selection.into_iter().map(String::from).collect(),
+                    reply,
+                })
+                .unwrap();
+            replies.push(result);
+        }
+        drop(sender);
+
+        let mut stored = Vec::new();
+        run_selection_writer(&receiver, Duration::from_millis(10), |audiobook_ids| {
+            stored.push(audiobook_ids.to_vec());
+            Ok(audiobook_ids.len())
+        });
+
+        assert_eq!(stored, vec![vec!["b".to_string()]]);
+        for reply in replies {
+            assert_eq!(reply.blocking_recv().unwrap(), Ok(1));
+        }
+    }
+}
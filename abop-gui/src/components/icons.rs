@@ -36,6 +36,18 @@ pub mod icon_names {
     pub const SEARCH: &str = "magnifying-glass";
     /// Refresh/reload icon for refreshing library content
     pub const REFRESH: &str = "arrow-rotate-right";
+    /// Overlapping copies icon for duplicate detection
+    pub const DUPLICATES: &str = "clone";
+    /// Folder tree icon for organizing library files
+    pub const ORGANIZE: &str = "folder-tree";
+    /// Bookmark icon for collections and tags
+    pub const COLLECTIONS: &str = "bookmark";
+    /// Person icon for listener profiles
+    pub const PROFILES: &str = "user";
+    /// Open eye icon for a library that is being watched
+    pub const WATCHING: &str = "eye";
+    /// Crossed out eye icon for a library that is not being watched
+    pub const NOT_WATCHING: &str = "eye-slash";
     /// Download icon for downloading content
     pub const DOWNLOAD: &str = "download"; // Media control icons
     /// Play button icon for media playback
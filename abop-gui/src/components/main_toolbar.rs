@@ -8,7 +8,8 @@ use iced::widget::{Space, container, row, text};
 use iced::{Alignment, Element, Length};
 use std::path::Path;
 
-use crate::components::buttons::{self, ButtonVariant};
+use crate::components::buttons::{self, ButtonSize, ButtonVariant};
+use crate::components::icons::icon_names as icons;
 use crate::messages::Message;
 use crate::state::DirectoryInfo;
 use crate::styling::material::MaterialTokens;
@@ -18,7 +19,8 @@ use crate::styling::material::MaterialTokens;
 /// Provides a comprehensive toolbar with:
 /// - App branding (ABOP title)
 /// - Directory controls (folder browser, scan button, path display)
-/// - Settings access
+/// - Watch mode toggle for the current library
+/// - Duplicate review and settings access
 ///
 /// Layout follows Material Design 3 principles with proper spacing and alignment.
 pub struct MainToolbar;
@@ -28,11 +30,12 @@ impl MainToolbar {
     ///
     /// The toolbar is organized with logical grouping:
     /// - Left: App title, folder button, scan button, current path
-    /// - Right: Settings button (pushed right with flexible spacing)
+    /// - Right: Watch, duplicates and settings buttons (pushed right with flexible spacing)
     ///
     /// # Arguments
     /// * `_recent_dirs` - List of recently used directories (reserved for future dropdown)
     /// * `current_path` - The current library path to display and scan
+    /// * `watching` - Whether the current library is watched for changes
     /// * `material_tokens` - Material Design 3 tokens for consistent styling
     ///
     /// # Returns
@@ -41,6 +44,7 @@ impl MainToolbar {
     pub fn view<'a>(
         _recent_dirs: &[DirectoryInfo],
         current_path: &Path,
+        watching: bool,
         material_tokens: &'a MaterialTokens,
     ) -> Element<'a, Message> {
         // === Button Creation ===        // Folder browser button - opens directory selection dialog
@@ -67,6 +71,56 @@ impl MainToolbar {
             },
             "scan library",
             Some("Scan"),
+        ); // Watch button - toggles automatic updates for the current library
+        let watch_button = buttons::create_button(
+            || {
+                buttons::button(material_tokens)
+                    .icon_only(
+                        if watching {
+                            icons::WATCHING
+                        } else {
+                            icons::NOT_WATCHING
+                        },
+                        ButtonSize::Medium,
+                    )
+                    .variant(if watching {
+                        ButtonVariant::Filled
+                    } else {
+                        ButtonVariant::FilledTonal
+                    })
+                    .on_press(Message::ToggleLibraryWatch(current_path.to_path_buf()))
+                    .build()
+            },
+            "watch library",
+            Some("👁"),
+        ); // Duplicates button - opens the duplicate review dialog
+        let duplicates_button = buttons::create_toolbar_button(
+            material_tokens,
+            "clone",
+            Message::ShowDuplicates,
+            "⧉",
+            "find duplicates",
+        ); // Organize button - previews moving files into the organizer layout
+        let organize_button = buttons::create_toolbar_button(
+            material_tokens,
+            "folder-tree",
+            Message::ShowOrganize,
+            "⌥",
+            "organize library",
+        ); // Collections button - assigns the selection to collections and tags
+        let collections_button = buttons::create_toolbar_button(
+            material_tokens,
+            "bookmark",
+            Message::ShowCollections,
+            "☰",
+            "collections and tags",
+        ); // Profiles button - switches the active listener
+        let profiles_button = buttons::create_toolbar_button(
+            material_tokens,
+            "user",
+            Message::ShowProfiles,
+            "☺",
+            "listener profiles",
         ); // Settings button - opens application settings
         let settings_button = buttons::create_toolbar_button(
             material_tokens,
@@ -93,7 +147,7 @@ impl MainToolbar {
         // === Toolbar Layout ===
 
         // Organize toolbar with logical grouping:
-        // [App Title] [Folder] [Scan] [Path Display] ... [Settings]
+        // [App Title] [Folder] [Scan] [Path Display] ... [Watch] [Duplicates] [Organize] [Collections] [Profiles] [Settings]
         let toolbar_row = row![
             // App branding - fixed width for consistent layout
             text("ABOP")
@@ -107,6 +161,12 @@ impl MainToolbar {
                 .width(Length::Fill), // Expands to fill available space
             // Flexible spacer - pushes settings button to the right
             Space::with_width(Length::Fill),
+            // Library maintenance
+            watch_button,
+            duplicates_button,
+            organize_button,
+            collections_button,
+            profiles_button,
             // Settings access - positioned on the right for easy access
             settings_button,
         ]
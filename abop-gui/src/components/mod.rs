@@ -33,6 +33,8 @@ pub mod table_core;
 pub mod table_header;
 /// Table row component for data display
 pub mod table_row;
+/// Seekable waveform overview for the player scrubber
+pub mod waveform;
 
 #[cfg(test)]
 mod tests;
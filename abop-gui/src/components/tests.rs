@@ -751,3 +751,41 @@ mod table_tests {
         let _ = element; // Just verify it compiles and runs
     }
 }
+
+#[cfg(test)]
+mod waveform_tests {
+    use crate::components::waveform::{WaveformColors, WaveformScrubber};
+    use crate::messages::Message;
+    use crate::styling::material::MaterialTokens;
+    use abop_core::audio::waveform::{WaveformBuilder, WaveformConfig};
+
+    #[test]
+    fn test_waveform_colors_follow_tokens() {
+        let tokens = MaterialTokens::default();
+        let colors = WaveformColors::from_tokens(&tokens);
+
+        assert_eq!(colors.played_rms, tokens.colors.primary.base);
+        assert_eq!(colors.background, tokens.colors.surface_container_low);
+    }
+
+    #[test]
+    fn test_waveform_scrubber_without_summary() {
+        let tokens = MaterialTokens::default();
+        let element = WaveformScrubber::<Message>::new(None, 0.5, &tokens).view();
+        let _ = element; // Just verify it compiles and runs
+    }
+
+    #[test]
+    fn test_waveform_scrubber_with_summary() {
+        let tokens = MaterialTokens::default();
+        let mut builder = WaveformBuilder::new(WaveformConfig::default(), 8000, 1);
+        builder.push_interleaved(&[0.5, -0.5].repeat(8000));
+        let summary = builder.finish();
+
+        let element = WaveformScrubber::new(Some(&summary), 1.5, &tokens)
+            .on_seek(|_| Message::NoOp)
+            .height(48.0)
+            .view();
+        let _ = element; // Just verify it compiles and runs
+    }
+}
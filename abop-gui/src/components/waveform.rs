@@ -0,0 +1,298 @@
+//! Waveform scrubber component
+//!
+//! Draws a [`WaveformSummary`] on a canvas as a seekable overview of an audiobook.
+//! The played portion is tinted with the primary color role, the remainder uses
+//! neutral surface tones, and clicking or dragging emits a seek message with the
+//! target position as a fraction of the total duration.
+
+use iced::mouse;
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, event};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+use abop_core::audio::waveform::{WaveformLevel, WaveformSummary};
+
+use crate::styling::material::MaterialTokens;
+
+/// Default height of the scrubber in logical pixels
+const DEFAULT_HEIGHT: f32 = 64.0;
+
+/// Width of a single waveform bar in logical pixels
+const BAR_WIDTH: f32 = 2.0;
+
+/// Gap between waveform bars in logical pixels
+const BAR_GAP: f32 = 1.0;
+
+/// Colors used to draw the waveform, derived from Material Design tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveformColors {
+    /// Track background
+    pub background: Color,
+    /// Peak envelope of the played portion
+    pub played_peak: Color,
+    /// RMS body of the played portion
+    pub played_rms: Color,
+    /// Peak envelope of the unplayed portion
+    pub unplayed_peak: Color,
+    /// RMS body of the unplayed portion
+    pub unplayed_rms: Color,
+    /// Playhead line
+    pub playhead: Color,
+    /// Hover indicator line
+    pub hover: Color,
+}
+
+impl WaveformColors {
+    /// Derives waveform colors from the Material color tokens
+    #[must_use]
+    pub fn from_tokens(tokens: &MaterialTokens) -> Self {
+        let colors = tokens.colors();
+        Self {
+            background: colors.surface_container_low,
+            played_peak: colors.primary.container,
+            played_rms: colors.primary.base,
+            unplayed_peak: colors.surface_container_highest,
+            unplayed_rms: colors.outline,
+            playhead: colors.primary.base,
+            hover: Color {
+                a: 0.6,
+                ..colors.on_surface_variant
+            },
+        }
+    }
+}
+
+/// Interaction state of the scrubber canvas
+#[derive(Debug, Default)]
+pub struct ScrubberState {
+    dragging: bool,
+}
+
+/// Seekable waveform overview drawn on an Iced canvas
+///
+/// # Examples
+/// ```
+/// use abop_gui::components::waveform::WaveformScrubber;
+/// use abop_gui::styling::material::MaterialTokens;
+///
+/// #[derive(Debug, Clone)]
+/// enum Message {
+///     Seek(f32),
+/// }
+///
+/// let tokens = MaterialTokens::default();
+/// let scrubber = WaveformScrubber::new(None, 0.25, &tokens).on_seek(Message::Seek);
+/// let _element: iced::Element<'_, Message> = scrubber.view();
+/// ```
+pub struct WaveformScrubber<'a, Message> {
+    summary: Option<&'a WaveformSummary>,
+    position: f32,
+    colors: WaveformColors,
+    height: f32,
+    on_seek: Option<Box<dyn Fn(f32) -> Message + 'a>>,
+}
+
+impl<'a, Message: 'a> WaveformScrubber<'a, Message> {
+    /// Creates a scrubber for the given summary and playback position (`0.0..=1.0`)
+    ///
+    /// When `summary` is `None` an empty track is drawn, which keeps the layout
+    /// stable while a waveform is still being generated.
+    #[must_use]
+    pub fn new(
+        summary: Option<&'a WaveformSummary>,
+        position: f32,
+        tokens: &MaterialTokens,
+    ) -> Self {
+        Self {
+            summary,
+            position: position.clamp(0.0, 1.0),
+            colors: WaveformColors::from_tokens(tokens),
+            height: DEFAULT_HEIGHT,
+            on_seek: None,
+        }
+    }
+
+    /// Sets the message produced when the user seeks to a position
+    #[must_use]
+    pub fn on_seek(mut self, on_seek: impl Fn(f32) -> Message + 'a) -> Self {
+        self.on_seek = Some(Box::new(on_seek));
+        self
+    }
+
+    /// Sets the height of the scrubber
+    #[must_use]
+    pub const fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Renders the scrubber into an element
+    #[must_use]
+    pub fn view(self) -> Element<'a, Message> {
+        let height = self.height;
+        Canvas::new(self)
+            .width(Length::Fill)
+            .height(Length::Fixed(height))
+            .into()
+    }
+}
+
+/// Converts a cursor x coordinate into a seek fraction
+fn fraction_at(bounds: Rectangle, position: Point) -> f32 {
+    if bounds.width <= 0.0 {
+        return 0.0;
+    }
+    ((position.x - bounds.x) / bounds.width).clamp(0.0, 1.0)
+}
+
+/// Returns the peak and RMS amplitude of the points covered by a bar
+fn bar_levels(level: &WaveformLevel, start: f32, end: f32) -> (f32, f32) {
+    let len = level.points.len();
+    if len == 0 {
+        return (0.0, 0.0);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let len_f = len as f32;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let first = ((start * len_f) as usize).min(len - 1);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let last = ((end * len_f).ceil() as usize).clamp(first + 1, len);
+
+    level.points[first..last]
+        .iter()
+        .fold((0.0f32, 0.0f32), |(peak, rms), point| {
+            (peak.max(point.peak()), rms.max(point.rms_amplitude()))
+        })
+}
+
+impl<Message> canvas::Program<Message> for WaveformScrubber<'_, Message> {
+    type State = ScrubberState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<Message>) {
+        let Some(on_seek) = &self.on_seek else {
+            return (event::Status::Ignored, None);
+        };
+
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(bounds) {
+                    state.dragging = true;
+                    let fraction = fraction_at(bounds, position);
+                    return (event::Status::Captured, Some(on_seek(fraction)));
+                }
+            }
+            canvas::Event::Mouse(mouse::Event::CursorMoved { position }) if state.dragging => {
+                let fraction = fraction_at(bounds, position);
+                return (event::Status::Captured, Some(on_seek(fraction)));
+            }
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                if state.dragging =>
+            {
+                state.dragging = false;
+                return (event::Status::Captured, None);
+            }
+            _ => {}
+        }
+
+        (event::Status::Ignored, None)
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        frame.fill_rectangle(Point::ORIGIN, bounds.size(), self.colors.background);
+
+        let center_y = bounds.height / 2.0;
+        let playhead_x = bounds.width * self.position;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bar_count = (bounds.width / (BAR_WIDTH + BAR_GAP)).floor().max(0.0) as usize;
+
+        if let Some(level) = self
+            .summary
+            .and_then(|summary| summary.level_for_width(bar_count))
+            .filter(|_| bar_count > 0)
+        {
+            #[allow(clippy::cast_precision_loss)]
+            let bar_count_f = bar_count as f32;
+            for bar in 0..bar_count {
+                #[allow(clippy::cast_precision_loss)]
+                let bar_f = bar as f32;
+                let (peak, rms) =
+                    bar_levels(level, bar_f / bar_count_f, (bar_f + 1.0) / bar_count_f);
+                let x = bar_f * (BAR_WIDTH + BAR_GAP);
+                let played = x + BAR_WIDTH / 2.0 <= playhead_x;
+                let (peak_color, rms_color) = if played {
+                    (self.colors.played_peak, self.colors.played_rms)
+                } else {
+                    (self.colors.unplayed_peak, self.colors.unplayed_rms)
+                };
+
+                let peak_height = (peak * bounds.height).max(1.0);
+                frame.fill_rectangle(
+                    Point::new(x, center_y - peak_height / 2.0),
+                    Size::new(BAR_WIDTH, peak_height),
+                    peak_color,
+                );
+                let rms_height = (rms * bounds.height).max(1.0);
+                frame.fill_rectangle(
+                    Point::new(x, center_y - rms_height / 2.0),
+                    Size::new(BAR_WIDTH, rms_height),
+                    rms_color,
+                );
+            }
+        }
+
+        if let Some(position) = cursor.position_in(bounds) {
+            frame.stroke(
+                &Path::line(
+                    Point::new(position.x, 0.0),
+                    Point::new(position.x, bounds.height),
+                ),
+                Stroke::default()
+                    .with_color(self.colors.hover)
+                    .with_width(1.0),
+            );
+        }
+
+        frame.stroke(
+            &Path::line(
+                Point::new(playhead_x, 0.0),
+                Point::new(playhead_x, bounds.height),
+            ),
+            Stroke::default()
+                .with_color(self.colors.playhead)
+                .with_width(2.0),
+        );
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if self.on_seek.is_none() {
+            mouse::Interaction::default()
+        } else if state.dragging {
+            mouse::Interaction::Grabbing
+        } else if cursor.is_over(bounds) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -3,8 +3,9 @@
 //! Handles messages that require async operations to update application data
 
 use crate::commands::library::scan_directory_async;
-use crate::messages::Message;
+use crate::messages::{Command, Message};
 use crate::state::AppState;
+use abop_core::models::JobType;
 use iced::Task;
 
 /// Handles GUI messages that require async operations
@@ -56,18 +57,170 @@ pub fn handle_gui_message(state: &mut AppState, message: Message) -> Option<Task
                     state.library.set_audiobooks(scan_result.audiobooks);
                     // scan_progress is cleared automatically by complete_scanning()
                     state.library.complete_scanning();
-                    Some(Task::none())
                 }
                 Err(_e) => {
                     state.library.error_scanning();
-                    Some(Task::none())
                 }
             }
+            // The scan job finished either way, refresh the job history
+            Some(Task::done(Message::command(Command::LoadJobHistory)))
+        }
+        Message::LibraryWatchUpdate(update) => {
+            log::info!(
+                "Library {} changed: {} updated, {} removed",
+                update.library_id,
+                update.updated.len(),
+                update.removed.len()
+            );
+            state.library.apply_watch_update(update);
+            Some(Task::none())
+        }
+        Message::JobHistoryLoaded(result) => {
+            match result {
+                Ok(jobs) => state.tasks.set_job_history(jobs),
+                Err(e) => log::error!("Failed to load job history: {e}"),
+            }
+            Some(Task::none())
+        }
+        Message::ResumableJobsFound(result) => {
+            let jobs = result.unwrap_or_else(|e| {
+                log::error!("Failed to load interrupted jobs: {e}");
+                Vec::new()
+            });
+            if !jobs.is_empty() {
+                log::info!("Resuming {} interrupted jobs", jobs.len());
+            }
+            // Each job runs as its own task and reports like a fresh scan or batch
+            let tasks = jobs.into_iter().map(|job| {
+                let command = match job.job_type {
+                    JobType::Scan => Command::ResumeScanJob { job_id: job.id },
+                    JobType::Process => Command::ResumeProcessingJob { job_id: job.id },
+                };
+                Task::done(Message::command(command))
+            });
+            Some(Task::batch(tasks))
         }
         Message::ScanProgress(progress) => {
             state.library.update_scan_progress(progress);
             Some(Task::none())
         }
+        Message::DuplicatesFound(result) => {
+            match result {
+                Ok(clusters) => {
+                    log::info!("Duplicate search found {} groups", clusters.len());
+                    state.library.set_duplicate_clusters(clusters);
+                }
+                Err(e) => {
+                    log::error!("Duplicate search failed: {e}");
+                    state.library.set_duplicate_clusters(Vec::new());
+                }
+            }
+            Some(Task::none())
+        }
+        Message::OrganizePlanned(result) => {
+            if let Err(e) = &result {
+                log::error!("Organize planning failed: {e}");
+            }
+            state.library.set_organize_plan(result);
+            Some(Task::none())
+        }
+        Message::CollectionsLoaded(result) => {
+            if let Err(e) = &result {
+                log::error!("Loading collections failed: {e}");
+            }
+            state.library.set_collections(result);
+            Some(Task::none())
+        }
+        Message::ProfilesLoaded(result) => {
+            if let Err(e) = &result {
+                log::error!("Loading profiles failed: {e}");
+            }
+            state.library.set_profiles(result);
+            Some(Task::none())
+        }
+        Message::ProfileCreated(result) => match result {
+            Ok(profile) => {
+                log::info!("Created listener profile {}", profile.name);
+                state.library.profile_name_input.clear();
+                state.library.switch_profile(profile.id.clone());
+                Some(Task::batch([
+                    Task::done(Message::command(Command::SaveActiveProfile {
+                        profile_id: profile.id,
+                    })),
+                    Task::done(Message::command(Command::LoadProfiles)),
+                ]))
+            }
+            Err(e) => {
+                log::error!("Creating profile failed: {e}");
+                state.library.set_profiles_error(e);
+                Some(Task::none())
+            }
+        },
+        Message::SelectionAssigned(result) => match result {
+            Ok(changed) => {
+                log::info!("Updated {changed} audiobooks in collection or tag");
+                state.library.collection_name_input.clear();
+                Some(Task::done(Message::command(Command::LoadCollections)))
+            }
+            Err(e) => {
+                log::error!("Updating collection or tag failed: {e}");
+                state.library.set_collections_error(e);
+                Some(Task::none())
+            }
+        },
+        Message::PlaybackProgressSaved(result) => {
+            if let Err(e) = result {
+                log::warn!("Saving playback progress failed: {e}");
+            }
+            Some(Task::none())
+        }
+        Message::ActiveProfileSaved(result) => {
+            if let Err(e) = result {
+                log::warn!("Remembering the active profile failed: {e}");
+            }
+            Some(Task::none())
+        }
+        Message::SelectionSaved(result) => {
+            match result {
+                Ok(selected) => log::debug!("Persisted selection of {selected} audiobooks"),
+                Err(e) => log::warn!("Persisting the selection failed: {e}"),
+            }
+            Some(Task::none())
+        }
+        Message::OrganizeComplete(result) => {
+            match result {
+                Ok(moved) => {
+                    log::info!("Organized library, moved {moved} files");
+                    state.library.apply_organize_plan();
+                    state.ui.close_organize();
+                }
+                Err(e) => {
+                    log::error!("Organizing library failed: {e}");
+                    state.library.set_organize_error(e);
+                }
+            }
+            Some(Task::none())
+        }
+        Message::AudioProcessingComplete(result) => {
+            state.player.complete_processing();
+            state.progress_cache.clear_processing_cache();
+            match result {
+                Ok(report) => {
+                    log::info!(
+                        "Audio processing complete: {} files, {} failed",
+                        report.summary.files_processed,
+                        report.summary.files_failed
+                    );
+                    state.player.set_processing_report(Some(report));
+                }
+                Err(e) => {
+                    log::error!("Audio processing failed: {e}");
+                    state.player.set_processing_report(None);
+                }
+            }
+            // The processing job finished either way, refresh the job history
+            Some(Task::done(Message::command(Command::LoadJobHistory)))
+        }
         _ => None,
     }
 }
@@ -9,6 +9,7 @@ mod ui_state_tests {
     use crate::test_utils::TestDataFactory;
     use crate::theme::ThemeMode;
     use std::path::PathBuf;
+    use std::time::Duration;
 
     // Test constants to reduce duplication and improve clarity
     mod test_constants {
@@ -156,6 +157,317 @@ mod ui_state_tests {
         assert!(task.is_some());
     }
 
+    #[test]
+    fn test_handle_duplicate_review() {
+        use abop_core::library::{DuplicateCandidate, DuplicateCluster};
+
+        let mut state = AppState::default();
+        let task = handle_ui_message(&mut state, Message::ShowDuplicates);
+        assert!(task.is_some());
+        assert!(state.ui.duplicates_open);
+        assert!(state.library.duplicate_clusters.is_none());
+
+        let keeper = crate::test_utils::create_test_audiobook(TEST_AUDIOBOOK_ID_1, TEST_TITLE_1);
+        let extra = crate::test_utils::create_test_audiobook(TEST_AUDIOBOOK_ID_2, TEST_TITLE_1);
+        state.library.set_duplicate_clusters(vec![DuplicateCluster {
+            title: TEST_TITLE_1.to_string(),
+            author: TEST_AUTHOR_A.to_string(),
+            keeper_id: keeper.id.clone(),
+            fingerprint_verified: false,
+            candidates: vec![
+                DuplicateCandidate::from_audiobook(&keeper),
+                DuplicateCandidate::from_audiobook(&extra),
+            ],
+        }]);
+
+        let task = handle_ui_message(&mut state, Message::SelectDuplicateExtras);
+        assert!(task.is_some());
+        assert!(!state.ui.duplicates_open);
+        assert_eq!(state.library.selected_audiobooks.len(), 1);
+        assert!(
+            state
+                .library
+                .selected_audiobooks
+                .contains(TEST_AUDIOBOOK_ID_2)
+        );
+    }
+
+    #[test]
+    fn test_handle_organize_preview() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::library::{OrganizePlan, PlannedMove};
+
+        let mut state = AppState::default();
+        let audiobook = crate::test_utils::create_test_audiobook(TEST_AUDIOBOOK_ID_1, TEST_TITLE_1);
+        state.library.audiobooks = vec![audiobook.clone()];
+
+        let task = handle_ui_message(&mut state, Message::ShowOrganize);
+        assert!(task.is_some());
+        assert!(state.ui.organize_open);
+        assert!(state.library.organize_plan.is_none());
+        // Nothing to apply while planning
+        assert!(handle_ui_message(&mut state, Message::ApplyOrganize).is_none());
+
+        let target = PathBuf::from("/library/Author/Title/book.mp3");
+        let plan = OrganizePlan {
+            library_root: PathBuf::from("/library"),
+            moves: vec![PlannedMove {
+                audiobook_id: audiobook.id.clone(),
+                from: audiobook.path.clone(),
+                to: target.clone(),
+            }],
+            unchanged: 0,
+            collisions: Vec::new(),
+        };
+        let task = handle_gui_message(&mut state, Message::OrganizePlanned(Ok(plan)));
+        assert!(task.is_some());
+        assert!(handle_ui_message(&mut state, Message::ApplyOrganize).is_some());
+
+        let task = handle_gui_message(&mut state, Message::OrganizeComplete(Ok(1)));
+        assert!(task.is_some());
+        assert!(!state.ui.organize_open);
+        assert_eq!(state.library.audiobooks[0].path, target);
+    }
+
+    #[test]
+    fn test_handle_assign_selection() {
+        use super::super::data_updates::handle_gui_message;
+        use crate::messages::SelectionTarget;
+
+        let mut state = AppState::default();
+        let audiobook = crate::test_utils::create_test_audiobook(TEST_AUDIOBOOK_ID_1, TEST_TITLE_1);
+        state.library.audiobooks = vec![audiobook.clone()];
+
+        let task = handle_ui_message(&mut state, Message::ShowCollections);
+        assert!(task.is_some());
+        assert!(state.ui.collections_open);
+        assert!(state.library.collections.is_none());
+
+        // Nothing to assign without a selection
+        let assign = Message::AssignSelection {
+            target: SelectionTarget::Collection("Commute queue".to_string()),
+            assign: true,
+        };
+        assert!(handle_ui_message(&mut state, assign.clone()).is_none());
+
+        let task = handle_ui_message(
+            &mut state,
+            Message::ToggleAudiobookSelection(audiobook.id.clone()),
+        );
+        assert!(task.is_some());
+        assert_eq!(state.library.selected_ids(), [audiobook.id]);
+        assert!(handle_ui_message(&mut state, assign).is_some());
+
+        let task = handle_ui_message(
+            &mut state,
+            Message::CollectionNameChanged("Commute queue".to_string()),
+        );
+        assert!(task.is_some());
+        let task = handle_gui_message(&mut state, Message::SelectionAssigned(Ok(1)));
+        assert!(task.is_some());
+        assert!(state.library.collection_name_input.is_empty());
+
+        let task = handle_gui_message(
+            &mut state,
+            Message::SelectionAssigned(Err("database is locked".to_string())),
+        );
+        assert!(task.is_some());
+        assert_eq!(
+            state.library.collections_error.as_deref(),
+            Some("database is locked")
+        );
+
+        let task = handle_ui_message(&mut state, Message::CloseCollections);
+        assert!(task.is_some());
+        assert!(!state.ui.collections_open);
+    }
+
+    #[test]
+    fn test_handle_profile_switching() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::models::{DEFAULT_PROFILE_ID, Profile};
+
+        let mut state = AppState::default();
+        assert_eq!(state.library.active_profile_id, DEFAULT_PROFILE_ID);
+
+        let task = handle_ui_message(&mut state, Message::ShowProfiles);
+        assert!(task.is_some());
+        assert!(state.ui.profiles_open);
+        assert!(state.library.profiles.is_none());
+
+        // Nothing to create without a name
+        assert!(handle_ui_message(&mut state, Message::CreateProfile).is_none());
+        let task = handle_ui_message(&mut state, Message::ProfileNameChanged("Sam".to_string()));
+        assert!(task.is_some());
+        assert!(handle_ui_message(&mut state, Message::CreateProfile).is_some());
+
+        let sam = Profile::new("Sam");
+        let task = handle_gui_message(&mut state, Message::ProfileCreated(Ok(sam.clone())));
+        assert!(task.is_some());
+        assert!(state.library.profile_name_input.is_empty());
+        assert_eq!(state.library.active_profile_id, sam.id);
+
+        let mut default = Profile::new("Default");
+        default.id = DEFAULT_PROFILE_ID.to_string();
+        let task = handle_gui_message(
+            &mut state,
+            Message::ProfilesLoaded(Ok(vec![default.clone(), sam.clone()])),
+        );
+        assert!(task.is_some());
+        assert_eq!(
+            state.library.active_profile().map(|p| &p.name),
+            Some(&sam.name)
+        );
+
+        let task = handle_ui_message(&mut state, Message::SwitchProfile(default.id.clone()));
+        assert!(task.is_some());
+        assert!(
+            state
+                .library
+                .active_profile()
+                .is_some_and(Profile::is_default)
+        );
+
+        // A deleted active profile falls back to the default one
+        state.library.switch_profile(sam.id);
+        let task = handle_gui_message(&mut state, Message::ProfilesLoaded(Ok(vec![default])));
+        assert!(task.is_some());
+        assert_eq!(state.library.active_profile_id, DEFAULT_PROFILE_ID);
+
+        let task = handle_ui_message(&mut state, Message::CloseProfiles);
+        assert!(task.is_some());
+        assert!(!state.ui.profiles_open);
+    }
+
+    #[test]
+    fn test_handle_job_history_loaded() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::models::{Job, JobStatus, JobType};
+
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+        let job = Job {
+            id: "job1".to_string(),
+            job_type: JobType::Scan,
+            status: JobStatus::Completed,
+            library_id: Some(TEST_LIBRARY_ID.to_string()),
+            parameters: "{}".to_string(),
+            total_items: 3,
+            processed_items: 3,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let task = handle_gui_message(&mut state, Message::JobHistoryLoaded(Ok(vec![job])));
+        assert!(task.is_some());
+        assert_eq!(state.tasks.job_history().len(), 1);
+        assert_eq!(state.tasks.job_history()[0].status, JobStatus::Completed);
+
+        // A failed load keeps the previous history
+        let task = handle_gui_message(
+            &mut state,
+            Message::JobHistoryLoaded(Err("database locked".to_string())),
+        );
+        assert!(task.is_some());
+        assert_eq!(state.tasks.job_history().len(), 1);
+    }
+
+    #[test]
+    fn test_handle_resumable_jobs_found() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::models::{Job, JobStatus, JobType};
+
+        let mut state = AppState::default();
+        let now = chrono::Utc::now();
+        let job = Job {
+            id: "job1".to_string(),
+            job_type: JobType::Scan,
+            status: JobStatus::Running,
+            library_id: Some(TEST_LIBRARY_ID.to_string()),
+            parameters: "{}".to_string(),
+            total_items: 3,
+            processed_items: 1,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Resumed jobs run as separate tasks, the history is left alone
+        let task = handle_gui_message(&mut state, Message::ResumableJobsFound(Ok(vec![job])));
+        assert!(task.is_some());
+        assert!(state.tasks.job_history().is_empty());
+
+        let task = handle_gui_message(
+            &mut state,
+            Message::ResumableJobsFound(Err("database locked".to_string())),
+        );
+        assert!(task.is_some());
+    }
+
+    #[test]
+    fn test_handle_audio_processing_complete() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::audio::processing::BatchProcessingReport;
+
+        let mut state = AppState::default();
+        state
+            .player
+            .start_processing(Some("Converting...".to_string()));
+
+        let report = BatchProcessingReport::new(Vec::new(), 2);
+        let task = handle_gui_message(&mut state, Message::AudioProcessingComplete(Ok(report)));
+        assert!(task.is_some());
+        assert!(!state.player.is_processing());
+        let stored = state.player.last_processing_report.as_ref().unwrap();
+        assert_eq!(stored.summary.files_failed, 2);
+
+        // A failed run clears the stale report
+        state.player.start_processing(None);
+        let task = handle_gui_message(
+            &mut state,
+            Message::AudioProcessingComplete(Err("no files".to_string())),
+        );
+        assert!(task.is_some());
+        assert!(!state.player.is_processing());
+        assert!(state.player.last_processing_report.is_none());
+    }
+
+    #[test]
+    fn test_handle_library_watch() {
+        use super::super::data_updates::handle_gui_message;
+        use abop_core::models::Audiobook;
+        use abop_core::scanner::WatchUpdate;
+
+        let mut state = AppState::default();
+        let root = PathBuf::from(TEST_DIRECTORY_PATH);
+
+        let task = handle_ui_message(&mut state, Message::ToggleLibraryWatch(root.clone()));
+        assert!(task.is_some());
+        assert!(state.library.is_library_watched(&root));
+
+        let existing = Audiobook::new(TEST_LIBRARY_ID, PathBuf::from(TEST_BOOK1_PATH));
+        state.library.set_audiobooks(vec![existing.clone()]);
+        state.library.select_audiobook(existing.id.clone());
+
+        let added = Audiobook::new(TEST_LIBRARY_ID, PathBuf::from(TEST_BOOK2_PATH));
+        let update = WatchUpdate {
+            library_id: TEST_LIBRARY_ID.to_string(),
+            updated: vec![added.clone()],
+            removed: vec![existing.path.clone()],
+            errors: 0,
+        };
+        let task = handle_gui_message(&mut state, Message::LibraryWatchUpdate(update));
+        assert!(task.is_some());
+        assert_eq!(state.library.audiobooks.len(), 1);
+        assert_eq!(state.library.audiobooks[0].id, added.id);
+        assert!(state.library.selected_audiobooks.is_empty());
+
+        let task = handle_ui_message(&mut state, Message::ToggleLibraryWatch(root.clone()));
+        assert!(task.is_some());
+        assert!(!state.library.is_library_watched(&root));
+    }
+
     #[test]
     fn test_handle_select_recent_directory() {
         let mut state = AppState::default();
@@ -195,6 +507,38 @@ mod ui_state_tests {
         assert!(task.is_some());
     }
 
+    #[test]
+    fn test_handle_seek_without_playback() {
+        let mut state = AppState::default();
+        let task = handle_ui_message(&mut state, Message::Seek(Duration::from_secs(30)));
+        assert!(task.is_some());
+        assert_eq!(state.player.player_state, abop_core::PlayerState::Stopped);
+    }
+
+    #[test]
+    fn test_handle_waveform_for_scrubber() {
+        use abop_core::audio::waveform::{WaveformBuilder, WaveformConfig};
+        use std::sync::Arc;
+
+        let mut builder = WaveformBuilder::new(WaveformConfig::default(), 8000, 1);
+        builder.push_interleaved(&vec![0.25; 8000 * 4]);
+        let waveform = Arc::new(builder.finish());
+        let mut state = AppState::default();
+
+        let loaded = Ok((TEST_AUDIOBOOK_ID.to_string(), waveform.clone()));
+        let task = handle_ui_message(&mut state, Message::WaveformLoaded(loaded));
+        assert!(task.is_some());
+        assert!(state.player.waveform.is_none(), "nothing is playing");
+
+        state.player.set_waveform(Some(waveform));
+        state.player.set_position(Duration::from_secs(1));
+        assert!((state.player.position_fraction() - 0.25).abs() < 0.01);
+
+        let _ = handle_ui_message(&mut state, Message::PlaybackStopped);
+        assert!(state.player.waveform.is_none());
+        assert_eq!(state.player.position, Duration::ZERO);
+    }
+
     #[test]
     fn test_handle_reset_redraw_flag() {
         let mut state = AppState::default();
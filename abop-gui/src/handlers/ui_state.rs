@@ -3,11 +3,15 @@
 //! Handles messages that update UI state without requiring async operations
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use abop_core::audio::waveform::WaveformSummary;
 
 use iced::Task;
 
 use crate::constants::{DEFAULT_SORT_COLUMN, VALID_SORT_COLUMNS};
-use crate::messages::Message;
+use crate::messages::{Command as GuiCommand, Message, SelectionTarget};
 use crate::state::AppState;
 use crate::theme::ThemeMode;
 use crate::utils::path_utils::PathCompare;
@@ -19,6 +23,24 @@ pub fn handle_ui_message(state: &mut AppState, message: Message) -> Option<Task<
         Message::ShowSettings => handle_show_settings(state),
         Message::CloseSettings => handle_close_settings(state),
         Message::ShowRecentDirectories => handle_show_recent_directories(state),
+        Message::ShowDuplicates => handle_show_duplicates(state),
+        Message::CloseDuplicates => handle_close_duplicates(state),
+        Message::SelectDuplicateExtras => handle_select_duplicate_extras(state),
+        Message::ShowOrganize => handle_show_organize(state),
+        Message::CloseOrganize => handle_close_organize(state),
+        Message::ApplyOrganize => handle_apply_organize(state),
+        Message::ShowCollections => handle_show_collections(state),
+        Message::CloseCollections => handle_close_collections(state),
+        Message::CollectionNameChanged(name) => handle_collection_name_changed(state, name),
+        Message::AssignSelection { target, assign } => {
+            handle_assign_selection(state, target, assign)
+        }
+        Message::ShowProfiles => handle_show_profiles(state),
+        Message::CloseProfiles => handle_close_profiles(state),
+        Message::ProfileNameChanged(name) => handle_profile_name_changed(state, name),
+        Message::CreateProfile => handle_create_profile(state),
+        Message::SwitchProfile(profile_id) => handle_switch_profile(state, profile_id),
+        Message::ToggleLibraryWatch(path) => handle_toggle_library_watch(state, path),
         Message::SetTheme(theme_mode) => handle_set_theme(state, theme_mode),
         Message::ToggleTheme => handle_toggle_theme(state),
         Message::ToggleSelectAll => handle_toggle_select_all(state),
@@ -32,6 +54,11 @@ pub fn handle_ui_message(state: &mut AppState, message: Message) -> Option<Task<
         Message::Stop => handle_stop(state),
         Message::Previous => handle_previous(state),
         Message::Next => handle_next(state),
+        Message::Seek(position) => handle_seek(state, position),
+        Message::PlaybackTick => handle_playback_tick(state),
+        Message::PlaybackStarted(result) => handle_playback_started(state, result),
+        Message::PlaybackStopped => handle_playback_stopped(state),
+        Message::WaveformLoaded(result) => handle_waveform_loaded(state, result),
         Message::ResetRedrawFlag => handle_reset_redraw_flag(state),
         Message::SortBy(column_id) => handle_sort_by(state, column_id),
         _ => None, // Not a UI message
@@ -48,6 +75,131 @@ fn handle_close_settings(state: &mut AppState) -> Option<Task<Message>> {
     Some(Task::none())
 }
 
+fn handle_toggle_library_watch(state: &mut AppState, path: PathBuf) -> Option<Task<Message>> {
+    if path.as_os_str().is_empty() {
+        return Some(Task::none());
+    }
+    let watched = state.library.toggle_library_watch(path.clone());
+    log::info!(
+        "{} watching library {}",
+        if watched { "Started" } else { "Stopped" },
+        path.display()
+    );
+    Some(Task::none())
+}
+
+fn handle_show_duplicates(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.open_duplicates();
+    state.library.start_duplicate_search();
+    Some(Task::done(Message::command(GuiCommand::FindDuplicates {
+        audiobooks: state.library.audiobooks.clone(),
+    })))
+}
+
+fn handle_close_duplicates(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.close_duplicates();
+    Some(Task::none())
+}
+
+fn handle_select_duplicate_extras(state: &mut AppState) -> Option<Task<Message>> {
+    state.library.select_duplicate_extras();
+    state.ui.close_duplicates();
+    Some(save_selection(state))
+}
+
+fn handle_show_organize(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.open_organize();
+    state.library.start_organize_planning();
+    Some(Task::done(Message::command(GuiCommand::PlanOrganize {
+        library_path: state.library.library_path.clone(),
+        audiobooks: state.library.audiobooks.clone(),
+    })))
+}
+
+fn handle_close_organize(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.close_organize();
+    Some(Task::none())
+}
+
+fn handle_apply_organize(state: &mut AppState) -> Option<Task<Message>> {
+    let plan = state
+        .library
+        .organize_plan
+        .clone()
+        .filter(|plan| !plan.moves.is_empty() && !plan.has_collisions())?;
+    Some(Task::done(Message::command(GuiCommand::ApplyOrganize {
+        plan,
+    })))
+}
+
+fn handle_show_collections(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.open_collections();
+    state.library.start_collections_loading();
+    Some(Task::done(Message::command(GuiCommand::LoadCollections)))
+}
+
+fn handle_close_collections(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.close_collections();
+    Some(Task::none())
+}
+
+fn handle_collection_name_changed(state: &mut AppState, name: String) -> Option<Task<Message>> {
+    state.library.collection_name_input = name;
+    state.library.mark_for_redraw();
+    Some(Task::none())
+}
+
+fn handle_assign_selection(
+    state: &mut AppState,
+    target: SelectionTarget,
+    assign: bool,
+) -> Option<Task<Message>> {
+    let audiobook_ids = state.library.selected_ids();
+    if audiobook_ids.is_empty() || target.name().trim().is_empty() {
+        return None;
+    }
+    Some(Task::done(Message::command(GuiCommand::AssignSelection {
+        target,
+        audiobook_ids,
+        assign,
+    })))
+}
+
+fn handle_show_profiles(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.open_profiles();
+    state.library.start_profiles_loading();
+    Some(Task::done(Message::command(GuiCommand::LoadProfiles)))
+}
+
+fn handle_close_profiles(state: &mut AppState) -> Option<Task<Message>> {
+    state.ui.close_profiles();
+    Some(Task::none())
+}
+
+fn handle_profile_name_changed(state: &mut AppState, name: String) -> Option<Task<Message>> {
+    state.library.profile_name_input = name;
+    state.library.mark_for_redraw();
+    Some(Task::none())
+}
+
+fn handle_create_profile(state: &mut AppState) -> Option<Task<Message>> {
+    let name = state.library.profile_name_input.trim().to_string();
+    if name.is_empty() {
+        return None;
+    }
+    Some(Task::done(Message::command(GuiCommand::CreateProfile {
+        name,
+    })))
+}
+
+fn handle_switch_profile(state: &mut AppState, profile_id: String) -> Option<Task<Message>> {
+    log::info!("Switching to listener profile {profile_id}");
+    state.library.switch_profile(profile_id.clone());
+    Some(Task::done(Message::command(
+        GuiCommand::SaveActiveProfile { profile_id },
+    )))
+}
+
 fn handle_show_recent_directories(state: &mut AppState) -> Option<Task<Message>> {
     state.ui.recent_directories_open = true;
     Some(Task::none())
@@ -70,8 +222,8 @@ fn handle_play_pause(state: &mut AppState) -> Option<Task<Message>> {
     let current_state = crate::audio::player::get_player_state();
     if current_state == abop_core::PlayerState::Playing {
         // Stop audio playback
-        crate::audio::player::stop_audio();
         state.player.player_state = abop_core::PlayerState::Paused;
+        return Some(stop_and_save_progress(state));
     } else {
         // Start playback if we have a file selected
         if let Some(current_file) = &state.player.current_playing_file {
@@ -94,6 +246,7 @@ fn handle_play_pause(state: &mut AppState) -> Option<Task<Message>> {
                     crate::audio::player::play_selected_audio(
                         vec![audiobook.id.clone()],
                         vec![audiobook.clone()],
+                        state.library.active_profile_id.clone(),
                     ),
                     Message::PlaybackStarted,
                 ));
@@ -112,6 +265,7 @@ fn handle_play_pause(state: &mut AppState) -> Option<Task<Message>> {
                     crate::audio::player::play_selected_audio(
                         vec![audiobook.id.clone()],
                         vec![audiobook.clone()],
+                        state.library.active_profile_id.clone(),
                     ),
                     Message::PlaybackStarted,
                 ));
@@ -124,8 +278,100 @@ fn handle_play_pause(state: &mut AppState) -> Option<Task<Message>> {
 
 fn handle_stop(state: &mut AppState) -> Option<Task<Message>> {
     log::info!("Stop button pressed");
-    crate::audio::player::stop_audio();
     state.player.player_state = abop_core::PlayerState::Stopped;
+    state.player.clear_track();
+    Some(stop_and_save_progress(state))
+}
+
+/// Stops playback and saves where the active profile stopped
+fn stop_and_save_progress(state: &AppState) -> Task<Message> {
+    crate::audio::player::stop_audio().map_or_else(Task::none, |(audiobook, position)| {
+        Task::perform(
+            crate::audio::player::save_playback_progress(
+                state.library.active_profile_id.clone(),
+                audiobook.id,
+                position,
+            ),
+            Message::PlaybackProgressSaved,
+        )
+    })
+}
+
+fn handle_seek(state: &mut AppState, position: Duration) -> Option<Task<Message>> {
+    log::info!("Seeking to {:.1}s", position.as_secs_f64());
+    match crate::audio::player::seek_audio(position) {
+        Ok(()) => state.player.set_position(position),
+        Err(e) => log::warn!("{e}"),
+    }
+    Some(Task::none())
+}
+
+fn handle_playback_tick(state: &mut AppState) -> Option<Task<Message>> {
+    if crate::audio::player::get_player_state() == abop_core::PlayerState::Stopped {
+        // The track ended on its own
+        return handle_playback_stopped(state);
+    }
+    state
+        .player
+        .set_position(crate::audio::player::playback_position());
+    Some(Task::none())
+}
+
+/// Marks the track as playing and loads its waveform for the scrubber
+fn handle_playback_started(
+    state: &mut AppState,
+    result: Result<String, String>,
+) -> Option<Task<Message>> {
+    match result {
+        Ok(status) => {
+            log::info!("{status}");
+            state
+                .player
+                .set_player_state(abop_core::PlayerState::Playing);
+            state.player.clear_track();
+            state
+                .player
+                .set_position(crate::audio::player::playback_position());
+            Some(
+                crate::audio::player::now_playing().map_or_else(Task::none, |audiobook| {
+                    Task::perform(
+                        crate::audio::player::load_waveform(audiobook),
+                        Message::WaveformLoaded,
+                    )
+                }),
+            )
+        }
+        Err(e) => {
+            log::error!("{e}");
+            Some(Task::none())
+        }
+    }
+}
+
+fn handle_playback_stopped(state: &mut AppState) -> Option<Task<Message>> {
+    state
+        .player
+        .set_player_state(abop_core::PlayerState::Stopped);
+    state.player.clear_track();
+    Some(Task::none())
+}
+
+/// Shows a loaded waveform unless another track started in the meantime
+fn handle_waveform_loaded(
+    state: &mut AppState,
+    result: Result<(String, Arc<WaveformSummary>), String>,
+) -> Option<Task<Message>> {
+    match result {
+        Ok((audiobook_id, waveform)) => {
+            let current = crate::audio::player::now_playing().map(|audiobook| audiobook.id);
+            if current.as_deref() == Some(audiobook_id.as_str()) {
+                state.player.set_waveform(Some(waveform));
+            } else {
+                log::debug!("Ignoring waveform of {audiobook_id}, which is no longer playing");
+            }
+        }
+        Err(e) => log::warn!("Loading the waveform failed: {e}"),
+    }
     Some(Task::none())
 }
 
@@ -152,6 +398,7 @@ fn handle_previous(state: &mut AppState) -> Option<Task<Message>> {
                     crate::audio::player::play_selected_audio(
                         vec![previous_audiobook.id.clone()],
                         vec![previous_audiobook.clone()],
+                        state.library.active_profile_id.clone(),
                     ),
                     Message::PlaybackStarted,
                 ));
@@ -179,6 +426,7 @@ fn handle_previous(state: &mut AppState) -> Option<Task<Message>> {
                 crate::audio::player::play_selected_audio(
                     vec![audiobook.id.clone()],
                     vec![audiobook.clone()],
+                    state.library.active_profile_id.clone(),
                 ),
                 Message::PlaybackStarted,
             ));
@@ -210,6 +458,7 @@ fn handle_next(state: &mut AppState) -> Option<Task<Message>> {
                     crate::audio::player::play_selected_audio(
                         vec![next_audiobook.id.clone()],
                         vec![next_audiobook.clone()],
+                        state.library.active_profile_id.clone(),
                     ),
                     Message::PlaybackStarted,
                 ));
@@ -237,6 +486,7 @@ fn handle_next(state: &mut AppState) -> Option<Task<Message>> {
                 crate::audio::player::play_selected_audio(
                     vec![audiobook.id.clone()],
                     vec![audiobook.clone()],
+                    state.library.active_profile_id.clone(),
                 ),
                 Message::PlaybackStarted,
             ));
@@ -311,7 +561,7 @@ fn handle_toggle_select_all(state: &mut AppState) -> Option<Task<Message>> {
             .collect();
         log::info!("Selected all {} audiobooks", state.library.audiobooks.len());
     }
-    Some(Task::none())
+    Some(save_selection(state))
 }
 
 fn handle_toggle_auto_save_library(state: &mut AppState) -> Option<Task<Message>> {
@@ -346,5 +596,12 @@ fn handle_toggle_audiobook_selection(
             .insert(audiobook_id.clone());
         log::info!("Selected audiobook: {audiobook_id}");
     }
-    Some(Task::none())
+    Some(save_selection(state))
+}
+
+/// Persists the current selection for commands that work on the `selected` flag
+fn save_selection(state: &AppState) -> Task<Message> {
+    Task::done(Message::command(GuiCommand::SaveSelection {
+        audiobook_ids: state.library.selected_ids(),
+    }))
 }
@@ -38,6 +38,10 @@ pub mod audio;
 // Library scanning and management
 pub mod library;
 
+// Media key and desktop widget control over D-Bus
+#[cfg(target_os = "linux")]
+pub mod mpris;
+
 // Utility functions
 pub mod utils;
 
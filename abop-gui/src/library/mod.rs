@@ -1,5 +1,7 @@
 //! Library module for scanning and managing audiobook libraries
 
 mod scanner;
+mod watcher;
 
 pub use scanner::*;
+pub use watcher::watch_library;
@@ -43,8 +43,49 @@ pub async fn open_directory_dialog() -> Option<PathBuf> {
 }
 
 /// Scans a library directory and returns a task that will complete with the scan result
+///
+/// The scan is recorded as a persisted job so it can resume after a crash.
 pub async fn scan_library(db: Database, library: Library) -> Result<ScanResult> {
     let scanner = LibraryScanner::new(db, library);
+    let scanner = match scanner.clone().with_job_tracking() {
+        Ok(tracked) => tracked,
+        Err(e) => {
+            log::warn!("Scanning without job tracking: {e}");
+            scanner
+        }
+    };
+    run_scanner(scanner).await
+}
+
+/// Resumes an interrupted scan job
+///
+/// Files the job already committed are skipped, so the result lists every
+/// audiobook of the library rather than only those scanned after resuming.
+pub async fn resume_scan_job(db: Database, job_id: String) -> Result<ScanResult> {
+    let scanner = {
+        let db = db.clone();
+        tokio::task::spawn_blocking(move || {
+            let job = db.job_repository().find_by_id(&job_id)?.ok_or_else(|| {
+                abop_core::error::AppError::InvalidData(format!("Scan job {job_id} not found"))
+            })?;
+            LibraryScanner::from_job(&db, &job)?.ok_or_else(|| {
+                abop_core::error::AppError::InvalidData(format!(
+                    "Library of scan job {job_id} no longer exists"
+                ))
+            })
+        })
+        .await
+        .map_err(abop_core::error::AppError::from)??
+    };
+
+    let library_id = scanner.library().id.clone();
+    let mut result = run_scanner(scanner).await?;
+    result.audiobooks = db.audiobook_repository().find_by_library(&library_id)?;
+    Ok(result)
+}
+
+/// Runs a scanner to completion while draining its progress updates
+async fn run_scanner(scanner: LibraryScanner) -> Result<ScanResult> {
     let (tx, mut rx) = tokio::sync::mpsc::channel(100);
     let (std_tx, std_rx) = std::sync::mpsc::channel();
 
@@ -0,0 +1,92 @@
+//! Library watch subscriptions for the GUI
+//!
+//! Each watched library runs a core [`LibraryWatcher`] on a blocking thread
+//! and forwards applied changes to the application as messages.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use abop_core::db::Database;
+use abop_core::models::Library;
+use abop_core::scanner::LibraryWatcher;
+use iced::Subscription;
+use iced::futures::SinkExt;
+
+use crate::messages::Message;
+
+/// Stops the watcher thread when the subscription is dropped
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Subscription that keeps the library at `path` in sync with the filesystem
+pub fn watch_library(path: PathBuf) -> Subscription<Message> {
+    Subscription::run_with_id(
+        ("library-watch", path.clone()),
+        iced::stream::channel(100, move |mut output| async move {
+            let library = match open_library(path.clone()).await {
+                Ok(library) => library,
+                Err(e) => {
+                    log::error!("Cannot watch library {}: {e}", path.display());
+                    return;
+                }
+            };
+
+            let (db, library) = library;
+            let watcher = LibraryWatcher::new(db, vec![library]);
+            let _guard = CancelOnDrop(watcher.cancellation_token());
+            let mut sender = output.clone();
+
+            let result = tokio::task::spawn_blocking(move || {
+                watcher.run(|update| {
+                    if let Err(e) = sender.try_send(Message::LibraryWatchUpdate(update)) {
+                        log::warn!("Dropped library watch update: {e}");
+                    }
+                })
+            })
+            .await;
+
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => log::error!("Watching {} failed: {e}", path.display()),
+                Err(e) => log::error!("Watch task for {} panicked: {e}", path.display()),
+            }
+            let _ = output.close().await;
+        }),
+    )
+}
+
+/// Opens the application database and finds or creates the library at `path`
+async fn open_library(path: PathBuf) -> Result<(Database, Library), String> {
+    tokio::task::spawn_blocking(move || {
+        let db = Database::open_app_database().map_err(|e| e.to_string())?;
+        let library = match db
+            .libraries()
+            .find_by_path(&path)
+            .map_err(|e| e.to_string())?
+        {
+            Some(library) => library,
+            None => {
+                let name = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("Audiobook Library");
+                let library_id = db
+                    .add_library_with_path(name, path.clone())
+                    .map_err(|e| e.to_string())?;
+                db.libraries()
+                    .find_by_id(&library_id)
+                    .map_err(|e| e.to_string())?
+                    .ok_or_else(|| "Library not found after creation".to_string())?
+            }
+        };
+        Ok((db, library))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -56,6 +56,9 @@ fn main() -> iced::Result {
         log::warn!("Failed to load configuration: {e}. Using defaults.");
         Config::default()
     });
+    if let Err(e) = abop_gui::audio::player::set_playback_effects(&config.processing) {
+        log::warn!("{e}. Playing without effects.");
+    }
 
     // Run the application using the Application trait with proper window settings
     iced::application(App::title, App::update, App::view)
@@ -65,7 +68,7 @@ fn main() -> iced::Result {
             config.window.min_width as f32,
             config.window.min_height as f32,
         ))
-        .run_with(App::initial)
+        .run_with(move || App::initial_with_config(&config))
         .map_err(|e| {
             log::error!("Application failed to run: {e}");
             e
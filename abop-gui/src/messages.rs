@@ -1,8 +1,14 @@
 //! Message and command definitions for the GUI application
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use abop_core::models::Audiobook;
+use abop_core::audio::processing::BatchProcessingReport;
+use abop_core::audio::waveform::WaveformSummary;
+use abop_core::library::{DuplicateCluster, OrganizePlan};
+use abop_core::models::{Audiobook, Collection, Job, Profile, Tag};
+use abop_core::scanner::WatchUpdate;
 use serde::{Deserialize, Serialize};
 
 use crate::{router::Route, state::DirectoryInfo, theme::ThemeMode};
@@ -52,6 +58,69 @@ pub enum Message {
     ScanComplete(Result<crate::library::ScanResult, String>),
     /// Progress information for a scan operation
     ScanProgress(abop_core::scanner::ScanProgress),
+    /// Show the duplicate review dialog and start a duplicate search
+    ShowDuplicates,
+    /// Close the duplicate review dialog
+    CloseDuplicates,
+    /// Result of a duplicate search
+    DuplicatesFound(Result<Vec<DuplicateCluster>, String>),
+    /// Select every copy that is not the recommended keeper
+    SelectDuplicateExtras,
+    /// Show the organize preview dialog and plan the moves for the current library
+    ShowOrganize,
+    /// Close the organize preview dialog
+    CloseOrganize,
+    /// Result of planning the organize moves
+    OrganizePlanned(Result<OrganizePlan, String>),
+    /// Move the files as shown in the organize preview
+    ApplyOrganize,
+    /// Result of moving the files, with the number of moved files
+    OrganizeComplete(Result<usize, String>),
+    /// Show the collections and tags dialog
+    ShowCollections,
+    /// Close the collections and tags dialog
+    CloseCollections,
+    /// Result of loading the collections and tags
+    CollectionsLoaded(Result<(Vec<Collection>, Vec<Tag>), String>),
+    /// The collection or tag name typed in the collections dialog changed
+    CollectionNameChanged(String),
+    /// Add the selected audiobooks to a collection or tag, or remove them
+    AssignSelection {
+        /// Collection or tag to change
+        target: SelectionTarget,
+        /// Whether to add (`true`) or remove (`false`) the selection
+        assign: bool,
+    },
+    /// Result of assigning the selection, with the number of changed audiobooks
+    SelectionAssigned(Result<usize, String>),
+    /// Result of persisting the selection, with the number of selected audiobooks
+    SelectionSaved(Result<usize, String>),
+    /// Result of saving the active profile's position in the stopped audiobook
+    PlaybackProgressSaved(Result<(), String>),
+    /// Result of remembering the active listener profile in the app config
+    ActiveProfileSaved(Result<(), String>),
+    /// Show the listener profiles dialog
+    ShowProfiles,
+    /// Close the listener profiles dialog
+    CloseProfiles,
+    /// Result of loading the listener profiles
+    ProfilesLoaded(Result<Vec<Profile>, String>),
+    /// The profile name typed in the profiles dialog changed
+    ProfileNameChanged(String),
+    /// Create a profile with the typed name
+    CreateProfile,
+    /// Result of creating a profile
+    ProfileCreated(Result<Profile, String>),
+    /// Make the profile with this ID the active listener
+    SwitchProfile(String),
+    /// Persisted job history was loaded
+    JobHistoryLoaded(Result<Vec<Job>, String>),
+    /// Interrupted jobs that can resume were loaded
+    ResumableJobsFound(Result<Vec<Job>, String>),
+    /// Start or stop watching a library directory for changes
+    ToggleLibraryWatch(PathBuf),
+    /// A watched library changed and the database was updated
+    LibraryWatchUpdate(WatchUpdate),
 
     // ===== Audiobook Selection =====
     /// Select a single audiobook by ID
@@ -76,18 +145,24 @@ pub enum Message {
     Previous,
     /// Play the next track
     Next,
+    /// Move playback to a position in the current track
+    Seek(Duration),
+    /// Refresh the playback position while a track plays
+    PlaybackTick,
     /// Stop all playback
     Stop,
     /// Process the selected audiobooks
     ProcessSelected,
 
     // ===== System Messages =====
-    /// Result of an audio processing operation
-    AudioProcessingComplete(Result<String, String>),
+    /// Result of an audio processing operation with its before/after report
+    AudioProcessingComplete(Result<BatchProcessingReport, String>),
     /// Result of starting playback
     PlaybackStarted(Result<String, String>),
     /// Notification that playback has stopped
     PlaybackStopped,
+    /// Waveform overview for the scrubber, with the ID of its audiobook
+    WaveformLoaded(Result<(String, Arc<WaveformSummary>), String>),
     /// Result of saving application state
     StateSaveComplete(Result<String, String>),
     /// Progress update for state saving (0.0 to 1.0)
@@ -142,6 +217,78 @@ pub enum Command {
         directory_path: PathBuf,
     },
 
+    /// Find duplicate audiobooks
+    FindDuplicates {
+        /// Audiobooks to search for duplicates
+        audiobooks: Vec<Audiobook>,
+    },
+
+    /// Plan moving a library's files into the organizer layout
+    PlanOrganize {
+        /// Root of the library to organize
+        library_path: PathBuf,
+        /// Audiobooks of the library
+        audiobooks: Vec<Audiobook>,
+    },
+
+    /// Move files and update the database as described by an organize plan
+    ApplyOrganize {
+        /// The previewed plan
+        plan: OrganizePlan,
+    },
+
+    /// Load the user collections and tags
+    LoadCollections,
+
+    /// Add audiobooks to a collection or tag, or remove them
+    AssignSelection {
+        /// Collection or tag to change, created on first use
+        target: SelectionTarget,
+        /// IDs of the selected audiobooks
+        audiobook_ids: Vec<String>,
+        /// Whether to add (`true`) or remove (`false`) the audiobooks
+        assign: bool,
+    },
+
+    /// Persist the selection to the `selected` flag of the audiobooks
+    SaveSelection {
+        /// IDs of the selected audiobooks
+        audiobook_ids: Vec<String>,
+    },
+
+    /// Load the listener profiles
+    LoadProfiles,
+
+    /// Remember the active listener profile in the app config
+    SaveActiveProfile {
+        /// ID of the profile switched to
+        profile_id: String,
+    },
+
+    /// Create a listener profile
+    CreateProfile {
+        /// Name of the new profile
+        name: String,
+    },
+
+    /// Resume scan and processing jobs interrupted by a crash or shutdown
+    ResumeJobs,
+
+    /// Load the persisted job history
+    LoadJobHistory,
+
+    /// Resume an interrupted scan job
+    ResumeScanJob {
+        /// ID of the persisted job
+        job_id: String,
+    },
+
+    /// Resume an interrupted processing job
+    ResumeProcessingJob {
+        /// ID of the persisted job
+        job_id: String,
+    },
+
     // ===== Audio Processing =====
     /// Convert selected audiobooks to mono
     ConvertToMono {
@@ -173,6 +320,25 @@ pub enum Command {
     Quit,
 }
 
+/// A user grouping the selected audiobooks can be assigned to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionTarget {
+    /// Collection with the given name
+    Collection(String),
+    /// Tag with the given name
+    Tag(String),
+}
+
+impl SelectionTarget {
+    /// Name of the collection or tag
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Collection(name) | Self::Tag(name) => name,
+        }
+    }
+}
+
 impl Command {
     /// Creates a new scan library command
     pub fn scan_library(library_path: PathBuf) -> Self {
@@ -0,0 +1,302 @@
+//! The `org.mpris.MediaPlayer2` D-Bus interfaces
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use abop_core::PlayerState;
+use abop_core::audio::player::{MAX_PLAYBACK_RATE, MIN_PLAYBACK_RATE};
+use iced::futures::channel::mpsc::Sender;
+use zbus::interface;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+use super::{PlaybackSnapshot, SnapshotSource, Track};
+use crate::messages::{Command, Message};
+
+/// Forward a command from the bus to the application
+fn send(commands: &Sender<Message>, message: Message) {
+    if let Err(e) = commands.clone().try_send(message) {
+        log::warn!("Dropped media control command: {e}");
+    }
+}
+
+/// A duration in the microseconds MPRIS uses
+pub(super) fn micros(duration: Duration) -> i64 {
+    i64::try_from(duration.as_micros()).unwrap_or(i64::MAX)
+}
+
+/// Object path identifying a track
+///
+/// Audiobook IDs contain hyphens, which object paths do not allow.
+pub(super) fn track_id(track: &Track) -> OwnedObjectPath {
+    let id: String = track
+        .id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    ObjectPath::try_from(format!("/org/abop/track/{id}"))
+        .map_or_else(|_| OwnedObjectPath::default(), OwnedObjectPath::from)
+}
+
+/// `file://` URL of a local path
+pub(super) fn file_url(path: &Path) -> String {
+    let mut url = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            url.push(char::from(byte));
+        } else {
+            let _ = write!(url, "%{byte:02X}");
+        }
+    }
+    url
+}
+
+/// MPRIS metadata of a track
+fn metadata(track: &Track) -> HashMap<String, OwnedValue> {
+    let mut metadata = HashMap::new();
+    let mut insert = |key: &str, value: Value<'_>| {
+        if let Ok(value) = value.try_to_owned() {
+            metadata.insert(key.to_string(), value);
+        }
+    };
+    insert("mpris:trackid", Value::from(track_id(track).into_inner()));
+    insert("xesam:url", Value::from(file_url(&track.path)));
+    if let Some(title) = &track.title {
+        insert("xesam:title", Value::from(title.as_str()));
+        insert("xesam:album", Value::from(title.as_str()));
+    }
+    if let Some(author) = &track.author {
+        insert("xesam:artist", Value::from(vec![author.as_str()]));
+    }
+    if let Some(length) = track.length {
+        insert("mpris:length", Value::from(micros(length)));
+    }
+    if let Some(art_path) = &track.art_path {
+        insert("mpris:artUrl", Value::from(file_url(art_path)));
+    }
+    metadata
+}
+
+/// The `org.mpris.MediaPlayer2` root interface
+pub struct MediaPlayer2 {
+    commands: Sender<Message>,
+}
+
+impl MediaPlayer2 {
+    /// Create the interface, sending commands to `commands`
+    #[must_use]
+    pub const fn new(commands: Sender<Message>) -> Self {
+        Self { commands }
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl MediaPlayer2 {
+    /// Raising the window is not supported
+    fn raise(&self) {}
+
+    /// Quit the application
+    fn quit(&self) {
+        send(&self.commands, Message::ExecuteCommand(Command::Quit));
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_quit(&self) -> bool {
+        true
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn identity(&self) -> &str {
+        "ABOP"
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// The `org.mpris.MediaPlayer2.Player` interface
+pub struct Player {
+    source: SnapshotSource,
+    commands: Sender<Message>,
+}
+
+impl Player {
+    /// Create the interface, reading state from `source` and sending
+    /// commands to `commands`
+    #[must_use]
+    pub fn new(source: SnapshotSource, commands: Sender<Message>) -> Self {
+        Self { source, commands }
+    }
+
+    fn snapshot(&self) -> PlaybackSnapshot {
+        (self.source)()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn next(&self) {
+        send(&self.commands, Message::Next);
+    }
+
+    fn previous(&self) {
+        send(&self.commands, Message::Previous);
+    }
+
+    fn pause(&self) {
+        if self.snapshot().state == PlayerState::Playing {
+            send(&self.commands, Message::PlayPause);
+        }
+    }
+
+    fn play_pause(&self) {
+        send(&self.commands, Message::PlayPause);
+    }
+
+    fn stop(&self) {
+        send(&self.commands, Message::Stop);
+    }
+
+    fn play(&self) {
+        if self.snapshot().state != PlayerState::Playing {
+            send(&self.commands, Message::PlayPause);
+        }
+    }
+
+    /// Move by `offset` microseconds; seeking past the end skips to the next track
+    fn seek(&self, offset: i64) {
+        let snapshot = self.snapshot();
+        let Some(track) = snapshot.track else {
+            return;
+        };
+        let distance = Duration::from_micros(offset.unsigned_abs());
+        let target = if offset < 0 {
+            snapshot.position.saturating_sub(distance)
+        } else {
+            snapshot.position + distance
+        };
+        if track.length.is_some_and(|length| target > length) {
+            send(&self.commands, Message::Next);
+        } else {
+            send(&self.commands, Message::Seek(target));
+        }
+    }
+
+    /// Move to `position` microseconds, ignored unless `track` is still loaded
+    fn set_position(&self, track: ObjectPath<'_>, position: i64) {
+        let Some(loaded) = self.snapshot().track else {
+            return;
+        };
+        let Ok(position) = u64::try_from(position).map(Duration::from_micros) else {
+            return;
+        };
+        if track.as_str() != track_id(&loaded).as_str()
+            || loaded.length.is_some_and(|length| position > length)
+        {
+            return;
+        }
+        send(&self.commands, Message::Seek(position));
+    }
+
+    fn open_uri(&self, _uri: &str) -> zbus::fdo::Result<()> {
+        Err(zbus::fdo::Error::NotSupported(
+            "Opening URIs is not supported".to_string(),
+        ))
+    }
+
+    /// Emitted when the position jumps
+    #[zbus(signal)]
+    pub async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> &str {
+        match self.snapshot().state {
+            PlayerState::Playing => "Playing",
+            PlayerState::Paused => "Paused",
+            PlayerState::Stopped => "Stopped",
+        }
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        self.snapshot().rate.into()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn minimum_rate(&self) -> f64 {
+        MIN_PLAYBACK_RATE.into()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn maximum_rate(&self) -> f64 {
+        MAX_PLAYBACK_RATE.into()
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        self.snapshot()
+            .track
+            .as_ref()
+            .map(metadata)
+            .unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.snapshot().volume.into()
+    }
+
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> i64 {
+        micros(self.snapshot().position)
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.snapshot().track.is_some()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,255 @@
+//! MPRIS media controls on Linux
+//!
+//! ABOP publishes an MPRIS2 player on the session bus so that media keys,
+//! desktop widgets and `playerctl` can control playback. Calls from the bus
+//! become the same [`Message`]s the toolbar buttons send, and the state they
+//! read comes from the global audio player. Changes are polled and announced
+//! with `PropertiesChanged` and `Seeked` signals.
+
+mod interface;
+#[cfg(test)]
+mod tests;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use abop_core::PlayerState;
+use abop_core::models::Audiobook;
+use iced::Subscription;
+use iced::futures::channel::mpsc::Sender;
+use zbus::blocking::Connection;
+use zbus::blocking::connection::Builder;
+
+pub use interface::{MediaPlayer2, Player};
+
+use crate::messages::Message;
+
+/// Well-known bus name the player is published under
+pub const BUS_NAME: &str = "org.mpris.MediaPlayer2.abop";
+/// Object path required by the MPRIS specification
+pub const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+/// How often the player is checked for changes to announce
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Position drift beyond which a jump is announced as a seek
+const SEEK_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// The audiobook loaded in the player, as shown to media controls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    /// Audiobook ID
+    pub id: String,
+    /// Book title
+    pub title: Option<String>,
+    /// Book author
+    pub author: Option<String>,
+    /// Total duration, if known
+    pub length: Option<Duration>,
+    /// Audio file
+    pub path: PathBuf,
+    /// Cover image file
+    pub art_path: Option<PathBuf>,
+}
+
+impl Track {
+    /// Describe an audiobook
+    ///
+    /// Media controls load artwork by URL, so embedded cover art is written
+    /// to `cover_dir` once and referenced from there.
+    #[must_use]
+    pub fn from_audiobook(audiobook: &Audiobook, cover_dir: &Path) -> Self {
+        Self {
+            id: audiobook.id.clone(),
+            title: audiobook.title.clone(),
+            author: audiobook.author.clone(),
+            length: audiobook.duration_seconds.map(Duration::from_secs),
+            path: audiobook.path.clone(),
+            art_path: cover_file(audiobook, cover_dir),
+        }
+    }
+}
+
+/// Write an audiobook's cover to `cover_dir` unless it is already there
+fn cover_file(audiobook: &Audiobook, cover_dir: &Path) -> Option<PathBuf> {
+    let data = audiobook.cover_art.as_ref()?;
+    let mime_type = audiobook.cover_mime_type()?;
+    let extension = mime_type.rsplit('/').next().unwrap_or("img");
+    let path = cover_dir.join(format!("{}.{extension}", audiobook.id));
+    if !path.exists() {
+        let written = std::fs::create_dir_all(cover_dir).and_then(|()| std::fs::write(&path, data));
+        if let Err(e) = written {
+            log::debug!("Cannot write cover to {}: {e}", path.display());
+            return None;
+        }
+    }
+    Some(path)
+}
+
+/// Playback state published over MPRIS
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackSnapshot {
+    /// Whether the player is playing, paused or stopped
+    pub state: PlayerState,
+    /// The loaded audiobook
+    pub track: Option<Track>,
+    /// Position in the current file
+    pub position: Duration,
+    /// Playback rate multiplier
+    pub rate: f32,
+    /// Volume (0.0 to 1.0)
+    pub volume: f32,
+}
+
+impl Default for PlaybackSnapshot {
+    fn default() -> Self {
+        Self {
+            state: PlayerState::Stopped,
+            track: None,
+            position: Duration::ZERO,
+            rate: 1.0,
+            volume: 1.0,
+        }
+    }
+}
+
+/// Reads the current playback state
+pub type SnapshotSource = Arc<dyn Fn() -> PlaybackSnapshot + Send + Sync>;
+
+/// Snapshot of the global audio player
+#[must_use]
+pub fn current_snapshot(cover_dir: &Path) -> PlaybackSnapshot {
+    let player = crate::audio::player::get_audio_player();
+    PlaybackSnapshot {
+        state: player.get_state(),
+        track: crate::audio::player::now_playing()
+            .map(|audiobook| Track::from_audiobook(&audiobook, cover_dir)),
+        position: player.position(),
+        rate: player.rate(),
+        volume: player.get_volume(),
+    }
+}
+
+/// An MPRIS player published on a D-Bus connection
+pub struct MprisService {
+    connection: Connection,
+    source: SnapshotSource,
+    last: PlaybackSnapshot,
+    last_refresh: Instant,
+}
+
+impl MprisService {
+    /// Publish the player on the bus at `address`, or the session bus when `None`
+    ///
+    /// Commands from the bus are sent to `commands`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bus cannot be reached or [`BUS_NAME`] is taken.
+    pub fn start(
+        address: Option<&str>,
+        source: SnapshotSource,
+        commands: Sender<Message>,
+    ) -> zbus::Result<Self> {
+        let builder = match address {
+            Some(address) => Builder::address(address)?,
+            None => Builder::session()?,
+        };
+        let connection = builder
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, MediaPlayer2::new(commands.clone()))?
+            .serve_at(OBJECT_PATH, Player::new(source.clone(), commands))?
+            .build()?;
+        let last = source();
+        Ok(Self {
+            connection,
+            source,
+            last,
+            last_refresh: Instant::now(),
+        })
+    }
+
+    /// Announce what changed since the last refresh
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a signal cannot be sent.
+    pub fn refresh(&mut self) -> zbus::Result<()> {
+        let current = (self.source)();
+        let now = Instant::now();
+        let elapsed = now.duration_since(std::mem::replace(&mut self.last_refresh, now));
+        let previous = std::mem::replace(&mut self.last, current.clone());
+
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, Player>(OBJECT_PATH)?;
+        let player = iface.get();
+        let emitter = iface.signal_emitter();
+        zbus::block_on(async {
+            if current.state != previous.state {
+                player.playback_status_changed(emitter).await?;
+            }
+            if current.track != previous.track {
+                player.metadata_changed(emitter).await?;
+                player.can_seek_changed(emitter).await?;
+            }
+            if (current.rate - previous.rate).abs() > f32::EPSILON {
+                player.rate_changed(emitter).await?;
+            }
+            if (current.volume - previous.volume).abs() > f32::EPSILON {
+                player.volume_changed(emitter).await?;
+            }
+            if current.track.is_some()
+                && current.track == previous.track
+                && jumped(&previous, &current, elapsed)
+            {
+                Player::seeked(emitter, interface::micros(current.position)).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Whether the position moved other than by playing for `elapsed`
+fn jumped(previous: &PlaybackSnapshot, current: &PlaybackSnapshot, elapsed: Duration) -> bool {
+    let expected = if previous.state == PlayerState::Playing {
+        previous.position + elapsed.mul_f32(previous.rate)
+    } else {
+        previous.position
+    };
+    current.position.abs_diff(expected) > SEEK_THRESHOLD
+}
+
+/// Subscription publishing the global audio player over MPRIS
+///
+/// Without a session bus, or with another ABOP already published, this
+/// logs a warning and does nothing.
+pub fn media_controls() -> Subscription<Message> {
+    Subscription::run_with_id(
+        "mpris",
+        iced::stream::channel(16, |output| async move {
+            match tokio::task::spawn_blocking(move || serve(&output)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => log::warn!("MPRIS media controls unavailable: {e}"),
+                Err(e) => log::error!("MPRIS task panicked: {e}"),
+            }
+        }),
+    )
+}
+
+/// Publish the player and announce changes until the application exits
+fn serve(output: &Sender<Message>) -> zbus::Result<()> {
+    let cover_dir = dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("abop")
+        .join("covers");
+    let source: SnapshotSource = Arc::new(move || current_snapshot(&cover_dir));
+    let mut service = MprisService::start(None, source, output.clone())?;
+    log::info!("Published MPRIS player as {BUS_NAME}");
+
+    while !output.is_closed() {
+        std::thread::sleep(POLL_INTERVAL);
+        service.refresh()?;
+    }
+    Ok(())
+}
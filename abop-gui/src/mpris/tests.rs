@@ -0,0 +1,357 @@
+//! MPRIS tests against a private `dbus-daemon`
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use abop_core::PlayerState;
+use iced::futures::channel::mpsc::{self, Receiver};
+use zbus::blocking::{Connection, Proxy};
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
+
+use super::interface::{file_url, track_id};
+use super::*;
+
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+const SIGNAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A session bus of its own, stopped when dropped
+struct PrivateBus {
+    daemon: Child,
+    address: String,
+    _dir: tempfile::TempDir,
+}
+
+impl PrivateBus {
+    /// Start `dbus-daemon`, or `None` when it is not installed
+    fn start() -> Option<Self> {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("session.conf");
+        std::fs::write(
+            &config,
+            format!(
+                r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:path={}</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>
+"#,
+                dir.path().join("bus").display()
+            ),
+        )
+        .unwrap();
+
+        let mut daemon = match Command::new("dbus-daemon")
+            .arg(format!("--config-file={}", config.display()))
+            .args(["--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+        {
+            Ok(daemon) => daemon,
+            Err(e) => {
+                eprintln!("Skipping MPRIS test, cannot run dbus-daemon: {e}");
+                return None;
+            }
+        };
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+        Some(Self {
+            daemon,
+            address: address.trim().to_string(),
+            _dir: dir,
+        })
+    }
+
+    fn connect(&self) -> Connection {
+        zbus::blocking::connection::Builder::address(self.address.as_str())
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+}
+
+impl Drop for PrivateBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+fn track() -> Track {
+    Track {
+        id: "8f0c-41d2".to_string(),
+        title: Some("The Hobbit".to_string()),
+        author: Some("J. R. R. Tolkien".to_string()),
+        length: Some(Duration::from_secs(3600)),
+        path: PathBuf::from("/books/The Hobbit.m4b"),
+        art_path: Some(PathBuf::from("/covers/hobbit.png")),
+    }
+}
+
+/// A published player whose state the test controls
+struct Fixture {
+    bus: PrivateBus,
+    state: Arc<Mutex<PlaybackSnapshot>>,
+    service: MprisService,
+    messages: Receiver<Message>,
+}
+
+impl Fixture {
+    fn start(snapshot: PlaybackSnapshot) -> Option<Self> {
+        let bus = PrivateBus::start()?;
+        let state = Arc::new(Mutex::new(snapshot));
+        let shared = state.clone();
+        let source: SnapshotSource = Arc::new(move || shared.lock().unwrap().clone());
+        let (commands, messages) = mpsc::channel(16);
+        let service = MprisService::start(Some(&bus.address), source, commands).unwrap();
+        Some(Self {
+            bus,
+            state,
+            service,
+            messages,
+        })
+    }
+
+    fn proxy<'a>(&self, connection: &'a Connection, interface: &'static str) -> Proxy<'a> {
+        zbus::blocking::proxy::Builder::new(connection)
+            .destination(BUS_NAME)
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .interface(interface)
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .build()
+            .unwrap()
+    }
+
+    /// The message a call produced, if any
+    fn message(&mut self) -> Option<Message> {
+        self.messages.try_next().ok().flatten()
+    }
+}
+
+fn playing() -> PlaybackSnapshot {
+    PlaybackSnapshot {
+        state: PlayerState::Playing,
+        track: Some(track()),
+        position: Duration::from_secs(30),
+        rate: 1.5,
+        volume: 0.7,
+    }
+}
+
+#[test]
+fn test_bus_calls_become_messages() {
+    let Some(mut fixture) = Fixture::start(playing()) else {
+        return;
+    };
+    let connection = fixture.bus.connect();
+    let player = fixture.proxy(&connection, PLAYER_INTERFACE);
+
+    player.call_method("PlayPause", &()).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::PlayPause)));
+    player.call_method("Next", &()).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::Next)));
+    player.call_method("Previous", &()).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::Previous)));
+    player.call_method("Stop", &()).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::Stop)));
+
+    // Play is a no-op while playing, Pause toggles
+    player.call_method("Play", &()).unwrap();
+    assert!(fixture.message().is_none());
+    player.call_method("Pause", &()).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::PlayPause)));
+
+    player.call_method("Seek", &(-5_000_000_i64)).unwrap();
+    assert!(matches!(
+        fixture.message(),
+        Some(Message::Seek(position)) if position == Duration::from_secs(25)
+    ));
+    player.call_method("Seek", &(-60_000_000_i64)).unwrap();
+    assert!(matches!(
+        fixture.message(),
+        Some(Message::Seek(Duration::ZERO))
+    ));
+    // Past the end moves on to the next track
+    player.call_method("Seek", &(7_200_000_000_i64)).unwrap();
+    assert!(matches!(fixture.message(), Some(Message::Next)));
+
+    let current = track_id(&track());
+    player
+        .call_method("SetPosition", &(current.as_ref(), 90_000_000_i64))
+        .unwrap();
+    assert!(matches!(
+        fixture.message(),
+        Some(Message::Seek(position)) if position == Duration::from_secs(90)
+    ));
+    let stale = ObjectPath::try_from("/org/abop/track/other").unwrap();
+    player
+        .call_method("SetPosition", &(stale, 90_000_000_i64))
+        .unwrap();
+    assert!(fixture.message().is_none());
+
+    let root = fixture.proxy(&connection, "org.mpris.MediaPlayer2");
+    root.call_method("Quit", &()).unwrap();
+    assert!(matches!(
+        fixture.message(),
+        Some(Message::ExecuteCommand(crate::messages::Command::Quit))
+    ));
+    assert!(player.call_method("OpenUri", &("file:///a.mp3")).is_err());
+}
+
+#[test]
+fn test_properties_reflect_player_state() {
+    let Some(fixture) = Fixture::start(playing()) else {
+        return;
+    };
+    let connection = fixture.bus.connect();
+    let player = fixture.proxy(&connection, PLAYER_INTERFACE);
+
+    assert_eq!(
+        player.get_property::<String>("PlaybackStatus").unwrap(),
+        "Playing"
+    );
+    assert!((player.get_property::<f64>("Rate").unwrap() - 1.5).abs() < f64::EPSILON);
+    assert_eq!(player.get_property::<i64>("Position").unwrap(), 30_000_000);
+    assert!(player.get_property::<bool>("CanSeek").unwrap());
+
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").unwrap();
+    let text = |key: &str| String::try_from(metadata[key].try_clone().unwrap()).unwrap();
+    assert_eq!(text("xesam:title"), "The Hobbit");
+    assert_eq!(text("mpris:artUrl"), "file:///covers/hobbit.png");
+    assert_eq!(text("xesam:url"), "file:///books/The%20Hobbit.m4b");
+    assert_eq!(
+        Vec::<String>::try_from(metadata["xesam:artist"].try_clone().unwrap()).unwrap(),
+        ["J. R. R. Tolkien"]
+    );
+    assert_eq!(
+        i64::try_from(&metadata["mpris:length"]).unwrap(),
+        3_600_000_000
+    );
+    assert_eq!(
+        OwnedObjectPath::try_from(metadata["mpris:trackid"].try_clone().unwrap())
+            .unwrap()
+            .as_str(),
+        "/org/abop/track/8f0c_41d2"
+    );
+
+    *fixture.state.lock().unwrap() = PlaybackSnapshot::default();
+    assert_eq!(
+        player.get_property::<String>("PlaybackStatus").unwrap(),
+        "Stopped"
+    );
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").unwrap();
+    assert!(metadata.is_empty());
+    assert!(!player.get_property::<bool>("CanSeek").unwrap());
+
+    let root = fixture.proxy(&connection, "org.mpris.MediaPlayer2");
+    assert_eq!(root.get_property::<String>("Identity").unwrap(), "ABOP");
+    assert!(root.get_property::<bool>("CanQuit").unwrap());
+}
+
+#[test]
+fn test_refresh_announces_changes() {
+    let paused = PlaybackSnapshot {
+        state: PlayerState::Paused,
+        ..playing()
+    };
+    let Some(mut fixture) = Fixture::start(paused) else {
+        return;
+    };
+    let connection = fixture.bus.connect();
+    let player = fixture.proxy(&connection, PLAYER_INTERFACE);
+    let properties = fixture.proxy(&connection, "org.freedesktop.DBus.Properties");
+
+    let (sender, received) = std::sync::mpsc::channel();
+    let changes = properties.receive_signal("PropertiesChanged").unwrap();
+    let changed = sender.clone();
+    std::thread::spawn(move || {
+        for signal in changes {
+            let (_, properties, _): (String, HashMap<String, OwnedValue>, Vec<String>) =
+                signal.body().deserialize().unwrap();
+            let mut names: Vec<String> = properties.into_keys().collect();
+            names.sort();
+            if changed
+                .send(format!("changed {}", names.join(",")))
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    let seeks = player.receive_signal("Seeked").unwrap();
+    std::thread::spawn(move || {
+        for signal in seeks {
+            let position: i64 = signal.body().deserialize().unwrap();
+            if sender.send(format!("seeked {position}")).is_err() {
+                break;
+            }
+        }
+    });
+
+    fixture.service.refresh().unwrap();
+    fixture.state.lock().unwrap().state = PlayerState::Playing;
+    fixture.service.refresh().unwrap();
+    assert_eq!(
+        received.recv_timeout(SIGNAL_TIMEOUT).unwrap(),
+        "changed PlaybackStatus"
+    );
+
+    fixture.state.lock().unwrap().position = Duration::from_secs(600);
+    fixture.service.refresh().unwrap();
+    assert_eq!(
+        received.recv_timeout(SIGNAL_TIMEOUT).unwrap(),
+        "seeked 600000000"
+    );
+
+    // Each property is announced in a signal of its own, and no seek
+    *fixture.state.lock().unwrap() = PlaybackSnapshot::default();
+    fixture.service.refresh().unwrap();
+    let mut signals: Vec<String> =
+        std::iter::from_fn(|| received.recv_timeout(Duration::from_millis(500)).ok()).collect();
+    signals.sort();
+    assert_eq!(
+        signals,
+        [
+            "changed CanSeek",
+            "changed Metadata",
+            "changed PlaybackStatus",
+            "changed Rate",
+            "changed Volume"
+        ]
+    );
+}
+
+#[test]
+fn test_track_from_audiobook_writes_cover_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut audiobook = abop_core::models::Audiobook::new("library", "/books/Dune.m4b");
+    audiobook.title = Some("Dune".to_string());
+    audiobook.duration_seconds = Some(75);
+    audiobook.cover_art = Some(b"\x89PNG\r\n\x1a\n".to_vec());
+
+    let track = Track::from_audiobook(&audiobook, dir.path());
+    let art_path = track.art_path.clone().unwrap();
+    assert_eq!(art_path, dir.path().join(format!("{}.png", audiobook.id)));
+    assert_eq!(std::fs::read(&art_path).unwrap(), b"\x89PNG\r\n\x1a\n");
+    assert_eq!(track.length, Some(Duration::from_secs(75)));
+    assert_eq!(Track::from_audiobook(&audiobook, dir.path()), track);
+
+    audiobook.cover_art = None;
+    assert_eq!(Track::from_audiobook(&audiobook, dir.path()).art_path, None);
+    assert_eq!(file_url(Path::new("/a b/ü")), "file:///a%20b/%C3%BC");
+}
@@ -4,15 +4,16 @@
 //! and scanning operations.
 
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::sync::Mutex;
 
 use crate::utils::platform;
-use abop_core::models::{AppState, Audiobook};
+use abop_core::library::{DuplicateCluster, OrganizePlan};
+use abop_core::models::{AppState, Audiobook, Collection, DEFAULT_PROFILE_ID, Profile, Tag};
 use abop_core::scanner::progress::ScanProgress;
-use abop_core::scanner::{LibraryScanner, ScannerState};
+use abop_core::scanner::{LibraryScanner, ScannerState, WatchUpdate};
 
 /// Directory information with scan metadata
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,6 +59,30 @@ pub struct LibraryState {
     pub selected_audiobooks: HashSet<String>,
     /// State of the audiobook table (sorting, selection, etc.)
     pub table_state: TableState,
+    /// Result of the last duplicate search, `None` while a search is running
+    pub duplicate_clusters: Option<Vec<DuplicateCluster>>,
+    /// Organize preview for the current library, `None` while it is being planned
+    pub organize_plan: Option<OrganizePlan>,
+    /// Why the last organize planning or run failed
+    pub organize_error: Option<String>,
+    /// User collections, `None` while they are being loaded
+    pub collections: Option<Vec<Collection>>,
+    /// Tags in use
+    pub tags: Vec<Tag>,
+    /// Collection or tag name typed in the collections dialog
+    pub collection_name_input: String,
+    /// Why the last collection or tag change failed
+    pub collections_error: Option<String>,
+    /// Listener profiles, `None` while they are being loaded
+    pub profiles: Option<Vec<Profile>>,
+    /// ID of the profile whose progress and preferences are in use
+    pub active_profile_id: String,
+    /// Profile name typed in the profiles dialog
+    pub profile_name_input: String,
+    /// Why the last profile change failed
+    pub profiles_error: Option<String>,
+    /// Library roots watched for filesystem changes
+    pub watched_libraries: HashSet<PathBuf>,
 
     // User preferences
     /// Whether to automatically save library state after scanning
@@ -103,6 +128,18 @@ impl LibraryState {
             audiobooks: core_state.app_data.audiobooks.clone(),
             selected_audiobooks: HashSet::new(),
             table_state: TableState::default(),
+            duplicate_clusters: Some(Vec::new()),
+            organize_plan: None,
+            organize_error: None,
+            collections: Some(Vec::new()),
+            tags: Vec::new(),
+            collection_name_input: String::new(),
+            collections_error: None,
+            profiles: Some(Vec::new()),
+            active_profile_id: DEFAULT_PROFILE_ID.to_string(),
+            profile_name_input: String::new(),
+            profiles_error: None,
+            watched_libraries: HashSet::new(),
             auto_save_library: true,
             scan_subdirectories: true,
             scanner_state: ScannerState::Idle,
@@ -207,6 +244,207 @@ impl LibraryState {
         }
     }
 
+    /// Mark a duplicate search as running
+    pub fn start_duplicate_search(&mut self) {
+        self.duplicate_clusters = None;
+        self.mark_for_redraw();
+    }
+
+    /// Store the result of a duplicate search
+    pub fn set_duplicate_clusters(&mut self, clusters: Vec<DuplicateCluster>) {
+        self.duplicate_clusters = Some(clusters);
+        self.mark_for_redraw();
+    }
+
+    /// Mark organize planning as running
+    pub fn start_organize_planning(&mut self) {
+        self.organize_plan = None;
+        self.organize_error = None;
+        self.mark_for_redraw();
+    }
+
+    /// Store the result of organize planning
+    pub fn set_organize_plan(&mut self, result: Result<OrganizePlan, String>) {
+        match result {
+            Ok(plan) => self.organize_plan = Some(plan),
+            Err(e) => self.organize_error = Some(e),
+        }
+        self.mark_for_redraw();
+    }
+
+    /// Record that moving the files failed; every file stayed in place
+    pub fn set_organize_error(&mut self, error: String) {
+        self.organize_error = Some(error);
+        self.mark_for_redraw();
+    }
+
+    /// Point the loaded audiobooks at the paths the organize plan moved them to
+    pub fn apply_organize_plan(&mut self) {
+        let Some(plan) = self.organize_plan.take() else {
+            return;
+        };
+        for planned in plan.moves {
+            if let Some(audiobook) = self
+                .audiobooks
+                .iter_mut()
+                .find(|audiobook| audiobook.id == planned.audiobook_id)
+            {
+                audiobook.path = planned.to;
+            }
+        }
+        self.mark_for_redraw();
+    }
+
+    /// Mark the collections and tags as loading
+    pub fn start_collections_loading(&mut self) {
+        self.collections = None;
+        self.collections_error = None;
+        self.mark_for_redraw();
+    }
+
+    /// Store the loaded collections and tags
+    pub fn set_collections(&mut self, result: Result<(Vec<Collection>, Vec<Tag>), String>) {
+        match result {
+            Ok((collections, tags)) => {
+                self.collections = Some(collections);
+                self.tags = tags;
+            }
+            Err(e) => {
+                self.collections = Some(Vec::new());
+                self.collections_error = Some(e);
+            }
+        }
+        self.mark_for_redraw();
+    }
+
+    /// Record that changing a collection or tag failed
+    pub fn set_collections_error(&mut self, error: String) {
+        self.collections_error = Some(error);
+        self.mark_for_redraw();
+    }
+
+    /// Mark the listener profiles as loading
+    pub fn start_profiles_loading(&mut self) {
+        self.profiles = None;
+        self.profiles_error = None;
+        self.mark_for_redraw();
+    }
+
+    /// Store the loaded listener profiles
+    ///
+    /// Falls back to the default profile if the active one no longer exists.
+    pub fn set_profiles(&mut self, result: Result<Vec<Profile>, String>) {
+        match result {
+            Ok(profiles) => {
+                if !profiles.iter().any(|p| p.id == self.active_profile_id) {
+                    self.active_profile_id = DEFAULT_PROFILE_ID.to_string();
+                }
+                self.profiles = Some(profiles);
+            }
+            Err(e) => {
+                self.profiles = Some(Vec::new());
+                self.profiles_error = Some(e);
+            }
+        }
+        self.mark_for_redraw();
+    }
+
+    /// Record that creating a profile failed
+    pub fn set_profiles_error(&mut self, error: String) {
+        self.profiles_error = Some(error);
+        self.mark_for_redraw();
+    }
+
+    /// Make a profile the active listener
+    pub fn switch_profile(&mut self, profile_id: String) {
+        if self.active_profile_id != profile_id {
+            self.active_profile_id = profile_id;
+            self.mark_for_redraw();
+        }
+    }
+
+    /// The active listener profile, if the profiles are loaded
+    #[must_use]
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.profiles
+            .as_ref()?
+            .iter()
+            .find(|profile| profile.id == self.active_profile_id)
+    }
+
+    /// IDs of the selected audiobooks in table order
+    #[must_use]
+    pub fn selected_ids(&self) -> Vec<String> {
+        self.audiobooks
+            .iter()
+            .filter(|audiobook| self.selected_audiobooks.contains(&audiobook.id))
+            .map(|audiobook| audiobook.id.clone())
+            .collect()
+    }
+
+    /// Select every duplicate copy that is not the recommended keeper
+    pub fn select_duplicate_extras(&mut self) {
+        let extras: Vec<String> = self
+            .duplicate_clusters
+            .iter()
+            .flatten()
+            .flat_map(DuplicateCluster::extras)
+            .map(|candidate| candidate.audiobook_id.clone())
+            .collect();
+        self.selected_audiobooks.clear();
+        self.selected_audiobooks.extend(extras);
+        self.mark_for_redraw();
+    }
+
+    /// Whether the library at the path is watched for changes
+    #[must_use]
+    pub fn is_library_watched(&self, path: &Path) -> bool {
+        self.watched_libraries.contains(path)
+    }
+
+    /// Start or stop watching a library, returning whether it is now watched
+    pub fn toggle_library_watch(&mut self, path: PathBuf) -> bool {
+        let watched = if self.watched_libraries.remove(&path) {
+            false
+        } else {
+            self.watched_libraries.insert(path)
+        };
+        self.mark_for_redraw();
+        watched
+    }
+
+    /// Merge changes detected by a library watcher into the loaded audiobooks
+    pub fn apply_watch_update(&mut self, update: WatchUpdate) {
+        if !update.removed.is_empty() {
+            let removed: HashSet<&PathBuf> = update.removed.iter().collect();
+            let removed_ids: Vec<String> = self
+                .audiobooks
+                .iter()
+                .filter(|audiobook| removed.contains(&audiobook.path))
+                .map(|audiobook| audiobook.id.clone())
+                .collect();
+            self.audiobooks
+                .retain(|audiobook| !removed.contains(&audiobook.path));
+            for id in &removed_ids {
+                self.selected_audiobooks.remove(id);
+            }
+        }
+
+        for audiobook in update.updated {
+            match self
+                .audiobooks
+                .iter_mut()
+                .find(|existing| existing.id == audiobook.id)
+            {
+                Some(existing) => *existing = audiobook,
+                None => self.audiobooks.push(audiobook),
+            }
+        }
+
+        self.sync_directory_metadata();
+        self.mark_for_redraw();
+    }
+
     /// Update table sorting
     pub fn set_sort_column(&mut self, column: String, ascending: bool) {
         if self.table_state.sort_column != column || self.table_state.sort_ascending != ascending {
@@ -334,6 +572,15 @@ impl std::fmt::Debug for LibraryState {
             .field("audiobooks_count", &self.audiobooks.len())
             .field("selected_count", &self.selected_audiobooks.len())
             .field("table_state", &self.table_state)
+            .field(
+                "duplicate_cluster_count",
+                &self.duplicate_clusters.as_ref().map(Vec::len),
+            )
+            .field(
+                "organize_move_count",
+                &self.organize_plan.as_ref().map(|plan| plan.moves.len()),
+            )
+            .field("collection_count", &self.collections.as_ref().map(Vec::len))
             .field("auto_save_library", &self.auto_save_library)
             .field("scan_subdirectories", &self.scan_subdirectories)
             .field("scanner_state", &self.scanner_state())
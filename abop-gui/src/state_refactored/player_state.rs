@@ -3,7 +3,11 @@
 //! This module handles all audio playback related state.
 
 use abop_core::audio::player::PlayerState as CorePlayerState;
+use abop_core::audio::processing::BatchProcessingReport;
+use abop_core::audio::waveform::WaveformSummary;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Audio player state management
 #[derive(Debug, Clone)]
@@ -18,6 +22,12 @@ pub struct PlayerState {
     pub processing_progress: Option<f32>,
     /// Current audio processing status message
     pub processing_status: Option<String>,
+    /// Before/after report of the last completed processing run
+    pub last_processing_report: Option<BatchProcessingReport>,
+    /// Waveform overview of the loaded track, once generated or read from the cache
+    pub waveform: Option<Arc<WaveformSummary>>,
+    /// Playback position in the loaded track
+    pub position: Duration,
     /// Flag to indicate player state needs UI redraw
     pub needs_redraw: bool,
 }
@@ -32,6 +42,9 @@ impl PlayerState {
             processing_audio: false,
             processing_progress: None,
             processing_status: None,
+            last_processing_report: None,
+            waveform: None,
+            position: Duration::ZERO,
             needs_redraw: false,
         }
     }
@@ -52,6 +65,40 @@ impl PlayerState {
         }
     }
 
+    /// Set the waveform overview of the loaded track
+    pub fn set_waveform(&mut self, waveform: Option<Arc<WaveformSummary>>) {
+        self.waveform = waveform;
+        self.needs_redraw = true;
+    }
+
+    /// Set the playback position in the loaded track
+    pub fn set_position(&mut self, position: Duration) {
+        if self.position != position {
+            self.position = position;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Forget the waveform and position of a track that is no longer loaded
+    pub fn clear_track(&mut self) {
+        self.set_waveform(None);
+        self.set_position(Duration::ZERO);
+    }
+
+    /// Playback position as a fraction of the track length (`0.0..=1.0`)
+    #[must_use]
+    pub fn position_fraction(&self) -> f32 {
+        let Some(duration) = self.waveform.as_ref().map(|waveform| waveform.duration()) else {
+            return 0.0;
+        };
+        if duration <= 0.0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let fraction = (self.position.as_secs_f64() / duration) as f32;
+        fraction.clamp(0.0, 1.0)
+    }
+
     /// Start audio processing
     pub fn start_processing(&mut self, status: Option<String>) {
         self.processing_audio = true;
@@ -85,6 +132,12 @@ impl PlayerState {
         }
     }
 
+    /// Store the report of a finished processing run
+    pub fn set_processing_report(&mut self, report: Option<BatchProcessingReport>) {
+        self.last_processing_report = report;
+        self.needs_redraw = true;
+    }
+
     /// Cancel audio processing
     pub fn cancel_processing(&mut self) {
         if self.processing_audio {
@@ -3,6 +3,8 @@
 //! This module handles all background task related state including task tracking,
 //! progress monitoring, and task history.
 
+use abop_core::models::Job;
+
 /// Information about a background task
 #[derive(Debug, Clone)]
 pub struct TaskInfo {
@@ -82,6 +84,8 @@ pub struct TaskState {
     pub saving: bool,
     /// Progress of the current state save (0.0 to 1.0)
     pub save_progress: Option<f32>,
+    /// Persisted scan and processing jobs, newest first
+    pub job_history: Vec<Job>,
     /// Flag to indicate task state needs UI redraw
     pub needs_redraw: bool,
 }
@@ -96,6 +100,7 @@ impl TaskState {
             max_task_history: max_history,
             saving: false,
             save_progress: None,
+            job_history: Vec::new(),
             needs_redraw: false,
         }
     }
@@ -225,6 +230,17 @@ impl TaskState {
         }
     }
 
+    /// Replace the persisted job history
+    pub fn set_job_history(&mut self, jobs: Vec<Job>) {
+        self.job_history = jobs;
+        self.needs_redraw = true;
+    }
+
+    /// Get persisted job history, newest first
+    pub fn job_history(&self) -> &[Job] {
+        &self.job_history
+    }
+
     /// Check if any task is currently running
     pub fn has_active_task(&self) -> bool {
         self.active_task.as_ref().is_some_and(|t| t.is_running)
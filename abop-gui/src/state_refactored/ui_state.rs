@@ -18,6 +18,14 @@ pub struct UiState {
     pub recent_directories_open: bool,
     /// Whether task history dialog is open
     pub show_task_history: bool,
+    /// Whether the duplicate review dialog is open
+    pub duplicates_open: bool,
+    /// Whether the organize preview dialog is open
+    pub organize_open: bool,
+    /// Whether the collections and tags dialog is open
+    pub collections_open: bool,
+    /// Whether the listener profiles dialog is open
+    pub profiles_open: bool,
     /// Flag to force a UI redraw when state changes
     pub needs_redraw: bool,
 }
@@ -42,6 +50,10 @@ impl UiState {
             settings_open: false,
             recent_directories_open: false,
             show_task_history: false,
+            duplicates_open: false,
+            organize_open: false,
+            collections_open: false,
+            profiles_open: false,
             needs_redraw: false,
         }
     }
@@ -138,6 +150,70 @@ impl UiState {
         self.needs_redraw = true;
     }
 
+    /// Open the duplicate review dialog
+    pub fn open_duplicates(&mut self) {
+        if !self.duplicates_open {
+            self.duplicates_open = true;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Close the duplicate review dialog
+    pub fn close_duplicates(&mut self) {
+        if self.duplicates_open {
+            self.duplicates_open = false;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Open the organize preview dialog
+    pub fn open_organize(&mut self) {
+        if !self.organize_open {
+            self.organize_open = true;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Close the organize preview dialog
+    pub fn close_organize(&mut self) {
+        if self.organize_open {
+            self.organize_open = false;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Open the collections and tags dialog
+    pub fn open_collections(&mut self) {
+        if !self.collections_open {
+            self.collections_open = true;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Close the collections and tags dialog
+    pub fn close_collections(&mut self) {
+        if self.collections_open {
+            self.collections_open = false;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Open the listener profiles dialog
+    pub fn open_profiles(&mut self) {
+        if !self.profiles_open {
+            self.profiles_open = true;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Close the listener profiles dialog
+    pub fn close_profiles(&mut self) {
+        if self.profiles_open {
+            self.profiles_open = false;
+            self.needs_redraw = true;
+        }
+    }
+
     /// Check if the UI state needs a redraw
     #[must_use]
     pub const fn needs_redraw(&self) -> bool {
@@ -1,7 +1,9 @@
 //! Audio processing view module
 
-use iced::Length;
-use iced::widget::{column, container};
+use iced::widget::{column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use abop_core::audio::processing::{BatchProcessingReport, FileProcessingReport, LevelMeasurement};
 
 use crate::components::audio_controls::AudioControls;
 use crate::components::status::StatusDisplay;
@@ -35,8 +37,11 @@ pub fn audio_processing_view(state: &AppState) -> iced::Element<'_, Message> {
         &state.ui.material_tokens,
     );
     // Combine components into the audio mixdown view with consistent spacing
-    let content =
+    let mut content =
         column![status_display, audio_controls].spacing(state.ui.material_tokens.spacing().md);
+    if let Some(report) = &state.player.last_processing_report {
+        content = content.push(report_view(state, report));
+    }
     container(content)
         .width(Length::Fill)
         .height(Length::Fill)
@@ -44,3 +49,62 @@ pub fn audio_processing_view(state: &AppState) -> iced::Element<'_, Message> {
         .padding(state.ui.material_tokens.spacing().md)
         .into()
 }
+
+/// Renders the before/after report of the last processing run
+fn report_view<'a>(state: &'a AppState, report: &'a BatchProcessingReport) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    let summary = &report.summary;
+
+    let mut overview = format!(
+        "{} processed · {} failed · {:.1}s silence removed",
+        summary.files_processed, summary.files_failed, summary.silence_removed_secs
+    );
+    if summary.files_processed > 0 {
+        overview.push_str(&format!(
+            " · {:.1} → {:.1} LUFS · max peak {:.1} dB",
+            summary.mean_input_lufs, summary.mean_output_lufs, summary.max_output_peak_db
+        ));
+    }
+
+    let files = report.files.iter().map(|file| {
+        row![
+            text(report_file_name(file))
+                .size(tokens.typography().body_small.size)
+                .width(Length::Fill),
+            text(report_file_details(file)).size(tokens.typography().body_small.size),
+        ]
+        .spacing(tokens.spacing().sm)
+        .into()
+    });
+
+    column![
+        text("Processing Report").size(tokens.typography().title_medium.size),
+        text(overview).size(tokens.typography().body_medium.size),
+        scrollable(column(files).spacing(tokens.spacing().xs)).height(Length::Fill),
+    ]
+    .spacing(tokens.spacing().sm)
+    .into()
+}
+
+/// File name of a processed file, falling back to the full path
+fn report_file_name(file: &FileProcessingReport) -> String {
+    file.input_path.file_name().map_or_else(
+        || file.input_path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Loudness, peak, RMS and gain summary of a processed file
+fn report_file_details(file: &FileProcessingReport) -> String {
+    let level = |before: &LevelMeasurement, after: &LevelMeasurement| {
+        format!(
+            "{:.1} → {:.1} LUFS · peak {:.1} → {:.1} dB · RMS {:.1} → {:.1} dB",
+            before.lufs, after.lufs, before.peak_db, after.peak_db, before.rms_db, after.rms_db
+        )
+    };
+    let mut details = level(&file.report.input, &file.report.output);
+    if let Some(gain) = file.report.gain_db() {
+        details.push_str(&format!(" · gain {gain:+.1} dB"));
+    }
+    details
+}
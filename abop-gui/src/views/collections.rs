@@ -0,0 +1,182 @@
+//! Collections and tags dialog
+//!
+//! Lists the user's collections and tags and assigns the audiobooks selected
+//! in the library table to them. Typing a new name and adding the selection
+//! creates the collection or tag.
+
+use iced::widget::{Space, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::components::buttons;
+use crate::components::buttons::builder::ButtonBuilder;
+use crate::components::buttons::variants::ButtonVariant;
+use crate::messages::{Message, SelectionTarget};
+use crate::state::AppState;
+use crate::styling::container::dialog::DialogContainerStyles;
+use crate::styling::material::components::feedback::dialog::DialogSize;
+use crate::styling::material::components::inputs::{TextFieldVariant, material_text_field};
+
+/// Maximum height of the collection and tag lists before they scroll
+const LIST_MAX_HEIGHT: f32 = 360.0;
+
+/// Creates the collections and tags dialog
+#[must_use]
+pub fn collections_view(state: &AppState) -> Element<'_, Message> {
+    let tokens = &state.ui.material_tokens;
+    let selected = state.library.selected_audiobooks.len();
+    let name = state.library.collection_name_input.trim();
+
+    let mut content = column![
+        text("Collections & Tags").size(tokens.typography().title_medium.size),
+        text(format!("{selected} audiobooks selected")).size(tokens.typography().body_small.size),
+    ]
+    .spacing(tokens.spacing().md)
+    .padding(tokens.spacing().lg);
+    if let Some(error) = &state.library.collections_error {
+        content = content.push(
+            text(error)
+                .size(tokens.typography().body_small.size)
+                .color(tokens.colors.error.base),
+        );
+    }
+
+    let can_assign = selected > 0 && !name.is_empty();
+    let new_target = row![
+        material_text_field(
+            &state.library.collection_name_input,
+            "Collection or tag name",
+            TextFieldVariant::Outlined,
+            Message::CollectionNameChanged,
+            tokens,
+        )
+        .width(Length::Fill),
+        action_button(
+            state,
+            "Add to collection",
+            can_assign.then(|| Message::AssignSelection {
+                target: SelectionTarget::Collection(name.to_string()),
+                assign: true,
+            }),
+        ),
+        action_button(
+            state,
+            "Tag",
+            can_assign.then(|| Message::AssignSelection {
+                target: SelectionTarget::Tag(name.to_string()),
+                assign: true,
+            }),
+        ),
+    ]
+    .spacing(tokens.spacing().sm);
+    content = content.push(new_target);
+
+    let body: Element<'_, Message> = match &state.library.collections {
+        None => text("Loading collections…")
+            .size(tokens.typography().body_medium.size)
+            .into(),
+        Some(collections) if collections.is_empty() && state.library.tags.is_empty() => {
+            text("No collections or tags yet")
+                .size(tokens.typography().body_medium.size)
+                .into()
+        }
+        Some(collections) => {
+            let collections = collections.iter().map(|collection| {
+                target_row(
+                    state,
+                    SelectionTarget::Collection(collection.name.clone()),
+                    collection.audiobook_count,
+                )
+            });
+            let tags = state.library.tags.iter().map(|tag| {
+                target_row(
+                    state,
+                    SelectionTarget::Tag(tag.name.clone()),
+                    tag.audiobook_count,
+                )
+            });
+            scrollable(column(collections.chain(tags)).spacing(tokens.spacing().xs))
+                .height(Length::Shrink)
+                .into()
+        }
+    };
+    content = content.push(container(body).max_height(LIST_MAX_HEIGHT));
+
+    let actions = row![
+        Space::new(Length::Fill, 0),
+        buttons::create_button(
+            || {
+                ButtonBuilder::new(tokens)
+                    .label("Close")
+                    .variant(ButtonVariant::Filled)
+                    .on_press(Message::CloseCollections)
+                    .build()
+            },
+            "close collections",
+            Some("Close"),
+        ),
+    ];
+
+    container(content.push(actions))
+        .width(Length::from(DialogSize::Medium))
+        .style(DialogContainerStyles::modal(state.ui.theme_mode))
+        .into()
+}
+
+/// One collection or tag with buttons to add or remove the selection
+fn target_row(state: &AppState, target: SelectionTarget, count: usize) -> Element<'_, Message> {
+    let tokens = &state.ui.material_tokens;
+    let has_selection = !state.library.selected_audiobooks.is_empty();
+    let kind = match target {
+        SelectionTarget::Collection(_) => "Collection",
+        SelectionTarget::Tag(_) => "Tag",
+    };
+    row![
+        text(kind)
+            .size(tokens.typography().label_medium.size)
+            .width(Length::Fixed(tokens.spacing().xl * 2.0)),
+        text(format!("{} ({count})", target.name()))
+            .size(tokens.typography().body_medium.size)
+            .width(Length::Fill),
+        action_button(
+            state,
+            "Add",
+            has_selection.then(|| Message::AssignSelection {
+                target: target.clone(),
+                assign: true,
+            }),
+        ),
+        action_button(
+            state,
+            "Remove",
+            has_selection.then(|| Message::AssignSelection {
+                target: target.clone(),
+                assign: false,
+            }),
+        ),
+    ]
+    .spacing(tokens.spacing().sm)
+    .into()
+}
+
+/// Text button that is disabled without a message
+fn action_button<'a>(
+    state: &'a AppState,
+    label: &'a str,
+    message: Option<Message>,
+) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    buttons::create_button(
+        || {
+            let builder = ButtonBuilder::new(tokens)
+                .label(label)
+                .variant(ButtonVariant::Text);
+            match message {
+                Some(message) => builder.on_press(message),
+                None => builder,
+            }
+            .build()
+        },
+        label,
+        Some(label),
+    )
+}
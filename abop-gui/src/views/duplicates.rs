@@ -0,0 +1,147 @@
+//! Duplicate review dialog
+//!
+//! Lists groups of audiobooks that appear to be the same book and marks the
+//! recommended copy to keep. The remaining copies can be selected in the
+//! library table for further action.
+
+use iced::widget::{Space, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use abop_core::library::{DuplicateCandidate, DuplicateCluster};
+
+use crate::components::buttons;
+use crate::components::buttons::builder::ButtonBuilder;
+use crate::components::buttons::variants::ButtonVariant;
+use crate::messages::Message;
+use crate::state::AppState;
+use crate::styling::container::dialog::DialogContainerStyles;
+use crate::styling::material::components::feedback::dialog::DialogSize;
+
+/// Maximum height of the cluster list before it scrolls
+const LIST_MAX_HEIGHT: f32 = 420.0;
+
+/// Creates the duplicate review dialog
+#[must_use]
+pub fn duplicates_view(state: &AppState) -> Element<'_, Message> {
+    let tokens = &state.ui.material_tokens;
+    let clusters = state.library.duplicate_clusters.as_deref();
+
+    let body: Element<'_, Message> = match clusters {
+        None => text("Searching for duplicates…")
+            .size(tokens.typography().body_medium.size)
+            .into(),
+        Some([]) => text("No duplicate audiobooks found")
+            .size(tokens.typography().body_medium.size)
+            .into(),
+        Some(clusters) => scrollable(
+            column(clusters.iter().map(|cluster| cluster_view(state, cluster)))
+                .spacing(tokens.spacing().md),
+        )
+        .height(Length::Shrink)
+        .into(),
+    };
+
+    let extra_count: usize = clusters
+        .unwrap_or_default()
+        .iter()
+        .map(|cluster| cluster.extras().count())
+        .sum();
+
+    let mut actions = row![Space::new(Length::Fill, 0)].spacing(tokens.spacing().sm);
+    if extra_count > 0 {
+        actions = actions.push(buttons::create_button(
+            || {
+                ButtonBuilder::new(tokens)
+                    .label("Select extras")
+                    .variant(ButtonVariant::Outlined)
+                    .on_press(Message::SelectDuplicateExtras)
+                    .build()
+            },
+            "select duplicate extras",
+            Some("Select extras"),
+        ));
+    }
+    actions = actions.push(buttons::create_button(
+        || {
+            ButtonBuilder::new(tokens)
+                .label("Close")
+                .variant(ButtonVariant::Filled)
+                .on_press(Message::CloseDuplicates)
+                .build()
+        },
+        "close duplicates",
+        Some("Close"),
+    ));
+
+    container(
+        column![
+            text("Duplicate Audiobooks").size(tokens.typography().title_medium.size),
+            container(body).max_height(LIST_MAX_HEIGHT),
+            actions,
+        ]
+        .spacing(tokens.spacing().md)
+        .padding(tokens.spacing().lg),
+    )
+    .width(Length::from(DialogSize::Medium))
+    .style(DialogContainerStyles::modal(state.ui.theme_mode))
+    .into()
+}
+
+/// Renders one duplicate group with its keeper listed first
+fn cluster_view<'a>(state: &'a AppState, cluster: &'a DuplicateCluster) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    let match_kind = if cluster.fingerprint_verified {
+        "audio verified"
+    } else {
+        "tags and duration"
+    };
+
+    let header = column![
+        text(&cluster.title).size(tokens.typography().label_large.size),
+        text(format!("{} · {match_kind}", cluster.author))
+            .size(tokens.typography().body_small.size),
+    ];
+
+    let candidates = cluster.candidates.iter().map(|candidate| {
+        let role = if candidate.audiobook_id == cluster.keeper_id {
+            "Keep"
+        } else {
+            "Extra"
+        };
+        row![
+            text(role)
+                .size(tokens.typography().label_medium.size)
+                .width(Length::Fixed(tokens.spacing().lg * 2.0)),
+            text(candidate_file_name(candidate))
+                .size(tokens.typography().body_small.size)
+                .width(Length::Fill),
+            text(candidate_details(candidate)).size(tokens.typography().body_small.size),
+        ]
+        .spacing(tokens.spacing().sm)
+        .into()
+    });
+
+    column![header, column(candidates).spacing(tokens.spacing().xs)]
+        .spacing(tokens.spacing().xs)
+        .into()
+}
+
+/// File name of a candidate, falling back to the full path
+fn candidate_file_name(candidate: &DuplicateCandidate) -> String {
+    candidate.path.file_name().map_or_else(
+        || candidate.path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Bitrate, tag and similarity summary of a candidate
+fn candidate_details(candidate: &DuplicateCandidate) -> String {
+    let bitrate = candidate
+        .bitrate_kbps
+        .map_or_else(|| "? kbps".to_string(), |kbps| format!("{kbps} kbps"));
+    let mut details = format!("{bitrate} · {} tags", candidate.tag_score);
+    if let Some(similarity) = candidate.similarity {
+        details.push_str(&format!(" · {:.0}% match", similarity * 100.0));
+    }
+    details
+}
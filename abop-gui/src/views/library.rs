@@ -1,11 +1,15 @@
 //! Library view module
 
+use std::time::Duration;
+
+use abop_core::audio::waveform::WaveformSummary;
 use iced::Length;
 use iced::widget::{column, container};
 
 use crate::components::audio_toolbar::AudioToolbar;
 use crate::components::status::{EnhancedStatusDisplayParams, StatusDisplay};
 use crate::components::table_core::AudiobookTable;
+use crate::components::waveform::WaveformScrubber;
 use crate::messages::Message;
 use crate::state::AppState;
 use crate::styling::container::LayoutContainerStyles;
@@ -116,6 +120,11 @@ fn build_content_layout<'a>(
         content_items.push(toolbar_element);
     }
 
+    // Show the waveform scrubber while a track is loaded
+    if !state.player.is_stopped() {
+        content_items.push(create_waveform_scrubber(state));
+    }
+
     // Add table content
     content_items.push(table_content);
 
@@ -147,6 +156,28 @@ fn create_audio_toolbar(state: &AppState) -> iced::Element<'_, Message> {
         .into()
 }
 
+/// Creates the waveform scrubber for the loaded track
+///
+/// Clicking or dragging seeks to the matching position. Until the waveform is
+/// loaded an empty track keeps the layout stable.
+fn create_waveform_scrubber(state: &AppState) -> iced::Element<'_, Message> {
+    let waveform = state.player.waveform.as_deref();
+    let duration = waveform.map_or(0.0, WaveformSummary::duration);
+    let scrubber = WaveformScrubber::new(
+        waveform,
+        state.player.position_fraction(),
+        &state.ui.material_tokens,
+    );
+    let scrubber = if duration > 0.0 {
+        scrubber.on_seek(move |fraction| {
+            Message::Seek(Duration::from_secs_f64(duration * f64::from(fraction)))
+        })
+    } else {
+        scrubber
+    };
+    container(scrubber.view()).width(Length::Fill).into()
+}
+
 /// Assembles the final container with styling
 fn assemble_final_container<'a>(
     state: &'a AppState,
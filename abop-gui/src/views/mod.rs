@@ -2,7 +2,11 @@
 
 pub mod about;
 pub mod audio_processing;
+pub mod collections;
+pub mod duplicates;
 pub mod library;
+pub mod organize;
+pub mod profiles;
 pub mod settings;
 
 #[cfg(test)]
@@ -17,7 +21,11 @@ use crate::state::AppState;
 
 pub use about::about_view;
 pub use audio_processing::audio_processing_view;
+pub use collections::collections_view;
+pub use duplicates::duplicates_view;
 pub use library::library_view;
+pub use organize::organize_view;
+pub use profiles::profiles_view;
 pub use settings::settings_view;
 
 /// Creates a modal overlay with the given content over a base element
@@ -54,6 +62,9 @@ pub fn view(state: &AppState, route: crate::router::Route) -> Element<'_, Messag
     let toolbar = MainToolbar::view(
         &state.library.recent_directories,
         &state.library.library_path,
+        state
+            .library
+            .is_library_watched(&state.library.library_path),
         &state.ui.material_tokens,
     );
 
@@ -76,6 +87,18 @@ pub fn view(state: &AppState, route: crate::router::Route) -> Element<'_, Messag
     // If settings dialog is open, show it as a modal overlay (single render)
     if state.ui.settings_open {
         modal(main_content, settings_view(state), Message::CloseSettings)
+    } else if state.ui.duplicates_open {
+        modal(main_content, duplicates_view(state), Message::CloseDuplicates)
+    } else if state.ui.organize_open {
+        modal(main_content, organize_view(state), Message::CloseOrganize)
+    } else if state.ui.collections_open {
+        modal(
+            main_content,
+            collections_view(state),
+            Message::CloseCollections,
+        )
+    } else if state.ui.profiles_open {
+        modal(main_content, profiles_view(state), Message::CloseProfiles)
     } else {
         main_content.into()
     }
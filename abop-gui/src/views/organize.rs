@@ -0,0 +1,176 @@
+//! Organize preview dialog
+//!
+//! Shows where each file of the current library would move under the
+//! organizer template, together with any target paths that collide. The moves
+//! are only performed once the preview is confirmed and no collisions remain.
+
+use std::path::Path;
+
+use iced::widget::{Space, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use abop_core::library::{Collision, CollisionKind, OrganizePlan, PlannedMove};
+
+use crate::components::buttons;
+use crate::components::buttons::builder::ButtonBuilder;
+use crate::components::buttons::variants::ButtonVariant;
+use crate::messages::Message;
+use crate::state::AppState;
+use crate::styling::container::dialog::DialogContainerStyles;
+use crate::styling::material::components::feedback::dialog::DialogSize;
+
+/// Maximum height of the move list before it scrolls
+const LIST_MAX_HEIGHT: f32 = 420.0;
+
+/// Creates the organize preview dialog
+#[must_use]
+pub fn organize_view(state: &AppState) -> Element<'_, Message> {
+    let tokens = &state.ui.material_tokens;
+    let plan = state.library.organize_plan.as_ref();
+
+    let body: Element<'_, Message> = match plan {
+        None if state.library.organize_error.is_none() => text("Planning moves…")
+            .size(tokens.typography().body_medium.size)
+            .into(),
+        None => Space::new(Length::Shrink, 0).into(),
+        Some(plan) if plan.moves.is_empty() => text(format!(
+            "All {} files are already organized",
+            plan.unchanged
+        ))
+        .size(tokens.typography().body_medium.size)
+        .into(),
+        Some(plan) => scrollable(
+            column(
+                plan.collisions
+                    .iter()
+                    .map(|collision| collision_view(state, plan, collision))
+                    .chain(
+                        plan.moves
+                            .iter()
+                            .map(|planned| move_view(state, plan, planned)),
+                    ),
+            )
+            .spacing(tokens.spacing().sm),
+        )
+        .height(Length::Shrink)
+        .into(),
+    };
+
+    let mut content = column![text("Organize Library").size(tokens.typography().title_medium.size)]
+        .spacing(tokens.spacing().md)
+        .padding(tokens.spacing().lg);
+    if let Some(plan) = plan {
+        content = content.push(text(plan_summary(plan)).size(tokens.typography().body_small.size));
+    }
+    if let Some(error) = &state.library.organize_error {
+        content = content.push(
+            text(format!("Nothing was moved: {error}"))
+                .size(tokens.typography().body_small.size)
+                .color(tokens.colors.error.base),
+        );
+    }
+    content = content.push(container(body).max_height(LIST_MAX_HEIGHT));
+
+    let can_apply = plan.is_some_and(|plan| !plan.moves.is_empty() && !plan.has_collisions());
+    let mut actions = row![Space::new(Length::Fill, 0)].spacing(tokens.spacing().sm);
+    actions = actions.push(buttons::create_button(
+        || {
+            ButtonBuilder::new(tokens)
+                .label("Cancel")
+                .variant(ButtonVariant::Outlined)
+                .on_press(Message::CloseOrganize)
+                .build()
+        },
+        "cancel organize",
+        Some("Cancel"),
+    ));
+    if can_apply {
+        actions = actions.push(buttons::create_button(
+            || {
+                ButtonBuilder::new(tokens)
+                    .label("Move files")
+                    .variant(ButtonVariant::Filled)
+                    .on_press(Message::ApplyOrganize)
+                    .build()
+            },
+            "apply organize",
+            Some("Move files"),
+        ));
+    }
+
+    container(content.push(actions))
+        .width(Length::from(DialogSize::Large))
+        .style(DialogContainerStyles::modal(state.ui.theme_mode))
+        .into()
+}
+
+/// One planned move, shown relative to the library root
+fn move_view<'a>(
+    state: &'a AppState,
+    plan: &OrganizePlan,
+    planned: &PlannedMove,
+) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    column![
+        text(relative_path(&plan.library_root, &planned.from))
+            .size(tokens.typography().body_small.size),
+        text(format!(
+            "→ {}",
+            relative_path(&plan.library_root, &planned.to)
+        ))
+        .size(tokens.typography().body_small.size),
+    ]
+    .into()
+}
+
+/// A contested target with the files that want it
+fn collision_view<'a>(
+    state: &'a AppState,
+    plan: &OrganizePlan,
+    collision: &Collision,
+) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    let reason = match collision.kind {
+        CollisionKind::SharedTarget => "is the target of several files",
+        CollisionKind::TargetExists => "already exists",
+    };
+    let sources = collision.sources.iter().map(|source| {
+        text(format!("  {}", relative_path(&plan.library_root, source)))
+            .size(tokens.typography().body_small.size)
+            .into()
+    });
+    column![
+        text(format!(
+            "{} {reason}",
+            relative_path(&plan.library_root, &collision.target)
+        ))
+        .size(tokens.typography().label_medium.size)
+        .color(tokens.colors.error.base),
+        column(sources),
+    ]
+    .into()
+}
+
+/// Counts of moved, unchanged and colliding files
+fn plan_summary(plan: &OrganizePlan) -> String {
+    let mut summary = format!(
+        "{} files to move, {} already in place",
+        plan.moves.len(),
+        plan.unchanged
+    );
+    if plan.has_collisions() {
+        summary.push_str(&format!(
+            " · {} collisions must be resolved first",
+            plan.collisions.len()
+        ));
+    }
+    summary
+}
+
+/// Path relative to the library root, falling back to the full path
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
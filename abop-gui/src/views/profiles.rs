@@ -0,0 +1,139 @@
+//! Listener profiles dialog
+//!
+//! Lists the listener profiles and switches the active one. Each profile has
+//! its own progress, bookmarks and playback preferences, so switching lets
+//! several people share the library without losing their place.
+
+use iced::widget::{Space, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use abop_core::models::Profile;
+
+use crate::components::buttons;
+use crate::components::buttons::builder::ButtonBuilder;
+use crate::components::buttons::variants::ButtonVariant;
+use crate::messages::Message;
+use crate::state::AppState;
+use crate::styling::container::dialog::DialogContainerStyles;
+use crate::styling::material::components::feedback::dialog::DialogSize;
+use crate::styling::material::components::inputs::{TextFieldVariant, material_text_field};
+
+/// Maximum height of the profile list before it scrolls
+const LIST_MAX_HEIGHT: f32 = 360.0;
+
+/// Creates the listener profiles dialog
+#[must_use]
+pub fn profiles_view(state: &AppState) -> Element<'_, Message> {
+    let tokens = &state.ui.material_tokens;
+    let active = state
+        .library
+        .active_profile()
+        .map_or("Default", |profile| profile.name.as_str());
+
+    let mut content = column![
+        text("Listener Profiles").size(tokens.typography().title_medium.size),
+        text(format!("Listening as {active}")).size(tokens.typography().body_small.size),
+    ]
+    .spacing(tokens.spacing().md)
+    .padding(tokens.spacing().lg);
+    if let Some(error) = &state.library.profiles_error {
+        content = content.push(
+            text(error)
+                .size(tokens.typography().body_small.size)
+                .color(tokens.colors.error.base),
+        );
+    }
+
+    let can_create = !state.library.profile_name_input.trim().is_empty();
+    let new_profile = row![
+        material_text_field(
+            &state.library.profile_name_input,
+            "Profile name",
+            TextFieldVariant::Outlined,
+            Message::ProfileNameChanged,
+            tokens,
+        )
+        .width(Length::Fill),
+        action_button(
+            state,
+            "Create",
+            can_create.then_some(Message::CreateProfile)
+        ),
+    ]
+    .spacing(tokens.spacing().sm);
+    content = content.push(new_profile);
+
+    let body: Element<'_, Message> = match &state.library.profiles {
+        None => text("Loading profiles…")
+            .size(tokens.typography().body_medium.size)
+            .into(),
+        Some(profiles) => scrollable(
+            column(profiles.iter().map(|profile| profile_row(state, profile)))
+                .spacing(tokens.spacing().xs),
+        )
+        .height(Length::Shrink)
+        .into(),
+    };
+    content = content.push(container(body).max_height(LIST_MAX_HEIGHT));
+
+    let actions = row![
+        Space::new(Length::Fill, 0),
+        buttons::create_button(
+            || {
+                ButtonBuilder::new(tokens)
+                    .label("Close")
+                    .variant(ButtonVariant::Filled)
+                    .on_press(Message::CloseProfiles)
+                    .build()
+            },
+            "close profiles",
+            Some("Close"),
+        ),
+    ];
+
+    container(content.push(actions))
+        .width(Length::from(DialogSize::Medium))
+        .style(DialogContainerStyles::modal(state.ui.theme_mode))
+        .into()
+}
+
+/// One profile with a button to switch to it
+fn profile_row<'a>(state: &'a AppState, profile: &'a Profile) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    let is_active = profile.id == state.library.active_profile_id;
+    row![
+        text(&profile.name)
+            .size(tokens.typography().body_medium.size)
+            .width(Length::Fill),
+        action_button(
+            state,
+            if is_active { "Active" } else { "Switch" },
+            (!is_active).then(|| Message::SwitchProfile(profile.id.clone())),
+        ),
+    ]
+    .spacing(tokens.spacing().sm)
+    .into()
+}
+
+/// Text button that is disabled without a message
+fn action_button<'a>(
+    state: &'a AppState,
+    label: &'a str,
+    message: Option<Message>,
+) -> Element<'a, Message> {
+    let tokens = &state.ui.material_tokens;
+    buttons::create_button(
+        || {
+            let builder = ButtonBuilder::new(tokens)
+                .label(label)
+                .variant(ButtonVariant::Text);
+            match message {
+                Some(message) => builder.on_press(message),
+                None => builder,
+            }
+            .build()
+        },
+        label,
+        Some(label),
+    )
+}
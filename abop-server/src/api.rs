@@ -0,0 +1,448 @@
+//! REST endpoints
+//!
+//! Routes are matched on the method and the decoded path segments after
+//! `/api`. Handlers return a [`Reply`], which the server turns into an HTTP
+//! response; failures become JSON error bodies through [`ApiError`].
+
+use crate::auth::{bearer_token, token_matches};
+use crate::error::{ApiError, ApiResult};
+use crate::range::{ByteRange, parse_range};
+use crate::request::Target;
+use abop_core::audio::AudioFormat;
+use abop_core::db::Database;
+use abop_core::models::{Audiobook, DEFAULT_PROFILE_ID, Profile, Progress, SearchQuery};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use tiny_http::Method;
+
+/// A request as the API sees it, independent of the HTTP library
+#[derive(Debug)]
+pub struct ApiRequest {
+    /// Request method
+    pub method: Method,
+    /// Decoded path and query
+    pub target: Target,
+    /// Value of the `Authorization` header
+    pub authorization: Option<String>,
+    /// Value of the `X-Abop-Profile` header
+    pub profile: Option<String>,
+    /// Value of the `Range` header
+    pub range: Option<String>,
+    /// Request body
+    pub body: Vec<u8>,
+}
+
+/// A successful response, before it is written to the client
+#[derive(Debug)]
+pub enum Reply {
+    /// A JSON document
+    Json(serde_json::Value),
+    /// An empty response
+    NoContent,
+    /// Image bytes
+    Image {
+        /// MIME type of the image
+        content_type: &'static str,
+        /// Image data
+        data: Vec<u8>,
+    },
+    /// An audio file, whole or in part
+    Audio {
+        /// The open file
+        file: File,
+        /// MIME type of the audio
+        content_type: &'static str,
+        /// Size of the whole file in bytes
+        size: u64,
+        /// The part of the file to send, if the client asked for one
+        range: Option<ByteRange>,
+    },
+}
+
+/// A library with the number of books it holds
+#[derive(Debug, Serialize)]
+pub struct LibraryInfo {
+    /// Library ID
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Number of books in the library
+    pub book_count: usize,
+}
+
+/// A book as listed by the API
+///
+/// Cover art is left out and fetched separately from `cover_url`.
+#[derive(Debug, Serialize)]
+pub struct BookInfo {
+    /// Book ID
+    pub id: String,
+    /// ID of the library holding the book
+    pub library_id: String,
+    /// Title, if known
+    pub title: Option<String>,
+    /// Author, if known
+    pub author: Option<String>,
+    /// Narrator, if known
+    pub narrator: Option<String>,
+    /// Description, if known
+    pub description: Option<String>,
+    /// Duration in seconds, if known
+    pub duration_seconds: Option<u64>,
+    /// File size in bytes, if known
+    pub size_bytes: Option<u64>,
+    /// File extension of the audio
+    pub format: Option<String>,
+    /// Where to stream the audio from
+    pub stream_url: String,
+    /// Where to fetch the cover from, if the book has one
+    pub cover_url: Option<String>,
+}
+
+impl From<&Audiobook> for BookInfo {
+    fn from(audiobook: &Audiobook) -> Self {
+        let id = &audiobook.id;
+        Self {
+            id: id.clone(),
+            library_id: audiobook.library_id.clone(),
+            title: audiobook.title.clone(),
+            author: audiobook.author.clone(),
+            narrator: audiobook.narrator.clone(),
+            description: audiobook.description.clone(),
+            duration_seconds: audiobook.duration_seconds,
+            size_bytes: audiobook.size_bytes,
+            format: audiobook.extension().map(str::to_lowercase),
+            stream_url: format!("/api/books/{id}/stream"),
+            cover_url: audiobook
+                .cover_art
+                .as_ref()
+                .map(|_| format!("/api/books/{id}/cover")),
+        }
+    }
+}
+
+/// A book together with the listener's progress in it
+#[derive(Debug, Serialize)]
+pub struct BookDetail {
+    /// The book
+    #[serde(flatten)]
+    pub book: BookInfo,
+    /// The listener's progress, if they have started it
+    pub progress: Option<Progress>,
+}
+
+/// The listener's progress in one book
+#[derive(Debug, Serialize)]
+pub struct ProgressEntry {
+    /// The book
+    pub book: BookInfo,
+    /// Progress in the book
+    pub progress: Progress,
+}
+
+/// Body of `PUT /api/books/{id}/progress`
+#[derive(Debug, Deserialize)]
+pub struct ProgressUpdate {
+    /// New playback position in seconds
+    pub position_seconds: Option<u64>,
+    /// Whether the book is finished
+    pub completed: Option<bool>,
+}
+
+/// Request router and handlers
+#[derive(Debug, Clone)]
+pub struct Api {
+    db: Database,
+    token: String,
+}
+
+impl Api {
+    /// Create the API over a database, guarded by `token`
+    #[must_use]
+    pub fn new(db: Database, token: impl Into<String>) -> Self {
+        Self {
+            db,
+            token: token.into(),
+        }
+    }
+
+    /// Route a request to its handler
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::Unauthorized`] without a valid token,
+    /// [`ApiError::NotFound`] for unknown routes or records, and whatever the
+    /// handler fails with otherwise.
+    pub fn handle(&self, request: &ApiRequest) -> ApiResult<Reply> {
+        let segments: Vec<&str> = request.target.segments.iter().map(String::as_str).collect();
+        let Some((&"api", route)) = segments.split_first() else {
+            return Err(not_found_route(&segments));
+        };
+        let method = &request.method;
+
+        if route == ["health"] {
+            only_get(method)?;
+            return json(&serde_json::json!({
+                "status": "ok",
+                "version": env!("CARGO_PKG_VERSION"),
+            }));
+        }
+        self.authorize(request)?;
+
+        match route {
+            ["libraries"] => {
+                only_get(method)?;
+                self.libraries()
+            }
+            ["libraries", id, "books"] => {
+                only_get(method)?;
+                self.library_books(id)
+            }
+            ["books", id] => {
+                only_get(method)?;
+                self.book(id, &self.profile(request)?)
+            }
+            ["books", id, "cover"] => {
+                only_get(method)?;
+                self.cover(id)
+            }
+            ["books", id, "stream"] => {
+                only_get(method)?;
+                self.stream(id, request.range.as_deref())
+            }
+            ["books", id, "progress"] => {
+                let profile = self.profile(request)?;
+                match method {
+                    Method::Get | Method::Head => self.book_progress(id, &profile),
+                    Method::Put => self.update_progress(id, &profile, &request.body),
+                    Method::Delete => self.reset_progress(id, &profile),
+                    _ => Err(ApiError::MethodNotAllowed),
+                }
+            }
+            ["search"] => {
+                only_get(method)?;
+                self.search(&request.target, &self.profile(request)?)
+            }
+            ["profiles"] => {
+                only_get(method)?;
+                json(&self.db.profile_repository().find_all()?)
+            }
+            ["progress"] => {
+                only_get(method)?;
+                self.all_progress(&self.profile(request)?)
+            }
+            _ => Err(not_found_route(&segments)),
+        }
+    }
+
+    fn authorize(&self, request: &ApiRequest) -> ApiResult<()> {
+        let presented = request
+            .authorization
+            .as_deref()
+            .and_then(bearer_token)
+            .or_else(|| request.target.param("token"));
+        match presented {
+            Some(token) if token_matches(&self.token, token) => Ok(()),
+            _ => Err(ApiError::Unauthorized),
+        }
+    }
+
+    /// The profile picked by header or query, or the default profile
+    fn profile(&self, request: &ApiRequest) -> ApiResult<Profile> {
+        let selected = request
+            .profile
+            .as_deref()
+            .or_else(|| request.target.param("profile"))
+            .unwrap_or(DEFAULT_PROFILE_ID);
+        let repo = self.db.profile_repository();
+        if let Some(profile) = repo.find_by_id(selected)? {
+            return Ok(profile);
+        }
+        repo.find_by_name(selected)?
+            .ok_or_else(|| ApiError::NotFound(format!("Profile {selected}")))
+    }
+
+    fn find_book(&self, id: &str) -> ApiResult<Audiobook> {
+        self.db
+            .audiobook_repository()
+            .find_by_id(id)?
+            .ok_or_else(|| ApiError::NotFound(format!("Book {id}")))
+    }
+
+    fn libraries(&self) -> ApiResult<Reply> {
+        let libraries = self
+            .db
+            .get_libraries()?
+            .into_iter()
+            .map(|library| {
+                Ok(LibraryInfo {
+                    book_count: self.db.count_audiobooks_in_library(&library.id)?,
+                    id: library.id,
+                    name: library.name,
+                })
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+        json(&libraries)
+    }
+
+    fn library_books(&self, id: &str) -> ApiResult<Reply> {
+        if self.db.library_repository().find_by_id(id)?.is_none() {
+            return Err(ApiError::NotFound(format!("Library {id}")));
+        }
+        let books: Vec<BookInfo> = self
+            .db
+            .get_audiobooks_in_library(id)?
+            .iter()
+            .map(BookInfo::from)
+            .collect();
+        json(&books)
+    }
+
+    fn book(&self, id: &str, profile: &Profile) -> ApiResult<Reply> {
+        let audiobook = self.find_book(id)?;
+        let progress = self
+            .db
+            .progress_repository()
+            .find_for_profile(&profile.id, id)?;
+        json(&BookDetail {
+            book: BookInfo::from(&audiobook),
+            progress,
+        })
+    }
+
+    fn cover(&self, id: &str) -> ApiResult<Reply> {
+        let audiobook = self.find_book(id)?;
+        match (audiobook.cover_mime_type(), audiobook.cover_art) {
+            (Some(content_type), Some(data)) => Ok(Reply::Image { content_type, data }),
+            _ => Err(ApiError::NotFound(format!("Cover for book {id}"))),
+        }
+    }
+
+    fn stream(&self, id: &str, range: Option<&str>) -> ApiResult<Reply> {
+        let audiobook = self.find_book(id)?;
+        let file = File::open(&audiobook.path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ApiError::NotFound(format!("Audio file for book {id}")),
+            _ => e.into(),
+        })?;
+        let size = file.metadata()?.len();
+        let range = match range {
+            Some(header) => parse_range(header, size)?,
+            None => None,
+        };
+        Ok(Reply::Audio {
+            file,
+            content_type: AudioFormat::from_path(&audiobook.path)
+                .map_or("application/octet-stream", |format| format.mime_type()),
+            size,
+            range,
+        })
+    }
+
+    fn search(&self, target: &Target, profile: &Profile) -> ApiResult<Reply> {
+        let mut query = SearchQuery::new(target.param("q").unwrap_or_default())
+            .for_profile(&profile.id)
+            .include_completed(target.param("completed") != Some("false"));
+        if let Some(library) = target.param("library") {
+            query = query.in_library(library);
+        }
+        if let Some(author) = target.param("author") {
+            query = query.by_author(author);
+        }
+        if let Some(narrator) = target.param("narrator") {
+            query = query.by_narrator(narrator);
+        }
+        if let Some(limit) = target.param("limit") {
+            let limit = limit
+                .parse()
+                .map_err(|_| ApiError::BadRequest(format!("Invalid limit: {limit}")))?;
+            query = query.limit(limit);
+        }
+        let books: Vec<BookInfo> = self
+            .db
+            .audiobook_repository()
+            .search(&query)?
+            .iter()
+            .map(BookInfo::from)
+            .collect();
+        json(&books)
+    }
+
+    fn all_progress(&self, profile: &Profile) -> ApiResult<Reply> {
+        let books = self.db.audiobook_repository();
+        let mut entries = Vec::new();
+        for progress in self
+            .db
+            .progress_repository()
+            .find_all_for_profile(&profile.id)?
+        {
+            if let Some(audiobook) = books.find_by_id(&progress.audiobook_id)? {
+                entries.push(ProgressEntry {
+                    book: BookInfo::from(&audiobook),
+                    progress,
+                });
+            }
+        }
+        json(&entries)
+    }
+
+    fn book_progress(&self, id: &str, profile: &Profile) -> ApiResult<Reply> {
+        self.find_book(id)?;
+        let progress = self
+            .db
+            .progress_repository()
+            .find_for_profile(&profile.id, id)?
+            .ok_or_else(|| ApiError::NotFound(format!("Progress in book {id}")))?;
+        json(&progress)
+    }
+
+    fn update_progress(&self, id: &str, profile: &Profile, body: &[u8]) -> ApiResult<Reply> {
+        let update: ProgressUpdate = serde_json::from_slice(body)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid progress update: {e}")))?;
+        if update.position_seconds.is_none() && update.completed.is_none() {
+            return Err(ApiError::BadRequest(
+                "Expected position_seconds or completed".to_string(),
+            ));
+        }
+        self.find_book(id)?;
+
+        let repo = self.db.progress_repository();
+        let mut progress = repo
+            .find_for_profile(&profile.id, id)?
+            .unwrap_or_else(|| Progress::new(id, 0).with_profile(&profile.id));
+        if let Some(position) = update.position_seconds {
+            progress.update_position(position);
+        }
+        match update.completed {
+            Some(true) => progress.mark_completed(),
+            Some(false) => progress.completed = false,
+            None => {}
+        }
+        repo.upsert(&progress)?;
+        json(&progress)
+    }
+
+    fn reset_progress(&self, id: &str, profile: &Profile) -> ApiResult<Reply> {
+        self.find_book(id)?;
+        self.db
+            .progress_repository()
+            .delete_for_profile(&profile.id, id)?;
+        Ok(Reply::NoContent)
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> ApiResult<Reply> {
+    serde_json::to_value(value)
+        .map(Reply::Json)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize response: {e}")))
+}
+
+const fn only_get(method: &Method) -> ApiResult<()> {
+    match method {
+        Method::Get | Method::Head => Ok(()),
+        _ => Err(ApiError::MethodNotAllowed),
+    }
+}
+
+fn not_found_route(segments: &[&str]) -> ApiError {
+    ApiError::NotFound(format!("No route for /{}", segments.join("/")))
+}
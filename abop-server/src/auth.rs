@@ -0,0 +1,56 @@
+//! Token authentication
+//!
+//! The server has a single shared token. Clients present it as a bearer
+//! token, or as a `token` query parameter so stream and cover URLs can be
+//! handed straight to an audio player or image view.
+
+use std::fmt::Write as _;
+
+/// Create a random token of 64 hexadecimal characters
+#[must_use]
+pub fn generate_token() -> String {
+    rand::random::<[u8; 32]>()
+        .iter()
+        .fold(String::with_capacity(64), |mut token, byte| {
+            let _ = write!(token, "{byte:02x}");
+            token
+        })
+}
+
+/// Compare a presented token with the server token in constant time
+#[must_use]
+pub fn token_matches(expected: &str, presented: &str) -> bool {
+    let (expected, presented) = (expected.as_bytes(), presented.as_bytes());
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The token carried by an `Authorization` header value
+pub(crate) fn bearer_token(header: &str) -> Option<&str> {
+    let (scheme, token) = header.trim().split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_token());
+
+        assert!(token_matches(&token, &token.clone()));
+        assert!(!token_matches(&token, &token[1..]));
+        assert!(!token_matches(&token, &token.to_uppercase()));
+
+        assert_eq!(bearer_token("Bearer abc"), Some("abc"));
+        assert_eq!(bearer_token("bearer  abc "), Some("abc"));
+        assert_eq!(bearer_token("Basic abc"), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! Error types for the HTTP API
+//!
+//! Every failure maps to an HTTP status code and is sent to the client as a
+//! JSON body of the form `{"error": "..."}`.
+
+use abop_core::AppError;
+use abop_core::db::DatabaseError;
+use thiserror::Error;
+
+/// Result type for API handlers
+pub type ApiResult<T> = Result<T, ApiError>;
+
+/// Errors returned by API handlers
+#[derive(Debug, Error)]
+pub enum ApiError {
+    /// The request did not carry the server token
+    #[error("Missing or invalid access token")]
+    Unauthorized,
+
+    /// The requested resource does not exist
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The request was malformed
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    /// The path exists but not for this method
+    #[error("Method not allowed")]
+    MethodNotAllowed,
+
+    /// A `Range` header asked for bytes past the end of the file
+    #[error("Requested range is outside the file ({size} bytes)")]
+    RangeNotSatisfiable {
+        /// Size of the file in bytes
+        size: u64,
+    },
+
+    /// The database or filesystem failed
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl ApiError {
+    /// HTTP status code for this error
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        match self {
+            Self::Unauthorized => 401,
+            Self::NotFound(_) => 404,
+            Self::BadRequest(_) => 400,
+            Self::MethodNotAllowed => 405,
+            Self::RangeNotSatisfiable { .. } => 416,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        Self::Internal(error.to_string())
+    }
+}
+
+impl From<DatabaseError> for ApiError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Internal(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for ApiError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Internal(error.to_string())
+    }
+}
@@ -0,0 +1,37 @@
+//! ABOP local HTTP server
+//!
+//! Exposes the library over a small REST API so phones and other devices on
+//! the LAN can browse books, search, keep listening progress in step and
+//! stream audio. Every endpoint except `GET /api/health` needs the server
+//! token, sent as `Authorization: Bearer <token>` or, for players that cannot
+//! set headers, as a `token` query parameter.
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | `GET` | `/api/health` | Liveness check, no token needed |
+//! | `GET` | `/api/libraries` | Libraries with their book counts |
+//! | `GET` | `/api/libraries/{id}/books` | Books in a library |
+//! | `GET` | `/api/books/{id}` | One book with the listener's progress |
+//! | `GET` | `/api/books/{id}/cover` | Embedded cover image |
+//! | `GET` | `/api/books/{id}/stream` | Audio file, with `Range` support |
+//! | `GET` | `/api/search?q=` | Search by text, `library`, `author`, `narrator` |
+//! | `GET` | `/api/profiles` | Listener profiles |
+//! | `GET` | `/api/progress` | The listener's progress in every book |
+//! | `GET`, `PUT`, `DELETE` | `/api/books/{id}/progress` | Progress in one book |
+//!
+//! Progress belongs to a listener profile, picked with the `X-Abop-Profile`
+//! header or a `profile` query parameter (ID or name). Without one the
+//! default profile is used.
+
+pub mod api;
+pub mod auth;
+pub mod error;
+pub mod range;
+pub mod request;
+pub mod server;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::{ApiError, ApiResult};
+pub use server::{DEFAULT_PORT, ServerConfig, ServerHandle, start};
@@ -0,0 +1,76 @@
+//! ABOP HTTP server
+//!
+//! Serves the library API and audio streams to devices on the local network.
+//! Without `--token` the token comes from `ABOP_SERVER_TOKEN`, or a new one
+//! is generated and printed at startup.
+
+use abop_core::db::Database;
+use abop_server::auth::generate_token;
+use abop_server::{DEFAULT_PORT, ServerConfig};
+use anyhow::Context;
+use clap::Parser;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+
+/// Environment variable holding the access token
+const TOKEN_ENV: &str = "ABOP_SERVER_TOKEN";
+
+/// Command line arguments for the ABOP server
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Path to the database file (optional, defaults to centralized app database)
+    #[arg(short = 'f', long)]
+    database: Option<PathBuf>,
+
+    /// Address to listen on; use 0.0.0.0 to accept connections from the LAN
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    host: IpAddr,
+
+    /// Port to listen on
+    #[arg(short, long, default_value_t = DEFAULT_PORT)]
+    port: u16,
+
+    /// Access token clients must present
+    #[arg(long)]
+    token: Option<String>,
+
+    /// Number of worker threads
+    #[arg(long, default_value_t = 4)]
+    workers: usize,
+
+    /// Enable verbose output
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let level = if args.verbose { "debug" } else { "info" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+
+    let db = match &args.database {
+        Some(path) => {
+            Database::open(path).with_context(|| format!("opening database {}", path.display()))?
+        }
+        None => Database::open_app_database().context("opening the application database")?,
+    };
+
+    let token = args
+        .token
+        .or_else(|| std::env::var(TOKEN_ENV).ok())
+        .filter(|token| !token.is_empty());
+    let token = token.unwrap_or_else(|| {
+        let token = generate_token();
+        println!("Access token: {token}");
+        token
+    });
+
+    let config = ServerConfig::new(token)
+        .with_bind(SocketAddr::new(args.host, args.port))
+        .with_workers(args.workers);
+    let server = abop_server::start(db, config).context("starting the server")?;
+    println!("Listening on http://{}", server.addr());
+    server.wait();
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! HTTP `Range` header handling for audio streaming
+//!
+//! Only single byte ranges are honoured, which is what audio players send
+//! when seeking. Anything else the server may ignore under RFC 9110, so
+//! unsupported or malformed headers fall back to sending the whole file.
+
+use crate::error::{ApiError, ApiResult};
+
+/// An inclusive range of bytes within a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    /// First byte to send
+    pub start: u64,
+    /// Last byte to send
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Number of bytes in the range
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Ranges always hold at least one byte
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Value of the `Content-Range` header for a file of `size` bytes
+    #[must_use]
+    pub fn content_range(&self, size: u64) -> String {
+        format!("bytes {}-{}/{size}", self.start, self.end)
+    }
+}
+
+/// Parse a `Range` header against a file of `size` bytes
+///
+/// Returns `Ok(None)` when the whole file should be sent instead.
+///
+/// # Errors
+///
+/// Returns [`ApiError::RangeNotSatisfiable`] when the range is well formed
+/// but lies entirely past the end of the file.
+pub fn parse_range(header: &str, size: u64) -> ApiResult<Option<ByteRange>> {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let unsatisfiable = ApiError::RangeNotSatisfiable { size };
+    match (start.trim(), end.trim()) {
+        ("", "") => Ok(None),
+        // Suffix range: the last `n` bytes
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<u64>() else {
+                return Ok(None);
+            };
+            if suffix == 0 || size == 0 {
+                return Err(unsatisfiable);
+            }
+            Ok(Some(ByteRange {
+                start: size.saturating_sub(suffix),
+                end: size - 1,
+            }))
+        }
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return Ok(None);
+            };
+            let end = if end.is_empty() {
+                u64::MAX
+            } else {
+                let Ok(end) = end.parse::<u64>() else {
+                    return Ok(None);
+                };
+                end
+            };
+            if end < start {
+                return Ok(None);
+            }
+            if start >= size {
+                return Err(unsatisfiable);
+            }
+            Ok(Some(ByteRange {
+                start,
+                end: end.min(size - 1),
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> Option<ByteRange> {
+        Some(ByteRange { start, end })
+    }
+
+    #[test]
+    fn test_parse_range_forms() {
+        assert_eq!(parse_range("bytes=0-99", 1000).unwrap(), range(0, 99));
+        assert_eq!(parse_range("bytes=900-", 1000).unwrap(), range(900, 999));
+        assert_eq!(parse_range("bytes=-100", 1000).unwrap(), range(900, 999));
+        assert_eq!(
+            parse_range("bytes=500-5000", 1000).unwrap(),
+            range(500, 999)
+        );
+        assert_eq!(parse_range("bytes=-5000", 1000).unwrap(), range(0, 999));
+        assert_eq!(range(10, 19).unwrap().len(), 10);
+        assert_eq!(range(0, 99).unwrap().content_range(1000), "bytes 0-99/1000");
+    }
+
+    #[test]
+    fn test_unsupported_ranges_send_the_whole_file() {
+        for header in [
+            "items=0-1",
+            "bytes=0-1,5-6",
+            "bytes=abc-",
+            "bytes=9-3",
+            "bytes=-",
+        ] {
+            assert_eq!(parse_range(header, 1000).unwrap(), None, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_ranges_past_the_end_are_unsatisfiable() {
+        for (header, size) in [("bytes=1000-", 1000), ("bytes=-0", 1000), ("bytes=0-", 0)] {
+            let result = parse_range(header, size);
+            assert!(
+                matches!(result, Err(ApiError::RangeNotSatisfiable { .. })),
+                "{header}"
+            );
+        }
+    }
+}
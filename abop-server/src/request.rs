@@ -0,0 +1,89 @@
+//! Request target parsing
+//!
+//! Splits the raw request target into decoded path segments and query
+//! parameters.
+
+use std::collections::HashMap;
+
+/// Decoded path and query of a request target
+#[derive(Debug, Default)]
+pub struct Target {
+    /// Path segments, percent-decoded, without empty segments
+    pub segments: Vec<String>,
+    /// Query parameters; the last value wins for repeated keys
+    pub query: HashMap<String, String>,
+}
+
+impl Target {
+    /// Parse a request target such as `/api/search?q=dune`
+    #[must_use]
+    pub fn parse(target: &str) -> Self {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| percent_decode(segment, false))
+            .collect();
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key, true), percent_decode(value, true))
+            })
+            .collect();
+        Self { segments, query }
+    }
+
+    /// A query parameter, if present and not blank
+    #[must_use]
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Decode `%XX` escapes, and `+` as a space in query strings
+///
+/// Invalid escapes are kept as they are.
+fn percent_decode(text: &str, plus_as_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                decoded.push(b'%');
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let target = Target::parse("/api/books/a%20b/stream?q=the+hobbit&token=x%2By&empty=");
+        assert_eq!(target.segments, ["api", "books", "a b", "stream"]);
+        assert_eq!(target.param("q"), Some("the hobbit"));
+        assert_eq!(target.param("token"), Some("x+y"));
+        assert_eq!(target.param("empty"), None);
+        assert_eq!(target.param("missing"), None);
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%zz%4", false), "%zz%4");
+    }
+}
@@ -0,0 +1,268 @@
+//! HTTP listener and worker threads
+//!
+//! Requests are served by a small pool of blocking worker threads, which
+//! suits the synchronous database layer. Audio is streamed straight from the
+//! file, so a slow client only ever holds one worker.
+
+use crate::api::{Api, ApiRequest, Reply};
+use crate::error::ApiError;
+use crate::request::Target;
+use abop_core::db::Database;
+use log::{debug, info, warn};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use tiny_http::{Header, Request, Response, Server};
+
+/// Port the server listens on unless configured otherwise
+pub const DEFAULT_PORT: u16 = 8765;
+
+/// Largest request body accepted, in bytes
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+type BoxedResponse = Response<Box<dyn Read + Send>>;
+
+/// Server settings
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Address to listen on
+    pub bind: SocketAddr,
+    /// Token clients must present
+    pub token: String,
+    /// Number of worker threads
+    pub workers: usize,
+}
+
+impl ServerConfig {
+    /// Listen on localhost at [`DEFAULT_PORT`] with four workers
+    #[must_use]
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            bind: SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)),
+            token: token.into(),
+            workers: 4,
+        }
+    }
+
+    /// Listen on another address
+    #[must_use]
+    pub const fn with_bind(mut self, bind: SocketAddr) -> Self {
+        self.bind = bind;
+        self
+    }
+
+    /// Use another number of worker threads
+    #[must_use]
+    pub const fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+}
+
+/// A running server
+pub struct ServerHandle {
+    addr: SocketAddr,
+    server: Arc<Server>,
+    running: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ServerHandle {
+    /// Address the server is listening on
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Block until the server stops
+    pub fn wait(self) {
+        for worker in self.workers {
+            let _ = worker.join();
+        }
+    }
+
+    /// Stop accepting requests and wait for in-flight ones to finish
+    pub fn shutdown(self) {
+        self.running.store(false, Ordering::Release);
+        for _ in &self.workers {
+            self.server.unblock();
+        }
+        self.wait();
+    }
+}
+
+/// Start serving the API over `db`
+///
+/// Binding to port 0 picks a free port; see [`ServerHandle::addr`].
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound.
+pub fn start(db: Database, config: ServerConfig) -> std::io::Result<ServerHandle> {
+    let server = Arc::new(Server::http(config.bind).map_err(std::io::Error::other)?);
+    let addr = server
+        .server_addr()
+        .to_ip()
+        .ok_or_else(|| std::io::Error::other("Server is not listening on an IP address"))?;
+    let api = Arc::new(Api::new(db, config.token));
+    let running = Arc::new(AtomicBool::new(true));
+
+    let workers = (0..config.workers.max(1))
+        .map(|index| {
+            let (server, api, running) = (server.clone(), api.clone(), running.clone());
+            std::thread::Builder::new()
+                .name(format!("abop-server-{index}"))
+                .spawn(move || serve(&server, &api, &running))
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    info!("Serving ABOP API on http://{addr}");
+    Ok(ServerHandle {
+        addr,
+        server,
+        running,
+        workers,
+    })
+}
+
+fn serve(server: &Server, api: &Api, running: &AtomicBool) {
+    while running.load(Ordering::Acquire) {
+        match server.recv() {
+            Ok(request) => respond(api, request),
+            Err(e) if running.load(Ordering::Acquire) => warn!("Failed to receive request: {e}"),
+            Err(_) => {}
+        }
+    }
+}
+
+fn respond(api: &Api, mut request: Request) {
+    let header = |name: &'static str| {
+        request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(name))
+            .map(|header| header.value.to_string())
+    };
+    let (authorization, profile, range) = (
+        header("Authorization"),
+        header("X-Abop-Profile"),
+        header("Range"),
+    );
+    let mut body = Vec::new();
+    if let Err(e) = request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_end(&mut body)
+    {
+        debug!("Failed to read request body: {e}");
+    }
+
+    let target = Target::parse(request.url());
+    // The query may carry the token, so only the path is logged
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let api_request = ApiRequest {
+        method: request.method().clone(),
+        target,
+        authorization,
+        profile,
+        range,
+        body,
+    };
+
+    let response = match api.handle(&api_request).and_then(into_response) {
+        Ok(response) => response,
+        Err(error) => error_response(&error),
+    };
+    debug!(
+        "{} {path} -> {}",
+        api_request.method,
+        response.status_code().0
+    );
+    if let Err(e) = request.respond(response) {
+        debug!("Client went away before the response was sent: {e}");
+    }
+}
+
+fn into_response(reply: Reply) -> Result<BoxedResponse, ApiError> {
+    Ok(match reply {
+        Reply::Json(value) => {
+            bytes_response(200, "application/json", value.to_string().into_bytes())
+        }
+        Reply::NoContent => Response::new(
+            204.into(),
+            Vec::new(),
+            Box::new(std::io::empty()),
+            Some(0),
+            None,
+        ),
+        Reply::Image { content_type, data } => bytes_response(200, content_type, data),
+        Reply::Audio {
+            mut file,
+            content_type,
+            size,
+            range,
+        } => {
+            let mut response_headers =
+                headers(&[("Content-Type", content_type), ("Accept-Ranges", "bytes")]);
+            let (status, length, reader): (u16, u64, Box<dyn Read + Send>) = match range {
+                Some(range) => {
+                    file.seek(SeekFrom::Start(range.start))?;
+                    response_headers
+                        .extend(headers(&[("Content-Range", &range.content_range(size))]));
+                    (206, range.len(), Box::new(file.take(range.len())))
+                }
+                None => (200, size, Box::new(file)),
+            };
+            // A known length keeps `Content-Length` set, which players rely on for seeking
+            Response::new(
+                status.into(),
+                response_headers,
+                reader,
+                usize::try_from(length).ok(),
+                None,
+            )
+            .with_chunked_threshold(usize::MAX)
+        }
+    })
+}
+
+fn error_response(error: &ApiError) -> BoxedResponse {
+    let body = serde_json::json!({ "error": error.to_string() }).to_string();
+    let mut response = bytes_response(error.status(), "application/json", body.into_bytes());
+    let extra = match error {
+        ApiError::Unauthorized => headers(&[("WWW-Authenticate", "Bearer")]),
+        ApiError::RangeNotSatisfiable { size } => {
+            headers(&[("Content-Range", &format!("bytes */{size}"))])
+        }
+        _ => Vec::new(),
+    };
+    for header in extra {
+        response.add_header(header);
+    }
+    response
+}
+
+fn bytes_response(status: u16, content_type: &str, data: Vec<u8>) -> BoxedResponse {
+    let length = data.len();
+    Response::new(
+        status.into(),
+        headers(&[("Content-Type", content_type)]),
+        Box::new(Cursor::new(data)),
+        Some(length),
+        None,
+    )
+}
+
+fn headers(pairs: &[(&str, &str)]) -> Vec<Header> {
+    pairs
+        .iter()
+        .filter_map(|(name, value)| Header::from_bytes(*name, *value).ok())
+        .collect()
+}
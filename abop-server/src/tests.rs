@@ -0,0 +1,284 @@
+//! End-to-end tests against a server on localhost backed by an in-memory
+//! database
+
+use crate::{ServerConfig, ServerHandle, start};
+use abop_core::db::Database;
+use abop_core::models::Audiobook;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use tempfile::TempDir;
+
+const TOKEN: &str = "test-token";
+
+/// Bytes 0, 1, 2, ... wrapping at 251, so any slice is recognisable
+fn audio_bytes() -> Vec<u8> {
+    (0..10_000u32).map(|i| (i % 251) as u8).collect()
+}
+
+struct Fixture {
+    _dir: TempDir,
+    db: Database,
+    server: Option<ServerHandle>,
+    library_id: String,
+    book_id: String,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::in_memory().unwrap();
+        let library = db
+            .libraries()
+            .create("Books", dir.path().to_path_buf())
+            .unwrap();
+
+        let path = dir.path().join("dune.mp3");
+        std::fs::write(&path, audio_bytes()).unwrap();
+        let mut audiobook = Audiobook::new(&library.id, &path);
+        audiobook.title = Some("Dune".to_string());
+        audiobook.author = Some("Frank Herbert".to_string());
+        audiobook.cover_art = Some(b"\x89PNG\r\n\x1a\nnot really a png".to_vec());
+        db.add_audiobook(&audiobook).unwrap();
+
+        let mut missing = Audiobook::new(&library.id, dir.path().join("gone.m4b"));
+        missing.title = Some("Gone".to_string());
+        db.add_audiobook(&missing).unwrap();
+
+        let config = ServerConfig::new(TOKEN)
+            .with_bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .with_workers(2);
+        let server = start(db.clone(), config).unwrap();
+        Self {
+            _dir: dir,
+            db,
+            server: Some(server),
+            library_id: library.id,
+            book_id: audiobook.id,
+        }
+    }
+
+    fn addr(&self) -> SocketAddr {
+        self.server.as_ref().unwrap().addr()
+    }
+
+    /// Send a request with the token and any extra headers
+    fn request(&self, method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> Reply {
+        let mut all = vec![("Authorization", "Bearer test-token")];
+        all.extend_from_slice(headers);
+        send(self.addr(), method, path, &all, body)
+    }
+
+    fn get(&self, path: &str) -> Reply {
+        self.request("GET", path, &[], "")
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        if let Some(server) = self.server.take() {
+            server.shutdown();
+        }
+    }
+}
+
+struct Reply {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+}
+
+fn send(addr: SocketAddr, method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> Reply {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut request = format!(
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).unwrap();
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+    let head = String::from_utf8_lossy(&raw[..split]).into_owned();
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .unwrap()
+        .split(' ')
+        .nth(1)
+        .unwrap()
+        .parse()
+        .unwrap();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    Reply {
+        status,
+        headers,
+        body: raw[split + 4..].to_vec(),
+    }
+}
+
+#[test]
+fn test_requests_need_the_token() {
+    let fixture = Fixture::new();
+    let addr = fixture.addr();
+
+    let health = send(addr, "GET", "/api/health", &[], "");
+    assert_eq!(health.status, 200);
+    assert_eq!(health.json()["status"], "ok");
+
+    assert_eq!(send(addr, "GET", "/api/libraries", &[], "").status, 401);
+    let wrong = send(
+        addr,
+        "GET",
+        "/api/libraries",
+        &[("Authorization", "Bearer nope")],
+        "",
+    );
+    assert_eq!(wrong.status, 401);
+    assert_eq!(wrong.header("WWW-Authenticate"), Some("Bearer"));
+    assert!(wrong.json()["error"].is_string());
+
+    let by_query = send(addr, "GET", "/api/libraries?token=test-token", &[], "");
+    assert_eq!(by_query.status, 200);
+    assert_eq!(fixture.get("/api/nowhere").status, 404);
+    assert_eq!(
+        fixture.request("POST", "/api/libraries", &[], "").status,
+        405
+    );
+}
+
+#[test]
+fn test_browse_and_search() {
+    let fixture = Fixture::new();
+
+    let libraries = fixture.get("/api/libraries").json();
+    assert_eq!(libraries[0]["id"], fixture.library_id.as_str());
+    assert_eq!(libraries[0]["book_count"], 2);
+
+    let books = fixture
+        .get(&format!("/api/libraries/{}/books", fixture.library_id))
+        .json();
+    assert_eq!(books.as_array().unwrap().len(), 2);
+    assert_eq!(fixture.get("/api/libraries/unknown/books").status, 404);
+
+    let book = fixture
+        .get(&format!("/api/books/{}", fixture.book_id))
+        .json();
+    assert_eq!(book["title"], "Dune");
+    assert_eq!(book["format"], "mp3");
+    assert_eq!(book["progress"], Value::Null);
+    assert_eq!(
+        book["cover_url"],
+        format!("/api/books/{}/cover", fixture.book_id).as_str()
+    );
+    assert!(book.get("cover_art").is_none());
+
+    let found = fixture.get("/api/search?q=frank+herbert").json();
+    assert_eq!(found.as_array().unwrap().len(), 1);
+    assert_eq!(found[0]["id"], fixture.book_id.as_str());
+    assert_eq!(fixture.get("/api/search?q=dune&limit=x").status, 400);
+
+    let cover = fixture.get(&format!("/api/books/{}/cover", fixture.book_id));
+    assert_eq!(cover.status, 200);
+    assert_eq!(cover.header("Content-Type"), Some("image/png"));
+    assert!(cover.body.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn test_stream_supports_ranges() {
+    let fixture = Fixture::new();
+    let path = format!("/api/books/{}/stream", fixture.book_id);
+    let audio = audio_bytes();
+
+    let whole = fixture.get(&path);
+    assert_eq!(whole.status, 200);
+    assert_eq!(whole.header("Content-Type"), Some("audio/mpeg"));
+    assert_eq!(whole.header("Accept-Ranges"), Some("bytes"));
+    assert_eq!(whole.header("Content-Length"), Some("10000"));
+    assert_eq!(whole.body, audio);
+
+    let part = fixture.request("GET", &path, &[("Range", "bytes=1000-1999")], "");
+    assert_eq!(part.status, 206);
+    assert_eq!(part.header("Content-Range"), Some("bytes 1000-1999/10000"));
+    assert_eq!(part.header("Content-Length"), Some("1000"));
+    assert_eq!(part.body, audio[1000..2000]);
+
+    let tail = fixture.request("GET", &path, &[("Range", "bytes=-10")], "");
+    assert_eq!(tail.status, 206);
+    assert_eq!(tail.body, audio[9990..]);
+
+    let past = fixture.request("GET", &path, &[("Range", "bytes=20000-")], "");
+    assert_eq!(past.status, 416);
+    assert_eq!(past.header("Content-Range"), Some("bytes */10000"));
+
+    let head = fixture.request("HEAD", &path, &[], "");
+    assert_eq!(head.status, 200);
+    assert!(head.body.is_empty());
+
+    let gone = fixture.db.audiobook_repository().find_all().unwrap();
+    let gone = gone.iter().find(|book| book.id != fixture.book_id).unwrap();
+    assert_eq!(
+        fixture
+            .get(&format!("/api/books/{}/stream", gone.id))
+            .status,
+        404
+    );
+}
+
+#[test]
+fn test_progress_round_trip_per_profile() {
+    let fixture = Fixture::new();
+    let sam = fixture.db.profile_repository().create("Sam").unwrap();
+    let path = format!("/api/books/{}/progress", fixture.book_id);
+
+    assert_eq!(fixture.get(&path).status, 404);
+    let saved = fixture.request("PUT", &path, &[], r#"{"position_seconds": 754}"#);
+    assert_eq!(saved.status, 200);
+    assert_eq!(saved.json()["position_seconds"], 754);
+
+    let as_sam = [("X-Abop-Profile", "Sam")];
+    assert_eq!(fixture.request("GET", &path, &as_sam, "").status, 404);
+    let finished = fixture.request("PUT", &path, &as_sam, r#"{"completed": true}"#);
+    assert_eq!(finished.json()["completed"], true);
+    assert_eq!(finished.json()["profile_id"], sam.id.as_str());
+
+    let stored = fixture
+        .db
+        .progress_repository()
+        .find_for_profile(abop_core::models::DEFAULT_PROFILE_ID, &fixture.book_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.position_seconds, 754);
+    assert!(!stored.completed);
+
+    let listed = fixture.get("/api/progress?profile=Sam").json();
+    assert_eq!(listed[0]["book"]["title"], "Dune");
+    assert_eq!(listed[0]["progress"]["completed"], true);
+
+    assert_eq!(fixture.request("PUT", &path, &[], "{}").status, 400);
+    assert_eq!(fixture.request("PUT", &path, &[], "nonsense").status, 400);
+    assert_eq!(fixture.get("/api/progress?profile=Nobody").status, 404);
+
+    assert_eq!(fixture.request("DELETE", &path, &[], "").status, 204);
+    assert_eq!(fixture.get(&path).status, 404);
+    assert_eq!(fixture.request("GET", &path, &as_sam, "").status, 200);
+}